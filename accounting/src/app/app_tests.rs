@@ -33,6 +33,21 @@ mod processing_state_tests {
         }
     }
 
+    #[test]
+    fn processing_state_preparing_contacts_tracks_progress() {
+        let state = ProcessingState::PreparingContacts {
+            current: 2,
+            total: 4,
+        };
+
+        if let ProcessingState::PreparingContacts { current, total } = state {
+            assert_eq!(current, 2);
+            assert_eq!(total, 4);
+        } else {
+            panic!("Expected PreparingContacts state");
+        }
+    }
+
     #[test]
     fn processing_state_completed() {
         let state = ProcessingState::Completed;
@@ -1,14 +1,56 @@
 //! Business logic for the InvoiceApp (API calls, CSV loading, invoice processing).
 
 use log::{debug, error, info, warn};
+use mtg_common::DecimalLocale;
 
 use crate::{
     csv_processor::CsvProcessor,
-    models::{CheckAccountResponse, InvoiceCreationResult, InvoiceWorkflowOptions},
-    sevdesk_api::SevDeskApi,
+    fee_report::{fees_for_orders, write_fee_report, SaleFees},
+    invoice_drafts::{approved_drafts, InvoiceDraft},
+    models::{
+        parse_tags, CheckAccountResponse, InvoiceCreationResult, InvoiceWorkflowOptions,
+        OrderRecord, SendType, WorkflowOverride,
+    },
+    order_board::OrderBoard,
+    order_grouping::{
+        group_orders_by_buyer, latest_order, order_reference, single_order_groups, OrderGroup,
+    },
+    order_risk::{assess_orders, OrderRisk},
+    pdf_queue::{missing_pdf_jobs, PdfDownloadSettings, PdfJob},
+    profiles::ConfigProfile,
+    sevdesk_api::{ContactCache, SevDeskApi},
+    shipping_split::{split_by_shipping, write_shipping_exports, ShippingBatch},
+    tracking::load_tracking_file,
 };
 
-use super::{InvoiceApp, ProcessingState};
+use super::{configured_base_url, InvoiceApp, ProcessingState};
+
+/// An invoice to create: planned from its orders when it is created, or
+/// taken as reviewed in the draft window.
+enum InvoiceWork {
+    Group(OrderGroup),
+    Draft(InvoiceDraft),
+}
+
+impl InvoiceWork {
+    fn orders(&self) -> &[OrderRecord] {
+        match self {
+            InvoiceWork::Group(group) => group,
+            InvoiceWork::Draft(draft) => &draft.orders,
+        }
+    }
+
+    /// The invoice's workflow override: as reviewed for drafts, else as
+    /// stored on the order board.
+    fn workflow_override(&self, board: &OrderBoard) -> WorkflowOverride {
+        match self {
+            InvoiceWork::Group(group) => {
+                board.workflow_override(group.iter().map(|o| o.order_id.as_str()))
+            }
+            InvoiceWork::Draft(draft) => draft.workflow.clone(),
+        }
+    }
+}
 
 impl InvoiceApp {
     pub(super) fn test_api_connection(&mut self) {
@@ -17,7 +59,7 @@ impl InvoiceApp {
             self.api_token.len()
         );
         if !self.api_token.is_empty() {
-            let api = SevDeskApi::new(self.api_token.clone());
+            let api = self.base_api();
             match self.runtime.block_on(api.test_connection()) {
                 Ok(success) => {
                     if success {
@@ -39,19 +81,155 @@ impl InvoiceApp {
         }
     }
 
+    /// Creates an API client for the configured endpoint, without the
+    /// invoice settings.
+    fn base_api(&self) -> SevDeskApi {
+        let api = SevDeskApi::new(self.api_token.clone());
+        match self.sevdesk_base_url.trim() {
+            "" => api,
+            url => api.with_base_url(url),
+        }
+    }
+
+    /// Creates an API client that shares this session's contact cache.
+    fn sevdesk_api(&self) -> SevDeskApi {
+        self.base_api()
+            .with_contact_cache(self.contact_cache.clone())
+            .with_invoice_settings(self.tax_mode, self.invoice_texts.clone())
+            .with_english_texts(self.english_texts.clone())
+            .with_language_overrides(self.language_overrides.clone())
+            .with_reconciliation(self.reconciliation)
+            .with_locale(self.csv_locale)
+            .with_cost_centre_and_tags(
+                Some(self.cost_centre.trim().to_string()),
+                parse_tags(&self.invoice_tags),
+            )
+    }
+
+    /// Points the app at another SevDesk endpoint. The connection has to be
+    /// tested again and contacts resolved there are not valid elsewhere.
+    pub(super) fn set_sevdesk_base_url(&mut self, base_url: String) {
+        if base_url.trim() == self.sevdesk_base_url.trim() {
+            return;
+        }
+        info!(
+            "SevDesk API endpoint changed to {}",
+            if base_url.trim().is_empty() {
+                crate::sevdesk_api::DEFAULT_BASE_URL
+            } else {
+                base_url.trim()
+            }
+        );
+        self.sevdesk_base_url = base_url;
+        self.api_connection_status = None;
+        self.clear_contact_cache();
+    }
+
+    /// Drops all resolved contact IDs, e.g. after the API token changed.
+    pub(super) fn clear_contact_cache(&mut self) {
+        self.contact_cache = ContactCache::default();
+        self.contact_results.clear();
+        self.new_buyers.clear();
+    }
+
+    /// Loaded orders flagged by the current risk rules.
+    pub(super) fn order_risks(&self) -> Vec<OrderRisk> {
+        assess_orders(&self.orders, &self.risk_rules, &self.new_buyers)
+    }
+
+    /// Loaded orders grouped by suggested shipping method.
+    pub(super) fn shipping_batches(&self) -> Vec<ShippingBatch> {
+        split_by_shipping(&self.orders, &self.shipping_rules)
+    }
+
+    /// Cardmarket fees of every loaded order.
+    pub(super) fn sale_fees(&self) -> anyhow::Result<Vec<SaleFees>> {
+        fees_for_orders(&self.orders, &self.fee_settings)
+    }
+
+    /// Writes the per-sale and monthly fee CSVs into a folder picked by the
+    /// user.
+    pub(super) fn export_fee_report(&mut self) {
+        let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+            info!("Fee report export cancelled: no folder chosen");
+            return;
+        };
+        let exported = self
+            .sale_fees()
+            .and_then(|sales| write_fee_report(&dir, &sales).map(|files| (sales.len(), files)));
+        self.fee_export_status = Some(match exported {
+            Ok((sales, files)) => {
+                info!("Exported {} fee files to {}", files.len(), dir.display());
+                Ok(format!(
+                    "Exported fees of {sales} sales to {}",
+                    dir.display()
+                ))
+            }
+            Err(e) => {
+                error!("Fee report export failed: {e:#}");
+                Err(format!("{e:#}"))
+            }
+        });
+    }
+
+    /// Writes the per-group address CSVs and the summary into a folder
+    /// picked by the user.
+    pub(super) fn export_shipping_groups(&mut self) {
+        let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+            info!("Shipping export cancelled: no folder chosen");
+            return;
+        };
+        let batches = self.shipping_batches();
+        self.shipping_export_status = Some(
+            match write_shipping_exports(&dir, &batches, &self.shipping_rules) {
+                Ok(files) => {
+                    info!(
+                        "Exported {} shipping files to {}",
+                        files.len(),
+                        dir.display()
+                    );
+                    Ok(format!(
+                        "Exported {} groups to {}",
+                        batches.len(),
+                        dir.display()
+                    ))
+                }
+                Err(e) => {
+                    error!("Shipping export failed: {e:#}");
+                    Err(format!("{e:#}"))
+                }
+            },
+        );
+    }
+
     pub(super) fn load_check_accounts(&mut self) {
         info!("Loading check accounts");
         self.check_accounts_loading = true;
         self.check_accounts_error = None;
 
-        let api = SevDeskApi::new(self.api_token.clone());
+        let api = self.base_api();
         match self.runtime.block_on(api.fetch_check_accounts()) {
             Ok(accounts) => {
                 info!("Loaded {} check accounts", accounts.len());
 
-                // Find and auto-select the default account
-                let default_index = accounts.iter().position(|a| a.is_default());
-                if let Some(idx) = default_index {
+                // Select the active profile's account, else the default one
+                let preferred_index = self
+                    .preferred_check_account_id
+                    .as_ref()
+                    .and_then(|id| accounts.iter().position(|a| &a.id == id));
+                if let Some(idx) = preferred_index {
+                    info!("Selecting profile account: {}", accounts[idx].name);
+                    self.selected_check_account_index = Some(idx);
+                    // Booking was switched off while no account was selected
+                    if let Some(profile) = self
+                        .profiles
+                        .selected
+                        .as_ref()
+                        .and_then(|name| self.profiles.get(name))
+                    {
+                        self.workflow_book = profile.workflow_book;
+                    }
+                } else if let Some(idx) = accounts.iter().position(|a| a.is_default()) {
                     info!("Auto-selecting default account: {}", accounts[idx].name);
                     self.selected_check_account_index = Some(idx);
                 }
@@ -77,24 +255,36 @@ impl InvoiceApp {
     pub(super) fn load_csv_file(&mut self) {
         debug!("Opening file dialog for CSV selection");
         if let Some(path) = rfd::FileDialog::new()
-            .add_filter("CSV files", &["csv"])
+            .add_filter("CSV files", &["csv", "gz", "zip"])
             .pick_file()
         {
             info!("Selected CSV file: {path:?}");
             self.processing_state = ProcessingState::LoadingCsv;
             self.csv_file_path = Some(path.clone());
+            self.contact_results.clear();
+            self.order_edits.clear();
+            self.order_edit_errors.clear();
 
-            let processor = CsvProcessor::new();
-            debug!("Starting CSV file processing");
+            let processor = CsvProcessor::with_locale(DecimalLocale::from_env());
+            debug!(
+                "Starting CSV file processing ({} number format)",
+                processor.locale()
+            );
             match self.runtime.block_on(processor.load_orders_from_csv(&path)) {
                 Ok(orders) => {
                     info!("Successfully loaded {} orders from CSV", orders.len());
                     // Validate orders
                     debug!("Validating loaded orders");
                     self.validation_errors = processor.validate_orders(&orders);
+                    self.csv_locale = processor.locale();
 
                     if self.validation_errors.is_empty() {
                         info!("All orders passed validation");
+                        let added = self.order_board.import_orders(&orders);
+                        if added > 0 {
+                            info!("Added {added} new orders to the order board");
+                            self.save_order_board();
+                        }
                         self.orders = orders;
                     } else {
                         warn!("Found {} validation errors", self.validation_errors.len());
@@ -118,7 +308,164 @@ impl InvoiceApp {
         }
     }
 
+    /// Resolves or creates the SevDesk contact for every loaded order before the
+    /// invoice run, so contact failures surface early and invoice creation
+    /// reuses the cached IDs.
+    pub(super) fn prepare_contacts(&mut self) {
+        info!("Preparing contacts for {} orders", self.orders.len());
+        if self.orders.is_empty() || self.api_token.is_empty() {
+            warn!(
+                "Cannot prepare contacts: orders={}, token_empty={}",
+                self.orders.len(),
+                self.api_token.is_empty()
+            );
+            return;
+        }
+
+        self.contact_results.clear();
+        self.processing_state = ProcessingState::PreparingContacts {
+            current: 0,
+            total: self.orders.len(),
+        };
+
+        let api = self.sevdesk_api();
+
+        for (index, order) in self.orders.iter().enumerate() {
+            debug!(
+                "Preparing contact {}/{}: {} ({})",
+                index + 1,
+                self.orders.len(),
+                order.name,
+                order.order_id
+            );
+            let result = self.runtime.block_on(api.prepare_contact(order));
+            if result.created {
+                self.new_buyers.insert(result.customer_name.clone());
+            }
+            self.contact_results.push(result);
+
+            self.processing_state = ProcessingState::PreparingContacts {
+                current: index + 1,
+                total: self.orders.len(),
+            };
+        }
+
+        let error_count = self
+            .contact_results
+            .iter()
+            .filter(|r| r.error.is_some())
+            .count();
+        info!(
+            "Contact preparation completed: {} resolved, {error_count} errors",
+            self.contact_results.len() - error_count
+        );
+
+        self.processing_state = ProcessingState::Idle;
+    }
+
     pub(super) fn process_invoices(&mut self) {
+        let work = self
+            .invoice_groups()
+            .into_iter()
+            .map(InvoiceWork::Group)
+            .collect();
+        self.run_invoices(work);
+    }
+
+    /// Computes a draft of every invoice, with the workflow overrides stored
+    /// on the order board, and opens the review window.
+    pub(super) fn review_invoice_drafts(&mut self) {
+        let api = self.sevdesk_api();
+        self.invoice_drafts = self
+            .invoice_groups()
+            .iter()
+            .map(|group| InvoiceDraft {
+                workflow: self
+                    .order_board
+                    .workflow_override(group.iter().map(|o| o.order_id.as_str())),
+                ..api.draft_invoice(group)
+            })
+            .collect();
+        info!(
+            "Prepared {} invoice drafts for review",
+            self.invoice_drafts.len()
+        );
+        self.show_draft_review = true;
+    }
+
+    /// Saves the order open in the edit form. Drafts computed before the
+    /// change are discarded, since they still show the old values.
+    pub(super) fn save_order_edit(&mut self) {
+        match self.order_edits.save(&mut self.orders) {
+            Ok(changed) => {
+                self.order_edit_errors.clear();
+                if changed {
+                    info!(
+                        "Order edited, {} orders differ from the CSV",
+                        self.order_edits.modified_count()
+                    );
+                    self.discard_invoice_drafts();
+                }
+            }
+            Err(errors) => self.order_edit_errors = errors,
+        }
+    }
+
+    /// Restores an edited order to its CSV values.
+    pub(super) fn revert_order_edit(&mut self, order_id: &str) {
+        if self.order_edits.revert(order_id, &mut self.orders) {
+            info!("Order {order_id} reverted to the CSV values");
+            self.order_edit_errors.clear();
+            self.discard_invoice_drafts();
+        }
+    }
+
+    /// Stores a draft's workflow override on its orders' board entries.
+    pub(super) fn save_draft_workflow(&mut self, index: usize) {
+        let Some(draft) = self.invoice_drafts.get(index) else {
+            return;
+        };
+        let changed = self.order_board.set_workflow_override(
+            draft.orders.iter().map(|o| o.order_id.as_str()),
+            &draft.workflow,
+        );
+        if changed > 0 {
+            info!(
+                "Workflow for orders {}: {}",
+                draft.reference,
+                if draft.workflow.is_empty() {
+                    "as the run".to_string()
+                } else {
+                    draft.workflow.describe()
+                }
+            );
+            self.save_order_board();
+        }
+    }
+
+    fn discard_invoice_drafts(&mut self) {
+        if !self.invoice_drafts.is_empty() {
+            info!("Discarding invoice drafts computed before the order edit");
+            self.invoice_drafts.clear();
+            self.show_draft_review = false;
+        }
+    }
+
+    /// Creates the approved drafts as reviewed and closes the review window.
+    pub(super) fn submit_invoice_drafts(&mut self) {
+        let drafts = approved_drafts(&self.invoice_drafts);
+        info!(
+            "Submitting {} of {} reviewed drafts",
+            drafts.len(),
+            self.invoice_drafts.len()
+        );
+        self.invoice_drafts.clear();
+        self.show_draft_review = false;
+        self.run_invoices(drafts.into_iter().map(InvoiceWork::Draft).collect());
+    }
+
+    fn run_invoices(&mut self, work: Vec<InvoiceWork>) {
+        let orders: usize = work.iter().map(|w| w.orders().len()).sum();
         info!(
             "Starting invoice {} for {} orders",
             if self.dry_run_mode {
@@ -126,12 +473,12 @@ impl InvoiceApp {
             } else {
                 "processing"
             },
-            self.orders.len()
+            orders
         );
-        if self.orders.is_empty() || self.api_token.is_empty() {
+        if work.is_empty() || self.api_token.is_empty() {
             warn!(
                 "Cannot process invoices: orders={}, token_empty={}",
-                self.orders.len(),
+                orders,
                 self.api_token.is_empty()
             );
             return;
@@ -140,30 +487,42 @@ impl InvoiceApp {
         self.results.clear();
         self.processing_state = ProcessingState::Processing {
             current: 0,
-            total: self.orders.len(),
+            total: work.len(),
         };
 
-        let api = SevDeskApi::new(self.api_token.clone());
+        let api = self.sevdesk_api();
 
-        for (index, order) in self.orders.iter().enumerate() {
+        for (index, item) in work.iter().enumerate() {
+            let group = item.orders();
+            // Combined invoices are addressed and dated on the latest order
+            let order = latest_order(group);
             let action = if self.dry_run_mode {
                 "Simulating"
             } else {
                 "Processing"
             };
             debug!(
-                "{} order {}/{}: {} ({})",
+                "{} invoice {}/{}: {} ({})",
                 action,
                 index + 1,
-                self.orders.len(),
+                work.len(),
                 order.name,
-                order.order_id
+                order_reference(group)
             );
 
-            let result = if self.dry_run_mode {
-                self.runtime.block_on(api.simulate_invoice_creation(order))
-            } else {
-                self.runtime.block_on(api.create_invoice(order))
+            let result = match (item, self.dry_run_mode) {
+                (InvoiceWork::Group(group), true) => {
+                    self.runtime.block_on(api.simulate_combined_invoice(group))
+                }
+                (InvoiceWork::Group(group), false) => {
+                    self.runtime.block_on(api.create_combined_invoice(group))
+                }
+                (InvoiceWork::Draft(draft), true) => self
+                    .runtime
+                    .block_on(api.simulate_invoice_from_draft(draft)),
+                (InvoiceWork::Draft(draft), false) => {
+                    self.runtime.block_on(api.create_invoice_from_draft(draft))
+                }
             };
 
             match result {
@@ -200,8 +559,25 @@ impl InvoiceApp {
                     let mut final_result = invoice_result;
                     if let Some(invoice_id) = final_result.invoice_id {
                         if final_result.error.is_none() {
-                            let workflow_options =
-                                self.build_workflow_options_with_date(&order.date_of_purchase);
+                            let overrides = item.workflow_override(&self.order_board);
+                            if !overrides.is_empty() {
+                                info!(
+                                    "Workflow override for {}: {}",
+                                    order_reference(group),
+                                    overrides.describe()
+                                );
+                            }
+                            let mut workflow_options = self
+                                .build_workflow_options_with_date(&order.date_of_purchase)
+                                .with_override(&overrides);
+                            // Real PDF downloads go through the queue after the run
+                            let pdf_dir = if self.dry_run_mode
+                                || workflow_options.send_type != SendType::Vpdf
+                            {
+                                None
+                            } else {
+                                workflow_options.pdf_download_path.take()
+                            };
                             if workflow_options.finalize
                                 || workflow_options.enshrine
                                 || workflow_options.book
@@ -228,12 +604,28 @@ impl InvoiceApp {
                                     error!("Workflow error for {}: {}", order.name, err);
                                 }
 
+                                if let Some(dir) = pdf_dir.filter(|_| workflow_status.finalized) {
+                                    self.pdf_queue.enqueue([PdfJob::new(
+                                        invoice_id,
+                                        invoice_number,
+                                        &dir,
+                                    )]);
+                                }
+
                                 final_result.workflow_status = Some(workflow_status);
                             }
                         }
                     }
 
-                    self.results.push(final_result);
+                    // One result row per order, so the results table and the
+                    // order board still work per order
+                    for grouped in group {
+                        self.results.push(InvoiceCreationResult {
+                            order_id: grouped.order_id.clone(),
+                            customer_name: grouped.name.clone(),
+                            ..final_result.clone()
+                        });
+                    }
                 }
                 Err(e) => {
                     error!(
@@ -246,21 +638,23 @@ impl InvoiceApp {
                         order.name,
                         e
                     );
-                    self.results.push(InvoiceCreationResult {
-                        order_id: order.order_id.clone(),
-                        customer_name: order.name.clone(),
-                        invoice_id: None,
-                        invoice_number: None,
-                        error: Some(e.to_string()),
-                        workflow_status: None,
-                    });
+                    for grouped in group {
+                        self.results.push(InvoiceCreationResult {
+                            order_id: grouped.order_id.clone(),
+                            customer_name: grouped.name.clone(),
+                            invoice_id: None,
+                            invoice_number: None,
+                            error: Some(e.to_string()),
+                            workflow_status: None,
+                        });
+                    }
                 }
             }
 
             // Update progress
             self.processing_state = ProcessingState::Processing {
                 current: index + 1,
-                total: self.orders.len(),
+                total: work.len(),
             };
         }
 
@@ -273,11 +667,322 @@ impl InvoiceApp {
         };
         info!("Invoice {action} completed: {success_count} successful, {error_count} errors");
 
+        if !self.dry_run_mode {
+            self.download_queued_pdfs();
+
+            let mut invoiced = 0;
+            for result in self.results.iter().filter(|r| r.error.is_none()) {
+                if let Some(invoice_id) = result.invoice_id {
+                    invoiced += self.order_board.record_invoice(
+                        &result.order_id,
+                        invoice_id,
+                        result.invoice_number.as_deref(),
+                    );
+                }
+            }
+            if invoiced > 0 {
+                info!("Marked {invoiced} orders as invoiced on the order board");
+                self.save_order_board();
+            }
+        }
+
         self.processing_state = ProcessingState::Completed;
     }
 
+    /// Attaches the tracking numbers of a Cardmarket export picked by the
+    /// user to the order board and, outside dry-run mode, to the invoices
+    /// of orders already invoiced.
+    pub(super) fn import_tracking_numbers(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV files", &["csv", "gz", "zip"])
+            .pick_file()
+        else {
+            info!("Tracking import cancelled: no file chosen");
+            return;
+        };
+        let tracking = match load_tracking_file(&path) {
+            Ok(tracking) => tracking,
+            Err(e) => {
+                error!("Tracking import failed: {e:#}");
+                self.tracking_import_status = Some(Err(format!("{e:#}")));
+                return;
+            }
+        };
+
+        let import = self.order_board.attach_tracking(&tracking);
+        if !import.attached.is_empty() {
+            self.save_order_board();
+        }
+        if !import.unknown.is_empty() {
+            warn!(
+                "Tracking numbers for orders not on the board: {}",
+                import.unknown.join(", ")
+            );
+        }
+
+        // Invoices are annotated once per import of a new tracking number
+        let notes: Vec<(u32, String)> = tracking
+            .iter()
+            .filter(|t| import.attached.contains(&t.order_id))
+            .filter_map(|t| {
+                let invoice_id = self.order_board.entries.get(&t.order_id)?.invoice_id?;
+                Some((invoice_id, t.invoice_note()))
+            })
+            .collect();
+        let mut annotated = 0;
+        let mut failed = Vec::new();
+        if !notes.is_empty() && !self.dry_run_mode && !self.api_token.is_empty() {
+            let api = self.sevdesk_api();
+            for (invoice_id, note) in &notes {
+                match self
+                    .runtime
+                    .block_on(api.annotate_invoice(*invoice_id, note))
+                {
+                    Ok(()) => annotated += 1,
+                    Err(e) => {
+                        error!("Failed to annotate invoice {invoice_id}: {e:#}");
+                        failed.push(invoice_id.to_string());
+                    }
+                }
+            }
+        }
+
+        info!(
+            "Imported {} tracking numbers: {} attached, {} unknown orders, {annotated} invoices annotated",
+            tracking.len(),
+            import.attached.len(),
+            import.unknown.len()
+        );
+        let mut message = format!(
+            "{} tracking numbers attached, {annotated} of {} invoices annotated",
+            import.attached.len(),
+            notes.len()
+        );
+        if !import.unknown.is_empty() {
+            message.push_str(&format!(
+                "; {} orders not on the board",
+                import.unknown.len()
+            ));
+        }
+        self.tracking_import_status = Some(if failed.is_empty() {
+            Ok(message)
+        } else {
+            Err(format!(
+                "{message}; failed to annotate invoices {}",
+                failed.join(", ")
+            ))
+        });
+    }
+
+    /// Runs the PDF download queue and notes the saved files on the results.
+    /// Downloads that still fail stay queued for the next try.
+    pub(super) fn download_queued_pdfs(&mut self) {
+        if self.pdf_queue.jobs.is_empty() {
+            return;
+        }
+        let api = self.sevdesk_api();
+        let outcomes = self.runtime.block_on(
+            api.download_pdfs(self.pdf_queue.jobs.clone(), PdfDownloadSettings::default()),
+        );
+        let saved = self.pdf_queue.apply(outcomes);
+        for (invoice_id, path) in &saved {
+            for result in self
+                .results
+                .iter_mut()
+                .filter(|r| r.invoice_id == Some(*invoice_id))
+            {
+                result
+                    .workflow_status
+                    .get_or_insert_with(Default::default)
+                    .pdf_path = Some(path.clone());
+            }
+        }
+        info!(
+            "PDF downloads: {} saved, {} still queued",
+            saved.len(),
+            self.pdf_queue.jobs.len()
+        );
+        self.save_pdf_queue();
+    }
+
+    /// Queues the PDFs of this run's finalized invoices that are missing from
+    /// the download folder, then runs the queue.
+    pub(super) fn redownload_missing_pdfs(&mut self) {
+        let Some(dir) = self.pdf_download_path.clone() else {
+            return;
+        };
+        let jobs = missing_pdf_jobs(self.finalized_invoices(), &dir);
+        let added = self.pdf_queue.enqueue(jobs);
+        info!("Queued {added} missing PDFs for {}", dir.display());
+        self.save_pdf_queue();
+        self.download_queued_pdfs();
+    }
+
+    /// Ids and numbers of the invoices finalized in this run, once each.
+    pub(super) fn finalized_invoices(&self) -> Vec<(u32, &str)> {
+        let mut invoices: Vec<(u32, &str)> = Vec::new();
+        for result in &self.results {
+            let finalized = result
+                .workflow_status
+                .as_ref()
+                .is_some_and(|status| status.finalized);
+            if let (Some(id), Some(number), true, None) = (
+                result.invoice_id,
+                result.invoice_number.as_deref(),
+                finalized,
+                &result.error,
+            ) {
+                if !invoices.iter().any(|(known, _)| *known == id) {
+                    invoices.push((id, number));
+                }
+            }
+        }
+        invoices
+    }
+
+    /// Drops all queued PDF downloads.
+    pub(super) fn clear_pdf_queue(&mut self) {
+        self.pdf_queue.jobs.clear();
+        self.save_pdf_queue();
+    }
+
+    /// Writes the PDF queue to disk, surfacing failures next to the queue.
+    fn save_pdf_queue(&mut self) {
+        match self.pdf_queue.save(&self.pdf_queue_path) {
+            Ok(()) => self.pdf_queue_error = None,
+            Err(e) => {
+                error!(
+                    "Failed to save PDF queue {}: {e}",
+                    self.pdf_queue_path.display()
+                );
+                self.pdf_queue_error = Some(format!("Failed to save PDF queue: {e}"));
+            }
+        }
+    }
+
+    /// The loaded orders split into invoices: grouped by buyer and address
+    /// when combining is on, one order per invoice otherwise.
+    pub(super) fn invoice_groups(&self) -> Vec<OrderGroup> {
+        if self.combine_orders_by_buyer {
+            group_orders_by_buyer(&self.orders)
+        } else {
+            single_order_groups(&self.orders)
+        }
+    }
+
+    /// The current settings as a profile with the given name.
+    pub(super) fn current_profile(&self, name: &str) -> ConfigProfile {
+        ConfigProfile {
+            name: name.to_string(),
+            tax_mode: self.tax_mode,
+            invoice_texts: self.invoice_texts.clone(),
+            english_texts: self.english_texts.clone(),
+            reconciliation: self.reconciliation,
+            combine_orders_by_buyer: self.combine_orders_by_buyer,
+            cost_centre: Some(self.cost_centre.trim().to_string()).filter(|c| !c.is_empty()),
+            invoice_tags: parse_tags(&self.invoice_tags),
+            workflow_finalize: self.workflow_finalize,
+            workflow_send_type: self.workflow_send_type.clone(),
+            workflow_enshrine: self.workflow_enshrine,
+            workflow_book: self.workflow_book,
+            check_account_id: self
+                .selected_check_account_index
+                .and_then(|idx| self.check_accounts.get(idx))
+                .map(|acc| acc.id.clone())
+                .or_else(|| self.preferred_check_account_id.clone()),
+            pdf_download_path: self.pdf_download_path.clone(),
+            sevdesk_base_url: Some(self.sevdesk_base_url.trim().to_string())
+                .filter(|url| !url.is_empty() && *url != configured_base_url()),
+        }
+    }
+
+    /// Switches every setting to the profile's values. The check account is
+    /// selected now if accounts are loaded, otherwise when they are.
+    pub(super) fn apply_profile(&mut self, profile: &ConfigProfile) {
+        info!("Applying profile '{}'", profile.name);
+        self.tax_mode = profile.tax_mode;
+        self.invoice_texts = profile.invoice_texts.clone();
+        self.english_texts = profile.english_texts.clone();
+        self.reconciliation = profile.reconciliation;
+        self.combine_orders_by_buyer = profile.combine_orders_by_buyer;
+        self.cost_centre = profile.cost_centre.clone().unwrap_or_default();
+        self.invoice_tags = profile.invoice_tags.join(", ");
+        self.workflow_finalize = profile.workflow_finalize;
+        self.workflow_send_type = profile.workflow_send_type.clone();
+        self.workflow_enshrine = profile.workflow_enshrine;
+        self.workflow_book = profile.workflow_book;
+        self.pdf_download_path = profile.pdf_download_path.clone();
+        self.set_sevdesk_base_url(
+            profile
+                .sevdesk_base_url
+                .clone()
+                .unwrap_or_else(configured_base_url),
+        );
+        self.preferred_check_account_id = profile.check_account_id.clone();
+        if let Some(id) = &profile.check_account_id {
+            if let Some(idx) = self.check_accounts.iter().position(|a| &a.id == id) {
+                self.selected_check_account_index = Some(idx);
+            } else if !self.check_accounts.is_empty() {
+                warn!("Profile check account {id} not found, keeping current selection");
+            }
+        }
+        self.profile_name = profile.name.clone();
+        self.profiles.selected = Some(profile.name.clone());
+        self.save_profiles();
+    }
+
+    /// Stores the current settings under `profile_name` and selects it.
+    pub(super) fn save_current_profile(&mut self) {
+        let name = self.profile_name.trim().to_string();
+        if name.is_empty() {
+            self.profile_error = Some("Enter a profile name first".to_string());
+            return;
+        }
+        info!("Saving profile '{name}'");
+        self.profiles.upsert(self.current_profile(&name));
+        self.profiles.selected = Some(name);
+        self.save_profiles();
+    }
+
+    /// Deletes the profile named in `profile_name`.
+    pub(super) fn delete_current_profile(&mut self) {
+        let name = self.profile_name.trim().to_string();
+        if self.profiles.remove(&name) {
+            info!("Deleted profile '{name}'");
+            self.profile_name.clear();
+            self.save_profiles();
+        }
+    }
+
+    fn save_profiles(&mut self) {
+        match self.profiles.save(&self.profiles_path) {
+            Ok(()) => self.profile_error = None,
+            Err(e) => {
+                error!(
+                    "Failed to save profiles {}: {e}",
+                    self.profiles_path.display()
+                );
+                self.profile_error = Some(format!("Failed to save profiles: {e}"));
+            }
+        }
+    }
+
+    /// Writes the order board to disk, surfacing failures in the board window.
+    pub(super) fn save_order_board(&mut self) {
+        match self.order_board.save(&self.board_path) {
+            Ok(()) => self.board_error = None,
+            Err(e) => {
+                error!(
+                    "Failed to save order board {}: {e}",
+                    self.board_path.display()
+                );
+                self.board_error = Some(format!("Failed to save order board: {e}"));
+            }
+        }
+    }
+
     /// Builds workflow options from current UI state
-    fn build_workflow_options(&self) -> InvoiceWorkflowOptions {
+    pub(super) fn build_workflow_options(&self) -> InvoiceWorkflowOptions {
         InvoiceWorkflowOptions {
             finalize: self.workflow_finalize,
             send_type: self.workflow_send_type.clone(),
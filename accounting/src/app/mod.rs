@@ -1,8 +1,23 @@
 mod logic;
 mod ui;
 
-use crate::models::{CheckAccountResponse, InvoiceCreationResult, OrderRecord, SendType};
+use crate::fee_report::FeeSettings;
+use crate::invoice_drafts::InvoiceDraft;
+use crate::models::{
+    CheckAccountResponse, ContactPreparationResult, InvoiceCreationResult, InvoiceLanguage,
+    InvoiceTexts, OrderRecord, SendType, TaxMode,
+};
+use crate::order_board::{BoardFilter, OrderBoard};
+use crate::order_edits::OrderEdits;
+use crate::order_risk::RiskRules;
+use crate::pdf_queue::PdfQueue;
+use crate::profiles::ProfileStore;
+use crate::reconciliation::ReconciliationSettings;
+use crate::sevdesk_api::ContactCache;
+use crate::shipping_split::ShippingRules;
+use mtg_common::DecimalLocale;
 
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use tokio::runtime::Runtime;
 
@@ -10,12 +25,15 @@ use tokio::runtime::Runtime;
 pub enum ProcessingState {
     Idle,
     LoadingCsv,
+    PreparingContacts { current: usize, total: usize },
     Processing { current: usize, total: usize },
     Completed,
 }
 
 pub struct InvoiceApp {
     api_token: String,
+    // SevDesk API base URL, blank for the public API
+    sevdesk_base_url: String,
     csv_file_path: Option<PathBuf>,
     orders: Vec<OrderRecord>,
     processing_state: ProcessingState,
@@ -36,26 +54,121 @@ pub struct InvoiceApp {
     workflow_book: bool,
     // PDF download folder
     pdf_download_path: Option<PathBuf>,
-    // Order preview window
+    // VAT treatment and invoice texts
+    tax_mode: TaxMode,
+    invoice_texts: InvoiceTexts,
+    english_texts: InvoiceTexts,
+    // Invoice language picked in the order preview, keyed by order ID
+    language_overrides: HashMap<String, InvoiceLanguage>,
+    reconciliation: ReconciliationSettings,
+    // Number format the loaded CSV was parsed with
+    csv_locale: DecimalLocale,
+    combine_orders_by_buyer: bool,
+    // SevDesk cost centre name (blank: none) and comma-separated invoice tags
+    cost_centre: String,
+    invoice_tags: String,
+    // Configuration profiles, persisted to profiles_path
+    profiles: ProfileStore,
+    profiles_path: PathBuf,
+    profile_name: String,
+    profile_error: Option<String>,
+    // Check account a profile asked for, applied once accounts are loaded
+    preferred_check_account_id: Option<String>,
+    // Order preview window and the corrections made there
     show_order_preview: bool,
+    order_edits: OrderEdits,
+    order_edit_errors: Vec<String>,
+    // Invoice drafts under review, submitted from the review window
+    invoice_drafts: Vec<InvoiceDraft>,
+    show_draft_review: bool,
+    // Contact IDs resolved up-front, shared with the invoice run
+    contact_cache: ContactCache,
+    contact_results: Vec<ContactPreparationResult>,
+    // Order risk checks; new buyers are those whose contact had to be created
+    risk_rules: RiskRules,
+    new_buyers: HashSet<String>,
+    // Shipping method split and the outcome of its last export
+    shipping_rules: ShippingRules,
+    shipping_export_status: Option<Result<String, String>>,
+    // Payment fee rates for the fee report and the outcome of its last export
+    fee_settings: FeeSettings,
+    fee_export_status: Option<Result<String, String>>,
+    // Order pipeline board, persisted to board_path
+    order_board: OrderBoard,
+    board_path: PathBuf,
+    board_filter: BoardFilter,
+    board_selection: HashSet<String>,
+    board_error: Option<String>,
+    show_order_board: bool,
+    tracking_import_status: Option<Result<String, String>>,
+    // PDF downloads still to do, persisted to pdf_queue_path
+    pdf_queue: PdfQueue,
+    pdf_queue_path: PathBuf,
+    pdf_queue_error: Option<String>,
 }
 
 impl Default for InvoiceApp {
     fn default() -> Self {
         log::info!("Initializing InvoiceApp");
-        let api_token = std::env::var("SEVDESK_API").unwrap_or_default();
+        let api_token = mtg_common::config::config()
+            .accounting
+            .sevdesk_api_token
+            .clone()
+            .unwrap_or_default();
 
         if api_token.is_empty() {
-            log::warn!("SEVDESK_API environment variable not set");
+            log::warn!("No SevDesk API token (SEVDESK_API or [accounting] sevdesk_api_token)");
         } else {
-            log::info!("SEVDESK_API environment variable found");
+            log::info!("SevDesk API token configured");
         }
 
         log::debug!("Creating Tokio runtime");
         let runtime = Runtime::new().expect("Failed to create Tokio runtime");
 
-        Self {
+        let board_path = OrderBoard::default_path();
+        let (order_board, board_error) = match OrderBoard::load(&board_path) {
+            Ok(board) => (board, None),
+            Err(e) => {
+                log::error!("Failed to load order board {}: {e}", board_path.display());
+                (
+                    OrderBoard::default(),
+                    Some(format!("Failed to load order board: {e}")),
+                )
+            }
+        };
+
+        let pdf_queue_path = PdfQueue::default_path();
+        let (pdf_queue, pdf_queue_error) = match PdfQueue::load(&pdf_queue_path) {
+            Ok(queue) => (queue, None),
+            Err(e) => {
+                log::error!("Failed to load PDF queue {}: {e}", pdf_queue_path.display());
+                (
+                    PdfQueue::default(),
+                    Some(format!("Failed to load PDF queue: {e}")),
+                )
+            }
+        };
+
+        let profiles_path = ProfileStore::default_path();
+        let (profiles, profile_error) = match ProfileStore::load(&profiles_path) {
+            Ok(profiles) => (profiles, None),
+            Err(e) => {
+                log::error!("Failed to load profiles {}: {e}", profiles_path.display());
+                (
+                    ProfileStore::default(),
+                    Some(format!("Failed to load profiles: {e}")),
+                )
+            }
+        };
+
+        let sevdesk_base_url = configured_base_url();
+        if !sevdesk_base_url.is_empty() {
+            log::info!("Using SevDesk API at {sevdesk_base_url}");
+        }
+
+        let mut app = Self {
             api_token,
+            sevdesk_base_url,
             csv_file_path: None,
             orders: Vec::new(),
             processing_state: ProcessingState::Idle,
@@ -76,12 +189,77 @@ impl Default for InvoiceApp {
             workflow_book: false,
             // PDF download path - default to None
             pdf_download_path: None,
+            // Kleingewerbe invoices until a profile says otherwise
+            tax_mode: TaxMode::default(),
+            invoice_texts: InvoiceTexts::default(),
+            english_texts: InvoiceTexts::english(),
+            language_overrides: HashMap::new(),
+            reconciliation: ReconciliationSettings::default(),
+            csv_locale: DecimalLocale::default(),
+            combine_orders_by_buyer: false,
+            cost_centre: String::new(),
+            invoice_tags: String::new(),
+            // Profiles - loaded from disk, the last selected one is applied below
+            profiles,
+            profiles_path,
+            profile_name: String::new(),
+            profile_error,
+            preferred_check_account_id: None,
             // Order preview window - default to closed
             show_order_preview: false,
+            order_edits: OrderEdits::default(),
+            order_edit_errors: Vec::new(),
+            // Draft review - nothing drafted yet, window closed
+            invoice_drafts: Vec::new(),
+            show_draft_review: false,
+            // Contact preparation - nothing resolved yet
+            contact_cache: ContactCache::default(),
+            contact_results: Vec::new(),
+            // Risk checks - default thresholds, no buyers known to be new yet
+            risk_rules: RiskRules::default(),
+            new_buyers: HashSet::new(),
+            // Shipping split - default weights and thresholds, nothing exported yet
+            shipping_rules: ShippingRules::default(),
+            shipping_export_status: None,
+            // Fee report - no payment fees until configured, nothing exported yet
+            fee_settings: FeeSettings::default(),
+            fee_export_status: None,
+            // Order board - loaded from disk, all statuses shown, window closed
+            order_board,
+            board_path,
+            board_filter: BoardFilter::default(),
+            board_selection: HashSet::new(),
+            board_error,
+            show_order_board: false,
+            tracking_import_status: None,
+            // PDF queue - loaded from disk, left over downloads are retried on demand
+            pdf_queue,
+            pdf_queue_path,
+            pdf_queue_error,
+        };
+        if let Some(profile) = app
+            .profiles
+            .selected
+            .as_ref()
+            .and_then(|name| app.profiles.get(name))
+            .cloned()
+        {
+            app.apply_profile(&profile);
         }
+        app
     }
 }
 
+/// The SevDesk base URL from the shared config (`SEVDESK_BASE_URL` or
+/// `[accounting] sevdesk_base_url`), blank for the public API.
+fn configured_base_url() -> String {
+    mtg_common::config::config()
+        .accounting
+        .sevdesk_base_url
+        .clone()
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 #[path = "app_tests.rs"]
 mod tests;
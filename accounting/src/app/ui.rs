@@ -3,13 +3,20 @@
 use eframe::egui;
 use log::info;
 
-use crate::models::SendType;
+use crate::csv_processor::field_parsers::parse_price;
+use crate::fee_report::monthly_fees;
+use crate::invoice_drafts::InvoiceDraft;
+use crate::models::{InvoiceLanguage, InvoiceWorkflowOptions, SendType, TaxMode};
+use crate::order_board::OrderStatus;
+use crate::sevdesk_api::DEFAULT_BASE_URL;
 
 use super::{InvoiceApp, ProcessingState};
 
 impl eframe::App for InvoiceApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.render_order_preview_window(ctx);
+        self.render_draft_review_window(ctx);
+        self.render_order_board_window(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
@@ -20,14 +27,22 @@ impl eframe::App for InvoiceApp {
 
                 self.render_api_token_section(ui);
                 ui.add_space(20.0);
+                self.render_profile_section(ui);
+                ui.add_space(20.0);
                 self.render_csv_file_section(ui);
                 ui.add_space(20.0);
+                self.render_risk_section(ui);
+                self.render_shipping_section(ui);
+                self.render_fee_section(ui);
                 self.render_check_account_section(ui);
                 ui.add_space(20.0);
                 self.render_workflow_options_section(ui);
                 ui.add_space(20.0);
+                self.render_invoice_settings_section(ui);
+                ui.add_space(20.0);
                 self.render_processing_section(ui);
                 ui.add_space(20.0);
+                self.render_contact_results_section(ui);
                 self.render_results_section(ui);
             });
         });
@@ -49,6 +64,8 @@ impl InvoiceApp {
                 if response.changed() {
                     log::debug!("API token changed, resetting connection status");
                     self.api_connection_status = None;
+                    // Contacts resolved under another account may not exist here
+                    self.clear_contact_cache();
                 }
 
                 if ui
@@ -71,6 +88,77 @@ impl InvoiceApp {
                     None => {}
                 }
             });
+
+            ui.horizontal(|ui| {
+                ui.label("API URL:");
+                let mut base_url = self.sevdesk_base_url.clone();
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut base_url)
+                        .desired_width(400.0)
+                        .hint_text(DEFAULT_BASE_URL),
+                );
+                if response.changed() {
+                    self.set_sevdesk_base_url(base_url);
+                }
+                let url = self.sevdesk_base_url.trim().trim_end_matches('/');
+                if !url.is_empty() && url != DEFAULT_BASE_URL {
+                    ui.colored_label(egui::Color32::ORANGE, "⚠ Not the live SevDesk");
+                }
+            });
+        });
+    }
+
+    fn render_profile_section(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label("Configuration Profile:");
+            ui.add_space(5.0);
+
+            let mut chosen = None;
+            ui.horizontal(|ui| {
+                let selected_text = self
+                    .profiles
+                    .selected
+                    .clone()
+                    .unwrap_or_else(|| "Select profile...".to_string());
+                egui::ComboBox::from_id_salt("profile_combo")
+                    .selected_text(selected_text)
+                    .width(220.0)
+                    .show_ui(ui, |ui| {
+                        for profile in &self.profiles.profiles {
+                            let is_selected =
+                                self.profiles.selected.as_deref() == Some(profile.name.as_str());
+                            if ui.selectable_label(is_selected, &profile.name).clicked() {
+                                chosen = Some(profile.clone());
+                            }
+                        }
+                    });
+            });
+            if let Some(profile) = chosen {
+                self.apply_profile(&profile);
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.text_edit_singleline(&mut self.profile_name);
+                if ui
+                    .button("Save")
+                    .on_hover_text("Save tax mode, texts, workflow options, check account and PDF folder under this name")
+                    .clicked()
+                {
+                    self.save_current_profile();
+                }
+                let exists = self.profiles.get(self.profile_name.trim()).is_some();
+                if ui
+                    .add_enabled(exists, egui::Button::new("Delete"))
+                    .clicked()
+                {
+                    self.delete_current_profile();
+                }
+            });
+
+            if let Some(error) = &self.profile_error {
+                ui.colored_label(egui::Color32::RED, error);
+            }
         });
     }
 
@@ -118,6 +206,20 @@ impl InvoiceApp {
                     }
                 });
             }
+
+            ui.horizontal(|ui| {
+                if ui.button("Order Board").clicked() {
+                    self.show_order_board = true;
+                }
+                let counts = self
+                    .order_board
+                    .counts()
+                    .iter()
+                    .map(|(status, count)| format!("{status}: {count}"))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                ui.label(counts);
+            });
         });
     }
 
@@ -269,6 +371,63 @@ impl InvoiceApp {
                         );
                     }
                 });
+
+                if self.pdf_download_path.is_some() {
+                    let missing_enabled = !self.finalized_invoices().is_empty();
+                    if ui
+                        .add_enabled(
+                            missing_enabled,
+                            egui::Button::new("Re-download missing PDFs"),
+                        )
+                        .on_hover_text("Queue the PDFs of this run's finalized invoices that are not in the folder and download them")
+                        .clicked()
+                    {
+                        self.redownload_missing_pdfs();
+                    }
+                }
+            }
+
+            // PDF downloads left over from failed attempts (also from earlier sessions)
+            if !self.pdf_queue.jobs.is_empty() {
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!("⚠ {} PDF downloads pending", self.pdf_queue.jobs.len()),
+                    )
+                    .on_hover_text(
+                        self.pdf_queue
+                            .jobs
+                            .iter()
+                            .map(|job| {
+                                format!(
+                                    "#{} → {} ({} failed attempts){}",
+                                    job.invoice_number,
+                                    job.directory.display(),
+                                    job.attempts,
+                                    job.last_error
+                                        .as_deref()
+                                        .map(|e| format!(": {e}"))
+                                        .unwrap_or_default()
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    );
+                    if ui.button("Retry downloads").clicked() {
+                        self.download_queued_pdfs();
+                    }
+                    if ui
+                        .button("Clear")
+                        .on_hover_text("Forget the pending downloads")
+                        .clicked()
+                    {
+                        self.clear_pdf_queue();
+                    }
+                });
+            }
+            if let Some(err) = &self.pdf_queue_error {
+                ui.colored_label(egui::Color32::RED, err);
             }
 
             // Show workflow summary
@@ -301,6 +460,92 @@ impl InvoiceApp {
         });
     }
 
+    fn render_invoice_settings_section(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label("Invoice Settings:");
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Tax Mode:");
+                egui::ComboBox::from_id_salt("tax_mode_combo")
+                    .selected_text(self.tax_mode.description())
+                    .width(260.0)
+                    .show_ui(ui, |ui| {
+                        for mode in TaxMode::all() {
+                            ui.selectable_value(&mut self.tax_mode, *mode, mode.description());
+                        }
+                    });
+            });
+
+            egui::Grid::new("invoice_texts_grid")
+                .num_columns(3)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.strong("German");
+                    ui.strong("English");
+                    ui.end_row();
+                    ui.label("Header:");
+                    ui.text_edit_singleline(&mut self.invoice_texts.header);
+                    ui.text_edit_singleline(&mut self.english_texts.header);
+                    ui.end_row();
+                    ui.label("Head Text:");
+                    ui.text_edit_singleline(&mut self.invoice_texts.head_text);
+                    ui.text_edit_singleline(&mut self.english_texts.head_text);
+                    ui.end_row();
+                    ui.label("Foot Text:");
+                    ui.text_edit_singleline(&mut self.invoice_texts.foot_text);
+                    ui.text_edit_singleline(&mut self.english_texts.foot_text);
+                    ui.end_row();
+                });
+            ui.colored_label(
+                egui::Color32::GRAY,
+                "{order_id} is replaced with the Cardmarket order ID; empty texts are left out.",
+            );
+            ui.colored_label(
+                egui::Color32::GRAY,
+                "Buyers in Germany, Austria, Switzerland, Liechtenstein and Luxembourg get the \
+                 German texts, everyone else the English ones. Change it per order in the \
+                 order preview.",
+            );
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.label("Rounding tolerance (EUR):");
+                ui.add(
+                    egui::DragValue::new(&mut self.reconciliation.tolerance)
+                        .range(0.0..=1.0)
+                        .speed(0.01)
+                        .fixed_decimals(2),
+                )
+                .on_hover_text("Invoices whose positions differ from the CSV total by more than this fail instead of being created");
+                ui.checkbox(
+                    &mut self.reconciliation.add_rounding_position,
+                    "Add rounding-difference position",
+                )
+                .on_hover_text("Adds a 'Rundungsdifferenz' position so the invoice total matches the order total");
+            });
+
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.label("Cost centre:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.cost_centre)
+                        .hint_text("none")
+                        .desired_width(160.0),
+                )
+                .on_hover_text("Name of a SevDesk cost centre (Kostenstelle) to book every invoice on; invoices fail if SevDesk has no cost centre with this name");
+                ui.label("Tags:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.invoice_tags)
+                        .hint_text("e.g. Cardmarket, MTG")
+                        .desired_width(200.0),
+                )
+                .on_hover_text("Comma-separated tags attached to every created invoice; missing tags are created in SevDesk");
+            });
+        });
+    }
+
     fn render_processing_section(&mut self, ui: &mut egui::Ui) {
         ui.group(|ui| {
             ui.horizontal(|ui| {
@@ -317,6 +562,25 @@ impl InvoiceApp {
                 }
             });
 
+            ui.horizontal(|ui| {
+                ui.checkbox(
+                    &mut self.combine_orders_by_buyer,
+                    "Combine orders by buyer",
+                )
+                .on_hover_text(
+                    "Orders with the same username and address get one invoice with a section per order",
+                );
+
+                if self.combine_orders_by_buyer && !self.orders.is_empty() {
+                    let invoices = self.invoice_groups().len();
+                    ui.label(format!(
+                        "{} orders → {} invoices",
+                        self.orders.len(),
+                        invoices
+                    ));
+                }
+            });
+
             ui.separator();
 
             match &self.processing_state {
@@ -331,6 +595,14 @@ impl InvoiceApp {
                         "Create Invoices"
                     };
 
+                    let flagged = self.order_risks().len();
+                    if flagged > 0 {
+                        ui.colored_label(
+                            egui::Color32::ORANGE,
+                            format!("⚠ {flagged} orders flagged by risk checks - review above"),
+                        );
+                    }
+
                     if ui
                         .add_enabled(can_process, egui::Button::new(button_text))
                         .on_disabled_hover_text(
@@ -349,11 +621,47 @@ impl InvoiceApp {
                         );
                         self.process_invoices();
                     }
+
+                    if ui
+                        .add_enabled(can_process, egui::Button::new("Review Drafts…"))
+                        .on_hover_text(
+                            "Check every invoice (contact, positions, totals, tax) before anything \
+                             is created, edit position texts and exclude orders",
+                        )
+                        .on_disabled_hover_text(
+                            "Load CSV file and test API connection first (or enable dry run mode)",
+                        )
+                        .clicked()
+                    {
+                        self.review_invoice_drafts();
+                    }
+
+                    let can_prepare = !self.orders.is_empty()
+                        && !self.dry_run_mode
+                        && self.api_connection_status == Some(true);
+                    if ui
+                        .add_enabled(can_prepare, egui::Button::new("Prepare Contacts"))
+                        .on_hover_text(
+                            "Resolve or create the SevDesk contact for every order up-front, \
+                             so contact problems show up before any invoice is created",
+                        )
+                        .on_disabled_hover_text(
+                            "Load CSV file and test API connection first (not available in dry run mode)",
+                        )
+                        .clicked()
+                    {
+                        self.prepare_contacts();
+                    }
                 }
                 ProcessingState::LoadingCsv => {
                     ui.label("Loading CSV file...");
                     ui.add(egui::ProgressBar::new(0.0).animate(true));
                 }
+                ProcessingState::PreparingContacts { current, total } => {
+                    ui.label(format!("Preparing contacts... ({current}/{total})"));
+                    let progress = *current as f32 / *total as f32;
+                    ui.add(egui::ProgressBar::new(progress));
+                }
                 ProcessingState::Processing { current, total } => {
                     let action = if self.dry_run_mode {
                         "Simulating"
@@ -381,6 +689,264 @@ impl InvoiceApp {
         });
     }
 
+    fn render_risk_section(&mut self, ui: &mut egui::Ui) {
+        if self.orders.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.label("Order Risk Checks:");
+            egui::CollapsingHeader::new("Rules")
+                .id_salt("risk_rules")
+                .show(ui, |ui| {
+                    let rules = &mut self.risk_rules;
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut rules.new_buyer_enabled, "New buyer, order total ≥");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.new_buyer_min_total)
+                                .range(0.0..=10_000.0)
+                                .suffix(" EUR"),
+                        );
+                    })
+                    .response
+                    .on_hover_text(
+                        "Buyers count as new when \"Prepare Contacts\" had to create their contact",
+                    );
+                    ui.checkbox(
+                        &mut rules.vat_country_enabled,
+                        "VAT number country differs from shipping country",
+                    );
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut rules.bulk_card_enabled, "At least");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.bulk_card_min_quantity).range(1..=100),
+                        );
+                        ui.label("copies of one card priced ≥");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.bulk_card_min_price)
+                                .range(0.0..=10_000.0)
+                                .suffix(" EUR"),
+                        );
+                    });
+                });
+
+            let risks = self.order_risks();
+            if risks.is_empty() {
+                ui.colored_label(egui::Color32::GREEN, "✓ No orders flagged");
+            } else {
+                egui::ScrollArea::vertical()
+                    .id_salt("order_risks")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for risk in &risks {
+                            for flag in &risk.flags {
+                                ui.colored_label(
+                                    egui::Color32::ORANGE,
+                                    format!(
+                                        "⚠ {} ({}) - {}",
+                                        risk.customer_name, risk.order_id, flag
+                                    ),
+                                );
+                            }
+                        }
+                    });
+            }
+        });
+        ui.add_space(20.0);
+    }
+
+    fn render_shipping_section(&mut self, ui: &mut egui::Ui) {
+        if self.orders.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.label("Shipping Groups:");
+            egui::CollapsingHeader::new("Weights and thresholds")
+                .id_salt("shipping_rules")
+                .show(ui, |ui| {
+                    let rules = &mut self.shipping_rules;
+                    ui.horizontal(|ui| {
+                        ui.label("Per card:");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.grams_per_card)
+                                .range(0.1..=50.0)
+                                .speed(0.1)
+                                .suffix(" g"),
+                        );
+                        ui.label("Packaging per order:");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.packaging_grams)
+                                .range(0.0..=500.0)
+                                .suffix(" g"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Tracked from merchandise value:");
+                        ui.add(
+                            egui::DragValue::new(&mut rules.tracked_from_value)
+                                .range(0.0..=10_000.0)
+                                .suffix(" EUR"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Home country:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut rules.home_country)
+                                .desired_width(120.0),
+                        );
+                    });
+                });
+
+            for batch in self.shipping_batches() {
+                ui.label(format!(
+                    "{}: {} orders, {} articles",
+                    batch.shipment,
+                    batch.orders.len(),
+                    batch.article_count()
+                ));
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Export Shipping CSVs")
+                    .on_hover_text(
+                        "One address CSV per group plus a summary, written into a chosen folder",
+                    )
+                    .clicked()
+                {
+                    self.export_shipping_groups();
+                }
+                match &self.shipping_export_status {
+                    Some(Ok(message)) => {
+                        ui.colored_label(egui::Color32::GREEN, message);
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                    None => {}
+                }
+            });
+        });
+        ui.add_space(20.0);
+    }
+
+    fn render_fee_section(&mut self, ui: &mut egui::Ui) {
+        if self.orders.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            ui.label("Cardmarket Fees:");
+            egui::CollapsingHeader::new("Payment fees")
+                .id_salt("fee_settings")
+                .show(ui, |ui| {
+                    ui.label("The order export has no payment fee column; set the rate charged per order.");
+                    ui.horizontal(|ui| {
+                        ui.label("Rate:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.fee_settings.payment_fee_percent)
+                                .range(0.0..=20.0)
+                                .speed(0.05)
+                                .suffix(" %"),
+                        );
+                        ui.label("plus per order:");
+                        ui.add(
+                            egui::DragValue::new(&mut self.fee_settings.payment_fee_fixed)
+                                .range(0.0..=10.0)
+                                .speed(0.01)
+                                .suffix(" EUR"),
+                        );
+                    });
+                });
+
+            match self.sale_fees() {
+                Ok(sales) => {
+                    egui::Grid::new("monthly_fees")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Month");
+                            ui.strong("Orders");
+                            ui.strong("Commission");
+                            ui.strong("Payment fees");
+                            ui.strong("Total");
+                            ui.end_row();
+                            for month in monthly_fees(&sales) {
+                                ui.label(&month.month);
+                                ui.label(month.orders.to_string());
+                                ui.label(format!("{:.2} {}", month.commission, month.currency));
+                                ui.label(format!("{:.2} {}", month.payment_fee, month.currency));
+                                ui.label(format!("{:.2} {}", month.total_fees(), month.currency));
+                                ui.end_row();
+                            }
+                        });
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::RED, format!("{e:#}"));
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Export Fee Report")
+                    .on_hover_text(
+                        "Fees per sale and per month as CSV, for checking the fee vouchers",
+                    )
+                    .clicked()
+                {
+                    self.export_fee_report();
+                }
+                match &self.fee_export_status {
+                    Some(Ok(message)) => {
+                        ui.colored_label(egui::Color32::GREEN, message);
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                    None => {}
+                }
+            });
+        });
+        ui.add_space(20.0);
+    }
+
+    fn render_contact_results_section(&self, ui: &mut egui::Ui) {
+        if self.contact_results.is_empty() {
+            return;
+        }
+        ui.group(|ui| {
+            let error_count = self
+                .contact_results
+                .iter()
+                .filter(|r| r.error.is_some())
+                .count();
+            let resolved_count = self.contact_results.len() - error_count;
+
+            ui.label(format!(
+                "Contacts: {resolved_count} ready, {error_count} errors"
+            ));
+
+            // Only failures need attention; successful lookups are just counted
+            if error_count > 0 {
+                egui::ScrollArea::vertical()
+                    .id_salt("contact_results")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for result in &self.contact_results {
+                            if let Some(error) = &result.error {
+                                ui.colored_label(
+                                    egui::Color32::RED,
+                                    format!(
+                                        "✗ {} ({}) - Error: {}",
+                                        result.customer_name, result.order_id, error
+                                    ),
+                                );
+                            }
+                        }
+                    });
+            }
+        });
+        ui.add_space(20.0);
+    }
+
     fn render_results_section(&self, ui: &mut egui::Ui) {
         if !self.results.is_empty() {
             ui.group(|ui| {
@@ -425,18 +991,34 @@ impl InvoiceApp {
         }
 
         let mut open = self.show_order_preview;
+        let mut save_edit = false;
+        let mut revert: Option<String> = None;
         egui::Window::new("Order Preview")
             .open(&mut open)
             .resizable(true)
             .default_size([900.0, 500.0])
             .show(ctx, |ui| {
-                ui.label(format!("{} orders to be invoiced:", self.orders.len()));
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} orders to be invoiced:", self.orders.len()));
+                    let modified = self.order_edits.modified_count();
+                    if modified > 0 {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("{modified} edited (differ from the CSV)"),
+                        );
+                    }
+                });
                 ui.add_space(5.0);
 
+                if self.order_edits.editing.is_some() {
+                    save_edit = self.render_order_edit_form(ui);
+                    ui.add_space(5.0);
+                }
+
                 let total: f64 = self
                     .orders
                     .iter()
-                    .filter_map(|o| o.total_value.replace(',', ".").parse::<f64>().ok())
+                    .filter_map(|o| parse_price(&o.total_value).ok())
                     .sum();
                 ui.colored_label(
                     egui::Color32::LIGHT_BLUE,
@@ -449,16 +1031,19 @@ impl InvoiceApp {
                         .striped(true)
                         .resizable(true)
                         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                        .column(egui_extras::Column::auto().at_least(50.0)) // Edit
                         .column(egui_extras::Column::auto().at_least(70.0)) // Order ID
                         .column(egui_extras::Column::auto().at_least(80.0)) // Date
                         .column(egui_extras::Column::auto().at_least(120.0)) // Customer
                         .column(egui_extras::Column::auto().at_least(100.0)) // Country
+                        .column(egui_extras::Column::auto().at_least(80.0)) // Language
                         .column(egui_extras::Column::auto().at_least(40.0)) // Items
                         .column(egui_extras::Column::auto().at_least(80.0)) // Merchandise
                         .column(egui_extras::Column::auto().at_least(60.0)) // Shipping
                         .column(egui_extras::Column::auto().at_least(70.0)) // Total
                         .column(egui_extras::Column::remainder()) // Description
                         .header(20.0, |mut header| {
+                            header.col(|_| {});
                             header.col(|ui| {
                                 ui.strong("Order ID");
                             });
@@ -471,6 +1056,9 @@ impl InvoiceApp {
                             header.col(|ui| {
                                 ui.strong("Country");
                             });
+                            header.col(|ui| {
+                                ui.strong("Language");
+                            });
                             header.col(|ui| {
                                 ui.strong("Items");
                             });
@@ -491,9 +1079,37 @@ impl InvoiceApp {
                             for order in &self.orders {
                                 let line_count = order.items.len().max(1);
                                 let row_height = line_count as f32 * 18.0;
+                                let modified = self.order_edits.is_modified(&order.order_id);
                                 body.row(row_height, |mut row| {
                                     row.col(|ui| {
-                                        ui.label(&order.order_id);
+                                        if ui
+                                            .small_button("✏")
+                                            .on_hover_text(
+                                                "Edit name, address, VAT number and totals",
+                                            )
+                                            .clicked()
+                                        {
+                                            self.order_edits.start(order);
+                                            self.order_edit_errors.clear();
+                                        }
+                                        if modified
+                                            && ui
+                                                .small_button("↺")
+                                                .on_hover_text("Revert to the CSV values")
+                                                .clicked()
+                                        {
+                                            revert = Some(order.order_id.clone());
+                                        }
+                                    });
+                                    row.col(|ui| {
+                                        if modified {
+                                            ui.colored_label(
+                                                egui::Color32::from_rgb(255, 165, 0),
+                                                format!("{} (edited)", order.order_id),
+                                            );
+                                        } else {
+                                            ui.label(&order.order_id);
+                                        }
                                     });
                                     row.col(|ui| {
                                         ui.label(&order.date_of_purchase);
@@ -504,6 +1120,37 @@ impl InvoiceApp {
                                     row.col(|ui| {
                                         ui.label(&order.country);
                                     });
+                                    row.col(|ui| {
+                                        let detected = InvoiceLanguage::for_country(&order.country);
+                                        let mut language = self
+                                            .language_overrides
+                                            .get(&order.order_id)
+                                            .copied()
+                                            .unwrap_or(detected);
+                                        egui::ComboBox::from_id_salt((
+                                            "invoice_language",
+                                            &order.order_id,
+                                        ))
+                                        .selected_text(language.label())
+                                        .show_ui(
+                                            ui,
+                                            |ui| {
+                                                for option in InvoiceLanguage::all() {
+                                                    ui.selectable_value(
+                                                        &mut language,
+                                                        *option,
+                                                        option.label(),
+                                                    );
+                                                }
+                                            },
+                                        );
+                                        if language == detected {
+                                            self.language_overrides.remove(&order.order_id);
+                                        } else {
+                                            self.language_overrides
+                                                .insert(order.order_id.clone(), language);
+                                        }
+                                    });
                                     row.col(|ui| {
                                         ui.label(order.article_count.to_string());
                                     });
@@ -540,6 +1187,507 @@ impl InvoiceApp {
                         });
                 });
             });
+        if save_edit {
+            self.save_order_edit();
+        }
+        if let Some(order_id) = revert {
+            self.revert_order_edit(&order_id);
+        }
         self.show_order_preview = open;
+        if !open {
+            self.order_edits.cancel();
+            self.order_edit_errors.clear();
+        }
+    }
+
+    /// Form for the order being edited. Returns whether "Save" was clicked.
+    fn render_order_edit_form(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut save = false;
+        let mut cancel = false;
+        let Some(edit) = self.order_edits.editing.as_mut() else {
+            return false;
+        };
+        ui.group(|ui| {
+            ui.strong(format!("Edit order {}", edit.order_id));
+            egui::Grid::new("order_edit_form")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let field = |ui: &mut egui::Ui, label: &str, value: &mut String| {
+                        ui.label(label);
+                        ui.add(egui::TextEdit::singleline(value).desired_width(260.0));
+                        ui.end_row();
+                    };
+                    field(ui, "Name:", &mut edit.name);
+                    field(ui, "Street:", &mut edit.street);
+                    field(ui, "Postal code:", &mut edit.zip);
+                    field(ui, "City:", &mut edit.city);
+                    field(ui, "VAT number:", &mut edit.vat_number);
+                    field(ui, "Merchandise value:", &mut edit.merchandise_value);
+                    field(ui, "Shipping costs:", &mut edit.shipment_costs);
+                    field(ui, "Total:", &mut edit.total_value);
+                });
+
+            // Live feedback; saving re-validates
+            let errors = edit.validate();
+            for error in errors.iter().chain(
+                self.order_edit_errors
+                    .iter()
+                    .filter(|e| !errors.contains(e)),
+            ) {
+                ui.colored_label(egui::Color32::RED, format!("✗ {error}"));
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(errors.is_empty(), egui::Button::new("Save"))
+                    .clicked()
+                {
+                    save = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+        if cancel {
+            self.order_edits.cancel();
+            self.order_edit_errors.clear();
+        }
+        save
+    }
+
+    fn render_draft_review_window(&mut self, ctx: &egui::Context) {
+        if !self.show_draft_review {
+            return;
+        }
+
+        let mut open = self.show_draft_review;
+        let mut submit = false;
+        let mut changed_workflows = Vec::new();
+        let can_submit = self.dry_run_mode || self.api_connection_status == Some(true);
+        let dry_run = self.dry_run_mode;
+        let run_workflow = self.build_workflow_options();
+        egui::Window::new("Invoice Draft Review")
+            .open(&mut open)
+            .resizable(true)
+            .default_size([900.0, 600.0])
+            .show(ctx, |ui| {
+                let approved: Vec<_> = self
+                    .invoice_drafts
+                    .iter()
+                    .filter(|d| d.is_approved())
+                    .collect();
+                let approved_count = approved.len();
+                let approved_total: f64 = approved.iter().map(|d| d.total_gross()).sum();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} drafts, {approved_count} approved ({approved_total:.2} EUR)",
+                        self.invoice_drafts.len()
+                    ));
+                    if ui.button("Include all").clicked() {
+                        for draft in &mut self.invoice_drafts {
+                            draft.included = draft.error.is_none();
+                        }
+                    }
+                    if ui.button("Exclude all").clicked() {
+                        for draft in &mut self.invoice_drafts {
+                            draft.included = false;
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(ui.available_height() - 40.0)
+                    .show(ui, |ui| {
+                        for (index, draft) in self.invoice_drafts.iter_mut().enumerate() {
+                            if Self::render_draft(ui, draft, &run_workflow) {
+                                changed_workflows.push(index);
+                            }
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let label = if dry_run {
+                        format!("Simulate {approved_count} Approved Invoices")
+                    } else {
+                        format!("Create {approved_count} Approved Invoices")
+                    };
+                    if ui
+                        .add_enabled(can_submit && approved_count > 0, egui::Button::new(label))
+                        .on_disabled_hover_text(
+                            "Approve at least one draft and test the API connection \
+                             (or enable dry run mode)",
+                        )
+                        .clicked()
+                    {
+                        submit = true;
+                    }
+                    if dry_run {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            "⚠ Dry Run: No invoices will be created",
+                        );
+                    }
+                });
+            });
+
+        for index in changed_workflows {
+            self.save_draft_workflow(index);
+        }
+        if submit {
+            self.submit_invoice_drafts();
+        } else {
+            self.show_draft_review = open;
+        }
+    }
+
+    /// One draft: include checkbox, contact and totals, workflow overrides,
+    /// editable positions. Returns whether the workflow override changed.
+    fn render_draft(
+        ui: &mut egui::Ui,
+        draft: &mut InvoiceDraft,
+        run: &InvoiceWorkflowOptions,
+    ) -> bool {
+        let mut workflow_changed = false;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.add_enabled(
+                    draft.error.is_none(),
+                    egui::Checkbox::new(&mut draft.included, ""),
+                );
+                ui.strong(format!("{} - {}", draft.reference, draft.order().name));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.strong(format!(
+                        "{:.2} {}",
+                        draft.total_gross(),
+                        draft.order().currency
+                    ));
+                });
+            });
+
+            ui.label(format!(
+                "{} · {} · {}",
+                draft.address().replace('\n', ", "),
+                draft.order().country,
+                draft.language.label()
+            ));
+            ui.label(&draft.texts.header);
+
+            if let Some(error) = &draft.error {
+                ui.colored_label(egui::Color32::RED, format!("✗ {error}"));
+                return;
+            }
+
+            let (net, tax) = draft.net_and_tax();
+            ui.label(format!(
+                "{} · net {net:.2} · VAT {tax:.2}",
+                draft.tax_mode.description()
+            ));
+
+            ui.horizontal(|ui| {
+                ui.label("Workflow:");
+                let id = draft.reference.as_str();
+                let workflow = &mut draft.workflow;
+                workflow_changed |= Self::workflow_step_combo(
+                    ui,
+                    (id, "finalize"),
+                    "Finalize",
+                    run.finalize,
+                    &mut workflow.finalize,
+                );
+                let before = workflow.send_type.clone();
+                egui::ComboBox::from_id_salt(("draft_send_type", id))
+                    .selected_text(match &workflow.send_type {
+                        Some(send_type) => send_type.description().to_string(),
+                        None => format!("{} (run)", run.send_type.description()),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut workflow.send_type,
+                            None,
+                            format!("As run: {}", run.send_type.description()),
+                        );
+                        for send_type in SendType::all() {
+                            ui.selectable_value(
+                                &mut workflow.send_type,
+                                Some(send_type.clone()),
+                                send_type.description(),
+                            );
+                        }
+                    });
+                workflow_changed |= workflow.send_type != before;
+                workflow_changed |= Self::workflow_step_combo(
+                    ui,
+                    (id, "enshrine"),
+                    "Enshrine",
+                    run.enshrine,
+                    &mut workflow.enshrine,
+                );
+                workflow_changed |= Self::workflow_step_combo(
+                    ui,
+                    (id, "book"),
+                    "Book",
+                    run.book,
+                    &mut workflow.book,
+                );
+                if !workflow.is_empty() {
+                    ui.colored_label(egui::Color32::YELLOW, "✎ differs from the run");
+                }
+            });
+
+            egui::Grid::new(("draft_positions", &draft.reference))
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    for position in &mut draft.positions {
+                        ui.label(format!("{}x", position.quantity));
+                        ui.add(egui::TextEdit::singleline(&mut position.name).desired_width(220.0));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut position.description)
+                                .desired_width(320.0),
+                        );
+                        ui.label(format!("{:.2}", position.price_gross));
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.add_space(4.0);
+        workflow_changed
+    }
+
+    /// Yes / no / as-run choice for one workflow step of a draft. Returns
+    /// whether the choice changed.
+    fn workflow_step_combo(
+        ui: &mut egui::Ui,
+        id: (&str, &str),
+        label: &str,
+        run: bool,
+        value: &mut Option<bool>,
+    ) -> bool {
+        let yes_no = |on: bool| if on { "yes" } else { "no" };
+        let before = *value;
+        egui::ComboBox::from_id_salt(("draft_workflow", id.0, id.1))
+            .selected_text(match *value {
+                Some(on) => format!("{label}: {}", yes_no(on)),
+                None => format!("{label}: {} (run)", yes_no(run)),
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(value, None, format!("As run: {}", yes_no(run)));
+                ui.selectable_value(value, Some(true), "Yes");
+                ui.selectable_value(value, Some(false), "No");
+            });
+        *value != before
+    }
+
+    fn render_order_board_window(&mut self, ctx: &egui::Context) {
+        if !self.show_order_board {
+            return;
+        }
+
+        let mut open = self.show_order_board;
+        let mut bulk_status = None;
+        let mut import_tracking = false;
+        egui::Window::new("Order Board")
+            .open(&mut open)
+            .resizable(true)
+            .default_size([800.0, 500.0])
+            .show(ctx, |ui| {
+                if let Some(err) = &self.board_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+                ui.horizontal(|ui| {
+                    import_tracking = ui
+                        .button("Import tracking numbers…")
+                        .on_hover_text(
+                            "Attach the tracking numbers of a Cardmarket export to their \
+                             orders and invoices",
+                        )
+                        .clicked();
+                    match &self.tracking_import_status {
+                        Some(Ok(message)) => {
+                            ui.colored_label(egui::Color32::GREEN, message);
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(egui::Color32::RED, e);
+                        }
+                        None => {}
+                    }
+                });
+
+                // Filters
+                ui.horizontal(|ui| {
+                    for (status, count) in self.order_board.counts() {
+                        let mut shown = self.board_filter.statuses.contains(&status);
+                        if ui
+                            .checkbox(&mut shown, format!("{status} ({count})"))
+                            .changed()
+                        {
+                            if shown {
+                                self.board_filter.statuses.insert(status);
+                            } else {
+                                self.board_filter.statuses.remove(&status);
+                            }
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.board_filter.text)
+                            .desired_width(200.0)
+                            .hint_text("Order ID, name, username or tracking number"),
+                    );
+                    ui.label("Country:");
+                    egui::ComboBox::from_id_salt("board_country")
+                        .selected_text(if self.board_filter.country.is_empty() {
+                            "All"
+                        } else {
+                            self.board_filter.country.as_str()
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.board_filter.country,
+                                String::new(),
+                                "All",
+                            );
+                            for country in self.order_board.countries() {
+                                let label = country.clone();
+                                ui.selectable_value(&mut self.board_filter.country, country, label);
+                            }
+                        });
+                });
+
+                let visible: Vec<String> = self
+                    .order_board
+                    .filtered(&self.board_filter)
+                    .iter()
+                    .map(|e| e.order_id.clone())
+                    .collect();
+
+                // Bulk actions on the selected orders
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Select all shown").clicked() {
+                        self.board_selection.extend(visible.iter().cloned());
+                    }
+                    if ui.button("Clear selection").clicked() {
+                        self.board_selection.clear();
+                    }
+                    ui.label(format!("{} selected, mark as:", self.board_selection.len()));
+                    for status in OrderStatus::ALL {
+                        if ui
+                            .add_enabled(
+                                !self.board_selection.is_empty(),
+                                egui::Button::new(status.to_string()),
+                            )
+                            .clicked()
+                        {
+                            bulk_status = Some(status);
+                        }
+                    }
+                });
+                ui.add_space(5.0);
+
+                egui::ScrollArea::both().show(ui, |ui| {
+                    egui_extras::TableBuilder::new(ui)
+                        .striped(true)
+                        .resizable(true)
+                        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                        .column(egui_extras::Column::auto().at_least(20.0)) // Selected
+                        .column(egui_extras::Column::auto().at_least(70.0)) // Order ID
+                        .column(egui_extras::Column::auto().at_least(80.0)) // Date
+                        .column(egui_extras::Column::auto().at_least(120.0)) // Customer
+                        .column(egui_extras::Column::auto().at_least(100.0)) // Country
+                        .column(egui_extras::Column::auto().at_least(70.0)) // Total
+                        .column(egui_extras::Column::auto().at_least(70.0)) // Status
+                        .column(egui_extras::Column::auto().at_least(90.0)) // Invoice
+                        .column(egui_extras::Column::auto().at_least(120.0)) // Tracking
+                        .column(egui_extras::Column::remainder()) // Updated
+                        .header(20.0, |mut header| {
+                            for title in [
+                                "", "Order ID", "Date", "Customer", "Country", "Total", "Status",
+                                "Invoice", "Tracking", "Updated",
+                            ] {
+                                header.col(|ui| {
+                                    ui.strong(title);
+                                });
+                            }
+                        })
+                        .body(|mut body| {
+                            for id in &visible {
+                                let Some(entry) = self.order_board.entries.get(id) else {
+                                    continue;
+                                };
+                                body.row(18.0, |mut row| {
+                                    row.col(|ui| {
+                                        let mut selected = self.board_selection.contains(id);
+                                        if ui.checkbox(&mut selected, "").changed() {
+                                            if selected {
+                                                self.board_selection.insert(id.clone());
+                                            } else {
+                                                self.board_selection.remove(id);
+                                            }
+                                        }
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(&entry.order_id);
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(&entry.date_of_purchase);
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(format!("{} ({})", entry.name, entry.username));
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(&entry.country);
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(format!("{:.2} EUR", entry.total_value));
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(entry.status.to_string());
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(entry.invoice_number.as_deref().unwrap_or("—"));
+                                    });
+                                    row.col(|ui| match &entry.tracking_number {
+                                        Some(number) => {
+                                            if ui
+                                                .link(number)
+                                                .on_hover_text("Copy tracking number")
+                                                .clicked()
+                                            {
+                                                ui.ctx().copy_text(number.clone());
+                                            }
+                                        }
+                                        None => {
+                                            ui.label("—");
+                                        }
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(&entry.updated_at);
+                                    });
+                                });
+                            }
+                        });
+                });
+            });
+        self.show_order_board = open;
+
+        if import_tracking {
+            self.import_tracking_numbers();
+        }
+
+        if let Some(status) = bulk_status {
+            let changed = self
+                .order_board
+                .set_status(self.board_selection.iter().map(String::as_str), status);
+            info!("Marked {changed} orders as {status}");
+            self.board_selection.clear();
+            if changed > 0 {
+                self.save_order_board();
+            }
+        }
     }
 }
@@ -0,0 +1,110 @@
+//! Generates anonymized Cardmarket order CSVs for reproducing bugs.
+//!
+//! ```text
+//! cargo run --bin generate_fixtures -- --orders 50 --max-items 4 \
+//!     --countries Germany,France --edge-cases missing-zip,professional \
+//!     --edge-case-rate 0.3 --seed 7 --out orders.csv
+//! ```
+
+use std::process::ExitCode;
+
+use anyhow::{anyhow, bail, Context, Result};
+use sevdesk_invoicing::fixtures::{countries, generate_orders_csv, EdgeCase, FixtureConfig};
+
+fn usage() -> String {
+    let edge_cases: Vec<_> = EdgeCase::ALL.iter().map(EdgeCase::name).collect();
+    format!(
+        "Usage: generate_fixtures [OPTIONS]
+
+Options:
+  --orders N            Number of orders (default 10)
+  --max-items N         Maximum distinct items per order (default 3)
+  --countries A,B       Shipping countries (default Germany)
+                        Known: {}
+  --edge-cases A,B|all|none
+                        Edge cases to mix in (default all)
+                        Known: {}
+  --edge-case-rate R    Share of orders with an edge case, 0.0-1.0 (default 0.2)
+  --seed N              Random seed (default 1)
+  --first-order-id N    First order ID (default 1000000000)
+  --out PATH            Output file (default stdout)
+  -h, --help            Show this help",
+        countries().collect::<Vec<_>>().join(", "),
+        edge_cases.join(", ")
+    )
+}
+
+fn parse_args(args: &[String]) -> Result<(FixtureConfig, Option<String>)> {
+    let mut config = FixtureConfig::default();
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| anyhow!("Missing value for {flag}"))
+        };
+        match flag.as_str() {
+            "--orders" => config.orders = value()?.parse().context("--orders")?,
+            "--max-items" => {
+                config.max_items_per_order = value()?.parse().context("--max-items")?
+            }
+            "--countries" => {
+                config.countries = value()?.split(',').map(|c| c.trim().to_string()).collect()
+            }
+            "--edge-cases" => {
+                config.edge_cases = match value()?.as_str() {
+                    "all" => EdgeCase::ALL.to_vec(),
+                    "none" => Vec::new(),
+                    list => list
+                        .split(',')
+                        .map(|name| {
+                            EdgeCase::parse(name)
+                                .ok_or_else(|| anyhow!("Unknown edge case '{name}'"))
+                        })
+                        .collect::<Result<_>>()?,
+                }
+            }
+            "--edge-case-rate" => {
+                config.edge_case_rate = value()?.parse().context("--edge-case-rate")?
+            }
+            "--seed" => config.seed = value()?.parse().context("--seed")?,
+            "--first-order-id" => {
+                config.first_order_id = value()?.parse().context("--first-order-id")?
+            }
+            "--out" => out = Some(value()?.clone()),
+            other => bail!("Unknown option '{other}'"),
+        }
+    }
+    if config.max_items_per_order == 0 {
+        bail!("--max-items must be at least 1");
+    }
+    Ok((config, out))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    let result = parse_args(&args).and_then(|(config, out)| {
+        let csv = generate_orders_csv(&config)?;
+        match out {
+            Some(path) => {
+                std::fs::write(&path, csv).with_context(|| format!("Cannot write {path}"))?;
+                eprintln!("Wrote {} orders to {path}", config.orders);
+            }
+            None => print!("{csv}"),
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:#}\n\n{}", usage());
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -7,9 +7,11 @@ use anyhow::Result;
 use chrono::Utc;
 use log::{debug, warn};
 
+use mtg_common::DecimalLocale;
+
 use crate::models::{CardRecord, OrderItem, OrderRecord};
 
-use super::field_parsers::parse_price;
+use super::field_parsers::parse_price_in;
 
 /// Parses a single line of card data.
 ///
@@ -139,13 +141,14 @@ pub fn parse_set_info(set_info: &str) -> (String, String, String, String, String
 ///
 /// # Arguments
 /// * `card` - The card record to convert
+/// * `locale` - Decimal/thousands separator convention of the source file
 ///
 /// # Returns
 /// An OrderRecord with default values for order-specific fields.
-pub fn card_to_order(card: CardRecord) -> OrderRecord {
+pub fn card_to_order(card: CardRecord, locale: DecimalLocale) -> OrderRecord {
     debug!("Converting card to order: {}", card.card_name);
 
-    let parsed_price = parse_price(&card.price).unwrap_or_else(|e| {
+    let parsed_price = parse_price_in(&card.price, locale).unwrap_or_else(|e| {
         warn!("Failed to parse price '{}': {}, using 0.0", card.price, e);
         0.0
     });
@@ -1,6 +1,7 @@
 //! Unit tests for card/inventory CSV parsing.
 
 use super::*;
+use mtg_common::DecimalLocale;
 
 mod parse_set_info_tests {
     use super::*;
@@ -113,7 +114,7 @@ mod card_to_order_tests {
             currency: "EUR".to_string(),
         };
 
-        let order = card_to_order(card);
+        let order = card_to_order(card, DecimalLocale::Auto);
 
         assert_eq!(order.order_id, "12345");
         assert_eq!(order.name, "Card Customer");
@@ -140,7 +141,7 @@ mod card_to_order_tests {
             currency: "EUR".to_string(),
         };
 
-        let order = card_to_order(card);
+        let order = card_to_order(card, DecimalLocale::Auto);
 
         // Should default to 0.0 when price can't be parsed
         assert!((order.items[0].price - 0.0).abs() < 0.001);
@@ -160,7 +161,7 @@ mod card_to_order_tests {
             currency: "EUR".to_string(),
         };
 
-        let order = card_to_order(card);
+        let order = card_to_order(card, DecimalLocale::Auto);
 
         assert!(order.description.contains("Test Card"));
         assert!(order.description.contains("Test Set"));
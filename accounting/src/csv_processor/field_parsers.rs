@@ -3,10 +3,11 @@
 //! Contains pure functions for parsing individual fields like prices,
 //! cities, quantities, etc.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use log::{debug, warn};
+use mtg_common::{parse_decimal, DecimalLocale};
 
-/// Parses a price string, handling both comma and dot decimal separators.
+/// Parses a price string, auto-detecting the decimal separator.
 ///
 /// # Arguments
 /// * `price_str` - A string containing a price value (e.g., "5,00" or "5.00")
@@ -14,9 +15,21 @@ use log::{debug, warn};
 /// # Returns
 /// The parsed price as f64, or an error if parsing fails.
 pub fn parse_price(price_str: &str) -> Result<f64> {
-    debug!("Parsing price string: {price_str}");
-    let clean_price = price_str.replace(',', ".");
-    let result = clean_price.parse::<f64>().context("Failed to parse price");
+    parse_price_in(price_str, DecimalLocale::Auto)
+}
+
+/// Parses a price string in the given number format.
+///
+/// # Arguments
+/// * `price_str` - A string containing a price value (e.g., "1.234,56")
+/// * `locale` - Decimal/thousands separator convention of the source file
+///
+/// # Returns
+/// The parsed price as f64, or an error if parsing fails.
+pub fn parse_price_in(price_str: &str, locale: DecimalLocale) -> Result<f64> {
+    debug!("Parsing price string: {price_str} ({locale})");
+    let result =
+        parse_decimal(price_str, locale).ok_or_else(|| anyhow::anyhow!("Failed to parse price"));
 
     match &result {
         Ok(value) => debug!("Successfully parsed price: {value}"),
@@ -85,17 +98,17 @@ pub fn extract_quantity_from_description(description: &str) -> u32 {
 ///
 /// # Arguments
 /// * `description` - A description string like "1x Card Name - 5,00 EUR"
+/// * `locale` - Decimal/thousands separator convention of the source file
 ///
 /// # Returns
 /// The extracted price as f64, or an error if no price pattern is found.
-pub fn extract_price_from_description(description: &str) -> Result<f64> {
+pub fn extract_price_from_description(description: &str, locale: DecimalLocale) -> Result<f64> {
     debug!("Extracting price from description: {description}");
 
     // Look for pattern like "- 0,19 EUR" or "- 5,35 EUR"
     if let Some(price_match) = description.split(" - ").last() {
         if price_match.contains("EUR") {
-            let price_str = price_match.replace("EUR", "").trim().replace(',', ".");
-            if let Ok(price) = price_str.parse::<f64>() {
+            if let Some(price) = parse_decimal(&price_match.replace("EUR", ""), locale) {
                 debug!("Extracted price: {price:.2}");
                 return Ok(price);
             }
@@ -105,8 +118,7 @@ pub fn extract_price_from_description(description: &str) -> Result<f64> {
     // Fallback: look for any number followed by EUR
     for part in description.split_whitespace() {
         if part.contains("EUR") {
-            let price_str = part.replace("EUR", "").replace(',', ".");
-            if let Ok(price) = price_str.parse::<f64>() {
+            if let Some(price) = parse_decimal(&part.replace("EUR", ""), locale) {
                 debug!("Extracted price (fallback): {price:.2}");
                 return Ok(price);
             }
@@ -1,6 +1,7 @@
 //! Unit tests for field parsing utilities.
 
 use super::*;
+use mtg_common::DecimalLocale;
 
 mod parse_price_tests {
     use super::*;
@@ -40,6 +41,22 @@ mod parse_price_tests {
         let result = parse_price("");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn parses_thousands_separators() {
+        assert_eq!(parse_price("1.234,56").unwrap(), 1234.56);
+        assert_eq!(parse_price("1,234.56").unwrap(), 1234.56);
+    }
+
+    #[test]
+    fn explicit_locale_is_strict() {
+        assert_eq!(
+            parse_price_in("1.234", DecimalLocale::German).unwrap(),
+            1234.0
+        );
+        assert!(parse_price_in("1.87", DecimalLocale::German).is_err());
+        assert!(parse_price_in("1,87", DecimalLocale::English).is_err());
+    }
 }
 
 mod parse_city_field_tests {
@@ -129,13 +146,15 @@ mod extract_price_tests {
 
     #[test]
     fn extracts_price_with_comma_decimal() {
-        let price = extract_price_from_description("Some Card - 1,87 EUR").unwrap();
+        let price =
+            extract_price_from_description("Some Card - 1,87 EUR", DecimalLocale::Auto).unwrap();
         assert!((price - 1.87).abs() < 0.001);
     }
 
     #[test]
     fn extracts_price_with_dot_decimal() {
-        let price = extract_price_from_description("Some Card - 5.35 EUR").unwrap();
+        let price =
+            extract_price_from_description("Some Card - 5.35 EUR", DecimalLocale::Auto).unwrap();
         assert!((price - 5.35).abs() < 0.001);
     }
 
@@ -143,6 +162,7 @@ mod extract_price_tests {
     fn extracts_price_from_full_description() {
         let price = extract_price_from_description(
             "1x High Fae Trickster (Magic: The Gathering Foundations) - 40 - Rare - NM - English - 1,87 EUR",
+            DecimalLocale::Auto,
         )
         .unwrap();
         assert!((price - 1.87).abs() < 0.001);
@@ -150,19 +170,21 @@ mod extract_price_tests {
 
     #[test]
     fn extracts_larger_price() {
-        let price = extract_price_from_description("Expensive Card - 125,99 EUR").unwrap();
+        let price =
+            extract_price_from_description("Expensive Card - 125,99 EUR", DecimalLocale::Auto)
+                .unwrap();
         assert!((price - 125.99).abs() < 0.001);
     }
 
     #[test]
     fn fails_without_eur_marker() {
-        let result = extract_price_from_description("Some Card - 1,87");
+        let result = extract_price_from_description("Some Card - 1,87", DecimalLocale::Auto);
         assert!(result.is_err());
     }
 
     #[test]
     fn fails_for_empty_string() {
-        let result = extract_price_from_description("");
+        let result = extract_price_from_description("", DecimalLocale::Auto);
         assert!(result.is_err());
     }
 }
@@ -35,6 +35,7 @@ pub mod validator;
 
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
+use mtg_common::DecimalLocale;
 use std::path::Path;
 
 use crate::models::OrderRecord;
@@ -44,13 +45,27 @@ use crate::models::OrderRecord;
 /// Provides a unified interface for loading and validating CSV data,
 /// automatically detecting the format (order CSV vs card data).
 #[derive(Default)]
-pub struct CsvProcessor;
+pub struct CsvProcessor {
+    locale: DecimalLocale,
+}
 
 impl CsvProcessor {
-    /// Creates a new CSV processor instance.
+    /// Creates a new CSV processor that auto-detects the number format.
+    #[allow(dead_code)]
     pub fn new() -> Self {
-        debug!("Creating new CSV processor");
-        Self
+        Self::with_locale(DecimalLocale::Auto)
+    }
+
+    /// Creates a CSV processor for files in a known number format, e.g.
+    /// [`DecimalLocale::German`] for `1.234,56`.
+    pub fn with_locale(locale: DecimalLocale) -> Self {
+        debug!("Creating new CSV processor ({locale} number format)");
+        Self { locale }
+    }
+
+    /// The number format prices are parsed in.
+    pub fn locale(&self) -> DecimalLocale {
+        self.locale
     }
 
     /// Loads orders from a CSV file.
@@ -71,9 +86,12 @@ impl CsvProcessor {
         let path = file_path.as_ref();
         info!("Loading orders from CSV file: {path:?}");
 
-        let file_content = tokio::fs::read_to_string(path)
-            .await
-            .context("Failed to read CSV file")?;
+        // Gzipped and zipped exports are decompressed while reading
+        let owned_path = path.to_path_buf();
+        let file_content =
+            tokio::task::spawn_blocking(move || mtg_common::compressed::read_to_string(owned_path))
+                .await?
+                .context("Failed to read CSV file")?;
 
         debug!("CSV file size: {} bytes", file_content.len());
         self.parse_csv_content(&file_content)
@@ -106,7 +124,7 @@ impl CsvProcessor {
             || header_line.contains("Name")
         {
             info!("Detected CSV format with headers");
-            return order_parser::parse_csv_with_headers(content);
+            return order_parser::parse_csv_with_headers(content, self.locale);
         }
 
         // Otherwise try to parse as card data
@@ -120,7 +138,7 @@ impl CsvProcessor {
             match card_parser::parse_card_line(line) {
                 Ok(card) => {
                     debug!("Successfully parsed card: {}", card.card_name);
-                    orders.push(card_parser::card_to_order(card));
+                    orders.push(card_parser::card_to_order(card, self.locale));
                 }
                 Err(e) => {
                     warn!("Failed to parse line {} as card data: {}", line_num + 1, e);
@@ -4,9 +4,8 @@ use super::*;
 
 #[test]
 fn csv_processor_default_works() {
-    let processor = CsvProcessor;
-    // Just ensure it can be created
-    let _ = processor;
+    let processor = CsvProcessor::default();
+    assert_eq!(processor.locale(), DecimalLocale::Auto);
 }
 
 #[test]
@@ -65,3 +64,18 @@ fn validate_orders_delegates_to_validator() {
     assert!(!errors.is_empty());
     assert!(errors[0].contains("Customer name is empty"));
 }
+
+#[test]
+fn with_locale_parses_thousands_separators() {
+    let content = "OrderID;Username;Name;Street;City;Country;IsProfessional;VATNumber;DateOfPurchase;ArticleCount;MerchandiseValue;ShipmentCosts;TotalValue;Commission;Currency;Description;ProductID;LocalizedProductName\n\
+                  1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;1;1.250,00;1,50;1.251,50;0,10;EUR;1x Black Lotus - 1.250,00 EUR;98765;Black Lotus";
+
+    let german = CsvProcessor::with_locale(DecimalLocale::German);
+    let orders = german.parse_csv_content(content).unwrap();
+    assert_eq!(orders[0].items[0].price, 1250.0);
+
+    // Strict English parsing rejects the German price and falls back to 0
+    let english = CsvProcessor::with_locale(DecimalLocale::English);
+    let orders = english.parse_csv_content(content).unwrap();
+    assert_eq!(orders[0].items[0].price, 0.0);
+}
@@ -4,6 +4,7 @@
 
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
+use mtg_common::DecimalLocale;
 
 use crate::models::{OrderItem, OrderRecord};
 
@@ -20,10 +21,11 @@ use super::field_parsers::{
 ///
 /// # Arguments
 /// * `content` - The raw CSV content as a string
+/// * `locale` - Decimal/thousands separator convention of the item prices
 ///
 /// # Returns
 /// A vector of parsed OrderRecord, or an error if parsing fails.
-pub fn parse_csv_with_headers(content: &str) -> Result<Vec<OrderRecord>> {
+pub fn parse_csv_with_headers(content: &str, locale: DecimalLocale) -> Result<Vec<OrderRecord>> {
     debug!("Parsing CSV with headers");
     let mut orders = Vec::new();
     let lines: Vec<&str> = content.lines().collect();
@@ -46,7 +48,7 @@ pub fn parse_csv_with_headers(content: &str) -> Result<Vec<OrderRecord>> {
             continue;
         }
 
-        match parse_order_line(line) {
+        match parse_order_line(line, locale) {
             Ok(order) => {
                 debug!("Successfully parsed order: {:?}", order.order_id);
                 orders.push(order);
@@ -66,10 +68,11 @@ pub fn parse_csv_with_headers(content: &str) -> Result<Vec<OrderRecord>> {
 ///
 /// # Arguments
 /// * `line` - A semicolon-separated line from the CSV
+/// * `locale` - Decimal/thousands separator convention of the item prices
 ///
 /// # Returns
 /// A parsed OrderRecord, or an error if the line is malformed.
-pub fn parse_order_line(line: &str) -> Result<OrderRecord> {
+pub fn parse_order_line(line: &str, locale: DecimalLocale) -> Result<OrderRecord> {
     debug!("Parsing CSV line: {line}");
     let parts: Vec<&str> = line.split(';').collect();
 
@@ -87,7 +90,7 @@ pub fn parse_order_line(line: &str) -> Result<OrderRecord> {
     let (zip, city) = parse_city_field(city_field)?;
 
     // Parse individual items if multiple items are present
-    let items = parse_order_items(parts[15].trim(), parts[16].trim(), parts[17].trim())?;
+    let items = parse_order_items(parts[15].trim(), parts[16].trim(), parts[17].trim(), locale)?;
 
     let order_record = OrderRecord {
         order_id: parts[0].trim().to_string(),
@@ -145,6 +148,7 @@ pub fn parse_order_line(line: &str) -> Result<OrderRecord> {
 /// * `description` - Item description(s), possibly " | "-separated
 /// * `product_ids` - Product ID(s), possibly " | "-separated
 /// * `product_names` - Product name(s), possibly " | "-separated
+/// * `locale` - Decimal/thousands separator convention of the item prices
 ///
 /// # Returns
 /// A vector of parsed OrderItem.
@@ -152,6 +156,7 @@ pub fn parse_order_items(
     description: &str,
     product_ids: &str,
     product_names: &str,
+    locale: DecimalLocale,
 ) -> Result<Vec<OrderItem>> {
     let ids = product_ids.split(" | ").collect::<Vec<&str>>();
     let names = product_names.split(" | ").collect::<Vec<&str>>();
@@ -172,12 +177,12 @@ pub fn parse_order_items(
                 descriptions.len(),
                 expected_count
             );
-            return parse_as_single_item(description, product_ids, product_names);
+            return parse_as_single_item(description, product_ids, product_names, locale);
         }
 
         let mut items = Vec::new();
         for ((desc, id), name) in descriptions.iter().zip(ids.iter()).zip(names.iter()) {
-            let price = extract_price_from_description(desc)?;
+            let price = extract_price_from_description(desc, locale)?;
             let quantity = extract_quantity_from_description(desc);
             items.push(OrderItem {
                 description: desc.trim().to_string(),
@@ -191,7 +196,7 @@ pub fn parse_order_items(
         debug!("Successfully parsed {} items", items.len());
         Ok(items)
     } else {
-        parse_as_single_item(description, product_ids, product_names)
+        parse_as_single_item(description, product_ids, product_names, locale)
     }
 }
 
@@ -263,8 +268,9 @@ fn parse_as_single_item(
     description: &str,
     product_ids: &str,
     product_names: &str,
+    locale: DecimalLocale,
 ) -> Result<Vec<OrderItem>> {
-    let price = extract_price_from_description(description).unwrap_or(0.0);
+    let price = extract_price_from_description(description, locale).unwrap_or(0.0);
     let quantity = extract_quantity_from_description(description);
     let item = OrderItem {
         description: description.to_string(),
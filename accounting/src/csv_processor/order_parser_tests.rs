@@ -1,13 +1,20 @@
 //! Unit tests for order CSV parsing.
 
 use super::*;
+use mtg_common::DecimalLocale;
 
 mod parse_order_items_tests {
     use super::*;
 
     #[test]
     fn parses_single_item() {
-        let items = parse_order_items("1x Card Name - 1,87 EUR", "12345", "Card Name").unwrap();
+        let items = parse_order_items(
+            "1x Card Name - 1,87 EUR",
+            "12345",
+            "Card Name",
+            DecimalLocale::Auto,
+        )
+        .unwrap();
 
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].product_id, "12345");
@@ -22,6 +29,7 @@ mod parse_order_items_tests {
             "1x Card One - 1,50 EUR | 2x Card Two - 3,00 EUR",
             "111 | 222",
             "Card One | Card Two",
+            DecimalLocale::Auto,
         )
         .unwrap();
 
@@ -45,6 +53,7 @@ mod parse_order_items_tests {
             "1x Card One - 1,50 EUR | 2x Card Two - 3,00 EUR",
             "111", // Only one ID
             "Card One | Card Two",
+            DecimalLocale::Auto,
         )
         .unwrap();
 
@@ -59,6 +68,7 @@ mod parse_order_items_tests {
             "1x Moss Diamond - 0,02 EUR | 1x Robot Token (Magic: The Gathering | Marvel's Spider-Man) - 0,02 EUR",
             "512140 | 848235",
             "Moss Diamond | Robot Token",
+            DecimalLocale::Auto,
         )
         .unwrap();
 
@@ -80,6 +90,7 @@ mod parse_order_items_tests {
             "1x Moss Diamond (Commander Legends) - 327 - Common - NM - English - 0,02 EUR | 2x Gift of Paradise (Commander Legends) - 229 - Common - NM - English - 0,04 EUR",
             "512140 | 510645",
             "Moss Diamond | Gift of Paradise",
+            DecimalLocale::Auto,
         )
         .unwrap();
 
@@ -104,7 +115,8 @@ mod parse_order_items_tests {
 
         let product_names = "Moss Diamond | Secluded Courtyard | Fairgrounds Warden | Ilysian Caryatid | Cinderclasm | Unclaimed Territory | Secluded Steppe | Kessig Wolf Run | Cultivate | Selvala's Stampede | Setessan Oathsworn | Robot Token (A 1/1) // Food Token | Wayward Swordtooth | Ancestral Blade | Raise the Alarm | Inspiring Roar | Wild Celebrants | Court Street Denizen | Lys Alana Bowmaster | Jalum Tome | Fin-Clade Fugitives | Lifecrafter's Gift | Spark Harvest | Gift of Paradise | Wildsize | Iona's Judgment | Dispeller's Capsule | Kinsbaile Courier | Seer's Lantern | Opal Palace | Palace Sentinels | Ninth Bridge Patrol | Captain's Call | Ripscale Predator | Skywhaler's Shot | Welding Sparks | Rupture Spire";
 
-        let items = parse_order_items(description, product_ids, product_names).unwrap();
+        let items = parse_order_items(description, product_ids, product_names, DecimalLocale::Auto)
+            .unwrap();
 
         // Should parse all 37 distinct line items (some have quantities > 1)
         assert_eq!(items.len(), 37);
@@ -166,7 +178,8 @@ mod parse_order_items_tests {
 
         let product_names = "Mishra's Factory (V.1) | Runeflare Trap | Zwiefalt der Gottheit | Mishra's Factory | Lightning Helix (V.1) | Qasali Ambusher | Mage Slayer | Hidden Footblade | Sinkhole Surveyor | The Sibsig Ceremony | Lasyd Prowler | Veteran Ice Climber | Molt Tender | Terrian, World Tyrant | Greasewrench Goblin | Transit Mage | Shocking Sharpshooter | Gas Guzzler | Voyager Glidecar | Gas Guzzler | Regal Imperiosaur | Webstrike Elite | Zahur, Glory's Past | Anthem of Champions | Sylvan Scavenging | Ajani, Caller of the Pride | Demon of Catastrophes | Diamond Lion | Lupinflower Village | Heartfire Hero | Shrike Force | Sunshower Druid | Duskwatch Recruiter / Krallenhorde Howler (V.1) | Abundant Growth | Young Wolf | Twinblade Geist // Twinblade Invocation | Ghoultree | Lupine Prototype | Decimator of the Provinces | Glint-Nest Crane | Morbid Curiosity | Skirsdag High Priest | Bedlam Reveler | Kruin Outlaw / Terror of Kruin Pass | Collective Brutality | Rabbit Battery | Twinblade Geist // Twinblade Invocation | Ascendant Packleader | Sticky Fingers | Sorin, rachsüchtiger Blutfürst | Mosswood Dreadknight // Dread Whispers | Realm-Scorcher Hellkite | Callous Sell-Sword // Burn Together | Egon, God of Death // Throne of Death | Silhana Ledgewalker | Stalking Vengeance | Judge's Familiar | Mask of Memory | Veteran Beastrider | Light Up the Stage | Ghalta, Primal Hunger | Scout for Survivors | Xu-Ifit, Osteoharmonist | Volcano Hellion | Lupine Prototype | Sinkhole Surveyor | Lasyd Prowler | Come Back Wrong | Niko, Light of Hope | Doomsday Excruciator | Wall of Reverence | Salvage Titan | Disciple of Bolas | Leyline Tyrant | Magus of the Candelabra | Irresistible Prey | Flame Slash | Faithless Looting | Myr Superion | Nissa, Voice of Zendikar | Thundermaw Hellkite | Leatherback Baloth | Chandra, Pyromaster | Heartless Summoning | Pelt Collector | Yorvo, Lord of Garenbrig | Serra Avenger | Hazoret the Fervent | Talara's Battalion | Traverse the Ulvenwald | Kaya's Guile | Stormbreath Dragon | Infernal Tutor | Hollow One | Fracturing Gust | Leyline of Sanctity | Kolaghan's Command | Liliana, Death's Majesty | Dungrove Elder | Collective Brutality | Lightning Skelemental | Nachtstahl-Zitadelle | Temur Battle Rage | Flameblade Adept | Shattering Spree | Ornithopter | Nameless Inversion | Groundswell | Grisly Salvage | Spectral Procession | Mask of Memory | Crack the Earth | Gnaw to the Bone | Browbeat | Apostle's Blessing | Hallowed Burial | Emissary Escort (V.2) | Bender's Waterskin | Tolls of War | Shadow of the Goblin | Origin of Metalbending | Octopus Form | Night's Whisper | Mass Hysteria | Curious Farm Animals";
 
-        let items = parse_order_items(description, product_ids, product_names).unwrap();
+        let items = parse_order_items(description, product_ids, product_names, DecimalLocale::Auto)
+            .unwrap();
 
         // Should parse all 125 line items
         assert_eq!(items.len(), 125);
@@ -278,7 +291,7 @@ mod parse_order_line_tests {
     fn parses_valid_order_line() {
         let line = "1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name";
 
-        let order = parse_order_line(line).unwrap();
+        let order = parse_order_line(line, DecimalLocale::Auto).unwrap();
 
         assert_eq!(order.order_id, "1234567");
         assert_eq!(order.username, "user123");
@@ -301,7 +314,7 @@ mod parse_order_line_tests {
     fn parses_order_with_optional_fields_empty() {
         let line = "1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name";
 
-        let order = parse_order_line(line).unwrap();
+        let order = parse_order_line(line, DecimalLocale::Auto).unwrap();
 
         assert!(order.is_professional.is_none());
         assert!(order.vat_number.is_none());
@@ -311,7 +324,7 @@ mod parse_order_line_tests {
     fn parses_order_with_professional_flag() {
         let line = "1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;yes;DE123456789;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name";
 
-        let order = parse_order_line(line).unwrap();
+        let order = parse_order_line(line, DecimalLocale::Auto).unwrap();
 
         assert_eq!(order.is_professional, Some("yes".to_string()));
         assert_eq!(order.vat_number, Some("DE123456789".to_string()));
@@ -321,7 +334,7 @@ mod parse_order_line_tests {
     fn fails_with_insufficient_columns() {
         let line = "1234567;user123;John Doe";
 
-        let result = parse_order_line(line);
+        let result = parse_order_line(line, DecimalLocale::Auto);
         assert!(result.is_err());
     }
 
@@ -329,7 +342,7 @@ mod parse_order_line_tests {
     fn fails_with_invalid_article_count() {
         let line = "1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;not_a_number;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name";
 
-        let result = parse_order_line(line);
+        let result = parse_order_line(line, DecimalLocale::Auto);
         assert!(result.is_err());
     }
 }
@@ -342,7 +355,7 @@ mod parse_csv_with_headers_tests {
         let content = "OrderID;Username;Name;Street;City;Country;IsProfessional;VATNumber;DateOfPurchase;ArticleCount;MerchandiseValue;ShipmentCosts;TotalValue;Commission;Currency;Description;ProductID;LocalizedProductName\n\
                       1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name";
 
-        let orders = parse_csv_with_headers(content).unwrap();
+        let orders = parse_csv_with_headers(content, DecimalLocale::Auto).unwrap();
 
         assert_eq!(orders.len(), 1);
         assert_eq!(orders[0].order_id, "1234567");
@@ -355,7 +368,7 @@ mod parse_csv_with_headers_tests {
                       1234567;user1;John Doe;Street 1;10557 Berlin;Germany;;;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card One\n\
                       1234568;user2;Jane Doe;Street 2;20095 Hamburg;Germany;;;2025-01-16;2;10,00;1,50;11,50;0,20;EUR;2x Card - 5,00 EUR;98766;Card Two";
 
-        let orders = parse_csv_with_headers(content).unwrap();
+        let orders = parse_csv_with_headers(content, DecimalLocale::Auto).unwrap();
 
         assert_eq!(orders.len(), 2);
         assert_eq!(orders[0].name, "John Doe");
@@ -366,7 +379,7 @@ mod parse_csv_with_headers_tests {
     fn returns_empty_for_empty_content() {
         // Empty content has no header, so parse_csv_with_headers won't be called
         // But if it is, it should return empty
-        let orders = parse_csv_with_headers("").unwrap();
+        let orders = parse_csv_with_headers("", DecimalLocale::Auto).unwrap();
         assert!(orders.is_empty());
     }
 
@@ -374,7 +387,7 @@ mod parse_csv_with_headers_tests {
     fn returns_empty_for_header_only() {
         let content = "OrderID;Username;Name;Street;City;Country;IsProfessional;VATNumber;DateOfPurchase;ArticleCount;MerchandiseValue;ShipmentCosts;TotalValue;Commission;Currency;Description;ProductID;LocalizedProductName";
 
-        let orders = parse_csv_with_headers(content).unwrap();
+        let orders = parse_csv_with_headers(content, DecimalLocale::Auto).unwrap();
         assert!(orders.is_empty());
     }
 
@@ -385,7 +398,7 @@ mod parse_csv_with_headers_tests {
                       1234567;user123;John Doe;Main Street 1;10557 Berlin;Germany;;;2025-01-15;1;5,00;1,50;6,50;0,10;EUR;1x Card - 5,00 EUR;98765;Card Name\n\
                       ";
 
-        let orders = parse_csv_with_headers(content).unwrap();
+        let orders = parse_csv_with_headers(content, DecimalLocale::Auto).unwrap();
         assert_eq!(orders.len(), 1);
     }
 }
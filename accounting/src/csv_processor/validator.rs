@@ -52,21 +52,12 @@ pub fn validate_orders(orders: &[OrderRecord]) -> Vec<String> {
             warn!("{error_msg}");
             errors.push(error_msg);
         } else if parse_price(&order.total_value).is_err() {
-            // More lenient price parsing - accept comma as decimal separator
-            let normalized_price = order.total_value.replace(',', ".");
-            if normalized_price.parse::<f64>().is_err() {
-                let error_msg = format!(
-                    "Line {}: Invalid total value format: {}",
-                    line_num, order.total_value
-                );
-                warn!("{error_msg}");
-                errors.push(error_msg);
-            } else {
-                debug!(
-                    "Line {}: Price format acceptable after normalization: {} -> {}",
-                    line_num, order.total_value, normalized_price
-                );
-            }
+            let error_msg = format!(
+                "Line {}: Invalid total value format: {}",
+                line_num, order.total_value
+            );
+            warn!("{error_msg}");
+            errors.push(error_msg);
         }
 
         if order.currency.trim().is_empty() {
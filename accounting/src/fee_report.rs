@@ -0,0 +1,216 @@
+//! Cardmarket fees per sale and per month.
+//!
+//! Every loaded order is a sale; its `Commission` column is the fee
+//! Cardmarket keeps. The order export has no payment fee column, so payment
+//! fees are computed from [`FeeSettings`] (a percentage of the total value
+//! plus a fixed amount per order, both zero by default). Fees are summed per
+//! month and currency so the amounts on Cardmarket's monthly fee vouchers can
+//! be cross-checked, and both views can be exported as CSV.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use mtg_common::round_cents;
+
+use crate::csv_processor::field_parsers::parse_price;
+use crate::models::OrderRecord;
+
+/// How payment fees are charged; Cardmarket's own commission comes from the
+/// order export.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeeSettings {
+    /// Percentage of the order's total value
+    pub payment_fee_percent: f64,
+    /// Fixed amount per order
+    pub payment_fee_fixed: f64,
+}
+
+impl FeeSettings {
+    /// The payment fee for an order total, rounded to cents.
+    pub fn payment_fee(&self, total_value: f64) -> f64 {
+        if self.payment_fee_percent == 0.0 && self.payment_fee_fixed == 0.0 {
+            return 0.0;
+        }
+        round_cents(total_value * self.payment_fee_percent / 100.0 + self.payment_fee_fixed)
+    }
+}
+
+/// The fees of one sale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaleFees {
+    pub order_id: String,
+    /// Purchase date as in the export (date part only)
+    pub date: String,
+    pub currency: String,
+    pub merchandise_value: f64,
+    pub total_value: f64,
+    pub commission: f64,
+    pub payment_fee: f64,
+}
+
+impl SaleFees {
+    /// `YYYY-MM` of the purchase.
+    pub fn month(&self) -> &str {
+        self.date.get(..7).unwrap_or(&self.date)
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        round_cents(self.commission + self.payment_fee)
+    }
+}
+
+/// Fees of all sales in one month and currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlyFees {
+    /// `YYYY-MM`
+    pub month: String,
+    pub currency: String,
+    pub orders: usize,
+    pub merchandise_value: f64,
+    pub commission: f64,
+    pub payment_fee: f64,
+}
+
+impl MonthlyFees {
+    pub fn total_fees(&self) -> f64 {
+        round_cents(self.commission + self.payment_fee)
+    }
+}
+
+/// The fees of one order. Fails if a money column cannot be parsed.
+pub fn sale_fees(order: &OrderRecord, settings: &FeeSettings) -> Result<SaleFees> {
+    let total_value = parse_price(&order.total_value)
+        .with_context(|| format!("Order {}: invalid total value", order.order_id))?;
+    Ok(SaleFees {
+        order_id: order.order_id.clone(),
+        date: order
+            .date_of_purchase
+            .split(' ')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        currency: order.currency.clone(),
+        merchandise_value: parse_price(&order.merchandise_value)
+            .with_context(|| format!("Order {}: invalid merchandise value", order.order_id))?,
+        total_value,
+        commission: parse_price(&order.commission)
+            .with_context(|| format!("Order {}: invalid commission", order.order_id))?,
+        payment_fee: settings.payment_fee(total_value),
+    })
+}
+
+/// Fees for every order, in CSV order.
+pub fn fees_for_orders(orders: &[OrderRecord], settings: &FeeSettings) -> Result<Vec<SaleFees>> {
+    orders
+        .iter()
+        .map(|order| sale_fees(order, settings))
+        .collect()
+}
+
+/// Sums the sales per month and currency, oldest month first. Sums are
+/// taken in whole cents.
+pub fn monthly_fees(sales: &[SaleFees]) -> Vec<MonthlyFees> {
+    let cents = |amount: f64| (amount * 100.0).round() as i64;
+    let mut months: BTreeMap<(&str, &str), (usize, i64, i64, i64)> = BTreeMap::new();
+    for sale in sales {
+        let entry = months
+            .entry((sale.month(), sale.currency.as_str()))
+            .or_default();
+        entry.0 += 1;
+        entry.1 += cents(sale.merchandise_value);
+        entry.2 += cents(sale.commission);
+        entry.3 += cents(sale.payment_fee);
+    }
+    months
+        .into_iter()
+        .map(
+            |((month, currency), (orders, merchandise, commission, payment_fee))| MonthlyFees {
+                month: month.to_string(),
+                currency: currency.to_string(),
+                orders,
+                merchandise_value: merchandise as f64 / 100.0,
+                commission: commission as f64 / 100.0,
+                payment_fee: payment_fee as f64 / 100.0,
+            },
+        )
+        .collect()
+}
+
+/// One row per sale with its fees.
+pub fn format_sales_csv(sales: &[SaleFees]) -> String {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let _ = wtr.write_record([
+        "orderId",
+        "date",
+        "currency",
+        "merchandiseValue",
+        "totalValue",
+        "commission",
+        "paymentFee",
+        "totalFees",
+    ]);
+    for sale in sales {
+        let _ = wtr.write_record([
+            sale.order_id.as_str(),
+            &sale.date,
+            &sale.currency,
+            &format!("{:.2}", sale.merchandise_value),
+            &format!("{:.2}", sale.total_value),
+            &format!("{:.2}", sale.commission),
+            &format!("{:.2}", sale.payment_fee),
+            &format!("{:.2}", sale.total_fees()),
+        ]);
+    }
+    String::from_utf8(wtr.into_inner().unwrap_or_default()).unwrap_or_default()
+}
+
+/// One row per month and currency, to compare with the fee vouchers.
+pub fn format_monthly_csv(months: &[MonthlyFees]) -> String {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let _ = wtr.write_record([
+        "month",
+        "currency",
+        "orders",
+        "merchandiseValue",
+        "commission",
+        "paymentFee",
+        "totalFees",
+    ]);
+    for month in months {
+        let _ = wtr.write_record([
+            month.month.as_str(),
+            &month.currency,
+            &month.orders.to_string(),
+            &format!("{:.2}", month.merchandise_value),
+            &format!("{:.2}", month.commission),
+            &format!("{:.2}", month.payment_fee),
+            &format!("{:.2}", month.total_fees()),
+        ]);
+    }
+    String::from_utf8(wtr.into_inner().unwrap_or_default()).unwrap_or_default()
+}
+
+/// Writes `cardmarket_fees.csv` (per sale) and `cardmarket_fees_monthly.csv`
+/// into `dir`. Returns the written files.
+pub fn write_fee_report(dir: &Path, sales: &[SaleFees]) -> Result<Vec<PathBuf>> {
+    let files = [
+        ("cardmarket_fees.csv", format_sales_csv(sales)),
+        (
+            "cardmarket_fees_monthly.csv",
+            format_monthly_csv(&monthly_fees(sales)),
+        ),
+    ];
+    let mut written = Vec::with_capacity(files.len());
+    for (name, contents) in files {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+#[path = "fee_report_tests.rs"]
+mod tests;
@@ -0,0 +1,102 @@
+//! Tests for the Cardmarket fee report.
+
+use super::*;
+
+fn order(order_id: &str, date: &str, total: &str, commission: &str) -> OrderRecord {
+    OrderRecord {
+        order_id: order_id.to_string(),
+        username: "testuser".to_string(),
+        name: "Max Mustermann".to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: date.to_string(),
+        article_count: 1,
+        merchandise_value: total.to_string(),
+        shipment_costs: "0,00".to_string(),
+        total_value: total.to_string(),
+        commission: commission.to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items: Vec::new(),
+    }
+}
+
+#[test]
+fn commission_comes_from_the_export_and_payment_fees_from_settings() {
+    let settings = FeeSettings {
+        payment_fee_percent: 1.0,
+        payment_fee_fixed: 0.35,
+    };
+
+    let fees = sale_fees(
+        &order("1001", "2025-01-15 10:30:00", "20,00", "1,00"),
+        &settings,
+    )
+    .unwrap();
+
+    assert_eq!(fees.date, "2025-01-15");
+    assert_eq!(fees.month(), "2025-01");
+    assert_eq!(fees.commission, 1.0);
+    assert_eq!(fees.payment_fee, 0.55);
+    assert_eq!(fees.total_fees(), 1.55);
+    assert_eq!(FeeSettings::default().payment_fee(20.0), 0.0);
+}
+
+#[test]
+fn unparseable_commission_names_the_order() {
+    let err = sale_fees(
+        &order("1001", "2025-01-15", "20,00", "n/a"),
+        &FeeSettings::default(),
+    )
+    .unwrap_err();
+
+    assert!(format!("{err:#}").contains("Order 1001: invalid commission"));
+}
+
+#[test]
+fn fees_are_summed_per_month_in_cents() {
+    let orders = [
+        order("1", "2025-02-01", "0,10", "0,01"),
+        order("2", "2025-01-31", "0,20", "0,01"),
+        order("3", "2025-02-28", "0,20", "0,02"),
+    ];
+    let sales = fees_for_orders(&orders, &FeeSettings::default()).unwrap();
+
+    let months = monthly_fees(&sales);
+
+    assert_eq!(months.len(), 2);
+    assert_eq!(months[0].month, "2025-01");
+    assert_eq!(months[0].orders, 1);
+    assert_eq!(months[1].month, "2025-02");
+    assert_eq!(months[1].orders, 2);
+    assert_eq!(months[1].merchandise_value, 0.3);
+    assert_eq!(months[1].total_fees(), 0.03);
+}
+
+#[test]
+fn report_writes_sales_and_monthly_csvs() {
+    let sales = fees_for_orders(
+        &[order("1001", "2025-01-15", "20,00", "1,00")],
+        &FeeSettings::default(),
+    )
+    .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let written = write_fee_report(dir.path(), &sales).unwrap();
+
+    assert_eq!(written.len(), 2);
+    let per_sale = std::fs::read_to_string(dir.path().join("cardmarket_fees.csv")).unwrap();
+    assert_eq!(
+        per_sale,
+        "orderId,date,currency,merchandiseValue,totalValue,commission,paymentFee,totalFees\n\
+         1001,2025-01-15,EUR,20.00,20.00,1.00,0.00,1.00\n"
+    );
+    let monthly = std::fs::read_to_string(dir.path().join("cardmarket_fees_monthly.csv")).unwrap();
+    assert!(monthly.ends_with("2025-01,EUR,1,20.00,1.00,0.00,1.00\n"));
+}
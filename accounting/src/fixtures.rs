@@ -0,0 +1,464 @@
+//! Anonymized Cardmarket order CSV generator.
+//!
+//! Produces realistic but entirely made-up order exports in the same
+//! semicolon-separated format Cardmarket uses, so parser bugs can be
+//! reproduced and tested without sharing real customer data. Output is fully
+//! determined by [`FixtureConfig::seed`]: the same config always yields the
+//! same file.
+//!
+//! Money is generated in cents so that merchandise value, shipping, total and
+//! commission always add up exactly like in a real export.
+
+use anyhow::{bail, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+/// Header row of a Cardmarket order export.
+pub const CSV_HEADER: &str = "OrderID;Username;Name;Street;City;Country;IsProfessional;\
+VATNumber;DateOfPurchase;ArticleCount;MerchandiseValue;ShipmentCosts;TotalValue;Commission;\
+Currency;Description;ProductID;LocalizedProductName";
+
+/// Cardmarket's commission on the merchandise value, in percent.
+const COMMISSION_PERCENT: u64 = 5;
+
+/// Unusual-but-valid shapes seen in real exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeCase {
+    /// City field without a postal code (e.g. Ireland before Eircodes).
+    MissingZip,
+    /// Empty street column.
+    MissingStreet,
+    /// Set name containing the " | " item separator.
+    PipeInSetName,
+    /// Business buyer with `IsProfessional` and a VAT number.
+    Professional,
+    /// Names and cities with umlauts, accents and other non-ASCII letters.
+    NonAsciiNames,
+    /// One item bought in a large quantity.
+    BulkQuantity,
+    /// Shipping costs of 0,00.
+    FreeShipping,
+}
+
+impl EdgeCase {
+    pub const ALL: [EdgeCase; 7] = [
+        EdgeCase::MissingZip,
+        EdgeCase::MissingStreet,
+        EdgeCase::PipeInSetName,
+        EdgeCase::Professional,
+        EdgeCase::NonAsciiNames,
+        EdgeCase::BulkQuantity,
+        EdgeCase::FreeShipping,
+    ];
+
+    /// Kebab-case name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EdgeCase::MissingZip => "missing-zip",
+            EdgeCase::MissingStreet => "missing-street",
+            EdgeCase::PipeInSetName => "pipe-in-set-name",
+            EdgeCase::Professional => "professional",
+            EdgeCase::NonAsciiNames => "non-ascii-names",
+            EdgeCase::BulkQuantity => "bulk-quantity",
+            EdgeCase::FreeShipping => "free-shipping",
+        }
+    }
+
+    /// Parses a name as returned by [`EdgeCase::name`].
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL.into_iter().find(|e| e.name() == name)
+    }
+}
+
+/// Settings for [`generate_orders_csv`].
+#[derive(Debug, Clone)]
+pub struct FixtureConfig {
+    pub orders: usize,
+    /// Each order gets between 1 and this many distinct items.
+    pub max_items_per_order: usize,
+    /// Shipping countries, by English name (see [`countries`]).
+    pub countries: Vec<String>,
+    pub edge_cases: Vec<EdgeCase>,
+    /// Share of orders (0.0–1.0) that get one of the enabled edge cases.
+    pub edge_case_rate: f64,
+    pub seed: u64,
+    pub first_order_id: u64,
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            orders: 10,
+            max_items_per_order: 3,
+            countries: vec!["Germany".to_string()],
+            edge_cases: EdgeCase::ALL.to_vec(),
+            edge_case_rate: 0.2,
+            seed: 1,
+            first_order_id: 1_000_000_000,
+        }
+    }
+}
+
+/// Fake address data for one shipping country.
+struct Country {
+    name: &'static str,
+    vat_prefix: &'static str,
+    /// Postal code pattern: `9` becomes a random digit, `A` a random letter.
+    zip_pattern: &'static str,
+    cities: &'static [&'static str],
+    non_ascii_cities: &'static [&'static str],
+    streets: &'static [&'static str],
+}
+
+const COUNTRY_DATA: [Country; 8] = [
+    Country {
+        name: "Germany",
+        vat_prefix: "DE",
+        zip_pattern: "99999",
+        cities: &[
+            "Berlin",
+            "Hamburg",
+            "Frankfurt am Main",
+            "Leipzig",
+            "Bremen",
+        ],
+        non_ascii_cities: &["München", "Köln", "Düsseldorf", "Nürnberg"],
+        streets: &["Hauptstraße", "Bahnhofstraße", "Gartenweg", "Lindenallee"],
+    },
+    Country {
+        name: "Austria",
+        vat_prefix: "ATU",
+        zip_pattern: "9999",
+        cities: &["Wien", "Graz", "Linz", "Salzburg"],
+        non_ascii_cities: &["Sankt Pölten", "Wörgl", "Mödling"],
+        streets: &["Mariahilfer Straße", "Ringstraße", "Kirchengasse"],
+    },
+    Country {
+        name: "France",
+        vat_prefix: "FR",
+        zip_pattern: "99999",
+        cities: &["Paris", "Lyon", "Marseille", "Toulouse", "Lille"],
+        non_ascii_cities: &["Orléans", "Besançon", "Nîmes"],
+        streets: &["Rue de la Paix", "Avenue Victor Hugo", "Boulevard Voltaire"],
+    },
+    Country {
+        name: "Italy",
+        vat_prefix: "IT",
+        zip_pattern: "99999",
+        cities: &["Roma", "Milano", "Torino", "Bologna", "Napoli"],
+        non_ascii_cities: &["Forlì", "Cantù", "Mondovì"],
+        streets: &["Via Roma", "Corso Garibaldi", "Via Dante"],
+    },
+    Country {
+        name: "Spain",
+        vat_prefix: "ES",
+        zip_pattern: "99999",
+        cities: &["Madrid", "Barcelona", "Valencia", "Sevilla"],
+        non_ascii_cities: &["Málaga", "Logroño", "Córdoba"],
+        streets: &["Calle Mayor", "Gran Vía", "Avenida de la Constitución"],
+    },
+    Country {
+        name: "Netherlands",
+        vat_prefix: "NL",
+        zip_pattern: "9999AA",
+        cities: &["Amsterdam", "Rotterdam", "Utrecht", "Eindhoven"],
+        non_ascii_cities: &["Ĳsselstein", "Ĳmuiden"],
+        streets: &["Kerkstraat", "Dorpsstraat", "Molenweg"],
+    },
+    Country {
+        name: "Poland",
+        vat_prefix: "PL",
+        zip_pattern: "99-999",
+        cities: &["Warszawa", "Poznan", "Gdansk"],
+        non_ascii_cities: &["Kraków", "Łódź", "Wrocław"],
+        streets: &["ul. Marszałkowska", "ul. Długa", "ul. Polna"],
+    },
+    Country {
+        name: "United Kingdom",
+        vat_prefix: "GB",
+        zip_pattern: "AA9",
+        cities: &["London", "Manchester", "Leeds", "Bristol"],
+        non_ascii_cities: &["Ynys Môn", "Tŷ Croes"],
+        streets: &["High Street", "Station Road", "Church Lane"],
+    },
+];
+
+const FIRST_NAMES: &[&str] = &[
+    "Anna", "Ben", "Clara", "David", "Emma", "Felix", "Greta", "Hugo", "Ida", "Jonas", "Lena",
+    "Marco", "Nina", "Oskar", "Paula", "Tom",
+];
+const LAST_NAMES: &[&str] = &[
+    "Becker", "Fischer", "Hoffmann", "Klein", "Martin", "Meyer", "Richter", "Rossi", "Schmidt",
+    "Smith", "Wagner", "Weber",
+];
+const NON_ASCII_NAMES: &[&str] = &[
+    "Zoë Müller",
+    "Łukasz Wójcik",
+    "François Lefèvre",
+    "Søren Ærø",
+    "José Núñez",
+    "Åsa Öberg",
+];
+const COMPANY_SUFFIXES: &[&str] = &["GmbH", "Games Ltd", "SARL", "Spiele OG"];
+
+/// (card name, set name, rarity, price range in cents)
+const CARDS: &[(&str, &str, &str, (u64, u64))] = &[
+    (
+        "High Fae Trickster",
+        "Magic: The Gathering Foundations",
+        "Rare",
+        (50, 400),
+    ),
+    ("Lightning Bolt", "Magic 2010", "Common", (80, 300)),
+    (
+        "Counterspell",
+        "Dominaria Remastered",
+        "Uncommon",
+        (50, 250),
+    ),
+    ("Sol Ring", "Commander Masters", "Uncommon", (100, 400)),
+    (
+        "The One Ring",
+        "The Lord of the Rings",
+        "Mythic",
+        (3000, 7000),
+    ),
+    ("Llanowar Elves", "Dominaria", "Common", (10, 60)),
+    (
+        "Sheoldred, the Apocalypse",
+        "Dominaria United",
+        "Mythic",
+        (5000, 9000),
+    ),
+    ("Thoughtseize", "Theros", "Rare", (900, 1800)),
+    ("Arcane Signet", "Commander Legends", "Common", (20, 150)),
+    ("Swords to Plowshares", "Ice Age", "Uncommon", (150, 600)),
+];
+const PIPE_SET_CARD: (&str, &str, &str, (u64, u64)) = (
+    "Spider-Man, Brooklyn Visionary",
+    "Magic: The Gathering | Marvel's Spider-Man",
+    "Rare",
+    (100, 900),
+);
+const CONDITIONS: &[&str] = &["NM", "NM", "NM", "EX", "GD", "LP"];
+const LANGUAGES: &[&str] = &["English", "English", "English", "German", "French"];
+
+/// Deterministic SplitMix64 generator. Good enough for test data and keeps
+/// fixtures reproducible across platforms without pulling in `rand`.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `low..=high`.
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next_u64() % (high - low + 1)
+    }
+
+    /// True with the given probability (0.0 never, 1.0 always).
+    fn chance(&mut self, probability: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < probability
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+
+    fn fill_pattern(&mut self, pattern: &str) -> String {
+        pattern
+            .chars()
+            .map(|c| match c {
+                '9' => char::from(b'0' + self.range(0, 9) as u8),
+                'A' => char::from(b'A' + self.range(0, 25) as u8),
+                other => other,
+            })
+            .collect()
+    }
+}
+
+/// Countries the generator has address data for.
+pub fn countries() -> impl Iterator<Item = &'static str> {
+    COUNTRY_DATA.iter().map(|c| c.name)
+}
+
+/// Formats cents the way Cardmarket does: `1234` → `"12,34"`.
+pub fn format_cents(cents: u64) -> String {
+    format!("{},{:02}", cents / 100, cents % 100)
+}
+
+fn find_country(name: &str) -> Result<&'static Country> {
+    match COUNTRY_DATA
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(name.trim()))
+    {
+        Some(country) => Ok(country),
+        None => bail!(
+            "No fixture data for country '{name}'. Known countries: {}",
+            countries().collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Generates a complete order export (header plus one line per order).
+///
+/// Returns an error for an unknown country or an empty country list.
+pub fn generate_orders_csv(config: &FixtureConfig) -> Result<String> {
+    if config.countries.is_empty() {
+        bail!("At least one country is required");
+    }
+    let countries = config
+        .countries
+        .iter()
+        .map(|name| find_country(name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut rng = Rng(config.seed);
+    let mut purchased_at = NaiveDate::from_ymd_opt(2025, 7, 1)
+        .and_then(|d| d.and_hms_opt(9, 0, 0))
+        .expect("valid start date");
+
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for i in 0..config.orders {
+        purchased_at += Duration::minutes(rng.range(5, 600) as i64);
+        let edge_case = if !config.edge_cases.is_empty() && rng.chance(config.edge_case_rate) {
+            Some(*rng.pick(&config.edge_cases))
+        } else {
+            None
+        };
+        let country = *rng.pick(&countries);
+        let order_id = config.first_order_id + i as u64;
+        csv.push_str(&order_line(
+            &mut rng,
+            config,
+            order_id,
+            country,
+            edge_case,
+            purchased_at,
+        ));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+fn order_line(
+    rng: &mut Rng,
+    config: &FixtureConfig,
+    order_id: u64,
+    country: &Country,
+    edge_case: Option<EdgeCase>,
+    purchased_at: NaiveDateTime,
+) -> String {
+    let is = |case| edge_case == Some(case);
+
+    let (name, username) = if is(EdgeCase::NonAsciiNames) {
+        let name = *rng.pick(NON_ASCII_NAMES);
+        (name.to_string(), format!("player{}", rng.range(100, 99999)))
+    } else {
+        let (first, last) = (*rng.pick(FIRST_NAMES), *rng.pick(LAST_NAMES));
+        let username = format!("{}{}", first.to_lowercase(), rng.range(1, 9999));
+        (format!("{first} {last}"), username)
+    };
+    let (name, is_professional, vat_number) = if is(EdgeCase::Professional) {
+        let company = format!("{} {}", name, rng.pick(COMPANY_SUFFIXES));
+        let vat = format!("{}{}", country.vat_prefix, rng.fill_pattern("999999999"));
+        (company, "yes", vat)
+    } else {
+        (name, "", String::new())
+    };
+
+    let street = if is(EdgeCase::MissingStreet) {
+        String::new()
+    } else {
+        format!("{} {}", rng.pick(country.streets), rng.range(1, 180))
+    };
+    let city_name = if is(EdgeCase::NonAsciiNames) {
+        *rng.pick(country.non_ascii_cities)
+    } else {
+        *rng.pick(country.cities)
+    };
+    let city = if is(EdgeCase::MissingZip) {
+        // Without a postal code the parser takes everything as the city name,
+        // so multi-word cities would come back unchanged — use one word.
+        city_name.split(' ').next().unwrap_or(city_name).to_string()
+    } else {
+        format!("{} {city_name}", rng.fill_pattern(country.zip_pattern))
+    };
+
+    let item_count = rng.range(1, config.max_items_per_order.max(1) as u64) as usize;
+    let mut cards: Vec<_> = Vec::with_capacity(item_count);
+    while cards.len() < item_count.min(CARDS.len()) {
+        let card = *rng.pick(CARDS);
+        if !cards.contains(&card) {
+            cards.push(card);
+        }
+    }
+    if is(EdgeCase::PipeInSetName) {
+        cards[0] = PIPE_SET_CARD;
+    }
+
+    let mut descriptions = Vec::new();
+    let mut product_ids = Vec::new();
+    let mut product_names = Vec::new();
+    let mut article_count = 0;
+    let mut merchandise_cents = 0;
+    for (index, (card, set, rarity, (min_cents, max_cents))) in cards.into_iter().enumerate() {
+        let quantity = if is(EdgeCase::BulkQuantity) && index == 0 {
+            rng.range(8, 40)
+        } else {
+            *rng.pick(&[1, 1, 1, 1, 2, 3, 4])
+        };
+        let price = rng.range(min_cents, max_cents);
+        article_count += quantity;
+        merchandise_cents += quantity * price;
+        descriptions.push(format!(
+            "{quantity}x {card} ({set}) - {} - {rarity} - {} - {} - {} EUR",
+            rng.range(1, 350),
+            rng.pick(CONDITIONS),
+            rng.pick(LANGUAGES),
+            format_cents(price)
+        ));
+        product_ids.push(rng.range(100_000, 899_999).to_string());
+        product_names.push(card);
+    }
+
+    let shipping_cents = if is(EdgeCase::FreeShipping) {
+        0
+    } else {
+        *rng.pick(&[125, 150, 195, 350, 595])
+    };
+    let commission_cents = (merchandise_cents * COMMISSION_PERCENT).div_ceil(100);
+
+    [
+        order_id.to_string(),
+        username,
+        name,
+        street,
+        city,
+        country.name.to_string(),
+        is_professional.to_string(),
+        vat_number,
+        purchased_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        article_count.to_string(),
+        format_cents(merchandise_cents),
+        format_cents(shipping_cents),
+        format_cents(merchandise_cents + shipping_cents),
+        format_cents(commission_cents),
+        "EUR".to_string(),
+        descriptions.join(" | "),
+        product_ids.join(" | "),
+        product_names.join(" | "),
+    ]
+    .join(";")
+}
+
+#[cfg(test)]
+#[path = "fixtures_tests.rs"]
+mod tests;
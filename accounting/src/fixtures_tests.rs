@@ -0,0 +1,196 @@
+//! Tests for fixtures.
+
+use super::*;
+
+fn data_lines(csv: &str) -> Vec<Vec<&str>> {
+    csv.lines()
+        .skip(1)
+        .map(|l| l.split(';').collect())
+        .collect()
+}
+
+fn cents(value: &str) -> u64 {
+    let (euros, cents) = value.split_once(',').unwrap();
+    euros.parse::<u64>().unwrap() * 100 + cents.parse::<u64>().unwrap()
+}
+
+fn config_with(edge_case: EdgeCase) -> FixtureConfig {
+    FixtureConfig {
+        orders: 20,
+        edge_cases: vec![edge_case],
+        edge_case_rate: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn same_seed_gives_same_output() {
+    let config = FixtureConfig::default();
+    assert_eq!(
+        generate_orders_csv(&config).unwrap(),
+        generate_orders_csv(&config).unwrap()
+    );
+
+    let other_seed = FixtureConfig {
+        seed: 2,
+        ..Default::default()
+    };
+    assert_ne!(
+        generate_orders_csv(&config).unwrap(),
+        generate_orders_csv(&other_seed).unwrap()
+    );
+}
+
+#[test]
+fn writes_header_and_requested_number_of_orders() {
+    let config = FixtureConfig {
+        orders: 25,
+        first_order_id: 500,
+        ..Default::default()
+    };
+    let csv = generate_orders_csv(&config).unwrap();
+    assert_eq!(csv.lines().next(), Some(CSV_HEADER));
+
+    let lines = data_lines(&csv);
+    assert_eq!(lines.len(), 25);
+    assert!(lines.iter().all(|l| l.len() == 18));
+    assert_eq!(lines[0][0], "500");
+    assert_eq!(lines[24][0], "524");
+}
+
+#[test]
+fn totals_add_up() {
+    let config = FixtureConfig {
+        orders: 50,
+        max_items_per_order: 5,
+        // Splitting descriptions on " | " is what that edge case breaks
+        edge_cases: EdgeCase::ALL
+            .into_iter()
+            .filter(|&e| e != EdgeCase::PipeInSetName)
+            .collect(),
+        edge_case_rate: 0.5,
+        ..Default::default()
+    };
+    for line in data_lines(&generate_orders_csv(&config).unwrap()) {
+        let merchandise = cents(line[10]);
+        assert_eq!(merchandise + cents(line[11]), cents(line[12]));
+        assert_eq!(cents(line[13]), (merchandise * 5).div_ceil(100));
+
+        let item_total: u64 = line[15]
+            .split(" | ")
+            .map(|d| {
+                let qty: u64 = d.split('x').next().unwrap().parse().unwrap();
+                let price = d.rsplit(" - ").next().unwrap().trim_end_matches(" EUR");
+                qty * cents(price)
+            })
+            .sum();
+        assert_eq!(item_total, merchandise);
+    }
+}
+
+#[test]
+fn respects_max_items_per_order() {
+    let config = FixtureConfig {
+        orders: 30,
+        max_items_per_order: 2,
+        edge_cases: vec![],
+        ..Default::default()
+    };
+    for line in data_lines(&generate_orders_csv(&config).unwrap()) {
+        let ids = line[16].split(" | ").count();
+        assert!((1..=2).contains(&ids));
+        assert_eq!(line[17].split(" | ").count(), ids);
+    }
+}
+
+#[test]
+fn edge_cases_are_applied() {
+    let csv = generate_orders_csv(&config_with(EdgeCase::MissingZip)).unwrap();
+    assert!(data_lines(&csv).iter().all(|l| !l[4].contains(' ')));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::MissingStreet)).unwrap();
+    assert!(data_lines(&csv).iter().all(|l| l[3].is_empty()));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::Professional)).unwrap();
+    assert!(data_lines(&csv)
+        .iter()
+        .all(|l| l[6] == "yes" && l[7].starts_with("DE")));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::FreeShipping)).unwrap();
+    assert!(data_lines(&csv).iter().all(|l| l[11] == "0,00"));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::NonAsciiNames)).unwrap();
+    assert!(data_lines(&csv)
+        .iter()
+        .all(|l| !l[2].is_ascii() && !l[4].is_ascii()));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::PipeInSetName)).unwrap();
+    assert!(data_lines(&csv)
+        .iter()
+        .all(|l| l[15].split(" | ").count() > l[16].split(" | ").count()));
+
+    let csv = generate_orders_csv(&config_with(EdgeCase::BulkQuantity)).unwrap();
+    assert!(data_lines(&csv)
+        .iter()
+        .all(|l| l[9].parse::<u32>().unwrap() >= 8));
+}
+
+#[test]
+fn no_edge_cases_when_rate_is_zero() {
+    let config = FixtureConfig {
+        orders: 50,
+        edge_case_rate: 0.0,
+        ..Default::default()
+    };
+    for line in data_lines(&generate_orders_csv(&config).unwrap()) {
+        assert!(line[4].contains(' '));
+        assert!(!line[3].is_empty());
+        assert!(line[6].is_empty());
+    }
+}
+
+#[test]
+fn uses_only_requested_countries() {
+    let config = FixtureConfig {
+        orders: 40,
+        countries: vec!["france".to_string(), "Poland".to_string()],
+        ..Default::default()
+    };
+    let csv = generate_orders_csv(&config).unwrap();
+    let lines = data_lines(&csv);
+    assert!(lines.iter().all(|l| l[5] == "France" || l[5] == "Poland"));
+    assert!(lines.iter().any(|l| l[5] == "France"));
+    assert!(lines.iter().any(|l| l[5] == "Poland"));
+}
+
+#[test]
+fn rejects_unknown_or_missing_countries() {
+    let unknown = FixtureConfig {
+        countries: vec!["Atlantis".to_string()],
+        ..Default::default()
+    };
+    let err = generate_orders_csv(&unknown).unwrap_err().to_string();
+    assert!(err.contains("Atlantis"));
+    assert!(err.contains("Germany"));
+
+    let none = FixtureConfig {
+        countries: vec![],
+        ..Default::default()
+    };
+    assert!(generate_orders_csv(&none).is_err());
+}
+
+#[test]
+fn edge_case_names_round_trip() {
+    for case in EdgeCase::ALL {
+        assert_eq!(EdgeCase::parse(case.name()), Some(case));
+    }
+    assert_eq!(EdgeCase::parse("nope"), None);
+}
+
+#[test]
+fn formats_cents_with_comma() {
+    assert_eq!(format_cents(0), "0,00");
+    assert_eq!(format_cents(7), "0,07");
+    assert_eq!(format_cents(1234), "12,34");
+}
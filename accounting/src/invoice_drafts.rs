@@ -0,0 +1,83 @@
+//! Invoice drafts for review before submission.
+//!
+//! A draft is everything an invoice will contain (contact, address, texts,
+//! positions and tax), computed without touching SevDesk. The review window
+//! lists one draft per invoice; position texts can be edited there, the
+//! run's workflow steps can be overridden per draft, and drafts can be
+//! excluded before the approved ones are submitted.
+
+use crate::models::{InvoiceLanguage, InvoiceTexts, OrderRecord, TaxMode, WorkflowOverride};
+use crate::order_grouping::{latest_order, OrderGroup};
+use crate::reconciliation::PlannedPosition;
+
+/// One invoice as it would be created.
+#[derive(Debug, Clone)]
+pub struct InvoiceDraft {
+    /// The orders on the invoice; combined invoices have several
+    pub orders: OrderGroup,
+    /// Order IDs filled into the texts, e.g. `1001, 1002`
+    pub reference: String,
+    pub language: InvoiceLanguage,
+    /// Header and texts with the order IDs filled in
+    pub texts: InvoiceTexts,
+    pub tax_mode: TaxMode,
+    /// Planned positions; empty when `error` is set
+    pub positions: Vec<PlannedPosition>,
+    /// Why the positions could not be planned, e.g. a total mismatch
+    pub error: Option<String>,
+    /// Whether the draft is submitted; drafts with an error never are
+    pub included: bool,
+    /// Workflow steps that differ from the run's options for this invoice
+    pub workflow: WorkflowOverride,
+}
+
+impl InvoiceDraft {
+    /// The order the invoice is addressed and dated on.
+    pub fn order(&self) -> &OrderRecord {
+        latest_order(&self.orders)
+    }
+
+    /// Address block as printed on the invoice.
+    pub fn address(&self) -> String {
+        let order = self.order();
+        format!(
+            "{}\n{}\n{} {}",
+            order.name, order.street, order.zip, order.city
+        )
+    }
+
+    /// Sum of the positions, gross.
+    pub fn total_gross(&self) -> f64 {
+        self.positions
+            .iter()
+            .map(PlannedPosition::total_cents)
+            .sum::<i64>() as f64
+            / 100.0
+    }
+
+    /// Net total and VAT, split per position the way SevDesk books them.
+    pub fn net_and_tax(&self) -> (f64, f64) {
+        self.positions.iter().fold((0.0, 0.0), |(net, tax), p| {
+            let (unit_net, unit_tax) = self.tax_mode.split_gross(p.price_gross);
+            (net + unit_net * p.quantity, tax + unit_tax * p.quantity)
+        })
+    }
+
+    /// Whether the draft will be submitted.
+    pub fn is_approved(&self) -> bool {
+        self.included && self.error.is_none()
+    }
+}
+
+/// The drafts to submit, in review order.
+pub fn approved_drafts(drafts: &[InvoiceDraft]) -> Vec<InvoiceDraft> {
+    drafts
+        .iter()
+        .filter(|draft| draft.is_approved())
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "invoice_drafts_tests.rs"]
+mod tests;
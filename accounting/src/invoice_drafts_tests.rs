@@ -0,0 +1,96 @@
+//! Tests for invoice drafts.
+
+use super::*;
+
+fn position(name: &str, quantity: f64, price_gross: f64) -> PlannedPosition {
+    PlannedPosition {
+        name: name.to_string(),
+        description: String::new(),
+        quantity,
+        price_gross,
+    }
+}
+
+fn order() -> OrderRecord {
+    OrderRecord {
+        order_id: "1001".to_string(),
+        username: "testuser".to_string(),
+        name: "Max Mustermann".to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: "2025-01-15".to_string(),
+        article_count: 1,
+        merchandise_value: "1,00".to_string(),
+        shipment_costs: "0,00".to_string(),
+        total_value: "1,00".to_string(),
+        commission: "0,05".to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items: Vec::new(),
+    }
+}
+
+fn draft(tax_mode: TaxMode, positions: Vec<PlannedPosition>) -> InvoiceDraft {
+    let order = order();
+    InvoiceDraft {
+        reference: order.order_id.clone(),
+        orders: vec![order],
+        language: InvoiceLanguage::German,
+        texts: InvoiceTexts::default(),
+        tax_mode,
+        positions,
+        error: None,
+        included: true,
+        workflow: WorkflowOverride::default(),
+    }
+}
+
+#[test]
+fn address_is_taken_from_the_latest_order() {
+    let draft = draft(TaxMode::SmallBusiness, Vec::new());
+
+    assert_eq!(
+        draft.address(),
+        "Max Mustermann\nMain Street 1\n10557 Berlin"
+    );
+}
+
+#[test]
+fn totals_sum_positions_and_split_vat() {
+    let draft = draft(
+        TaxMode::Standard,
+        vec![position("Card", 2.0, 1.19), position("Shipping", 1.0, 2.38)],
+    );
+
+    assert_eq!(draft.total_gross(), 4.76);
+    let (net, tax) = draft.net_and_tax();
+    assert!((net - 4.0).abs() < 1e-9);
+    assert!((tax - 0.76).abs() < 1e-9);
+}
+
+#[test]
+fn small_business_drafts_have_no_vat() {
+    let draft = draft(TaxMode::SmallBusiness, vec![position("Card", 3.0, 0.50)]);
+
+    assert_eq!(draft.net_and_tax(), (1.5, 0.0));
+}
+
+#[test]
+fn only_included_drafts_without_errors_are_approved() {
+    let included = draft(TaxMode::SmallBusiness, vec![position("Card", 1.0, 1.0)]);
+    let mut excluded = included.clone();
+    excluded.included = false;
+    let mut failed = included.clone();
+    failed.error = Some("Total mismatch".to_string());
+
+    let approved = approved_drafts(&[excluded, included, failed]);
+
+    assert_eq!(approved.len(), 1);
+    assert!(approved[0].included && approved[0].error.is_none());
+}
@@ -5,10 +5,33 @@
 
 pub mod app;
 pub mod csv_processor;
+pub mod fee_report;
+pub mod fixtures;
+pub mod invoice_drafts;
 pub mod models;
+pub mod order_board;
+pub mod order_edits;
+pub mod order_grouping;
+pub mod order_risk;
+pub mod pdf_queue;
+pub mod profiles;
+pub mod reconciliation;
 pub mod sevdesk_api;
+pub mod shipping_split;
+pub mod tracking;
 
 pub use app::*;
 pub use csv_processor::*;
+pub use fee_report::*;
+pub use invoice_drafts::*;
 pub use models::*;
+pub use order_board::*;
+pub use order_edits::*;
+pub use order_grouping::*;
+pub use order_risk::*;
+pub use pdf_queue::*;
+pub use profiles::*;
+pub use reconciliation::*;
 pub use sevdesk_api::*;
+pub use shipping_split::*;
+pub use tracking::*;
@@ -1,9 +1,21 @@
 mod app;
 mod csv_processor;
+mod fee_report;
+mod invoice_drafts;
 mod models;
+mod order_board;
+mod order_edits;
+mod order_grouping;
+mod order_risk;
+mod pdf_queue;
+mod profiles;
+mod reconciliation;
 mod sevdesk_api;
+mod shipping_split;
+mod tracking;
 
 use eframe::egui;
+use mtg_common::config::ConfigArgs;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn main() -> Result<(), eframe::Error> {
@@ -16,6 +28,26 @@ fn main() -> Result<(), eframe::Error> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    let config_args = match ConfigArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(2);
+        }
+    };
+    let config = match mtg_common::config::init(config_args.path.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("Failed to load config: {e}");
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    };
+    if config_args.print {
+        print!("{}", config.to_redacted_toml());
+        return Ok(());
+    }
+
     log::info!("Starting SevDesk Invoice Creator");
 
     let options = eframe::NativeOptions {
@@ -121,6 +121,30 @@ pub struct SevDeskInvoice {
     #[serde(rename = "invoiceType")]
     pub invoice_type: String,
     pub currency: String,
+    #[serde(rename = "costCentre", skip_serializing_if = "Option::is_none")]
+    pub cost_centre: Option<SevDeskCostCentre>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SevDeskCostCentre {
+    pub id: u32,
+    #[serde(rename = "objectName")]
+    pub object_name: String,
+}
+
+/// Body of `POST /Tag/Factory/create`, which creates the tag if needed and
+/// attaches it to the object.
+#[derive(Debug, Clone, Serialize)]
+pub struct SevDeskTagRequest {
+    pub name: String,
+    pub object: SevDeskInvoiceRef,
+}
+
+/// Body of `PUT /Invoice/{id}` that only sets the invoice's internal note.
+#[derive(Debug, Clone, Serialize)]
+pub struct SevDeskInvoiceNote {
+    #[serde(rename = "customerInternalNote")]
+    pub customer_internal_note: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -245,6 +269,18 @@ pub struct InvoiceCreationResult {
     pub workflow_status: Option<InvoiceWorkflowStatus>,
 }
 
+/// Outcome of resolving a single order's contact during the "prepare contacts" step
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ContactPreparationResult {
+    pub order_id: String,
+    pub customer_name: String,
+    pub contact_id: Option<u32>,
+    /// The customer had no SevDesk contact yet, so one was created (a new buyer)
+    pub created: bool,
+    pub error: Option<String>,
+}
+
 /// Status of the invoice workflow steps
 #[derive(Debug, Clone, Default)]
 pub struct InvoiceWorkflowStatus {
@@ -279,8 +315,75 @@ pub struct InvoiceWorkflowOptions {
     pub payment_date: Option<String>,
 }
 
+impl InvoiceWorkflowOptions {
+    /// These options with one order's overrides applied. An order that is
+    /// not finalized is neither enshrined nor booked, since both need a
+    /// finalized invoice.
+    pub fn with_override(&self, overrides: &WorkflowOverride) -> Self {
+        let mut options = self.clone();
+        if let Some(finalize) = overrides.finalize {
+            options.finalize = finalize;
+        }
+        if let Some(send_type) = &overrides.send_type {
+            options.send_type = send_type.clone();
+        }
+        if let Some(enshrine) = overrides.enshrine {
+            options.enshrine = enshrine;
+        }
+        if let Some(book) = overrides.book {
+            options.book = book;
+        }
+        if !options.finalize {
+            options.enshrine = false;
+            options.book = false;
+        }
+        options
+    }
+}
+
+/// Workflow steps set for one order in the draft review, e.g. "don't book"
+/// for an unpaid bank transfer. `None` follows the run's options.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalize: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_type: Option<SendType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enshrine: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub book: Option<bool>,
+}
+
+impl WorkflowOverride {
+    /// Whether every step follows the run's options.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Short list of the overridden steps for the review and the log, e.g.
+    /// `no booking, send: Email`.
+    pub fn describe(&self) -> String {
+        let step = |value: Option<bool>, name: &str| {
+            value.map(|on| format!("{} {name}", if on { "with" } else { "no" }))
+        };
+        [
+            step(self.finalize, "finalizing"),
+            self.send_type
+                .as_ref()
+                .map(|t| format!("send: {}", t.description())),
+            step(self.enshrine, "enshrining"),
+            step(self.book, "booking"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
 /// Send type for invoice finalization
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum SendType {
     /// Downloaded as PDF
     #[default]
@@ -317,6 +420,154 @@ impl SendType {
     }
 }
 
+/// VAT treatment of created invoices. Order prices are always gross.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaxMode {
+    /// Kleinunternehmerregelung (§19 UStG): no VAT, SevDesk tax rule 11
+    #[default]
+    SmallBusiness,
+    /// Regular 19% German VAT, SevDesk tax rule 1
+    Standard,
+}
+
+impl TaxMode {
+    pub fn description(&self) -> &'static str {
+        match self {
+            TaxMode::SmallBusiness => "Kleingewerbe (§19 UStG, no VAT)",
+            TaxMode::Standard => "Regular VAT (19%)",
+        }
+    }
+
+    pub fn all() -> &'static [TaxMode] {
+        &[TaxMode::SmallBusiness, TaxMode::Standard]
+    }
+
+    /// VAT rate in percent
+    pub fn rate(&self) -> f64 {
+        match self {
+            TaxMode::SmallBusiness => 0.0,
+            TaxMode::Standard => 19.0,
+        }
+    }
+
+    pub fn tax_rule_id(&self) -> u32 {
+        match self {
+            TaxMode::SmallBusiness => 11,
+            TaxMode::Standard => 1,
+        }
+    }
+
+    pub fn tax_text(&self) -> &'static str {
+        match self {
+            TaxMode::SmallBusiness => "Kleinunternehmerregelung §19 UStG",
+            TaxMode::Standard => "Umsatzsteuer 19%",
+        }
+    }
+
+    /// Splits a gross price into `(net, tax)`, rounded to cents.
+    pub fn split_gross(&self, gross: f64) -> (f64, f64) {
+        let net = (gross / (1.0 + self.rate() / 100.0) * 100.0).round() / 100.0;
+        (net, ((gross - net) * 100.0).round() / 100.0)
+    }
+}
+
+/// Texts printed on every invoice. `{order_id}` is replaced by the
+/// Cardmarket order ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvoiceTexts {
+    pub header: String,
+    pub head_text: String,
+    pub foot_text: String,
+}
+
+impl Default for InvoiceTexts {
+    fn default() -> Self {
+        Self {
+            header: "Rechnung für Bestellnummer {order_id}".to_string(),
+            head_text: "Vielen Dank für Ihre Bestellung.".to_string(),
+            foot_text: "Betrag beglichen.".to_string(),
+        }
+    }
+}
+
+impl InvoiceTexts {
+    /// English defaults for buyers outside the German-speaking countries
+    pub fn english() -> Self {
+        Self {
+            header: "Invoice for order number {order_id}".to_string(),
+            head_text: "Thank you for your order.".to_string(),
+            foot_text: "Amount paid in full.".to_string(),
+        }
+    }
+
+    /// The texts for one order, placeholders filled in
+    pub fn render(&self, order_id: &str) -> InvoiceTexts {
+        let fill = |text: &str| text.replace("{order_id}", order_id);
+        InvoiceTexts {
+            header: fill(&self.header),
+            head_text: fill(&self.head_text),
+            foot_text: fill(&self.foot_text),
+        }
+    }
+}
+
+/// Language the invoice texts are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InvoiceLanguage {
+    German,
+    English,
+}
+
+/// Countries whose buyers get German invoices, in English and German
+/// spelling plus ISO codes. Everyone else gets English ones.
+const GERMAN_SPEAKING_COUNTRIES: &[&str] = &[
+    "germany",
+    "deutschland",
+    "de",
+    "austria",
+    "österreich",
+    "at",
+    "switzerland",
+    "schweiz",
+    "ch",
+    "liechtenstein",
+    "li",
+    "luxembourg",
+    "luxemburg",
+    "lu",
+];
+
+impl InvoiceLanguage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InvoiceLanguage::German => "German",
+            InvoiceLanguage::English => "English",
+        }
+    }
+
+    /// Word for "order" in position texts, e.g. `Bestellung 1001: …`.
+    pub fn order_label(&self) -> &'static str {
+        match self {
+            InvoiceLanguage::German => "Bestellung",
+            InvoiceLanguage::English => "Order",
+        }
+    }
+
+    pub fn all() -> &'static [InvoiceLanguage] {
+        &[InvoiceLanguage::German, InvoiceLanguage::English]
+    }
+
+    /// The buyer's likely language from the shipping country.
+    pub fn for_country(country: &str) -> Self {
+        let country = country.trim().to_lowercase();
+        if GERMAN_SPEAKING_COUNTRIES.contains(&country.as_str()) {
+            InvoiceLanguage::German
+        } else {
+            InvoiceLanguage::English
+        }
+    }
+}
+
 /// Response from /StaticCountry endpoint
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
@@ -331,6 +582,25 @@ pub struct StaticCountryResponse {
     pub priority: Option<String>,
 }
 
+/// Cost centre (Kostenstelle) from `GET /CostCentre`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostCentreResponse {
+    pub id: String,
+    pub name: String,
+}
+
+/// Splits a comma-separated tag list, dropping blanks and repeats
+/// (case-insensitive; the first spelling wins).
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
 /// Response from /CheckAccount endpoint - represents a payment/clearing account
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
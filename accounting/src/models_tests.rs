@@ -1,6 +1,9 @@
 //! Tests for model types used by the SevDesk API.
 
-use crate::models::{InvoiceCreationResult, OrderItem, OrderRecord};
+use crate::models::{
+    parse_tags, InvoiceCreationResult, InvoiceLanguage, InvoiceTexts, InvoiceWorkflowOptions,
+    OrderItem, OrderRecord, SendType, TaxMode, WorkflowOverride,
+};
 
 fn create_test_order() -> OrderRecord {
     OrderRecord {
@@ -107,3 +110,105 @@ fn invoice_creation_result_error() {
     assert!(result.invoice_id.is_none());
     assert!(result.error.is_some());
 }
+
+#[test]
+fn tax_mode_splits_gross_prices() {
+    assert_eq!(TaxMode::SmallBusiness.split_gross(11.90), (11.90, 0.0));
+    assert_eq!(TaxMode::Standard.split_gross(11.90), (10.0, 1.9));
+    assert_eq!(TaxMode::Standard.split_gross(0.25), (0.21, 0.04));
+}
+
+#[test]
+fn invoice_texts_fill_in_order_id() {
+    let texts = InvoiceTexts::default().render("12345");
+    assert_eq!(texts.header, "Rechnung für Bestellnummer 12345");
+    assert_eq!(texts.foot_text, "Betrag beglichen.");
+}
+
+#[test]
+fn invoice_language_follows_the_country() {
+    assert_eq!(
+        InvoiceLanguage::for_country("Germany"),
+        InvoiceLanguage::German
+    );
+    assert_eq!(
+        InvoiceLanguage::for_country(" Österreich "),
+        InvoiceLanguage::German
+    );
+    assert_eq!(InvoiceLanguage::for_country("CH"), InvoiceLanguage::German);
+    assert_eq!(
+        InvoiceLanguage::for_country("France"),
+        InvoiceLanguage::English
+    );
+    assert_eq!(InvoiceLanguage::for_country(""), InvoiceLanguage::English);
+}
+
+#[test]
+fn tags_are_trimmed_and_deduplicated() {
+    assert_eq!(
+        parse_tags(" Cardmarket, MTG,,cardmarket , Singles "),
+        vec!["Cardmarket", "MTG", "Singles"]
+    );
+    assert!(parse_tags(" , ").is_empty());
+}
+
+fn full_workflow() -> InvoiceWorkflowOptions {
+    InvoiceWorkflowOptions {
+        finalize: true,
+        send_type: SendType::Vpdf,
+        enshrine: true,
+        book: true,
+        check_account_id: Some("7".to_string()),
+        ..InvoiceWorkflowOptions::default()
+    }
+}
+
+#[test]
+fn workflow_override_changes_only_the_set_steps() {
+    let run = full_workflow();
+
+    let same = run.with_override(&WorkflowOverride::default());
+    assert!(same.finalize && same.enshrine && same.book);
+    assert_eq!(same.send_type, SendType::Vpdf);
+
+    let unpaid = WorkflowOverride {
+        book: Some(false),
+        send_type: Some(SendType::Vm),
+        ..WorkflowOverride::default()
+    };
+    let options = run.with_override(&unpaid);
+    assert!(options.finalize && options.enshrine && !options.book);
+    assert_eq!(options.send_type, SendType::Vm);
+    assert_eq!(options.check_account_id.as_deref(), Some("7"));
+    assert_eq!(unpaid.describe(), "send: Email, no booking");
+}
+
+#[test]
+fn unfinalized_orders_are_neither_enshrined_nor_booked() {
+    let draft_only = WorkflowOverride {
+        finalize: Some(false),
+        book: Some(true),
+        ..WorkflowOverride::default()
+    };
+
+    let options = full_workflow().with_override(&draft_only);
+
+    assert!(!options.finalize && !options.enshrine && !options.book);
+}
+
+#[test]
+fn workflow_override_stores_only_the_set_steps() {
+    assert!(WorkflowOverride::default().is_empty());
+    let overrides = WorkflowOverride {
+        enshrine: Some(false),
+        ..WorkflowOverride::default()
+    };
+    assert!(!overrides.is_empty());
+
+    let json = serde_json::to_string(&overrides).unwrap();
+    assert_eq!(json, r#"{"enshrine":false}"#);
+    assert_eq!(
+        serde_json::from_str::<WorkflowOverride>(&json).unwrap(),
+        overrides
+    );
+}
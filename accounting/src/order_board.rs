@@ -0,0 +1,347 @@
+//! Local order pipeline board.
+//!
+//! Tracks every imported Cardmarket order through
+//! imported → picked → shipped → invoiced, so shipment state no longer lives
+//! in a spreadsheet. The board is a small JSON file in the user's data
+//! directory; orders are added when a CSV is loaded and marked invoiced once
+//! their SevDesk invoice has been created. Imported tracking numbers (see
+//! [`crate::tracking`]) mark their orders shipped. Everything else is set by
+//! hand, in bulk, from the "Order Board" window.
+//!
+//! Workflow overrides set for an order in the draft review (e.g. "don't
+//! book" for an unpaid bank transfer) are kept on its entry as well, so they
+//! survive a restart and apply whenever the order's invoice is created.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::csv_processor::field_parsers::parse_price;
+use crate::models::{OrderRecord, WorkflowOverride};
+use crate::tracking::TrackingEntry;
+
+/// Where an order is in the fulfilment pipeline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    #[default]
+    Imported,
+    Picked,
+    Shipped,
+    Invoiced,
+}
+
+impl OrderStatus {
+    /// All statuses in pipeline order.
+    pub const ALL: [OrderStatus; 4] = [
+        OrderStatus::Imported,
+        OrderStatus::Picked,
+        OrderStatus::Shipped,
+        OrderStatus::Invoiced,
+    ];
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OrderStatus::Imported => "Imported",
+            OrderStatus::Picked => "Picked",
+            OrderStatus::Shipped => "Shipped",
+            OrderStatus::Invoiced => "Invoiced",
+        })
+    }
+}
+
+/// One order on the board. Only what's needed to recognise the order is
+/// kept; the full record stays in the CSV.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardEntry {
+    pub order_id: String,
+    pub name: String,
+    pub username: String,
+    pub country: String,
+    /// Order total in EUR, 0.0 if the CSV value was unparseable
+    pub total_value: f64,
+    pub date_of_purchase: String,
+    pub status: OrderStatus,
+    /// Date (`YYYY-MM-DD`) of the last status change
+    pub updated_at: String,
+    /// SevDesk invoice, once created
+    #[serde(default)]
+    pub invoice_id: Option<u32>,
+    #[serde(default)]
+    pub invoice_number: Option<String>,
+    /// Parcel tracking number from the Cardmarket export
+    #[serde(default)]
+    pub tracking_number: Option<String>,
+    /// Workflow steps set for this order in the draft review
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_override: Option<WorkflowOverride>,
+}
+
+/// Outcome of [`OrderBoard::attach_tracking`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackingImport {
+    /// Orders whose tracking number was added or changed
+    pub attached: Vec<String>,
+    /// Orders in the export that aren't on the board
+    pub unknown: Vec<String>,
+}
+
+/// Which entries the board window shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardFilter {
+    pub statuses: HashSet<OrderStatus>,
+    /// Case-insensitive match on order ID, name, username or tracking number
+    pub text: String,
+    /// Exact country match; empty shows all countries
+    pub country: String,
+}
+
+impl Default for BoardFilter {
+    fn default() -> Self {
+        Self {
+            statuses: OrderStatus::ALL.into_iter().collect(),
+            text: String::new(),
+            country: String::new(),
+        }
+    }
+}
+
+impl BoardFilter {
+    pub fn matches(&self, entry: &BoardEntry) -> bool {
+        if !self.statuses.contains(&entry.status) {
+            return false;
+        }
+        if !self.country.is_empty() && !entry.country.eq_ignore_ascii_case(&self.country) {
+            return false;
+        }
+        let needle = self.text.trim().to_lowercase();
+        needle.is_empty()
+            || entry.order_id.to_lowercase().contains(&needle)
+            || entry.name.to_lowercase().contains(&needle)
+            || entry.username.to_lowercase().contains(&needle)
+            || entry
+                .tracking_number
+                .as_ref()
+                .is_some_and(|t| t.to_lowercase().contains(&needle))
+    }
+}
+
+/// All tracked orders, keyed by order ID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBoard {
+    pub entries: BTreeMap<String, BoardEntry>,
+}
+
+impl OrderBoard {
+    /// Default board file: `<data dir>/sevdesk_invoicing/order_board.json`
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("sevdesk_invoicing")
+            .join("order_board.json")
+    }
+
+    /// Loads the board; a missing file is an empty board.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds orders not on the board yet as `Imported`; orders already tracked
+    /// keep their status. Returns how many were added.
+    pub fn import_orders(&mut self, orders: &[OrderRecord]) -> usize {
+        let today = today();
+        let mut added = 0;
+        for order in orders {
+            if self.entries.contains_key(&order.order_id) {
+                continue;
+            }
+            self.entries.insert(
+                order.order_id.clone(),
+                BoardEntry {
+                    order_id: order.order_id.clone(),
+                    name: order.name.clone(),
+                    username: order.username.clone(),
+                    country: order.country.clone(),
+                    total_value: parse_price(&order.total_value).unwrap_or(0.0),
+                    date_of_purchase: order.date_of_purchase.clone(),
+                    status: OrderStatus::Imported,
+                    updated_at: today.clone(),
+                    invoice_id: None,
+                    invoice_number: None,
+                    tracking_number: None,
+                    workflow_override: None,
+                },
+            );
+            added += 1;
+        }
+        added
+    }
+
+    /// Sets the status of every listed order that is on the board. Returns
+    /// how many entries changed.
+    pub fn set_status<'a>(
+        &mut self,
+        order_ids: impl IntoIterator<Item = &'a str>,
+        status: OrderStatus,
+    ) -> usize {
+        let today = today();
+        let mut changed = 0;
+        for id in order_ids {
+            if let Some(entry) = self.entries.get_mut(id) {
+                if entry.status != status {
+                    entry.status = status;
+                    entry.updated_at = today.clone();
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Marks the orders of an invoice as invoiced and remembers the invoice.
+    /// `reference` is the order ID, or the comma-separated IDs of a combined
+    /// invoice. Returns how many entries were on the board.
+    pub fn record_invoice(
+        &mut self,
+        reference: &str,
+        invoice_id: u32,
+        invoice_number: Option<&str>,
+    ) -> usize {
+        let today = today();
+        let mut recorded = 0;
+        for id in reference.split(',').map(str::trim) {
+            if let Some(entry) = self.entries.get_mut(id) {
+                if entry.status != OrderStatus::Invoiced {
+                    entry.status = OrderStatus::Invoiced;
+                    entry.updated_at = today.clone();
+                }
+                entry.invoice_id = Some(invoice_id);
+                entry.invoice_number = invoice_number.map(str::to_string);
+                recorded += 1;
+            }
+        }
+        recorded
+    }
+
+    /// Stores the workflow override of an invoice on each of its orders that
+    /// is on the board; an empty override is removed. Returns how many
+    /// entries changed.
+    pub fn set_workflow_override<'a>(
+        &mut self,
+        order_ids: impl IntoIterator<Item = &'a str>,
+        overrides: &WorkflowOverride,
+    ) -> usize {
+        let stored = (!overrides.is_empty()).then(|| overrides.clone());
+        let mut changed = 0;
+        for id in order_ids {
+            if let Some(entry) = self.entries.get_mut(id) {
+                if entry.workflow_override != stored {
+                    entry.workflow_override = stored.clone();
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// The workflow override of an invoice: the first one stored on any of
+    /// its orders, or an empty one.
+    pub fn workflow_override<'a>(
+        &self,
+        order_ids: impl IntoIterator<Item = &'a str>,
+    ) -> WorkflowOverride {
+        order_ids
+            .into_iter()
+            .filter_map(|id| self.entries.get(id)?.workflow_override.clone())
+            .next()
+            .unwrap_or_default()
+    }
+
+    /// Stores tracking numbers on their orders. Orders not yet shipped are
+    /// marked `Shipped`; invoiced ones keep their status.
+    pub fn attach_tracking(&mut self, tracking: &[TrackingEntry]) -> TrackingImport {
+        let today = today();
+        let mut import = TrackingImport::default();
+        for item in tracking {
+            let Some(entry) = self.entries.get_mut(&item.order_id) else {
+                import.unknown.push(item.order_id.clone());
+                continue;
+            };
+            let mut changed = false;
+            if entry.tracking_number.as_deref() != Some(item.tracking_number.as_str()) {
+                entry.tracking_number = Some(item.tracking_number.clone());
+                import.attached.push(item.order_id.clone());
+                changed = true;
+            }
+            if matches!(entry.status, OrderStatus::Imported | OrderStatus::Picked) {
+                entry.status = OrderStatus::Shipped;
+                changed = true;
+            }
+            if changed {
+                entry.updated_at = today.clone();
+            }
+        }
+        import
+    }
+
+    /// Entries matching the filter, newest purchase first.
+    pub fn filtered(&self, filter: &BoardFilter) -> Vec<&BoardEntry> {
+        let mut entries: Vec<&BoardEntry> = self
+            .entries
+            .values()
+            .filter(|e| filter.matches(e))
+            .collect();
+        entries.sort_by(|a, b| {
+            b.date_of_purchase
+                .cmp(&a.date_of_purchase)
+                .then_with(|| a.order_id.cmp(&b.order_id))
+        });
+        entries
+    }
+
+    /// Number of entries per status, in pipeline order.
+    pub fn counts(&self) -> [(OrderStatus, usize); 4] {
+        OrderStatus::ALL.map(|status| {
+            let count = self.entries.values().filter(|e| e.status == status).count();
+            (status, count)
+        })
+    }
+
+    /// Distinct shipping countries, sorted, for the country filter.
+    pub fn countries(&self) -> Vec<String> {
+        let mut countries: Vec<String> = self
+            .entries
+            .values()
+            .map(|e| e.country.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        countries.sort();
+        countries
+    }
+}
+
+/// Status dates are market days, like every other date the tools store.
+fn today() -> String {
+    mtg_common::dates::today_date()
+}
+
+#[cfg(test)]
+#[path = "order_board_tests.rs"]
+mod tests;
@@ -0,0 +1,244 @@
+//! Tests for the order pipeline board.
+
+use super::*;
+
+fn create_test_order(order_id: &str, name: &str, country: &str, date: &str) -> OrderRecord {
+    OrderRecord {
+        order_id: order_id.to_string(),
+        username: format!("{}_user", name.to_lowercase()),
+        name: name.to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: country.to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: date.to_string(),
+        article_count: 1,
+        merchandise_value: "10,00".to_string(),
+        shipment_costs: "1,50".to_string(),
+        total_value: "11,50".to_string(),
+        commission: "0,50".to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items: Vec::new(),
+    }
+}
+
+fn sample_board() -> OrderBoard {
+    let mut board = OrderBoard::default();
+    board.import_orders(&[
+        create_test_order("1001", "Alice", "Germany", "2025-01-15"),
+        create_test_order("1002", "Bob", "France", "2025-01-16"),
+        create_test_order("1003", "Carol", "Germany", "2025-01-14"),
+    ]);
+    board
+}
+
+#[test]
+fn import_adds_new_orders_as_imported() {
+    let board = sample_board();
+    assert_eq!(board.entries.len(), 3);
+    let entry = &board.entries["1001"];
+    assert_eq!(entry.status, OrderStatus::Imported);
+    assert_eq!(entry.name, "Alice");
+    assert!((entry.total_value - 11.5).abs() < 1e-9);
+}
+
+#[test]
+fn reimport_keeps_existing_status() {
+    let mut board = sample_board();
+    board.set_status(["1001"], OrderStatus::Shipped);
+
+    let added = board.import_orders(&[
+        create_test_order("1001", "Alice", "Germany", "2025-01-15"),
+        create_test_order("1004", "Dave", "Spain", "2025-01-17"),
+    ]);
+
+    assert_eq!(added, 1);
+    assert_eq!(board.entries["1001"].status, OrderStatus::Shipped);
+    assert_eq!(board.entries["1004"].status, OrderStatus::Imported);
+}
+
+#[test]
+fn bulk_status_change_counts_only_changed_known_orders() {
+    let mut board = sample_board();
+    board.set_status(["1002"], OrderStatus::Picked);
+
+    let changed = board.set_status(["1001", "1002", "9999"], OrderStatus::Picked);
+
+    assert_eq!(changed, 1);
+    assert_eq!(board.entries["1001"].status, OrderStatus::Picked);
+    assert_eq!(board.entries["1003"].status, OrderStatus::Imported);
+}
+
+#[test]
+fn filter_by_status_country_and_text() {
+    let mut board = sample_board();
+    board.set_status(["1003"], OrderStatus::Shipped);
+
+    let mut filter = BoardFilter::default();
+    filter.statuses.remove(&OrderStatus::Shipped);
+    let ids: Vec<&str> = board
+        .filtered(&filter)
+        .iter()
+        .map(|e| e.order_id.as_str())
+        .collect();
+    assert_eq!(ids, ["1002", "1001"], "newest purchase first");
+
+    let filter = BoardFilter {
+        country: "germany".to_string(),
+        ..BoardFilter::default()
+    };
+    assert_eq!(board.filtered(&filter).len(), 2);
+
+    let filter = BoardFilter {
+        text: "BOB".to_string(),
+        ..BoardFilter::default()
+    };
+    let matches = board.filtered(&filter);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].order_id, "1002");
+}
+
+#[test]
+fn counts_follow_pipeline_order() {
+    let mut board = sample_board();
+    board.set_status(["1001", "1002"], OrderStatus::Invoiced);
+
+    assert_eq!(
+        board.counts(),
+        [
+            (OrderStatus::Imported, 1),
+            (OrderStatus::Picked, 0),
+            (OrderStatus::Shipped, 0),
+            (OrderStatus::Invoiced, 2),
+        ]
+    );
+    assert_eq!(board.countries(), ["France", "Germany"]);
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("order_board.json");
+    let mut board = sample_board();
+    board.set_status(["1002"], OrderStatus::Shipped);
+
+    board.save(&path).unwrap();
+    let loaded = OrderBoard::load(&path).unwrap();
+
+    assert_eq!(loaded, board);
+    let json = std::fs::read_to_string(&path).unwrap();
+    assert!(json.contains("\"shipped\""));
+}
+
+#[test]
+fn missing_board_file_loads_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let board = OrderBoard::load(&dir.path().join("absent.json")).unwrap();
+    assert!(board.entries.is_empty());
+}
+
+#[test]
+fn combined_invoice_marks_every_order() {
+    let mut board = sample_board();
+
+    let recorded = board.record_invoice("1001, 1003, 9999", 42, Some("RE-100"));
+
+    assert_eq!(recorded, 2);
+    for id in ["1001", "1003"] {
+        assert_eq!(board.entries[id].status, OrderStatus::Invoiced);
+        assert_eq!(board.entries[id].invoice_id, Some(42));
+        assert_eq!(board.entries[id].invoice_number.as_deref(), Some("RE-100"));
+    }
+    assert_eq!(board.entries["1002"].invoice_id, None);
+}
+
+#[test]
+fn workflow_overrides_are_stored_per_order_and_survive_a_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("order_board.json");
+    let mut board = sample_board();
+    let no_booking = WorkflowOverride {
+        book: Some(false),
+        ..WorkflowOverride::default()
+    };
+
+    assert_eq!(
+        board.set_workflow_override(["1001", "1003", "9999"], &no_booking),
+        2
+    );
+    assert_eq!(board.set_workflow_override(["1001"], &no_booking), 0);
+    board.save(&path).unwrap();
+    let mut board = OrderBoard::load(&path).unwrap();
+
+    assert_eq!(board.workflow_override(["1002", "1003"]), no_booking);
+    assert!(board.workflow_override(["1002"]).is_empty());
+
+    // Back to the run's options: nothing is stored any more
+    board.set_workflow_override(["1001", "1003"], &WorkflowOverride::default());
+    assert_eq!(board.entries["1001"].workflow_override, None);
+    board.save(&path).unwrap();
+    assert!(!std::fs::read_to_string(&path)
+        .unwrap()
+        .contains("workflow_override"));
+}
+
+#[test]
+fn tracking_numbers_mark_orders_shipped_and_are_searchable() {
+    let mut board = sample_board();
+    board.record_invoice("1002", 7, None);
+    let tracking = |order_id: &str, number: &str| TrackingEntry {
+        order_id: order_id.to_string(),
+        tracking_number: number.to_string(),
+        carrier: None,
+    };
+
+    let import = board.attach_tracking(&[
+        tracking("1001", "RR111DE"),
+        tracking("1002", "RR222DE"),
+        tracking("4711", "RR333DE"),
+    ]);
+
+    assert_eq!(import.attached, ["1001", "1002"]);
+    assert_eq!(import.unknown, ["4711"]);
+    assert_eq!(board.entries["1001"].status, OrderStatus::Shipped);
+    assert_eq!(board.entries["1002"].status, OrderStatus::Invoiced);
+
+    // Importing the same export again changes nothing
+    let again = board.attach_tracking(&[tracking("1001", "RR111DE")]);
+    assert!(again.attached.is_empty());
+
+    let filter = BoardFilter {
+        text: "rr222".to_string(),
+        ..BoardFilter::default()
+    };
+    let found: Vec<&str> = board
+        .filtered(&filter)
+        .iter()
+        .map(|e| e.order_id.as_str())
+        .collect();
+    assert_eq!(found, ["1002"]);
+}
+
+#[test]
+fn boards_saved_before_tracking_still_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("order_board.json");
+    std::fs::write(
+        &path,
+        r#"{"entries":{"1001":{"order_id":"1001","name":"Alice","username":"alice",
+        "country":"Germany","total_value":11.5,"date_of_purchase":"2025-01-15",
+        "status":"shipped","updated_at":"2025-01-16"}}}"#,
+    )
+    .unwrap();
+
+    let board = OrderBoard::load(&path).unwrap();
+
+    assert_eq!(board.entries["1001"].tracking_number, None);
+    assert_eq!(board.entries["1001"].invoice_id, None);
+    assert_eq!(board.entries["1001"].workflow_override, None);
+}
@@ -0,0 +1,207 @@
+//! Corrections of loaded orders before invoicing.
+//!
+//! The order preview edits the fields that end up on the invoice — buyer
+//! name, address, VAT number and the order totals — without touching the
+//! CSV. An [`OrderEdit`] is a working copy of those fields; saving it
+//! validates the copy and writes it back into the loaded order.
+//! [`OrderEdits`] remembers every changed order as loaded from the CSV, so
+//! the preview can flag modified orders and revert them.
+
+use std::collections::HashMap;
+
+use crate::csv_processor::field_parsers::parse_price;
+use crate::models::OrderRecord;
+
+/// Working copy of an order's editable fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderEdit {
+    pub order_id: String,
+    pub name: String,
+    pub street: String,
+    pub zip: String,
+    pub city: String,
+    /// Empty for orders without a VAT number
+    pub vat_number: String,
+    pub merchandise_value: String,
+    pub shipment_costs: String,
+    pub total_value: String,
+}
+
+impl OrderEdit {
+    pub fn new(order: &OrderRecord) -> Self {
+        Self {
+            order_id: order.order_id.clone(),
+            name: order.name.clone(),
+            street: order.street.clone(),
+            zip: order.zip.clone(),
+            city: order.city.clone(),
+            vat_number: order.vat_number.clone().unwrap_or_default(),
+            merchandise_value: order.merchandise_value.clone(),
+            shipment_costs: order.shipment_costs.clone(),
+            total_value: order.total_value.clone(),
+        }
+    }
+
+    /// Everything that keeps the edit from being saved; empty when it is
+    /// valid.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.name.trim().is_empty() {
+            errors.push("Name must not be empty".to_string());
+        }
+        if !self.vat_number.trim().is_empty() && !is_valid_vat_number(&self.vat_number) {
+            errors.push(format!(
+                "VAT number {} must be a country code followed by 2 to 13 letters or digits",
+                self.vat_number.trim()
+            ));
+        }
+
+        let amount = |label: &str, value: &str, errors: &mut Vec<String>| {
+            let parsed = parse_price(value).ok();
+            if parsed.is_none() {
+                errors.push(format!("{label} is not an amount: {:?}", value.trim()));
+            }
+            parsed.map(|v| (v * 100.0).round() as i64)
+        };
+        let merchandise = amount("Merchandise value", &self.merchandise_value, &mut errors);
+        let shipping = amount("Shipping costs", &self.shipment_costs, &mut errors);
+        let total = amount("Total", &self.total_value, &mut errors);
+        if let (Some(merchandise), Some(shipping), Some(total)) = (merchandise, shipping, total) {
+            if merchandise + shipping != total {
+                errors.push(format!(
+                    "Total {:.2} does not equal merchandise value {:.2} plus shipping {:.2}",
+                    total as f64 / 100.0,
+                    merchandise as f64 / 100.0,
+                    shipping as f64 / 100.0
+                ));
+            }
+        }
+        errors
+    }
+
+    /// Writes the (trimmed) fields into `order`.
+    fn apply(&self, order: &mut OrderRecord) {
+        order.name = self.name.trim().to_string();
+        order.street = self.street.trim().to_string();
+        order.zip = self.zip.trim().to_string();
+        order.city = self.city.trim().to_string();
+        let vat_number = normalize_vat_number(&self.vat_number);
+        order.vat_number = (!vat_number.is_empty()).then_some(vat_number);
+        order.merchandise_value = self.merchandise_value.trim().to_string();
+        order.shipment_costs = self.shipment_costs.trim().to_string();
+        order.total_value = self.total_value.trim().to_string();
+    }
+}
+
+/// Upper-case VAT number without the spaces, dots and dashes it is often
+/// written with.
+fn normalize_vat_number(vat_number: &str) -> String {
+    vat_number
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '.' | '-'))
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Whether `vat_number` looks like an EU-style VAT number: a two-letter
+/// country code followed by 2 to 13 letters or digits. The check digits are
+/// not verified.
+pub fn is_valid_vat_number(vat_number: &str) -> bool {
+    let normalized = normalize_vat_number(vat_number);
+    let (prefix, rest) = normalized.split_at(normalized.len().min(2));
+    prefix.len() == 2
+        && prefix.chars().all(|c| c.is_ascii_uppercase())
+        && (2..=13).contains(&rest.len())
+        && rest.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The order being edited and the CSV state of every modified order.
+#[derive(Debug, Default)]
+pub struct OrderEdits {
+    /// Working copy shown in the edit form, if any
+    pub editing: Option<OrderEdit>,
+    /// Modified orders as loaded from the CSV, by order ID
+    originals: HashMap<String, OrderRecord>,
+}
+
+impl OrderEdits {
+    /// Opens the edit form for `order`.
+    pub fn start(&mut self, order: &OrderRecord) {
+        self.editing = Some(OrderEdit::new(order));
+    }
+
+    /// Validates the open edit and writes it into its order in `orders`.
+    /// Returns whether the order changed; on validation errors the form stays
+    /// open.
+    pub fn save(&mut self, orders: &mut [OrderRecord]) -> Result<bool, Vec<String>> {
+        let Some(edit) = self.editing.take() else {
+            return Ok(false);
+        };
+        let errors = edit.validate();
+        if !errors.is_empty() {
+            self.editing = Some(edit);
+            return Err(errors);
+        }
+        let Some(order) = orders.iter_mut().find(|o| o.order_id == edit.order_id) else {
+            return Ok(false);
+        };
+        let before = OrderEdit::new(order);
+        self.originals
+            .entry(order.order_id.clone())
+            .or_insert_with(|| order.clone());
+        edit.apply(order);
+        let after = OrderEdit::new(order);
+        // Edited back to the CSV values: no longer modified
+        if self
+            .originals
+            .get(&order.order_id)
+            .is_some_and(|original| OrderEdit::new(original) == after)
+        {
+            self.originals.remove(&order.order_id);
+        }
+        Ok(before != after)
+    }
+
+    /// Closes the edit form without saving.
+    pub fn cancel(&mut self) {
+        self.editing = None;
+    }
+
+    /// Restores the CSV values of a modified order. Returns whether there was
+    /// anything to restore.
+    pub fn revert(&mut self, order_id: &str, orders: &mut [OrderRecord]) -> bool {
+        let Some(original) = self.originals.remove(order_id) else {
+            return false;
+        };
+        if let Some(order) = orders.iter_mut().find(|o| o.order_id == order_id) {
+            *order = original;
+        }
+        if self
+            .editing
+            .as_ref()
+            .is_some_and(|e| e.order_id == order_id)
+        {
+            self.editing = None;
+        }
+        true
+    }
+
+    /// Whether the order differs from the CSV.
+    pub fn is_modified(&self, order_id: &str) -> bool {
+        self.originals.contains_key(order_id)
+    }
+
+    /// Number of orders that differ from the CSV.
+    pub fn modified_count(&self) -> usize {
+        self.originals.len()
+    }
+
+    /// Forgets all edits, e.g. when another CSV is loaded.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+#[path = "order_edits_tests.rs"]
+mod tests;
@@ -0,0 +1,119 @@
+//! Tests for order edits.
+
+use super::*;
+
+fn order() -> OrderRecord {
+    OrderRecord {
+        order_id: "1001".to_string(),
+        username: "testuser".to_string(),
+        name: "Max Mustermann".to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: "2025-01-15".to_string(),
+        article_count: 1,
+        merchandise_value: "10,00".to_string(),
+        shipment_costs: "1,50".to_string(),
+        total_value: "11,50".to_string(),
+        commission: "0,50".to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items: Vec::new(),
+    }
+}
+
+#[test]
+fn unchanged_order_is_valid() {
+    assert!(OrderEdit::new(&order()).validate().is_empty());
+}
+
+#[test]
+fn validation_reports_every_problem() {
+    let mut edit = OrderEdit::new(&order());
+    edit.name = "  ".to_string();
+    edit.vat_number = "123".to_string();
+    edit.shipment_costs = "free".to_string();
+    let errors = edit.validate();
+    assert_eq!(errors.len(), 3, "{errors:?}");
+    assert!(errors[2].contains("Shipping costs"));
+
+    let mut edit = OrderEdit::new(&order());
+    edit.total_value = "12,50".to_string();
+    assert_eq!(
+        edit.validate(),
+        vec!["Total 12.50 does not equal merchandise value 10.00 plus shipping 1.50"]
+    );
+}
+
+#[test]
+fn vat_numbers_need_a_country_code() {
+    assert!(is_valid_vat_number("DE123456789"));
+    assert!(is_valid_vat_number("fr 12.345-678 901"));
+    assert!(is_valid_vat_number("ATU12345678"));
+    assert!(!is_valid_vat_number("123456789"));
+    assert!(!is_valid_vat_number("DE1"));
+    assert!(!is_valid_vat_number("DE12345678901234"));
+    assert!(!is_valid_vat_number("D"));
+}
+
+#[test]
+fn saving_applies_the_edit_and_marks_the_order() {
+    let mut orders = vec![order()];
+    let mut edits = OrderEdits::default();
+    edits.start(&orders[0]);
+    let edit = edits.editing.as_mut().unwrap();
+    edit.name = " Max Musterman ".to_string();
+    edit.vat_number = "de 123 456 789".to_string();
+
+    assert_eq!(edits.save(&mut orders), Ok(true));
+
+    assert!(edits.editing.is_none());
+    assert_eq!(orders[0].name, "Max Musterman");
+    assert_eq!(orders[0].vat_number.as_deref(), Some("DE123456789"));
+    assert!(edits.is_modified("1001"));
+    assert_eq!(edits.modified_count(), 1);
+
+    assert!(edits.revert("1001", &mut orders));
+    assert_eq!(orders[0].name, "Max Mustermann");
+    assert_eq!(orders[0].vat_number, None);
+    assert!(!edits.is_modified("1001"));
+}
+
+#[test]
+fn invalid_edit_stays_open() {
+    let mut orders = vec![order()];
+    let mut edits = OrderEdits::default();
+    edits.start(&orders[0]);
+    edits.editing.as_mut().unwrap().total_value = "abc".to_string();
+
+    assert!(edits.save(&mut orders).is_err());
+
+    assert!(edits.editing.is_some());
+    assert_eq!(orders[0].total_value, "11,50");
+    assert!(!edits.is_modified("1001"));
+}
+
+#[test]
+fn editing_back_to_the_csv_values_clears_the_flag() {
+    let mut orders = vec![order()];
+    let mut edits = OrderEdits::default();
+    edits.start(&orders[0]);
+    edits.editing.as_mut().unwrap().city = "Hamburg".to_string();
+    edits.save(&mut orders).unwrap();
+    assert!(edits.is_modified("1001"));
+
+    edits.start(&orders[0]);
+    edits.editing.as_mut().unwrap().city = "Berlin".to_string();
+    assert_eq!(edits.save(&mut orders), Ok(true));
+    assert!(!edits.is_modified("1001"));
+
+    // Saving without changes is not a modification
+    edits.start(&orders[0]);
+    assert_eq!(edits.save(&mut orders), Ok(false));
+    assert!(!edits.is_modified("1001"));
+}
@@ -0,0 +1,111 @@
+//! Combining a buyer's orders into one invoice.
+//!
+//! Buyers sometimes place several orders on one day that ship together. With
+//! grouping switched on, orders with the same username and shipping address
+//! (and currency) are invoiced once: every order keeps its own section of
+//! positions, reconciled against its own total, and each position text names
+//! the order it belongs to. The invoice is dated on the latest order.
+
+use anyhow::Result;
+use mtg_common::DecimalLocale;
+
+use crate::models::{InvoiceLanguage, OrderRecord};
+use crate::reconciliation::{reconciled_positions, PlannedPosition, ReconciliationSettings};
+
+/// Orders that go on one invoice, in CSV order.
+pub type OrderGroup = Vec<OrderRecord>;
+
+/// Identifies a buyer, shipping address and purchase day; case and
+/// surrounding whitespace are ignored so re-typed addresses still match.
+fn buyer_key(order: &OrderRecord) -> Vec<String> {
+    let purchase_day = order.date_of_purchase.split(' ').next().unwrap_or_default();
+    [
+        purchase_day,
+        &order.username,
+        &order.name,
+        &order.street,
+        &order.zip,
+        &order.city,
+        &order.country,
+        &order.currency,
+    ]
+    .iter()
+    .map(|field| field.trim().to_lowercase())
+    .collect()
+}
+
+/// Groups orders by buyer, address and purchase day. Groups appear in the order of their
+/// first order; orders keep their CSV order within a group.
+pub fn group_orders_by_buyer(orders: &[OrderRecord]) -> Vec<OrderGroup> {
+    let mut keys: Vec<Vec<String>> = Vec::new();
+    let mut groups: Vec<OrderGroup> = Vec::new();
+    for order in orders {
+        let key = buyer_key(order);
+        match keys.iter().position(|k| *k == key) {
+            Some(idx) => groups[idx].push(order.clone()),
+            None => {
+                keys.push(key);
+                groups.push(vec![order.clone()]);
+            }
+        }
+    }
+    groups
+}
+
+/// One group per order, for runs without grouping.
+pub fn single_order_groups(orders: &[OrderRecord]) -> Vec<OrderGroup> {
+    orders.iter().map(|order| vec![order.clone()]).collect()
+}
+
+/// The group's latest order by purchase date; its date and address are used
+/// for the invoice.
+pub fn latest_order(group: &[OrderRecord]) -> &OrderRecord {
+    group
+        .iter()
+        .max_by(|a, b| a.date_of_purchase.cmp(&b.date_of_purchase))
+        .expect("order groups are never empty")
+}
+
+/// The group's order ids joined for invoice texts, e.g. `1001, 1002`.
+pub fn order_reference(group: &[OrderRecord]) -> String {
+    group
+        .iter()
+        .map(|order| order.order_id.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Positions for a combined invoice: each order's reconciled positions in
+/// turn, with the order id in front of every position text, labelled in the
+/// invoice's `language`. A single order gets its positions unchanged.
+pub fn combined_positions(
+    group: &[OrderRecord],
+    settings: &ReconciliationSettings,
+    locale: DecimalLocale,
+    language: InvoiceLanguage,
+) -> Result<Vec<PlannedPosition>> {
+    if let [order] = group {
+        return reconciled_positions(order, settings, locale);
+    }
+    let mut positions = Vec::new();
+    for order in group {
+        positions.extend(
+            reconciled_positions(order, settings, locale)?
+                .into_iter()
+                .map(|position| PlannedPosition {
+                    description: format!(
+                        "{} {}: {}",
+                        language.order_label(),
+                        order.order_id,
+                        position.description
+                    ),
+                    ..position
+                }),
+        );
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+#[path = "order_grouping_tests.rs"]
+mod tests;
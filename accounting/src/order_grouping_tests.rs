@@ -0,0 +1,187 @@
+//! Tests for order grouping.
+
+use super::*;
+use crate::models::OrderItem;
+
+fn create_test_order(order_id: &str, username: &str, street: &str, date: &str) -> OrderRecord {
+    OrderRecord {
+        order_id: order_id.to_string(),
+        username: username.to_string(),
+        name: "John Doe".to_string(),
+        street: street.to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: date.to_string(),
+        article_count: 1,
+        merchandise_value: "5,00".to_string(),
+        shipment_costs: "1,25".to_string(),
+        total_value: "6,25".to_string(),
+        commission: "0,25".to_string(),
+        currency: "EUR".to_string(),
+        description: "1x Test Card - 5,00 EUR".to_string(),
+        product_id: "1".to_string(),
+        localized_product_name: "Test Card".to_string(),
+        items: vec![OrderItem {
+            description: "1x Test Card - 5,00 EUR".to_string(),
+            product_id: "1".to_string(),
+            localized_product_name: "Test Card".to_string(),
+            price: 5.0,
+            quantity: 1,
+        }],
+    }
+}
+
+fn ids(group: &[OrderRecord]) -> Vec<&str> {
+    group.iter().map(|o| o.order_id.as_str()).collect()
+}
+
+#[test]
+fn same_buyer_and_address_share_a_group() {
+    let orders = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        create_test_order("2", "jane", "Other Road 5", "2025-01-15 11:00:00"),
+        create_test_order("3", "John ", "main street 1", "2025-01-15 12:00:00"),
+    ];
+
+    let groups = group_orders_by_buyer(&orders);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(ids(&groups[0]), vec!["1", "3"]);
+    assert_eq!(ids(&groups[1]), vec!["2"]);
+}
+
+#[test]
+fn different_address_keeps_orders_apart() {
+    let orders = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        create_test_order("2", "john", "Holiday Lane 9", "2025-01-15 11:00:00"),
+    ];
+
+    assert_eq!(group_orders_by_buyer(&orders).len(), 2);
+}
+
+#[test]
+fn different_purchase_days_keep_orders_apart() {
+    let orders = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        create_test_order("2", "john", "Main Street 1", "2025-01-16 09:00:00"),
+        create_test_order("3", "john", "Main Street 1", "2025-01-15 22:00:00"),
+    ];
+
+    let groups = group_orders_by_buyer(&orders);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(ids(&groups[0]), vec!["1", "3"]);
+    assert_eq!(ids(&groups[1]), vec!["2"]);
+}
+
+#[test]
+fn different_currency_keeps_orders_apart() {
+    let mut other = create_test_order("2", "john", "Main Street 1", "2025-01-15 11:00:00");
+    other.currency = "GBP".to_string();
+    let orders = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        other,
+    ];
+
+    assert_eq!(group_orders_by_buyer(&orders).len(), 2);
+}
+
+#[test]
+fn latest_order_and_reference() {
+    let group = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 18:00:00"),
+        create_test_order("2", "john", "Main Street 1", "2025-01-16 09:00:00"),
+        create_test_order("3", "john", "Main Street 1", "2025-01-15 20:00:00"),
+    ];
+
+    assert_eq!(latest_order(&group).order_id, "2");
+    assert_eq!(order_reference(&group), "1, 2, 3");
+}
+
+#[test]
+fn combined_positions_keep_sections_per_order() {
+    let group = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        create_test_order("2", "john", "Main Street 1", "2025-01-15 11:00:00"),
+    ];
+
+    let positions = combined_positions(
+        &group,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+        InvoiceLanguage::German,
+    )
+    .unwrap();
+
+    let descriptions: Vec<&str> = positions.iter().map(|p| p.description.as_str()).collect();
+    assert_eq!(
+        descriptions,
+        vec![
+            "Bestellung 1: 1x Test Card - 5,00 EUR",
+            "Bestellung 1: Shipping costs",
+            "Bestellung 2: 1x Test Card - 5,00 EUR",
+            "Bestellung 2: Shipping costs",
+        ]
+    );
+    let total: i64 = positions.iter().map(PlannedPosition::total_cents).sum();
+    assert_eq!(total, 1250);
+}
+
+#[test]
+fn combined_positions_are_labelled_in_the_invoice_language() {
+    let group = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        create_test_order("2", "john", "Main Street 1", "2025-01-15 11:00:00"),
+    ];
+
+    let positions = combined_positions(
+        &group,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+        InvoiceLanguage::English,
+    )
+    .unwrap();
+
+    assert_eq!(positions[0].description, "Order 1: 1x Test Card - 5,00 EUR");
+}
+
+#[test]
+fn single_order_positions_are_unchanged() {
+    let order = create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00");
+    let settings = ReconciliationSettings::default();
+
+    assert_eq!(
+        combined_positions(
+            std::slice::from_ref(&order),
+            &settings,
+            DecimalLocale::Auto,
+            InvoiceLanguage::German,
+        )
+        .unwrap(),
+        reconciled_positions(&order, &settings, DecimalLocale::Auto).unwrap()
+    );
+}
+
+#[test]
+fn mismatch_in_one_order_fails_the_group() {
+    let mut broken = create_test_order("2", "john", "Main Street 1", "2025-01-15 11:00:00");
+    broken.total_value = "20,00".to_string();
+    let group = vec![
+        create_test_order("1", "john", "Main Street 1", "2025-01-15 10:00:00"),
+        broken,
+    ];
+
+    let err = combined_positions(
+        &group,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+        InvoiceLanguage::German,
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().starts_with("Order 2:"), "{err}");
+}
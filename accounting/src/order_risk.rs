@@ -0,0 +1,216 @@
+//! Order risk heuristics.
+//!
+//! Flags orders worth a second look before invoicing and shipping. Each rule
+//! in [`RiskRules`] can be switched off or tuned from the UI:
+//!
+//! - a **new buyer** placing a high-value order (buyers count as new when the
+//!   "Prepare Contacts" step had to create their SevDesk contact),
+//! - a **VAT number** whose country prefix does not match the shipping country,
+//! - an **unusually large quantity** of the same expensive card.
+//!
+//! The checks are pure; flags are advisory and never block processing.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::csv_processor::field_parsers::parse_price;
+use crate::models::OrderRecord;
+
+/// Configurable thresholds for the risk checks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskRules {
+    pub new_buyer_enabled: bool,
+    /// Order total (EUR) from which a new buyer's order is flagged
+    pub new_buyer_min_total: f64,
+    pub vat_country_enabled: bool,
+    pub bulk_card_enabled: bool,
+    /// Unit price (EUR) from which a card counts as expensive
+    pub bulk_card_min_price: f64,
+    /// Copies of one expensive card from which an item is flagged
+    pub bulk_card_min_quantity: u32,
+}
+
+impl Default for RiskRules {
+    fn default() -> Self {
+        Self {
+            new_buyer_enabled: true,
+            new_buyer_min_total: 100.0,
+            vat_country_enabled: true,
+            bulk_card_enabled: true,
+            bulk_card_min_price: 20.0,
+            bulk_card_min_quantity: 5,
+        }
+    }
+}
+
+/// A single reason an order was flagged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RiskFlag {
+    NewBuyerHighValue {
+        total: f64,
+    },
+    VatCountryMismatch {
+        vat_number: String,
+        shipping_country: String,
+    },
+    BulkExpensiveCard {
+        card: String,
+        quantity: u32,
+        unit_price: f64,
+    },
+}
+
+impl fmt::Display for RiskFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskFlag::NewBuyerHighValue { total } => {
+                write!(f, "New buyer with a high-value order ({total:.2} EUR)")
+            }
+            RiskFlag::VatCountryMismatch {
+                vat_number,
+                shipping_country,
+            } => write!(
+                f,
+                "VAT number {vat_number} does not match shipping country {shipping_country}"
+            ),
+            RiskFlag::BulkExpensiveCard {
+                card,
+                quantity,
+                unit_price,
+            } => write!(f, "{quantity}x {card} at {unit_price:.2} EUR each"),
+        }
+    }
+}
+
+/// All flags raised for one order.
+#[derive(Debug, Clone)]
+pub struct OrderRisk {
+    pub order_id: String,
+    pub customer_name: String,
+    pub flags: Vec<RiskFlag>,
+}
+
+/// Country names (English and German, lowercase) accepted for each VAT prefix.
+const VAT_PREFIX_COUNTRIES: &[(&str, &[&str])] = &[
+    ("AT", &["austria", "österreich"]),
+    ("BE", &["belgium", "belgien"]),
+    ("BG", &["bulgaria", "bulgarien"]),
+    ("CH", &["switzerland", "schweiz"]),
+    ("CY", &["cyprus", "zypern"]),
+    ("CZ", &["czech republic", "czechia", "tschechien"]),
+    ("DE", &["germany", "deutschland"]),
+    ("DK", &["denmark", "dänemark"]),
+    ("EE", &["estonia", "estland"]),
+    ("EL", &["greece", "griechenland"]),
+    ("ES", &["spain", "spanien"]),
+    ("FI", &["finland", "finnland"]),
+    ("FR", &["france", "frankreich"]),
+    (
+        "GB",
+        &["united kingdom", "großbritannien", "vereinigtes königreich"],
+    ),
+    ("HR", &["croatia", "kroatien"]),
+    ("HU", &["hungary", "ungarn"]),
+    ("IE", &["ireland", "irland"]),
+    ("IT", &["italy", "italien"]),
+    ("LT", &["lithuania", "litauen"]),
+    ("LU", &["luxembourg", "luxemburg"]),
+    ("LV", &["latvia", "lettland"]),
+    ("MT", &["malta"]),
+    ("NL", &["netherlands", "niederlande"]),
+    ("NO", &["norway", "norwegen"]),
+    ("PL", &["poland", "polen"]),
+    ("PT", &["portugal"]),
+    ("RO", &["romania", "rumänien"]),
+    ("SE", &["sweden", "schweden"]),
+    ("SI", &["slovenia", "slowenien"]),
+    ("SK", &["slovakia", "slowakei"]),
+    ("XI", &["united kingdom", "northern ireland", "nordirland"]),
+];
+
+/// Whether a VAT number's country prefix contradicts the shipping country.
+///
+/// Unknown prefixes (or numbers without one) are never reported as a
+/// mismatch, as the check cannot tell.
+fn vat_country_mismatch(vat_number: &str, shipping_country: &str) -> bool {
+    let prefix: String = vat_number
+        .trim()
+        .chars()
+        .take(2)
+        .collect::<String>()
+        .to_uppercase();
+    let country = shipping_country.trim().to_lowercase();
+    VAT_PREFIX_COUNTRIES
+        .iter()
+        .find(|(code, _)| *code == prefix)
+        .is_some_and(|(_, names)| !names.contains(&country.as_str()))
+}
+
+/// Runs every enabled rule against one order.
+///
+/// `new_buyers` holds the customer names whose contact was newly created.
+pub fn assess_order(
+    order: &OrderRecord,
+    rules: &RiskRules,
+    new_buyers: &HashSet<String>,
+) -> Vec<RiskFlag> {
+    let mut flags = Vec::new();
+
+    if rules.new_buyer_enabled && new_buyers.contains(&order.name) {
+        if let Ok(total) = parse_price(&order.total_value) {
+            if total >= rules.new_buyer_min_total {
+                flags.push(RiskFlag::NewBuyerHighValue { total });
+            }
+        }
+    }
+
+    if rules.vat_country_enabled {
+        if let Some(vat_number) = &order.vat_number {
+            if vat_country_mismatch(vat_number, &order.country) {
+                flags.push(RiskFlag::VatCountryMismatch {
+                    vat_number: vat_number.clone(),
+                    shipping_country: order.country.clone(),
+                });
+            }
+        }
+    }
+
+    if rules.bulk_card_enabled {
+        for item in &order.items {
+            if item.quantity >= rules.bulk_card_min_quantity
+                && item.price >= rules.bulk_card_min_price
+            {
+                flags.push(RiskFlag::BulkExpensiveCard {
+                    card: item.localized_product_name.clone(),
+                    quantity: item.quantity,
+                    unit_price: item.price,
+                });
+            }
+        }
+    }
+
+    flags
+}
+
+/// Assesses all orders and returns only the flagged ones, in input order.
+pub fn assess_orders(
+    orders: &[OrderRecord],
+    rules: &RiskRules,
+    new_buyers: &HashSet<String>,
+) -> Vec<OrderRisk> {
+    orders
+        .iter()
+        .filter_map(|order| {
+            let flags = assess_order(order, rules, new_buyers);
+            (!flags.is_empty()).then(|| OrderRisk {
+                order_id: order.order_id.clone(),
+                customer_name: order.name.clone(),
+                flags,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "order_risk_tests.rs"]
+mod tests;
@@ -0,0 +1,172 @@
+//! Tests for order risk heuristics.
+
+use super::*;
+use crate::models::OrderItem;
+
+fn item(name: &str, price: f64, quantity: u32) -> OrderItem {
+    OrderItem {
+        description: format!("{quantity}x {name} - {price:.2} EUR"),
+        product_id: "1".to_string(),
+        localized_product_name: name.to_string(),
+        price,
+        quantity,
+    }
+}
+
+fn create_test_order(name: &str, total: &str, items: Vec<OrderItem>) -> OrderRecord {
+    OrderRecord {
+        order_id: "12345".to_string(),
+        username: "testuser".to_string(),
+        name: name.to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: "2025-01-15".to_string(),
+        article_count: items.iter().map(|i| i.quantity).sum(),
+        merchandise_value: total.to_string(),
+        shipment_costs: "0,00".to_string(),
+        total_value: total.to_string(),
+        commission: "0,10".to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items,
+    }
+}
+
+fn new_buyers(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn ordinary_order_raises_no_flags() {
+    let order = create_test_order("John Doe", "6,50", vec![item("Test Card", 5.0, 1)]);
+    assert!(assess_order(&order, &RiskRules::default(), &HashSet::new()).is_empty());
+}
+
+#[test]
+fn new_buyer_flagged_only_above_threshold() {
+    let rules = RiskRules::default();
+    let buyers = new_buyers(&["John Doe"]);
+
+    let big = create_test_order("John Doe", "150,00", vec![item("Card", 15.0, 1)]);
+    assert_eq!(
+        assess_order(&big, &rules, &buyers),
+        vec![RiskFlag::NewBuyerHighValue { total: 150.0 }]
+    );
+
+    let small = create_test_order("John Doe", "99,99", vec![item("Card", 15.0, 1)]);
+    assert!(assess_order(&small, &rules, &buyers).is_empty());
+
+    // The same order from a returning buyer is fine
+    assert!(assess_order(&big, &rules, &HashSet::new()).is_empty());
+}
+
+#[test]
+fn vat_prefix_must_match_shipping_country() {
+    let rules = RiskRules::default();
+    let mut order = create_test_order("Pro GmbH", "50,00", vec![item("Card", 5.0, 1)]);
+
+    order.vat_number = Some("DE123456789".to_string());
+    assert!(assess_order(&order, &rules, &HashSet::new()).is_empty());
+
+    order.country = "Deutschland".to_string();
+    assert!(assess_order(&order, &rules, &HashSet::new()).is_empty());
+
+    order.vat_number = Some("fr12345678901".to_string());
+    assert_eq!(
+        assess_order(&order, &rules, &HashSet::new()),
+        vec![RiskFlag::VatCountryMismatch {
+            vat_number: "fr12345678901".to_string(),
+            shipping_country: "Deutschland".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn unknown_vat_prefix_is_not_a_mismatch() {
+    let mut order = create_test_order("Pro Inc", "50,00", vec![item("Card", 5.0, 1)]);
+    order.vat_number = Some("123456789".to_string());
+    assert!(assess_order(&order, &RiskRules::default(), &HashSet::new()).is_empty());
+}
+
+#[test]
+fn bulk_expensive_card_flagged_per_item() {
+    let order = create_test_order(
+        "John Doe",
+        "310,00",
+        vec![
+            item("The One Ring", 60.0, 5),
+            item("Lightning Bolt", 1.0, 10),
+            item("Ragavan", 40.0, 2),
+        ],
+    );
+    assert_eq!(
+        assess_order(&order, &RiskRules::default(), &HashSet::new()),
+        vec![RiskFlag::BulkExpensiveCard {
+            card: "The One Ring".to_string(),
+            quantity: 5,
+            unit_price: 60.0,
+        }]
+    );
+}
+
+#[test]
+fn disabled_rules_are_skipped() {
+    let mut order = create_test_order("John Doe", "500,00", vec![item("Ring", 100.0, 5)]);
+    order.vat_number = Some("FR12345678901".to_string());
+    let rules = RiskRules {
+        new_buyer_enabled: false,
+        vat_country_enabled: false,
+        bulk_card_enabled: false,
+        ..RiskRules::default()
+    };
+    assert!(assess_order(&order, &rules, &new_buyers(&["John Doe"])).is_empty());
+}
+
+#[test]
+fn custom_thresholds_apply() {
+    let order = create_test_order("John Doe", "30,00", vec![item("Card", 10.0, 3)]);
+    let rules = RiskRules {
+        new_buyer_min_total: 25.0,
+        bulk_card_min_price: 10.0,
+        bulk_card_min_quantity: 3,
+        ..RiskRules::default()
+    };
+    assert_eq!(
+        assess_order(&order, &rules, &new_buyers(&["John Doe"])).len(),
+        2
+    );
+}
+
+#[test]
+fn assess_orders_returns_only_flagged_orders() {
+    let mut flagged = create_test_order("Jane Smith", "6,50", vec![item("Card", 5.0, 1)]);
+    flagged.order_id = "2".to_string();
+    flagged.vat_number = Some("NL123456789B01".to_string());
+    let clean = create_test_order("John Doe", "6,50", vec![item("Card", 5.0, 1)]);
+
+    let risks = assess_orders(&[clean, flagged], &RiskRules::default(), &HashSet::new());
+    assert_eq!(risks.len(), 1);
+    assert_eq!(risks[0].order_id, "2");
+    assert_eq!(risks[0].customer_name, "Jane Smith");
+    assert_eq!(risks[0].flags.len(), 1);
+}
+
+#[test]
+fn flags_render_readable_messages() {
+    let flag = RiskFlag::BulkExpensiveCard {
+        card: "The One Ring".to_string(),
+        quantity: 5,
+        unit_price: 60.0,
+    };
+    assert_eq!(flag.to_string(), "5x The One Ring at 60.00 EUR each");
+    assert_eq!(
+        RiskFlag::NewBuyerHighValue { total: 150.0 }.to_string(),
+        "New buyer with a high-value order (150.00 EUR)"
+    );
+}
@@ -0,0 +1,156 @@
+//! Persistent queue of invoice PDF downloads.
+//!
+//! With the VPDF send type every finalized invoice's PDF is saved to the
+//! download folder. Downloads go through this queue instead of happening
+//! inline: they run a few at a time, each is retried with a growing delay,
+//! and whatever still fails is kept in a small JSON file in the user's data
+//! directory so it can be retried later — also after a restart. "Re-download
+//! missing PDFs" puts every created invoice whose file is not in the folder
+//! back on the queue.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// One PDF to download.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfJob {
+    pub invoice_id: u32,
+    pub invoice_number: String,
+    /// Folder the PDF is saved to, as `<invoice number>.pdf`
+    pub directory: PathBuf,
+    /// Failed attempts so far, over all runs
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl PdfJob {
+    pub fn new(invoice_id: u32, invoice_number: &str, directory: &Path) -> Self {
+        Self {
+            invoice_id,
+            invoice_number: invoice_number.to_string(),
+            directory: directory.to_path_buf(),
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    /// Where the PDF ends up.
+    pub fn pdf_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.pdf", self.invoice_number))
+    }
+}
+
+/// How queued downloads are run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PdfDownloadSettings {
+    /// Downloads running at the same time
+    pub concurrency: usize,
+    /// Tries per job and run
+    pub attempts: u32,
+    /// Wait before the second try; doubles with every further try
+    pub retry_delay: Duration,
+}
+
+impl Default for PdfDownloadSettings {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            attempts: 3,
+            retry_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Result of downloading one job.
+#[derive(Debug, Clone)]
+pub struct PdfDownloadOutcome {
+    pub job: PdfJob,
+    pub result: Result<PathBuf, String>,
+}
+
+/// Downloads that have not succeeded yet.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PdfQueue {
+    pub jobs: Vec<PdfJob>,
+}
+
+impl PdfQueue {
+    /// Default queue file: `<data dir>/sevdesk_invoicing/pdf_queue.json`
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("sevdesk_invoicing")
+            .join("pdf_queue.json")
+    }
+
+    /// Loads the queue; a missing file is an empty queue.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Queues jobs whose invoice is not queued yet. Returns how many were added.
+    pub fn enqueue(&mut self, jobs: impl IntoIterator<Item = PdfJob>) -> usize {
+        let mut added = 0;
+        for job in jobs {
+            if !self.jobs.iter().any(|j| j.invoice_id == job.invoice_id) {
+                self.jobs.push(job);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Takes the finished downloads off the queue and records the failures
+    /// on their jobs. Returns the invoice ids and paths that were saved.
+    pub fn apply(&mut self, outcomes: Vec<PdfDownloadOutcome>) -> Vec<(u32, PathBuf)> {
+        let mut saved = Vec::new();
+        for outcome in outcomes {
+            let position = self
+                .jobs
+                .iter()
+                .position(|j| j.invoice_id == outcome.job.invoice_id);
+            match (outcome.result, position) {
+                (Ok(path), Some(idx)) => {
+                    self.jobs.remove(idx);
+                    saved.push((outcome.job.invoice_id, path));
+                }
+                (Ok(path), None) => saved.push((outcome.job.invoice_id, path)),
+                (Err(_), Some(idx)) => self.jobs[idx] = outcome.job,
+                (Err(_), None) => self.jobs.push(outcome.job),
+            }
+        }
+        saved
+    }
+}
+
+/// Jobs for the created invoices whose PDF is not in `directory`.
+pub fn missing_pdf_jobs<'a>(
+    invoices: impl IntoIterator<Item = (u32, &'a str)>,
+    directory: &Path,
+) -> Vec<PdfJob> {
+    invoices
+        .into_iter()
+        .map(|(invoice_id, invoice_number)| PdfJob::new(invoice_id, invoice_number, directory))
+        .filter(|job| !job.pdf_path().exists())
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "pdf_queue_tests.rs"]
+mod tests;
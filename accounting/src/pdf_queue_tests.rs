@@ -0,0 +1,81 @@
+//! Tests for the PDF download queue.
+
+use super::*;
+
+fn failed(mut job: PdfJob, error: &str) -> PdfDownloadOutcome {
+    job.attempts += 1;
+    job.last_error = Some(error.to_string());
+    PdfDownloadOutcome {
+        job,
+        result: Err(error.to_string()),
+    }
+}
+
+fn saved(job: PdfJob) -> PdfDownloadOutcome {
+    let path = job.pdf_path();
+    PdfDownloadOutcome {
+        job,
+        result: Ok(path),
+    }
+}
+
+#[test]
+fn enqueue_skips_invoices_already_queued() {
+    let dir = Path::new("/tmp/pdfs");
+    let mut queue = PdfQueue::default();
+
+    assert_eq!(
+        queue.enqueue([PdfJob::new(1, "RE-1", dir), PdfJob::new(2, "RE-2", dir)]),
+        2
+    );
+    assert_eq!(
+        queue.enqueue([PdfJob::new(2, "RE-2", dir), PdfJob::new(3, "RE-3", dir)]),
+        1
+    );
+    assert_eq!(queue.jobs.len(), 3);
+}
+
+#[test]
+fn apply_removes_saved_jobs_and_keeps_failures() {
+    let dir = Path::new("/tmp/pdfs");
+    let mut queue = PdfQueue::default();
+    queue.enqueue([PdfJob::new(1, "RE-1", dir), PdfJob::new(2, "RE-2", dir)]);
+
+    let done = queue.apply(vec![
+        saved(PdfJob::new(1, "RE-1", dir)),
+        failed(PdfJob::new(2, "RE-2", dir), "503 Service Unavailable"),
+    ]);
+
+    assert_eq!(done, vec![(1, dir.join("RE-1.pdf"))]);
+    assert_eq!(queue.jobs.len(), 1);
+    assert_eq!(queue.jobs[0].invoice_id, 2);
+    assert_eq!(queue.jobs[0].attempts, 1);
+    assert_eq!(
+        queue.jobs[0].last_error.as_deref(),
+        Some("503 Service Unavailable")
+    );
+}
+
+#[test]
+fn missing_pdf_jobs_skips_files_already_saved() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("RE-1.pdf"), b"%PDF").unwrap();
+
+    let jobs = missing_pdf_jobs([(1, "RE-1"), (2, "RE-2")], dir.path());
+
+    assert_eq!(jobs, vec![PdfJob::new(2, "RE-2", dir.path())]);
+}
+
+#[test]
+fn queue_survives_a_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("pdf_queue.json");
+    assert_eq!(PdfQueue::load(&path).unwrap(), PdfQueue::default());
+
+    let mut queue = PdfQueue::default();
+    queue.enqueue([PdfJob::new(7, "RE-7", dir.path())]);
+    queue.apply(vec![failed(PdfJob::new(7, "RE-7", dir.path()), "timeout")]);
+    queue.save(&path).unwrap();
+
+    assert_eq!(PdfQueue::load(&path).unwrap(), queue);
+}
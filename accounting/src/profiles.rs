@@ -0,0 +1,176 @@
+//! Named configuration profiles.
+//!
+//! A profile bundles everything that differs between kinds of sales runs —
+//! tax mode, invoice texts, cost centre and tags, workflow options, check
+//! account, PDF folder and, for trying things out, the SevDesk endpoint —
+//! so switching between e.g. Cardmarket orders under the Kleingewerbe rule
+//! and direct B2B sales is one dropdown selection. Profiles are stored as
+//! JSON next to the order board.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{InvoiceTexts, SendType, TaxMode};
+use crate::reconciliation::ReconciliationSettings;
+
+/// One named set of invoice settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub tax_mode: TaxMode,
+    /// Texts for German-speaking buyers
+    pub invoice_texts: InvoiceTexts,
+    /// Texts for everyone else; older profiles get the English defaults
+    #[serde(default = "InvoiceTexts::english")]
+    pub english_texts: InvoiceTexts,
+    /// Profiles saved before reconciliation settings existed get the defaults
+    #[serde(default)]
+    pub reconciliation: ReconciliationSettings,
+    /// Invoice a buyer's orders to the same address together
+    #[serde(default)]
+    pub combine_orders_by_buyer: bool,
+    /// SevDesk cost centre (by name) the invoices are booked on
+    #[serde(default)]
+    pub cost_centre: Option<String>,
+    /// Tags attached to every invoice
+    #[serde(default)]
+    pub invoice_tags: Vec<String>,
+    pub workflow_finalize: bool,
+    pub workflow_send_type: SendType,
+    pub workflow_enshrine: bool,
+    pub workflow_book: bool,
+    /// SevDesk ID of the check account to book against
+    pub check_account_id: Option<String>,
+    pub pdf_download_path: Option<PathBuf>,
+    /// SevDesk API base URL, e.g. a mock server or sandbox; `None` uses the
+    /// configured one
+    #[serde(default)]
+    pub sevdesk_base_url: Option<String>,
+}
+
+impl ConfigProfile {
+    /// Cardmarket orders: Kleingewerbe, already paid, booked on finalize.
+    pub fn cardmarket_kleingewerbe() -> Self {
+        Self {
+            name: "Cardmarket Kleingewerbe".to_string(),
+            tax_mode: TaxMode::SmallBusiness,
+            invoice_texts: InvoiceTexts::default(),
+            english_texts: InvoiceTexts::english(),
+            reconciliation: ReconciliationSettings::default(),
+            combine_orders_by_buyer: false,
+            cost_centre: None,
+            invoice_tags: Vec::new(),
+            workflow_finalize: true,
+            workflow_send_type: SendType::Vpdf,
+            workflow_enshrine: false,
+            workflow_book: true,
+            check_account_id: None,
+            pdf_download_path: None,
+            sevdesk_base_url: None,
+        }
+    }
+
+    /// Direct sales to businesses: regular VAT, sent by email, paid later.
+    pub fn direct_b2b() -> Self {
+        Self {
+            name: "Direct B2B sales".to_string(),
+            tax_mode: TaxMode::Standard,
+            invoice_texts: InvoiceTexts {
+                header: "Rechnung {order_id}".to_string(),
+                head_text: "Vielen Dank für Ihren Auftrag.".to_string(),
+                foot_text: "Zahlbar innerhalb von 14 Tagen ohne Abzug.".to_string(),
+            },
+            english_texts: InvoiceTexts {
+                header: "Invoice {order_id}".to_string(),
+                head_text: "Thank you for your order.".to_string(),
+                foot_text: "Payable within 14 days without deduction.".to_string(),
+            },
+            reconciliation: ReconciliationSettings::default(),
+            combine_orders_by_buyer: false,
+            cost_centre: None,
+            invoice_tags: Vec::new(),
+            workflow_finalize: true,
+            workflow_send_type: SendType::Vm,
+            workflow_enshrine: false,
+            workflow_book: false,
+            check_account_id: None,
+            pdf_download_path: None,
+            sevdesk_base_url: None,
+        }
+    }
+}
+
+/// All saved profiles plus the one selected last.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileStore {
+    pub profiles: Vec<ConfigProfile>,
+    /// Name of the profile applied at startup
+    pub selected: Option<String>,
+}
+
+impl Default for ProfileStore {
+    /// The built-in Cardmarket and B2B profiles, none selected.
+    fn default() -> Self {
+        Self {
+            profiles: vec![
+                ConfigProfile::cardmarket_kleingewerbe(),
+                ConfigProfile::direct_b2b(),
+            ],
+            selected: None,
+        }
+    }
+}
+
+impl ProfileStore {
+    /// Default profile file: `<data dir>/sevdesk_invoicing/profiles.json`
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("sevdesk_invoicing")
+            .join("profiles.json")
+    }
+
+    /// Loads the profiles; a missing file gives the built-in ones.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConfigProfile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Adds the profile, replacing one with the same name in place.
+    pub fn upsert(&mut self, profile: ConfigProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// Removes the named profile. Returns false if there was none.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.profiles.len();
+        self.profiles.retain(|p| p.name != name);
+        if self.selected.as_deref() == Some(name) {
+            self.selected = None;
+        }
+        self.profiles.len() != before
+    }
+}
+
+#[cfg(test)]
+#[path = "profiles_tests.rs"]
+mod tests;
@@ -0,0 +1,88 @@
+//! Tests for configuration profiles.
+
+use super::*;
+
+#[test]
+fn default_store_has_builtin_profiles() {
+    let store = ProfileStore::default();
+    let cardmarket = store.get("Cardmarket Kleingewerbe").unwrap();
+    assert_eq!(cardmarket.tax_mode, TaxMode::SmallBusiness);
+    let b2b = store.get("Direct B2B sales").unwrap();
+    assert_eq!(b2b.tax_mode, TaxMode::Standard);
+    assert!(!b2b.workflow_book, "B2B invoices are not paid yet");
+    assert_eq!(store.selected, None);
+}
+
+#[test]
+fn upsert_replaces_by_name_and_remove_clears_selection() {
+    let mut store = ProfileStore::default();
+    let mut changed = ConfigProfile::direct_b2b();
+    changed.check_account_id = Some("42".to_string());
+    store.upsert(changed.clone());
+    assert_eq!(store.profiles.len(), 2);
+    assert_eq!(store.get("Direct B2B sales"), Some(&changed));
+
+    let mut custom = ConfigProfile::cardmarket_kleingewerbe();
+    custom.name = "Convention".to_string();
+    store.upsert(custom);
+    assert_eq!(store.profiles.len(), 3);
+
+    store.selected = Some("Convention".to_string());
+    assert!(store.remove("Convention"));
+    assert!(!store.remove("Convention"));
+    assert_eq!(store.selected, None);
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("profiles.json");
+    let mut store = ProfileStore::default();
+    let mut profile = ConfigProfile::direct_b2b();
+    profile.pdf_download_path = Some(PathBuf::from("/tmp/invoices"));
+    store.upsert(profile);
+    store.selected = Some("Direct B2B sales".to_string());
+
+    store.save(&path).unwrap();
+    assert_eq!(ProfileStore::load(&path).unwrap(), store);
+}
+
+#[test]
+fn missing_file_loads_builtin_profiles() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ProfileStore::load(&dir.path().join("absent.json")).unwrap();
+    assert_eq!(store, ProfileStore::default());
+}
+
+#[test]
+fn profiles_without_english_texts_get_the_defaults() {
+    let mut json = serde_json::to_value(ConfigProfile::cardmarket_kleingewerbe()).unwrap();
+    json.as_object_mut().unwrap().remove("english_texts");
+
+    let profile: ConfigProfile = serde_json::from_value(json).unwrap();
+
+    assert_eq!(profile.english_texts, InvoiceTexts::english());
+}
+
+#[test]
+fn profiles_without_cost_centre_or_tags_book_without_them() {
+    let mut json = serde_json::to_value(ConfigProfile::cardmarket_kleingewerbe()).unwrap();
+    let fields = json.as_object_mut().unwrap();
+    fields.remove("cost_centre");
+    fields.remove("invoice_tags");
+
+    let profile: ConfigProfile = serde_json::from_value(json).unwrap();
+
+    assert_eq!(profile.cost_centre, None);
+    assert!(profile.invoice_tags.is_empty());
+}
+
+#[test]
+fn profiles_without_base_url_use_the_configured_endpoint() {
+    let mut json = serde_json::to_value(ConfigProfile::cardmarket_kleingewerbe()).unwrap();
+    json.as_object_mut().unwrap().remove("sevdesk_base_url");
+
+    let profile: ConfigProfile = serde_json::from_value(json).unwrap();
+
+    assert_eq!(profile.sevdesk_base_url, None);
+}
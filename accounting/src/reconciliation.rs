@@ -0,0 +1,178 @@
+//! Invoice total reconciliation.
+//!
+//! Invoice positions are built from per-item prices parsed out of the order
+//! description, so their sum can drift a cent away from the order's
+//! `TotalValue` that the buyer actually paid. Before anything is sent to
+//! SevDesk the positions are summed in whole cents and compared with the CSV
+//! total: an exact match goes through unchanged, a difference within the
+//! configured tolerance gets a rounding-difference position (or is accepted
+//! silently if that is switched off), and anything larger fails the invoice
+//! with both totals in the error, since it points at a parsing problem.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use mtg_common::DecimalLocale;
+
+use crate::csv_processor::field_parsers::parse_price_in;
+use crate::models::OrderRecord;
+
+/// Name of the position that absorbs a rounding difference.
+pub const ROUNDING_POSITION_NAME: &str = "Rundungsdifferenz";
+
+/// How invoice totals are reconciled with the CSV total.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationSettings {
+    /// Largest difference in EUR still treated as rounding
+    pub tolerance: f64,
+    /// Add a position for differences within the tolerance, so the invoice
+    /// total equals the order total
+    pub add_rounding_position: bool,
+}
+
+impl Default for ReconciliationSettings {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.05,
+            add_rounding_position: true,
+        }
+    }
+}
+
+/// One invoice position before it is sent to SevDesk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedPosition {
+    pub name: String,
+    pub description: String,
+    pub quantity: f64,
+    /// Gross unit price in EUR
+    pub price_gross: f64,
+}
+
+impl PlannedPosition {
+    /// Line total in whole cents.
+    pub fn total_cents(&self) -> i64 {
+        (self.quantity * self.price_gross * 100.0).round() as i64
+    }
+}
+
+/// Outcome of comparing the position sum with the order total.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reconciliation {
+    Balanced,
+    /// Order total minus position sum, within the tolerance
+    Rounding {
+        difference: f64,
+    },
+    Mismatch {
+        computed: f64,
+        expected: f64,
+    },
+}
+
+/// Positions for the order's items (or the merchandise value if no items
+/// were parsed) plus shipping, without any reconciliation. `locale` is the
+/// number format of the CSV the order was loaded from.
+pub fn plan_positions(order: &OrderRecord, locale: DecimalLocale) -> Result<Vec<PlannedPosition>> {
+    let merchandise_value = parse_price_in(&order.merchandise_value, locale)?;
+    let shipment_costs = parse_price_in(&order.shipment_costs, locale)?;
+
+    let mut positions: Vec<PlannedPosition> = if order.items.is_empty() {
+        vec![PlannedPosition {
+            name: order.localized_product_name.clone(),
+            description: order.description.clone(),
+            quantity: order.article_count as f64,
+            price_gross: merchandise_value,
+        }]
+    } else {
+        order
+            .items
+            .iter()
+            .map(|item| PlannedPosition {
+                name: item.localized_product_name.clone(),
+                description: item.description.clone(),
+                quantity: item.quantity as f64,
+                price_gross: item.price,
+            })
+            .collect()
+    };
+
+    if shipment_costs > 0.0 {
+        positions.push(PlannedPosition {
+            name: "Shipping".to_string(),
+            description: "Shipping costs".to_string(),
+            quantity: 1.0,
+            price_gross: shipment_costs,
+        });
+    }
+    Ok(positions)
+}
+
+/// Compares the positions' sum with `expected_total`, both in whole cents.
+pub fn reconcile(
+    positions: &[PlannedPosition],
+    expected_total: f64,
+    settings: &ReconciliationSettings,
+) -> Reconciliation {
+    let computed_cents: i64 = positions.iter().map(PlannedPosition::total_cents).sum();
+    let expected_cents = (expected_total * 100.0).round() as i64;
+    let difference_cents = expected_cents - computed_cents;
+    let tolerance_cents = (settings.tolerance * 100.0).round() as i64;
+
+    if difference_cents == 0 {
+        Reconciliation::Balanced
+    } else if difference_cents.abs() <= tolerance_cents {
+        Reconciliation::Rounding {
+            difference: difference_cents as f64 / 100.0,
+        }
+    } else {
+        Reconciliation::Mismatch {
+            computed: computed_cents as f64 / 100.0,
+            expected: expected_cents as f64 / 100.0,
+        }
+    }
+}
+
+/// Plans the order's positions and reconciles them with its `TotalValue`.
+/// Fails if the difference exceeds the tolerance.
+pub fn reconciled_positions(
+    order: &OrderRecord,
+    settings: &ReconciliationSettings,
+    locale: DecimalLocale,
+) -> Result<Vec<PlannedPosition>> {
+    let mut positions = plan_positions(order, locale)?;
+    let expected_total = parse_price_in(&order.total_value, locale)?;
+
+    match reconcile(&positions, expected_total, settings) {
+        Reconciliation::Balanced => {}
+        Reconciliation::Rounding { difference } if settings.add_rounding_position => {
+            log::info!(
+                "Order {}: adding rounding position of {difference:+.2} EUR",
+                order.order_id
+            );
+            positions.push(PlannedPosition {
+                name: ROUNDING_POSITION_NAME.to_string(),
+                description: format!("Ausgleich auf Bestellsumme {expected_total:.2} EUR"),
+                quantity: 1.0,
+                price_gross: difference,
+            });
+        }
+        Reconciliation::Rounding { difference } => log::warn!(
+            "Order {}: invoice total differs from the order total by {difference:+.2} EUR",
+            order.order_id
+        ),
+        Reconciliation::Mismatch { computed, expected } => bail!(
+            "Order {}: invoice positions total {computed:.2} EUR but the order total is \
+             {expected:.2} EUR; the difference of {:.2} EUR exceeds the {:.2} EUR rounding \
+             tolerance",
+            order.order_id,
+            (expected - computed).abs(),
+            settings.tolerance
+        ),
+    }
+    Ok(positions)
+}
+
+#[cfg(test)]
+#[path = "reconciliation_tests.rs"]
+mod tests;
@@ -0,0 +1,167 @@
+//! Tests for invoice total reconciliation.
+
+use super::*;
+use crate::models::OrderItem;
+
+fn item(price: f64, quantity: u32) -> OrderItem {
+    OrderItem {
+        description: format!("{quantity}x Card - {price:.2} EUR"),
+        product_id: "1".to_string(),
+        localized_product_name: "Card".to_string(),
+        price,
+        quantity,
+    }
+}
+
+fn order(items: Vec<OrderItem>, merchandise: &str, shipping: &str, total: &str) -> OrderRecord {
+    OrderRecord {
+        order_id: "1001".to_string(),
+        username: "buyer".to_string(),
+        name: "Buyer".to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: "Germany".to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: "2025-01-15".to_string(),
+        article_count: 1,
+        merchandise_value: merchandise.to_string(),
+        shipment_costs: shipping.to_string(),
+        total_value: total.to_string(),
+        commission: "0,00".to_string(),
+        currency: "EUR".to_string(),
+        description: "Card".to_string(),
+        product_id: "1".to_string(),
+        localized_product_name: "Card".to_string(),
+        items,
+    }
+}
+
+#[test]
+fn balanced_order_keeps_its_positions() {
+    // 0.1 + 0.2 style float sums must not show up as a difference
+    let order = order(
+        vec![item(0.1, 1), item(0.2, 1), item(1.15, 3)],
+        "3,75",
+        "1,25",
+        "5,00",
+    );
+
+    let positions = reconciled_positions(
+        &order,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+    )
+    .unwrap();
+
+    assert_eq!(positions.len(), 4);
+    assert_eq!(positions[3].name, "Shipping");
+    assert!(!positions.iter().any(|p| p.name == ROUNDING_POSITION_NAME));
+}
+
+#[test]
+fn cent_difference_adds_rounding_position() {
+    let order = order(vec![item(3.33, 3)], "10,00", "0,00", "10,00");
+
+    let positions = reconciled_positions(
+        &order,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+    )
+    .unwrap();
+
+    let rounding = positions.last().unwrap();
+    assert_eq!(rounding.name, ROUNDING_POSITION_NAME);
+    assert_eq!(rounding.total_cents(), 1);
+    let total: i64 = positions.iter().map(PlannedPosition::total_cents).sum();
+    assert_eq!(total, 1000);
+}
+
+#[test]
+fn rounding_position_can_be_switched_off() {
+    let order = order(vec![item(3.33, 3)], "10,00", "0,00", "9,98");
+    let settings = ReconciliationSettings {
+        add_rounding_position: false,
+        ..ReconciliationSettings::default()
+    };
+
+    let positions = reconciled_positions(&order, &settings, DecimalLocale::Auto).unwrap();
+
+    assert_eq!(positions.len(), 1);
+}
+
+#[test]
+fn difference_above_tolerance_fails() {
+    let order = order(vec![item(5.0, 1)], "5,00", "1,50", "12,00");
+
+    let err = reconciled_positions(
+        &order,
+        &ReconciliationSettings::default(),
+        DecimalLocale::Auto,
+    )
+    .unwrap_err();
+
+    let message = err.to_string();
+    assert!(message.contains("6.50 EUR"), "{message}");
+    assert!(message.contains("12.00 EUR"), "{message}");
+}
+
+#[test]
+fn reconcile_compares_in_whole_cents() {
+    let settings = ReconciliationSettings {
+        tolerance: 0.02,
+        add_rounding_position: true,
+    };
+    let positions = plan_positions(
+        &order(vec![item(0.99, 2)], "1,98", "0,00", "1,98"),
+        DecimalLocale::Auto,
+    )
+    .unwrap();
+
+    assert_eq!(
+        reconcile(&positions, 1.98, &settings),
+        Reconciliation::Balanced
+    );
+    assert_eq!(
+        reconcile(&positions, 1.96, &settings),
+        Reconciliation::Rounding { difference: -0.02 }
+    );
+    assert_eq!(
+        reconcile(&positions, 2.01, &settings),
+        Reconciliation::Mismatch {
+            computed: 1.98,
+            expected: 2.01
+        }
+    );
+}
+
+#[test]
+fn fallback_position_uses_merchandise_value() {
+    let positions = plan_positions(
+        &order(Vec::new(), "7,50", "2,00", "9,50"),
+        DecimalLocale::Auto,
+    )
+    .unwrap();
+
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0].price_gross, 7.5);
+    assert_eq!(positions[1].price_gross, 2.0);
+}
+
+#[test]
+fn totals_are_parsed_in_the_csv_locale() {
+    // "1.000" is one thousand in a German export, one in an English one
+    let order = order(Vec::new(), "1.000", "0", "1.000");
+
+    let german = reconciled_positions(
+        &order,
+        &ReconciliationSettings::default(),
+        DecimalLocale::German,
+    )
+    .unwrap();
+    assert_eq!(german[0].price_gross, 1000.0);
+
+    let english = plan_positions(&order, DecimalLocale::English).unwrap();
+    assert_eq!(english[0].price_gross, 1.0);
+}
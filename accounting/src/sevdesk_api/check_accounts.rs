@@ -5,6 +5,7 @@ use log::{debug, info, warn};
 
 use crate::models::{CheckAccountResponse, SevDeskResponse};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
@@ -20,7 +21,7 @@ impl SevDeskApi {
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to fetch check accounts")?;
 
@@ -3,6 +3,7 @@
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
@@ -18,7 +19,7 @@ impl SevDeskApi {
             .client
             .get(&test_url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to test API connection")?;
 
@@ -4,15 +4,65 @@ use anyhow::{Context, Result};
 use log::{debug, error, info};
 
 use crate::models::{
-    AddressCategory, ContactCategory, ContactResponse, OrderRecord, SevDeskAddress, SevDeskContact,
-    SevDeskCountry, SevDeskResponse, SevDeskSingleObjectResponse,
+    AddressCategory, ContactCategory, ContactPreparationResult, ContactResponse, OrderRecord,
+    SevDeskAddress, SevDeskContact, SevDeskCountry, SevDeskResponse, SevDeskSingleObjectResponse,
 };
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
+    /// Resolves (or creates) the contact for an order ahead of invoice creation.
+    ///
+    /// The resolved ID lands in the contact cache, so the later invoice run
+    /// skips the lookup. Failures are reported in the result rather than
+    /// returned, mirroring [`SevDeskApi::create_invoice`].
+    pub async fn prepare_contact(&self, order: &OrderRecord) -> ContactPreparationResult {
+        let result = self.resolve_contact(order).await;
+        if let Err(e) = &result {
+            error!(
+                "Failed to prepare contact for order {}: {e}",
+                order.order_id
+            );
+        }
+        ContactPreparationResult {
+            order_id: order.order_id.clone(),
+            customer_name: order.name.clone(),
+            contact_id: result.as_ref().ok().map(|&(id, _)| id),
+            created: result.as_ref().is_ok_and(|&(_, created)| created),
+            error: result.err().map(|e| e.to_string()),
+        }
+    }
+
     /// Gets an existing contact by name or creates a new one.
+    ///
+    /// IDs already in the contact cache are returned without an API call.
     pub(crate) async fn get_or_create_contact(&self, order: &OrderRecord) -> Result<u32> {
+        self.resolve_contact(order).await.map(|(id, _)| id)
+    }
+
+    /// Like [`SevDeskApi::get_or_create_contact`], also reporting whether the
+    /// contact had to be created. Cache hits count as existing contacts.
+    async fn resolve_contact(&self, order: &OrderRecord) -> Result<(u32, bool)> {
+        if let Some(&contact_id) = self.contact_cache.read().await.get(&order.name) {
+            debug!(
+                "Contact '{}' found in cache with ID: {contact_id}",
+                order.name
+            );
+            return Ok((contact_id, false));
+        }
+
+        let (contact_id, created) = self.find_or_create_contact(order).await?;
+        self.contact_cache
+            .write()
+            .await
+            .insert(order.name.clone(), contact_id);
+        Ok((contact_id, created))
+    }
+
+    /// Searches SevDesk for a contact by name, creating it if none exists.
+    /// The flag is `true` when a new contact was created.
+    async fn find_or_create_contact(&self, order: &OrderRecord) -> Result<(u32, bool)> {
         debug!("Getting or creating contact for: {}", order.name);
         // First, try to find existing contact by name
         let search_url = format!("{}/Contact", self.base_url);
@@ -23,7 +73,7 @@ impl SevDeskApi {
             .get(&search_url)
             .header("Authorization", &self.api_token)
             .query(&[("name", &order.name)])
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to search for contact")?;
 
@@ -51,7 +101,7 @@ impl SevDeskApi {
                         "Found existing contact: {} (ID: {})",
                         order.name, contact_id
                     );
-                    return Ok(contact_id);
+                    return Ok((contact_id, false));
                 }
             }
         }
@@ -95,7 +145,7 @@ impl SevDeskApi {
             .header("Authorization", &self.api_token)
             .header("Content-Type", "application/json")
             .json(&new_contact)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to create contact")?;
 
@@ -132,7 +182,7 @@ impl SevDeskApi {
             .context("Failed to parse created contact ID from string")?;
 
         info!("Created new contact: {} (ID: {})", order.name, contact_id);
-        Ok(contact_id)
+        Ok((contact_id, true))
     }
 }
 
@@ -1,4 +1,4 @@
-//! Tests for contact management (get_or_create_contact).
+//! Tests for contact management (get_or_create_contact, prepare_contact).
 
 use wiremock::matchers::{header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -7,9 +7,9 @@ use crate::models::OrderRecord;
 use crate::sevdesk_api::SevDeskApi;
 
 fn api_with_mock(mock_uri: &str) -> SevDeskApi {
-    let mut api = SevDeskApi::new("test_token".to_string());
-    api.base_url = mock_uri.to_string();
-    api
+    SevDeskApi::new("test_token".to_string())
+        .with_base_url(mock_uri)
+        .with_request_interval(std::time::Duration::ZERO)
 }
 
 fn create_test_order() -> OrderRecord {
@@ -243,3 +243,156 @@ async fn find_contact_with_different_country() {
     let contact_id = api.get_or_create_contact(&order).await.unwrap();
     assert_eq!(contact_id, 555);
 }
+
+// ── contact cache ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn cached_contact_skips_search() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+    let order = create_test_order();
+
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": [{
+                "id": "123",
+                "name": "Test Customer",
+                "objectName": "Contact",
+                "customerNumber": null,
+                "status": null
+            }]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    assert_eq!(api.get_or_create_contact(&order).await.unwrap(), 123);
+    assert_eq!(api.get_or_create_contact(&order).await.unwrap(), 123);
+}
+
+#[tokio::test]
+async fn failed_lookup_is_not_cached() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+    let order = create_test_order();
+
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("this is not json"))
+        .mount(&mock_server)
+        .await;
+
+    assert!(api.get_or_create_contact(&order).await.is_err());
+    assert!(api.contact_cache.read().await.is_empty());
+}
+
+#[tokio::test]
+async fn shared_cache_is_used_by_another_client() {
+    let mock_server = MockServer::start().await;
+    let order = create_test_order();
+
+    // No mocks mounted: any request would fail, so a hit must come from the cache.
+    let cache = crate::sevdesk_api::ContactCache::default();
+    cache.write().await.insert("Test Customer".to_string(), 77);
+    let api = api_with_mock(&mock_server.uri()).with_contact_cache(cache);
+
+    assert_eq!(api.get_or_create_contact(&order).await.unwrap(), 77);
+}
+
+// ── prepare_contact ──────────────────────────────────────────────────
+
+#[tokio::test]
+async fn prepare_contact_reports_resolved_id() {
+    let mock_server = MockServer::start().await;
+    let cache = crate::sevdesk_api::ContactCache::default();
+    let api = api_with_mock(&mock_server.uri()).with_contact_cache(cache.clone());
+    let order = create_test_order();
+
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": [{
+                "id": "321",
+                "name": "Test Customer",
+                "objectName": "Contact",
+                "customerNumber": null,
+                "status": null
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let result = api.prepare_contact(&order).await;
+    assert_eq!(result.order_id, "ORD-001");
+    assert_eq!(result.customer_name, "Test Customer");
+    assert_eq!(result.contact_id, Some(321));
+    assert!(!result.created, "existing contact is not a new buyer");
+    assert!(result.error.is_none());
+    assert_eq!(cache.read().await.get("Test Customer"), Some(&321));
+}
+
+#[tokio::test]
+async fn prepare_contact_reports_error() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+    let order = create_test_order();
+
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": []
+        })))
+        .mount(&mock_server)
+        .await;
+    mock_countries(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("Internal Server Error"))
+        .mount(&mock_server)
+        .await;
+
+    let result = api.prepare_contact(&order).await;
+    assert!(result.contact_id.is_none());
+    assert!(!result.created);
+    assert!(result.error.unwrap().contains("Failed to create contact"));
+}
+
+#[tokio::test]
+async fn prepare_contact_flags_created_contact_once() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+    let order = create_test_order();
+
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": []
+        })))
+        .mount(&mock_server)
+        .await;
+    mock_countries(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "objects": {
+                "id": "456",
+                "name": "Test Customer",
+                "objectName": "Contact",
+                "customerNumber": null,
+                "status": null
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let first = api.prepare_contact(&order).await;
+    assert_eq!(first.contact_id, Some(456));
+    assert!(first.created);
+
+    // The second lookup is served from the cache and reports an existing contact
+    let second = api.prepare_contact(&order).await;
+    assert_eq!(second.contact_id, Some(456));
+    assert!(!second.created);
+}
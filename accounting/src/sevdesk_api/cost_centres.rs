@@ -0,0 +1,66 @@
+//! Cost centre (Kostenstelle) lookup.
+
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+
+use crate::models::{CostCentreResponse, SevDeskResponse};
+
+use super::rate_limit::RequestExt;
+use super::SevDeskApi;
+
+impl SevDeskApi {
+    /// Fetches all cost centres from SevDesk.
+    pub async fn fetch_cost_centres(&self) -> Result<Vec<CostCentreResponse>> {
+        info!("Fetching cost centres from SevDesk");
+        let url = format!("{}/CostCentre", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", &self.api_token)
+            .send_paced(&self.pacer)
+            .await
+            .context("Failed to fetch cost centres")?;
+
+        let status = response.status();
+        debug!("Cost centres response status: {status}");
+
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            warn!("Failed to fetch cost centres: {error_text}");
+            anyhow::bail!("Failed to fetch cost centres: {}", status);
+        }
+
+        let centres: SevDeskResponse<CostCentreResponse> = response
+            .json()
+            .await
+            .context("Failed to parse cost centres response")?;
+        Ok(centres.objects.unwrap_or_default())
+    }
+
+    /// ID of the configured cost centre, looked up by name (ignoring case)
+    /// on first use; `None` without one. An unknown name is an error, so no
+    /// invoice is created without its cost centre.
+    pub(crate) async fn cost_centre_id(&self) -> Result<Option<u32>> {
+        let Some(name) = self.cost_centre.as_deref() else {
+            return Ok(None);
+        };
+        let id = self
+            .cost_centre_id
+            .get_or_try_init(|| async {
+                let centres = self.fetch_cost_centres().await?;
+                let centre = centres
+                    .iter()
+                    .find(|c| c.name.trim().eq_ignore_ascii_case(name.trim()))
+                    .ok_or_else(|| anyhow::anyhow!("Unknown SevDesk cost centre '{name}'"))?;
+                let id = centre
+                    .id
+                    .parse::<u32>()
+                    .context("Failed to parse cost centre ID")?;
+                info!("Using cost centre '{}' (ID: {id})", centre.name);
+                Ok::<u32, anyhow::Error>(id)
+            })
+            .await?;
+        Ok(Some(*id))
+    }
+}
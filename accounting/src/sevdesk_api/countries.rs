@@ -7,6 +7,7 @@ use log::{debug, error, info, warn};
 
 use crate::models::{SevDeskResponse, StaticCountryResponse};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 /// Cached country data with both name variants mapped to ID.
@@ -41,7 +42,7 @@ impl SevDeskApi {
             .get(&url)
             .header("Authorization", &self.api_token)
             .query(&[("limit", "500")]) // Fetch all countries
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to fetch countries")?;
 
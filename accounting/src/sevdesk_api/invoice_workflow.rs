@@ -5,7 +5,9 @@ use log::{debug, error, info, warn};
 use std::path::Path;
 
 use crate::models::{InvoiceWorkflowOptions, InvoiceWorkflowStatus, SendType};
+use crate::pdf_queue::{PdfDownloadOutcome, PdfDownloadSettings, PdfJob};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
@@ -164,7 +166,7 @@ impl SevDeskApi {
             .header("Authorization", &self.api_token)
             .header("Content-Type", "application/json")
             .json(&body)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to send finalize request")?;
 
@@ -196,7 +198,7 @@ impl SevDeskApi {
             .client
             .put(&url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to send enshrine request")?;
 
@@ -286,7 +288,7 @@ impl SevDeskApi {
             .header("Authorization", &self.api_token)
             .header("Content-Type", "application/json")
             .json(&body)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to send book request")?;
 
@@ -344,7 +346,7 @@ impl SevDeskApi {
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to get invoice")?;
 
@@ -412,7 +414,7 @@ impl SevDeskApi {
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to send PDF download request")?;
 
@@ -483,6 +485,86 @@ impl SevDeskApi {
         Ok(pdf_path)
     }
 
+    /// Downloads queued PDFs, at most `settings.concurrency` at a time.
+    ///
+    /// Returns one outcome per job, in completion order. Failed jobs come
+    /// back with their attempt count and last error updated.
+    pub async fn download_pdfs(
+        &self,
+        jobs: Vec<PdfJob>,
+        settings: PdfDownloadSettings,
+    ) -> Vec<PdfDownloadOutcome> {
+        info!(
+            "Downloading {} queued PDFs ({} at a time)",
+            jobs.len(),
+            settings.concurrency
+        );
+        let mut pending = jobs.into_iter();
+        let mut running = tokio::task::JoinSet::new();
+        let mut outcomes = Vec::new();
+        loop {
+            while running.len() < settings.concurrency.max(1) {
+                let Some(job) = pending.next() else {
+                    break;
+                };
+                let api = self.clone();
+                running.spawn(async move { api.download_pdf_job(job, settings).await });
+            }
+            match running.join_next().await {
+                Some(Ok(outcome)) => outcomes.push(outcome),
+                // The job stays queued and is retried on the next run
+                Some(Err(e)) => error!("PDF download task failed: {}", e),
+                None => break,
+            }
+        }
+        outcomes
+    }
+
+    /// Downloads one queued PDF, retrying with a doubling delay.
+    async fn download_pdf_job(
+        &self,
+        mut job: PdfJob,
+        settings: PdfDownloadSettings,
+    ) -> PdfDownloadOutcome {
+        let mut delay = settings.retry_delay;
+        for attempt in 1..=settings.attempts.max(1) {
+            match self
+                .download_invoice_pdf(job.invoice_id, &job.invoice_number, &job.directory)
+                .await
+            {
+                Ok(path) => {
+                    job.last_error = None;
+                    return PdfDownloadOutcome {
+                        job,
+                        result: Ok(path),
+                    };
+                }
+                Err(e) => {
+                    job.attempts += 1;
+                    job.last_error = Some(e.to_string());
+                    if attempt < settings.attempts {
+                        warn!(
+                            "PDF download for invoice #{} failed (try {}), retrying in {:?}: {}",
+                            job.invoice_number, attempt, delay, e
+                        );
+                        tokio::time::sleep(delay).await;
+                        delay *= 2;
+                    }
+                }
+            }
+        }
+        warn!(
+            "Giving up on PDF for invoice #{} for now: {}",
+            job.invoice_number,
+            job.last_error.as_deref().unwrap_or_default()
+        );
+        let error = job.last_error.clone().unwrap_or_default();
+        PdfDownloadOutcome {
+            job,
+            result: Err(error),
+        }
+    }
+
     /// Simulates the invoice workflow without making actual API calls.
     pub async fn simulate_invoice_workflow(
         &self,
@@ -4,13 +4,14 @@ use wiremock::matchers::{header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use crate::models::{InvoiceWorkflowOptions, SendType};
+use crate::pdf_queue::{PdfDownloadSettings, PdfJob};
 use crate::sevdesk_api::SevDeskApi;
 
 /// Creates a SevDeskApi pointing at the given mock server.
 fn api_with_mock(mock_uri: &str) -> SevDeskApi {
-    let mut api = SevDeskApi::new("test_token".to_string());
-    api.base_url = mock_uri.to_string();
-    api
+    SevDeskApi::new("test_token".to_string())
+        .with_base_url(mock_uri)
+        .with_request_interval(std::time::Duration::ZERO)
 }
 
 // ── finalize_invoice ─────────────────────────────────────────────────
@@ -374,6 +375,84 @@ async fn download_pdf_404_not_found() {
     assert!(result.is_err());
 }
 
+// ── download_pdfs ────────────────────────────────────────────────────
+
+fn quick_retries() -> PdfDownloadSettings {
+    PdfDownloadSettings {
+        concurrency: 2,
+        attempts: 3,
+        retry_delay: std::time::Duration::from_millis(1),
+    }
+}
+
+#[tokio::test]
+async fn download_pdfs_retries_until_success() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/Invoice/100/getPdf"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/Invoice/100/getPdf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4 test".to_vec()))
+        .mount(&mock_server)
+        .await;
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let jobs = vec![PdfJob::new(100, "INV-100", tmp_dir.path())];
+    let outcomes = api.download_pdfs(jobs, quick_retries()).await;
+
+    assert_eq!(outcomes.len(), 1);
+    assert_eq!(
+        outcomes[0].result.as_ref().unwrap(),
+        &tmp_dir.path().join("INV-100.pdf")
+    );
+    assert_eq!(outcomes[0].job.attempts, 2);
+}
+
+#[tokio::test]
+async fn download_pdfs_reports_failures_per_job() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+
+    Mock::given(method("GET"))
+        .and(path("/Invoice/100/getPdf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4 test".to_vec()))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/Invoice/200/getPdf"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("broken"))
+        .expect(3)
+        .mount(&mock_server)
+        .await;
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let jobs = vec![
+        PdfJob::new(100, "INV-100", tmp_dir.path()),
+        PdfJob::new(200, "INV-200", tmp_dir.path()),
+        PdfJob::new(100, "INV-100-copy", tmp_dir.path()),
+    ];
+    let mut outcomes = api.download_pdfs(jobs, quick_retries()).await;
+    outcomes.sort_by(|a, b| a.job.invoice_number.cmp(&b.job.invoice_number));
+
+    assert_eq!(outcomes.len(), 3);
+    assert!(outcomes[0].result.is_ok());
+    assert!(outcomes[1].result.is_ok());
+    let failed = &outcomes[2];
+    assert_eq!(failed.job.invoice_number, "INV-200");
+    assert_eq!(failed.job.attempts, 3);
+    assert!(failed.result.as_ref().unwrap_err().contains("500"));
+    assert_eq!(
+        failed.job.last_error.as_deref(),
+        failed.result.as_ref().err().map(String::as_str)
+    );
+}
+
 // ── execute_invoice_workflow ─────────────────────────────────────────
 
 #[tokio::test]
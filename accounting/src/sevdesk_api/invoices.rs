@@ -1,15 +1,18 @@
 //! Invoice creation and management.
 
 use anyhow::{Context, Result};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 
-use crate::csv_processor::field_parsers::parse_price;
+use crate::invoice_drafts::InvoiceDraft;
 use crate::models::{
-    InvoiceCreationResult, InvoiceResponse, OrderRecord, SevDeskContactRef, SevDeskCountry,
-    SevDeskInvoice, SevDeskInvoicePos, SevDeskInvoiceRef, SevDeskSingleObjectResponse,
-    SevDeskTaxRule, SevDeskUnity, SevDeskUser,
+    InvoiceCreationResult, InvoiceResponse, InvoiceTexts, OrderRecord, SevDeskContactRef,
+    SevDeskCostCentre, SevDeskCountry, SevDeskInvoice, SevDeskInvoicePos, SevDeskInvoiceRef,
+    SevDeskSingleObjectResponse, SevDeskTaxRule, SevDeskUnity, SevDeskUser, WorkflowOverride,
 };
+use crate::order_grouping::{combined_positions, latest_order, order_reference};
+use crate::reconciliation::{reconciled_positions, PlannedPosition};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
@@ -48,6 +51,60 @@ impl SevDeskApi {
         }
     }
 
+    /// Creates one invoice for a buyer's orders, with a section of
+    /// positions per order. The result carries the joined order ids; a
+    /// single-order group is invoiced exactly like [`Self::create_invoice`].
+    pub async fn create_combined_invoice(
+        &self,
+        group: &[OrderRecord],
+    ) -> Result<InvoiceCreationResult> {
+        if let [order] = group {
+            return self.create_invoice(order).await;
+        }
+        let order = latest_order(group);
+        let reference = order_reference(group);
+        info!(
+            "Creating combined invoice for orders: {reference} ({})",
+            order.name
+        );
+
+        let created = match combined_positions(
+            group,
+            &self.reconciliation,
+            self.locale,
+            self.invoice_language(order),
+        ) {
+            Ok(positions) => {
+                let texts = self.invoice_texts_for(order).render(&reference);
+                self.create_invoice_with_positions(order, texts, &positions)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let (invoice_id, invoice_number, error) = match created {
+            Ok((invoice_id, invoice_number)) => {
+                info!("Successfully created invoice: {invoice_number} for orders {reference}");
+                (
+                    Some(invoice_id.parse().unwrap_or(0)),
+                    Some(invoice_number),
+                    None,
+                )
+            }
+            Err(e) => {
+                error!("Failed to create invoice for orders {reference}: {e}");
+                (None, None, Some(e.to_string()))
+            }
+        };
+        Ok(InvoiceCreationResult {
+            order_id: reference,
+            customer_name: order.name.clone(),
+            invoice_id,
+            invoice_number,
+            error,
+            workflow_status: None,
+        })
+    }
+
     /// Internal implementation of invoice creation.
     pub(crate) async fn create_invoice_internal(
         &self,
@@ -58,19 +115,111 @@ impl SevDeskApi {
             order.order_id
         );
 
+        // Positions are checked against the order total before anything is
+        // created in SevDesk
+        let positions = reconciled_positions(order, &self.reconciliation, self.locale)?;
+        let texts = self.invoice_texts_for(order).render(&order.order_id);
+        self.create_invoice_with_positions(order, texts, &positions)
+            .await
+    }
+
+    /// The invoice for a group of orders as it would be created, without
+    /// any API calls. Positions that fail to plan leave the draft with an
+    /// error and excluded.
+    pub fn draft_invoice(&self, group: &[OrderRecord]) -> InvoiceDraft {
+        let order = latest_order(group);
+        let reference = order_reference(group);
+        let (positions, error) = match combined_positions(
+            group,
+            &self.reconciliation,
+            self.locale,
+            self.invoice_language(order),
+        ) {
+            Ok(positions) => (positions, None),
+            Err(e) => {
+                warn!("Draft for orders {reference} has no positions: {e}");
+                (Vec::new(), Some(e.to_string()))
+            }
+        };
+        InvoiceDraft {
+            orders: group.to_vec(),
+            texts: self.invoice_texts_for(order).render(&reference),
+            reference,
+            language: self.invoice_language(order),
+            tax_mode: self.tax_mode,
+            positions,
+            included: error.is_none(),
+            error,
+            workflow: WorkflowOverride::default(),
+        }
+    }
+
+    /// Creates the invoice exactly as reviewed: the draft's texts and
+    /// (possibly edited) positions instead of freshly planned ones.
+    pub async fn create_invoice_from_draft(
+        &self,
+        draft: &InvoiceDraft,
+    ) -> Result<InvoiceCreationResult> {
+        let order = draft.order();
+        info!(
+            "Creating reviewed invoice for orders: {} ({})",
+            draft.reference, order.name
+        );
+
+        let created = match &draft.error {
+            Some(e) => Err(anyhow::anyhow!("{e}")),
+            None => {
+                self.create_invoice_with_positions(order, draft.texts.clone(), &draft.positions)
+                    .await
+            }
+        };
+        let (invoice_id, invoice_number, error) = match created {
+            Ok((invoice_id, invoice_number)) => {
+                info!(
+                    "Successfully created invoice: {invoice_number} for orders {}",
+                    draft.reference
+                );
+                (
+                    Some(invoice_id.parse().unwrap_or(0)),
+                    Some(invoice_number),
+                    None,
+                )
+            }
+            Err(e) => {
+                error!(
+                    "Failed to create invoice for orders {}: {e}",
+                    draft.reference
+                );
+                (None, None, Some(e.to_string()))
+            }
+        };
+        Ok(InvoiceCreationResult {
+            order_id: draft.reference.clone(),
+            customer_name: order.name.clone(),
+            invoice_id,
+            invoice_number,
+            error,
+            workflow_status: None,
+        })
+    }
+
+    /// Creates a draft invoice addressed to `order`'s buyer and dated on its
+    /// purchase, with the given texts, and adds `positions`.
+    async fn create_invoice_with_positions(
+        &self,
+        order: &OrderRecord,
+        texts: InvoiceTexts,
+        positions: &[PlannedPosition],
+    ) -> Result<(String, String)> {
+        // An unknown cost centre fails before anything is created
+        let cost_centre = self.cost_centre_id().await?;
+
         // Get or create contact
         let contact_id = self.get_or_create_contact(order).await?;
 
         // Get current user ID
         let user_id = self.get_current_user().await?;
 
-        // Parse prices
-        let merchandise_value = parse_price(&order.merchandise_value)?;
-        let shipment_costs = parse_price(&order.shipment_costs)?;
-        let _total_value = parse_price(&order.total_value)?;
-
-        debug!("Parsed prices - merchandise: {merchandise_value}, shipping: {shipment_costs}");
-
         // Create invoice
         let country_id = self.get_country_id(&order.country).await?;
 
@@ -92,9 +241,9 @@ impl SevDeskApi {
                 .next()
                 .unwrap_or("")
                 .to_string(),
-            header: format!("Rechnung für Bestellnummer {}", order.order_id),
-            head_text: Some("Vielen Dank für Ihre Bestellung.".to_string()),
-            foot_text: Some("Betrag beglichen.".to_string()),
+            header: texts.header,
+            head_text: Some(texts.head_text).filter(|t| !t.is_empty()),
+            foot_text: Some(texts.foot_text).filter(|t| !t.is_empty()),
             address: Some(formatted_address),
             address_country: SevDeskCountry {
                 id: country_id,
@@ -112,15 +261,19 @@ impl SevDeskApi {
                 id: user_id,
                 object_name: "SevUser".to_string(),
             },
-            tax_rate: 0.0, // No VAT for Kleingewerbe
-            tax_text: "Kleinunternehmerregelung §19 UStG".to_string(),
+            tax_rate: self.tax_mode.rate(),
+            tax_text: self.tax_mode.tax_text().to_string(),
             tax_rule: SevDeskTaxRule {
-                id: 11, // Tax rule 11 for Kleingewerbe
+                id: self.tax_mode.tax_rule_id(),
                 object_name: "TaxRule".to_string(),
             },
             dunning_level: None,
             invoice_type: "RE".to_string(), // Regular invoice
             currency: order.currency.clone(),
+            cost_centre: cost_centre.map(|id| SevDeskCostCentre {
+                id,
+                object_name: "CostCentre".to_string(),
+            }),
         };
 
         let create_invoice_url = format!("{}/Invoice", self.base_url);
@@ -132,7 +285,7 @@ impl SevDeskApi {
             .header("Authorization", &self.api_token)
             .header("Content-Type", "application/json")
             .json(&invoice)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to create invoice")?;
 
@@ -166,76 +319,28 @@ impl SevDeskApi {
         let invoice_number = created_invoice.objects.invoice_number;
         debug!("Created invoice with ID: {invoice_id} and number: {invoice_number}");
 
-        // Add each item as a separate invoice position
-        let mut position_number = 1;
-
-        if order.items.len() > 1 {
-            info!("Adding {} individual items to invoice", order.items.len());
-            for item in &order.items {
-                debug!(
-                    "Adding item position {}: {} x {} @ {:.2} EUR",
-                    position_number, item.quantity, item.localized_product_name, item.price
-                );
-                self.add_invoice_position(
-                    &invoice_id,
-                    position_number,
-                    &item.localized_product_name,
-                    &item.description,
-                    item.quantity as f64, // Use the extracted quantity from description
-                    item.price,
-                )
-                .await?;
-                position_number += 1;
-            }
-        } else if !order.items.is_empty() {
-            // Single item order
-            let item = &order.items[0];
+        info!("Adding {} positions to invoice", positions.len());
+        for (position_number, position) in (1..).zip(positions) {
             debug!(
-                "Adding single item position: {} x {} @ {:.2} EUR",
-                item.quantity, item.localized_product_name, item.price
+                "Adding position {position_number}: {} x {} @ {:.2} EUR",
+                position.quantity, position.name, position.price_gross
             );
             self.add_invoice_position(
                 &invoice_id,
                 position_number,
-                &item.localized_product_name,
-                &item.description,
-                item.quantity as f64, // Use the extracted quantity from description
-                item.price,
+                &position.name,
+                &position.description,
+                position.quantity,
+                position.price_gross,
             )
             .await?;
-            position_number += 1;
-        } else {
-            // Fallback to original merchandise value
-            debug!(
-                "No items found, using fallback merchandise position: {} x {} = {}",
-                order.article_count,
-                merchandise_value,
-                order.article_count as f64 * merchandise_value
-            );
-            self.add_invoice_position(
-                &invoice_id,
-                position_number,
-                &order.localized_product_name,
-                &order.description,
-                order.article_count as f64,
-                merchandise_value,
-            )
-            .await?;
-            position_number += 1;
         }
 
-        // Add shipping costs as separate position if any
-        if shipment_costs > 0.0 {
-            debug!("Adding shipping position: {shipment_costs}");
-            self.add_invoice_position(
-                &invoice_id,
-                position_number,
-                "Shipping",
-                "Shipping costs",
-                1.0,
-                shipment_costs,
-            )
-            .await?;
+        // The invoice exists by now, so a failed tag is only logged
+        for tag in &self.invoice_tags {
+            if let Err(e) = self.tag_invoice(&invoice_id, tag).await {
+                warn!("Invoice {invoice_number} was created without tag '{tag}': {e}");
+            }
         }
 
         Ok((invoice_id, invoice_number))
@@ -253,13 +358,13 @@ impl SevDeskApi {
     ) -> Result<()> {
         debug!("Adding invoice position {position_number}: {quantity} x {name} @ {price_gross}");
 
-        // For Kleingewerbe (tax rule 11), no VAT is applied
-        let tax_rate = 0.0; // No VAT for Kleingewerbe
-        let price_net = price_gross; // Price is the same as gross since no VAT
-        let price_tax = 0.0; // No tax
+        // Order prices are gross; under Kleingewerbe net equals gross
+        let tax_rate = self.tax_mode.rate();
+        let (price_net, price_tax) = self.tax_mode.split_gross(price_gross);
 
         debug!(
-            "Kleingewerbe pricing - net: {price_net:.2}, tax: {price_tax:.2}, gross: {price_gross:.2}"
+            "{:?} pricing - net: {price_net:.2}, tax: {price_tax:.2}, gross: {price_gross:.2}",
+            self.tax_mode
         );
 
         let position = SevDeskInvoicePos {
@@ -293,7 +398,7 @@ impl SevDeskApi {
             .header("Authorization", &self.api_token)
             .header("Content-Type", "application/json")
             .json(&position)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to create invoice position")?;
 
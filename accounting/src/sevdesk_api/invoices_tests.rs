@@ -1,15 +1,15 @@
 //! Tests for invoice creation and position management.
 
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
-use crate::models::{OrderItem, OrderRecord};
+use crate::models::{InvoiceLanguage, InvoiceTexts, OrderItem, OrderRecord, TaxMode};
 use crate::sevdesk_api::SevDeskApi;
 
 fn api_with_mock(mock_uri: &str) -> SevDeskApi {
-    let mut api = SevDeskApi::new("test_token".to_string());
-    api.base_url = mock_uri.to_string();
-    api
+    SevDeskApi::new("test_token".to_string())
+        .with_base_url(mock_uri)
+        .with_request_interval(std::time::Duration::ZERO)
 }
 
 fn create_test_order() -> OrderRecord {
@@ -124,6 +124,30 @@ async fn add_invoice_position_500_error() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn add_invoice_position_standard_tax_splits_gross() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri())
+        .with_invoice_settings(TaxMode::Standard, InvoiceTexts::default());
+
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .and(body_partial_json(serde_json::json!({
+            "taxRate": 19.0,
+            "priceNet": 10.0,
+            "priceTax": 1.9,
+            "priceGross": 11.9
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_string("{}"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    api.add_invoice_position("INV-100", 1, "Sol Ring", "C21 NM", 1.0, 11.90)
+        .await
+        .unwrap();
+}
+
 // ── create_invoice_internal ──────────────────────────────────────────
 
 #[tokio::test]
@@ -158,6 +182,142 @@ async fn create_invoice_single_item() {
     assert_eq!(invoice_number, "RE-2025-001");
 }
 
+#[tokio::test]
+async fn create_invoice_uses_english_texts_abroad_unless_overridden() {
+    let mock_server = MockServer::start().await;
+    let mut order = create_test_order();
+    order.country = "France".to_string();
+
+    mock_invoice_dependencies(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .and(body_partial_json(serde_json::json!({
+            "header": "Invoice for order number ORD-001",
+            "footText": "Amount paid in full."
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "objects": { "id": "501", "invoiceNumber": "RE-2025-002" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .and(body_partial_json(serde_json::json!({
+            "header": "Rechnung für Bestellnummer ORD-001"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "objects": { "id": "502", "invoiceNumber": "RE-2025-003" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("{}"))
+        .mount(&mock_server)
+        .await;
+
+    let api = api_with_mock(&mock_server.uri());
+    let (invoice_id, _) = api.create_invoice_internal(&order).await.unwrap();
+    assert_eq!(invoice_id, "501");
+
+    let api =
+        api.with_language_overrides([("ORD-001".to_string(), InvoiceLanguage::German)].into());
+    let (invoice_id, _) = api.create_invoice_internal(&order).await.unwrap();
+    assert_eq!(invoice_id, "502");
+}
+
+#[tokio::test]
+async fn create_invoice_books_cost_centre_and_attaches_tags() {
+    let mock_server = MockServer::start().await;
+    mock_invoice_dependencies(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/CostCentre"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": [
+                { "id": "3", "name": "Privat" },
+                { "id": "7", "name": "Cardmarket" }
+            ]
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .and(body_partial_json(serde_json::json!({
+            "costCentre": { "id": 7, "objectName": "CostCentre" }
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "objects": { "id": "503", "invoiceNumber": "RE-2025-004" }
+        })))
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("{}"))
+        .mount(&mock_server)
+        .await;
+    for tag in ["Cardmarket", "MTG"] {
+        Mock::given(method("POST"))
+            .and(path("/Tag/Factory/create"))
+            .and(body_partial_json(serde_json::json!({
+                "name": tag,
+                "object": { "id": "503", "objectName": "Invoice" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+    }
+
+    let api = api_with_mock(&mock_server.uri()).with_cost_centre_and_tags(
+        Some("cardmarket".to_string()),
+        vec!["Cardmarket".to_string(), "MTG".to_string()],
+    );
+    // The cost centre is looked up once, also by clones
+    api.create_invoice_internal(&create_test_order())
+        .await
+        .unwrap();
+    let (invoice_id, _) = api
+        .clone()
+        .create_invoice_internal(&create_test_order())
+        .await
+        .unwrap();
+    assert_eq!(invoice_id, "503");
+}
+
+#[tokio::test]
+async fn create_invoice_fails_for_unknown_cost_centre() {
+    let mock_server = MockServer::start().await;
+    mock_invoice_dependencies(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/CostCentre"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "objects": [{ "id": "3", "name": "Privat" }]
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("{}"))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let api = api_with_mock(&mock_server.uri())
+        .with_cost_centre_and_tags(Some("Cardmarket".to_string()), Vec::new());
+    let err = api
+        .create_invoice_internal(&create_test_order())
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Unknown SevDesk cost centre 'Cardmarket'"));
+}
+
 #[tokio::test]
 async fn create_invoice_multiple_items() {
     let mock_server = MockServer::start().await;
@@ -339,3 +499,93 @@ async fn create_invoice_fallback_no_items() {
     let result = api.create_invoice_internal(&order).await;
     assert!(result.is_ok());
 }
+
+// ── invoice drafts ───────────────────────────────────────────────────
+
+#[test]
+fn draft_invoice_plans_positions_and_texts() {
+    let api = SevDeskApi::new("test_token".to_string())
+        .with_invoice_settings(TaxMode::Standard, InvoiceTexts::default());
+
+    let draft = api.draft_invoice(&[create_test_order()]);
+
+    assert_eq!(draft.reference, "ORD-001");
+    assert_eq!(draft.texts.header, "Rechnung für Bestellnummer ORD-001");
+    assert_eq!(draft.tax_mode, TaxMode::Standard);
+    assert_eq!(draft.total_gross(), 10.0);
+    assert!(draft.included);
+    assert!(draft.error.is_none());
+}
+
+#[test]
+fn draft_invoice_with_total_mismatch_is_excluded() {
+    let api = SevDeskApi::new("test_token".to_string());
+    let mut order = create_test_order();
+    order.total_value = "25,00".to_string();
+
+    let draft = api.draft_invoice(&[order]);
+
+    assert!(draft.positions.is_empty());
+    assert!(draft.error.is_some());
+    assert!(!draft.is_approved());
+}
+
+#[tokio::test]
+async fn create_invoice_from_draft_uses_edited_positions() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+
+    mock_invoice_dependencies(&mock_server).await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "objects": { "id": "510", "invoiceNumber": "RE-2025-010" }
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Lightning Bolt (edited)"
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_string("{}"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let mut draft = api.draft_invoice(&[create_test_order()]);
+    draft.positions[0].name = "Lightning Bolt (edited)".to_string();
+    let result = api.create_invoice_from_draft(&draft).await.unwrap();
+
+    assert_eq!(result.order_id, "ORD-001");
+    assert_eq!(result.invoice_number.as_deref(), Some("RE-2025-010"));
+    assert!(result.error.is_none());
+}
+
+#[tokio::test]
+async fn annotate_invoice_sets_internal_note() {
+    let mock_server = MockServer::start().await;
+    let api = api_with_mock(&mock_server.uri());
+
+    Mock::given(method("PUT"))
+        .and(path("/Invoice/510"))
+        .and(header("Authorization", "test_token"))
+        .and(body_partial_json(serde_json::json!({
+            "customerInternalNote": "Sendungsnummer: RR123456789DE"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/Invoice/511"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("invoice is locked"))
+        .mount(&mock_server)
+        .await;
+
+    api.annotate_invoice(510, "Sendungsnummer: RR123456789DE")
+        .await
+        .unwrap();
+    let err = api.annotate_invoice(511, "x").await.unwrap_err();
+    assert!(err.to_string().contains("invoice is locked"));
+}
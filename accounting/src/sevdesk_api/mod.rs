@@ -2,33 +2,87 @@
 //!
 //! This module provides functionality to interact with the SevDesk API,
 //! including creating invoices, managing contacts, and handling country lookups.
+//! Requests are paced and rate-limited ones retried transparently (see
+//! `rate_limit`).
 
 mod check_accounts;
 mod client;
 mod contacts;
+mod cost_centres;
 mod countries;
 mod invoice_workflow;
 mod invoices;
+mod notes;
+mod rate_limit;
 mod simulation;
+mod tags;
 mod users;
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 
 use std::time::Duration;
 
+use mtg_common::DecimalLocale;
 use reqwest::Client;
 
 use countries::CountryCache;
+use rate_limit::RequestPacer;
+
+use crate::models::{InvoiceLanguage, InvoiceTexts, OrderRecord, TaxMode};
+use crate::reconciliation::ReconciliationSettings;
+
+/// Contact IDs resolved so far, keyed by customer name (the field the
+/// contact search matches on). Shared between API clients so a "prepare
+/// contacts" pass can be reused by the following invoice run.
+pub type ContactCache = Arc<RwLock<HashMap<String, u32>>>;
 
 const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// The public SevDesk API, used unless another base URL is configured.
+pub const DEFAULT_BASE_URL: &str = "https://my.sevdesk.de/api/v1";
+
+/// Builds the HTTP client with the shared proxy/timeout settings. A broken
+/// setting (e.g. an unreadable CA file) is logged and the defaults are used.
+fn build_client() -> Client {
+    mtg_common::http::client_builder(HTTP_TIMEOUT)
+        .and_then(|builder| Ok(builder.build()?))
+        .unwrap_or_else(|e| {
+            log::error!("Invalid HTTP configuration, using defaults: {e}");
+            Client::builder()
+                .timeout(HTTP_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client")
+        })
+}
+
 /// SevDesk API client for creating invoices and managing contacts.
+///
+/// Cloning is cheap; clones share the HTTP client and caches.
+#[derive(Clone)]
 pub struct SevDeskApi {
     pub(crate) client: Client,
     pub(crate) api_token: String,
     pub(crate) base_url: String,
     pub(crate) country_cache: Arc<RwLock<CountryCache>>,
+    pub(crate) contact_cache: ContactCache,
+    pub(crate) tax_mode: TaxMode,
+    pub(crate) invoice_texts: InvoiceTexts,
+    pub(crate) english_texts: InvoiceTexts,
+    /// Invoice language chosen by hand, keyed by order ID
+    pub(crate) language_overrides: HashMap<String, InvoiceLanguage>,
+    pub(crate) reconciliation: ReconciliationSettings,
+    /// Number format of the CSV the orders were loaded from
+    pub(crate) locale: DecimalLocale,
+    /// Name of the cost centre every invoice is booked on
+    pub(crate) cost_centre: Option<String>,
+    /// Resolved ID of `cost_centre`, shared between clones
+    pub(crate) cost_centre_id: Arc<OnceCell<u32>>,
+    /// Tags attached to every created invoice
+    pub(crate) invoice_tags: Vec<String>,
+    /// Spaces requests to the API host and retries 429s (see `rate_limit`)
+    pub(crate) pacer: RequestPacer,
 }
 
 impl SevDeskApi {
@@ -36,14 +90,116 @@ impl SevDeskApi {
     pub fn new(api_token: String) -> Self {
         log::info!("Creating SevDesk API client");
         log::debug!("API token length: {}", api_token.len());
+        let base_url = DEFAULT_BASE_URL.to_string();
         Self {
-            client: Client::builder()
-                .timeout(HTTP_TIMEOUT)
-                .build()
-                .expect("Failed to build HTTP client"),
+            client: build_client(),
             api_token,
-            base_url: "https://my.sevdesk.de/api/v1".to_string(),
+            pacer: RequestPacer::for_host(&base_url),
+            base_url,
             country_cache: Arc::new(RwLock::new(CountryCache::default())),
+            contact_cache: ContactCache::default(),
+            tax_mode: TaxMode::default(),
+            invoice_texts: InvoiceTexts::default(),
+            english_texts: InvoiceTexts::english(),
+            language_overrides: HashMap::new(),
+            reconciliation: ReconciliationSettings::default(),
+            locale: DecimalLocale::default(),
+            cost_centre: None,
+            cost_centre_id: Arc::default(),
+            invoice_tags: Vec::new(),
+        }
+    }
+
+    /// Uses a shared contact cache instead of the client's private one.
+    pub fn with_contact_cache(mut self, contact_cache: ContactCache) -> Self {
+        self.contact_cache = contact_cache;
+        self
+    }
+
+    /// Creates invoices with the given VAT treatment and texts instead of the
+    /// Kleingewerbe defaults.
+    pub fn with_invoice_settings(mut self, tax_mode: TaxMode, invoice_texts: InvoiceTexts) -> Self {
+        self.tax_mode = tax_mode;
+        self.invoice_texts = invoice_texts;
+        self
+    }
+
+    /// Uses these texts for buyers outside the German-speaking countries.
+    pub fn with_english_texts(mut self, english_texts: InvoiceTexts) -> Self {
+        self.english_texts = english_texts;
+        self
+    }
+
+    /// Writes the given orders' invoices in the chosen language instead of
+    /// the one detected from the country.
+    pub fn with_language_overrides(
+        mut self,
+        language_overrides: HashMap<String, InvoiceLanguage>,
+    ) -> Self {
+        self.language_overrides = language_overrides;
+        self
+    }
+
+    /// Talks to another SevDesk-compatible endpoint instead of the public
+    /// API, e.g. a mock server or a sandbox. Every request, PDF downloads
+    /// included, goes to this URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim().trim_end_matches('/').to_string();
+        self.pacer = RequestPacer::for_host(&self.base_url);
+        self
+    }
+
+    /// Spaces requests `interval` apart on a schedule of its own instead of
+    /// the one shared by all clients of the API host — e.g. no pacing
+    /// against a mock server.
+    #[allow(dead_code)] // Only used by the tests
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.pacer = RequestPacer::new(interval);
+        self
+    }
+
+    /// Sets how invoice positions are reconciled with the order total.
+    pub fn with_reconciliation(mut self, reconciliation: ReconciliationSettings) -> Self {
+        self.reconciliation = reconciliation;
+        self
+    }
+
+    /// Parses order totals in the number format the orders' CSV was loaded
+    /// with, so invoices reconcile against the same values they were
+    /// itemized from.
+    pub fn with_locale(mut self, locale: DecimalLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Books every invoice on the cost centre with this name (looked up in
+    /// SevDesk before the first invoice) and attaches the given tags. A blank
+    /// name means no cost centre.
+    pub fn with_cost_centre_and_tags(
+        mut self,
+        cost_centre: Option<String>,
+        invoice_tags: Vec<String>,
+    ) -> Self {
+        self.cost_centre = cost_centre.filter(|name| !name.trim().is_empty());
+        self.cost_centre_id = Arc::default();
+        self.invoice_tags = invoice_tags;
+        self
+    }
+
+    /// The language of the order's invoice: the override if there is one,
+    /// otherwise detected from the shipping country.
+    pub fn invoice_language(&self, order: &OrderRecord) -> InvoiceLanguage {
+        self.language_overrides
+            .get(&order.order_id)
+            .copied()
+            .unwrap_or_else(|| InvoiceLanguage::for_country(&order.country))
+    }
+
+    /// The unrendered texts for the order's invoice language.
+    pub(crate) fn invoice_texts_for(&self, order: &OrderRecord) -> &InvoiceTexts {
+        match self.invoice_language(order) {
+            InvoiceLanguage::German => &self.invoice_texts,
+            InvoiceLanguage::English => &self.english_texts,
         }
     }
 }
@@ -20,3 +20,27 @@ fn api_has_default_base_url() {
     let api = SevDeskApi::new("test_token".to_string());
     assert_eq!(api.base_url, "https://my.sevdesk.de/api/v1");
 }
+
+#[test]
+fn with_base_url_drops_trailing_slash() {
+    let api =
+        SevDeskApi::new("test_token".to_string()).with_base_url(" http://localhost:8089/api/v1/ ");
+    assert_eq!(api.base_url, "http://localhost:8089/api/v1");
+}
+
+#[test]
+fn new_api_starts_with_empty_contact_cache() {
+    let api = SevDeskApi::new("test_token".to_string());
+    assert!(api.contact_cache.try_read().unwrap().is_empty());
+}
+
+#[test]
+fn with_contact_cache_shares_the_cache() {
+    let cache = crate::sevdesk_api::ContactCache::default();
+    let api = SevDeskApi::new("test_token".to_string()).with_contact_cache(cache.clone());
+    cache.try_write().unwrap().insert("Customer".to_string(), 1);
+    assert_eq!(
+        api.contact_cache.try_read().unwrap().get("Customer"),
+        Some(&1)
+    );
+}
@@ -0,0 +1,38 @@
+//! Internal invoice notes.
+
+use anyhow::{Context, Result};
+use log::{debug, error};
+
+use crate::models::SevDeskInvoiceNote;
+
+use super::rate_limit::RequestExt;
+use super::SevDeskApi;
+
+impl SevDeskApi {
+    /// Sets the invoice's internal note (not printed on the invoice),
+    /// replacing any previous note.
+    pub async fn annotate_invoice(&self, invoice_id: u32, note: &str) -> Result<()> {
+        debug!("Setting note of invoice {invoice_id}: {note}");
+        let request = SevDeskInvoiceNote {
+            customer_internal_note: note.to_string(),
+        };
+
+        let response = self
+            .client
+            .put(format!("{}/Invoice/{invoice_id}", self.base_url))
+            .header("Authorization", &self.api_token)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send_paced(&self.pacer)
+            .await
+            .context("Failed to update invoice note")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Setting the note of invoice {invoice_id} failed with status {status}: {error_text}");
+            anyhow::bail!("Failed to update invoice note: {} - {}", status, error_text);
+        }
+        Ok(())
+    }
+}
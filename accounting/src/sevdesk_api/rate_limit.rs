@@ -0,0 +1,155 @@
+//! Request pacing and 429 retries for the SevDesk API.
+//!
+//! SevDesk answers bursts with `429 Too Many Requests`. Every request goes
+//! through [`RequestExt::send_paced`], which
+//!
+//! - spaces requests to the same API host at least [`MIN_REQUEST_INTERVAL`]
+//!   apart, across all clients and threads of the process, and
+//! - on a 429 waits as long as the `Retry-After` header asks (seconds or an
+//!   HTTP date, capped at [`MAX_RETRY_WAIT`]; exponential backoff without the
+//!   header), holds back every other request to that host for the same time,
+//!   and sends the request again — up to [`MAX_RETRIES`] times.
+//!
+//! A 429 means the request was not processed, so retrying is safe even for
+//! invoice creation. Only a request still throttled after the last retry
+//! returns its 429 to the caller and shows up as a failed order.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use log::{debug, warn};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use tokio::time::Instant;
+
+/// Minimum time between two requests to the same API host.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+/// Retries of a throttled request before its 429 is returned.
+pub const MAX_RETRIES: u32 = 5;
+/// Longest wait honoured from a `Retry-After` header.
+pub const MAX_RETRY_WAIT: Duration = Duration::from_secs(60);
+
+/// Spaces the requests to one API host; clones share the schedule.
+#[derive(Debug, Clone)]
+pub struct RequestPacer {
+    min_interval: Duration,
+    /// Earliest time the next request may be sent
+    next_slot: Arc<Mutex<Instant>>,
+}
+
+impl RequestPacer {
+    /// A pacer of its own, not shared with other clients.
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next_slot: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// The process-wide pacer for the host of `base_url`.
+    pub fn for_host(base_url: &str) -> Self {
+        static PACERS: OnceLock<Mutex<HashMap<String, RequestPacer>>> = OnceLock::new();
+        let host = reqwest::Url::parse(base_url)
+            .ok()
+            .and_then(|url| {
+                let host = url.host_str()?.to_string();
+                Some(match url.port() {
+                    Some(port) => format!("{host}:{port}"),
+                    None => host,
+                })
+            })
+            .unwrap_or_else(|| base_url.to_string());
+        PACERS
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap()
+            .entry(host)
+            .or_insert_with(|| Self::new(MIN_REQUEST_INTERVAL))
+            .clone()
+    }
+
+    /// Waits for this request's slot.
+    pub async fn wait(&self) {
+        let slot = {
+            let mut next = self.next_slot.lock().unwrap();
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.min_interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+
+    /// Holds back every request for `wait` from now.
+    pub fn pause_for(&self, wait: Duration) {
+        let mut next = self.next_slot.lock().unwrap();
+        *next = (*next).max(Instant::now() + wait);
+    }
+}
+
+/// How long a `Retry-After` header asks to wait: delay seconds or an HTTP
+/// date (relative to `now`), capped at [`MAX_RETRY_WAIT`].
+pub fn retry_after(headers: &HeaderMap, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let wait = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => {
+            let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (date.with_timezone(&chrono::Utc) - now)
+                .to_std()
+                .unwrap_or_default()
+        }
+    };
+    Some(wait.min(MAX_RETRY_WAIT))
+}
+
+/// Backoff before retry `attempt` (1-based) when no `Retry-After` was sent.
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << (attempt - 1).min(5)).min(MAX_RETRY_WAIT)
+}
+
+/// Sends requests through a [`RequestPacer`], retrying 429s.
+pub(crate) trait RequestExt {
+    async fn send_paced(self, pacer: &RequestPacer) -> reqwest::Result<Response>;
+}
+
+impl RequestExt for RequestBuilder {
+    async fn send_paced(self, pacer: &RequestPacer) -> reqwest::Result<Response> {
+        let mut request = self;
+        let mut attempt = 0;
+        loop {
+            // Bodies are JSON, so this only fails for streamed uploads,
+            // which then get no retries
+            let retry = request.try_clone();
+            pacer.wait().await;
+            let response = request.send().await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS || attempt == MAX_RETRIES {
+                if attempt > 0 {
+                    debug!(
+                        "{} answered {} after {attempt} retries",
+                        response.url(),
+                        response.status()
+                    );
+                }
+                return Ok(response);
+            }
+            let Some(next) = retry else {
+                return Ok(response);
+            };
+            attempt += 1;
+            let wait = retry_after(response.headers(), chrono::Utc::now())
+                .unwrap_or_else(|| backoff(attempt));
+            warn!(
+                "SevDesk rate limit hit for {}; retry {attempt}/{MAX_RETRIES} in {:.1}s",
+                response.url().path(),
+                wait.as_secs_f64()
+            );
+            pacer.pause_for(wait);
+            request = next;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "rate_limit_tests.rs"]
+mod tests;
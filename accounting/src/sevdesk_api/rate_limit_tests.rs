@@ -0,0 +1,96 @@
+//! Tests for request pacing and 429 retries.
+
+use super::*;
+use reqwest::header::HeaderValue;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn headers(retry_after: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_str(retry_after).unwrap());
+    headers
+}
+
+#[test]
+fn reads_retry_after_seconds_and_dates() {
+    let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+
+    assert_eq!(
+        retry_after(&headers("3"), now),
+        Some(Duration::from_secs(3))
+    );
+    assert_eq!(
+        retry_after(&headers("Sun, 01 Mar 2026 10:00:05 GMT"), now),
+        Some(Duration::from_secs(5))
+    );
+    // Dates in the past mean "now"; long waits are capped
+    assert_eq!(
+        retry_after(&headers("Sun, 01 Mar 2026 09:00:00 GMT"), now),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(retry_after(&headers("3600"), now), Some(MAX_RETRY_WAIT));
+    assert_eq!(retry_after(&headers("soon"), now), None);
+    assert_eq!(retry_after(&HeaderMap::new(), now), None);
+
+    assert_eq!(backoff(1), Duration::from_secs(1));
+    assert_eq!(backoff(3), Duration::from_secs(4));
+    assert_eq!(backoff(20), Duration::from_secs(32));
+}
+
+#[tokio::test]
+async fn pacer_spaces_requests() {
+    let pacer = RequestPacer::new(Duration::from_millis(20));
+    let start = Instant::now();
+    for _ in 0..4 {
+        pacer.clone().wait().await;
+    }
+    assert!(start.elapsed() >= Duration::from_millis(60));
+}
+
+#[tokio::test]
+async fn retries_throttled_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/Invoice", server.uri()))
+        .json(&serde_json::json!({ "invoiceNumber": "RE-1" }))
+        .send_paced(&RequestPacer::new(Duration::ZERO))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn persistent_throttling_is_returned() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/Contact"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .expect(u64::from(MAX_RETRIES) + 1)
+        .mount(&server)
+        .await;
+
+    let response = reqwest::Client::new()
+        .get(format!("{}/Contact", server.uri()))
+        .send_paced(&RequestPacer::new(Duration::ZERO))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
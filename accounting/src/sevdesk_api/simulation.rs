@@ -4,7 +4,10 @@ use anyhow::Result;
 use log::{debug, error, info};
 
 use crate::csv_processor::field_parsers::parse_price;
+use crate::invoice_drafts::InvoiceDraft;
 use crate::models::{InvoiceCreationResult, OrderRecord};
+use crate::order_grouping::{combined_positions, latest_order, order_reference};
+use crate::reconciliation::reconciled_positions;
 
 use super::SevDeskApi;
 
@@ -50,6 +53,94 @@ impl SevDeskApi {
         }
     }
 
+    /// Simulates a combined invoice for a buyer's orders without making
+    /// actual API calls.
+    pub async fn simulate_combined_invoice(
+        &self,
+        group: &[OrderRecord],
+    ) -> Result<InvoiceCreationResult> {
+        if let [order] = group {
+            return self.simulate_invoice_creation(order).await;
+        }
+        let order = latest_order(group);
+        let reference = order_reference(group);
+        info!(
+            "Simulating combined invoice for orders: {reference} ({})",
+            order.name
+        );
+
+        let validated = match self.get_country_id(&order.country).await {
+            Ok(_) => combined_positions(
+                group,
+                &self.reconciliation,
+                self.locale,
+                self.invoice_language(order),
+            ),
+            Err(e) => Err(e),
+        };
+        let (invoice_id, invoice_number, error) = match validated {
+            Ok(positions) => {
+                debug!("Would create {} invoice positions", positions.len());
+                let simulated_invoice_number = format!("DRY-{}", reference.replace(", ", "+"));
+                info!("Successfully simulated invoice: {simulated_invoice_number} for orders {reference}");
+                (Some(99999), Some(simulated_invoice_number), None)
+            }
+            Err(e) => {
+                error!("Failed to simulate invoice for orders {reference}: {e}");
+                (None, None, Some(e.to_string()))
+            }
+        };
+        Ok(InvoiceCreationResult {
+            order_id: reference,
+            customer_name: order.name.clone(),
+            invoice_id,
+            invoice_number,
+            error,
+            workflow_status: None,
+        })
+    }
+
+    /// Simulates creating a reviewed draft: the country is checked, the
+    /// draft's positions are taken as they are.
+    pub async fn simulate_invoice_from_draft(
+        &self,
+        draft: &InvoiceDraft,
+    ) -> Result<InvoiceCreationResult> {
+        let order = draft.order();
+        info!(
+            "Simulating reviewed invoice for orders: {} ({})",
+            draft.reference, order.name
+        );
+
+        let validated = match &draft.error {
+            Some(e) => Err(anyhow::anyhow!("{e}")),
+            None => self.get_country_id(&order.country).await.map(|_| ()),
+        };
+        let (invoice_id, invoice_number, error) = match validated {
+            Ok(()) => {
+                debug!("Would create {} invoice positions", draft.positions.len());
+                let simulated_invoice_number =
+                    format!("DRY-{}", draft.reference.replace(", ", "+"));
+                (Some(99999), Some(simulated_invoice_number), None)
+            }
+            Err(e) => {
+                error!(
+                    "Failed to simulate invoice for orders {}: {e}",
+                    draft.reference
+                );
+                (None, None, Some(e.to_string()))
+            }
+        };
+        Ok(InvoiceCreationResult {
+            order_id: draft.reference.clone(),
+            customer_name: order.name.clone(),
+            invoice_id,
+            invoice_number,
+            error,
+            workflow_status: None,
+        })
+    }
+
     /// Validates order data for invoice creation without making API calls.
     pub(crate) async fn simulate_invoice_validation(&self, order: &OrderRecord) -> Result<String> {
         debug!(
@@ -70,29 +161,19 @@ impl SevDeskApi {
         let total_value = parse_price(&order.total_value)?;
         debug!("Prices would be - merchandise: {merchandise_value:.2}, shipping: {shipment_costs:.2}, total: {total_value:.2}");
 
-        // Validate items and quantities
-        if !order.items.is_empty() {
-            debug!("Would create {} invoice positions:", order.items.len());
-            for (i, item) in order.items.iter().enumerate() {
-                debug!(
-                    "  Position {}: {} x {} @ {:.2} EUR",
-                    i + 1,
-                    item.quantity,
-                    item.localized_product_name,
-                    item.price
-                );
-            }
-        } else {
+        // Validate positions against the order total
+        let positions = reconciled_positions(order, &self.reconciliation, self.locale)?;
+        debug!("Would create {} invoice positions:", positions.len());
+        for (i, position) in positions.iter().enumerate() {
             debug!(
-                "Would create fallback position: {} x {} @ {:.2} EUR",
-                order.article_count, order.localized_product_name, merchandise_value
+                "  Position {}: {} x {} @ {:.2} EUR",
+                i + 1,
+                position.quantity,
+                position.name,
+                position.price_gross
             );
         }
 
-        if shipment_costs > 0.0 {
-            debug!("Would add shipping position: {shipment_costs:.2} EUR");
-        }
-
         // Generate a simulated invoice number
         let simulated_invoice_number = format!("DRY-{}", order.order_id);
         debug!("Simulated invoice number: {simulated_invoice_number}");
@@ -0,0 +1,42 @@
+//! Tagging invoices.
+
+use anyhow::{Context, Result};
+use log::{debug, error};
+
+use crate::models::{SevDeskInvoiceRef, SevDeskTagRequest};
+
+use super::rate_limit::RequestExt;
+use super::SevDeskApi;
+
+impl SevDeskApi {
+    /// Attaches the tag to an invoice, creating the tag in SevDesk if it
+    /// doesn't exist yet.
+    pub(crate) async fn tag_invoice(&self, invoice_id: &str, tag: &str) -> Result<()> {
+        debug!("Tagging invoice {invoice_id} with '{tag}'");
+        let request = SevDeskTagRequest {
+            name: tag.to_string(),
+            object: SevDeskInvoiceRef {
+                id: invoice_id.to_string(),
+                object_name: "Invoice".to_string(),
+            },
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/Tag/Factory/create", self.base_url))
+            .header("Authorization", &self.api_token)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send_paced(&self.pacer)
+            .await
+            .context("Failed to tag invoice")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            error!("Tagging invoice {invoice_id} with '{tag}' failed with status {status}: {error_text}");
+            anyhow::bail!("Failed to tag invoice: {} - {}", status, error_text);
+        }
+        Ok(())
+    }
+}
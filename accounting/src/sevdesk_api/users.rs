@@ -5,6 +5,7 @@ use log::{debug, error, info, warn};
 
 use crate::models::{SevDeskResponse, UserResponse};
 
+use super::rate_limit::RequestExt;
 use super::SevDeskApi;
 
 impl SevDeskApi {
@@ -17,7 +18,7 @@ impl SevDeskApi {
             .client
             .get(&url)
             .header("Authorization", &self.api_token)
-            .send()
+            .send_paced(&self.pacer)
             .await
             .context("Failed to get current user")?;
 
@@ -0,0 +1,256 @@
+//! Splitting orders by suggested shipping method.
+//!
+//! Postage is bought per shipping product, so packing goes faster when the
+//! orders arrive already sorted: each order's weight is estimated from its
+//! article count, which picks a Deutsche Post weight class (standard, compact,
+//! large or maxi letter, else a parcel). Orders worth at least
+//! [`ShippingRules::tracked_from_value`] go tracked, and orders leaving the
+//! home country are kept apart from domestic ones. Each group is exported as
+//! an address CSV, plus one summary of all groups.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::csv_processor::field_parsers::parse_price;
+use crate::models::OrderRecord;
+
+/// Assumptions for estimating weight and choosing the shipping method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShippingRules {
+    /// Weight of one card, sleeve included
+    pub grams_per_card: f64,
+    /// Envelope, toploader and padding per order
+    pub packaging_grams: f64,
+    /// Merchandise value (EUR) from which an order is shipped tracked
+    pub tracked_from_value: f64,
+    /// Shipping country counted as domestic, as written in the order CSV
+    pub home_country: String,
+}
+
+impl Default for ShippingRules {
+    fn default() -> Self {
+        Self {
+            grams_per_card: 2.0,
+            packaging_grams: 15.0,
+            tracked_from_value: 25.0,
+            home_country: "Germany".to_string(),
+        }
+    }
+}
+
+/// Deutsche Post letter formats by maximum weight, then parcels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WeightClass {
+    StandardLetter,
+    CompactLetter,
+    LargeLetter,
+    MaxiLetter,
+    Parcel,
+}
+
+impl WeightClass {
+    /// The lightest class a shipment of `grams` fits.
+    pub fn for_weight(grams: f64) -> Self {
+        match grams {
+            g if g <= 20.0 => Self::StandardLetter,
+            g if g <= 50.0 => Self::CompactLetter,
+            g if g <= 500.0 => Self::LargeLetter,
+            g if g <= 1000.0 => Self::MaxiLetter,
+            _ => Self::Parcel,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::StandardLetter => "Standard letter",
+            Self::CompactLetter => "Compact letter",
+            Self::LargeLetter => "Large letter",
+            Self::MaxiLetter => "Maxi letter",
+            Self::Parcel => "Parcel",
+        }
+    }
+
+    fn slug(self) -> &'static str {
+        match self {
+            Self::StandardLetter => "standard_letter",
+            Self::CompactLetter => "compact_letter",
+            Self::LargeLetter => "large_letter",
+            Self::MaxiLetter => "maxi_letter",
+            Self::Parcel => "parcel",
+        }
+    }
+}
+
+/// The suggested way to ship an order; orders with the same shipment share
+/// a postage product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Shipment {
+    pub weight_class: WeightClass,
+    pub tracked: bool,
+    pub international: bool,
+}
+
+impl Shipment {
+    /// File name stem for the group's address CSV, e.g.
+    /// `shipping_large_letter_tracked_international`.
+    pub fn file_stem(&self) -> String {
+        let mut stem = format!("shipping_{}", self.weight_class.slug());
+        if self.tracked {
+            stem.push_str("_tracked");
+        }
+        stem.push_str(if self.international {
+            "_international"
+        } else {
+            "_domestic"
+        });
+        stem
+    }
+}
+
+impl fmt::Display for Shipment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.weight_class.label())?;
+        if self.tracked {
+            write!(f, " · tracked")?;
+        }
+        write!(
+            f,
+            " · {}",
+            if self.international {
+                "international"
+            } else {
+                "domestic"
+            }
+        )
+    }
+}
+
+/// Orders sharing a suggested shipment, in CSV order.
+#[derive(Debug, Clone)]
+pub struct ShippingBatch {
+    pub shipment: Shipment,
+    pub orders: Vec<OrderRecord>,
+}
+
+impl ShippingBatch {
+    pub fn article_count(&self) -> u32 {
+        self.orders.iter().map(|order| order.article_count).sum()
+    }
+}
+
+/// Estimated weight of an order in grams.
+pub fn estimated_weight(order: &OrderRecord, rules: &ShippingRules) -> f64 {
+    rules.packaging_grams + order.article_count as f64 * rules.grams_per_card
+}
+
+/// The suggested shipment for one order. An unparseable merchandise value
+/// ships untracked.
+pub fn suggested_shipment(order: &OrderRecord, rules: &ShippingRules) -> Shipment {
+    Shipment {
+        weight_class: WeightClass::for_weight(estimated_weight(order, rules)),
+        tracked: parse_price(&order.merchandise_value)
+            .is_ok_and(|value| value >= rules.tracked_from_value),
+        international: !order
+            .country
+            .trim()
+            .eq_ignore_ascii_case(rules.home_country.trim()),
+    }
+}
+
+/// Groups orders by suggested shipment, lightest and untracked domestic
+/// first.
+pub fn split_by_shipping(orders: &[OrderRecord], rules: &ShippingRules) -> Vec<ShippingBatch> {
+    let mut batches: Vec<ShippingBatch> = Vec::new();
+    for order in orders {
+        let shipment = suggested_shipment(order, rules);
+        match batches.iter_mut().find(|b| b.shipment == shipment) {
+            Some(batch) => batch.orders.push(order.clone()),
+            None => batches.push(ShippingBatch {
+                shipment,
+                orders: vec![order.clone()],
+            }),
+        }
+    }
+    batches.sort_by_key(|batch| {
+        (
+            batch.shipment.weight_class,
+            batch.shipment.international,
+            batch.shipment.tracked,
+        )
+    });
+    batches
+}
+
+/// Address CSV for one batch: one row per order with the address and the
+/// estimated weight.
+pub fn format_address_csv(batch: &ShippingBatch, rules: &ShippingRules) -> String {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    let _ = wtr.write_record([
+        "orderId",
+        "name",
+        "street",
+        "zip",
+        "city",
+        "country",
+        "articles",
+        "weightGrams",
+        "merchandiseValue",
+    ]);
+    for order in &batch.orders {
+        let _ = wtr.write_record([
+            order.order_id.as_str(),
+            &order.name,
+            &order.street,
+            &order.zip,
+            &order.city,
+            &order.country,
+            &order.article_count.to_string(),
+            &format!("{:.0}", estimated_weight(order, rules)),
+            &order.merchandise_value,
+        ]);
+    }
+    String::from_utf8(wtr.into_inner().unwrap_or_default()).unwrap_or_default()
+}
+
+/// Plain-text summary of all batches, one line per postage product.
+pub fn format_summary(batches: &[ShippingBatch]) -> String {
+    let orders: usize = batches.iter().map(|b| b.orders.len()).sum();
+    let mut summary = format!("Shipping groups for {orders} orders\n\n");
+    for batch in batches {
+        summary.push_str(&format!(
+            "{}: {} orders, {} articles ({}.csv)\n",
+            batch.shipment,
+            batch.orders.len(),
+            batch.article_count(),
+            batch.shipment.file_stem()
+        ));
+    }
+    summary
+}
+
+/// Writes one address CSV per batch and `shipping_summary.txt` into `dir`.
+/// Returns the written files.
+pub fn write_shipping_exports(
+    dir: &Path,
+    batches: &[ShippingBatch],
+    rules: &ShippingRules,
+) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::with_capacity(batches.len() + 1);
+    for batch in batches {
+        let path = dir.join(format!("{}.csv", batch.shipment.file_stem()));
+        std::fs::write(&path, format_address_csv(batch, rules))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    let path = dir.join("shipping_summary.txt");
+    std::fs::write(&path, format_summary(batches))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    written.push(path);
+    Ok(written)
+}
+
+#[cfg(test)]
+#[path = "shipping_split_tests.rs"]
+mod tests;
@@ -0,0 +1,120 @@
+//! Tests for splitting orders by shipping method.
+
+use super::*;
+
+fn order(order_id: &str, country: &str, articles: u32, value: &str) -> OrderRecord {
+    OrderRecord {
+        order_id: order_id.to_string(),
+        username: "testuser".to_string(),
+        name: "Max Mustermann".to_string(),
+        street: "Main Street 1".to_string(),
+        zip: "10557".to_string(),
+        city: "Berlin".to_string(),
+        country: country.to_string(),
+        is_professional: None,
+        vat_number: None,
+        date_of_purchase: "2025-01-15".to_string(),
+        article_count: articles,
+        merchandise_value: value.to_string(),
+        shipment_costs: "1,25".to_string(),
+        total_value: value.to_string(),
+        commission: "0,10".to_string(),
+        currency: "EUR".to_string(),
+        description: String::new(),
+        product_id: String::new(),
+        localized_product_name: String::new(),
+        items: Vec::new(),
+    }
+}
+
+#[test]
+fn weight_classes_follow_the_letter_limits() {
+    assert_eq!(WeightClass::for_weight(20.0), WeightClass::StandardLetter);
+    assert_eq!(WeightClass::for_weight(20.1), WeightClass::CompactLetter);
+    assert_eq!(WeightClass::for_weight(500.0), WeightClass::LargeLetter);
+    assert_eq!(WeightClass::for_weight(1000.0), WeightClass::MaxiLetter);
+    assert_eq!(WeightClass::for_weight(1000.1), WeightClass::Parcel);
+}
+
+#[test]
+fn shipment_uses_weight_value_and_country() {
+    let rules = ShippingRules::default();
+
+    // 15 g packaging + 2 cards
+    let cheap = suggested_shipment(&order("1", "Germany", 2, "3,50"), &rules);
+    assert_eq!(
+        cheap,
+        Shipment {
+            weight_class: WeightClass::StandardLetter,
+            tracked: false,
+            international: false,
+        }
+    );
+
+    let valuable = suggested_shipment(&order("2", "France", 20, "40,00"), &rules);
+    assert_eq!(valuable.weight_class, WeightClass::LargeLetter);
+    assert!(valuable.tracked);
+    assert!(valuable.international);
+    assert_eq!(
+        valuable.file_stem(),
+        "shipping_large_letter_tracked_international"
+    );
+    assert_eq!(
+        valuable.to_string(),
+        "Large letter · tracked · international"
+    );
+}
+
+#[test]
+fn orders_are_grouped_lightest_first_in_csv_order() {
+    let orders = vec![
+        order("1", "Germany", 20, "5,00"),
+        order("2", "Germany", 1, "1,00"),
+        order("3", "germany", 30, "8,00"),
+    ];
+
+    let batches = split_by_shipping(&orders, &ShippingRules::default());
+
+    assert_eq!(batches.len(), 2);
+    assert_eq!(
+        batches[0].shipment.weight_class,
+        WeightClass::StandardLetter
+    );
+    let ids: Vec<&str> = batches[1]
+        .orders
+        .iter()
+        .map(|o| o.order_id.as_str())
+        .collect();
+    assert_eq!(ids, ["1", "3"]);
+    assert_eq!(batches[1].article_count(), 50);
+}
+
+#[test]
+fn exports_write_a_csv_per_group_and_a_summary() {
+    let rules = ShippingRules::default();
+    let batches = split_by_shipping(
+        &[
+            order("1", "Germany", 1, "1,00"),
+            order("2", "Spain", 1, "1,00"),
+        ],
+        &rules,
+    );
+    let dir = tempfile::tempdir().unwrap();
+
+    let written = write_shipping_exports(dir.path(), &batches, &rules).unwrap();
+
+    assert_eq!(written.len(), 3);
+    let csv =
+        std::fs::read_to_string(dir.path().join("shipping_standard_letter_domestic.csv")).unwrap();
+    assert_eq!(
+        csv,
+        "orderId,name,street,zip,city,country,articles,weightGrams,merchandiseValue\n\
+         1,Max Mustermann,Main Street 1,10557,Berlin,Germany,1,17,\"1,00\"\n"
+    );
+    let summary = std::fs::read_to_string(dir.path().join("shipping_summary.txt")).unwrap();
+    assert!(summary.starts_with("Shipping groups for 2 orders"));
+    assert!(summary.contains(
+        "Standard letter · international: 1 orders, 1 articles \
+         (shipping_standard_letter_international.csv)"
+    ));
+}
@@ -0,0 +1,110 @@
+//! Shipment tracking numbers.
+//!
+//! Cardmarket exports the tracking numbers entered for shipped orders as a
+//! CSV with one row per order. Importing it attaches each number to its
+//! order on the order board (so a buyer asking where their parcel is can be
+//! answered by searching the board) and, for orders that were already
+//! invoiced, to the SevDesk invoice as an internal note.
+//!
+//! The export's columns are found by header name rather than position;
+//! besides Cardmarket's `OrderID;TrackingNumber;ShippingMethod` layout,
+//! comma-separated files and spaced headers such as `Order ID` are accepted.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info, warn};
+use mtg_common::{find_column, normalize_header};
+
+/// A tracking number from the export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackingEntry {
+    pub order_id: String,
+    pub tracking_number: String,
+    /// Shipping method or carrier, if the export has that column
+    pub carrier: Option<String>,
+}
+
+impl TrackingEntry {
+    /// Internal note added to the order's SevDesk invoice.
+    pub fn invoice_note(&self) -> String {
+        match &self.carrier {
+            Some(carrier) => format!("Sendungsnummer: {} ({carrier})", self.tracking_number),
+            None => format!("Sendungsnummer: {}", self.tracking_number),
+        }
+    }
+}
+
+const ORDER_ID_HEADERS: [&str; 3] = ["orderid", "idorder", "order"];
+const TRACKING_HEADERS: [&str; 3] = ["trackingnumber", "tracking", "trackingcode"];
+const CARRIER_HEADERS: [&str; 3] = ["shippingmethod", "carrier", "shippingprovider"];
+
+/// Parses a tracking-number export. Rows without an order ID or tracking
+/// number are skipped; a later row for the same order wins.
+pub fn parse_tracking_csv(content: &str) -> Result<Vec<TrackingEntry>> {
+    let header_line = content.lines().next().unwrap_or_default();
+    let delimiter = if header_line.contains(';') {
+        b';'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .context("Failed to read tracking export header")?
+        .iter()
+        .map(normalize_header)
+        .collect();
+    let Some(order_col) = find_column(&headers, &ORDER_ID_HEADERS) else {
+        bail!("Tracking export has no order ID column (expected OrderID)");
+    };
+    let Some(tracking_col) = find_column(&headers, &TRACKING_HEADERS) else {
+        bail!("Tracking export has no tracking number column (expected TrackingNumber)");
+    };
+    let carrier_col = find_column(&headers, &CARRIER_HEADERS);
+
+    let mut entries: Vec<TrackingEntry> = Vec::new();
+    for (line, record) in (2..).zip(reader.records()) {
+        let record = record.with_context(|| format!("Invalid tracking export line {line}"))?;
+        let field = |col: usize| record.get(col).unwrap_or_default().trim().to_string();
+        let order_id = field(order_col);
+        let tracking_number = field(tracking_col);
+        if order_id.is_empty() || tracking_number.is_empty() {
+            debug!("Skipping tracking export line {line} without order ID or tracking number");
+            continue;
+        }
+        let entry = TrackingEntry {
+            order_id,
+            tracking_number,
+            carrier: carrier_col.map(field).filter(|c| !c.is_empty()),
+        };
+        match entries.iter_mut().find(|e| e.order_id == entry.order_id) {
+            Some(existing) => {
+                warn!(
+                    "Order {} appears twice in the tracking export; using {}",
+                    entry.order_id, entry.tracking_number
+                );
+                *existing = entry;
+            }
+            None => entries.push(entry),
+        }
+    }
+    info!("Parsed {} tracking numbers", entries.len());
+    Ok(entries)
+}
+
+/// Reads and parses a tracking export; gzipped, zipped and Excel-encoded
+/// files are handled like order CSVs.
+pub fn load_tracking_file(path: &Path) -> Result<Vec<TrackingEntry>> {
+    let content = mtg_common::compressed::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_tracking_csv(&content)
+}
+
+#[cfg(test)]
+#[path = "tracking_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+//! Tests for tracking number imports.
+
+use super::*;
+
+#[test]
+fn parses_cardmarket_export() {
+    let content = "OrderID;TrackingNumber;ShippingMethod\n\
+                   1001;RR123456789DE;Einschreiben\n\
+                   1002; 00340434161234567890 ;\n";
+
+    let entries = parse_tracking_csv(content).unwrap();
+
+    assert_eq!(
+        entries,
+        vec![
+            TrackingEntry {
+                order_id: "1001".to_string(),
+                tracking_number: "RR123456789DE".to_string(),
+                carrier: Some("Einschreiben".to_string()),
+            },
+            TrackingEntry {
+                order_id: "1002".to_string(),
+                tracking_number: "00340434161234567890".to_string(),
+                carrier: None,
+            },
+        ]
+    );
+    assert_eq!(
+        entries[0].invoice_note(),
+        "Sendungsnummer: RR123456789DE (Einschreiben)"
+    );
+    assert_eq!(
+        entries[1].invoice_note(),
+        "Sendungsnummer: 00340434161234567890"
+    );
+}
+
+#[test]
+fn finds_columns_by_name_and_skips_incomplete_rows() {
+    let content = "Tracking Number,Buyer,Order ID\n\
+                   RR1,alice,1001\n\
+                   ,bob,1002\n\
+                   RR3,carol,\n\
+                   RR4,alice,1001\n";
+
+    let entries = parse_tracking_csv(content).unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].order_id, "1001");
+    assert_eq!(entries[0].tracking_number, "RR4");
+}
+
+#[test]
+fn missing_columns_are_an_error() {
+    let err = parse_tracking_csv("OrderID;Username\n1001;alice\n").unwrap_err();
+    assert!(err.to_string().contains("tracking number column"));
+
+    let err = parse_tracking_csv("TrackingNumber\nRR1\n").unwrap_err();
+    assert!(err.to_string().contains("order ID column"));
+}
@@ -123,6 +123,27 @@ mod load_orders_from_csv {
         assert!(orders.is_empty());
     }
 
+    #[tokio::test]
+    async fn loads_gzipped_export() {
+        use std::io::Write;
+
+        let processor = CsvProcessor::new();
+        let csv = std::fs::read(fixtures_path().join("valid_single_order.csv")).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("orders.csv.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&path).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(&csv).unwrap();
+        encoder.finish().unwrap();
+
+        let orders = processor.load_orders_from_csv(&path).await.unwrap();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, "1218804750");
+    }
+
     #[tokio::test]
     async fn fails_for_nonexistent_file() {
         let processor = CsvProcessor::new();
@@ -144,6 +165,52 @@ mod load_orders_from_csv {
     }
 }
 
+// ==================== Excel Encoding Tests ====================
+
+mod excel_encodings {
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_exports_resaved_by_excel() {
+        let processor = CsvProcessor::new();
+
+        for name in [
+            "orders_utf8_bom.csv",
+            "orders_utf16le.csv",
+            "orders_utf16be.csv",
+            "orders_windows1252.csv",
+        ] {
+            let orders = processor
+                .load_orders_from_csv(fixtures_path().join(name))
+                .await
+                .unwrap();
+
+            assert_eq!(orders.len(), 1, "{name}");
+            // A BOM left in place would break the OrderID header
+            assert_eq!(orders[0].order_id, "1218804750", "{name}");
+            assert_eq!(orders[0].street, "Hedwig-Porschütz-Straße 28", "{name}");
+            // CRLF must not leave a carriage return on the last column
+            assert_eq!(
+                orders[0].localized_product_name, "High Fae Trickster",
+                "{name}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_quoted_windows_path() {
+        let processor = CsvProcessor::new();
+        let quoted = format!(
+            "\"{}\"",
+            fixtures_path().join("orders_windows1252.csv").display()
+        );
+
+        let orders = processor.load_orders_from_csv(&quoted).await.unwrap();
+
+        assert_eq!(orders.len(), 1);
+    }
+}
+
 // ==================== Validation Integration Tests ====================
 
 mod validation_integration {
@@ -0,0 +1,79 @@
+//! Integration tests for the anonymized order CSV generator.
+//!
+//! Generated files must go through the real CSV processor exactly like a
+//! Cardmarket export would.
+
+use sevdesk_invoicing::fixtures::{generate_orders_csv, EdgeCase, FixtureConfig};
+use sevdesk_invoicing::CsvProcessor;
+
+fn parse(config: &FixtureConfig) -> Vec<sevdesk_invoicing::OrderRecord> {
+    let csv = generate_orders_csv(config).unwrap();
+    CsvProcessor::new().parse_csv_content(&csv).unwrap()
+}
+
+fn only(edge_case: EdgeCase) -> FixtureConfig {
+    FixtureConfig {
+        orders: 15,
+        edge_cases: vec![edge_case],
+        edge_case_rate: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn generated_orders_parse_and_validate() {
+    let config = FixtureConfig {
+        orders: 200,
+        max_items_per_order: 6,
+        countries: sevdesk_invoicing::fixtures::countries()
+            .map(String::from)
+            .collect(),
+        edge_case_rate: 0.5,
+        ..Default::default()
+    };
+    let processor = CsvProcessor::new();
+    let orders = parse(&config);
+
+    assert_eq!(orders.len(), 200);
+    assert!(processor.validate_orders(&orders).is_empty());
+    for order in &orders {
+        let quantity: u32 = order.items.iter().map(|i| i.quantity).sum();
+        assert_eq!(quantity, order.article_count, "order {}", order.order_id);
+        assert_eq!(
+            order.items.len(),
+            order.product_id.split(" | ").count(),
+            "order {}",
+            order.order_id
+        );
+    }
+}
+
+#[test]
+fn missing_zip_parses_as_city_only() {
+    for order in parse(&only(EdgeCase::MissingZip)) {
+        assert!(order.zip.is_empty());
+        assert!(!order.city.is_empty());
+    }
+}
+
+#[test]
+fn pipe_in_set_name_keeps_item_count() {
+    let config = FixtureConfig {
+        max_items_per_order: 4,
+        ..only(EdgeCase::PipeInSetName)
+    };
+    for order in parse(&config) {
+        assert_eq!(order.items.len(), order.product_id.split(" | ").count());
+        assert!(order.items[0]
+            .description
+            .contains("Magic: The Gathering | Marvel's Spider-Man"));
+    }
+}
+
+#[test]
+fn professional_buyers_carry_vat_number() {
+    for order in parse(&only(EdgeCase::Professional)) {
+        assert_eq!(order.is_professional.as_deref(), Some("yes"));
+        assert!(order.vat_number.is_some());
+    }
+}
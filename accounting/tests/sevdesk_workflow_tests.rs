@@ -0,0 +1,559 @@
+//! End-to-end tests for the SevDesk client against a mock server.
+//!
+//! Each test loads an order from a CSV fixture, creates its invoice and runs
+//! the finalize/PDF/enshrine/book workflow against a wiremock server that
+//! mimics the SevDesk endpoints. Error cases override single endpoints with
+//! rate limits, server errors or malformed bodies.
+
+use sevdesk_invoicing::{
+    CsvProcessor, InvoiceCreationResult, InvoiceWorkflowOptions, OrderRecord, SendType, SevDeskApi,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const INVOICE_ID: u32 = 500;
+const INVOICE_NUMBER: &str = "RE-2025-001";
+
+fn fixtures_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// One item at 1,87 EUR plus 1,25 EUR shipping, shipped to Germany.
+async fn load_order() -> OrderRecord {
+    let processor = CsvProcessor::new();
+    let mut orders = processor
+        .load_orders_from_csv(fixtures_path().join("valid_single_order.csv"))
+        .await
+        .unwrap();
+    orders.remove(0)
+}
+
+fn api(server: &MockServer) -> SevDeskApi {
+    SevDeskApi::new("test_token".to_string())
+        .with_base_url(server.uri())
+        .with_request_interval(Duration::ZERO)
+}
+
+fn workflow_options(pdf_dir: &Path) -> InvoiceWorkflowOptions {
+    InvoiceWorkflowOptions {
+        finalize: true,
+        send_type: SendType::Vpdf,
+        enshrine: true,
+        book: true,
+        check_account_id: Some("42".to_string()),
+        pdf_download_path: Some(pdf_dir.to_path_buf()),
+        payment_date: Some("2025-07-01 22:42:27".to_string()),
+    }
+}
+
+fn json(body: serde_json::Value) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(body)
+}
+
+/// Mounts every endpoint the invoice run touches, all succeeding. The
+/// contact search finds nothing, so a new contact is created.
+async fn mount_sevdesk(server: &MockServer) {
+    let routes = [
+        (
+            "GET",
+            "/StaticCountry".to_string(),
+            json(serde_json::json!({
+                "objects": [{ "id": "1", "name": "Deutschland", "nameEn": "Germany" }]
+            })),
+        ),
+        (
+            "GET",
+            "/Contact".to_string(),
+            json(serde_json::json!({ "objects": [] })),
+        ),
+        (
+            "POST",
+            "/Contact".to_string(),
+            json(serde_json::json!({
+                "objects": { "id": "77", "name": "Lucas Cordeiro", "objectName": "Contact" }
+            })),
+        ),
+        (
+            "GET",
+            "/SevUser".to_string(),
+            json(serde_json::json!({
+                "objects": [{ "id": "1", "username": "admin", "objectName": "SevUser" }]
+            })),
+        ),
+        (
+            "POST",
+            "/Invoice".to_string(),
+            json(serde_json::json!({
+                "objects": { "id": INVOICE_ID.to_string(), "invoiceNumber": INVOICE_NUMBER }
+            })),
+        ),
+        (
+            "POST",
+            "/InvoicePos".to_string(),
+            json(serde_json::json!({ "objects": { "id": "1" } })),
+        ),
+        (
+            "PUT",
+            format!("/Invoice/{INVOICE_ID}/sendBy"),
+            json(serde_json::json!({ "objects": {} })),
+        ),
+        (
+            "GET",
+            format!("/Invoice/{INVOICE_ID}/getPdf"),
+            ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.4 test".to_vec()),
+        ),
+        (
+            "PUT",
+            format!("/Invoice/{INVOICE_ID}/enshrine"),
+            json(serde_json::json!({ "objects": null })),
+        ),
+        (
+            "GET",
+            format!("/Invoice/{INVOICE_ID}"),
+            json(serde_json::json!({
+                "objects": [{ "id": INVOICE_ID.to_string(), "sumGross": "3.12" }]
+            })),
+        ),
+        (
+            "PUT",
+            format!("/Invoice/{INVOICE_ID}/bookAmount"),
+            json(serde_json::json!({ "objects": {} })),
+        ),
+    ];
+
+    for (verb, route, response) in routes {
+        Mock::given(method(verb))
+            .and(path(route))
+            .and(header("Authorization", "test_token"))
+            .respond_with(response)
+            .mount(server)
+            .await;
+    }
+}
+
+/// Replaces the happy-path response of one endpoint.
+async fn override_route(server: &MockServer, verb: &str, route: &str, response: ResponseTemplate) {
+    Mock::given(method(verb))
+        .and(path(route))
+        .respond_with(response)
+        .with_priority(1)
+        .mount(server)
+        .await;
+}
+
+/// Number of requests the server received for `verb` on `route`.
+async fn requests_to(server: &MockServer, verb: &str, route: &str) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.method.as_str() == verb && r.url.path() == route)
+        .count()
+}
+
+async fn create_invoice(server: &MockServer) -> InvoiceCreationResult {
+    api(server)
+        .create_invoice(&load_order().await)
+        .await
+        .unwrap()
+}
+
+// ==================== Happy Path ====================
+
+#[tokio::test]
+async fn full_workflow_creates_books_and_downloads_invoice() {
+    let server = MockServer::start().await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let created = create_invoice(&server).await;
+    assert_eq!(created.error, None);
+    assert_eq!(created.invoice_id, Some(INVOICE_ID));
+    assert_eq!(created.invoice_number.as_deref(), Some(INVOICE_NUMBER));
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    assert_eq!(status.workflow_error, None);
+    assert!(status.finalized);
+    assert!(status.enshrined);
+    assert!(status.booked);
+    let pdf_path = status.pdf_path.expect("PDF should be downloaded");
+    assert_eq!(pdf_path, pdf_dir.path().join("RE-2025-001.pdf"));
+    assert_eq!(std::fs::read(pdf_path).unwrap(), b"%PDF-1.4 test");
+
+    assert_eq!(requests_to(&server, "POST", "/Contact").await, 1);
+    assert_eq!(requests_to(&server, "POST", "/Invoice").await, 1);
+    // Item and shipping
+    assert_eq!(requests_to(&server, "POST", "/InvoicePos").await, 2);
+    assert_eq!(
+        requests_to(&server, "PUT", "/Invoice/500/bookAmount").await,
+        1
+    );
+}
+
+#[tokio::test]
+async fn positions_carry_order_prices() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Shipping",
+            "quantity": 1.0,
+            "priceGross": 1.25,
+            "positionNumber": 2
+        })))
+        .respond_with(json(serde_json::json!({ "objects": { "id": "2" } })))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    assert_eq!(created.error, None);
+}
+
+#[tokio::test]
+async fn finalize_and_book_send_expected_bodies() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/Invoice/500/sendBy"))
+        .and(body_partial_json(serde_json::json!({
+            "sendType": "VPDF",
+            "sendDraft": false
+        })))
+        .respond_with(json(serde_json::json!({ "objects": {} })))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/Invoice/500/bookAmount"))
+        .and(body_partial_json(serde_json::json!({
+            "amount": 3.12,
+            "type": "N",
+            "checkAccount": { "id": 42, "objectName": "CheckAccount" }
+        })))
+        .respond_with(json(serde_json::json!({ "objects": {} })))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    assert!(status.booked);
+}
+
+#[tokio::test]
+async fn existing_contact_is_reused() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "GET",
+        "/Contact",
+        json(serde_json::json!({
+            "objects": [{ "id": "10", "name": "Lucas Cordeiro", "objectName": "Contact" }]
+        })),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    assert_eq!(created.error, None);
+    assert_eq!(requests_to(&server, "POST", "/Contact").await, 0);
+}
+
+#[tokio::test]
+async fn combined_orders_get_one_invoice_with_sections() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/InvoicePos"))
+        .and(body_partial_json(serde_json::json!({
+            "name": "Shipping",
+            "text": "Bestellung 1218805887: Shipping costs",
+            "positionNumber": 4
+        })))
+        .respond_with(json(serde_json::json!({ "objects": { "id": "4" } })))
+        .with_priority(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_sevdesk(&server).await;
+    let first = load_order().await;
+    let mut second = first.clone();
+    second.order_id = "1218805887".to_string();
+    second.date_of_purchase = "2025-07-02 09:00:00".to_string();
+
+    let created = api(&server)
+        .create_combined_invoice(&[first.clone(), second])
+        .await
+        .unwrap();
+
+    assert_eq!(created.error, None);
+    assert_eq!(created.order_id, format!("{}, 1218805887", first.order_id));
+    assert_eq!(requests_to(&server, "POST", "/Invoice").await, 1);
+    assert_eq!(requests_to(&server, "POST", "/InvoicePos").await, 4);
+    let requests = server.received_requests().await.unwrap();
+    let invoice: serde_json::Value = requests
+        .iter()
+        .find(|r| r.method.as_str() == "POST" && r.url.path() == "/Invoice")
+        .unwrap()
+        .body_json()
+        .unwrap();
+    assert_eq!(invoice["invoiceDate"], "2025-07-02");
+}
+
+// ==================== Error Injection ====================
+
+#[tokio::test]
+async fn briefly_rate_limited_invoice_creation_is_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/Invoice"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    assert_eq!(created.error, None);
+    assert_eq!(created.invoice_id, Some(INVOICE_ID));
+    assert_eq!(requests_to(&server, "POST", "/Invoice").await, 3);
+}
+
+/// Still throttled after every retry
+#[tokio::test]
+async fn rate_limited_invoice_creation_fails_the_order() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "POST",
+        "/Invoice",
+        ResponseTemplate::new(429)
+            .insert_header("Retry-After", "0")
+            .set_body_string("Too Many Requests"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    let error = created.error.expect("invoice creation should fail");
+    assert!(error.contains("429"), "unexpected error: {error}");
+    assert_eq!(created.invoice_id, None);
+    assert_eq!(requests_to(&server, "POST", "/InvoicePos").await, 0);
+}
+
+#[tokio::test]
+async fn server_error_on_contact_creation_fails_the_order() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "POST",
+        "/Contact",
+        ResponseTemplate::new(500).set_body_string("Internal Server Error"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    let error = created.error.expect("contact creation should fail");
+    assert!(error.contains("500"), "unexpected error: {error}");
+    assert_eq!(requests_to(&server, "POST", "/Invoice").await, 0);
+}
+
+#[tokio::test]
+async fn malformed_invoice_response_fails_the_order() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "POST",
+        "/Invoice",
+        ResponseTemplate::new(201).set_body_string("{\"objects\": {\"id\": "),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    let error = created.error.expect("invoice creation should fail");
+    assert!(
+        error.contains("parse create invoice response"),
+        "unexpected error: {error}"
+    );
+}
+
+#[tokio::test]
+async fn malformed_contact_search_fails_the_order() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "GET",
+        "/Contact",
+        ResponseTemplate::new(200).set_body_string("<html>maintenance</html>"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+
+    let created = create_invoice(&server).await;
+
+    let error = created.error.expect("contact search should fail");
+    assert!(
+        error.contains("parse contact search response"),
+        "unexpected error: {error}"
+    );
+}
+
+#[tokio::test]
+async fn rate_limited_finalize_stops_the_workflow() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "PUT",
+        "/Invoice/500/sendBy",
+        ResponseTemplate::new(429)
+            .insert_header("Retry-After", "0")
+            .set_body_string("Too Many Requests"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    let error = status.workflow_error.expect("finalize should fail");
+    assert!(
+        error.starts_with("Finalize failed"),
+        "unexpected error: {error}"
+    );
+    assert!(error.contains("429"), "unexpected error: {error}");
+    assert!(!status.finalized);
+    assert_eq!(
+        requests_to(&server, "PUT", "/Invoice/500/enshrine").await,
+        0
+    );
+    assert_eq!(
+        requests_to(&server, "PUT", "/Invoice/500/bookAmount").await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn server_error_on_booking_keeps_earlier_steps() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "PUT",
+        "/Invoice/500/bookAmount",
+        ResponseTemplate::new(500).set_body_string("Internal Server Error"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    let error = status.workflow_error.expect("booking should fail");
+    assert!(
+        error.starts_with("Book failed"),
+        "unexpected error: {error}"
+    );
+    assert!(status.finalized);
+    assert!(status.enshrined);
+    assert!(!status.booked);
+}
+
+#[tokio::test]
+async fn malformed_pdf_response_does_not_stop_the_workflow() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "GET",
+        "/Invoice/500/getPdf",
+        ResponseTemplate::new(200).set_body_string("{\"objects\": {\"content\": 12}}"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    assert_eq!(status.workflow_error, None);
+    assert_eq!(status.pdf_path, None);
+    assert!(status.enshrined);
+    assert!(status.booked);
+}
+
+#[tokio::test]
+async fn malformed_invoice_amount_fails_booking() {
+    let server = MockServer::start().await;
+    override_route(
+        &server,
+        "GET",
+        "/Invoice/500",
+        ResponseTemplate::new(200).set_body_string("not json"),
+    )
+    .await;
+    mount_sevdesk(&server).await;
+    let pdf_dir = tempfile::tempdir().unwrap();
+
+    let status = api(&server)
+        .execute_invoice_workflow(
+            INVOICE_ID,
+            INVOICE_NUMBER,
+            &workflow_options(pdf_dir.path()),
+        )
+        .await;
+
+    let error = status.workflow_error.expect("booking should fail");
+    assert!(
+        error.starts_with("Book failed"),
+        "unexpected error: {error}"
+    );
+    assert_eq!(
+        requests_to(&server, "PUT", "/Invoice/500/bookAmount").await,
+        0
+    );
+}
@@ -2,11 +2,14 @@
 
 pub mod cardmarket;
 pub mod inventory_sync;
+pub mod rate_limit;
 pub mod scryfall;
 
 #[cfg(test)]
 mod cardmarket_tests;
 #[cfg(test)]
+mod rate_limit_tests;
+#[cfg(test)]
 mod scryfall_tests;
 
 // Re-exports for public API convenience
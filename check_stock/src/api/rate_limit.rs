@@ -0,0 +1,160 @@
+//! Client-side rate limiting for the Scryfall API.
+//!
+//! Scryfall asks clients to stay at or below 10 requests per second and
+//! answers bursts above that with HTTP 429. Every `api.scryfall.com` call in
+//! this crate first takes a token from one shared [`RateLimiter`], so
+//! screens fetching cards concurrently cannot add up past the limit. Image
+//! downloads go to Scryfall's CDN (`*.scryfall.io`), which is not rate
+//! limited, and skip the bucket.
+//!
+//! The bucket holds up to `SCRYFALL_BURST` tokens (default 10) and refills
+//! at 10 per second. Callers that find it empty reserve the next free slot
+//! and sleep until then, so waiting requests go out in arrival order.
+
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+
+/// Scryfall's documented request rate limit.
+pub const SCRYFALL_REQUESTS_PER_SECOND: f64 = 10.0;
+
+/// Env var overriding how many requests may go out back to back.
+pub const SCRYFALL_BURST_ENV: &str = "SCRYFALL_BURST";
+
+const DEFAULT_BURST: u32 = 10;
+
+/// Counters on how often and how long callers were held back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleStats {
+    /// Requests that passed through the limiter
+    pub requests: u64,
+    /// Requests that had to wait for a token
+    pub throttled: u64,
+    /// Summed waiting time of all throttled requests
+    pub total_wait: Duration,
+}
+
+impl ThrottleStats {
+    /// Average wait of the throttled requests, zero if none waited.
+    pub fn average_wait(&self) -> Duration {
+        match u32::try_from(self.throttled) {
+            Ok(0) | Err(_) => Duration::ZERO,
+            Ok(n) => self.total_wait / n,
+        }
+    }
+}
+
+impl fmt::Display for ThrottleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, {} throttled, {:.1}s waited (avg {}ms)",
+            self.requests,
+            self.throttled,
+            self.total_wait.as_secs_f64(),
+            self.average_wait().as_millis()
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens; negative while callers hold reservations
+    tokens: f64,
+    last_refill: Instant,
+    stats: ThrottleStats,
+}
+
+/// Token bucket shared by all callers of one API.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_second: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// A full bucket of `burst` tokens refilling at `per_second`.
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                last_refill: Instant::now(),
+                stats: ThrottleStats::default(),
+            }),
+        }
+    }
+
+    /// Takes a token, or reserves the next one, and returns how long the
+    /// caller has to wait before sending its request.
+    pub fn reserve(&self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    pub(crate) fn reserve_at(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.per_second).min(self.burst);
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+
+        let wait = if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.per_second)
+        };
+        bucket.stats.requests += 1;
+        if !wait.is_zero() {
+            bucket.stats.throttled += 1;
+            bucket.stats.total_wait += wait;
+        }
+        wait
+    }
+
+    /// Blocks the current thread until a request may be sent.
+    pub fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            debug!("Rate limit: waiting {}ms", wait.as_millis());
+            std::thread::sleep(wait);
+        }
+    }
+
+    /// Waits without blocking the runtime until a request may be sent.
+    pub async fn acquire_async(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            debug!("Rate limit: waiting {}ms", wait.as_millis());
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    pub fn stats(&self) -> ThrottleStats {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner()).stats
+    }
+}
+
+/// The limiter shared by every Scryfall API call in the process.
+pub fn scryfall_limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| {
+        let burst = burst_from_env_value(std::env::var(SCRYFALL_BURST_ENV).ok().as_deref());
+        RateLimiter::new(SCRYFALL_REQUESTS_PER_SECOND, burst)
+    })
+}
+
+pub(crate) fn burst_from_env_value(value: Option<&str>) -> u32 {
+    match value.map(|v| v.trim().parse::<u32>()) {
+        Some(Ok(burst)) if burst > 0 => burst,
+        Some(_) => {
+            warn!("Ignoring {SCRYFALL_BURST_ENV}: expected a positive integer");
+            DEFAULT_BURST
+        }
+        None => DEFAULT_BURST,
+    }
+}
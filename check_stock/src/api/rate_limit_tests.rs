@@ -0,0 +1,69 @@
+//! Tests for the Scryfall rate limiter.
+
+use std::time::{Duration, Instant};
+
+use super::rate_limit::{burst_from_env_value, RateLimiter, ThrottleStats};
+
+#[test]
+fn burst_passes_without_waiting() {
+    let limiter = RateLimiter::new(10.0, 3);
+    let now = Instant::now();
+    for _ in 0..3 {
+        assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+    }
+    assert_eq!(limiter.stats().throttled, 0);
+}
+
+#[test]
+fn empty_bucket_queues_callers_at_the_refill_rate() {
+    let limiter = RateLimiter::new(10.0, 1);
+    let now = Instant::now();
+    assert_eq!(limiter.reserve_at(now), Duration::ZERO);
+
+    let first = limiter.reserve_at(now);
+    let second = limiter.reserve_at(now);
+    assert!((first.as_secs_f64() - 0.1).abs() < 1e-9);
+    assert!((second.as_secs_f64() - 0.2).abs() < 1e-9);
+
+    let stats = limiter.stats();
+    assert_eq!(stats.requests, 3);
+    assert_eq!(stats.throttled, 2);
+    assert!((stats.total_wait.as_secs_f64() - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn idle_time_refills_up_to_burst_only() {
+    let limiter = RateLimiter::new(10.0, 2);
+    let start = Instant::now();
+    limiter.reserve_at(start);
+    limiter.reserve_at(start);
+
+    // Ten idle seconds refill two tokens, not a hundred
+    let later = start + Duration::from_secs(10);
+    assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+    assert_eq!(limiter.reserve_at(later), Duration::ZERO);
+    assert!(!limiter.reserve_at(later).is_zero());
+}
+
+#[test]
+fn throttle_stats_average_and_display() {
+    let stats = ThrottleStats {
+        requests: 12,
+        throttled: 4,
+        total_wait: Duration::from_millis(600),
+    };
+    assert_eq!(stats.average_wait(), Duration::from_millis(150));
+    assert_eq!(
+        stats.to_string(),
+        "12 requests, 4 throttled, 0.6s waited (avg 150ms)"
+    );
+    assert_eq!(ThrottleStats::default().average_wait(), Duration::ZERO);
+}
+
+#[test]
+fn burst_env_value_falls_back_to_default() {
+    assert_eq!(burst_from_env_value(None), 10);
+    assert_eq!(burst_from_env_value(Some(" 25 ")), 25);
+    assert_eq!(burst_from_env_value(Some("0")), 10);
+    assert_eq!(burst_from_env_value(Some("fast")), 10);
+}
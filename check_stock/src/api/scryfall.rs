@@ -1,9 +1,14 @@
 //! Scryfall API client — thin wrappers over the shared client in `mtg_common`
-//! that convert errors into this crate's `ApiError`.
+//! that convert errors into this crate's `ApiError`. API calls wait for the
+//! shared [`scryfall_limiter`] first; image downloads from the CDN do not.
 
+use super::rate_limit::scryfall_limiter;
 use crate::error::ApiResult;
 
-pub use mtg_common::scryfall::{CardFace, ImageUris, ScryfallCard, ScryfallError, ScryfallPrices};
+pub use mtg_common::scryfall::{
+    for_each_bulk_card, BulkCard, BulkData, CardFace, ImageUris, ScryfallCard, ScryfallError,
+    ScryfallPrices, ScryfallSet, DEFAULT_CARDS,
+};
 
 /// Fetch a card from Scryfall by set code and collector number
 pub fn fetch_card(set_code: &str, collector_number: &str) -> ApiResult<ScryfallCard> {
@@ -20,6 +25,7 @@ pub(crate) fn fetch_card_from(
     set_code: &str,
     collector_number: &str,
 ) -> ApiResult<ScryfallCard> {
+    scryfall_limiter().acquire();
     Ok(mtg_common::scryfall::blocking::fetch_card_from(
         base_url,
         set_code,
@@ -27,11 +33,74 @@ pub(crate) fn fetch_card_from(
     )?)
 }
 
+/// Name of a printing in another language (a Scryfall code such as `de`),
+/// `None` when Scryfall has no such printing or no localized name for it
+pub fn fetch_printed_name(
+    set_code: &str,
+    collector_number: &str,
+    lang: &str,
+) -> ApiResult<Option<String>> {
+    fetch_printed_name_from(
+        mtg_common::scryfall::SCRYFALL_API,
+        set_code,
+        collector_number,
+        lang,
+    )
+}
+
+/// Fetches a printed name from the given base URL (for testing with mock servers).
+pub(crate) fn fetch_printed_name_from(
+    base_url: &str,
+    set_code: &str,
+    collector_number: &str,
+    lang: &str,
+) -> ApiResult<Option<String>> {
+    scryfall_limiter().acquire();
+    match mtg_common::scryfall::blocking::fetch_localized_card_from(
+        base_url,
+        set_code,
+        collector_number,
+        lang,
+    ) {
+        Ok(card) => Ok(card.printed_name.filter(|name| !name.trim().is_empty())),
+        Err(mtg_common::MtgError::Api { code, .. }) if code == "not_found" => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Fetch card image bytes
 pub fn fetch_image(url: &str) -> ApiResult<Vec<u8>> {
     Ok(mtg_common::scryfall::blocking::fetch_image(url)?)
 }
 
+/// Look up a bulk data export (e.g. [`DEFAULT_CARDS`]) and its download link
+pub fn fetch_bulk_data(kind: &str) -> ApiResult<BulkData> {
+    scryfall_limiter().acquire();
+    Ok(mtg_common::scryfall::blocking::fetch_bulk_data_from(
+        mtg_common::scryfall::SCRYFALL_API,
+        kind,
+    )?)
+}
+
+/// Download a bulk data file to `dest` (from the CDN, not rate limited)
+pub fn download_bulk_data(url: &str, dest: &std::path::Path) -> ApiResult<u64> {
+    Ok(mtg_common::scryfall::blocking::download_bulk_data(
+        url, dest,
+    )?)
+}
+
+/// Fetch the symbol SVG of a set (looks up the set's `icon_svg_uri` first)
+pub fn fetch_set_symbol(set_code: &str) -> ApiResult<Vec<u8>> {
+    fetch_set_symbol_from(mtg_common::scryfall::SCRYFALL_API, set_code)
+}
+
+/// Fetches a set symbol via the given API base URL (for testing with mock servers).
+pub(crate) fn fetch_set_symbol_from(base_url: &str, set_code: &str) -> ApiResult<Vec<u8>> {
+    scryfall_limiter().acquire();
+    let set = mtg_common::scryfall::blocking::fetch_set_from(base_url, set_code)?;
+    fetch_image(&set.icon_svg_uri)
+}
+
 /// Fetch a card from Scryfall by set code and collector number (async)
 pub async fn fetch_card_async(set_code: &str, collector_number: &str) -> ApiResult<ScryfallCard> {
     fetch_card_from_async(
@@ -48,6 +117,7 @@ pub(crate) async fn fetch_card_from_async(
     set_code: &str,
     collector_number: &str,
 ) -> ApiResult<ScryfallCard> {
+    scryfall_limiter().acquire_async().await;
     Ok(mtg_common::scryfall::fetch_card_from(base_url, set_code, collector_number).await?)
 }
 
@@ -4,8 +4,9 @@ use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 use super::scryfall::{
-    fetch_card_from, fetch_card_from_async, fetch_image, fetch_image_async, CardFace, ImageUris,
-    ScryfallCard, ScryfallPrices,
+    fetch_card_from, fetch_card_from_async, fetch_image, fetch_image_async,
+    fetch_printed_name_from, fetch_set_symbol_from, CardFace, ImageUris, ScryfallCard,
+    ScryfallPrices,
 };
 use crate::error::ApiError;
 
@@ -240,6 +241,7 @@ fn image_url_from_image_uris() {
         type_line: None,
         oracle_text: None,
         purchase_uris: None,
+        printed_name: None,
     };
 
     assert_eq!(card.image_url(), Some("https://example.com/normal.jpg"));
@@ -291,6 +293,7 @@ fn image_url_from_card_faces() {
         type_line: None,
         oracle_text: None,
         purchase_uris: None,
+        printed_name: None,
     };
 
     // Should return the front face image
@@ -314,6 +317,7 @@ fn image_url_none_when_both_missing() {
         type_line: None,
         oracle_text: None,
         purchase_uris: None,
+        printed_name: None,
     };
 
     assert_eq!(card.image_url(), None);
@@ -503,3 +507,93 @@ async fn fetch_image_async_returns_full_bytes() {
     assert_eq!(result, payload);
     assert_eq!(result.len(), 16);
 }
+
+// ── fetch_set_symbol_from ────────────────────────────────────────────
+
+#[tokio::test]
+async fn fetch_set_symbol_follows_icon_uri() {
+    let mock_server = MockServer::start().await;
+    let svg = b"<svg viewBox=\"0 0 32 32\"><path d=\"M0 0H32V32Z\"/></svg>".to_vec();
+
+    Mock::given(method("GET"))
+        .and(path("/sets/pm21"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "set",
+            "code": "pm21",
+            "name": "Core Set 2021 Promos",
+            "icon_svg_uri": format!("{}/svg/m21.svg", mock_server.uri()),
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/svg/m21.svg"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(svg.clone()))
+        .mount(&mock_server)
+        .await;
+
+    let base_url = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || fetch_set_symbol_from(&base_url, "PM21"))
+        .await
+        .unwrap();
+
+    assert_eq!(result.unwrap(), svg);
+}
+
+#[tokio::test]
+async fn fetch_set_symbol_unknown_set_returns_api_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/sets/zzz"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(scryfall_error_json("not_found", "No set found")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let base_url = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || fetch_set_symbol_from(&base_url, "zzz"))
+        .await
+        .unwrap();
+
+    assert!(matches!(result, Err(ApiError::ApiResponse { .. })));
+}
+
+// ── fetch_printed_name_from ──────────────────────────────────────────
+
+#[tokio::test]
+async fn fetch_printed_name_reads_the_localized_printing() {
+    let mock_server = MockServer::start().await;
+
+    let mut card = scryfall_card_json("Lightning Bolt", "m10", "146");
+    card["lang"] = "de".into();
+    card["printed_name"] = "Blitzschlag".into();
+    Mock::given(method("GET"))
+        .and(path("/cards/m10/146/de"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(card))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/cards/m10/146/it"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(scryfall_error_json("not_found", "No card found")),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let base_url = mock_server.uri();
+    let (german, italian) = tokio::task::spawn_blocking(move || {
+        (
+            fetch_printed_name_from(&base_url, "M10", "146", "de"),
+            fetch_printed_name_from(&base_url, "M10", "146", "it"),
+        )
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(german.unwrap().as_deref(), Some("Blitzschlag"));
+    // No Italian printing is a miss, not an error
+    assert_eq!(italian.unwrap(), None);
+}
@@ -30,7 +30,7 @@
 //! All logic here is pure and free of I/O so it can be tested deterministically.
 
 use crate::card_matching::parse_location_code;
-use crate::models::Card;
+use crate::models::{canonical_condition, canonical_language, Card};
 use std::collections::{HashMap, HashSet};
 
 /// Maximum cards a single bin holds. Mirrors `StockAnalysis::BIN_CAPACITY`.
@@ -112,8 +112,8 @@ fn variant_key(c: &Card) -> String {
     format!(
         "{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}",
         c.cardmarket_id,
-        c.condition.trim().to_lowercase(),
-        c.language.trim().to_lowercase(),
+        canonical_condition(&c.condition),
+        canonical_language(c.language.trim()),
         c.is_foil_card(),
         c.is_signed_card(),
     )
@@ -0,0 +1,93 @@
+//! Inventory-wide repricing — applying the pricing rules to all of the stock.
+//!
+//! The Stock Pricing screen evaluates its node graph against one loaded CSV.
+//! A bulk repricing runs the same graph against every in-stock variant in the
+//! inventory DB instead, so the whole stock follows the price guide in one
+//! step. This module turns the graph's result into a list of
+//! [`PriceChange`]s and renders them as a Cardmarket stock CSV;
+//! `inventory_db::apply_price_changes` writes the new prices to the DB and
+//! records each change in the `price_changes` audit table.
+//!
+//! Like the other report modules this is pure: the caller evaluates the graph
+//! and passes the cards, the new prices and the rule behind each one.
+
+use crate::formatters::format_price_diff_csv;
+use crate::models::Card;
+use std::collections::HashMap;
+
+/// Price changes smaller than this (in EUR) are rounding noise, not changes.
+const MIN_PRICE_CHANGE: f64 = 0.005;
+
+/// One listing whose price a repricing changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceChange {
+    /// The listing as it is today (with the old price).
+    pub card: Card,
+    pub old_price: f64,
+    pub new_price: f64,
+    /// Pricing rule (graph node) that set the new price.
+    pub rule: String,
+}
+
+/// The changes the pricing graph's output amounts to: every card in
+/// `indices` whose `overrides` price differs from its listed price, with the
+/// rule from `rules` (`"pricing graph"` when unknown). New prices are rounded
+/// to cents, as Cardmarket lists them.
+pub fn price_changes(
+    cards: &[Card],
+    indices: &[usize],
+    overrides: &HashMap<usize, f64>,
+    rules: &HashMap<usize, String>,
+) -> Vec<PriceChange> {
+    indices
+        .iter()
+        .filter_map(|&idx| {
+            let card = cards.get(idx)?;
+            let new_price = (overrides.get(&idx)? * 100.0).round() / 100.0;
+            let old_price = card.price_f64();
+            if (new_price - old_price).abs() < MIN_PRICE_CHANGE {
+                return None;
+            }
+            Some(PriceChange {
+                card: card.clone(),
+                old_price,
+                new_price,
+                rule: rules
+                    .get(&idx)
+                    .cloned()
+                    .unwrap_or_else(|| "pricing graph".to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Cardmarket stock CSV with the new price of every change, for the bulk
+/// price update upload. Same columns as the pricing screen's diff CSV.
+pub fn format_bulk_price_csv(changes: &[PriceChange]) -> String {
+    let cards: Vec<Card> = changes.iter().map(|c| c.card.clone()).collect();
+    let indices: Vec<usize> = (0..changes.len()).collect();
+    let overrides: HashMap<usize, f64> = changes
+        .iter()
+        .enumerate()
+        .map(|(idx, c)| (idx, c.new_price))
+        .collect();
+    format_price_diff_csv(&cards, &indices, &overrides)
+}
+
+/// Summary line for a repricing: how many listings went up and down.
+pub fn summarize(changes: &[PriceChange]) -> String {
+    let raised = changes.iter().filter(|c| c.new_price > c.old_price).count();
+    let delta: f64 = changes
+        .iter()
+        .map(|c| (c.new_price - c.old_price) * c.card.quantity.parse::<f64>().unwrap_or(1.0))
+        .sum();
+    format!(
+        "{} listings repriced ({raised} up, {} down), stock value {delta:+.2} €",
+        changes.len(),
+        changes.len() - raised
+    )
+}
+
+#[cfg(test)]
+#[path = "bulk_repricing_tests.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+//! Tests for bulk repricing.
+
+use super::*;
+
+fn card(id: &str, price: &str, qty: &str) -> Card {
+    Card {
+        cardmarket_id: id.into(),
+        price: price.into(),
+        quantity: qty.into(),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn only_changed_prices_become_changes() {
+    let cards = vec![
+        card("1", "1.00", "2"),
+        card("2", "3.00", "1"),
+        card("3", "5.00", "1"),
+    ];
+    let overrides = HashMap::from([(0, 1.504), (1, 3.001), (2, 4.0)]);
+    let rules = HashMap::from([(0, "Price Floor".to_string())]);
+
+    // Card 2 is not in the output; card 1 moves by less than a cent
+    let changes = price_changes(&cards, &[0, 1], &overrides, &rules);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].card.cardmarket_id, "1");
+    assert_eq!(changes[0].old_price, 1.0);
+    assert_eq!(changes[0].new_price, 1.5);
+    assert_eq!(changes[0].rule, "Price Floor");
+
+    let changes = price_changes(&cards, &[2], &overrides, &rules);
+    assert_eq!(changes[0].rule, "pricing graph");
+}
+
+#[test]
+fn bulk_csv_carries_the_new_prices() {
+    let changes = price_changes(
+        &[card("1", "1.00", "2"), card("2", "3.00", "1")],
+        &[0, 1],
+        &HashMap::from([(0, 1.25), (1, 2.5)]),
+        &HashMap::new(),
+    );
+
+    let csv = format_bulk_price_csv(&changes);
+    let rows: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with("cardmarketId,quantity,"));
+    assert!(rows[1].starts_with("1,2,") && rows[1].contains(",1.25,"));
+    assert!(rows[2].starts_with("2,1,") && rows[2].contains(",2.50,"));
+}
+
+#[test]
+fn summary_counts_direction_and_stock_value() {
+    let changes = price_changes(
+        &[card("1", "1.00", "2"), card("2", "3.00", "1")],
+        &[0, 1],
+        &HashMap::from([(0, 1.5), (1, 2.0)]),
+        &HashMap::new(),
+    );
+
+    assert_eq!(
+        summarize(&changes),
+        "2 listings repriced (1 up, 1 down), stock value +0.00 €"
+    );
+}
@@ -1,7 +1,13 @@
-use crate::api::scryfall::{fetch_card, ScryfallCard};
+use crate::api::scryfall::{fetch_card, fetch_card_async, ScryfallCard};
 use crate::error::ApiResult;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a cached card counts as fresh. Scryfall updates prices daily;
+/// names and image URLs practically never change.
+pub const CARD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
 /// Persistent cache for Scryfall card lookups
 /// Stores cards in a JSON file to avoid redundant API calls
@@ -9,6 +15,17 @@ use std::collections::HashMap;
 pub struct CardCache {
     /// Map of "set/collector_number" to card data
     cards: HashMap<String, ScryfallCard>,
+    /// Unix seconds each entry was fetched at. Entries without one (written
+    /// before the TTL existed) count as expired.
+    #[serde(default)]
+    fetched_at: HashMap<String, u64>,
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 impl CardCache {
@@ -25,8 +42,9 @@ impl CardCache {
         let path = Self::cache_path();
         if path.exists() {
             match std::fs::read_to_string(&path) {
-                Ok(content) => match serde_json::from_str(&content) {
-                    Ok(cache) => {
+                Ok(content) => match serde_json::from_str::<Self>(&content) {
+                    Ok(mut cache) => {
+                        cache.prune_expired(unix_now());
                         log::info!("Loaded card cache with {} entries", Self::count(&cache));
                         return cache;
                     }
@@ -68,15 +86,42 @@ impl CardCache {
         format!("{}/{}", set_code.to_lowercase(), collector_number)
     }
 
-    /// Get a card from cache
+    /// Get a card from cache, if it was fetched within [`CARD_TTL`]
     pub fn get(&self, set_code: &str, collector_number: &str) -> Option<&ScryfallCard> {
-        self.cards.get(&Self::key(set_code, collector_number))
+        self.get_at(set_code, collector_number, unix_now())
+    }
+
+    fn get_at(&self, set_code: &str, collector_number: &str, now: u64) -> Option<&ScryfallCard> {
+        let key = Self::key(set_code, collector_number);
+        let fetched_at = *self.fetched_at.get(&key)?;
+        if now.saturating_sub(fetched_at) > CARD_TTL.as_secs() {
+            return None;
+        }
+        self.cards.get(&key)
     }
 
     /// Insert a card into cache
     pub fn insert(&mut self, set_code: &str, collector_number: &str, card: ScryfallCard) {
-        self.cards
-            .insert(Self::key(set_code, collector_number), card);
+        self.insert_at(set_code, collector_number, card, unix_now());
+    }
+
+    fn insert_at(&mut self, set_code: &str, collector_number: &str, card: ScryfallCard, now: u64) {
+        let key = Self::key(set_code, collector_number);
+        self.fetched_at.insert(key.clone(), now);
+        self.cards.insert(key, card);
+    }
+
+    /// Drops entries older than [`CARD_TTL`] so the file does not grow forever.
+    fn prune_expired(&mut self, now: u64) {
+        let ttl = CARD_TTL.as_secs();
+        let fetched_at = &self.fetched_at;
+        self.cards.retain(|key, _| {
+            fetched_at
+                .get(key)
+                .is_some_and(|&at| now.saturating_sub(at) <= ttl)
+        });
+        let cards = &self.cards;
+        self.fetched_at.retain(|key, _| cards.contains_key(key));
     }
 
     /// Get card count
@@ -119,6 +164,47 @@ pub fn fetch_card_cached(
     Ok(card)
 }
 
+/// The process-wide card cache, loaded from disk on first use. Screens and
+/// background tasks share it so one lookup serves all of them and concurrent
+/// saves do not overwrite each other's entries.
+pub fn shared() -> &'static Mutex<CardCache> {
+    static CACHE: OnceLock<Mutex<CardCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(CardCache::load()))
+}
+
+/// A fresh card from the shared cache, if any.
+pub fn shared_get(set_code: &str, collector_number: &str) -> Option<ScryfallCard> {
+    shared()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(set_code, collector_number)
+        .cloned()
+}
+
+/// Stores a card in the shared cache and saves it to disk.
+pub fn shared_insert(set_code: &str, collector_number: &str, card: ScryfallCard) {
+    let mut cache = shared().lock().unwrap_or_else(|e| e.into_inner());
+    cache.insert(set_code, collector_number, card);
+    if let Err(e) = cache.save() {
+        log::warn!("Failed to save card cache: {}", e);
+    }
+}
+
+/// Fetch a card (async), answering from the shared cache when it is fresh.
+/// The lock is not held while the request runs.
+pub async fn fetch_card_shared_async(
+    set_code: &str,
+    collector_number: &str,
+) -> ApiResult<ScryfallCard> {
+    if let Some(card) = shared_get(set_code, collector_number) {
+        log::debug!("Card cache hit for {}/{}", set_code, collector_number);
+        return Ok(card);
+    }
+    let card = fetch_card_async(set_code, collector_number).await?;
+    shared_insert(set_code, collector_number, card.clone());
+    Ok(card)
+}
+
 #[cfg(test)]
 #[path = "card_cache_tests.rs"]
 mod tests;
@@ -18,6 +18,7 @@ fn create_test_card(name: &str, set: &str, collector_number: &str) -> ScryfallCa
         type_line: None,
         oracle_text: None,
         purchase_uris: None,
+        printed_name: None,
     }
 }
 
@@ -138,3 +139,27 @@ fn test_serialization_format() {
     // Verify the key format in JSON
     assert!(json.contains("\"lea/1\""));
 }
+
+#[test]
+fn test_entries_expire_after_ttl() {
+    let mut cache = CardCache::default();
+    cache.insert_at("lea", "1", create_test_card("Old", "lea", "1"), 1_000);
+    let ttl = CARD_TTL.as_secs();
+
+    assert!(cache.get_at("lea", "1", 1_000 + ttl).is_some());
+    assert!(cache.get_at("lea", "1", 1_001 + ttl).is_none());
+}
+
+#[test]
+fn test_legacy_entries_without_timestamp_are_stale_and_pruned() {
+    let legacy = r#"{"cards": {"lea/1": {"id": "x", "name": "Old", "set": "lea",
+        "set_name": "Alpha", "collector_number": "1", "rarity": "rare", "prices": {}}}}"#;
+    let mut cache: CardCache = serde_json::from_str(legacy).unwrap();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get("lea", "1").is_none());
+
+    cache.insert_at("lea", "2", create_test_card("New", "lea", "2"), 5_000);
+    cache.prune_expired(5_000);
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get_at("lea", "2", 5_000).is_some());
+}
@@ -2,6 +2,8 @@
 
 pub mod card_cache;
 pub mod image_cache;
+pub mod set_symbol_cache;
 
-pub use card_cache::{fetch_card_cached, CardCache};
+pub use card_cache::{fetch_card_cached, fetch_card_shared_async, CardCache};
 pub use image_cache::{fetch_image_cached, ImageCache};
+pub use set_symbol_cache::{fetch_set_symbol_cached, SetSymbolCache};
@@ -0,0 +1,74 @@
+use crate::api::scryfall::fetch_set_symbol;
+use crate::error::ApiResult;
+use mtg_common::FileCache;
+
+/// Persistent cache for set symbol SVGs, keyed by set code.
+/// Symbols never change, so cached files are kept indefinitely.
+pub struct SetSymbolCache {
+    files: FileCache,
+}
+
+impl Default for SetSymbolCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SetSymbolCache {
+    /// Create a new symbol cache in the platform cache directory
+    pub fn new() -> Self {
+        let cache_dir = dirs::cache_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("d2d_automations")
+            .join("set_symbols");
+        Self::with_dir(cache_dir)
+    }
+
+    /// Create a symbol cache rooted at the given directory (used by tests)
+    pub fn with_dir(cache_dir: std::path::PathBuf) -> Self {
+        Self {
+            files: FileCache::new(cache_dir),
+        }
+    }
+
+    /// Generate a filename from the set code, keeping only safe characters
+    fn filename(set_code: &str) -> String {
+        let code: String = set_code
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        format!("{code}.svg")
+    }
+
+    /// Get a cached symbol
+    pub fn get(&self, set_code: &str) -> Option<Vec<u8>> {
+        self.files.read(&Self::filename(set_code))
+    }
+
+    /// Store a symbol in the cache
+    pub fn insert(&self, set_code: &str, bytes: &[u8]) {
+        self.files.write(&Self::filename(set_code), bytes);
+        log::debug!("Cached set symbol for {}", set_code);
+    }
+}
+
+/// Fetch a set symbol SVG, checking the cache first
+pub fn fetch_set_symbol_cached(cache: &SetSymbolCache, set_code: &str) -> ApiResult<Vec<u8>> {
+    if let Some(bytes) = cache.get(set_code) {
+        return Ok(bytes);
+    }
+
+    log::info!(
+        "Set symbol cache miss for {}, fetching from Scryfall",
+        set_code
+    );
+    let bytes = fetch_set_symbol(set_code)?;
+    cache.insert(set_code, &bytes);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+#[path = "set_symbol_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,37 @@
+//! Tests for set_symbol_cache.
+
+use super::*;
+use tempfile::TempDir;
+
+fn create_test_cache() -> (SetSymbolCache, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let cache = SetSymbolCache::with_dir(temp_dir.path().to_path_buf());
+    (cache, temp_dir)
+}
+
+#[test]
+fn test_filename_lowercases_and_sanitizes() {
+    assert_eq!(SetSymbolCache::filename("MH2"), "mh2.svg");
+    assert_eq!(SetSymbolCache::filename(" pM21 "), "pm21.svg");
+    assert_eq!(SetSymbolCache::filename("../etc"), "etc.svg");
+}
+
+#[test]
+fn test_get_nonexistent_returns_none() {
+    let (cache, _temp_dir) = create_test_cache();
+    assert!(cache.get("lea").is_none());
+}
+
+#[test]
+fn test_insert_and_get_case_insensitive() {
+    let (cache, _temp_dir) = create_test_cache();
+    cache.insert("MH2", b"<svg/>");
+    assert_eq!(cache.get("mh2").as_deref(), Some(&b"<svg/>"[..]));
+}
+
+#[test]
+fn test_fetch_cached_hit_skips_network() {
+    let (cache, _temp_dir) = create_test_cache();
+    cache.insert("lea", b"<svg/>");
+    assert_eq!(fetch_set_symbol_cached(&cache, "LEA").unwrap(), b"<svg/>");
+}
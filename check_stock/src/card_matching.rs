@@ -1,5 +1,6 @@
-use crate::models::{Card, Language};
+use crate::models::{Card, Language, WantsEntry};
 use log::debug;
+use rayon::prelude::*;
 use std::collections::HashMap;
 
 pub fn get_card_name(card: &Card, language: Option<Language>) -> &str {
@@ -12,6 +13,58 @@ pub fn get_card_name(card: &Card, language: Option<Language>) -> &str {
     }
 }
 
+/// Case-insensitive name comparison that also folds non-ASCII letters
+/// (`ÆTHER VIAL` = `Æther Vial`), consistent with how wantslist parsing
+/// merges duplicate names.
+fn names_match(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || (!a.is_ascii() && a.to_lowercase() == b.to_lowercase())
+}
+
+/// Whether `card` satisfies a wantslist entry named `card_name` (already trimmed).
+///
+/// With `preferred_language_only`, the name must match the card's name in the
+/// preferred language (English when none is set) and the listing must be in
+/// that language; otherwise any localized name matches.
+pub(crate) fn matches_wanted_name(
+    card: &Card,
+    card_name: &str,
+    preferred_language: Option<Language>,
+    preferred_language_only: bool,
+) -> bool {
+    if preferred_language_only {
+        if let Some(lang) = preferred_language {
+            names_match(get_card_name(card, Some(lang)).trim(), card_name)
+                && Language::parse(&card.language) == Some(lang)
+        } else {
+            // If no preferred language is set, fallback to English
+            names_match(get_card_name(card, None).trim(), card_name)
+                && card
+                    .language
+                    .eq_ignore_ascii_case(Language::English.as_str())
+        }
+    } else {
+        // Match any language
+        Language::all()
+            .iter()
+            .any(|lang| names_match(get_card_name(card, Some(*lang)).trim(), card_name))
+    }
+}
+
+/// Whether `card` fills `want`: its name matches and it meets the entry's
+/// [`WantsConstraints`](crate::models::WantsConstraints). An entry that asks
+/// for a language is matched by name in any language, so the constraint
+/// rather than the preferred language decides.
+pub(crate) fn matches_want(
+    card: &Card,
+    want: &WantsEntry,
+    preferred_language: Option<Language>,
+    preferred_language_only: bool,
+) -> bool {
+    let language_only = preferred_language_only && want.constraints.language.is_none();
+    matches_wanted_name(card, want.name.trim(), preferred_language, language_only)
+        && want.constraints.matches(card)
+}
+
 #[derive(Clone)]
 pub struct MatchedCard<'a> {
     pub card: &'a Card,
@@ -26,34 +79,26 @@ pub fn find_matching_cards<'a>(
     preferred_language: Option<Language>,
     preferred_language_only: bool,
 ) -> Vec<MatchedCard<'a>> {
-    let trimmed_card_name = card_name.trim();
+    find_matches_for_entry(
+        &WantsEntry::new(needed_quantity, card_name.trim()),
+        inventory,
+        preferred_language,
+        preferred_language_only,
+    )
+}
+
+/// [`find_matching_cards`] for a wantslist entry, honouring its constraints.
+pub fn find_matches_for_entry<'a>(
+    want: &WantsEntry,
+    inventory: &'a [Card],
+    preferred_language: Option<Language>,
+    preferred_language_only: bool,
+) -> Vec<MatchedCard<'a>> {
+    let trimmed_card_name = want.name.trim();
+    let needed_quantity = want.quantity;
     let matching_cards: Vec<_> = inventory
         .iter()
-        .filter(|card| {
-            if preferred_language_only {
-                if let Some(lang) = preferred_language {
-                    get_card_name(card, Some(lang))
-                        .trim()
-                        .eq_ignore_ascii_case(trimmed_card_name)
-                        && card.language.eq_ignore_ascii_case(lang.as_str())
-                } else {
-                    // If no preferred language is set, fallback to English
-                    get_card_name(card, None)
-                        .trim()
-                        .eq_ignore_ascii_case(trimmed_card_name)
-                        && card
-                            .language
-                            .eq_ignore_ascii_case(Language::English.as_str())
-                }
-            } else {
-                // Match any language
-                Language::all().iter().any(|lang| {
-                    get_card_name(card, Some(*lang))
-                        .trim()
-                        .eq_ignore_ascii_case(trimmed_card_name)
-                })
-            }
-        })
+        .filter(|card| matches_want(card, want, preferred_language, preferred_language_only))
         .collect();
 
     if matching_cards.is_empty() {
@@ -91,7 +136,7 @@ pub fn find_matching_cards<'a>(
                 .then_with(|| {
                     let pa = a.price.parse::<f64>().unwrap_or(f64::MAX);
                     let pb = b.price.parse::<f64>().unwrap_or(f64::MAX);
-                    pa.partial_cmp(&pb).unwrap_or(std::cmp::Ordering::Equal)
+                    pa.total_cmp(&pb)
                 })
                 .then_with(|| a.name.cmp(&b.name))
                 .then_with(|| a.cardmarket_id.cmp(&b.cardmarket_id))
@@ -106,10 +151,7 @@ pub fn find_matching_cards<'a>(
     sets.sort_by(|a, b| {
         let price_a = a.1[0].price.parse::<f64>().unwrap_or(f64::MAX);
         let price_b = b.1[0].price.parse::<f64>().unwrap_or(f64::MAX);
-        price_a
-            .partial_cmp(&price_b)
-            .unwrap()
-            .then_with(|| b.0.cmp(a.0))
+        price_a.total_cmp(&price_b).then_with(|| b.0.cmp(a.0))
     });
 
     // Add cards from each set until we have enough
@@ -125,7 +167,7 @@ pub fn find_matching_cards<'a>(
             if let Ok(quantity) = card.quantity.parse::<i32>() {
                 if quantity > 0 {
                     let effective_quantity = if card.is_playset_card() {
-                        quantity * 4
+                        quantity.saturating_mul(4)
                     } else {
                         quantity
                     };
@@ -144,6 +186,29 @@ pub fn find_matching_cards<'a>(
     result
 }
 
+/// [`find_matches_for_entry`] for every wantslist entry, in wantslist order.
+///
+/// Entries only read the inventory, so they are matched in parallel; large
+/// wantslists against a large stock no longer run on a single core.
+pub fn find_matches_for_wantslist<'a>(
+    wantslist: &[WantsEntry],
+    inventory: &'a [Card],
+    preferred_language: Option<Language>,
+    preferred_language_only: bool,
+) -> Vec<Vec<MatchedCard<'a>>> {
+    wantslist
+        .par_iter()
+        .map(|entry| {
+            find_matches_for_entry(
+                entry,
+                inventory,
+                preferred_language,
+                preferred_language_only,
+            )
+        })
+        .collect()
+}
+
 pub fn parse_location_code(loc: &str) -> Vec<i32> {
     let main_part = loc.split("-L0").next().unwrap_or(loc);
 
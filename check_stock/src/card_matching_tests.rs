@@ -1,6 +1,7 @@
 //! Tests for card_matching.
 
 use super::*;
+use crate::models::WantsConstraints;
 
 fn create_test_card(name: &str, language: &str) -> Card {
     Card {
@@ -232,3 +233,166 @@ fn test_find_matching_cards_not_enough_quantity() {
     assert_eq!(matches.len(), 1);
     assert_eq!(matches[0].quantity, 2); // Takes all available
 }
+
+#[test]
+fn test_find_matches_for_wantslist_keeps_wantslist_order() {
+    let inventory = vec![
+        create_test_card("Lightning Bolt", "English"),
+        create_test_card("Counterspell", "English"),
+    ];
+    let wantslist = vec![
+        WantsEntry::new(2, "Counterspell"),
+        WantsEntry::new(1, "Black Lotus"),
+        WantsEntry::new(1, "Lightning Bolt"),
+    ];
+
+    let per_entry = find_matches_for_wantslist(&wantslist, &inventory, None, false);
+
+    assert_eq!(per_entry.len(), 3);
+    assert_eq!(per_entry[0][0].card.name, "Counterspell");
+    assert_eq!(per_entry[0][0].quantity, 2);
+    assert!(per_entry[1].is_empty());
+    assert_eq!(per_entry[2][0].card.name, "Lightning Bolt");
+}
+
+#[test]
+fn test_wantslist_constraints_select_exact_printing() {
+    let printing = |set_code: &str, cn: &str, condition: &str, price: &str| Card {
+        set_code: set_code.to_string(),
+        set: set_code.to_string(),
+        cn: cn.to_string(),
+        condition: condition.to_string(),
+        price: price.to_string(),
+        ..create_test_card("Lightning Bolt", "English")
+    };
+    let inventory = vec![
+        printing("M10", "146", "NM", "0.50"),
+        printing("2XM", "123", "NM", "1.50"),
+        printing("2XM", "0123", "LP", "0.90"),
+        printing("2XM", "123", "EX", "3.00"),
+    ];
+    let mut want = WantsEntry::new(8, "Lightning Bolt");
+    want.constraints = WantsConstraints {
+        set_code: Some("2xm".to_string()),
+        collector_number: Some("123".to_string()),
+        min_condition: Some("EX".to_string()),
+        max_price: Some(2.0),
+        ..WantsConstraints::default()
+    };
+
+    let result = find_matches_for_wantslist(&[want], &inventory, None, false);
+
+    let picked: Vec<_> = result[0]
+        .iter()
+        .map(|m| (m.card.set_code.as_str(), m.card.price.as_str()))
+        .collect();
+    assert_eq!(picked, vec![("2XM", "1.50")]);
+}
+
+#[test]
+fn test_language_constraint_overrides_preferred_language_only() {
+    let inventory = vec![
+        create_test_card("Lightning Bolt", "English"),
+        create_test_card("Lightning Bolt", "German"),
+    ];
+    let mut want = WantsEntry::new(1, "Lightning Bolt");
+    want.constraints.language = Some("de".to_string());
+
+    let result = find_matches_for_wantslist(&[want], &inventory, Some(Language::English), true);
+
+    assert_eq!(result[0].len(), 1);
+    assert_eq!(result[0][0].card.language, "German");
+}
+
+// ==================== Property Tests ====================
+
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    const NAMES: &[&str] = &["Lightning Bolt", "Æther Vial", "Jötun Grunt", "Séance"];
+
+    /// Inventory rows with well-formed and malformed quantities and prices.
+    fn inventory_card() -> impl Strategy<Value = Card> {
+        let quantity = prop_oneof![
+            (-3..50i32).prop_map(|q| q.to_string()),
+            (0..=i32::MAX).prop_map(|q| q.to_string()),
+            Just(String::new()),
+            Just("abc".to_string()),
+        ];
+        let price = prop_oneof![
+            (0..10_000u32).prop_map(|c| format!("{}.{:02}", c / 100, c % 100)),
+            Just(String::new()),
+            Just("NaN".to_string()),
+            Just("inf".to_string()),
+            Just("-1".to_string()),
+        ];
+        (
+            0..NAMES.len(),
+            quantity,
+            price,
+            prop::option::of(Just("1".to_string())),
+            prop_oneof![Just("English"), Just("German"), Just("French")],
+            0..4u8,
+            any::<u16>(),
+        )
+            .prop_map(
+                |(name, quantity, price, is_playset, language, set, id)| Card {
+                    cardmarket_id: id.to_string(),
+                    quantity,
+                    name: NAMES[name].to_string(),
+                    set: format!("Set {set}"),
+                    set_code: format!("S{set}"),
+                    price,
+                    is_playset,
+                    language: language.to_string(),
+                    ..Card::test_default()
+                },
+            )
+    }
+
+    /// Copies a listing can supply (playsets count four per unit).
+    fn available(card: &Card) -> i64 {
+        match card.quantity.parse::<i32>() {
+            Ok(q) if q > 0 && card.is_playset_card() => i64::from(q) * 4,
+            Ok(q) if q > 0 => i64::from(q),
+            _ => 0,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matched_quantity_never_exceeds_requested_or_available(
+            inventory in prop::collection::vec(inventory_card(), 0..12),
+            name in 0..NAMES.len(),
+            needed in prop_oneof![-5..200i32, 0..=i32::MAX],
+            upper in prop::bool::ANY,
+        ) {
+            let wanted = if upper {
+                NAMES[name].to_uppercase()
+            } else {
+                NAMES[name].to_string()
+            };
+            let matches = find_matching_cards(&format!(" {wanted} "), needed, &inventory, None, false);
+
+            let mut total = 0i64;
+            for m in &matches {
+                prop_assert!(m.quantity > 0);
+                prop_assert!(i64::from(m.quantity) <= available(m.card));
+                prop_assert_eq!(m.card.name.as_str(), NAMES[name]);
+                total += i64::from(m.quantity);
+            }
+            // Each listing is used at most once
+            for (i, a) in matches.iter().enumerate() {
+                prop_assert!(matches[i + 1..].iter().all(|b| !std::ptr::eq(a.card, b.card)));
+            }
+
+            let supply: i64 = inventory
+                .iter()
+                .filter(|c| c.name == NAMES[name])
+                .map(available)
+                .sum();
+            prop_assert_eq!(total, supply.min(i64::from(needed.max(0))));
+        }
+    }
+}
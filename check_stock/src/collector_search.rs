@@ -0,0 +1,92 @@
+//! Printing lookups by set code and collector number
+//!
+//! A search term of exactly two words where one is a set code and the other a
+//! collector number (`mh2 261`, `261 MH2`, `sld 0042`, `war 1★`) names one
+//! printing. Collector numbers are compared without leading zeros and with
+//! `*` read as `★`, the way Scryfall prints promo stars.
+
+use crate::models::Card;
+
+/// A set code and collector number parsed from a search term
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintingQuery {
+    /// Set code, lowercase
+    pub set_code: String,
+    /// Collector number as typed (without a leading `#`)
+    pub collector_number: String,
+}
+
+/// Whether `token` looks like a collector number: digits with an optional
+/// one-character letter or star suffix.
+fn is_collector_number(token: &str) -> bool {
+    let digits = token.trim_end_matches(|c: char| !c.is_ascii_digit());
+    let suffix = &token[digits.len()..];
+    !digits.is_empty()
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && suffix.chars().count() <= 1
+        && suffix
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '*' || c == '★')
+}
+
+/// Whether `token` looks like a set code: 2–6 letters and digits, at least
+/// one of them a letter.
+fn is_set_code(token: &str) -> bool {
+    (2..=6).contains(&token.len())
+        && token.chars().all(|c| c.is_ascii_alphanumeric())
+        && token.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// Parses `"<set> <number>"` or `"<number> <set>"`. Returns `None` for
+/// anything else, so the term can fall back to a text search.
+pub fn parse_printing_query(term: &str) -> Option<PrintingQuery> {
+    let tokens: Vec<&str> = term.split_whitespace().collect();
+    let [first, second] = tokens.as_slice() else {
+        return None;
+    };
+    let number = |token: &str| {
+        let token = token.strip_prefix('#').unwrap_or(token);
+        is_collector_number(token).then(|| token.to_string())
+    };
+    let reading = |set: &str, number: Option<String>| {
+        number
+            .filter(|_| is_set_code(set))
+            .map(|collector_number| PrintingQuery {
+                set_code: set.to_lowercase(),
+                collector_number,
+            })
+    };
+    let set_first = reading(first, number(second));
+    let number_first = reading(second, number(first));
+    // "10e 5" reads both ways; the plain number wins, then set-first order.
+    let plain = |query: &PrintingQuery| query.collector_number.chars().all(|c| c.is_ascii_digit());
+    match (set_first, number_first) {
+        (Some(a), Some(b)) if plain(&b) && !plain(&a) => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+/// Canonical form of a collector number: lowercase, no leading zeros, `*`
+/// written as `★`.
+pub fn normalize_collector_number(collector_number: &str) -> String {
+    let lower = collector_number.trim().to_lowercase().replace('*', "★");
+    let trimmed = lower.trim_start_matches('0');
+    if trimmed.is_empty() || !trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+        // "0" and "0a" keep their zero
+        format!("0{trimmed}")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Whether `card` is the printing named by `query`.
+pub fn card_matches_printing(card: &Card, query: &PrintingQuery) -> bool {
+    card.set_code.eq_ignore_ascii_case(&query.set_code)
+        && normalize_collector_number(&card.cn)
+            == normalize_collector_number(&query.collector_number)
+}
+
+#[cfg(test)]
+#[path = "collector_search_tests.rs"]
+mod tests;
@@ -0,0 +1,68 @@
+//! Tests for collector number search.
+
+use super::*;
+
+fn printing(set_code: &str, collector_number: &str) -> Option<PrintingQuery> {
+    Some(PrintingQuery {
+        set_code: set_code.to_string(),
+        collector_number: collector_number.to_string(),
+    })
+}
+
+fn card(set_code: &str, cn: &str) -> Card {
+    Card {
+        set_code: set_code.to_string(),
+        cn: cn.to_string(),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn parses_set_and_number_in_either_order() {
+    assert_eq!(parse_printing_query("mh2 261"), printing("mh2", "261"));
+    assert_eq!(parse_printing_query("261 MH2"), printing("mh2", "261"));
+    assert_eq!(
+        parse_printing_query("  MH2   #261 "),
+        printing("mh2", "261")
+    );
+}
+
+#[test]
+fn parses_suffixed_numbers() {
+    assert_eq!(parse_printing_query("war 1★"), printing("war", "1★"));
+    assert_eq!(parse_printing_query("war 1*"), printing("war", "1*"));
+    assert_eq!(parse_printing_query("123a dom"), printing("dom", "123a"));
+}
+
+#[test]
+fn ambiguous_tokens_prefer_the_plain_number() {
+    assert_eq!(parse_printing_query("10e 5"), printing("10e", "5"));
+    assert_eq!(parse_printing_query("5 10e"), printing("10e", "5"));
+    assert_eq!(parse_printing_query("2x2 12"), printing("2x2", "12"));
+}
+
+#[test]
+fn other_terms_are_not_printing_queries() {
+    assert_eq!(parse_printing_query("lightning bolt"), None);
+    assert_eq!(parse_printing_query("261"), None);
+    assert_eq!(parse_printing_query("mh2 261 foil"), None);
+    assert_eq!(parse_printing_query("bolt 12ab"), None);
+    assert_eq!(parse_printing_query("123 456"), None);
+}
+
+#[test]
+fn normalizes_leading_zeros_and_stars() {
+    assert_eq!(normalize_collector_number("0042"), "42");
+    assert_eq!(normalize_collector_number("1*"), "1★");
+    assert_eq!(normalize_collector_number("12A"), "12a");
+    assert_eq!(normalize_collector_number("000"), "0");
+}
+
+#[test]
+fn matches_printing_ignoring_case_and_padding() {
+    let query = parse_printing_query("SLD 42").unwrap();
+
+    assert!(card_matches_printing(&card("sld", "0042"), &query));
+    assert!(!card_matches_printing(&card("sld", "420"), &query));
+    assert!(!card_matches_printing(&card("mh2", "42"), &query));
+}
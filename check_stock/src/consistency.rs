@@ -11,7 +11,7 @@
 //! band. Pure module: no database or UI access.
 
 use crate::inventory_db::InStockCard;
-use crate::models::canonical_condition;
+use crate::models::{canonical_condition, canonical_language, condition_rank};
 use std::collections::HashMap;
 
 /// What kind of contradiction a pair of listings exhibits.
@@ -47,20 +47,6 @@ pub struct ConsistencyIssue {
     pub details: String,
 }
 
-/// Rank of a canonical condition on the Cardmarket scale, best first.
-/// `None` for unrecognized conditions (excluded from inversion checks).
-fn condition_rank(condition: &str) -> Option<u8> {
-    match canonical_condition(condition).as_str() {
-        "NM" => Some(0),
-        "EX" => Some(1),
-        "GD" => Some(2),
-        "LP" => Some(3),
-        "PL" => Some(4),
-        "PO" => Some(5),
-        _ => None,
-    }
-}
-
 fn describe(card: &InStockCard) -> String {
     format!(
         "{}{} €{:.2} @ {}",
@@ -108,7 +94,7 @@ pub fn find_issues(cards: &[InStockCard]) -> Vec<ConsistencyIssue> {
 
 /// Checks one pair of same-product listings for a contradiction.
 fn pair_issue(a: &InStockCard, b: &InStockCard) -> Option<IssueKind> {
-    let same_language = a.language.eq_ignore_ascii_case(&b.language);
+    let same_language = canonical_language(&a.language) == canonical_language(&b.language);
     if !same_language {
         return None; // language differences legitimately change value
     }
@@ -69,9 +69,8 @@ pub fn fetch_deck(source: &DeckSource) -> Result<Vec<WantsEntry>, String> {
         ),
     };
 
-    let client = reqwest::blocking::Client::builder()
-        .timeout(HTTP_TIMEOUT)
-        .build()
+    let client = mtg_common::http::blocking_client_builder(HTTP_TIMEOUT)
+        .and_then(|builder| Ok(builder.build()?))
         .map_err(|e| e.to_string())?;
 
     let resp = client
@@ -139,7 +138,7 @@ pub fn parse_moxfield_json(body: &str) -> Result<Vec<WantsEntry>, String> {
                 .trim()
                 .to_string();
             if quantity > 0 && !name.is_empty() {
-                entries.push(WantsEntry { quantity, name });
+                entries.push(WantsEntry::new(quantity, name));
             }
         }
     }
@@ -189,7 +188,7 @@ pub fn parse_archidekt_json(body: &str) -> Result<Vec<WantsEntry>, String> {
             .to_string();
 
         if quantity > 0 && !name.is_empty() {
-            entries.push(WantsEntry { quantity, name });
+            entries.push(WantsEntry::new(quantity, name));
         }
     }
 
@@ -1,10 +1,7 @@
 use super::*;
 
 fn entry(quantity: i32, name: &str) -> WantsEntry {
-    WantsEntry {
-        quantity,
-        name: name.to_string(),
-    }
+    WantsEntry::new(quantity, name)
 }
 
 // ==================== parse_deck_url ====================
@@ -0,0 +1,200 @@
+//! Duplicate-listing detector — finds one article listed in several rows.
+//!
+//! Cardmarket treats every row of a stock upload as its own listing, so the
+//! same article (product, condition, language, foil/signed/playset and other
+//! special flags) can end up split across rows that differ only in comment,
+//! storage location or a few cents of price. Buyers see the cheaper row
+//! first and the rest sits. This module groups such rows and suggests one
+//! merged listing per group, exported as a stock-update CSV that removes the
+//! split rows and adds the merged one.
+//!
+//! It works on the stock CSV: the inventory DB keys rows by article, so
+//! duplicates are already collapsed there by the time a sync stores them.
+//! Pure module: no database or UI access.
+
+use std::collections::BTreeMap;
+
+use crate::models::{canonical_condition, canonical_language, Card};
+
+/// Listings of one article spread over several rows.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// The rows as they are today, in CSV order.
+    pub rows: Vec<Card>,
+    /// Suggested single listing holding every copy of `rows`.
+    pub merged: Card,
+    /// Lowest and highest unit price across the rows.
+    pub price_range: (f64, f64),
+    /// Fields whose values differ between the rows (`price`, `comment`,
+    /// `location`), for the report.
+    pub differing: Vec<&'static str>,
+}
+
+impl DuplicateGroup {
+    /// Total copies across all rows.
+    pub fn total_quantity(&self) -> i64 {
+        self.rows.iter().map(quantity).sum()
+    }
+
+    /// Price difference relative to the lowest price, in percent.
+    pub fn price_spread_pct(&self) -> f64 {
+        let (low, high) = self.price_range;
+        if low > 0.0 {
+            (high - low) / low * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Fields that make two rows the same Cardmarket article.
+type ArticleKey = (String, String, String, bool, bool, bool, bool, bool);
+
+fn article_key(card: &Card) -> ArticleKey {
+    (
+        card.cardmarket_id.trim().to_string(),
+        canonical_condition(&card.condition),
+        canonical_language(card.language.trim()),
+        card.is_foil_card(),
+        card.is_signed_card(),
+        card.is_playset_card(),
+        card.is_first_ed_card(),
+        card.is_reverse_holo_card(),
+    )
+}
+
+fn quantity(card: &Card) -> i64 {
+    card.quantity.trim().parse::<i64>().unwrap_or(0)
+}
+
+/// Groups rows of the same article. Rows without copies are ignored; groups
+/// are sorted by card name, then set code.
+pub fn find_duplicates(cards: &[Card]) -> Vec<DuplicateGroup> {
+    let mut by_article: BTreeMap<ArticleKey, Vec<&Card>> = BTreeMap::new();
+    for card in cards.iter().filter(|c| quantity(c) > 0) {
+        by_article.entry(article_key(card)).or_default().push(card);
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_article
+        .into_values()
+        .filter(|rows| rows.len() > 1)
+        .map(|rows| build_group(&rows))
+        .collect();
+    groups.sort_by(|a, b| {
+        a.merged
+            .name
+            .cmp(&b.merged.name)
+            .then_with(|| a.merged.set_code.cmp(&b.merged.set_code))
+    });
+    groups
+}
+
+/// Builds the merge suggestion: the row with the most copies (the higher
+/// price on ties) provides price and location; distinct comments are
+/// combined, its own first.
+fn build_group(rows: &[&Card]) -> DuplicateGroup {
+    let main = rows
+        .iter()
+        .max_by(|a, b| {
+            quantity(a)
+                .cmp(&quantity(b))
+                .then_with(|| a.price_f64().total_cmp(&b.price_f64()))
+        })
+        .expect("groups have at least two rows");
+
+    let mut comments: Vec<&str> = Vec::new();
+    for row in std::iter::once(main).chain(rows) {
+        let comment = row.comment.trim();
+        if !comment.is_empty() && !comments.contains(&comment) {
+            comments.push(comment);
+        }
+    }
+
+    let mut merged = (*main).clone();
+    merged.quantity = rows.iter().map(|c| quantity(c)).sum::<i64>().to_string();
+    merged.comment = comments.join(" / ");
+
+    let prices: Vec<f64> = rows.iter().map(|c| c.price_f64()).collect();
+    let low = prices.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut differing = Vec::new();
+    if high - low > 0.005 {
+        differing.push("price");
+    }
+    if comments.len() > 1
+        || (comments.len() == 1 && rows.iter().any(|c| c.comment.trim().is_empty()))
+    {
+        differing.push("comment");
+    }
+    if rows.iter().any(|c| c.location != main.location) {
+        differing.push("location");
+    }
+
+    DuplicateGroup {
+        rows: rows.iter().map(|c| (*c).clone()).collect(),
+        merged,
+        price_range: (low, high),
+        differing,
+    }
+}
+
+/// Generates a stock-update CSV applying the merge suggestions.
+///
+/// Each group becomes one row per split listing with a negative quantity
+/// (removing it) followed by the merged listing with the total quantity.
+/// Columns match [`crate::playset::format_playset_conversion_csv`].
+pub fn format_merge_csv(groups: &[DuplicateGroup]) -> String {
+    use csv::WriterBuilder;
+
+    let mut wtr = WriterBuilder::new().has_headers(true).from_writer(vec![]);
+
+    let _ = wtr.write_record([
+        "cardmarketId",
+        "quantity",
+        "name",
+        "set",
+        "setCode",
+        "cn",
+        "condition",
+        "language",
+        "isFoil",
+        "isPlayset",
+        "isSigned",
+        "price",
+        "comment",
+        "location",
+        "rarity",
+    ]);
+
+    for group in groups {
+        let removals = group.rows.iter().map(|card| (card, -quantity(card)));
+        for (card, quantity) in removals.chain([(&group.merged, quantity(&group.merged))]) {
+            let quantity = quantity.to_string();
+            let _ = wtr.write_record([
+                &card.cardmarket_id,
+                &quantity,
+                &card.name,
+                &card.set,
+                &card.set_code,
+                &card.cn,
+                &card.condition,
+                &card.language,
+                &card.is_foil,
+                card.is_playset.as_deref().unwrap_or(""),
+                &card.is_signed,
+                &card.price,
+                &card.comment,
+                card.location.as_deref().unwrap_or(""),
+                &card.rarity,
+            ]);
+        }
+    }
+
+    let data = wtr.into_inner().unwrap();
+    String::from_utf8(data).unwrap()
+}
+
+#[cfg(test)]
+#[path = "duplicates_tests.rs"]
+mod tests;
@@ -0,0 +1,115 @@
+//! Tests for duplicates.
+
+use super::*;
+
+fn listing(quantity: &str, price: &str, comment: &str, location: &str) -> Card {
+    Card {
+        cardmarket_id: "501".to_string(),
+        name: "Counterspell".to_string(),
+        set_code: "MH2".to_string(),
+        quantity: quantity.to_string(),
+        price: price.to_string(),
+        comment: comment.to_string(),
+        location: Some(location.to_string()),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn groups_same_article_across_rows() {
+    let cards = vec![
+        listing("1", "1.20", "", "A1_S1_R1_C1"),
+        listing("3", "1.25", "mint", "A1_S1_R1_C1"),
+        listing("2", "1.20", "from collection", "A1_S1_R1_C2"),
+    ];
+
+    let groups = find_duplicates(&cards);
+
+    assert_eq!(groups.len(), 1);
+    let group = &groups[0];
+    assert_eq!(group.rows.len(), 3);
+    assert_eq!(group.total_quantity(), 6);
+    assert_eq!(group.merged.quantity, "6");
+    assert_eq!(
+        group.merged.price, "1.25",
+        "price of the row with most copies"
+    );
+    assert_eq!(group.merged.comment, "mint / from collection");
+    assert_eq!(group.merged.location.as_deref(), Some("A1_S1_R1_C1"));
+    assert_eq!(group.differing, ["price", "comment", "location"]);
+    assert!((group.price_spread_pct() - 25.0 / 6.0).abs() < 1e-9);
+}
+
+#[test]
+fn different_variants_are_not_duplicates() {
+    let base = listing("1", "1.00", "", "A1_S1_R1_C1");
+    let cards = vec![
+        base.clone(),
+        Card {
+            condition: "EX".to_string(),
+            ..base.clone()
+        },
+        Card {
+            language: "German".to_string(),
+            ..base.clone()
+        },
+        Card {
+            is_foil: "true".to_string(),
+            ..base.clone()
+        },
+        Card {
+            is_playset: Some("1".to_string()),
+            ..base.clone()
+        },
+        Card {
+            cardmarket_id: "502".to_string(),
+            ..base
+        },
+    ];
+
+    assert!(find_duplicates(&cards).is_empty());
+}
+
+#[test]
+fn condition_spellings_and_empty_rows() {
+    let cards = vec![
+        listing("1", "1.00", "", "A1_S1_R1_C1"),
+        Card {
+            condition: "near_mint".to_string(),
+            ..listing("1", "1.00", "", "A1_S1_R1_C1")
+        },
+        listing("0", "0.90", "", "A1_S1_R1_C1"),
+    ];
+
+    let groups = find_duplicates(&cards);
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].rows.len(), 2, "sold-out rows are ignored");
+    assert!(groups[0].differing.is_empty());
+}
+
+#[test]
+fn merge_csv_removes_rows_and_adds_merged_listing() {
+    let cards = vec![
+        listing("1", "1.20", "a", "A1_S1_R1_C1"),
+        listing("2", "1.25", "b", "A1_S1_R1_C1"),
+    ];
+    let csv = format_merge_csv(&find_duplicates(&cards));
+
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let rows: Vec<(String, String, String)> = reader
+        .records()
+        .map(|r| {
+            let r = r.unwrap();
+            (r[1].to_string(), r[11].to_string(), r[12].to_string())
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            ("-1".to_string(), "1.20".to_string(), "a".to_string()),
+            ("-2".to_string(), "1.25".to_string(), "b".to_string()),
+            ("3".to_string(), "1.25".to_string(), "b / a".to_string()),
+        ]
+    );
+}
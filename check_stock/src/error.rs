@@ -70,6 +70,9 @@ impl From<mtg_common::MtgError> for ApiError {
             mtg_common::MtgError::HttpStatus(s) => ApiError::HttpStatus(s),
             mtg_common::MtgError::Api { code, details } => ApiError::ApiResponse { code, details },
             mtg_common::MtgError::Io(e) => ApiError::Io(e),
+            mtg_common::MtgError::Config(msg) => {
+                ApiError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, msg))
+            }
         }
     }
 }
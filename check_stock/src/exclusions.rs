@@ -0,0 +1,97 @@
+//! Do-not-sell list — stock the wantslist matcher must never offer.
+//!
+//! Reserved copies and display pieces stay in the inventory (and in the
+//! Cardmarket CSV), but must not end up in a quote, on a picking list or in a
+//! stock-update export. An [`Exclusion`] names a card by its Cardmarket
+//! product ID, either as a whole (every condition, language and finish) or as
+//! one variant. The list itself lives in the inventory DB's `sale_exclusions`
+//! table (see `inventory_db::get_exclusions`); this module is the pure part:
+//! matching listings against it and dropping excluded ones before the matcher
+//! sees the stock.
+
+use crate::models::{canonical_condition, canonical_language, Card};
+
+/// The variant an exclusion is limited to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExcludedVariant {
+    pub condition: String,
+    pub language: String,
+    pub foil: bool,
+    pub signed: bool,
+}
+
+impl ExcludedVariant {
+    /// The variant of a listing.
+    pub fn of(card: &Card) -> Self {
+        Self {
+            condition: canonical_condition(&card.condition),
+            language: canonical_language(&card.language),
+            foil: card.is_foil_card(),
+            signed: card.is_signed_card(),
+        }
+    }
+}
+
+/// One entry of the do-not-sell list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exclusion {
+    pub cardmarket_id: String,
+    /// Card name, for display
+    pub name: String,
+    /// `None` excludes every variant of the card
+    pub variant: Option<ExcludedVariant>,
+    /// Why the card is held back, e.g. "reserved for Friday's trade"
+    pub note: String,
+}
+
+impl Exclusion {
+    /// Whether `card` is a listing this exclusion holds back. Conditions and
+    /// languages compare through the normalization table, so `NM` and
+    /// `near_mint` are one condition.
+    pub fn matches(&self, card: &Card) -> bool {
+        if card.cardmarket_id.trim() != self.cardmarket_id {
+            return false;
+        }
+        match &self.variant {
+            None => true,
+            Some(variant) => *variant == ExcludedVariant::of(card),
+        }
+    }
+
+    /// What the exclusion covers, e.g. `NM English Foil` or `all variants`.
+    pub fn scope_label(&self) -> String {
+        match &self.variant {
+            None => "all variants".to_string(),
+            Some(v) => {
+                let mut label = format!("{} {}", v.condition, v.language);
+                if v.foil {
+                    label.push_str(" Foil");
+                }
+                if v.signed {
+                    label.push_str(" Signed");
+                }
+                label
+            }
+        }
+    }
+}
+
+/// Whether any exclusion holds `card` back.
+pub fn is_excluded(card: &Card, exclusions: &[Exclusion]) -> bool {
+    exclusions.iter().any(|e| e.matches(card))
+}
+
+/// The listings of `inventory` that may be sold, and how many were held back.
+pub fn without_excluded(inventory: &[Card], exclusions: &[Exclusion]) -> (Vec<Card>, usize) {
+    let sellable: Vec<Card> = inventory
+        .iter()
+        .filter(|card| !is_excluded(card, exclusions))
+        .cloned()
+        .collect();
+    let withheld = inventory.len() - sellable.len();
+    (sellable, withheld)
+}
+
+#[cfg(test)]
+#[path = "exclusions_tests.rs"]
+mod tests;
@@ -0,0 +1,64 @@
+//! Tests for the do-not-sell list.
+
+use super::*;
+
+fn listing(id: &str, condition: &str, language: &str, foil: &str) -> Card {
+    Card {
+        cardmarket_id: id.into(),
+        condition: condition.into(),
+        language: language.into(),
+        is_foil: foil.into(),
+        ..Card::test_default()
+    }
+}
+
+fn exclusion(id: &str, variant: Option<ExcludedVariant>) -> Exclusion {
+    Exclusion {
+        cardmarket_id: id.into(),
+        name: "Black Lotus".into(),
+        variant,
+        note: String::new(),
+    }
+}
+
+#[test]
+fn whole_card_exclusion_holds_back_every_variant() {
+    let excl = exclusion("42", None);
+
+    assert!(excl.matches(&listing("42", "NM", "English", "")));
+    assert!(excl.matches(&listing("42", "PL", "German", "1")));
+    assert!(!excl.matches(&listing("43", "NM", "English", "")));
+    assert_eq!(excl.scope_label(), "all variants");
+}
+
+#[test]
+fn variant_exclusion_matches_normalized_condition_and_language() {
+    let excl = exclusion(
+        "42",
+        Some(ExcludedVariant::of(&listing("42", "NM", "English", "1"))),
+    );
+
+    assert!(excl.matches(&listing("42", "near_mint", "en", "true")));
+    assert!(!excl.matches(&listing("42", "NM", "English", "")));
+    assert!(!excl.matches(&listing("42", "EX", "English", "1")));
+    assert_eq!(excl.scope_label(), "NM English Foil");
+}
+
+#[test]
+fn excluded_listings_are_removed_before_matching() {
+    let inventory = vec![
+        listing("42", "NM", "English", ""),
+        listing("42", "EX", "English", ""),
+        listing("7", "NM", "English", ""),
+    ];
+    let exclusions = vec![exclusion(
+        "42",
+        Some(ExcludedVariant::of(&listing("42", "NM", "English", ""))),
+    )];
+
+    let (sellable, withheld) = without_excluded(&inventory, &exclusions);
+
+    assert_eq!(withheld, 1);
+    assert_eq!(sellable, vec![inventory[1].clone(), inventory[2].clone()]);
+    assert_eq!(without_excluded(&inventory, &[]).1, 0);
+}
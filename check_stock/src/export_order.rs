@@ -0,0 +1,126 @@
+//! Stable ordering and item IDs for exports.
+//!
+//! Every export that lists inventory items — the text and interactive
+//! picking lists, the stock-update, pick-log and discrepancy CSVs and the
+//! saved picking sessions — orders them the same way:
+//!
+//! 1. storage location, in bin order (see [`parse_location_code`]), items
+//!    without a location last
+//! 2. card name
+//! 3. collector number, numerically (`9` before `10`, `10a` after `10`)
+//!
+//! and then by set code, language, condition, foil and Cardmarket ID, so no
+//! two different listings ever compare equal. Together with [`item_id`],
+//! which derives an ID from what identifies a listing rather than from its
+//! position, two exports of the same data produce the same lines in the same
+//! order and a diff between them shows only what really changed.
+
+use crate::card_matching::parse_location_code;
+use crate::models::Card;
+use std::cmp::Ordering;
+
+/// Compares two strings with runs of digits compared as numbers, so `A-2`
+/// sorts before `A-10`.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (digits_a, rest_a) = split_digits(a);
+                let (digits_b, rest_b) = split_digits(b);
+                let trimmed_a = digits_a.trim_start_matches('0');
+                let trimmed_b = digits_b.trim_start_matches('0');
+                let ord = trimmed_a
+                    .len()
+                    .cmp(&trimmed_b.len())
+                    .then_with(|| trimmed_a.cmp(trimmed_b))
+                    .then_with(|| digits_a.len().cmp(&digits_b.len()));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                (a, b) = (rest_a, rest_b);
+            }
+            (Some(x), Some(y)) => {
+                let ord = x.cmp(&y);
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                (a, b) = (&a[x.len_utf8()..], &b[y.len_utf8()..]);
+            }
+        }
+    }
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// Compares storage locations in bin order; empty locations sort last.
+pub fn compare_locations(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.trim(), b.trim());
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => parse_location_code(a)
+            .cmp(&parse_location_code(b))
+            .then_with(|| natural_cmp(a, b)),
+    }
+}
+
+/// The export order of two listings: location, name, collector number, then
+/// the remaining identifying fields.
+pub fn compare_cards(a: &Card, b: &Card) -> Ordering {
+    compare_locations(
+        a.location.as_deref().unwrap_or(""),
+        b.location.as_deref().unwrap_or(""),
+    )
+    .then_with(|| a.name.cmp(&b.name))
+    .then_with(|| natural_cmp(&a.cn, &b.cn))
+    .then_with(|| a.set_code.cmp(&b.set_code))
+    .then_with(|| a.language.cmp(&b.language))
+    .then_with(|| a.condition.cmp(&b.condition))
+    .then_with(|| a.is_foil_card().cmp(&b.is_foil_card()))
+    .then_with(|| natural_cmp(&a.cardmarket_id, &b.cardmarket_id))
+}
+
+/// A stable ID for a listing: 16 hex digits of an FNV-1a hash over the
+/// fields that tell listings apart. Quantity and price are left out, so a
+/// repriced or partly sold listing keeps its ID.
+pub fn item_id(card: &Card) -> String {
+    let fields = [
+        card.cardmarket_id.as_str(),
+        &card.set_code,
+        &card.cn,
+        &card.language,
+        &card.condition,
+        if card.is_foil_card() { "foil" } else { "" },
+        if card.is_signed_card() { "signed" } else { "" },
+        if card.is_playset_card() {
+            "playset"
+        } else {
+            ""
+        },
+        card.is_first_ed.as_deref().unwrap_or(""),
+        card.is_reverse_holo.as_deref().unwrap_or(""),
+        card.location.as_deref().unwrap_or("").trim(),
+        card.comment.trim(),
+    ];
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for field in fields {
+        // The separator keeps ("ab", "c") and ("a", "bc") apart
+        for byte in field.bytes().chain([0x1f]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+#[path = "export_order_tests.rs"]
+mod tests;
@@ -0,0 +1,79 @@
+//! Tests for export_order.
+
+use super::*;
+
+fn card(name: &str, cn: &str, location: Option<&str>) -> Card {
+    Card {
+        name: name.to_string(),
+        cn: cn.to_string(),
+        location: location.map(str::to_string),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn natural_cmp_compares_digit_runs_as_numbers() {
+    assert_eq!(natural_cmp("9", "10"), Ordering::Less);
+    assert_eq!(natural_cmp("10", "10a"), Ordering::Less);
+    assert_eq!(natural_cmp("A-2-1", "A-10-1"), Ordering::Less);
+    assert_eq!(natural_cmp("007", "7"), Ordering::Greater);
+    assert_eq!(natural_cmp("Ä1", "Ä1"), Ordering::Equal);
+}
+
+#[test]
+fn sorts_by_location_then_name_then_collector_number() {
+    let mut cards = [
+        card("Bolt", "10", Some("A-0-1-2")),
+        card("Bolt", "9", Some("A-0-1-2")),
+        card("Anger", "3", Some("A-0-1-2")),
+        card("Zap", "1", None),
+        card("Zap", "1", Some("A-0-1-1")),
+        card("Zap", "1", Some("A1_S1_R10_C1")),
+        card("Zap", "1", Some("A1_S1_R2_C1")),
+    ];
+
+    cards.sort_by(compare_cards);
+
+    let order: Vec<(&str, &str, &str)> = cards
+        .iter()
+        .map(|c| {
+            (
+                c.location.as_deref().unwrap_or(""),
+                c.name.as_str(),
+                c.cn.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        order,
+        [
+            ("A1_S1_R2_C1", "Zap", "1"),
+            ("A1_S1_R10_C1", "Zap", "1"),
+            ("A-0-1-1", "Zap", "1"),
+            ("A-0-1-2", "Anger", "3"),
+            ("A-0-1-2", "Bolt", "9"),
+            ("A-0-1-2", "Bolt", "10"),
+            ("", "Zap", "1"),
+        ]
+    );
+}
+
+#[test]
+fn item_id_ignores_price_and_quantity() {
+    let listing = card("Bolt", "161", Some("A-0-1-1"));
+    let repriced = Card {
+        price: "9.99".to_string(),
+        quantity: "3".to_string(),
+        ..listing.clone()
+    };
+    let foil = Card {
+        is_foil: "true".to_string(),
+        ..listing.clone()
+    };
+
+    assert_eq!(item_id(&listing).len(), 16);
+    assert_eq!(item_id(&listing), item_id(&repriced));
+    assert_ne!(item_id(&listing), item_id(&foil));
+    // Pinned: IDs must not change between releases
+    assert_eq!(item_id(&listing), "6eeee8777c80e8ec");
+}
@@ -1,5 +1,7 @@
-use crate::card_matching::{get_card_name, parse_location_code, MatchedCard};
+use crate::card_matching::{get_card_name, MatchedCard};
+use crate::export_order::compare_cards;
 use crate::models::Language;
+use crate::translation::NameTranslation;
 
 pub fn format_regular_output(
     matches: &[(String, i32, Vec<MatchedCard>)],
@@ -113,7 +115,19 @@ pub fn format_regular_output(
     output
 }
 
+/// Text picking list in export order (see [`crate::export_order`]).
 pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
+    format_picking_list_in(matched_cards, None)
+}
+
+/// Picking list with the card names from `translation`; without one each
+/// card is named in its listing's language.
+pub fn format_picking_list_in(
+    matched_cards: &[MatchedCard],
+    translation: Option<&NameTranslation>,
+) -> String {
+    let mut sorted: Vec<&MatchedCard> = matched_cards.iter().collect();
+    sorted.sort_by(|a, b| compare_cards(a.card, b.card));
     let mut output_entries = Vec::new();
     let mut max_qty_len = 3; // Minimum width for "Qty"
     let mut max_loc_len = 0;
@@ -128,7 +142,11 @@ pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
         let card = matched_card.card;
         max_qty_len = max_qty_len.max(matched_card.quantity.to_string().len());
         max_loc_len = max_loc_len.max(card.location.as_deref().unwrap_or("").len());
-        max_name_len = max_name_len.max(card.name.len());
+        let name = match translation {
+            Some(translation) => translation.name_of(card),
+            None => &card.name,
+        };
+        max_name_len = max_name_len.max(name.len());
         max_lang_len = max_lang_len.max(card.language.len());
         max_rarity_len = max_rarity_len.max(card.rarity.len());
         max_cn_len = max_cn_len.max(card.cn.len());
@@ -136,17 +154,19 @@ pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
     }
 
     // Create entries for each card
-    for matched_card in matched_cards {
+    for matched_card in sorted {
         let card = matched_card.card;
-        let sort_key = card.location.as_deref().unwrap_or("").to_string();
-
-        // Get localized name based on language
-        let mut name = match Language::parse(&card.language) {
-            Some(Language::German) => card.name_de.clone(),
-            Some(Language::Spanish) => card.name_es.clone(),
-            Some(Language::French) => card.name_fr.clone(),
-            Some(Language::Italian) => card.name_it.clone(),
-            _ => card.name.clone(),
+
+        // Translated name, or the localized name of the listing's language
+        let mut name = match translation {
+            Some(translation) => translation.name_of(card).to_string(),
+            None => match Language::parse(&card.language) {
+                Some(Language::German) => card.name_de.clone(),
+                Some(Language::Spanish) => card.name_es.clone(),
+                Some(Language::French) => card.name_fr.clone(),
+                Some(Language::Italian) => card.name_it.clone(),
+                _ => card.name.clone(),
+            },
         };
 
         // If localized name is empty, fall back to English name
@@ -187,24 +207,9 @@ pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
             width_cn = max_cn_len,
             width_set = max_set_len,
         );
-        output_entries.push((sort_key, entry));
+        output_entries.push(entry);
     }
 
-    // Sort by location
-    output_entries.sort_by(|(loc_a, _), (loc_b, _)| {
-        if loc_a.is_empty() && loc_b.is_empty() {
-            std::cmp::Ordering::Equal
-        } else if loc_a.is_empty() {
-            std::cmp::Ordering::Greater
-        } else if loc_b.is_empty() {
-            std::cmp::Ordering::Less
-        } else {
-            let parts_a = parse_location_code(loc_a);
-            let parts_b = parse_location_code(loc_b);
-            parts_a.cmp(&parts_b)
-        }
-    });
-
     // Create header
     let header = format!(
         "{:>width_qty$} | {:<width_loc$} | {:<width_name$} | {:<width_lang$} | {:<width_rarity$} | {:<width_cn$} | {:<width_set$}\n",
@@ -247,7 +252,7 @@ pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
     let mut output = String::new();
     output.push_str(&header);
     output.push_str(&separator);
-    for (_, entry) in output_entries {
+    for entry in output_entries {
         output.push_str(&entry);
     }
 
@@ -265,6 +270,15 @@ pub fn format_picking_list(matched_cards: &[MatchedCard]) -> String {
 }
 
 pub fn format_invoice_list(matched_cards: &[MatchedCard]) -> String {
+    format_invoice_list_in(matched_cards, None)
+}
+
+/// Invoice list with the card names from `translation`; without one each
+/// card is named in its listing's language.
+pub fn format_invoice_list_in(
+    matched_cards: &[MatchedCard],
+    translation: Option<&NameTranslation>,
+) -> String {
     let mut total_price = 0.0;
     let mut output_entries = Vec::new();
     let mut max_name_len = 0;
@@ -274,7 +288,11 @@ pub fn format_invoice_list(matched_cards: &[MatchedCard]) -> String {
     // Calculate maximum lengths for alignment
     for matched_card in matched_cards {
         let card = matched_card.card;
-        max_name_len = max_name_len.max(card.name.len());
+        let name = match translation {
+            Some(translation) => translation.name_of(card),
+            None => &card.name,
+        };
+        max_name_len = max_name_len.max(name.len());
         max_lang_len = max_lang_len.max(card.language.len());
         max_cond_len = max_cond_len.max(card.condition.len());
     }
@@ -287,9 +305,11 @@ pub fn format_invoice_list(matched_cards: &[MatchedCard]) -> String {
         total_price += line_total;
         // Discount will be applied in the wrapper function
 
-        // Get localized name based on language
-        let lang = Language::parse(&card.language);
-        let mut name = get_card_name(card, lang).to_string();
+        // Translated name, or the localized name of the listing's language
+        let mut name = match translation {
+            Some(translation) => translation.name_of(card).to_string(),
+            None => get_card_name(card, Language::parse(&card.language)).to_string(),
+        };
         let special_conditions = card.special_conditions();
         if !special_conditions.is_empty() {
             name = format!("{} ({})", name, special_conditions.join(", "));
@@ -354,9 +374,14 @@ pub fn format_invoice_list(matched_cards: &[MatchedCard]) -> String {
     output
 }
 
+/// Stock-update CSV removing the matched copies, rows in export order (see
+/// [`crate::export_order`]).
 pub fn format_update_stock_csv(matched_cards: &[MatchedCard]) -> String {
     use csv::WriterBuilder;
 
+    let mut sorted: Vec<&MatchedCard> = matched_cards.iter().collect();
+    sorted.sort_by(|a, b| compare_cards(a.card, b.card));
+
     let mut wtr = WriterBuilder::new().has_headers(true).from_writer(vec![]);
 
     // Header matches the inventory-report CSV schema used by Cardmarket.
@@ -379,7 +404,7 @@ pub fn format_update_stock_csv(matched_cards: &[MatchedCard]) -> String {
         "rarity",
     ]);
 
-    for matched_card in matched_cards {
+    for matched_card in sorted {
         let card = matched_card.card;
         let quantity_str = (-matched_card.quantity).to_string();
         let _ = wtr.write_record([
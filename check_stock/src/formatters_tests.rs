@@ -174,6 +174,41 @@ fn test_format_picking_list_sorted_by_location() {
     assert!(a_pos < b_pos);
 }
 
+#[test]
+fn test_exports_use_stable_order_within_a_location() {
+    let mut cards = Vec::new();
+    for (name, cn) in [("Shock", "10"), ("Bolt", "200"), ("Shock", "9")] {
+        let mut card = create_test_card(name, "1.00", 1);
+        card.cn = cn.to_string();
+        card.location = Some("A-0-1-1".to_string());
+        cards.push(card);
+    }
+    let matched: Vec<MatchedCard> = cards
+        .iter()
+        .map(|c| create_matched_card(c, 1, "Set"))
+        .collect();
+    let mut reversed = matched.clone();
+    reversed.reverse();
+
+    // Input order doesn't matter: name first, then collector number numerically
+    for input in [&matched, &reversed] {
+        let csv = format_update_stock_csv(input);
+        let rows: Vec<(&str, &str)> = csv
+            .lines()
+            .skip(1)
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').collect();
+                (fields[2], fields[5])
+            })
+            .collect();
+        assert_eq!(rows, [("Bolt", "200"), ("Shock", "9"), ("Shock", "10")]);
+    }
+    assert_eq!(
+        format_picking_list(&matched),
+        format_picking_list(&reversed)
+    );
+}
+
 #[test]
 fn test_format_picking_list_german_card_uses_german_name() {
     let mut card = create_test_card("Lightning Bolt", "10.00", 4);
@@ -219,6 +254,37 @@ fn test_format_invoice_list_total() {
     assert!(output.contains("50.00"));
 }
 
+#[test]
+fn test_format_invoice_list_translated_names() {
+    let mut card = create_test_card("Lightning Bolt", "1.00", 4);
+    card.language = "German".to_string();
+    card.name_de = "Blitzschlag".to_string();
+    let matched = [create_matched_card(&card, 1, "Magic 2010 (M10)")];
+
+    // A German listing is invoiced under its German name by default
+    assert!(format_invoice_list(&matched).contains("Blitzschlag"));
+
+    let english = NameTranslation::build([&card], Language::English, |_, _| None);
+    let output = format_invoice_list_in(&matched, Some(&english));
+    assert!(output.contains("1 x Lightning Bolt | German"));
+    assert!(!output.contains("Blitzschlag"));
+}
+
+#[test]
+fn test_format_picking_list_translated_names() {
+    let mut card = create_test_card("Lightning Bolt", "1.00", 4);
+    card.language = "German".to_string();
+    card.name_de = "Blitzschlag".to_string();
+    let matched = [create_matched_card(&card, 1, "Magic 2010 (M10)")];
+
+    assert!(format_picking_list(&matched).contains("Blitzschlag"));
+
+    let english = NameTranslation::build([&card], Language::English, |_, _| None);
+    let output = format_picking_list_in(&matched, Some(&english));
+    assert!(output.contains("Lightning Bolt"));
+    assert!(!output.contains("Blitzschlag"));
+}
+
 // ==================== format_price_diff_csv Tests ====================
 
 #[test]
@@ -293,3 +359,126 @@ fn test_price_diff_csv_skips_indices_not_in_overrides() {
     let lines: Vec<&str> = output.lines().collect();
     assert_eq!(lines.len(), 1); // header only
 }
+
+// ==================== Snapshots ====================
+//
+// Whole outputs for a representative stock, so any formatting change shows
+// up as a snapshot diff. Review changes with `cargo insta review` (or rerun
+// with `INSTA_UPDATE=always` and inspect the `.snap` diff).
+
+/// Foil, signed, first edition and playset copies; localized names (with a
+/// missing translation), unicode names, comments, and cards with a blank or
+/// no location.
+fn snapshot_stock() -> Vec<Card> {
+    vec![
+        Card {
+            name: "Lightning Bolt".to_string(),
+            quantity: "3".to_string(),
+            is_foil: "true".to_string(),
+            price: "2.50".to_string(),
+            location: Some("A-0-1-2".to_string()),
+            set: "Magic 2010".to_string(),
+            cn: "146".to_string(),
+            ..Card::test_default()
+        },
+        Card {
+            name: "Lightning Bolt".to_string(),
+            name_de: "Blitzschlag".to_string(),
+            language: "German".to_string(),
+            quantity: "1".to_string(),
+            is_signed: "true".to_string(),
+            condition: "EX".to_string(),
+            price: "4,00".to_string(),
+            location: Some("A-0-1-10".to_string()),
+            set: "Alpha".to_string(),
+            cn: "161".to_string(),
+            rarity: "uncommon".to_string(),
+            ..Card::test_default()
+        },
+        Card {
+            name: "Jötun Grunt".to_string(),
+            language: "Spanish".to_string(),
+            quantity: "2".to_string(),
+            price: "0.35".to_string(),
+            comment: "slight edge wear".to_string(),
+            location: None,
+            set: "Coldsnap".to_string(),
+            cn: "8".to_string(),
+            ..Card::test_default()
+        },
+        Card {
+            name: "Æther Vial".to_string(),
+            quantity: "4".to_string(),
+            is_playset: Some("true".to_string()),
+            price: "1.20".to_string(),
+            location: Some("  ".to_string()),
+            set: "Darksteel".to_string(),
+            cn: "91".to_string(),
+            rarity: "uncommon".to_string(),
+            ..Card::test_default()
+        },
+        Card {
+            name: "Fire // Ice".to_string(),
+            name_fr: "Feu // Glace".to_string(),
+            language: "French".to_string(),
+            quantity: "1".to_string(),
+            is_first_ed: Some("true".to_string()),
+            price: "0.80".to_string(),
+            location: Some("B-1-2-3".to_string()),
+            set: "Apocalypse".to_string(),
+            cn: "128".to_string(),
+            ..Card::test_default()
+        },
+    ]
+}
+
+fn snapshot_matches(stock: &[Card]) -> Vec<(String, i32, Vec<MatchedCard<'_>>)> {
+    vec![
+        (
+            "Lightning Bolt".to_string(),
+            4,
+            vec![
+                create_matched_card(&stock[0], 3, "Magic 2010 (M10)"),
+                create_matched_card(&stock[1], 1, "Alpha (LEA)"),
+            ],
+        ),
+        (
+            "Jötun Grunt".to_string(),
+            4,
+            vec![create_matched_card(&stock[2], 2, "Coldsnap (CSP)")],
+        ),
+        (
+            "Æther Vial".to_string(),
+            1,
+            vec![create_matched_card(&stock[3], 1, "Darksteel (DST)")],
+        ),
+        ("Black Lotus".to_string(), 1, vec![]),
+        (
+            "Fire // Ice".to_string(),
+            1,
+            vec![create_matched_card(&stock[4], 1, "Apocalypse (APC)")],
+        ),
+    ]
+}
+
+#[test]
+fn snapshot_regular_output() {
+    let stock = snapshot_stock();
+    insta::assert_snapshot!(format_regular_output(&snapshot_matches(&stock), 0.0));
+}
+
+#[test]
+fn snapshot_regular_output_with_discount() {
+    let stock = snapshot_stock();
+    insta::assert_snapshot!(format_regular_output(&snapshot_matches(&stock), 15.0));
+}
+
+#[test]
+fn snapshot_picking_list() {
+    let stock = snapshot_stock();
+    let picked: Vec<MatchedCard> = snapshot_matches(&stock)
+        .into_iter()
+        .flat_map(|(_, _, cards)| cards)
+        .collect();
+    insta::assert_snapshot!(format_picking_list(&picked));
+}
@@ -0,0 +1,91 @@
+//! Wantslist fulfillment history
+//!
+//! Every stock check records how well the current stock covered the
+//! wantslist: copies wanted and found, how many cards were missing and what
+//! the found copies would be quoted at. Over time this shows whether the
+//! stock is getting better or worse at serving a recurring wantslist.
+
+/// Summary of one stock check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FulfillmentRecord {
+    /// Local timestamp, `YYYY-MM-DD HH:MM:SS`
+    pub checked_at: String,
+    /// Wantslist file name (or deck link) the check ran against
+    pub wantslist: String,
+    /// Copies wanted over all entries
+    pub wanted: i64,
+    /// Copies found in stock
+    pub found: i64,
+    /// Entries not fully covered by the stock
+    pub missing_cards: i64,
+    /// Price of the found copies after the discount, in EUR
+    pub quoted_value: f64,
+}
+
+impl FulfillmentRecord {
+    /// Share of wanted copies found, 0.0–1.0 (1.0 for an empty wantslist).
+    pub fn fulfillment_rate(&self) -> f64 {
+        if self.wanted <= 0 {
+            1.0
+        } else {
+            (self.found as f64 / self.wanted as f64).min(1.0)
+        }
+    }
+}
+
+/// How fulfillment of one wantslist developed over its recorded checks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FulfillmentTrend {
+    pub wantslist: String,
+    pub checks: usize,
+    pub average_rate: f64,
+    pub latest_rate: f64,
+    /// Latest minus first rate; `None` with a single check
+    pub rate_change: Option<f64>,
+    pub average_quoted_value: f64,
+    pub average_missing_cards: f64,
+    /// `checked_at` of the latest check
+    pub last_checked: String,
+}
+
+/// One trend per wantslist, most recently checked first. Records may come in
+/// any order; within a wantslist they are ordered by `checked_at`.
+pub fn fulfillment_trends(records: &[FulfillmentRecord]) -> Vec<FulfillmentTrend> {
+    let mut by_wantslist: Vec<(&str, Vec<&FulfillmentRecord>)> = Vec::new();
+    for record in records {
+        match by_wantslist
+            .iter_mut()
+            .find(|(name, _)| *name == record.wantslist)
+        {
+            Some((_, runs)) => runs.push(record),
+            None => by_wantslist.push((&record.wantslist, vec![record])),
+        }
+    }
+
+    let mut trends: Vec<FulfillmentTrend> = by_wantslist
+        .into_iter()
+        .map(|(wantslist, mut runs)| {
+            runs.sort_by(|a, b| a.checked_at.cmp(&b.checked_at));
+            let n = runs.len() as f64;
+            let first = runs[0];
+            let latest = runs[runs.len() - 1];
+            FulfillmentTrend {
+                wantslist: wantslist.to_string(),
+                checks: runs.len(),
+                average_rate: runs.iter().map(|r| r.fulfillment_rate()).sum::<f64>() / n,
+                latest_rate: latest.fulfillment_rate(),
+                rate_change: (runs.len() > 1)
+                    .then(|| latest.fulfillment_rate() - first.fulfillment_rate()),
+                average_quoted_value: runs.iter().map(|r| r.quoted_value).sum::<f64>() / n,
+                average_missing_cards: runs.iter().map(|r| r.missing_cards as f64).sum::<f64>() / n,
+                last_checked: latest.checked_at.clone(),
+            }
+        })
+        .collect();
+    trends.sort_by(|a, b| b.last_checked.cmp(&a.last_checked));
+    trends
+}
+
+#[cfg(test)]
+#[path = "fulfillment_tests.rs"]
+mod tests;
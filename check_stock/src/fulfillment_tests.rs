@@ -0,0 +1,47 @@
+//! Tests for wantslist fulfillment history.
+
+use super::*;
+
+fn record(checked_at: &str, wantslist: &str, wanted: i64, found: i64) -> FulfillmentRecord {
+    FulfillmentRecord {
+        checked_at: checked_at.to_string(),
+        wantslist: wantslist.to_string(),
+        wanted,
+        found,
+        missing_cards: (wanted - found).max(0),
+        quoted_value: found as f64 * 2.0,
+    }
+}
+
+#[test]
+fn rate_is_share_of_wanted_copies() {
+    assert_eq!(record("2025-01-01", "a", 4, 3).fulfillment_rate(), 0.75);
+    assert_eq!(record("2025-01-01", "a", 0, 0).fulfillment_rate(), 1.0);
+}
+
+#[test]
+fn trends_group_by_wantslist_in_time_order() {
+    let records = vec![
+        record("2025-01-03 10:00:00", "cube.txt", 10, 8),
+        record("2025-01-01 10:00:00", "cube.txt", 10, 5),
+        record("2025-01-02 10:00:00", "deck.txt", 4, 4),
+        record("2025-01-02 12:00:00", "cube.txt", 10, 5),
+    ];
+
+    let trends = fulfillment_trends(&records);
+
+    assert_eq!(trends.len(), 2);
+    let cube = &trends[0];
+    assert_eq!(cube.wantslist, "cube.txt");
+    assert_eq!(cube.checks, 3);
+    assert_eq!(cube.latest_rate, 0.8);
+    assert!((cube.rate_change.unwrap() - 0.3).abs() < 1e-9);
+    assert!((cube.average_rate - 0.6).abs() < 1e-9);
+    assert!((cube.average_quoted_value - 12.0).abs() < 1e-9);
+    assert!((cube.average_missing_cards - 4.0).abs() < 1e-9);
+    assert_eq!(cube.last_checked, "2025-01-03 10:00:00");
+
+    let deck = &trends[1];
+    assert_eq!(deck.checks, 1);
+    assert_eq!(deck.rate_change, None);
+}
@@ -0,0 +1,202 @@
+//! Image pre-seeding — every card image on disk before going offline.
+//!
+//! The picking screen shows each card's image, fetched from Scryfall the
+//! first time and kept in the [`ImageCache`]. In a warehouse without signal
+//! that first fetch never happens. `--preseed-images` fills the cache ahead
+//! of time: it downloads Scryfall's `default_cards` bulk file once (one entry
+//! per printing, with its image links on Scryfall's CDN) instead of asking
+//! the API card by card, picks the in-stock printings whose image is not
+//! cached yet, and downloads those images from the CDN at a gentle rate.
+
+use crate::api::rate_limit::RateLimiter;
+use crate::api::scryfall::{
+    download_bulk_data, fetch_bulk_data, fetch_image, for_each_bulk_card, DEFAULT_CARDS,
+};
+use crate::cache::ImageCache;
+use crate::error::ApiResult;
+use crate::inventory_db::InStockCard;
+use log::{info, warn};
+use std::collections::{BTreeSet, HashMap};
+
+/// Image downloads per second unless `--images-per-second` says otherwise.
+/// Scryfall asks for 50–100 ms between requests.
+pub const DEFAULT_IMAGES_PER_SECOND: f64 = 10.0;
+
+/// A printing, identified the way the image cache names its files.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Printing {
+    /// Lowercase, as in Scryfall's bulk data
+    pub set_code: String,
+    pub collector_number: String,
+}
+
+impl Printing {
+    pub fn new(set_code: &str, collector_number: &str) -> Self {
+        Self {
+            set_code: set_code.trim().to_lowercase(),
+            collector_number: collector_number.trim().to_string(),
+        }
+    }
+}
+
+/// What a pre-seeding run did.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreseedReport {
+    /// Distinct in-stock printings looked at
+    pub printings: usize,
+    /// Printings whose image was cached already
+    pub already_cached: usize,
+    pub downloaded: usize,
+    /// Printings the bulk file has no image for, e.g. set codes Scryfall
+    /// doesn't know
+    pub not_in_bulk: Vec<Printing>,
+    /// Downloads that failed; a later run retries them
+    pub failed: usize,
+}
+
+impl PreseedReport {
+    /// One line for the terminal, e.g. `812 printings: 640 already cached,
+    /// 170 downloaded, 2 without a Scryfall image, 0 failed`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} printings: {} already cached, {} downloaded, {} without a Scryfall image, {} failed",
+            self.printings,
+            self.already_cached,
+            self.downloaded,
+            self.not_in_bulk.len(),
+            self.failed
+        )
+    }
+}
+
+/// The distinct printings of `cards` (only set `set`, by code, when given)
+/// whose image is not in `cache`, and how many printings were cached
+/// already. Cards without a set code or collector number are skipped, as on
+/// the picking screen.
+pub fn printings_to_seed(
+    cards: &[InStockCard],
+    set: Option<&str>,
+    cache: &ImageCache,
+) -> (Vec<Printing>, usize) {
+    let set = set.map(|code| code.trim().to_lowercase());
+    let printings: BTreeSet<Printing> = cards
+        .iter()
+        .filter(|card| !card.set_code.trim().is_empty() && !card.cn.trim().is_empty())
+        .map(|card| Printing::new(&card.set_code, &card.cn))
+        .filter(|printing| set.as_ref().is_none_or(|code| printing.set_code == *code))
+        .collect();
+    let total = printings.len();
+    let missing: Vec<Printing> = printings
+        .into_iter()
+        .filter(|p| !cache.contains(&p.set_code, &p.collector_number))
+        .collect();
+    let cached = total - missing.len();
+    (missing, cached)
+}
+
+/// The image URLs of the `wanted` printings found in a bulk data file.
+pub fn image_urls(
+    bulk: impl std::io::Read,
+    wanted: &[Printing],
+) -> ApiResult<HashMap<Printing, String>> {
+    let wanted: BTreeSet<&Printing> = wanted.iter().collect();
+    let mut urls = HashMap::new();
+    for_each_bulk_card(bulk, |card| {
+        let printing = Printing::new(&card.set, &card.collector_number);
+        if wanted.contains(&printing) {
+            if let Some(url) = card.image_url() {
+                urls.insert(printing, url.to_string());
+            }
+        }
+    })?;
+    Ok(urls)
+}
+
+/// Downloads each image with `fetch`, at most `per_second` per second, and
+/// stores it in `cache`. Failures are logged and counted, not fatal.
+/// `progress` is called with (done, total) after every image. Returns
+/// (downloaded, failed).
+pub fn download_images(
+    images: &[(Printing, String)],
+    cache: &ImageCache,
+    per_second: f64,
+    mut fetch: impl FnMut(&str) -> ApiResult<Vec<u8>>,
+    mut progress: impl FnMut(usize, usize),
+) -> (usize, usize) {
+    let limiter = RateLimiter::new(per_second, 1);
+    let (mut downloaded, mut failed) = (0, 0);
+    for (done, (printing, url)) in images.iter().enumerate() {
+        limiter.acquire();
+        match fetch(url) {
+            Ok(bytes) => {
+                cache.insert(&printing.set_code, &printing.collector_number, &bytes);
+                downloaded += 1;
+            }
+            Err(e) => {
+                warn!(
+                    "Image for {}/{} failed: {e}",
+                    printing.set_code, printing.collector_number
+                );
+                failed += 1;
+            }
+        }
+        progress(done + 1, images.len());
+    }
+    (downloaded, failed)
+}
+
+/// Pre-seeds `cache` with the images of the in-stock `cards` (only set `set`
+/// when given): bulk lookup, then CDN downloads at `per_second`. The bulk
+/// file is only downloaded when some image is missing, and removed again
+/// afterwards.
+pub fn preseed_images(
+    cards: &[InStockCard],
+    set: Option<&str>,
+    cache: &ImageCache,
+    per_second: f64,
+    progress: impl FnMut(usize, usize),
+) -> ApiResult<PreseedReport> {
+    let (missing, already_cached) = printings_to_seed(cards, set, cache);
+    let mut report = PreseedReport {
+        printings: missing.len() + already_cached,
+        already_cached,
+        ..PreseedReport::default()
+    };
+    if missing.is_empty() {
+        return Ok(report);
+    }
+
+    let bulk = fetch_bulk_data(DEFAULT_CARDS)?;
+    info!(
+        "Scryfall {} from {} ({} MB)",
+        bulk.kind,
+        bulk.updated_at,
+        bulk.size / 1_000_000
+    );
+    std::fs::create_dir_all(cache.cache_dir())?;
+    let bulk_path = cache.cache_dir().join(format!("{DEFAULT_CARDS}.json.part"));
+    let urls = download_bulk_data(&bulk.download_uri, &bulk_path).and_then(|_| {
+        let file = std::fs::File::open(&bulk_path)?;
+        image_urls(file, &missing)
+    });
+    if let Err(e) = std::fs::remove_file(&bulk_path) {
+        warn!("Could not remove {}: {e}", bulk_path.display());
+    }
+    let mut urls = urls?;
+
+    let mut images = Vec::new();
+    for printing in missing {
+        match urls.remove(&printing) {
+            Some(url) => images.push((printing, url)),
+            None => report.not_in_bulk.push(printing),
+        }
+    }
+    let (downloaded, failed) = download_images(&images, cache, per_second, fetch_image, progress);
+    report.downloaded = downloaded;
+    report.failed = failed;
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "image_preseed_tests.rs"]
+mod tests;
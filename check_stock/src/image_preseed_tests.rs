@@ -0,0 +1,128 @@
+//! Tests for image_preseed.
+
+use super::*;
+use crate::error::ApiError;
+use tempfile::TempDir;
+
+fn card(set_code: &str, cn: &str) -> InStockCard {
+    InStockCard {
+        cardmarket_id: "1".to_string(),
+        name: "Test".to_string(),
+        set_code: set_code.to_string(),
+        cn: cn.to_string(),
+        condition: "NM".to_string(),
+        language: "English".to_string(),
+        is_foil: false,
+        rarity: "common".to_string(),
+        quantity: 1,
+        price: 1.0,
+        location: String::new(),
+        effective_date: "2026-01-01".to_string(),
+    }
+}
+
+fn test_cache() -> (ImageCache, TempDir) {
+    let dir = TempDir::new().unwrap();
+    (ImageCache::with_dir(dir.path().to_path_buf()), dir)
+}
+
+#[test]
+fn printings_are_deduplicated_and_cached_ones_skipped() {
+    let (cache, _dir) = test_cache();
+    cache.insert("lea", "161", b"jpg");
+    let cards = vec![
+        card("LEA", "161"),
+        card("LEA", "232"),
+        card("lea", "232"),
+        card("ISD", "51"),
+        card("", "1"),
+        card("M21", " "),
+    ];
+
+    let (missing, cached) = printings_to_seed(&cards, None, &cache);
+    assert_eq!(cached, 1);
+    assert_eq!(
+        missing,
+        vec![Printing::new("isd", "51"), Printing::new("lea", "232")]
+    );
+
+    let (missing, cached) = printings_to_seed(&cards, Some("Isd"), &cache);
+    assert_eq!(cached, 0);
+    assert_eq!(missing, vec![Printing::new("isd", "51")]);
+}
+
+#[test]
+fn image_urls_are_taken_from_the_bulk_file_for_wanted_printings() {
+    let bulk = r#"[
+        {"set": "lea", "collector_number": "232", "image_uris": {"normal": "https://cdn/lea-232.jpg"}},
+        {"set": "lea", "collector_number": "233", "image_uris": {"normal": "https://cdn/lea-233.jpg"}},
+        {"set": "isd", "collector_number": "51", "card_faces": [
+            {"name": "Delver of Secrets", "image_uris": {"normal": "https://cdn/isd-51.jpg"}}
+        ]}
+    ]"#;
+    let wanted = vec![
+        Printing::new("LEA", "232"),
+        Printing::new("isd", "51"),
+        Printing::new("xyz", "1"),
+    ];
+
+    let urls = image_urls(bulk.as_bytes(), &wanted).unwrap();
+    assert_eq!(urls.len(), 2);
+    assert_eq!(urls[&wanted[0]], "https://cdn/lea-232.jpg");
+    assert_eq!(urls[&wanted[1]], "https://cdn/isd-51.jpg");
+}
+
+#[test]
+fn downloads_are_cached_and_failures_counted() {
+    let (cache, _dir) = test_cache();
+    let images = vec![
+        (
+            Printing::new("lea", "232"),
+            "https://cdn/ok.jpg".to_string(),
+        ),
+        (
+            Printing::new("isd", "51"),
+            "https://cdn/gone.jpg".to_string(),
+        ),
+    ];
+    let mut steps = Vec::new();
+
+    let (downloaded, failed) = download_images(
+        &images,
+        &cache,
+        1000.0,
+        |url| {
+            if url.ends_with("ok.jpg") {
+                Ok(b"jpg".to_vec())
+            } else {
+                Err(ApiError::HttpStatus(reqwest::StatusCode::NOT_FOUND))
+            }
+        },
+        |done, total| steps.push((done, total)),
+    );
+
+    assert_eq!((downloaded, failed), (1, 1));
+    assert_eq!(cache.get("lea", "232"), Some(b"jpg".to_vec()));
+    assert!(!cache.contains("isd", "51"));
+    assert_eq!(steps, vec![(1, 2), (2, 2)]);
+}
+
+#[test]
+fn nothing_is_downloaded_when_every_image_is_cached() {
+    let (cache, _dir) = test_cache();
+    cache.insert("lea", "161", b"jpg");
+
+    let report = preseed_images(&[card("LEA", "161")], None, &cache, 10.0, |_, _| {}).unwrap();
+    assert_eq!(
+        report,
+        PreseedReport {
+            printings: 1,
+            already_cached: 1,
+            ..PreseedReport::default()
+        }
+    );
+    assert_eq!(
+        report.summary(),
+        "1 printings: 1 already cached, 0 downloaded, 0 without a Scryfall image, 0 failed"
+    );
+}
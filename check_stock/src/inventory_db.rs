@@ -7,8 +7,20 @@
 //! - Articles already at quantity 0 and not in the CSV are left untouched.
 //! - Multiple CSV rows for the same card variant (same condition/language/foil/signed)
 //!   in different physical locations are merged: quantities are summed, one DB row kept.
-
-use crate::models::{canonical_condition, Card, Language};
+//!
+//! Rows belong to an *inventory* (`inventory_id`), e.g. the Cardmarket shop
+//! stock and a personal collection. A loaded CSV syncs into the active
+//! inventory (stored in `app_meta`, [`DEFAULT_INVENTORY`] until changed), and
+//! every per-variant write — discards, picks, playset conversions, repricing
+//! — applies to it. Stats and in-stock reads take an inventory, or `None` to
+//! cover all of them.
+
+use crate::bulk_repricing::PriceChange;
+use crate::exclusions::{ExcludedVariant, Exclusion};
+use crate::fulfillment::FulfillmentRecord;
+use crate::models::{canonical_condition, canonical_language, Card};
+use crate::playset::PlaysetConversion;
+use mtg_common::{parse_decimal, DecimalLocale};
 use rusqlite::{params, Connection, OptionalExtension};
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -31,9 +43,23 @@ fn normalize_flag(s: &str) -> String {
 /// Normalise a language value to canonical capitalised form (`"English"`, …).
 /// Unknown values pass through unchanged so we never silently discard data.
 fn normalize_language(s: &str) -> String {
-    Language::parse(s)
-        .map(|l| l.as_str().to_string())
-        .unwrap_or_else(|| s.to_string())
+    canonical_language(s)
+}
+
+/// Re-keys a `(value, copies)` breakdown through `canonical` and merges the
+/// buckets that collapse, largest first. Rows synced before a value was known
+/// to the normalization table are stored with their raw spelling.
+fn merge_breakdown(rows: Vec<(String, i64)>, canonical: fn(&str) -> String) -> Vec<(String, i64)> {
+    let mut merged: Vec<(String, i64)> = Vec::new();
+    for (value, total) in rows {
+        let value = canonical(&value);
+        match merged.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, sum)) => *sum += total,
+            None => merged.push((value, total)),
+        }
+    }
+    merged.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    merged
 }
 
 /// Returns true if `card.location` is set and not just whitespace.
@@ -282,6 +308,8 @@ pub struct SyncPreview {
     /// Total in-stock copies before / after the sync.
     pub copies_before: i64,
     pub copies_after: i64,
+    /// In-stock variants before the sync.
+    pub variants_before: usize,
 }
 
 /// Guard threshold: the safety check only engages on inventories of at least
@@ -292,12 +320,26 @@ pub const MIN_COPIES_FOR_GUARD: i64 = 100;
 /// treated as a suspect CSV rather than real sales.
 pub const SUSPICIOUS_DROP_FRACTION: f64 = 0.5;
 
+/// A sync zeroing more than this share of the in-stock variants is treated as
+/// a broken export (e.g. a missing set or language) even if few copies go.
+pub const SUSPICIOUS_ZEROED_FRACTION: f64 = 0.2;
+
 impl SyncPreview {
+    /// Share of the in-stock variants the sync would zero out.
+    pub fn zeroed_fraction(&self) -> f64 {
+        if self.variants_before == 0 {
+            0.0
+        } else {
+            self.zeroed_variants as f64 / self.variants_before as f64
+        }
+    }
+
     /// True when the sync would wipe out a large share of a non-trivial
     /// inventory at once — more likely a truncated or wrong CSV than sales.
     pub fn is_suspicious(&self) -> bool {
         self.copies_before >= MIN_COPIES_FOR_GUARD
-            && self.copies_sold as f64 > self.copies_before as f64 * SUSPICIOUS_DROP_FRACTION
+            && (self.copies_sold as f64 > self.copies_before as f64 * SUSPICIOUS_DROP_FRACTION
+                || self.zeroed_fraction() > SUSPICIOUS_ZEROED_FRACTION)
     }
 }
 
@@ -309,7 +351,7 @@ pub enum SyncOutcome {
     Blocked(SyncPreview),
 }
 
-/// Statistics from a discard (write-off) operation.
+/// Statistics from a discard (write-off) or pick decrement.
 #[derive(Debug, Default, PartialEq)]
 pub struct DiscardStats {
     /// Number of distinct DB variant rows whose quantity was reduced.
@@ -319,12 +361,29 @@ pub struct DiscardStats {
     pub copies_discarded: i64,
 }
 
-/// Returns the path to the inventory database file.
+/// The inventory rows belong to unless another one is selected: the shop
+/// stock. Databases from before inventories existed are migrated into it.
+/// Must match the `DEFAULT` of the `inventory_id` columns below.
+pub const DEFAULT_INVENTORY: &str = "shop";
+
+/// One inventory and its current size, for the inventory selector and the
+/// per-inventory overview.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventorySummary {
+    pub id: String,
+    /// Variants with quantity > 0
+    pub in_stock_variants: i64,
+    pub in_stock_copies: i64,
+    /// Sum of (price × quantity) for the in-stock variants, in EUR
+    pub in_stock_value: f64,
+    /// Most recent sync into this inventory; `None` while it is empty
+    pub last_sync: Option<String>,
+}
+
+/// Returns the path to the inventory database file (`[check_stock] database`
+/// in the shared config, by default in the d2d_automations data dir).
 fn db_path() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("d2d_automations")
-        .join("inventory.db")
+    mtg_common::config::config().check_stock.database.clone()
 }
 
 /// Opens (or creates) the inventory database and initialises the schema.
@@ -334,7 +393,8 @@ fn open_db() -> DbResult<Connection> {
         std::fs::create_dir_all(parent).ok();
     }
     log::info!("Inventory DB: {}", path.display());
-    let conn = Connection::open(&path)?;
+    let mut conn = Connection::open(&path)?;
+    crate::slow_queries::instrument(&mut conn, &path);
     init_schema(&conn)?;
     Ok(conn)
 }
@@ -368,24 +428,28 @@ const INVENTORY_CARDS_DDL: &str = "
         listed_at       TEXT NOT NULL,
         first_synced_at TEXT NOT NULL,
         last_synced_at  TEXT NOT NULL,
-        sold_quantity   INTEGER NOT NULL DEFAULT 0
+        sold_quantity   INTEGER NOT NULL DEFAULT 0,
+        inventory_id    TEXT NOT NULL DEFAULT 'shop'
     );
-    CREATE UNIQUE INDEX idx_inventory_article_key
-        ON inventory_cards (cardmarket_id, condition, language, is_foil, is_signed);
+    CREATE UNIQUE INDEX idx_inventory_article_key_inv
+        ON inventory_cards (inventory_id, cardmarket_id, condition, language, is_foil, is_signed);
 ";
 
-// Daily point-in-time snapshot of the whole inventory, written once per sync day.
+// Daily point-in-time snapshot of each inventory, written once per sync day.
 // Enables period-over-period sales velocity: because `sold_copies` and
 // `sold_revenue` are stored *cumulatively*, the difference between any two
 // snapshot dates is exactly what sold (and was earned) in that window.
-// `date` is the primary key so re-syncing on the same day overwrites its row.
+// (`date`, `inventory_id`) is the primary key so re-syncing an inventory on the
+// same day overwrites its row.
 const INVENTORY_SNAPSHOTS_DDL: &str = "
     CREATE TABLE IF NOT EXISTS inventory_snapshots (
-        date                    TEXT PRIMARY KEY,
+        date                    TEXT NOT NULL,
+        inventory_id            TEXT NOT NULL DEFAULT 'shop',
         in_stock_copies         INTEGER NOT NULL,
         in_stock_value          REAL NOT NULL,
         sold_copies_cumulative  INTEGER NOT NULL,
-        sold_revenue_cumulative REAL NOT NULL
+        sold_revenue_cumulative REAL NOT NULL,
+        PRIMARY KEY (date, inventory_id)
     );
 ";
 
@@ -413,13 +477,32 @@ const SOLD_EVENTS_DDL: &str = "
         is_foil       TEXT NOT NULL,
         is_signed     TEXT NOT NULL,
         copies        INTEGER NOT NULL,
-        price         REAL NOT NULL
+        price         REAL NOT NULL,
+        inventory_id  TEXT NOT NULL DEFAULT 'shop'
     );
     CREATE INDEX IF NOT EXISTS idx_sold_events_variant
         ON sold_events (cardmarket_id, condition, language, is_foil, is_signed);
     CREATE INDEX IF NOT EXISTS idx_sold_events_date ON sold_events (date);
 ";
 
+// Audit trail of manual stock removals that bypass a sync, one row per variant
+// decremented (e.g. copies picked for an off-Cardmarket order). `actor` is who
+// did it (the picker's name, may be empty); `at` is a local timestamp.
+const STOCK_DECREMENTS_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS stock_decrements (
+        at            TEXT NOT NULL,
+        cardmarket_id TEXT NOT NULL,
+        condition     TEXT NOT NULL,
+        language      TEXT NOT NULL,
+        is_foil       TEXT NOT NULL,
+        is_signed     TEXT NOT NULL,
+        name          TEXT NOT NULL,
+        copies        INTEGER NOT NULL,
+        reason        TEXT NOT NULL,
+        actor         TEXT NOT NULL
+    );
+";
+
 // Manually recorded acquisition cost per lot. One row per lot ID (e.g. `L12`),
 // storing the total price paid for that purchase. Orthogonal to the card schema;
 // created on every open like the snapshot and sold-event tables. `updated_at`
@@ -432,6 +515,71 @@ const LOT_COSTS_DDL: &str = "
     );
 ";
 
+// One row per stock check: how much of a wantslist the stock covered at the
+// time. Append-only; `wantslist` is the file name so recurring lists can be
+// followed over time.
+const FULFILLMENT_HISTORY_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS fulfillment_history (
+        checked_at    TEXT NOT NULL,
+        wantslist     TEXT NOT NULL,
+        wanted        INTEGER NOT NULL,
+        found         INTEGER NOT NULL,
+        missing_cards INTEGER NOT NULL,
+        quoted_value  REAL NOT NULL
+    );
+";
+
+// Optional photo per card variant, taken to settle condition disputes. Keyed
+// like `inventory_cards` but kept in its own table so syncs never touch it;
+// `path` points at the copy in the photo folder (see crate::listing_photos).
+const LISTING_PHOTOS_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS listing_photos (
+        cardmarket_id TEXT NOT NULL,
+        condition     TEXT NOT NULL,
+        language      TEXT NOT NULL,
+        is_foil       TEXT NOT NULL,
+        is_signed     TEXT NOT NULL,
+        path          TEXT NOT NULL,
+        added_at      TEXT NOT NULL,
+        PRIMARY KEY (cardmarket_id, condition, language, is_foil, is_signed)
+    );
+";
+
+// Audit trail of bulk repricings, one row per variant whose listed price was
+// changed outside a sync. `rule` names the pricing rule that set the new price;
+// `changed_at` is a local timestamp shared by all rows of one repricing.
+const PRICE_CHANGES_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS price_changes (
+        changed_at    TEXT NOT NULL,
+        cardmarket_id TEXT NOT NULL,
+        condition     TEXT NOT NULL,
+        language      TEXT NOT NULL,
+        is_foil       TEXT NOT NULL,
+        is_signed     TEXT NOT NULL,
+        name          TEXT NOT NULL,
+        old_price     REAL NOT NULL,
+        new_price     REAL NOT NULL,
+        rule          TEXT NOT NULL
+    );
+";
+
+// Do-not-sell list (see crate::exclusions): cards the wantslist matcher skips.
+// Keyed like `listing_photos`; a whole-card exclusion leaves the variant
+// columns empty, which no canonical condition ever is.
+const SALE_EXCLUSIONS_DDL: &str = "
+    CREATE TABLE IF NOT EXISTS sale_exclusions (
+        cardmarket_id TEXT NOT NULL,
+        condition     TEXT NOT NULL,
+        language      TEXT NOT NULL,
+        is_foil       TEXT NOT NULL,
+        is_signed     TEXT NOT NULL,
+        name          TEXT NOT NULL,
+        note          TEXT NOT NULL,
+        added_at      TEXT NOT NULL,
+        PRIMARY KEY (cardmarket_id, condition, language, is_foil, is_signed)
+    );
+";
+
 // Migration v1 → v2: replace single cardmarket_id PRIMARY KEY with composite UNIQUE key.
 const MIGRATION_V1_TO_V2: &str = "
     BEGIN;
@@ -535,6 +683,54 @@ const MIGRATION_V3_TO_V2: &str = "
     COMMIT;
 ";
 
+// Migration to inventories: every existing row joins the default inventory and
+// the variant key gains `inventory_id`, so one variant can be stocked in several
+// inventories.
+const MIGRATION_ADD_INVENTORY_ID: &str = "
+    BEGIN;
+    ALTER TABLE inventory_cards ADD COLUMN inventory_id TEXT NOT NULL DEFAULT 'shop';
+    DROP INDEX IF EXISTS idx_inventory_article_key;
+    CREATE UNIQUE INDEX idx_inventory_article_key_inv
+        ON inventory_cards (inventory_id, cardmarket_id, condition, language, is_foil, is_signed);
+    COMMIT;
+";
+
+// Snapshots from before inventories were one row per day for the whole DB;
+// they become the default inventory's history.
+const MIGRATION_SNAPSHOTS_PER_INVENTORY: &str = "
+    BEGIN;
+    ALTER TABLE inventory_snapshots RENAME TO inventory_snapshots_old;
+    CREATE TABLE inventory_snapshots (
+        date                    TEXT NOT NULL,
+        inventory_id            TEXT NOT NULL DEFAULT 'shop',
+        in_stock_copies         INTEGER NOT NULL,
+        in_stock_value          REAL NOT NULL,
+        sold_copies_cumulative  INTEGER NOT NULL,
+        sold_revenue_cumulative REAL NOT NULL,
+        PRIMARY KEY (date, inventory_id)
+    );
+    INSERT INTO inventory_snapshots
+        (date, in_stock_copies, in_stock_value,
+         sold_copies_cumulative, sold_revenue_cumulative)
+        SELECT date, in_stock_copies, in_stock_value,
+               sold_copies_cumulative, sold_revenue_cumulative
+        FROM inventory_snapshots_old;
+    DROP TABLE inventory_snapshots_old;
+    COMMIT;
+";
+
+/// Whether `table` has a column named `column`.
+fn has_column(conn: &Connection, table: &str, column: &str) -> DbResult<bool> {
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
 /// Creates or migrates the `inventory_cards` table to the current schema (v2).
 ///
 /// Migration chain:
@@ -543,6 +739,9 @@ const MIGRATION_V3_TO_V2: &str = "
 /// - v2 (composite 5-field key, `idx_inventory_article_key`) → no-op.
 /// - v3 (composite 6-field key with location, `idx_inventory_article_key_v3`) → v2
 ///   (collapses per-location rows, sums quantities).
+///
+/// v2 tables without `inventory_id` then move into [`DEFAULT_INVENTORY`], and
+/// so do the sold events and snapshots recorded before it.
 fn init_schema(conn: &Connection) -> DbResult<()> {
     let table_exists: bool = conn
         .query_row(
@@ -558,6 +757,11 @@ fn init_schema(conn: &Connection) -> DbResult<()> {
         conn.execute_batch(INVENTORY_SNAPSHOTS_DDL)?;
         conn.execute_batch(SOLD_EVENTS_DDL)?;
         conn.execute_batch(LOT_COSTS_DDL)?;
+        conn.execute_batch(STOCK_DECREMENTS_DDL)?;
+        conn.execute_batch(FULFILLMENT_HISTORY_DDL)?;
+        conn.execute_batch(LISTING_PHOTOS_DDL)?;
+        conn.execute_batch(PRICE_CHANGES_DDL)?;
+        conn.execute_batch(SALE_EXCLUSIONS_DDL)?;
         return conn.execute_batch(APP_META_DDL);
     }
 
@@ -575,14 +779,16 @@ fn init_schema(conn: &Connection) -> DbResult<()> {
 
     if v3_index_exists {
         log::info!("Migrating inventory_db: collapsing location rows into variants (v3 → v2)");
-        return conn.execute_batch(MIGRATION_V3_TO_V2);
+        conn.execute_batch(MIGRATION_V3_TO_V2)?;
     }
 
-    // v2 is identified by idx_inventory_article_key (5-field key, no location).
+    // v2 is identified by idx_inventory_article_key (5-field key, no location),
+    // or by idx_inventory_article_key_inv once inventories were added.
     let v2_index_exists: bool = conn
         .query_row(
             "SELECT 1 FROM sqlite_master \
-             WHERE type='index' AND name='idx_inventory_article_key'",
+             WHERE type='index'
+               AND name IN ('idx_inventory_article_key', 'idx_inventory_article_key_inv')",
             [],
             |_| Ok(()),
         )
@@ -615,11 +821,33 @@ fn init_schema(conn: &Connection) -> DbResult<()> {
     // Snapshot and sold-event tables are orthogonal to the card-schema migrations;
     // ensure they exist on every open regardless of which card-schema version we
     // came from.
+    if !has_column(conn, "inventory_cards", "inventory_id")? {
+        log::info!(
+            "Migrating inventory_db: moving all cards into the '{DEFAULT_INVENTORY}' inventory"
+        );
+        conn.execute_batch(MIGRATION_ADD_INVENTORY_ID)?;
+    }
+
     conn.execute_batch(INVENTORY_SNAPSHOTS_DDL)?;
     conn.execute_batch(SOLD_EVENTS_DDL)?;
     conn.execute_batch(LOT_COSTS_DDL)?;
+    conn.execute_batch(STOCK_DECREMENTS_DDL)?;
+    conn.execute_batch(FULFILLMENT_HISTORY_DDL)?;
+    conn.execute_batch(LISTING_PHOTOS_DDL)?;
+    conn.execute_batch(PRICE_CHANGES_DDL)?;
+    conn.execute_batch(SALE_EXCLUSIONS_DDL)?;
     conn.execute_batch(APP_META_DDL)?;
 
+    // Tables created before inventories existed
+    if !has_column(conn, "sold_events", "inventory_id")? {
+        conn.execute_batch(
+            "ALTER TABLE sold_events ADD COLUMN inventory_id TEXT NOT NULL DEFAULT 'shop';",
+        )?;
+    }
+    if !has_column(conn, "inventory_snapshots", "inventory_id")? {
+        conn.execute_batch(MIGRATION_SNAPSHOTS_PER_INVENTORY)?;
+    }
+
     Ok(())
 }
 
@@ -637,9 +865,10 @@ fn article_key(
     format!("{id}\x1F{condition}\x1F{language}\x1F{is_foil}\x1F{is_signed}")
 }
 
-/// Returns today's date as `YYYY-MM-DD` using local system time.
+/// Returns today's date as `YYYY-MM-DD`, as a Cardmarket (Europe/Berlin) day
+/// so sync and sale dates line up with the price dates.
 fn today_date() -> String {
-    chrono::Local::now().format("%Y-%m-%d").to_string()
+    mtg_common::dates::today_date()
 }
 
 /// Syncs a slice of cards (from a freshly loaded inventory CSV) to the local DB,
@@ -709,12 +938,96 @@ pub fn discard_cards(discards: &[(Card, i64)]) -> DbResult<DiscardStats> {
 
 /// Inner discard that accepts an explicit connection — used in tests.
 fn discard_cards_conn(conn: &mut Connection, discards: &[(Card, i64)]) -> DbResult<DiscardStats> {
+    let stats = remove_copies_conn(conn, discards, None)?;
+    if stats.variants_updated > 0 {
+        log::info!(
+            "Inventory DB discard: {} copies written off across {} variants (revenue unaffected)",
+            stats.copies_discarded,
+            stats.variants_updated
+        );
+    }
+    Ok(stats)
+}
+
+/// Removes the copies of an archived picking session from stock.
+///
+/// Same semantics as [`discard_cards`] — quantities drop (clamped at zero) and
+/// `sold_quantity` is untouched, since the matching stock-update CSV removes the
+/// copies on Cardmarket too — but every variant decremented also gets a
+/// `stock_decrements` audit row naming the picker, so picked stock can be traced.
+pub fn decrement_picked_cards(picks: &[(Card, i64)], picker: &str) -> DbResult<DiscardStats> {
+    let mut conn = open_db()?;
+    let at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    decrement_picked_cards_conn(&mut conn, picks, picker, &at)
+}
+
+/// Inner pick decrement that accepts an explicit connection and timestamp — used in tests.
+fn decrement_picked_cards_conn(
+    conn: &mut Connection,
+    picks: &[(Card, i64)],
+    picker: &str,
+    at: &str,
+) -> DbResult<DiscardStats> {
+    let stats = remove_copies_conn(conn, picks, Some(("pick", picker.trim(), at)))?;
+    if stats.variants_updated > 0 {
+        log::info!(
+            "Inventory DB pick decrement: {} copies removed across {} variants",
+            stats.copies_discarded,
+            stats.variants_updated
+        );
+    }
+    Ok(stats)
+}
+
+/// Current DB quantity of each card's variant (`None` if the variant isn't in
+/// the DB), in input order. Used to reconcile short picks against inventory.
+pub fn variant_quantities(cards: &[&Card]) -> DbResult<Vec<Option<i64>>> {
+    let conn = open_db()?;
+    variant_quantities_conn(&conn, cards)
+}
+
+fn variant_quantities_conn(conn: &Connection, cards: &[&Card]) -> DbResult<Vec<Option<i64>>> {
+    let inventory = active_inventory_conn(conn)?;
+    let mut stmt = conn.prepare_cached(
+        "SELECT quantity FROM inventory_cards
+         WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
+           AND is_foil = ?4 AND is_signed = ?5 AND inventory_id = ?6",
+    )?;
+    cards
+        .iter()
+        .map(|card| {
+            stmt.query_row(
+                params![
+                    card.cardmarket_id,
+                    canonical_condition(&card.condition),
+                    normalize_language(&card.language),
+                    normalize_flag(&card.is_foil),
+                    normalize_flag(&card.is_signed),
+                    inventory,
+                ],
+                |r| r.get(0),
+            )
+            .optional()
+        })
+        .collect()
+}
+
+/// Shared core of discards and pick decrements: reduces each variant's quantity
+/// without touching `sold_quantity`. With `audit` (`reason`, `actor`, `at`), a
+/// `stock_decrements` row is written for every variant actually reduced.
+fn remove_copies_conn(
+    conn: &mut Connection,
+    removals: &[(Card, i64)],
+    audit: Option<(&str, &str, &str)>,
+) -> DbResult<DiscardStats> {
     // Aggregate requested copies by the same canonical variant key the sync uses,
     // so two rows for the same variant (different physical locations) collapse into
     // a single UPDATE and clamp against the one merged DB row.
-    let mut agg: std::collections::HashMap<(String, String, String, String, String), i64> =
+    // (cardmarket_id, condition, language, is_foil, is_signed) → (copies, name)
+    type VariantKey = (String, String, String, String, String);
+    let mut agg: std::collections::HashMap<VariantKey, (i64, &str)> =
         std::collections::HashMap::new();
-    for (card, qty) in discards {
+    for (card, qty) in removals {
         if *qty <= 0 {
             continue;
         }
@@ -725,28 +1038,29 @@ fn discard_cards_conn(conn: &mut Connection, discards: &[(Card, i64)]) -> DbResu
             normalize_flag(&card.is_foil),
             normalize_flag(&card.is_signed),
         );
-        *agg.entry(key).or_insert(0) += *qty;
+        agg.entry(key).or_insert((0, card.name.as_str())).0 += *qty;
     }
 
+    let inventory = active_inventory_conn(conn)?;
     let tx = conn.transaction()?;
     let mut stats = DiscardStats::default();
 
-    for ((id, cond, lang, foil, signed), requested) in &agg {
+    for ((id, cond, lang, foil, signed), (requested, name)) in &agg {
         // Read the current quantity so we can clamp and report the true amount
         // removed. sold_quantity is deliberately never referenced here.
         let current: Option<i64> = tx
             .query_row(
                 "SELECT quantity FROM inventory_cards
                  WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
-                   AND is_foil = ?4 AND is_signed = ?5",
-                params![id, cond, lang, foil, signed],
+                   AND is_foil = ?4 AND is_signed = ?5 AND inventory_id = ?6",
+                params![id, cond, lang, foil, signed, inventory],
                 |r| r.get(0),
             )
             .optional()?;
 
         let Some(current) = current else {
             log::warn!(
-                "Discard skipped: no DB row for variant {id}/{cond}/{lang} (foil={foil}, signed={signed})"
+                "Stock removal skipped: no DB row for variant {id}/{cond}/{lang} (foil={foil}, signed={signed})"
             );
             continue;
         };
@@ -759,28 +1073,120 @@ fn discard_cards_conn(conn: &mut Connection, discards: &[(Card, i64)]) -> DbResu
         tx.execute(
             "UPDATE inventory_cards SET quantity = quantity - ?1
              WHERE cardmarket_id = ?2 AND condition = ?3 AND language = ?4
-               AND is_foil = ?5 AND is_signed = ?6",
-            params![removed, id, cond, lang, foil, signed],
+               AND is_foil = ?5 AND is_signed = ?6 AND inventory_id = ?7",
+            params![removed, id, cond, lang, foil, signed, inventory],
         )?;
+        if let Some((reason, actor, at)) = audit {
+            tx.execute(
+                "INSERT INTO stock_decrements
+                     (at, cardmarket_id, condition, language, is_foil, is_signed,
+                      name, copies, reason, actor)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![at, id, cond, lang, foil, signed, name, removed, reason, actor],
+            )?;
+        }
         stats.variants_updated += 1;
         stats.copies_discarded += removed;
     }
 
     tx.commit()?;
-    if stats.variants_updated > 0 {
+    Ok(stats)
+}
+
+/// Applies playset split/merge conversions to the inventory DB.
+///
+/// A conversion changes how many *listings* a variant has (one playset becomes
+/// four singles, or four singles become one playset) without any copy leaving
+/// the shelf. The DB stores listing counts, so each variant's `quantity` is
+/// shifted by the conversion's net listing delta — never below zero — and
+/// `sold_quantity` is left untouched, so the next sync against the converted
+/// Cardmarket stock records no phantom sale. When a listing is converted in
+/// full, the row also takes the new playset flag and price.
+///
+/// Returns the number of variant rows updated. Conversions for variants not in
+/// the DB are skipped with a warning.
+pub fn apply_playset_conversions(conversions: &[PlaysetConversion]) -> DbResult<usize> {
+    let mut conn = open_db()?;
+    apply_playset_conversions_conn(&mut conn, conversions)
+}
+
+/// Inner conversion that accepts an explicit connection — used in tests.
+fn apply_playset_conversions_conn(
+    conn: &mut Connection,
+    conversions: &[PlaysetConversion],
+) -> DbResult<usize> {
+    let inventory = active_inventory_conn(conn)?;
+    let tx = conn.transaction()?;
+    let mut updated = 0;
+
+    for conversion in conversions {
+        let card = &conversion.source;
+        let (id, cond, lang, foil, signed) = (
+            card.cardmarket_id.clone(),
+            canonical_condition(&card.condition),
+            normalize_language(&card.language),
+            normalize_flag(&card.is_foil),
+            normalize_flag(&card.is_signed),
+        );
+
+        let current: Option<i64> = tx
+            .query_row(
+                "SELECT quantity FROM inventory_cards
+                 WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
+                   AND is_foil = ?4 AND is_signed = ?5 AND inventory_id = ?6",
+                params![id, cond, lang, foil, signed, inventory],
+                |r| r.get(0),
+            )
+            .optional()?;
+
+        let Some(current) = current else {
+            log::warn!(
+                "Playset conversion skipped: no DB row for variant {id}/{cond}/{lang} (foil={foil}, signed={signed})"
+            );
+            continue;
+        };
+
+        let new_quantity = (current + conversion.listing_delta()).max(0);
+        // The whole row was converted: it now describes only the target listing.
+        let fully_converted = current == conversion.removed;
+
+        tx.execute(
+            "UPDATE inventory_cards SET
+                quantity   = ?1,
+                is_playset = CASE WHEN ?2 THEN ?3 ELSE is_playset END,
+                price      = CASE WHEN ?2 THEN ?4 ELSE price END
+             WHERE cardmarket_id = ?5 AND condition = ?6 AND language = ?7
+               AND is_foil = ?8 AND is_signed = ?9 AND inventory_id = ?10",
+            params![
+                new_quantity,
+                fully_converted,
+                conversion.target.is_playset,
+                conversion.target.price,
+                id,
+                cond,
+                lang,
+                foil,
+                signed,
+                inventory
+            ],
+        )?;
+        updated += 1;
+    }
+
+    tx.commit()?;
+    if updated > 0 {
         log::info!(
-            "Inventory DB discard: {} copies written off across {} variants (revenue unaffected)",
-            stats.copies_discarded,
-            stats.variants_updated
+            "Inventory DB playset conversion: {updated} variants updated (revenue unaffected)"
         );
     }
-    Ok(stats)
+    Ok(updated)
 }
 
-/// Queries aggregate statistics from the local inventory database.
-pub fn get_db_stats() -> DbResult<DbStats> {
+/// Queries aggregate statistics from the local inventory database, for one
+/// inventory or (`None`) all of them together.
+pub fn get_db_stats(inventory: Option<&str>) -> DbResult<DbStats> {
     let conn = open_db()?;
-    get_db_stats_conn(&conn, &today_date())
+    get_db_stats_conn(&conn, &today_date(), inventory)
 }
 
 /// Records (or corrects) the total acquisition cost for a lot, in EUR.
@@ -814,14 +1220,267 @@ fn delete_lot_cost_conn(conn: &Connection, lot: &str) -> DbResult<()> {
     Ok(())
 }
 
+/// Appends the summary of a stock check to the fulfillment history.
+pub fn record_fulfillment(record: &FulfillmentRecord) -> DbResult<()> {
+    let conn = open_db()?;
+    record_fulfillment_conn(&conn, record)
+}
+
+/// Inner insert that accepts an explicit connection — used in tests.
+fn record_fulfillment_conn(conn: &Connection, record: &FulfillmentRecord) -> DbResult<()> {
+    conn.execute(
+        "INSERT INTO fulfillment_history
+             (checked_at, wantslist, wanted, found, missing_cards, quoted_value)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            record.checked_at,
+            record.wantslist,
+            record.wanted,
+            record.found,
+            record.missing_cards,
+            record.quoted_value,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Returns every recorded stock check, oldest first.
+pub fn get_fulfillment_history() -> DbResult<Vec<FulfillmentRecord>> {
+    let conn = open_db()?;
+    get_fulfillment_history_conn(&conn)
+}
+
+/// Inner query that accepts an explicit connection — used in tests.
+fn get_fulfillment_history_conn(conn: &Connection) -> DbResult<Vec<FulfillmentRecord>> {
+    conn.prepare(
+        "SELECT checked_at, wantslist, wanted, found, missing_cards, quoted_value
+         FROM fulfillment_history
+         ORDER BY checked_at, rowid",
+    )?
+    .query_map([], |r| {
+        Ok(FulfillmentRecord {
+            checked_at: r.get(0)?,
+            wantslist: r.get(1)?,
+            wanted: r.get(2)?,
+            found: r.get(3)?,
+            missing_cards: r.get(4)?,
+            quoted_value: r.get(5)?,
+        })
+    })?
+    .collect()
+}
+
+/// Directory of the inventory database; listing photos are stored below it.
+pub fn db_dir() -> PathBuf {
+    db_path()
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Records `path` as the photo of the card's variant, replacing any earlier one.
+pub fn set_listing_photo(card: &Card, path: &std::path::Path) -> DbResult<()> {
+    let conn = open_db()?;
+    let at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    set_listing_photo_conn(&conn, card, &path.to_string_lossy(), &at)
+}
+
+/// Inner upsert that accepts an explicit connection and timestamp — used in tests.
+fn set_listing_photo_conn(conn: &Connection, card: &Card, path: &str, at: &str) -> DbResult<()> {
+    conn.execute(
+        "INSERT INTO listing_photos
+             (cardmarket_id, condition, language, is_foil, is_signed, path, added_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(cardmarket_id, condition, language, is_foil, is_signed)
+         DO UPDATE SET path = excluded.path, added_at = excluded.added_at",
+        params![
+            card.cardmarket_id,
+            canonical_condition(&card.condition),
+            normalize_language(&card.language),
+            normalize_flag(&card.is_foil),
+            normalize_flag(&card.is_signed),
+            path,
+            at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Forgets the photo of the card's variant. The image file itself is kept.
+pub fn remove_listing_photo(card: &Card) -> DbResult<()> {
+    let conn = open_db()?;
+    remove_listing_photo_conn(&conn, card)
+}
+
+/// Inner delete that accepts an explicit connection — used in tests.
+fn remove_listing_photo_conn(conn: &Connection, card: &Card) -> DbResult<()> {
+    conn.execute(
+        "DELETE FROM listing_photos
+         WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
+           AND is_foil = ?4 AND is_signed = ?5",
+        params![
+            card.cardmarket_id,
+            canonical_condition(&card.condition),
+            normalize_language(&card.language),
+            normalize_flag(&card.is_foil),
+            normalize_flag(&card.is_signed),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Photo of each card's variant (`None` without one), in input order.
+pub fn listing_photos(cards: &[&Card]) -> DbResult<Vec<Option<PathBuf>>> {
+    let conn = open_db()?;
+    listing_photos_conn(&conn, cards)
+}
+
+fn listing_photos_conn(conn: &Connection, cards: &[&Card]) -> DbResult<Vec<Option<PathBuf>>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT path FROM listing_photos
+         WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
+           AND is_foil = ?4 AND is_signed = ?5",
+    )?;
+    cards
+        .iter()
+        .map(|card| {
+            stmt.query_row(
+                params![
+                    card.cardmarket_id,
+                    canonical_condition(&card.condition),
+                    normalize_language(&card.language),
+                    normalize_flag(&card.is_foil),
+                    normalize_flag(&card.is_signed),
+                ],
+                |r| r.get::<_, String>(0).map(PathBuf::from),
+            )
+            .optional()
+        })
+        .collect()
+}
+
+/// Key columns of an exclusion: the variant, or empty strings for the whole card.
+fn exclusion_key(exclusion: &Exclusion) -> [String; 4] {
+    match &exclusion.variant {
+        None => Default::default(),
+        Some(v) => [
+            canonical_condition(&v.condition),
+            normalize_language(&v.language),
+            if v.foil { "1" } else { "" }.to_string(),
+            if v.signed { "1" } else { "" }.to_string(),
+        ],
+    }
+}
+
+/// Adds a card or variant to the do-not-sell list; adding it again updates
+/// the note.
+pub fn add_exclusion(exclusion: &Exclusion) -> DbResult<()> {
+    let conn = open_db()?;
+    let at = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    add_exclusion_conn(&conn, exclusion, &at)
+}
+
+/// Inner upsert that accepts an explicit connection and timestamp — used in tests.
+fn add_exclusion_conn(conn: &Connection, exclusion: &Exclusion, at: &str) -> DbResult<()> {
+    let [condition, language, is_foil, is_signed] = exclusion_key(exclusion);
+    conn.execute(
+        "INSERT INTO sale_exclusions
+             (cardmarket_id, condition, language, is_foil, is_signed, name, note, added_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+         ON CONFLICT(cardmarket_id, condition, language, is_foil, is_signed)
+         DO UPDATE SET name = excluded.name, note = excluded.note",
+        params![
+            exclusion.cardmarket_id.trim(),
+            condition,
+            language,
+            is_foil,
+            is_signed,
+            exclusion.name,
+            exclusion.note.trim(),
+            at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Takes a card or variant off the do-not-sell list.
+pub fn remove_exclusion(exclusion: &Exclusion) -> DbResult<()> {
+    let conn = open_db()?;
+    remove_exclusion_conn(&conn, exclusion)
+}
+
+/// Inner delete that accepts an explicit connection — used in tests.
+fn remove_exclusion_conn(conn: &Connection, exclusion: &Exclusion) -> DbResult<()> {
+    let [condition, language, is_foil, is_signed] = exclusion_key(exclusion);
+    conn.execute(
+        "DELETE FROM sale_exclusions
+         WHERE cardmarket_id = ?1 AND condition = ?2 AND language = ?3
+           AND is_foil = ?4 AND is_signed = ?5",
+        params![
+            exclusion.cardmarket_id.trim(),
+            condition,
+            language,
+            is_foil,
+            is_signed
+        ],
+    )?;
+    Ok(())
+}
+
+/// The do-not-sell list, by card name.
+pub fn get_exclusions() -> DbResult<Vec<Exclusion>> {
+    let conn = open_db()?;
+    get_exclusions_conn(&conn)
+}
+
+/// Inner query that accepts an explicit connection — used in tests.
+fn get_exclusions_conn(conn: &Connection) -> DbResult<Vec<Exclusion>> {
+    conn.prepare(
+        "SELECT cardmarket_id, condition, language, is_foil, is_signed, name, note
+         FROM sale_exclusions
+         ORDER BY name, cardmarket_id, condition, language, is_foil, is_signed",
+    )?
+    .query_map([], |r| {
+        let condition: String = r.get(1)?;
+        let variant = if condition.is_empty() {
+            None
+        } else {
+            Some(ExcludedVariant {
+                condition,
+                language: r.get(2)?,
+                foil: r.get::<_, String>(3)? == "1",
+                signed: r.get::<_, String>(4)? == "1",
+            })
+        };
+        Ok(Exclusion {
+            cardmarket_id: r.get(0)?,
+            name: r.get(5)?,
+            variant,
+            note: r.get(6)?,
+        })
+    })?
+    .collect()
+}
+
 /// Returns every in-stock card variant (quantity > 0) from the database.
-pub fn get_in_stock_cards() -> DbResult<Vec<InStockCard>> {
+pub fn get_in_stock_cards(inventory: Option<&str>) -> DbResult<Vec<InStockCard>> {
     let conn = open_db()?;
-    get_in_stock_cards_conn(&conn)
+    get_in_stock_cards_conn(&conn, inventory)
+}
+
+/// [`get_in_stock_cards`] of the active inventory — the stock the pricing
+/// reports look at.
+pub fn get_active_in_stock_cards() -> DbResult<Vec<InStockCard>> {
+    let conn = open_db()?;
+    let inventory = active_inventory_conn(&conn)?;
+    get_in_stock_cards_conn(&conn, Some(&inventory))
 }
 
 /// Inner query that accepts an explicit connection — used in tests.
-fn get_in_stock_cards_conn(conn: &Connection) -> DbResult<Vec<InStockCard>> {
+fn get_in_stock_cards_conn(
+    conn: &Connection,
+    inventory: Option<&str>,
+) -> DbResult<Vec<InStockCard>> {
     conn.prepare(
         "SELECT cardmarket_id, name, set_code, cn, condition, language,
                 (is_foil = '1' OR LOWER(is_foil) = 'true') AS foil,
@@ -829,9 +1488,9 @@ fn get_in_stock_cards_conn(conn: &Connection) -> DbResult<Vec<InStockCard>> {
                 COALESCE(location, ''),
                 COALESCE(NULLIF(listed_at, ''), first_synced_at) AS effective_date
          FROM inventory_cards
-         WHERE quantity > 0",
+         WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)",
     )?
-    .query_map([], |r| {
+    .query_map([inventory], |r| {
         Ok(InStockCard {
             cardmarket_id: r.get(0)?,
             name: r.get(1)?,
@@ -850,7 +1509,124 @@ fn get_in_stock_cards_conn(conn: &Connection) -> DbResult<Vec<InStockCard>> {
     .collect()
 }
 
-/// Returns every recorded sold event, oldest first.
+/// Returns every in-stock variant (quantity > 0) of one inventory (`None`: all
+/// of them) as a full card row, in the shape the pricing graph evaluates, by
+/// name.
+pub fn get_in_stock_inventory(inventory: Option<&str>) -> DbResult<Vec<Card>> {
+    let conn = open_db()?;
+    get_in_stock_inventory_conn(&conn, inventory)
+}
+
+fn get_in_stock_inventory_conn(conn: &Connection, inventory: Option<&str>) -> DbResult<Vec<Card>> {
+    conn.prepare(
+        "SELECT cardmarket_id, quantity, name, set_name, set_code, cn, condition,
+                language, is_foil, is_playset, is_signed, price, comment, location,
+                name_de, name_es, name_fr, name_it, rarity, listed_at
+         FROM inventory_cards
+         WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+         ORDER BY name, set_code, cn",
+    )?
+    .query_map([inventory], |r| {
+        Ok(Card {
+            cardmarket_id: r.get(0)?,
+            quantity: r.get::<_, i64>(1)?.to_string(),
+            name: r.get(2)?,
+            set: r.get(3)?,
+            set_code: r.get(4)?,
+            cn: r.get(5)?,
+            condition: r.get(6)?,
+            language: r.get(7)?,
+            is_foil: r.get(8)?,
+            is_playset: r.get(9)?,
+            is_signed: r.get(10)?,
+            is_first_ed: None,
+            is_reverse_holo: None,
+            price: r.get(11)?,
+            comment: r.get(12)?,
+            location: r.get(13)?,
+            name_de: r.get(14)?,
+            name_es: r.get(15)?,
+            name_fr: r.get(16)?,
+            name_it: r.get(17)?,
+            rarity: r.get(18)?,
+            listed_at: r.get(19)?,
+        })
+    })?
+    .collect()
+}
+
+/// Writes the new prices of a bulk repricing to the inventory and records
+/// every change in `price_changes`, in one transaction. Changes for variants
+/// not in the DB are skipped with a warning. Returns the number of variants
+/// repriced.
+pub fn apply_price_changes(changes: &[PriceChange]) -> DbResult<usize> {
+    let mut conn = open_db()?;
+    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    apply_price_changes_conn(&mut conn, changes, &now)
+}
+
+fn apply_price_changes_conn(
+    conn: &mut Connection,
+    changes: &[PriceChange],
+    at: &str,
+) -> DbResult<usize> {
+    let inventory = active_inventory_conn(conn)?;
+    let tx = conn.transaction()?;
+    let mut applied = 0;
+    for change in changes {
+        let card = &change.card;
+        let (id, cond, lang, foil, signed) = (
+            &card.cardmarket_id,
+            canonical_condition(&card.condition),
+            normalize_language(&card.language),
+            normalize_flag(&card.is_foil),
+            normalize_flag(&card.is_signed),
+        );
+        let updated = tx.execute(
+            "UPDATE inventory_cards SET price = ?1
+             WHERE cardmarket_id = ?2 AND condition = ?3 AND language = ?4
+               AND is_foil = ?5 AND is_signed = ?6 AND inventory_id = ?7",
+            params![
+                format!("{:.2}", change.new_price),
+                id,
+                cond,
+                lang,
+                foil,
+                signed,
+                inventory
+            ],
+        )?;
+        if updated == 0 {
+            log::warn!(
+                "Repricing skipped: no DB row for variant {id}/{cond}/{lang} (foil={foil}, signed={signed})"
+            );
+            continue;
+        }
+        tx.execute(
+            "INSERT INTO price_changes
+                 (changed_at, cardmarket_id, condition, language, is_foil, is_signed,
+                  name, old_price, new_price, rule)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                at,
+                id,
+                cond,
+                lang,
+                foil,
+                signed,
+                card.name,
+                change.old_price,
+                change.new_price,
+                change.rule
+            ],
+        )?;
+        applied += 1;
+    }
+    tx.commit()?;
+    Ok(applied)
+}
+
+/// Returns every sold event recorded for the active inventory, oldest first.
 pub fn get_sold_events() -> DbResult<Vec<SoldEvent>> {
     let conn = open_db()?;
     get_sold_events_conn(&conn)
@@ -860,9 +1636,9 @@ pub fn get_sold_events() -> DbResult<Vec<SoldEvent>> {
 fn get_sold_events_conn(conn: &Connection) -> DbResult<Vec<SoldEvent>> {
     conn.prepare(
         "SELECT date, cardmarket_id, condition, language, is_foil, is_signed, copies, price
-         FROM sold_events ORDER BY date ASC, rowid ASC",
+         FROM sold_events WHERE inventory_id = ?1 ORDER BY date ASC, rowid ASC",
     )?
-    .query_map([], |r| {
+    .query_map([active_inventory_conn(conn)?], |r| {
         Ok(SoldEvent {
             date: r.get(0)?,
             cardmarket_id: r.get(1)?,
@@ -902,6 +1678,79 @@ fn set_meta(conn: &Connection, key: &str, value: &str) -> DbResult<()> {
     Ok(())
 }
 
+/// Normalises a user-entered inventory name to its ID: trimmed, lower-case,
+/// spaces as dashes. `None` when empty or when it contains anything but
+/// letters, digits, `-` and `_`.
+pub fn normalize_inventory_id(name: &str) -> Option<String> {
+    let id = name.trim().to_lowercase().replace(' ', "-");
+    (!id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_'))
+    .then_some(id)
+}
+
+/// The inventory loaded CSVs sync into and per-variant writes apply to.
+pub fn active_inventory() -> DbResult<String> {
+    let conn = open_db()?;
+    active_inventory_conn(&conn)
+}
+
+fn active_inventory_conn(conn: &Connection) -> DbResult<String> {
+    Ok(get_meta(conn, "active_inventory")?.unwrap_or_else(|| DEFAULT_INVENTORY.to_string()))
+}
+
+/// Selects the inventory loaded CSVs sync into; `id` comes from
+/// [`normalize_inventory_id`]. A new ID starts an empty inventory.
+pub fn set_active_inventory(id: &str) -> DbResult<()> {
+    let conn = open_db()?;
+    set_meta(&conn, "active_inventory", id)
+}
+
+/// Every inventory with rows in the DB, plus the active one even while it is
+/// still empty, by ID.
+pub fn list_inventories() -> DbResult<Vec<InventorySummary>> {
+    let conn = open_db()?;
+    list_inventories_conn(&conn)
+}
+
+fn list_inventories_conn(conn: &Connection) -> DbResult<Vec<InventorySummary>> {
+    let mut inventories: Vec<InventorySummary> = conn
+        .prepare(
+            "SELECT inventory_id,
+                    COALESCE(SUM(CASE WHEN quantity > 0 THEN 1 ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN quantity > 0 THEN quantity ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN quantity > 0
+                                      THEN CAST(price AS REAL) * quantity ELSE 0 END), 0.0),
+                    MAX(last_synced_at)
+             FROM inventory_cards
+             GROUP BY inventory_id
+             ORDER BY inventory_id",
+        )?
+        .query_map([], |r| {
+            Ok(InventorySummary {
+                id: r.get(0)?,
+                in_stock_variants: r.get(1)?,
+                in_stock_copies: r.get(2)?,
+                in_stock_value: r.get(3)?,
+                last_sync: r.get(4)?,
+            })
+        })?
+        .collect::<DbResult<_>>()?;
+    let active = active_inventory_conn(conn)?;
+    if !inventories.iter().any(|i| i.id == active) {
+        inventories.push(InventorySummary {
+            id: active,
+            in_stock_variants: 0,
+            in_stock_copies: 0,
+            in_stock_value: 0.0,
+            last_sync: None,
+        });
+        inventories.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+    Ok(inventories)
+}
+
 /// What changed since the previous visit — shown on the welcome screen.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct VisitDigest {
@@ -1027,14 +1876,21 @@ fn get_restock_candidates_conn(conn: &Connection) -> DbResult<Vec<RestockCandida
     .collect()
 }
 
-/// Reads all snapshot rows ordered by date ascending.
-fn read_snapshots_conn(conn: &Connection) -> DbResult<Vec<InventorySnapshot>> {
+/// Reads the snapshot rows of one inventory ordered by date ascending. For all
+/// inventories (`None`) each day sums the snapshots the inventories took that
+/// day.
+fn read_snapshots_conn(
+    conn: &Connection,
+    inventory: Option<&str>,
+) -> DbResult<Vec<InventorySnapshot>> {
     conn.prepare(
-        "SELECT date, in_stock_copies, in_stock_value,
-                sold_copies_cumulative, sold_revenue_cumulative
-         FROM inventory_snapshots ORDER BY date ASC",
+        "SELECT date, SUM(in_stock_copies), SUM(in_stock_value),
+                SUM(sold_copies_cumulative), SUM(sold_revenue_cumulative)
+         FROM inventory_snapshots
+         WHERE (?1 IS NULL OR inventory_id = ?1)
+         GROUP BY date ORDER BY date ASC",
     )?
-    .query_map([], |r| {
+    .query_map([inventory], |r| {
         Ok(InventorySnapshot {
             date: r.get(0)?,
             in_stock_copies: r.get(1)?,
@@ -1115,19 +1971,51 @@ fn lot_costs_map(conn: &Connection) -> DbResult<std::collections::HashMap<String
         .collect()
 }
 
-/// Builds the per-lot revenue breakdown from all inventory rows that carry a
-/// location with a recognisable lot number.
-fn lot_breakdown_from(conn: &Connection) -> DbResult<Vec<LotBreakdown>> {
+/// Estimated acquisition cost of one copy, per lot with a recorded cost: the
+/// lot cost spread evenly over every copy the lot has held (in stock + sold).
+/// Lots with no recorded cost or no copies are absent.
+pub fn lot_copy_costs() -> DbResult<std::collections::HashMap<String, f64>> {
+    let conn = open_db()?;
+    lot_copy_costs_conn(&conn)
+}
+
+fn lot_copy_costs_conn(conn: &Connection) -> DbResult<std::collections::HashMap<String, f64>> {
+    Ok(lot_breakdown_from(conn, None)?
+        .into_iter()
+        .filter_map(|lot| {
+            let copies = lot.in_stock_copies + lot.sold_copies;
+            match lot.cost {
+                Some(cost) if copies > 0 => Some((lot.lot, cost / copies as f64)),
+                _ => None,
+            }
+        })
+        .collect())
+}
+
+/// Per-copy acquisition cost of a listing, looked up by the lot in its
+/// location. `None` when the location has no lot or the lot has no cost.
+pub fn copy_cost_for(costs: &std::collections::HashMap<String, f64>, card: &Card) -> Option<f64> {
+    card.location
+        .as_deref()
+        .and_then(extract_lot_number)
+        .and_then(|lot| costs.get(lot).copied())
+}
+
+/// Builds the per-lot revenue breakdown from the inventory rows (of one
+/// inventory, or all with `None`) that carry a location with a recognisable
+/// lot number.
+fn lot_breakdown_from(conn: &Connection, inventory: Option<&str>) -> DbResult<Vec<LotBreakdown>> {
     let mut stmt = conn.prepare(
         "SELECT location, quantity, CAST(price AS REAL), sold_quantity
          FROM inventory_cards
-         WHERE location IS NOT NULL AND location != ''",
+         WHERE location IS NOT NULL AND location != ''
+           AND (?1 IS NULL OR inventory_id = ?1)",
     )?;
 
     let mut lots: std::collections::HashMap<String, (i64, i64, f64, i64, f64)> =
         std::collections::HashMap::new();
 
-    let rows = stmt.query_map([], |r| {
+    let rows = stmt.query_map([inventory], |r| {
         Ok((
             r.get::<_, String>(0)?,
             r.get::<_, i64>(1)?,
@@ -1187,8 +2075,9 @@ fn lot_breakdown_from(conn: &Connection) -> DbResult<Vec<LotBreakdown>> {
 }
 
 /// Inner stats query that accepts an explicit connection and reference date —
-/// used in tests. `today` (as `YYYY-MM-DD`) anchors the dead-stock aging report.
-fn get_db_stats_conn(conn: &Connection, today: &str) -> DbResult<DbStats> {
+/// used in tests. `today` (as `YYYY-MM-DD`) anchors the dead-stock aging report;
+/// `inventory` limits the stats to one inventory (`None`: all of them).
+fn get_db_stats_conn(conn: &Connection, today: &str, inventory: Option<&str>) -> DbResult<DbStats> {
     let (total_articles, in_stock_articles, total_copies, total_value): (i64, i64, i64, f64) = conn
         .query_row(
             "SELECT
@@ -1196,40 +2085,44 @@ fn get_db_stats_conn(conn: &Connection, today: &str) -> DbResult<DbStats> {
                 COALESCE(SUM(CASE WHEN quantity > 0 THEN 1 ELSE 0 END), 0),
                 COALESCE(SUM(quantity), 0),
                 COALESCE(SUM(CAST(price AS REAL) * quantity), 0.0)
-             FROM inventory_cards",
-            [],
+             FROM inventory_cards WHERE (?1 IS NULL OR inventory_id = ?1)",
+            [inventory],
             |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
         )?;
 
     let foil_count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM inventory_cards
-         WHERE quantity > 0 AND (is_foil = '1' OR LOWER(is_foil) = 'true')",
-        [],
+         WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+           AND (is_foil = '1' OR LOWER(is_foil) = 'true')",
+        [inventory],
         |r| r.get(0),
     )?;
 
     let signed_count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM inventory_cards
-         WHERE quantity > 0 AND (is_signed = '1' OR LOWER(is_signed) = 'true')",
-        [],
+         WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+           AND (is_signed = '1' OR LOWER(is_signed) = 'true')",
+        [inventory],
         |r| r.get(0),
     )?;
 
     let top_by_quantity: Vec<(String, i64)> = conn
         .prepare(
             "SELECT name, SUM(quantity) AS total FROM inventory_cards
-             WHERE quantity > 0 GROUP BY name ORDER BY total DESC LIMIT 5",
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+             GROUP BY name ORDER BY total DESC LIMIT 5",
         )?
-        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .query_map([inventory], |r| Ok((r.get(0)?, r.get(1)?)))?
         .collect::<DbResult<Vec<_>>>()?;
 
     let top_by_price: Vec<(String, f64)> = conn
         .prepare(
             "SELECT name, CAST(price AS REAL) AS p FROM inventory_cards
-             WHERE quantity > 0 AND CAST(price AS REAL) > 0
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+               AND CAST(price AS REAL) > 0
              ORDER BY p DESC LIMIT 5",
         )?
-        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .query_map([inventory], |r| Ok((r.get(0)?, r.get(1)?)))?
         .collect::<DbResult<Vec<_>>>()?;
 
     // Use listed_at when available; fall back to first_synced_at when empty.
@@ -1241,10 +2134,10 @@ fn get_db_stats_conn(conn: &Connection, today: &str) -> DbResult<DbStats> {
                     quantity,
                     COALESCE(location, '')
              FROM inventory_cards
-             WHERE quantity > 0
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
              ORDER BY effective_date ASC, quantity DESC LIMIT 5",
         )?
-        .query_map([], |r| {
+        .query_map([inventory], |r| {
             Ok(OldestInStockEntry {
                 name: r.get(0)?,
                 date: r.get(1)?,
@@ -1258,9 +2151,9 @@ fn get_db_stats_conn(conn: &Connection, today: &str) -> DbResult<DbStats> {
     let oldest_listed: Option<(String, String)> = conn
         .query_row(
             "SELECT name, COALESCE(NULLIF(listed_at, ''), first_synced_at)
-             FROM inventory_cards WHERE quantity > 0
+             FROM inventory_cards WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
              ORDER BY COALESCE(NULLIF(listed_at, ''), first_synced_at) ASC LIMIT 1",
-            [],
+            [inventory],
             |r| Ok((r.get(0)?, r.get(1)?)),
         )
         .optional()?;
@@ -1268,54 +2161,60 @@ fn get_db_stats_conn(conn: &Connection, today: &str) -> DbResult<DbStats> {
     let newest_listed: Option<(String, String)> = conn
         .query_row(
             "SELECT name, COALESCE(NULLIF(listed_at, ''), first_synced_at)
-             FROM inventory_cards WHERE quantity > 0
+             FROM inventory_cards WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
              ORDER BY COALESCE(NULLIF(listed_at, ''), first_synced_at) DESC LIMIT 1",
-            [],
+            [inventory],
             |r| Ok((r.get(0)?, r.get(1)?)),
         )
         .optional()?;
 
     // MIN on an empty table returns a single row with NULL, so we use Option<String>
     let first_synced_date: Option<String> = conn.query_row(
-        "SELECT MIN(first_synced_at) FROM inventory_cards",
-        [],
+        "SELECT MIN(first_synced_at) FROM inventory_cards
+         WHERE (?1 IS NULL OR inventory_id = ?1)",
+        [inventory],
         |r| r.get(0),
     )?;
 
     let language_breakdown: Vec<(String, i64)> = conn
         .prepare(
             "SELECT language, SUM(quantity) AS total FROM inventory_cards
-             WHERE quantity > 0 GROUP BY language ORDER BY total DESC",
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+             GROUP BY language ORDER BY total DESC",
         )?
-        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .query_map([inventory], |r| Ok((r.get(0)?, r.get(1)?)))?
         .collect::<DbResult<Vec<_>>>()?;
+    let language_breakdown = merge_breakdown(language_breakdown, canonical_language);
 
     let condition_breakdown: Vec<(String, i64)> = conn
         .prepare(
             "SELECT condition, SUM(quantity) AS total FROM inventory_cards
-             WHERE quantity > 0 GROUP BY condition ORDER BY total DESC",
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+             GROUP BY condition ORDER BY total DESC",
         )?
-        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .query_map([inventory], |r| Ok((r.get(0)?, r.get(1)?)))?
         .collect::<DbResult<Vec<_>>>()?;
+    let condition_breakdown = merge_breakdown(condition_breakdown, canonical_condition);
 
     let rarity_breakdown: Vec<(String, i64)> = conn
         .prepare(
             "SELECT rarity, SUM(quantity) AS total FROM inventory_cards
-             WHERE quantity > 0 GROUP BY rarity ORDER BY total DESC",
+             WHERE quantity > 0 AND (?1 IS NULL OR inventory_id = ?1)
+             GROUP BY rarity ORDER BY total DESC",
         )?
-        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+        .query_map([inventory], |r| Ok((r.get(0)?, r.get(1)?)))?
         .collect::<DbResult<Vec<_>>>()?;
 
-    let lot_breakdown = lot_breakdown_from(conn)?;
+    let lot_breakdown = lot_breakdown_from(conn, inventory)?;
 
     // Dead-stock aging: bucket in-stock cards by listing age relative to `today`.
-    let in_stock = get_in_stock_cards_conn(conn)?;
+    let in_stock = get_in_stock_cards_conn(conn, inventory)?;
     let aging_buckets = parse_date(today)
         .map(|d| crate::aging::bucket_cards(&in_stock, d))
         .unwrap_or_default();
 
     // Sales velocity from snapshot history.
-    let velocity = compute_velocity(&read_snapshots_conn(conn)?);
+    let velocity = compute_velocity(&read_snapshots_conn(conn, inventory)?);
 
     Ok(DbStats {
         total_articles,
@@ -1377,11 +2276,12 @@ fn aggregate_by_variant(
 
 /// Parses a CSV price string (both `.` and `,` decimal separators).
 fn parse_csv_price(price: &str) -> Option<f64> {
-    price.trim().replace(',', ".").parse::<f64>().ok()
+    parse_decimal(price, DecimalLocale::Auto)
 }
 
 /// Computes what a sync of `cards` would change, without writing anything.
 fn preview_sync_conn(conn: &Connection, cards: &[Card]) -> DbResult<SyncPreview> {
+    let inventory = active_inventory_conn(conn)?;
     let agg = aggregate_by_variant(cards);
     let mut preview = SyncPreview::default();
 
@@ -1390,9 +2290,10 @@ fn preview_sync_conn(conn: &Connection, cards: &[Card]) -> DbResult<SyncPreview>
     let rows = conn
         .prepare(
             "SELECT cardmarket_id, condition, language, is_foil, is_signed,
-                    quantity, CAST(price AS REAL) FROM inventory_cards",
+                    quantity, CAST(price AS REAL) FROM inventory_cards
+             WHERE inventory_id = ?1",
         )?
-        .query_map([], |row| {
+        .query_map([&inventory], |row| {
             Ok((
                 article_key(
                     &row.get::<_, String>(0)?,
@@ -1409,6 +2310,7 @@ fn preview_sync_conn(conn: &Connection, cards: &[Card]) -> DbResult<SyncPreview>
     for (key, qty, price) in rows {
         if qty > 0 {
             preview.copies_before += qty;
+            preview.variants_before += 1;
         }
         db.insert(key, (qty, price));
     }
@@ -1493,9 +2395,120 @@ fn backup_db_at(
     Ok(())
 }
 
+/// Size and fragmentation of the inventory DB, for the maintenance panel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DbSizeReport {
+    /// Size of the DB file on disk, in bytes.
+    pub file_bytes: u64,
+    pub page_size: i64,
+    pub page_count: i64,
+    /// Pages on SQLite's free list — space a VACUUM would give back.
+    pub free_pages: i64,
+    /// Row count per table, largest first.
+    pub table_rows: Vec<(String, i64)>,
+}
+
+impl DbSizeReport {
+    /// Share of the file that is free pages (0.0–1.0).
+    pub fn fragmentation(&self) -> f64 {
+        if self.page_count == 0 {
+            0.0
+        } else {
+            self.free_pages as f64 / self.page_count as f64
+        }
+    }
+
+    /// Bytes a VACUUM would reclaim.
+    pub fn reclaimable_bytes(&self) -> i64 {
+        self.free_pages * self.page_size
+    }
+}
+
+/// File sizes before and after a [`vacuum_db`] run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VacuumResult {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// On-disk size of the DB file (0 if it does not exist yet).
+fn db_file_bytes(path: &std::path::Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Reports file size, rows per table and free-page fragmentation.
+pub fn get_db_size_report() -> DbResult<DbSizeReport> {
+    let conn = open_db()?;
+    let mut report = get_db_size_report_conn(&conn)?;
+    report.file_bytes = db_file_bytes(&db_path());
+    Ok(report)
+}
+
+/// Inner report that accepts an explicit connection — used in tests. Leaves
+/// `file_bytes` at 0; the caller knows where the file lives.
+fn get_db_size_report_conn(conn: &Connection) -> DbResult<DbSizeReport> {
+    let pragma = |name: &str| conn.query_row(&format!("PRAGMA {name}"), [], |r| r.get(0));
+    let tables: Vec<String> = conn
+        .prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )?
+        .query_map([], |r| r.get(0))?
+        .collect::<DbResult<_>>()?;
+    let mut table_rows = Vec::with_capacity(tables.len());
+    for table in tables {
+        let quoted = table.replace('"', "\"\"");
+        let rows: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM \"{quoted}\""), [], |r| {
+            r.get(0)
+        })?;
+        table_rows.push((table, rows));
+    }
+    table_rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok(DbSizeReport {
+        file_bytes: 0,
+        page_size: pragma("page_size")?,
+        page_count: pragma("page_count")?,
+        free_pages: pragma("freelist_count")?,
+        table_rows,
+    })
+}
+
+/// Compacts the inventory DB: takes the daily backup, rewrites the file with
+/// `VACUUM` and refreshes the query planner statistics. `progress` receives a
+/// description of each stage as it starts. Blocks until done — run it off the
+/// UI thread; months of syncs make this take a few seconds.
+pub fn vacuum_db(progress: impl Fn(&str)) -> DbResult<VacuumResult> {
+    let path = db_path();
+    let conn = open_db()?;
+    let bytes_before = db_file_bytes(&path);
+    progress("Backing up database…");
+    backup_db_file(&conn, &today_date());
+    vacuum_db_conn(&conn, &progress)?;
+    let bytes_after = db_file_bytes(&path);
+    log::info!("Inventory DB compacted: {bytes_before} → {bytes_after} bytes");
+    Ok(VacuumResult {
+        bytes_before,
+        bytes_after,
+    })
+}
+
+/// Inner compaction that accepts an explicit connection — used in tests.
+fn vacuum_db_conn(conn: &Connection, progress: &impl Fn(&str)) -> DbResult<()> {
+    progress("Compacting database…");
+    conn.execute_batch("VACUUM")?;
+    progress("Updating query statistics…");
+    conn.execute_batch("PRAGMA optimize")?;
+    Ok(())
+}
+
 /// Inner sync that accepts an explicit connection and date — used in tests.
 fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> DbResult<SyncStats> {
-    log::debug!("Syncing {} cards to inventory DB ({})", cards.len(), today);
+    let inventory = active_inventory_conn(conn)?;
+    log::debug!(
+        "Syncing {} cards to inventory '{inventory}' ({today})",
+        cards.len()
+    );
     let tx = conn.transaction()?;
     let mut stats = SyncStats::default();
     let agg = aggregate_by_variant(cards);
@@ -1507,9 +2520,10 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
     let db_rows: Vec<(String, String, String, String, String, i64, f64, String)> = tx
         .prepare(
             "SELECT cardmarket_id, condition, language, is_foil, is_signed,
-                    quantity, CAST(price AS REAL), last_synced_at FROM inventory_cards",
+                    quantity, CAST(price AS REAL), last_synced_at FROM inventory_cards
+             WHERE inventory_id = ?1",
         )?
-        .query_map([], |row| {
+        .query_map([&inventory], |row| {
             Ok((
                 row.get(0)?,
                 row.get(1)?,
@@ -1540,14 +2554,15 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
                 cardmarket_id, quantity, name, set_name, set_code, cn,
                 condition, language, is_foil, is_playset, is_signed,
                 price, comment, location, name_de, name_es, name_fr, name_it,
-                rarity, listed_at, first_synced_at, last_synced_at
+                rarity, listed_at, first_synced_at, last_synced_at, inventory_id
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6,
                 ?7, ?8, ?9, ?10, ?11,
                 ?12, ?13, ?14, ?15, ?16, ?17, ?18,
-                ?19, ?20, ?21, ?21
+                ?19, ?20, ?21, ?21, ?22
             )
-            ON CONFLICT(cardmarket_id, condition, language, is_foil, is_signed) DO UPDATE SET
+            ON CONFLICT(inventory_id, cardmarket_id, condition, language, is_foil, is_signed)
+            DO UPDATE SET
                 sold_quantity   = CASE
                     WHEN excluded.quantity < inventory_cards.quantity
                     THEN inventory_cards.sold_quantity
@@ -1573,7 +2588,8 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
                     WHEN last_synced_at = excluded.last_synced_at THEN last_synced_at
                     ELSE excluded.last_synced_at
                 END
-                -- condition/language/is_foil/is_signed are the unique key; they don't change.
+                -- inventory_id/condition/language/is_foil/is_signed are the unique key;
+                -- they don't change.
                 -- first_synced_at is intentionally excluded: preserved from the original INSERT.",
         )?;
 
@@ -1617,6 +2633,7 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
                 rep_card.rarity,
                 rep_card.listed_at,
                 today,
+                inventory,
             ])?;
             stats.upserted += 1;
         }
@@ -1653,8 +2670,8 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
                      quantity = 0,
                      last_synced_at = ?1
                  WHERE cardmarket_id = ?2 AND condition = ?3 AND language = ?4
-                   AND is_foil = ?5 AND is_signed = ?6",
-                params![new_date, id, condition, language, is_foil, is_signed],
+                   AND is_foil = ?5 AND is_signed = ?6 AND inventory_id = ?7",
+                params![new_date, id, condition, language, is_foil, is_signed, inventory],
             )?;
             sold_events.push((
                 id.clone(),
@@ -1675,11 +2692,14 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
     if !sold_events.is_empty() {
         let mut stmt = tx.prepare_cached(
             "INSERT INTO sold_events
-                (date, cardmarket_id, condition, language, is_foil, is_signed, copies, price)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                (date, cardmarket_id, condition, language, is_foil, is_signed, copies, price,
+                 inventory_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
         )?;
         for (id, cond, lang, foil, signed, copies, price) in &sold_events {
-            stmt.execute(params![today, id, cond, lang, foil, signed, copies, price])?;
+            stmt.execute(params![
+                today, id, cond, lang, foil, signed, copies, price, inventory
+            ])?;
         }
         log::info!(
             "Inventory DB sync: recorded {} sold event(s)",
@@ -1687,22 +2707,24 @@ fn sync_inventory_conn(conn: &mut Connection, cards: &[Card], today: &str) -> Db
         );
     }
 
-    // Phase 3: record today's snapshot from the now-current table state. Cumulative
-    // sold figures let later reads diff any two dates into a period velocity.
-    // INSERT OR REPLACE keyed on `date` keeps at most one row per day (same-day
-    // re-syncs overwrite it with the latest numbers).
+    // Phase 3: record today's snapshot of this inventory from the now-current table
+    // state. Cumulative sold figures let later reads diff any two dates into a
+    // period velocity. INSERT OR REPLACE keyed on (`date`, `inventory_id`) keeps at
+    // most one row per inventory and day (same-day re-syncs overwrite it).
     tx.execute(
         "INSERT OR REPLACE INTO inventory_snapshots
-            (date, in_stock_copies, in_stock_value,
+            (date, inventory_id, in_stock_copies, in_stock_value,
              sold_copies_cumulative, sold_revenue_cumulative)
          SELECT
             ?1,
+            ?2,
             COALESCE(SUM(CASE WHEN quantity > 0 THEN quantity ELSE 0 END), 0),
             COALESCE(SUM(CASE WHEN quantity > 0 THEN CAST(price AS REAL) * quantity ELSE 0 END), 0.0),
             COALESCE(SUM(sold_quantity), 0),
             COALESCE(SUM(CAST(price AS REAL) * sold_quantity), 0.0)
-         FROM inventory_cards",
-        params![today],
+         FROM inventory_cards
+         WHERE inventory_id = ?2",
+        params![today, inventory],
     )?;
 
     tx.commit()?;
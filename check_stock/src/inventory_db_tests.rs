@@ -236,7 +236,7 @@ fn today_date_format() {
 #[test]
 fn get_db_stats_empty_db() {
     let conn = test_conn();
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.total_articles, 0);
     assert_eq!(stats.in_stock_articles, 0);
     assert_eq!(stats.total_copies, 0);
@@ -277,7 +277,7 @@ fn get_db_stats_counts_correctly() {
     lotus.rarity = "Rare".to_string();
 
     sync_inventory_conn(&mut conn, &[bolt, lotus], "2026-01-01").unwrap();
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
 
     assert_eq!(stats.total_articles, 2);
     assert_eq!(stats.in_stock_articles, 2);
@@ -575,7 +575,7 @@ fn migration_v1_to_v2_preserves_data_and_adds_index() {
     let index_exists: bool = conn
         .query_row(
             "SELECT 1 FROM sqlite_master \
-             WHERE type='index' AND name='idx_inventory_article_key'",
+             WHERE type='index' AND name='idx_inventory_article_key_inv'",
             [],
             |_| Ok(true),
         )
@@ -629,10 +629,10 @@ fn migration_v3_to_v2_aggregates_quantities() {
     // init_schema detects v3 and downgrades.
     init_schema(&conn).unwrap();
 
-    // v2 index must exist; v3 index must not.
+    // The current variant index must exist; v3 index must not.
     let v2_idx: bool = conn
         .query_row(
-            "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_inventory_article_key'",
+            "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_inventory_article_key_inv'",
             [],
             |_| Ok(true),
         )
@@ -691,7 +691,7 @@ fn get_db_stats_separates_in_stock_from_zeroed() {
     let day2 = vec![make_card("1", "Counterspell", "3")];
     sync_inventory_conn(&mut conn, &day2, "2026-01-02").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     // Both rows exist in DB, only 1 is in stock
     assert_eq!(stats.total_articles, 2);
     assert_eq!(stats.in_stock_articles, 1);
@@ -1002,6 +1002,97 @@ fn discard_matches_new_format_variant_against_legacy_row() {
     assert_eq!(sold_qty(&conn, "42"), 0);
 }
 
+// ==================== Pick Decrement Tests ====================
+
+/// (cardmarket_id, copies, reason, actor, at) for every audit row, oldest first.
+fn decrement_rows(conn: &Connection) -> Vec<(String, i64, String, String, String)> {
+    conn.prepare(
+        "SELECT cardmarket_id, copies, reason, actor, at FROM stock_decrements ORDER BY rowid",
+    )
+    .unwrap()
+    .query_map([], |r| {
+        Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+    })
+    .unwrap()
+    .collect::<Result<_, _>>()
+    .unwrap()
+}
+
+#[test]
+fn pick_decrement_reduces_stock_and_writes_audit_rows() {
+    let mut conn = test_conn();
+    let bolt = make_card("1", "Bolt", "10");
+    let shock = make_card("2", "Shock", "3");
+    sync_inventory_conn(&mut conn, &[bolt.clone(), shock.clone()], "2026-01-01").unwrap();
+
+    let stats = decrement_picked_cards_conn(
+        &mut conn,
+        &[(bolt, 2), (shock, 1)],
+        " Alex ",
+        "2026-01-02 10:00:00",
+    )
+    .unwrap();
+    assert_eq!(stats.variants_updated, 2);
+    assert_eq!(stats.copies_discarded, 3);
+
+    assert_eq!(get_row(&conn, "1").unwrap().0, 8);
+    assert_eq!(get_row(&conn, "2").unwrap().0, 2);
+    assert_eq!(
+        sold_qty(&conn, "1"),
+        0,
+        "picks are not recorded as sync sales"
+    );
+
+    let mut rows = decrement_rows(&conn);
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "1".to_string(),
+                2,
+                "pick".to_string(),
+                "Alex".to_string(),
+                "2026-01-02 10:00:00".to_string()
+            ),
+            (
+                "2".to_string(),
+                1,
+                "pick".to_string(),
+                "Alex".to_string(),
+                "2026-01-02 10:00:00".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn pick_decrement_audits_clamped_amount_only() {
+    let mut conn = test_conn();
+    let card = make_card("1", "Bolt", "2");
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&card), "2026-01-01").unwrap();
+    let unknown = make_card("99", "Opt", "1");
+
+    let stats =
+        decrement_picked_cards_conn(&mut conn, &[(card, 5), (unknown, 1)], "", "2026-01-02")
+            .unwrap();
+    assert_eq!(stats.copies_discarded, 2);
+
+    let rows = decrement_rows(&conn);
+    assert_eq!(rows.len(), 1, "no audit row for a variant not in the DB");
+    assert_eq!(rows[0].1, 2);
+}
+
+#[test]
+fn discard_writes_no_audit_rows() {
+    let mut conn = test_conn();
+    let card = make_card("1", "Bolt", "4");
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&card), "2026-01-01").unwrap();
+
+    discard_cards_conn(&mut conn, &[(card, 1)]).unwrap();
+    assert!(decrement_rows(&conn).is_empty());
+}
+
 // ==================== Lot Breakdown Tests ====================
 
 #[test]
@@ -1021,7 +1112,7 @@ fn lot_breakdown_groups_by_lot() {
 
     sync_inventory_conn(&mut conn, &[c1, c2, c3], "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.lot_breakdown.len(), 2);
 
     let l1 = &stats.lot_breakdown[0];
@@ -1048,7 +1139,7 @@ fn lot_breakdown_tracks_sold_revenue() {
     // All 4 copies sold
     sync_inventory_conn(&mut conn, &[], "2026-01-02").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.lot_breakdown.len(), 1);
     let l3 = &stats.lot_breakdown[0];
     assert_eq!(l3.sold_copies, 4);
@@ -1067,7 +1158,7 @@ fn lot_breakdown_skips_l0_catch_all() {
 
     sync_inventory_conn(&mut conn, &[c1, c2], "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.lot_breakdown.len(), 1);
     assert_eq!(stats.lot_breakdown[0].lot, "L1");
 }
@@ -1078,7 +1169,7 @@ fn lot_breakdown_empty_without_locations() {
     let card = make_card("1", "Bolt", "2");
     sync_inventory_conn(&mut conn, &[card], "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert!(stats.lot_breakdown.is_empty());
 }
 
@@ -1089,7 +1180,7 @@ fn lot_breakdown_skips_location_without_lot() {
     card.location = Some("A-0-1-4".to_string());
     sync_inventory_conn(&mut conn, &[card], "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert!(stats.lot_breakdown.is_empty());
 }
 
@@ -1198,7 +1289,7 @@ fn lot_breakdown_populates_recorded_cost() {
     sync_inventory_conn(&mut conn, &[c1], "2026-01-01").unwrap();
     set_lot_cost_conn(&conn, "L3", 5.00, "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     let l3 = &stats.lot_breakdown[0];
     assert_eq!(l3.cost, Some(5.00));
     // Revenue is 0 so far → margin -100%, still €5 to recoup.
@@ -1213,10 +1304,36 @@ fn lot_breakdown_cost_is_none_when_unrecorded() {
     c1.location = Some("A-0-0-1-L1-R".to_string());
     sync_inventory_conn(&mut conn, &[c1], "2026-01-01").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.lot_breakdown[0].cost, None);
 }
 
+#[test]
+fn lot_copy_costs_spread_cost_over_held_copies() {
+    let mut conn = test_conn();
+    let mut c1 = make_card("1", "Bolt", "4");
+    c1.location = Some("A-0-0-1-L3-R".to_string());
+    let mut c2 = make_card("2", "Shock", "2");
+    c2.location = Some("A-0-0-2-L4-R".to_string());
+    sync_inventory_conn(&mut conn, &[c1.clone(), c2], "2026-01-01").unwrap();
+    // One Bolt sells: L3 has held 4 copies (3 in stock + 1 sold).
+    let mut c1_sold = c1.clone();
+    c1_sold.quantity = "3".to_string();
+    let mut c2_kept = make_card("2", "Shock", "2");
+    c2_kept.location = Some("A-0-0-2-L4-R".to_string());
+    sync_inventory_conn(&mut conn, &[c1_sold, c2_kept], "2026-01-02").unwrap();
+    set_lot_cost_conn(&conn, "L3", 10.00, "2026-01-02").unwrap();
+
+    let costs = lot_copy_costs_conn(&conn).unwrap();
+    assert_eq!(costs.len(), 1, "L4 has no recorded cost");
+    assert!((costs["L3"] - 2.50).abs() < 1e-9);
+
+    assert_eq!(copy_cost_for(&costs, &c1), Some(2.50));
+    let mut unlotted = make_card("9", "Opt", "1");
+    unlotted.location = Some("A-0-0-3".to_string());
+    assert_eq!(copy_cost_for(&costs, &unlotted), None);
+}
+
 // ==================== snapshots / velocity / in-stock ====================
 
 #[test]
@@ -1226,7 +1343,7 @@ fn sync_writes_daily_snapshot() {
     a.price = "2.00".to_string();
     sync_inventory_conn(&mut conn, &[a], "2026-01-01").unwrap();
 
-    let snaps = read_snapshots_conn(&conn).unwrap();
+    let snaps = read_snapshots_conn(&conn, None).unwrap();
     assert_eq!(snaps.len(), 1);
     assert_eq!(snaps[0].date, "2026-01-01");
     assert_eq!(snaps[0].in_stock_copies, 4);
@@ -1240,7 +1357,7 @@ fn same_day_resync_overwrites_snapshot() {
     sync_inventory_conn(&mut conn, &[make_card("1", "Bolt", "4")], "2026-01-01").unwrap();
     sync_inventory_conn(&mut conn, &[make_card("1", "Bolt", "2")], "2026-01-01").unwrap();
 
-    let snaps = read_snapshots_conn(&conn).unwrap();
+    let snaps = read_snapshots_conn(&conn, None).unwrap();
     assert_eq!(snaps.len(), 1, "one row per day");
     assert_eq!(snaps[0].in_stock_copies, 2);
 }
@@ -1257,7 +1374,7 @@ fn snapshot_tracks_cumulative_sales() {
     c2.quantity = "6".to_string();
     sync_inventory_conn(&mut conn, &[c2], "2026-01-08").unwrap();
 
-    let snaps = read_snapshots_conn(&conn).unwrap();
+    let snaps = read_snapshots_conn(&conn, None).unwrap();
     assert_eq!(snaps.len(), 2);
     assert_eq!(snaps[1].sold_copies_cumulative, 4);
     assert!((snaps[1].sold_revenue_cumulative - 4.0).abs() < 0.001);
@@ -1340,7 +1457,7 @@ fn get_in_stock_cards_excludes_zeroed_and_parses_foil() {
     let sold_out = make_card("2", "Shock", "0"); // zero qty, excluded
     sync_inventory_conn(&mut conn, &[foil, sold_out], "2026-01-01").unwrap();
 
-    let cards = get_in_stock_cards_conn(&conn).unwrap();
+    let cards = get_in_stock_cards_conn(&conn, None).unwrap();
     assert_eq!(cards.len(), 1);
     assert_eq!(cards[0].cardmarket_id, "1");
     assert!(cards[0].is_foil);
@@ -1357,7 +1474,7 @@ fn aging_buckets_present_in_stats() {
     old.listed_at = "2024-01-01".to_string(); // very old
     sync_inventory_conn(&mut conn, &[old], "2026-07-14").unwrap();
 
-    let stats = get_db_stats_conn(&conn, "2026-07-14").unwrap();
+    let stats = get_db_stats_conn(&conn, "2026-07-14", None).unwrap();
     assert_eq!(stats.aging_buckets.len(), 5);
     assert_eq!(stats.aging_buckets[4].copies, 2, "old card in 365+ bucket");
 }
@@ -1544,6 +1661,7 @@ fn preview_detects_drops_zeroes_and_price_changes() {
     assert_eq!(p.price_changes, 1);
     assert_eq!(p.copies_before, 10);
     assert_eq!(p.copies_after, 7);
+    assert_eq!(p.variants_before, 3);
     // Nothing was written by the preview itself.
     assert_eq!(count_rows(&conn), 3);
 }
@@ -1575,6 +1693,27 @@ fn preview_suspicious_only_on_large_relative_drop() {
     assert!(!half.is_suspicious());
 }
 
+#[test]
+fn preview_suspicious_when_many_variants_vanish() {
+    // Few copies sold, but a quarter of the listed variants disappeared —
+    // typical for an export missing a whole set or language.
+    let gap = SyncPreview {
+        copies_before: 1000,
+        copies_sold: 60,
+        variants_before: 400,
+        zeroed_variants: 100,
+        ..SyncPreview::default()
+    };
+    assert!(gap.is_suspicious());
+    assert!((gap.zeroed_fraction() - 0.25).abs() < 1e-9);
+
+    let normal = SyncPreview {
+        zeroed_variants: 80,
+        ..gap.clone()
+    };
+    assert!(!normal.is_suspicious());
+}
+
 #[test]
 fn backup_creates_dated_snapshot_and_prunes() {
     let dir = tempfile::tempdir().unwrap();
@@ -1602,6 +1741,33 @@ fn backup_creates_dated_snapshot_and_prunes() {
     assert!(dir.path().join("inventory-2026-01-03.db.bak").exists());
 }
 
+// ==================== fulfillment history ====================
+
+#[test]
+fn fulfillment_history_round_trips_in_time_order() {
+    let conn = test_conn();
+    let later = FulfillmentRecord {
+        checked_at: "2026-01-02 09:00:00".to_string(),
+        wantslist: "cube.txt".to_string(),
+        wanted: 10,
+        found: 7,
+        missing_cards: 2,
+        quoted_value: 12.5,
+    };
+    let earlier = FulfillmentRecord {
+        checked_at: "2026-01-01 18:30:00".to_string(),
+        found: 5,
+        ..later.clone()
+    };
+    record_fulfillment_conn(&conn, &later).unwrap();
+    record_fulfillment_conn(&conn, &earlier).unwrap();
+
+    assert_eq!(
+        get_fulfillment_history_conn(&conn).unwrap(),
+        vec![earlier, later]
+    );
+}
+
 // ==================== visit digest ====================
 
 #[test]
@@ -1655,3 +1821,435 @@ fn digest_counts_restock_candidates() {
     let d = visit_digest_conn(&conn, "2026-01-02").unwrap();
     assert_eq!(d.restock_candidates, 1);
 }
+
+// ==================== Playset Conversion Tests ====================
+
+#[test]
+fn playset_split_raises_listing_count_without_sale() {
+    let mut conn = test_conn();
+    let mut card = make_card("1", "Bolt", "2");
+    card.is_playset = Some("1".to_string());
+    card.price = "8.00".to_string();
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&card), "2026-01-01").unwrap();
+
+    let conv = crate::playset::plan_split(&card, 2).unwrap();
+    let updated = apply_playset_conversions_conn(&mut conn, &[conv]).unwrap();
+    assert_eq!(updated, 1);
+
+    let (qty, _, _) = get_row(&conn, "1").unwrap();
+    assert_eq!(qty, 8);
+    let (is_playset, price): (Option<String>, String) = conn
+        .query_row(
+            "SELECT is_playset, price FROM inventory_cards WHERE cardmarket_id = '1'",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(is_playset.as_deref(), Some("0"));
+    assert_eq!(price, "2.00");
+    assert_eq!(sold_qty(&conn, "1"), 0);
+}
+
+#[test]
+fn playset_merge_does_not_record_phantom_sale_on_next_sync() {
+    let mut conn = test_conn();
+    let card = make_card("1", "Bolt", "8");
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&card), "2026-01-01").unwrap();
+
+    let conv = crate::playset::plan_merge(&card, 8).unwrap();
+    apply_playset_conversions_conn(&mut conn, &[conv]).unwrap();
+    let (qty, _, _) = get_row(&conn, "1").unwrap();
+    assert_eq!(qty, 2);
+
+    // Cardmarket now lists the variant as two playsets.
+    let mut merged = make_card("1", "Bolt", "2");
+    merged.is_playset = Some("true".to_string());
+    sync_inventory_conn(&mut conn, &[merged], "2026-01-02").unwrap();
+
+    assert_eq!(sold_qty(&conn, "1"), 0, "merging is not a sale");
+}
+
+#[test]
+fn playset_partial_merge_keeps_row_flags() {
+    let mut conn = test_conn();
+    let card = make_card("1", "Bolt", "6");
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&card), "2026-01-01").unwrap();
+
+    // Only four of the six singles are merged: one playset + two singles remain.
+    let conv = crate::playset::plan_merge(&card, 4).unwrap();
+    apply_playset_conversions_conn(&mut conn, &[conv]).unwrap();
+
+    let (qty, _, _) = get_row(&conn, "1").unwrap();
+    assert_eq!(qty, 3);
+    let price: String = conn
+        .query_row(
+            "SELECT price FROM inventory_cards WHERE cardmarket_id = '1'",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(
+        price, "1.00",
+        "partially converted row keeps the single price"
+    );
+}
+
+#[test]
+fn playset_conversion_skips_unknown_variant() {
+    let mut conn = test_conn();
+    let card = make_card("1", "Bolt", "4");
+    let conv = crate::playset::plan_merge(&card, 4).unwrap();
+    assert_eq!(
+        apply_playset_conversions_conn(&mut conn, &[conv]).unwrap(),
+        0
+    );
+    assert_eq!(count_rows(&conn), 0);
+}
+
+#[test]
+fn size_report_counts_rows_per_table() {
+    let mut conn = test_conn();
+    let cards: Vec<Card> = (1..=3)
+        .map(|i| make_card(&i.to_string(), "Card", "1"))
+        .collect();
+    sync_inventory_conn(&mut conn, &cards, "2026-01-01").unwrap();
+
+    let report = get_db_size_report_conn(&conn).unwrap();
+    assert!(report.page_size > 0);
+    assert!(report.page_count > 0);
+    assert_eq!(report.table_rows[0], ("inventory_cards".to_string(), 3));
+    assert!(report
+        .table_rows
+        .iter()
+        .any(|(t, n)| t == "lot_costs" && *n == 0));
+    assert!(report
+        .table_rows
+        .iter()
+        .all(|(t, _)| !t.starts_with("sqlite_")));
+}
+
+#[test]
+fn vacuum_reclaims_free_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("inventory.db");
+    let mut conn = Connection::open(&path).unwrap();
+    init_schema(&conn).unwrap();
+    let cards: Vec<Card> = (1..=2000)
+        .map(|i| make_card(&i.to_string(), &format!("Card with a long name {i}"), "1"))
+        .collect();
+    sync_inventory_conn(&mut conn, &cards, "2026-01-01").unwrap();
+    conn.execute("DELETE FROM inventory_cards", []).unwrap();
+
+    let before = get_db_size_report_conn(&conn).unwrap();
+    assert!(before.free_pages > 0);
+    assert!(before.fragmentation() > 0.0);
+    assert_eq!(
+        before.reclaimable_bytes(),
+        before.free_pages * before.page_size
+    );
+
+    let stages = std::cell::RefCell::new(Vec::new());
+    vacuum_db_conn(&conn, &|stage: &str| {
+        stages.borrow_mut().push(stage.to_string())
+    })
+    .unwrap();
+    assert_eq!(stages.borrow().len(), 2);
+
+    let after = get_db_size_report_conn(&conn).unwrap();
+    assert_eq!(after.free_pages, 0);
+    assert!(after.page_count < before.page_count);
+    assert_eq!(after.fragmentation(), 0.0);
+}
+
+#[test]
+fn fragmentation_of_empty_report_is_zero() {
+    let report = DbSizeReport::default();
+    assert_eq!(report.fragmentation(), 0.0);
+    assert_eq!(report.reclaimable_bytes(), 0);
+}
+
+#[test]
+fn merge_breakdown_collapses_spellings() {
+    let rows = vec![
+        ("English".to_string(), 10),
+        ("German".to_string(), 4),
+        ("Englisch".to_string(), 3),
+        ("deutsch".to_string(), 2),
+        ("Klingon".to_string(), 1),
+    ];
+
+    assert_eq!(
+        merge_breakdown(rows, canonical_language),
+        vec![
+            ("English".to_string(), 13),
+            ("German".to_string(), 6),
+            ("Klingon".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn variant_quantities_look_up_each_variant() {
+    let mut conn = test_conn();
+    let bolt = make_card("1", "Bolt", "4");
+    sync_inventory_conn(&mut conn, std::slice::from_ref(&bolt), "2026-01-01").unwrap();
+    let mut lower_case = bolt.clone();
+    lower_case.condition = "near_mint".to_string();
+    let unknown = make_card("99", "Opt", "1");
+
+    let quantities = variant_quantities_conn(&conn, &[&bolt, &unknown, &lower_case]).unwrap();
+    assert_eq!(quantities, vec![Some(4), None, Some(4)]);
+}
+
+#[test]
+fn listing_photos_are_keyed_by_variant() {
+    let conn = test_conn();
+    let bolt = make_card("1", "Lightning Bolt", "4");
+    let mut foil_bolt = make_card("1", "Lightning Bolt", "1");
+    foil_bolt.is_foil = "true".to_string();
+    let mut legacy_spelling = bolt.clone();
+    legacy_spelling.condition = "Near Mint".to_string();
+
+    set_listing_photo_conn(&conn, &bolt, "/photos/a.jpg", "2026-01-01 10:00:00").unwrap();
+    assert_eq!(
+        listing_photos_conn(&conn, &[&bolt, &foil_bolt, &legacy_spelling]).unwrap(),
+        vec![
+            Some(PathBuf::from("/photos/a.jpg")),
+            None,
+            Some(PathBuf::from("/photos/a.jpg")),
+        ]
+    );
+
+    // A new photo replaces the old one
+    set_listing_photo_conn(&conn, &bolt, "/photos/b.jpg", "2026-01-02 10:00:00").unwrap();
+    assert_eq!(
+        listing_photos_conn(&conn, &[&bolt]).unwrap(),
+        vec![Some(PathBuf::from("/photos/b.jpg"))]
+    );
+
+    remove_listing_photo_conn(&conn, &bolt).unwrap();
+    assert_eq!(listing_photos_conn(&conn, &[&bolt]).unwrap(), vec![None]);
+}
+
+#[test]
+fn exclusions_round_trip_whole_cards_and_variants() {
+    let conn = test_conn();
+    let mut foil_bolt = make_card("1", "Lightning Bolt", "1");
+    foil_bolt.is_foil = "true".to_string();
+    let variant = Exclusion {
+        cardmarket_id: "1".to_string(),
+        name: "Lightning Bolt".to_string(),
+        variant: Some(ExcludedVariant::of(&foil_bolt)),
+        note: "reserved".to_string(),
+    };
+    let whole = Exclusion {
+        cardmarket_id: "2".to_string(),
+        name: "Black Lotus".to_string(),
+        variant: None,
+        note: "display".to_string(),
+    };
+
+    add_exclusion_conn(&conn, &variant, "2026-01-01 10:00:00").unwrap();
+    add_exclusion_conn(&conn, &whole, "2026-01-01 10:00:00").unwrap();
+    assert_eq!(
+        get_exclusions_conn(&conn).unwrap(),
+        vec![whole.clone(), variant.clone()]
+    );
+
+    // Adding again only updates the note
+    let renoted = Exclusion {
+        note: "reserved for Friday".to_string(),
+        ..variant.clone()
+    };
+    add_exclusion_conn(&conn, &renoted, "2026-01-02 10:00:00").unwrap();
+    let stored = get_exclusions_conn(&conn).unwrap();
+    assert_eq!(stored.len(), 2);
+    assert!(stored.contains(&renoted));
+
+    remove_exclusion_conn(&conn, &whole).unwrap();
+    assert_eq!(get_exclusions_conn(&conn).unwrap(), vec![renoted]);
+}
+
+#[test]
+fn bulk_repricing_updates_prices_and_records_the_changes() {
+    let mut conn = test_conn();
+    let mut foil = make_card("2", "Goblin Guide", "1");
+    foil.is_foil = "true".to_string();
+    let cards = vec![
+        make_card("1", "Lightning Bolt", "3"),
+        foil,
+        make_card("3", "Sold Out", "1"),
+    ];
+    sync_inventory_conn(&mut conn, &cards, "2026-01-01").unwrap();
+    sync_inventory_conn(&mut conn, &cards[..2], "2026-01-02").unwrap();
+
+    let stock = get_in_stock_inventory_conn(&conn, None).unwrap();
+    assert_eq!(stock.len(), 2);
+    assert_eq!(stock[0].name, "Goblin Guide");
+    assert_eq!(stock[0].is_foil, "1");
+    assert_eq!(stock[1].quantity, "3");
+
+    let change = |card: &Card, new_price: f64| PriceChange {
+        card: card.clone(),
+        old_price: 1.0,
+        new_price,
+        rule: "Inventory Price".to_string(),
+    };
+    let mut missing = make_card("99", "Not In Stock", "1");
+    missing.price = "2.00".to_string();
+    let applied = apply_price_changes_conn(
+        &mut conn,
+        &[
+            change(&stock[0], 4.5),
+            change(&stock[1], 0.25),
+            change(&missing, 3.0),
+        ],
+        "2026-01-03 10:00:00",
+    )
+    .unwrap();
+    assert_eq!(applied, 2);
+
+    let prices: Vec<String> = get_in_stock_inventory_conn(&conn, None)
+        .unwrap()
+        .into_iter()
+        .map(|c| c.price)
+        .collect();
+    assert_eq!(prices, ["4.50", "0.25"]);
+    let audit: Vec<(String, String, f64, f64, String)> = conn
+        .prepare(
+            "SELECT changed_at, name, old_price, new_price, rule FROM price_changes
+             ORDER BY name",
+        )
+        .unwrap()
+        .query_map([], |r| {
+            Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(audit.len(), 2);
+    assert_eq!(
+        audit[0],
+        (
+            "2026-01-03 10:00:00".to_string(),
+            "Goblin Guide".to_string(),
+            1.0,
+            4.5,
+            "Inventory Price".to_string()
+        )
+    );
+}
+
+#[test]
+fn migration_moves_existing_rows_into_the_default_inventory() {
+    // v2 schema from before inventories: 5-field key, per-day snapshots
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE inventory_cards (
+            cardmarket_id TEXT NOT NULL, quantity INTEGER NOT NULL,
+            name TEXT NOT NULL, set_name TEXT NOT NULL, set_code TEXT NOT NULL,
+            cn TEXT NOT NULL, condition TEXT NOT NULL, language TEXT NOT NULL,
+            is_foil TEXT NOT NULL, is_playset TEXT, is_signed TEXT NOT NULL,
+            price TEXT NOT NULL, comment TEXT NOT NULL, location TEXT,
+            name_de TEXT NOT NULL, name_es TEXT NOT NULL, name_fr TEXT NOT NULL,
+            name_it TEXT NOT NULL, rarity TEXT NOT NULL, listed_at TEXT NOT NULL,
+            first_synced_at TEXT NOT NULL, last_synced_at TEXT NOT NULL,
+            sold_quantity INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE UNIQUE INDEX idx_inventory_article_key
+            ON inventory_cards (cardmarket_id, condition, language, is_foil, is_signed);
+        INSERT INTO inventory_cards VALUES
+            ('1', 4, 'Lightning Bolt', 'Alpha', 'LEA', '1', 'NM', 'English',
+             '', NULL, '', '2.00', '', NULL, '', '', '', '', 'Common',
+             '2024-01-01', '2026-01-01', '2026-01-01', 0);
+        CREATE TABLE inventory_snapshots (
+            date TEXT PRIMARY KEY, in_stock_copies INTEGER NOT NULL,
+            in_stock_value REAL NOT NULL, sold_copies_cumulative INTEGER NOT NULL,
+            sold_revenue_cumulative REAL NOT NULL
+        );
+        INSERT INTO inventory_snapshots VALUES ('2026-01-01', 4, 8.0, 0, 0.0);
+        CREATE TABLE sold_events (
+            date TEXT NOT NULL, cardmarket_id TEXT NOT NULL, condition TEXT NOT NULL,
+            language TEXT NOT NULL, is_foil TEXT NOT NULL, is_signed TEXT NOT NULL,
+            copies INTEGER NOT NULL, price REAL NOT NULL
+        );
+        INSERT INTO sold_events VALUES ('2026-01-01', '1', 'NM', 'English', '', '', 1, 2.0);",
+    )
+    .unwrap();
+
+    init_schema(&conn).unwrap();
+    init_schema(&conn).unwrap();
+
+    let summaries = list_inventories_conn(&conn).unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].id, DEFAULT_INVENTORY);
+    assert_eq!(summaries[0].in_stock_copies, 4);
+    let snapshots = read_snapshots_conn(&conn, Some(DEFAULT_INVENTORY)).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(get_sold_events_conn(&conn).unwrap().len(), 1);
+}
+
+#[test]
+fn inventories_sync_separately_and_report_together() {
+    let mut conn = test_conn();
+    sync_inventory_conn(
+        &mut conn,
+        &[
+            make_card("1", "Lightning Bolt", "4"),
+            make_card("2", "Counterspell", "2"),
+        ],
+        "2026-03-01",
+    )
+    .unwrap();
+
+    // The collection holds the same variant; syncing it must not touch the shop
+    set_meta(&conn, "active_inventory", "collection").unwrap();
+    sync_inventory_conn(
+        &mut conn,
+        &[make_card("1", "Lightning Bolt", "1")],
+        "2026-03-02",
+    )
+    .unwrap();
+    let shop = get_db_stats_conn(&conn, "2026-03-02", Some(DEFAULT_INVENTORY)).unwrap();
+    assert_eq!(shop.total_copies, 6);
+    let collection = get_db_stats_conn(&conn, "2026-03-02", Some("collection")).unwrap();
+    assert_eq!(collection.total_copies, 1);
+    let all = get_db_stats_conn(&conn, "2026-03-02", None).unwrap();
+    assert_eq!(all.total_copies, 7);
+    assert_eq!(get_in_stock_inventory_conn(&conn, None).unwrap().len(), 3);
+
+    // Writes by variant key only reach the active inventory
+    discard_cards_conn(&mut conn, &[(make_card("1", "Lightning Bolt", "1"), 1)]).unwrap();
+    let quantities: Vec<(String, i64)> = conn
+        .prepare(
+            "SELECT inventory_id, quantity FROM inventory_cards
+             WHERE cardmarket_id = '1' ORDER BY inventory_id",
+        )
+        .unwrap()
+        .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))
+        .unwrap()
+        .collect::<DbResult<_>>()
+        .unwrap();
+    assert_eq!(
+        quantities,
+        vec![("collection".to_string(), 0), ("shop".to_string(), 4)]
+    );
+
+    let ids: Vec<String> = list_inventories_conn(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|i| i.id)
+        .collect();
+    assert_eq!(ids, vec!["collection", "shop"]);
+}
+
+#[test]
+fn inventory_names_are_normalized() {
+    assert_eq!(
+        normalize_inventory_id("  Personal Collection "),
+        Some("personal-collection".to_string())
+    );
+    assert_eq!(normalize_inventory_id("shop_2"), Some("shop_2".to_string()));
+    assert_eq!(normalize_inventory_id("   "), None);
+    assert_eq!(normalize_inventory_id("a/b"), None);
+}
@@ -0,0 +1,146 @@
+//! In-stock copies and value per set and rarity.
+//!
+//! Feeds the heatmap on the Stock Analysis screen: one row per set, one
+//! column per rarity, so sets overstuffed with bulk commons stand out next to
+//! the sets where the value sits. Clicking a cell lists its cards via
+//! [`cards_in_cell`].
+
+use crate::inventory_db::InStockCard;
+use crate::set_symbols::Rarity;
+
+/// Heatmap columns, lowest rarity first.
+pub const RARITY_COLUMNS: [Rarity; 6] = [
+    Rarity::Common,
+    Rarity::Uncommon,
+    Rarity::Rare,
+    Rarity::Mythic,
+    Rarity::Special,
+    Rarity::Other,
+];
+
+/// Copies and listed value (quantity × price, EUR) of one set/rarity cell.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HeatmapCell {
+    pub copies: i64,
+    pub value: f64,
+}
+
+impl HeatmapCell {
+    fn add(&mut self, other: HeatmapCell) {
+        self.copies += other.copies;
+        self.value += other.value;
+    }
+}
+
+/// One set's cells, in [`RARITY_COLUMNS`] order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapRow {
+    pub set_code: String,
+    pub cells: [HeatmapCell; RARITY_COLUMNS.len()],
+}
+
+impl HeatmapRow {
+    pub fn total(&self) -> HeatmapCell {
+        let mut total = HeatmapCell::default();
+        for cell in &self.cells {
+            total.add(*cell);
+        }
+        total
+    }
+}
+
+/// What the heatmap colours by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatmapMetric {
+    #[default]
+    Copies,
+    Value,
+}
+
+impl HeatmapMetric {
+    pub fn label(self) -> &'static str {
+        match self {
+            HeatmapMetric::Copies => "Copies",
+            HeatmapMetric::Value => "Value",
+        }
+    }
+
+    pub fn of(self, cell: &HeatmapCell) -> f64 {
+        match self {
+            HeatmapMetric::Copies => cell.copies as f64,
+            HeatmapMetric::Value => cell.value,
+        }
+    }
+}
+
+/// The column a rarity is counted in.
+pub fn column_index(rarity: Rarity) -> usize {
+    RARITY_COLUMNS
+        .iter()
+        .position(|r| *r == rarity)
+        .unwrap_or(RARITY_COLUMNS.len() - 1)
+}
+
+/// Sums the cards per set and rarity. Sets are ordered by `metric`, largest
+/// first, then by set code.
+pub fn build_heatmap(cards: &[InStockCard], metric: HeatmapMetric) -> Vec<HeatmapRow> {
+    let mut rows: Vec<HeatmapRow> = Vec::new();
+    for card in cards {
+        let set_code = card.set_code.to_uppercase();
+        let row = match rows.iter().position(|r| r.set_code == set_code) {
+            Some(i) => &mut rows[i],
+            None => {
+                rows.push(HeatmapRow {
+                    set_code,
+                    cells: Default::default(),
+                });
+                rows.last_mut().expect("row was just pushed")
+            }
+        };
+        row.cells[column_index(Rarity::parse(&card.rarity))].add(HeatmapCell {
+            copies: card.quantity,
+            value: card.quantity as f64 * card.price,
+        });
+    }
+    rows.sort_by(|a, b| {
+        metric
+            .of(&b.total())
+            .total_cmp(&metric.of(&a.total()))
+            .then_with(|| a.set_code.cmp(&b.set_code))
+    });
+    rows
+}
+
+/// The largest single cell, for scaling the colours.
+pub fn max_cell(rows: &[HeatmapRow], metric: HeatmapMetric) -> f64 {
+    rows.iter()
+        .flat_map(|row| row.cells.iter())
+        .map(|cell| metric.of(cell))
+        .fold(0.0, f64::max)
+}
+
+/// The cards behind one cell, most valuable first.
+pub fn cards_in_cell<'a>(
+    cards: &'a [InStockCard],
+    set_code: &str,
+    rarity: Rarity,
+) -> Vec<&'a InStockCard> {
+    let column = column_index(rarity);
+    let mut matching: Vec<&InStockCard> = cards
+        .iter()
+        .filter(|card| {
+            card.set_code.eq_ignore_ascii_case(set_code)
+                && column_index(Rarity::parse(&card.rarity)) == column
+        })
+        .collect();
+    matching.sort_by(|a, b| {
+        (b.quantity as f64 * b.price)
+            .total_cmp(&(a.quantity as f64 * a.price))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    matching
+}
+
+#[cfg(test)]
+#[path = "inventory_heatmap_tests.rs"]
+mod tests;
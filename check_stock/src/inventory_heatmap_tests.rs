@@ -0,0 +1,67 @@
+//! Tests for the set/rarity heatmap.
+
+use super::*;
+
+fn card(name: &str, set_code: &str, rarity: &str, quantity: i64, price: f64) -> InStockCard {
+    InStockCard {
+        cardmarket_id: name.to_string(),
+        name: name.to_string(),
+        set_code: set_code.to_string(),
+        cn: "1".to_string(),
+        condition: "NM".to_string(),
+        language: "English".to_string(),
+        is_foil: false,
+        rarity: rarity.to_string(),
+        quantity,
+        price,
+        location: "A-0-1-1".to_string(),
+        effective_date: "2026-01-01".to_string(),
+    }
+}
+
+fn cards() -> Vec<InStockCard> {
+    vec![
+        card("Bulk A", "dmu", "common", 40, 0.02),
+        card("Bulk B", "DMU", "common", 20, 0.05),
+        card("Bomb", "DMU", "mythic", 1, 25.0),
+        card("Chase", "MOM", "rare", 2, 30.0),
+        card("Token", "MOM", "token", 3, 0.1),
+    ]
+}
+
+#[test]
+fn sums_copies_and_value_per_set_and_rarity() {
+    let rows = build_heatmap(&cards(), HeatmapMetric::Copies);
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].set_code, "DMU");
+    let commons = rows[0].cells[column_index(Rarity::Common)];
+    assert_eq!(commons.copies, 60);
+    assert!((commons.value - 1.8).abs() < 1e-9);
+    assert_eq!(rows[0].cells[column_index(Rarity::Mythic)].copies, 1);
+    assert_eq!(rows[1].cells[column_index(Rarity::Other)].copies, 3);
+    assert_eq!(rows[0].total().copies, 61);
+}
+
+#[test]
+fn rows_follow_the_chosen_metric() {
+    let by_value = build_heatmap(&cards(), HeatmapMetric::Value);
+
+    assert_eq!(by_value[0].set_code, "MOM");
+    assert_eq!(max_cell(&by_value, HeatmapMetric::Value), 60.0);
+    assert_eq!(max_cell(&by_value, HeatmapMetric::Copies), 60.0);
+    assert_eq!(max_cell(&[], HeatmapMetric::Copies), 0.0);
+}
+
+#[test]
+fn cell_lists_its_cards_most_valuable_first() {
+    let cards = cards();
+
+    let listed: Vec<&str> = cards_in_cell(&cards, "dmu", Rarity::Common)
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+
+    assert_eq!(listed, ["Bulk B", "Bulk A"]);
+    assert!(cards_in_cell(&cards, "DMU", Rarity::Rare).is_empty());
+}
@@ -1,44 +1,245 @@
 use crate::models::{Card, WantsEntry};
-use crate::wantslist::parse_wantslist;
-use log::info;
+use crate::wantslist::{is_csv_wantslist, parse_wantslist, parse_wantslist_csv};
+use log::{info, warn};
+use mtg_common::{parse_decimal, DecimalLocale};
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
+/// File dialog extensions for inventory CSVs, including compressed ones.
+pub const CSV_EXTENSIONS: &[&str] = &["csv", "gz", "zip"];
+
+/// How [`read_csv`] treats malformed rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvLoadMode {
+    /// The first malformed row fails the whole load; nothing is loaded.
+    #[default]
+    Strict,
+    /// Malformed rows are skipped; the valid ones are loaded and the skipped
+    /// ones listed in a [`CsvLoadReport`].
+    Lenient,
+}
+
+impl CsvLoadMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            CsvLoadMode::Strict => "Strict",
+            CsvLoadMode::Lenient => "Lenient",
+        }
+    }
+}
+
+/// A CSV row that could not be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvLineError {
+    /// 1-based line in the file (the header is line 1)
+    pub line: u64,
+    pub message: String,
+}
+
+/// What loading one inventory CSV skipped, and why.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvLoadReport {
+    pub path: String,
+    pub loaded: usize,
+    /// Rows without a price or quantity
+    pub skipped_empty: usize,
+    /// Rows with quantity 0 (sold-out placeholders)
+    pub skipped_zero: usize,
+    /// Malformed rows; always empty in strict mode, which fails instead
+    pub errors: Vec<CsvLineError>,
+}
+
+impl CsvLoadReport {
+    /// The malformed rows as a `line,error` CSV, for saving from the UI.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("line,error\n");
+        for error in &self.errors {
+            out.push_str(&format!(
+                "{},\"{}\"\n",
+                error.line,
+                error.message.replace('"', "\"\"")
+            ));
+        }
+        out
+    }
+}
+
+static LENIENT: AtomicBool = AtomicBool::new(false);
+static PENDING_REPORT: Mutex<Option<CsvLoadReport>> = Mutex::new(None);
+
+/// The mode [`read_csv`] loads with; strict unless changed.
+pub fn csv_load_mode() -> CsvLoadMode {
+    if LENIENT.load(Ordering::Relaxed) {
+        CsvLoadMode::Lenient
+    } else {
+        CsvLoadMode::Strict
+    }
+}
+
+/// Sets the mode [`read_csv`] loads with, for the whole process.
+pub fn set_csv_load_mode(mode: CsvLoadMode) {
+    LENIENT.store(mode == CsvLoadMode::Lenient, Ordering::Relaxed);
+}
+
+/// Takes the report of the last [`read_csv`] that skipped malformed rows, if
+/// it has not been taken yet. The UI shows it once after a lenient load.
+pub fn take_load_report() -> Option<CsvLoadReport> {
+    PENDING_REPORT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+}
+
+/// Reads an inventory CSV, parsing prices in the format configured via
+/// `CSV_DECIMAL_LOCALE` (auto-detected by default) and treating malformed
+/// rows per [`csv_load_mode`]. Gzipped and single-entry zip files are
+/// decompressed, and files Excel saved as UTF-16 or Windows-1252 are
+/// converted (see [`mtg_common::text_encoding`]). A lenient load that skipped
+/// rows leaves its report for [`take_load_report`].
 pub fn read_csv(path: &str) -> Result<Vec<Card>, Box<dyn std::error::Error>> {
-    info!("Reading inventory CSV from: {}", path);
+    let (cards, report) = read_csv_report(path, DecimalLocale::from_env(), csv_load_mode())?;
+    if !report.errors.is_empty() {
+        *PENDING_REPORT.lock().unwrap_or_else(|e| e.into_inner()) = Some(report);
+    }
+    Ok(cards)
+}
+
+/// Reads an inventory CSV whose prices are in the given number format,
+/// strictly. Prices are rewritten to plain `1234.56` form, so the rest of
+/// the app can parse them with `str::parse`.
+pub fn read_csv_in(
+    path: &str,
+    locale: DecimalLocale,
+) -> Result<Vec<Card>, Box<dyn std::error::Error>> {
+    read_csv_report(path, locale, CsvLoadMode::Strict).map(|(cards, _)| cards)
+}
+
+/// Reads an inventory CSV in the given number format and load mode,
+/// returning the cards together with what was skipped. Strict mode fails on
+/// the first malformed row with its line number.
+pub fn read_csv_report(
+    path: &str,
+    locale: DecimalLocale,
+    mode: CsvLoadMode,
+) -> Result<(Vec<Card>, CsvLoadReport), Box<dyn std::error::Error>> {
+    info!(
+        "Reading inventory CSV from: {} ({} prices, {} mode)",
+        path,
+        locale,
+        mode.label().to_lowercase()
+    );
+
+    let content = mtg_common::compressed::read_to_string(path)?;
+    let (cards, mut report) = read_cards(&mut content.as_bytes(), locale, mode)?;
+    report.path = path.to_string();
+    Ok((cards, report))
+}
+
+/// Why a parsed row is unusable, if it is: a quantity that is not a whole
+/// number would break every stock count. Prices in an unexpected format are
+/// kept verbatim (see [`normalize_price`]).
+fn validate_card(card: &Card) -> Result<(), String> {
+    let quantity = card.quantity.trim();
+    if quantity.parse::<i64>().is_err() {
+        return Err(format!("quantity '{quantity}' is not a whole number"));
+    }
+    Ok(())
+}
 
+fn read_cards(
+    reader: &mut dyn io::Read,
+    locale: DecimalLocale,
+    mode: CsvLoadMode,
+) -> Result<(Vec<Card>, CsvLoadReport), Box<dyn std::error::Error>> {
     let mut rdr = csv::ReaderBuilder::new()
         .flexible(true)
         .trim(csv::Trim::All)
-        .from_path(path)?;
+        .from_reader(reader);
+    let headers = rdr.headers()?.clone();
 
     let mut cards = Vec::new();
-    let mut skipped_empty = 0;
-    let mut skipped_zero = 0;
-
-    for result in rdr.deserialize() {
-        let card: Card = result?;
-        if card.price.trim().is_empty() || card.quantity.trim().is_empty() {
-            skipped_empty += 1;
-            continue;
-        }
-        // The inventory-report CSV emits rows with quantity 0 both as summary
-        // placeholders and as "last-known shelf" entries for sold-out variants.
-        // Neither represents real inventory and they'd only dilute the DB sync's
-        // representative-picking / zeroing logic, so drop them at the read layer.
-        if card.quantity.trim().parse::<i64>().ok() == Some(0) {
-            skipped_zero += 1;
-            continue;
+    let mut report = CsvLoadReport::default();
+    let mut record = csv::StringRecord::new();
+
+    loop {
+        let row = match rdr.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let line = record.position().map_or(0, |pos| pos.line());
+                record
+                    .deserialize::<Card>(Some(&headers))
+                    .map_err(|e| (line, csv_error_message(&e)))
+                    .map(|card| (line, card))
+            }
+            // A failing reader won't recover; give up in either mode
+            Err(e) if e.is_io_error() => return Err(e.into()),
+            Err(e) => Err((
+                e.position().map_or(0, |pos| pos.line()),
+                csv_error_message(&e),
+            )),
+        };
+        let problem = match row {
+            Ok((line, mut card)) => {
+                normalize_price(&mut card, locale);
+                if card.price.trim().is_empty() || card.quantity.trim().is_empty() {
+                    report.skipped_empty += 1;
+                    continue;
+                }
+                // The inventory-report CSV emits rows with quantity 0 both as summary
+                // placeholders and as "last-known shelf" entries for sold-out variants.
+                // Neither represents real inventory and they'd only dilute the DB sync's
+                // representative-picking / zeroing logic, so drop them at the read layer.
+                if card.quantity.trim().parse::<i64>().ok() == Some(0) {
+                    report.skipped_zero += 1;
+                    continue;
+                }
+                match validate_card(&card) {
+                    Ok(()) => {
+                        cards.push(card);
+                        continue;
+                    }
+                    Err(message) => CsvLineError { line, message },
+                }
+            }
+            Err((line, message)) => CsvLineError { line, message },
+        };
+        if mode == CsvLoadMode::Strict {
+            return Err(format!("line {}: {}", problem.line, problem.message).into());
         }
-        cards.push(card);
+        warn!("Skipping line {}: {}", problem.line, problem.message);
+        report.errors.push(problem);
     }
 
+    report.loaded = cards.len();
     info!(
-        "Loaded {} cards from inventory (skipped {} with empty price/quantity, {} with quantity 0)",
+        "Loaded {} cards from inventory (skipped {} with empty price/quantity, {} with quantity 0, {} malformed)",
         cards.len(),
-        skipped_empty,
-        skipped_zero
+        report.skipped_empty,
+        report.skipped_zero,
+        report.errors.len()
     );
-    Ok(cards)
+    Ok((cards, report))
+}
+
+/// The error without csv's record/byte position prefix; the report has
+/// the line number already.
+fn csv_error_message(error: &csv::Error) -> String {
+    match error.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+        _ => error.to_string(),
+    }
+}
+
+/// Rewrites a localized price (`"1.234,56"`) to `"1234.56"`. Prices that
+/// don't parse are left alone.
+fn normalize_price(card: &mut Card, locale: DecimalLocale) {
+    let Some(price) = parse_decimal(&card.price, locale) else {
+        return;
+    };
+    if card.price.trim().parse::<f64>().ok() != Some(price) {
+        card.price = price.to_string();
+    }
 }
 
 /// Loads a wantslist from either a **deck URL** or a **file path**.
@@ -56,12 +257,18 @@ pub fn load_wantslist(input: &str) -> Result<Vec<WantsEntry>, String> {
 
 /// Reads a wantslist / decklist file and parses it via [`parse_wantslist`],
 /// which understands the common community export formats (plain, Arena, MTGO,
-/// Moxfield, Archidekt, MTGGoldfish). Duplicate card names are merged.
+/// Moxfield, Archidekt, MTGGoldfish), or via [`parse_wantslist_csv`] for CSV
+/// wantslists with printing constraints. Duplicate card names are merged. The
+/// file may be gzipped or zipped.
 pub fn read_wantslist(path: &str) -> Result<Vec<WantsEntry>, io::Error> {
     info!("Reading wantslist from: {}", path);
 
-    let content = std::fs::read_to_string(path)?;
-    let parsed = parse_wantslist(&content);
+    let content = mtg_common::compressed::read_to_string(path)?;
+    let parsed = if is_csv_wantslist(&content) {
+        parse_wantslist_csv(&content).map_err(io::Error::other)?
+    } else {
+        parse_wantslist(&content)
+    };
 
     for line in &parsed.unparseable {
         log::warn!("Could not parse wantslist line: {}", line);
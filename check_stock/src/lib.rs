@@ -1,33 +1,60 @@
 pub mod aging;
 pub mod api;
 pub mod bin_consolidation;
+pub mod bulk_repricing;
 pub mod buy_helper;
 pub mod cache;
 pub mod card_matching;
+pub mod collector_search;
 pub mod consistency;
 pub mod deck_fetch;
+pub mod duplicates;
 pub mod error;
+pub mod exclusions;
+pub mod export_order;
 pub mod formatters;
+pub mod fulfillment;
+pub mod image_preseed;
 pub mod inventory_db;
+pub mod inventory_heatmap;
 pub mod io;
+pub mod listing_photos;
 pub mod mispricing;
 pub mod models;
+pub mod normalization;
+pub mod picking_sessions;
+pub mod playset;
+pub mod price_history_db;
 pub mod price_trends;
+pub mod repricing_guard;
 pub mod restock;
+pub mod set_symbols;
+pub mod slow_queries;
 pub mod stock_analysis;
+pub mod sync_alert;
+pub mod translation;
 pub mod ui;
+pub mod valuation;
 pub mod wantslist;
+pub mod wantslist_optimizer;
 
 // Re-export commonly used items
 pub use api::{fetch_card, PriceGuide, ScryfallCard};
 pub use bin_consolidation::{plan_consolidation, ConsolidationPlan, Move as BinMove};
 pub use cache::{fetch_card_cached, CardCache, ImageCache};
-pub use card_matching::{find_matching_cards, MatchedCard};
+pub use card_matching::{
+    find_matches_for_entry, find_matches_for_wantslist, find_matching_cards, MatchedCard,
+};
 pub use deck_fetch::{fetch_deck, parse_deck_url, DeckSource};
+pub use duplicates::{find_duplicates, format_merge_csv, DuplicateGroup};
 pub use error::{ApiError, ApiResult};
 pub use formatters::{format_picking_list, format_regular_output};
-pub use io::{load_wantslist, read_csv, read_wantslist};
-pub use models::{Card, Language, WantsEntry};
+pub use io::{load_wantslist, read_csv, read_csv_in, read_csv_report, read_wantslist};
+pub use models::{Card, Language, WantsConstraints, WantsEntry};
+pub use playset::{plan_merge, plan_split, PlaysetConversion};
 pub use restock::{format_buy_list_csv, rank_candidates, RankedRestock};
 pub use stock_analysis::{format_stock_analysis_with_sort, SortOrder, StockAnalysis, StockStats};
-pub use wantslist::{parse_wantslist, ParsedLine, WantslistParse};
+pub use wantslist::{
+    is_csv_wantslist, parse_wantslist, parse_wantslist_csv, ParsedLine, WantslistParse,
+};
+pub use wantslist_optimizer::{optimize_wantslist, OptimizationGoal};
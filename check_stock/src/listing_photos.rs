@@ -0,0 +1,106 @@
+//! Photos of individual listings, for condition disputes.
+//!
+//! When a buyer complains about the condition of an expensive card, a photo
+//! taken before shipping settles it. A photo is imported per card variant:
+//! the image is copied into `listing_photos/` next to the inventory database
+//! (so it survives the camera roll being cleared) and its path is recorded
+//! in the database's `listing_photos` table.
+//!
+//! The capture workflow is deliberately low-tech: photograph the card with a
+//! phone or webcam that saves into a folder, then import the file — or take
+//! the newest image in that folder with one click. From the photo window the
+//! image can be opened in the system viewer or its path copied, to attach it
+//! to the reply to the buyer.
+
+use crate::export_order::item_id;
+use crate::inventory_db;
+use crate::models::Card;
+use log::info;
+use std::path::{Path, PathBuf};
+
+/// Folder next to the inventory database that holds the imported photos.
+pub const PHOTOS_DIR: &str = "listing_photos";
+
+/// File extensions accepted as photos.
+pub const IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "png", "webp"];
+
+/// Where imported photos are stored.
+pub fn photos_dir() -> PathBuf {
+    inventory_db::db_dir().join(PHOTOS_DIR)
+}
+
+/// Lowercase extension of `path` if it is one of [`IMAGE_EXTENSIONS`].
+fn image_extension(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    IMAGE_EXTENSIONS.contains(&ext.as_str()).then_some(ext)
+}
+
+/// Copies `source` into `dir` as `<item id>_<timestamp>.<ext>`, so several
+/// photos of one listing never overwrite each other. `now` is
+/// `YYYYMMDD-HHMMSS`.
+pub fn store_photo(card: &Card, source: &Path, dir: &Path, now: &str) -> Result<PathBuf, String> {
+    let ext = image_extension(source).ok_or_else(|| {
+        format!(
+            "{} is not a photo (expected {})",
+            source.display(),
+            IMAGE_EXTENSIONS.join(", ")
+        )
+    })?;
+    image::ImageReader::open(source)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to read {}: {e}", source.display()))?
+        .decode()
+        .map_err(|e| format!("{} is not a readable image: {e}", source.display()))?;
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    let target = dir.join(format!("{}_{now}.{ext}", item_id(card)));
+    std::fs::copy(source, &target)
+        .map_err(|e| format!("Failed to copy photo to {}: {e}", target.display()))?;
+    Ok(target)
+}
+
+/// Imports `source` as the photo of the card's listing and returns the stored copy.
+pub fn import_photo(card: &Card, source: &Path) -> Result<PathBuf, String> {
+    let now = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let stored = store_photo(card, source, &photos_dir(), &now)?;
+    inventory_db::set_listing_photo(card, &stored)
+        .map_err(|e| format!("Failed to record photo: {e}"))?;
+    info!("Stored photo of {} at {}", card.name, stored.display());
+    Ok(stored)
+}
+
+/// The most recently modified photo in `dir`, e.g. the one just taken by a
+/// camera app saving there.
+pub fn newest_image_in(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| image_extension(&entry.path()).is_some())
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)))
+        .map(|(_, path)| path)
+}
+
+/// Opens `path` in the system's default viewer.
+pub fn open_in_default_app(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    command
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+#[path = "listing_photos_tests.rs"]
+mod tests;
@@ -0,0 +1,69 @@
+//! Tests for listing_photos.
+
+use super::*;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+fn write_png(path: &Path) {
+    image::RgbImage::new(2, 2).save(path).unwrap();
+}
+
+#[test]
+fn stores_a_copy_named_after_the_listing() {
+    let camera = TempDir::new().unwrap();
+    let photos = TempDir::new().unwrap();
+    let source = camera.path().join("IMG_0001.PNG");
+    write_png(&source);
+    let card = Card::test_default();
+
+    let stored = store_photo(&card, &source, photos.path(), "20260301-101500").unwrap();
+
+    assert_eq!(
+        stored,
+        photos
+            .path()
+            .join(format!("{}_20260301-101500.png", item_id(&card)))
+    );
+    assert_eq!(
+        std::fs::read(&stored).unwrap(),
+        std::fs::read(&source).unwrap()
+    );
+}
+
+#[test]
+fn rejects_files_that_are_not_photos() {
+    let dir = TempDir::new().unwrap();
+    let text = dir.path().join("notes.txt");
+    std::fs::write(&text, "hello").unwrap();
+    let fake = dir.path().join("broken.jpg");
+    std::fs::write(&fake, "not a jpeg").unwrap();
+    let card = Card::test_default();
+
+    let err = store_photo(&card, &text, dir.path(), "now").unwrap_err();
+    assert!(err.contains("is not a photo"), "{err}");
+    let err = store_photo(&card, &fake, dir.path(), "now").unwrap_err();
+    assert!(err.contains("not a readable image"), "{err}");
+}
+
+#[test]
+fn newest_image_ignores_other_files() {
+    let dir = TempDir::new().unwrap();
+    assert_eq!(newest_image_in(dir.path()), None);
+
+    let now = SystemTime::now();
+    for (name, age) in [("old.jpg", 60), ("new.png", 10), ("newest.txt", 0)] {
+        let path = dir.path().join(name);
+        std::fs::write(&path, "x").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(now - Duration::from_secs(age))
+            .unwrap();
+    }
+
+    assert_eq!(
+        newest_image_in(dir.path()),
+        Some(dir.path().join("new.png"))
+    );
+}
@@ -1,12 +1,180 @@
+use d2d_automations::io::{set_csv_load_mode, CsvLoadMode};
+use mtg_common::config::ConfigArgs;
+
 fn main() {
     // Initialize logger. Set RUST_LOG environment variable to control log level.
     // Examples: RUST_LOG=info, RUST_LOG=warn, RUST_LOG=d2d_automations=trace
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let config_args = match ConfigArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            std::process::exit(2);
+        }
+    };
+    let config = match mtg_common::config::init(config_args.path.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            log::error!("Failed to load config: {e}");
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+    };
+    if config_args.print {
+        print!("{}", config.to_redacted_toml());
+        return;
+    }
+    mtg_common::sql_profile::set_slow_query_threshold(config.sql.slow_query_threshold());
+    // `--lenient-csv` / `--strict-csv` override the config's `lenient_csv`
+    let mut lenient_csv = config.check_stock.lenient_csv;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--lenient-csv" => lenient_csv = true,
+            "--strict-csv" => lenient_csv = false,
+            _ => {}
+        }
+    }
+    set_csv_load_mode(if lenient_csv {
+        CsvLoadMode::Lenient
+    } else {
+        CsvLoadMode::Strict
+    });
+    if let Some(code) = run_bulk_reprice(&config.check_stock.inventory_sync_url) {
+        std::process::exit(code);
+    }
+    if let Some(code) = run_preseed_images() {
+        std::process::exit(code);
+    }
     log::info!("Starting D2D Automations - MTG Stock Checker");
 
-    if let Err(e) = d2d_automations::ui::launch_gui() {
+    let result = d2d_automations::ui::launch_gui();
+    log::info!(
+        "Scryfall rate limiter: {}",
+        d2d_automations::api::rate_limit::scryfall_limiter().stats()
+    );
+    if let Err(e) = result {
         log::error!("Application error: {e}");
         eprintln!("Error: {e}");
         std::process::exit(1);
     }
 }
+
+/// `--preseed-images [--set CODE] [--images-per-second N]`: downloads the
+/// images of every in-stock printing (or one set's) into the image cache, so
+/// picking works offline. Returns the exit code, or `None` to start the GUI
+/// as usual.
+fn run_preseed_images() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.iter().any(|a| a == "--preseed-images") {
+        return None;
+    }
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .map(|i| args.get(i + 1).cloned())
+    };
+    let set = match value_of("--set") {
+        Some(None) => {
+            eprintln!("Error: --set needs a set code");
+            return Some(2);
+        }
+        set => set.flatten(),
+    };
+    let per_second = match value_of("--images-per-second") {
+        None => d2d_automations::image_preseed::DEFAULT_IMAGES_PER_SECOND,
+        Some(value) => match value.and_then(|v| v.parse::<f64>().ok()) {
+            Some(rate) if rate > 0.0 && rate.is_finite() => rate,
+            _ => {
+                eprintln!("Error: --images-per-second needs a positive number");
+                return Some(2);
+            }
+        },
+    };
+    let cards = match d2d_automations::inventory_db::get_in_stock_cards(None) {
+        Ok(cards) => cards,
+        Err(e) => {
+            eprintln!("Error: failed to read the inventory DB: {e}");
+            return Some(1);
+        }
+    };
+    let cache = d2d_automations::ImageCache::new();
+    eprintln!(
+        "Pre-seeding card images into {}",
+        cache.cache_dir().display()
+    );
+    let report = match d2d_automations::image_preseed::preseed_images(
+        &cards,
+        set.as_deref(),
+        &cache,
+        per_second,
+        |done, total| {
+            if done % 100 == 0 || done == total {
+                eprintln!("{done}/{total} images");
+            }
+        },
+    ) {
+        Ok(report) => report,
+        Err(e) => {
+            log::error!("Image pre-seeding failed: {e}");
+            eprintln!("Error: {e}");
+            return Some(1);
+        }
+    };
+    eprintln!("{}", report.summary());
+    for printing in &report.not_in_bulk {
+        eprintln!(
+            "  no Scryfall image: {} #{}",
+            printing.set_code, printing.collector_number
+        );
+    }
+    Some(if report.failed > 0 { 1 } else { 0 })
+}
+
+/// `--reprice-inventory GRAPH.json [--bulk-csv OUT.csv] [--dry-run]`: applies
+/// a saved pricing graph to the whole in-stock inventory without opening the
+/// window. Returns the exit code, or `None` to start the GUI as usual.
+fn run_bulk_reprice(inventory_sync_url: &str) -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let value_of = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .map(|i| args.get(i + 1).cloned())
+    };
+    let graph = value_of("--reprice-inventory")?;
+    let Some(graph) = graph else {
+        eprintln!("Error: --reprice-inventory needs a pricing graph file");
+        return Some(2);
+    };
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let outcome = match d2d_automations::ui::reprice_inventory_from_file(
+        std::path::Path::new(&graph),
+        inventory_sync_url,
+        dry_run,
+    ) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            log::error!("Inventory repricing failed: {e}");
+            eprintln!("Error: {e}");
+            return Some(1);
+        }
+    };
+    match value_of("--bulk-csv").flatten() {
+        Some(path) => {
+            if let Err(e) = std::fs::write(&path, &outcome.csv) {
+                eprintln!("Error: {path}: {e}");
+                return Some(1);
+            }
+        }
+        None => print!("{}", outcome.csv),
+    }
+    eprintln!(
+        "{}{}",
+        d2d_automations::bulk_repricing::summarize(&outcome.changes),
+        if dry_run {
+            " (dry run, inventory DB unchanged)".to_string()
+        } else {
+            format!("; {} written to the inventory DB", outcome.applied)
+        }
+    );
+    Some(0)
+}
@@ -23,7 +23,7 @@
 
 use crate::aging::age_days;
 use crate::inventory_db::InStockCard;
-use crate::models::canonical_condition;
+use crate::models::{canonical_condition, canonical_language};
 use crate::price_trends::pct_change;
 use chrono::NaiveDate;
 
@@ -57,12 +57,12 @@ pub fn condition_factor(condition: &str) -> f64 {
 
 /// True when the (language-blind) market reference likely overstates this
 /// listing's value: copies in languages other than English or German trade at
-/// a discount on the (mostly EU/EN) market. Accepts English and German
-/// spellings and the two-letter codes.
+/// a discount on the (mostly EU/EN) market. Accepts every spelling known to
+/// the normalization table.
 pub fn language_discounted(language: &str) -> bool {
     !matches!(
-        language.trim().to_lowercase().as_str(),
-        "english" | "englisch" | "en" | "german" | "deutsch" | "de"
+        canonical_language(language.trim()).as_str(),
+        "English" | "German"
     )
 }
 
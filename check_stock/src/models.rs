@@ -1,25 +1,38 @@
-use serde::Deserialize;
+use mtg_common::{parse_decimal, DecimalLocale};
+use serde::{Deserialize, Serialize};
 
 /// Maps a CSV condition value (either short form "NM" or long form "near_mint")
 /// to the canonical short form ("NM", "EX", "GD", "LP", "PL", "PO").
 ///
 /// Cardmarket's legacy export used short codes; the inventory report format uses
 /// snake_case full names. Both formats must be supported in filters and sort keys.
-/// Unknown values are returned unchanged (uppercased + trimmed).
+/// Unknown values are returned unchanged (uppercased + trimmed). Aliases come
+/// from the [`crate::normalization`] table, including configured overrides.
 pub fn canonical_condition(s: &str) -> String {
-    let trimmed = s.trim().to_lowercase();
-    let trimmed = trimmed.replace([' ', '-'], "_");
-    match trimmed.as_str() {
-        "nm" | "mint" | "m" | "near_mint" => "NM".to_string(),
-        "ex" | "excellent" => "EX".to_string(),
-        "gd" | "good" => "GD".to_string(),
-        "lp" | "light_played" | "lightly_played" => "LP".to_string(),
-        "pl" | "played" => "PL".to_string(),
-        "po" | "poor" => "PO".to_string(),
-        _ => s.trim().to_uppercase(),
+    crate::normalization::table().condition(s)
+}
+
+/// Rank of a condition on the Cardmarket scale, best first (`NM` = 0 …
+/// `PO` = 5). `None` for unrecognized conditions.
+pub fn condition_rank(condition: &str) -> Option<u8> {
+    match canonical_condition(condition).as_str() {
+        "NM" => Some(0),
+        "EX" => Some(1),
+        "GD" => Some(2),
+        "LP" => Some(3),
+        "PL" => Some(4),
+        "PO" => Some(5),
+        _ => None,
     }
 }
 
+/// Maps a language value ("english", "en", "Deutsch", "Inglés", …) to
+/// Cardmarket's English name ("English", "German", …). Unknown values pass
+/// through unchanged.
+pub fn canonical_language(s: &str) -> String {
+    crate::normalization::table().language(s)
+}
+
 /// Represents the supported card languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Language {
@@ -77,9 +90,12 @@ impl Language {
         }
     }
 
-    /// Parse either a language code or full name into a Language
+    /// Parse either a language code or full name into a Language; localized
+    /// names are resolved through [`canonical_language`]
     pub fn parse(s: &str) -> Option<Self> {
-        Self::from_code(s).or_else(|| Self::from_full_name(s))
+        Self::from_code(s)
+            .or_else(|| Self::from_full_name(s))
+            .or_else(|| Self::from_full_name(&canonical_language(s)))
     }
 
     /// Returns all supported languages
@@ -94,7 +110,7 @@ impl Language {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Card {
     #[serde(rename = "cardmarketId")]
     pub cardmarket_id: String,
@@ -185,9 +201,10 @@ impl Card {
         conditions
     }
 
-    /// Parse the price as f64, returning 0.0 if parsing fails
+    /// Parse the price as f64 (either decimal separator), returning 0.0 if
+    /// parsing fails
     pub fn price_f64(&self) -> f64 {
-        self.price.parse::<f64>().unwrap_or(0.0)
+        parse_decimal(&self.price, DecimalLocale::Auto).unwrap_or(0.0)
     }
 }
 
@@ -223,10 +240,65 @@ impl Card {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct WantsEntry {
     pub quantity: i32,
     pub name: String,
+    /// Printing and listing requirements from a CSV wantslist; empty for the
+    /// plain text formats, where any printing matches
+    pub constraints: WantsConstraints,
+}
+
+impl WantsEntry {
+    /// An entry any printing of `name` satisfies.
+    pub fn new(quantity: i32, name: impl Into<String>) -> Self {
+        Self {
+            quantity,
+            name: name.into(),
+            constraints: WantsConstraints::default(),
+        }
+    }
+}
+
+/// Requirements a listing must meet to fill a wantslist entry; unset fields
+/// accept anything.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WantsConstraints {
+    /// Set code, e.g. `2XM` (case-insensitive)
+    pub set_code: Option<String>,
+    /// Collector number within the set; leading zeros are ignored
+    pub collector_number: Option<String>,
+    /// Listing language, e.g. `English` or `de`
+    pub language: Option<String>,
+    /// Worst acceptable condition, e.g. `EX` accepts NM and EX
+    pub min_condition: Option<String>,
+    /// Highest acceptable price per copy, in EUR
+    pub max_price: Option<f64>,
+}
+
+impl WantsConstraints {
+    /// Whether `card` meets every requirement that is set.
+    pub fn matches(&self, card: &Card) -> bool {
+        let same_number = |wanted: &str| {
+            let trim = |cn: &str| cn.trim().trim_start_matches('0').to_lowercase();
+            trim(wanted) == trim(&card.cn)
+        };
+        self.set_code
+            .as_deref()
+            .is_none_or(|set| set.trim().eq_ignore_ascii_case(card.set_code.trim()))
+            && self.collector_number.as_deref().is_none_or(same_number)
+            && self.language.as_deref().is_none_or(|lang| {
+                canonical_language(lang).eq_ignore_ascii_case(&canonical_language(&card.language))
+            })
+            && self.min_condition.as_deref().is_none_or(|min| {
+                condition_rank(&card.condition)
+                    .zip(condition_rank(min))
+                    .is_some_and(|(rank, worst)| rank <= worst)
+            })
+            && self
+                .max_price
+                .is_none_or(|max| crate::wantslist_optimizer::copy_price(card) <= max + 1e-9)
+    }
 }
 
 #[cfg(test)]
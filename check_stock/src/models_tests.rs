@@ -282,6 +282,13 @@ fn test_card_price_f64_integer() {
     assert!((card.price_f64() - 100.0).abs() < 0.001);
 }
 
+#[test]
+fn test_card_price_f64_decimal_comma() {
+    let mut card = create_test_card();
+    card.price = "1.234,50".to_string();
+    assert!((card.price_f64() - 1234.50).abs() < 0.001);
+}
+
 #[test]
 fn test_card_price_f64_invalid() {
     let mut card = create_test_card();
@@ -0,0 +1,258 @@
+//! Condition and language normalization table.
+//!
+//! Cardmarket writes the same condition as `NM`, `near_mint` or `Near Mint`
+//! and the same language as `English`, `english`, `en` or — in exports made
+//! with a German, French, Spanish or Italian site language — `Englisch`,
+//! `Anglais`, `Inglés`. Every place that compares, groups or stores
+//! conditions and languages goes through [`canonical_condition`] and
+//! [`canonical_language`], which look the value up here, so breakdowns and
+//! variant keys never split one condition or language into two buckets.
+//!
+//! Canonical values are Cardmarket's short condition codes (`NM`, `EX`, `GD`,
+//! `LP`, `PL`, `PO`) and its English language names (`English`, `German`,
+//! `S-Chinese`, …). Mint (`MT`) counts as `NM` by default.
+//!
+//! Extra aliases can be configured in `normalization.json` next to the
+//! inventory database, e.g.
+//!
+//! ```json
+//! { "conditions": { "MT": "MT" }, "languages": { "Englisch (UK)": "English" } }
+//! ```
+//!
+//! Overrides win over the built-in table. Lookups ignore case, surrounding
+//! whitespace and the difference between spaces, hyphens and underscores.
+//!
+//! [`canonical_condition`]: crate::models::canonical_condition
+//! [`canonical_language`]: crate::models::canonical_language
+
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Built-in condition aliases: canonical code, then every spelling of it.
+const CONDITIONS: &[(&str, &[&str])] = &[
+    ("NM", &["nm", "mint", "m", "mt", "near_mint"]),
+    ("EX", &["ex", "excellent"]),
+    ("GD", &["gd", "good"]),
+    ("LP", &["lp", "light_played", "lightly_played"]),
+    ("PL", &["pl", "played"]),
+    ("PO", &["po", "poor"]),
+];
+
+/// Built-in language aliases: Cardmarket's English name, then codes and the
+/// names used by the German, French, Spanish and Italian site versions.
+const LANGUAGES: &[(&str, &[&str])] = &[
+    (
+        "English",
+        &[
+            "en", "english", "englisch", "anglais", "inglés", "ingles", "inglese",
+        ],
+    ),
+    (
+        "German",
+        &[
+            "de", "german", "deutsch", "allemand", "alemán", "aleman", "tedesco",
+        ],
+    ),
+    (
+        "French",
+        &[
+            "fr",
+            "french",
+            "französisch",
+            "franzosisch",
+            "français",
+            "francais",
+            "francés",
+            "frances",
+            "francese",
+        ],
+    ),
+    (
+        "Spanish",
+        &[
+            "es", "spanish", "spanisch", "espagnol", "español", "espanol", "spagnolo",
+        ],
+    ),
+    (
+        "Italian",
+        &["it", "italian", "italienisch", "italien", "italiano"],
+    ),
+    (
+        "Portuguese",
+        &[
+            "pt",
+            "portuguese",
+            "portugiesisch",
+            "portugais",
+            "portugués",
+            "portoghese",
+        ],
+    ),
+    (
+        "Japanese",
+        &[
+            "ja",
+            "jp",
+            "japanese",
+            "japanisch",
+            "japonais",
+            "japonés",
+            "giapponese",
+        ],
+    ),
+    (
+        "Korean",
+        &["ko", "kr", "korean", "koreanisch", "coréen", "coreano"],
+    ),
+    (
+        "Russian",
+        &["ru", "russian", "russisch", "russe", "ruso", "russo"],
+    ),
+    (
+        "S-Chinese",
+        &[
+            "zhs",
+            "zh_cn",
+            "s_chinese",
+            "simplified_chinese",
+            "chinese_simplified",
+        ],
+    ),
+    (
+        "T-Chinese",
+        &[
+            "zht",
+            "zh_tw",
+            "t_chinese",
+            "traditional_chinese",
+            "chinese_traditional",
+        ],
+    ),
+];
+
+/// File name of the override table, in the inventory database's directory.
+pub const NORMALIZATION_FILE: &str = "normalization.json";
+
+/// User-configured aliases, alias → canonical value.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct NormalizationOverrides {
+    pub conditions: HashMap<String, String>,
+    pub languages: HashMap<String, String>,
+}
+
+/// Alias lookup for conditions and languages.
+#[derive(Debug, Clone)]
+pub struct NormalizationTable {
+    conditions: HashMap<String, String>,
+    languages: HashMap<String, String>,
+}
+
+/// Lowercases and unifies separators, so `Near Mint`, `near-mint` and
+/// `NEAR_MINT` are one key.
+fn fold(s: &str) -> String {
+    s.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+fn build(table: &[(&str, &[&str])]) -> HashMap<String, String> {
+    table
+        .iter()
+        .flat_map(|(canonical, aliases)| {
+            aliases
+                .iter()
+                .map(|alias| (fold(alias), canonical.to_string()))
+                .chain(std::iter::once((fold(canonical), canonical.to_string())))
+        })
+        .collect()
+}
+
+impl NormalizationTable {
+    pub fn builtin() -> Self {
+        Self {
+            conditions: build(CONDITIONS),
+            languages: build(LANGUAGES),
+        }
+    }
+
+    /// Adds the configured aliases on top of this table; their canonical
+    /// values are used as written (trimmed).
+    pub fn with_overrides(mut self, overrides: NormalizationOverrides) -> Self {
+        for (alias, canonical) in overrides.conditions {
+            self.conditions
+                .insert(fold(&alias), canonical.trim().to_string());
+        }
+        for (alias, canonical) in overrides.languages {
+            self.languages
+                .insert(fold(&alias), canonical.trim().to_string());
+        }
+        self
+    }
+
+    /// Built-in table plus the overrides in `path`. A missing file means no
+    /// overrides; an unreadable one is logged and ignored, since a typo in
+    /// the config shouldn't stop the app from loading stock.
+    pub fn load(path: &Path) -> Self {
+        let table = Self::builtin();
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return table,
+            Err(e) => {
+                warn!("Failed to read {}: {e}", path.display());
+                return table;
+            }
+        };
+        match serde_json::from_str::<NormalizationOverrides>(&content) {
+            Ok(overrides) => {
+                info!(
+                    "Loaded {} condition and {} language aliases from {}",
+                    overrides.conditions.len(),
+                    overrides.languages.len(),
+                    path.display()
+                );
+                table.with_overrides(overrides)
+            }
+            Err(e) => {
+                warn!("Ignoring invalid {}: {e}", path.display());
+                table
+            }
+        }
+    }
+
+    /// Canonical condition code; unknown values are trimmed and uppercased.
+    pub fn condition(&self, s: &str) -> String {
+        self.conditions
+            .get(&fold(s))
+            .cloned()
+            .unwrap_or_else(|| s.trim().to_uppercase())
+    }
+
+    /// Canonical language name; unknown values pass through unchanged so no
+    /// data is silently rewritten.
+    pub fn language(&self, s: &str) -> String {
+        self.languages
+            .get(&fold(s))
+            .cloned()
+            .unwrap_or_else(|| s.to_string())
+    }
+}
+
+/// Path of the override file: `~/.local/share/d2d_automations/normalization.json`.
+pub fn overrides_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("d2d_automations")
+        .join(NORMALIZATION_FILE)
+}
+
+/// The table used by the whole app, loaded once on first use.
+pub fn table() -> &'static NormalizationTable {
+    static TABLE: OnceLock<NormalizationTable> = OnceLock::new();
+    TABLE.get_or_init(|| NormalizationTable::load(&overrides_path()))
+}
+
+#[cfg(test)]
+#[path = "normalization_tests.rs"]
+mod tests;
@@ -0,0 +1,70 @@
+//! Tests for normalization.
+
+use super::*;
+
+#[test]
+fn condition_spellings_share_one_code() {
+    let table = NormalizationTable::builtin();
+    for spelling in [
+        "NM",
+        "nm",
+        "near_mint",
+        "Near Mint",
+        "near-mint",
+        " MT ",
+        "Mint",
+    ] {
+        assert_eq!(table.condition(spelling), "NM", "{spelling}");
+    }
+    assert_eq!(table.condition("Lightly Played"), "LP");
+    assert_eq!(table.condition("poor"), "PO");
+    assert_eq!(table.condition(" damaged "), "DAMAGED");
+}
+
+#[test]
+fn localized_language_names_resolve() {
+    let table = NormalizationTable::builtin();
+    for spelling in [
+        "English", "english", "EN", "Englisch", "Anglais", "Inglés", "inglese",
+    ] {
+        assert_eq!(table.language(spelling), "English", "{spelling}");
+    }
+    assert_eq!(table.language("Deutsch"), "German");
+    assert_eq!(table.language("Japanisch"), "Japanese");
+    assert_eq!(table.language("s-chinese"), "S-Chinese");
+    assert_eq!(table.language("Klingon"), "Klingon");
+}
+
+#[test]
+fn overrides_add_and_replace_aliases() {
+    let overrides: NormalizationOverrides = serde_json::from_str(
+        r#"{ "conditions": { "MT": "MT", "Sehr gut": "EX" },
+             "languages": { "Englisch (UK)": "English" } }"#,
+    )
+    .unwrap();
+    let table = NormalizationTable::builtin().with_overrides(overrides);
+
+    assert_eq!(table.condition("mt"), "MT");
+    assert_eq!(table.condition("sehr gut"), "EX");
+    assert_eq!(table.condition("mint"), "NM", "other aliases unchanged");
+    assert_eq!(table.language("englisch (uk)"), "English");
+}
+
+#[test]
+fn load_falls_back_to_builtin() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let missing = NormalizationTable::load(&dir.path().join(NORMALIZATION_FILE));
+    assert_eq!(missing.language("Deutsch"), "German");
+
+    let path = dir.path().join("broken.json");
+    std::fs::write(&path, "{ not json").unwrap();
+    let broken = NormalizationTable::load(&path);
+    assert_eq!(broken.condition("excellent"), "EX");
+
+    let path = dir.path().join("partial.json");
+    std::fs::write(&path, r#"{ "languages": { "EN-GB": "English" } }"#).unwrap();
+    let partial = NormalizationTable::load(&path);
+    assert_eq!(partial.language("en-gb"), "English");
+    assert_eq!(partial.condition("near_mint"), "NM");
+}
@@ -0,0 +1,244 @@
+//! Saved picking sessions.
+//!
+//! Every interactive picking session is saved locally as it progresses, so an
+//! interrupted pick can be reopened where it stopped and a recurring one (a
+//! weekly store order) can be duplicated with its picks reset. All sessions
+//! live in one JSON file, `~/.local/share/d2d_automations/picking_sessions.json`,
+//! newest first.
+//!
+//! Valuable orders can get a second pass before shipping: another person
+//! checks every picked item against the card and records a [`Verification`].
+
+use crate::models::Card;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File the sessions are stored in, under the app's data directory.
+pub const SESSIONS_FILE: &str = "picking_sessions.json";
+
+/// One item of a saved session, with its pick state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPickItem {
+    /// The inventory listing the item is picked from
+    pub card: Card,
+    pub set_name: String,
+    pub quantity: i32,
+    pub picked: bool,
+    #[serde(default)]
+    pub picked_by: String,
+    #[serde(default)]
+    pub picked_at: Option<String>,
+    /// Short-pick reason as in the pick log (`missing`, `damaged`,
+    /// `wrong_location`), with the copies not picked
+    #[serde(default)]
+    pub short_pick: Option<(String, i32)>,
+    #[serde(default)]
+    pub verification: Option<Verification>,
+}
+
+/// A second person's check of a picked item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Verification {
+    pub verified_by: String,
+    /// Local time (`YYYY-MM-DD HH:MM:SS`) of the check
+    pub verified_at: String,
+    /// `false` when the pile held the wrong card, printing or count
+    pub correct: bool,
+}
+
+impl SavedPickItem {
+    /// Copies picked; short-picked copies don't count.
+    pub fn picked_quantity(&self) -> i32 {
+        if !self.picked {
+            return 0;
+        }
+        self.quantity - self.short_pick.as_ref().map_or(0, |(_, missing)| *missing)
+    }
+}
+
+/// A picking session as saved on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// Unique per session; derived from the creation time
+    pub id: String,
+    /// Wantslist (or search) the session was started from
+    pub name: String,
+    /// Local time (`YYYY-MM-DD HH:MM:SS`) the session was started
+    pub created_at: String,
+    /// Local time of the last pick or change
+    pub updated_at: String,
+    #[serde(default)]
+    pub picker_name: String,
+    /// Set once the picks were removed from inventory
+    #[serde(default)]
+    pub archived: bool,
+    pub items: Vec<SavedPickItem>,
+}
+
+impl SavedSession {
+    /// A new, unpicked session named `name`.
+    pub fn new(name: &str, items: Vec<SavedPickItem>, now: &str) -> Self {
+        Self {
+            id: session_id(now),
+            name: name.to_string(),
+            created_at: now.to_string(),
+            updated_at: now.to_string(),
+            picker_name: String::new(),
+            archived: false,
+            items,
+        }
+    }
+
+    /// Items picked (fully or short) and the total item count.
+    pub fn progress(&self) -> (usize, usize) {
+        let picked = self.items.iter().filter(|i| i.picked).count();
+        (picked, self.items.len())
+    }
+
+    /// Value of the whole session at the listed prices.
+    pub fn total_value(&self) -> f64 {
+        self.items
+            .iter()
+            .map(|i| i.card.price_f64() * i.quantity as f64)
+            .sum()
+    }
+
+    /// Value of the copies picked so far.
+    pub fn picked_value(&self) -> f64 {
+        self.items
+            .iter()
+            .map(|i| i.card.price_f64() * i.picked_quantity() as f64)
+            .sum()
+    }
+
+    /// Whether every item has been picked.
+    pub fn is_complete(&self) -> bool {
+        let (picked, total) = self.progress();
+        total > 0 && picked == total
+    }
+
+    /// A fresh copy for picking the same cards again: new id and
+    /// timestamps, every pick, check and the archive flag cleared.
+    pub fn duplicate(&self, now: &str) -> Self {
+        let items = self
+            .items
+            .iter()
+            .map(|item| SavedPickItem {
+                picked: false,
+                picked_by: String::new(),
+                picked_at: None,
+                short_pick: None,
+                verification: None,
+                ..item.clone()
+            })
+            .collect();
+        Self {
+            picker_name: self.picker_name.clone(),
+            ..Self::new(&format!("{} (copy)", self.name), items, now)
+        }
+    }
+}
+
+/// An id from a timestamp, e.g. `20260314-093012`; [`add_session`] makes
+/// it unique.
+fn session_id(now: &str) -> String {
+    let digits: String = now.chars().filter(char::is_ascii_digit).collect();
+    match digits.split_at_checked(8) {
+        Some((date, time)) if !time.is_empty() => format!("{date}-{time}"),
+        _ => digits,
+    }
+}
+
+/// Adds a new session, suffixing its id if another session started in the
+/// same second. Returns the id it was stored under.
+pub fn add_session(sessions: &mut Vec<SavedSession>, mut session: SavedSession) -> String {
+    let base = session.id.clone();
+    let mut n = 2;
+    while sessions.iter().any(|s| s.id == session.id) {
+        session.id = format!("{base}-{n}");
+        n += 1;
+    }
+    let id = session.id.clone();
+    sessions.push(session);
+    sort_sessions(sessions);
+    id
+}
+
+/// Replaces the saved session with the same id, or adds it if it was
+/// deleted in the meantime.
+pub fn replace_session(sessions: &mut Vec<SavedSession>, session: SavedSession) {
+    match sessions.iter_mut().find(|s| s.id == session.id) {
+        Some(existing) => *existing = session,
+        None => sessions.push(session),
+    }
+    sort_sessions(sessions);
+}
+
+/// Removes the session with `id`. Returns whether one was removed.
+pub fn delete_session(sessions: &mut Vec<SavedSession>, id: &str) -> bool {
+    let before = sessions.len();
+    sessions.retain(|s| s.id != id);
+    sessions.len() != before
+}
+
+fn sort_sessions(sessions: &mut [SavedSession]) {
+    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+}
+
+/// Reads the saved sessions. A missing file means none; an unreadable one
+/// is an error, so it isn't overwritten by the next save.
+pub fn load_sessions(path: &Path) -> Result<Vec<SavedSession>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+    };
+    let mut sessions: Vec<SavedSession> = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid sessions file {}: {e}", path.display()))?;
+    sort_sessions(&mut sessions);
+    info!(
+        "Loaded {} picking sessions from {}",
+        sessions.len(),
+        path.display()
+    );
+    Ok(sessions)
+}
+
+/// Writes all sessions, replacing the file.
+pub fn save_sessions(path: &Path, sessions: &[SavedSession]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(sessions)
+        .map_err(|e| format!("Failed to serialize picking sessions: {e}"))?;
+    std::fs::write(path, json).map_err(|e| {
+        warn!("Failed to save picking sessions: {e}");
+        format!("Failed to write {}: {e}", path.display())
+    })
+}
+
+/// Loads the sessions, applies `update` and writes them back. Returns the
+/// updated list.
+pub fn update_sessions<T>(
+    path: &Path,
+    update: impl FnOnce(&mut Vec<SavedSession>) -> T,
+) -> Result<(Vec<SavedSession>, T), String> {
+    let mut sessions = load_sessions(path)?;
+    let result = update(&mut sessions);
+    save_sessions(path, &sessions)?;
+    Ok((sessions, result))
+}
+
+/// Path of the sessions file: `~/.local/share/d2d_automations/picking_sessions.json`.
+pub fn sessions_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("d2d_automations")
+        .join(SESSIONS_FILE)
+}
+
+#[cfg(test)]
+#[path = "picking_sessions_tests.rs"]
+mod tests;
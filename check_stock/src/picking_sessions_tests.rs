@@ -0,0 +1,106 @@
+//! Tests for saved picking sessions.
+
+use super::*;
+
+fn item(name: &str, price: &str, quantity: i32) -> SavedPickItem {
+    SavedPickItem {
+        card: Card {
+            name: name.to_string(),
+            price: price.to_string(),
+            ..Card::test_default()
+        },
+        set_name: "Alpha".to_string(),
+        quantity,
+        picked: false,
+        picked_by: String::new(),
+        picked_at: None,
+        short_pick: None,
+        verification: None,
+    }
+}
+
+fn session(name: &str, now: &str) -> SavedSession {
+    let mut picked = item("Bolt", "2.00", 4);
+    picked.picked = true;
+    picked.picked_by = "AB".to_string();
+    picked.short_pick = Some(("missing".to_string(), 1));
+    picked.verification = Some(Verification {
+        verified_by: "CD".to_string(),
+        verified_at: "2026-03-14 10:02:00".to_string(),
+        correct: true,
+    });
+    SavedSession::new(name, vec![picked, item("Lotus", "10.00", 1)], now)
+}
+
+#[test]
+fn summarizes_progress_and_value() {
+    let s = session("weekly.txt", "2026-03-14 09:30:12");
+
+    assert_eq!(s.id, "20260314-093012");
+    assert_eq!(s.progress(), (1, 2));
+    assert_eq!(s.total_value(), 18.0);
+    assert_eq!(s.picked_value(), 6.0);
+    assert!(!s.is_complete());
+}
+
+#[test]
+fn duplicate_resets_picks() {
+    let s = session("weekly.txt", "2026-03-14 09:30:12");
+
+    let copy = s.duplicate("2026-03-21 08:00:00");
+
+    assert_eq!(copy.name, "weekly.txt (copy)");
+    assert_eq!(copy.id, "20260321-080000");
+    assert_eq!(copy.progress(), (0, 2));
+    assert!(copy.items.iter().all(|i| i.short_pick.is_none()));
+    assert!(copy.items.iter().all(|i| i.verification.is_none()));
+    assert_eq!(copy.items[0].card, s.items[0].card);
+}
+
+#[test]
+fn ids_stay_unique_and_newest_comes_first() {
+    let mut sessions = Vec::new();
+    let first = add_session(&mut sessions, session("a", "2026-03-14 09:30:12"));
+    let second = add_session(&mut sessions, session("b", "2026-03-14 09:30:12"));
+    add_session(&mut sessions, session("c", "2026-03-15 10:00:00"));
+
+    assert_eq!(first, "20260314-093012");
+    assert_eq!(second, "20260314-093012-2");
+    assert_eq!(sessions[0].name, "c");
+
+    let mut updated = sessions[2].clone();
+    updated.updated_at = "2026-03-16 12:00:00".to_string();
+    replace_session(&mut sessions, updated);
+    assert_eq!(sessions.len(), 3);
+    assert_eq!(sessions[0].updated_at, "2026-03-16 12:00:00");
+
+    assert!(delete_session(&mut sessions, &second));
+    assert!(!delete_session(&mut sessions, &second));
+    assert_eq!(sessions.len(), 2);
+}
+
+#[test]
+fn sessions_round_trip_through_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join(SESSIONS_FILE);
+    assert!(load_sessions(&path).unwrap().is_empty());
+
+    let (saved, id) = update_sessions(&path, |sessions| {
+        add_session(sessions, session("weekly.txt", "2026-03-14 09:30:12"))
+    })
+    .unwrap();
+
+    assert_eq!(id, "20260314-093012");
+    assert_eq!(load_sessions(&path).unwrap(), saved);
+}
+
+#[test]
+fn unreadable_file_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(SESSIONS_FILE);
+    std::fs::write(&path, "not json").unwrap();
+
+    assert!(load_sessions(&path).is_err());
+    assert!(update_sessions(&path, |_| ()).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "not json");
+}
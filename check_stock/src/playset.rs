@@ -0,0 +1,211 @@
+//! Playset split/merge — converting between playset and single listings.
+//!
+//! Cardmarket lets a seller list four copies as one *playset* listing (quantity
+//! counts playsets, price is per playset). Relisting playsets as singles or
+//! bundling four singles into a playset is a manual chore; this module plans
+//! the conversion for one listing at a time. Because a conversion acts on a
+//! single listing, the result always keeps that listing's condition, language,
+//! foil and signed flags — copies of different variants are never merged.
+//!
+//! The module is pure: [`format_playset_conversion_csv`] renders the plan as a
+//! stock-update CSV and `inventory_db::apply_playset_conversions` applies the
+//! same plan to the inventory DB so the next sync sees no phantom sale.
+//! [`apply_conversion_to_listings`] reflects it in an already loaded list.
+
+use crate::models::Card;
+
+/// Copies in one playset.
+pub const PLAYSET_SIZE: i64 = 4;
+
+/// One listing converted from playsets to singles or vice versa.
+#[derive(Debug, Clone)]
+pub struct PlaysetConversion {
+    /// The listing as it is today.
+    pub source: Card,
+    /// Listings removed from `source` (playsets for a split, singles for a merge).
+    pub removed: i64,
+    /// The replacement listing: same variant, flipped playset flag, converted price.
+    pub target: Card,
+    /// Listings added as `target`.
+    pub added: i64,
+}
+
+impl PlaysetConversion {
+    /// Net change in listing count for the card's variant (positive for a split).
+    pub fn listing_delta(&self) -> i64 {
+        self.added - self.removed
+    }
+}
+
+/// Writes the playset flag in the same style the source CSV uses (`1`/`0` or
+/// `true`/`false`), so exported rows look like the rows they replace. Falls
+/// back to the foil column's style when the playset column is empty.
+fn playset_flag_like(card: &Card, value: bool) -> String {
+    let reference = card
+        .is_playset
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(&card.is_foil);
+    let numeric = matches!(reference.trim(), "0" | "1");
+    match (numeric, value) {
+        (true, true) => "1".to_string(),
+        (true, false) => "0".to_string(),
+        (false, true) => "true".to_string(),
+        (false, false) => "false".to_string(),
+    }
+}
+
+/// Formats a price with two decimals, as Cardmarket expects.
+fn format_price(price: f64) -> String {
+    format!("{:.2}", (price * 100.0).round() / 100.0)
+}
+
+/// Plans splitting `playsets` playset listings of `card` into singles.
+///
+/// Returns `None` if the card is not a playset listing or `playsets` is not
+/// positive. The single price is the playset price divided by four.
+pub fn plan_split(card: &Card, playsets: i64) -> Option<PlaysetConversion> {
+    if !card.is_playset_card() || playsets <= 0 {
+        return None;
+    }
+    let mut target = card.clone();
+    target.is_playset = Some(playset_flag_like(card, false));
+    target.price = format_price(card.price_f64() / PLAYSET_SIZE as f64);
+    target.quantity = (playsets * PLAYSET_SIZE).to_string();
+    Some(PlaysetConversion {
+        source: card.clone(),
+        removed: playsets,
+        target,
+        added: playsets * PLAYSET_SIZE,
+    })
+}
+
+/// Plans merging up to `singles` single listings of `card` into playsets.
+///
+/// Only whole playsets are formed: a remainder below four stays listed as
+/// singles. Returns `None` if the card already is a playset listing or fewer
+/// than four singles are given. The playset price is four times the single price.
+pub fn plan_merge(card: &Card, singles: i64) -> Option<PlaysetConversion> {
+    if card.is_playset_card() {
+        return None;
+    }
+    let playsets = singles / PLAYSET_SIZE;
+    if playsets <= 0 {
+        return None;
+    }
+    let mut target = card.clone();
+    target.is_playset = Some(playset_flag_like(card, true));
+    target.price = format_price(card.price_f64() * PLAYSET_SIZE as f64);
+    target.quantity = playsets.to_string();
+    Some(PlaysetConversion {
+        source: card.clone(),
+        removed: playsets * PLAYSET_SIZE,
+        target,
+        added: playsets,
+    })
+}
+
+/// Generates a stock-update CSV for a set of conversions.
+///
+/// Each conversion becomes two rows: the source listing with a negative
+/// quantity (removing it) and the target listing with a positive quantity
+/// (adding it). Columns follow the inventory-report schema plus `isPlayset`.
+pub fn format_playset_conversion_csv(conversions: &[PlaysetConversion]) -> String {
+    use csv::WriterBuilder;
+
+    let mut wtr = WriterBuilder::new().has_headers(true).from_writer(vec![]);
+
+    let _ = wtr.write_record([
+        "cardmarketId",
+        "quantity",
+        "name",
+        "set",
+        "setCode",
+        "cn",
+        "condition",
+        "language",
+        "isFoil",
+        "isPlayset",
+        "isSigned",
+        "price",
+        "comment",
+        "location",
+        "rarity",
+    ]);
+
+    for conversion in conversions {
+        for (card, quantity) in [
+            (&conversion.source, -conversion.removed),
+            (&conversion.target, conversion.added),
+        ] {
+            let quantity = quantity.to_string();
+            let _ = wtr.write_record([
+                &card.cardmarket_id,
+                &quantity,
+                &card.name,
+                &card.set,
+                &card.set_code,
+                &card.cn,
+                &card.condition,
+                &card.language,
+                &card.is_foil,
+                card.is_playset.as_deref().unwrap_or(""),
+                &card.is_signed,
+                &card.price,
+                &card.comment,
+                card.location.as_deref().unwrap_or(""),
+                &card.rarity,
+            ]);
+        }
+    }
+
+    let data = wtr.into_inner().unwrap();
+    String::from_utf8(data).unwrap()
+}
+
+/// Whether two rows describe the same listing: same variant, playset flag
+/// and location.
+fn same_listing(a: &Card, b: &Card) -> bool {
+    a.cardmarket_id == b.cardmarket_id
+        && a.condition == b.condition
+        && a.language == b.language
+        && a.is_foil == b.is_foil
+        && a.is_signed == b.is_signed
+        && a.is_playset_card() == b.is_playset_card()
+        && a.location == b.location
+}
+
+/// Applies a conversion to loaded listing rows: the source row shrinks by the
+/// removed listings and is dropped once empty; the converted listings are
+/// added to the matching target row, or as a new row after the source if the
+/// list has none yet. Does nothing if the source row isn't in `cards`.
+pub fn apply_conversion_to_listings(cards: &mut Vec<Card>, conversion: &PlaysetConversion) {
+    let Some(source) = cards
+        .iter()
+        .position(|c| same_listing(c, &conversion.source))
+    else {
+        return;
+    };
+    let remaining =
+        (cards[source].quantity.parse::<i64>().unwrap_or(0) - conversion.removed).max(0);
+    cards[source].quantity = remaining.to_string();
+
+    match cards
+        .iter()
+        .position(|c| same_listing(c, &conversion.target))
+    {
+        Some(target) => {
+            let current: i64 = cards[target].quantity.parse().unwrap_or(0);
+            cards[target].quantity = (current + conversion.added).to_string();
+        }
+        None => cards.insert(source + 1, conversion.target.clone()),
+    }
+
+    if remaining == 0 {
+        cards.remove(source);
+    }
+}
+
+#[cfg(test)]
+#[path = "playset_tests.rs"]
+mod tests;
@@ -0,0 +1,146 @@
+//! Tests for playset.
+
+use super::*;
+
+fn playset(price: &str) -> Card {
+    Card {
+        is_playset: Some("1".to_string()),
+        is_foil: "0".to_string(),
+        price: price.to_string(),
+        quantity: "2".to_string(),
+        location: Some("A1_S1_R1_C1".to_string()),
+        ..Card::test_default()
+    }
+}
+
+fn single(price: &str, qty: &str) -> Card {
+    Card {
+        price: price.to_string(),
+        quantity: qty.to_string(),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn split_turns_playsets_into_four_singles_each() {
+    let card = playset("10.00");
+    let conv = plan_split(&card, 2).unwrap();
+
+    assert_eq!(conv.removed, 2);
+    assert_eq!(conv.added, 8);
+    assert_eq!(conv.listing_delta(), 6);
+    assert_eq!(conv.target.price, "2.50");
+    assert_eq!(conv.target.quantity, "8");
+    assert!(!conv.target.is_playset_card());
+    assert_eq!(conv.target.is_playset.as_deref(), Some("0"));
+}
+
+#[test]
+fn split_keeps_variant_and_location() {
+    let mut card = playset("4.00");
+    card.condition = "EX".to_string();
+    card.language = "German".to_string();
+    let conv = plan_split(&card, 1).unwrap();
+
+    assert_eq!(conv.target.cardmarket_id, card.cardmarket_id);
+    assert_eq!(conv.target.condition, "EX");
+    assert_eq!(conv.target.language, "German");
+    assert_eq!(conv.target.is_foil, card.is_foil);
+    assert_eq!(conv.target.location, card.location);
+}
+
+#[test]
+fn split_rejects_singles_and_non_positive_counts() {
+    assert!(plan_split(&single("1.00", "4"), 1).is_none());
+    assert!(plan_split(&playset("4.00"), 0).is_none());
+}
+
+#[test]
+fn split_rounds_price_to_cents() {
+    let conv = plan_split(&playset("1.00"), 1).unwrap();
+    assert_eq!(conv.target.price, "0.25");
+    let conv = plan_split(&playset("0.99"), 1).unwrap();
+    assert_eq!(conv.target.price, "0.25");
+}
+
+#[test]
+fn merge_forms_whole_playsets_only() {
+    let card = single("0.50", "10");
+    let conv = plan_merge(&card, 10).unwrap();
+
+    assert_eq!(conv.removed, 8, "two singles stay listed as singles");
+    assert_eq!(conv.added, 2);
+    assert_eq!(conv.listing_delta(), -6);
+    assert_eq!(conv.target.price, "2.00");
+    assert!(conv.target.is_playset_card());
+}
+
+#[test]
+fn merge_rejects_fewer_than_four_and_playsets() {
+    assert!(plan_merge(&single("1.00", "3"), 3).is_none());
+    assert!(plan_merge(&playset("4.00"), 4).is_none());
+}
+
+#[test]
+fn merge_uses_text_flags_when_csv_does() {
+    // test_default uses "false" for isFoil and no isPlayset column
+    let conv = plan_merge(&single("1.00", "4"), 4).unwrap();
+    assert_eq!(conv.target.is_playset.as_deref(), Some("true"));
+}
+
+#[test]
+fn conversion_csv_removes_source_and_adds_target() {
+    let conv = plan_split(&playset("10.00"), 1).unwrap();
+    let csv = format_playset_conversion_csv(&[conv]);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("cardmarketId,quantity,"));
+    assert!(lines[0].contains("isPlayset"));
+    assert!(lines[1].starts_with("12345,-1,"));
+    assert!(lines[1].contains(",1,") && lines[1].contains("10.00"));
+    assert!(lines[2].starts_with("12345,4,"));
+    assert!(lines[2].contains("2.50"));
+}
+
+#[test]
+fn conversion_csv_empty_has_header_only() {
+    let csv = format_playset_conversion_csv(&[]);
+    assert_eq!(csv.lines().count(), 1);
+}
+
+#[test]
+fn listings_merge_into_existing_target_and_drop_empty_source() {
+    let source = playset("10.00");
+    let mut existing = playset("10.00");
+    existing.is_playset = Some("0".to_string());
+    existing.price = "2.50".to_string();
+    existing.quantity = "3".to_string();
+    let mut cards = vec![source.clone(), existing];
+
+    let conv = plan_split(&source, 2).unwrap();
+    apply_conversion_to_listings(&mut cards, &conv);
+
+    assert_eq!(
+        cards.len(),
+        1,
+        "no duplicate row and no zero-quantity source"
+    );
+    assert!(!cards[0].is_playset_card());
+    assert_eq!(cards[0].quantity, "11");
+}
+
+#[test]
+fn listings_add_new_target_after_partially_converted_source() {
+    let mut cards = vec![single("0.50", "6"), single("9.99", "1")];
+    cards[1].cardmarket_id = "999".to_string();
+
+    let conv = plan_merge(&cards[0].clone(), 6).unwrap();
+    apply_conversion_to_listings(&mut cards, &conv);
+
+    assert_eq!(cards.len(), 3);
+    assert_eq!(cards[0].quantity, "2");
+    assert!(cards[1].is_playset_card());
+    assert_eq!(cards[1].quantity, "1");
+    assert_eq!(cards[2].cardmarket_id, "999");
+}
@@ -0,0 +1,94 @@
+//! Price history read straight from an inventory_sync database.
+//!
+//! When `[check_stock] price_history_db` points at the sync server's SQLite
+//! file (a local copy or a network share), the Search screen charts a card's
+//! history from it instead of asking the server, so the collected prices are
+//! usable without the web UI or a running server. The file is opened
+//! read-only and never migrated; only the `price_history`, `products` and
+//! expansion name tables are read.
+
+use crate::api::inventory_sync::{PriceData, PriceHistoryPoint};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::path::Path;
+
+/// A card's price history, from the database or the sync server.
+#[derive(Debug, Clone, Default)]
+pub struct PriceHistory {
+    /// Expansion name, when the database knows it
+    pub expansion_name: Option<String>,
+    /// Daily price rows, oldest first
+    pub points: Vec<PriceHistoryPoint>,
+}
+
+impl From<PriceData> for PriceHistory {
+    fn from(data: PriceData) -> Self {
+        Self {
+            expansion_name: data.product.expansion_name,
+            points: data.history,
+        }
+    }
+}
+
+/// Loads a product's price rows since `since` (`YYYY-MM-DD`) from the
+/// inventory_sync database at `path`. A product without rows gives an empty
+/// history; a missing file is an error.
+pub fn load_price_history(
+    path: &Path,
+    id_product: u64,
+    since: &str,
+) -> rusqlite::Result<PriceHistory> {
+    let mut conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    crate::slow_queries::instrument(&mut conn, path);
+
+    let expansion_name: Option<String> = conn
+        .query_row(
+            "SELECT COALESCE(x.name, e.name)
+             FROM products p
+             LEFT JOIN expansions x ON p.id_expansion = x.id_expansion
+             LEFT JOIN expansion_names e ON p.id_expansion = e.id_expansion
+             WHERE p.id_product = ?1",
+            params![id_product],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+
+    let mut stmt = conn.prepare(
+        "SELECT price_date, avg, low, trend, avg1, avg7, avg30,
+                avg_foil, low_foil, trend_foil, avg1_foil, avg7_foil, avg30_foil
+         FROM price_history
+         WHERE id_product = ?1 AND price_date >= ?2
+         ORDER BY price_date ASC",
+    )?;
+    let points = stmt
+        .query_map(params![id_product, since], |row| {
+            Ok(PriceHistoryPoint {
+                price_date: row.get(0)?,
+                avg: row.get(1)?,
+                low: row.get(2)?,
+                trend: row.get(3)?,
+                avg1: row.get(4)?,
+                avg7: row.get(5)?,
+                avg30: row.get(6)?,
+                avg_foil: row.get(7)?,
+                low_foil: row.get(8)?,
+                trend_foil: row.get(9)?,
+                avg1_foil: row.get(10)?,
+                avg7_foil: row.get(11)?,
+                avg30_foil: row.get(12)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(PriceHistory {
+        expansion_name,
+        points,
+    })
+}
+
+#[cfg(test)]
+#[path = "price_history_db_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+//! Tests for reading price history from an inventory_sync database.
+
+use super::*;
+
+/// The inventory_sync tables this module reads, with one product in a
+/// known expansion and three days of prices.
+fn sync_db() -> tempfile::NamedTempFile {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let conn = Connection::open(file.path()).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE products (id_product INTEGER PRIMARY KEY, name TEXT, id_expansion INTEGER);
+         CREATE TABLE expansions (id_expansion INTEGER PRIMARY KEY, name TEXT);
+         CREATE TABLE expansion_names (id_expansion INTEGER PRIMARY KEY, name TEXT);
+         CREATE TABLE price_history (
+             id_product INTEGER, price_date TEXT, avg REAL, low REAL, trend REAL,
+             avg1 REAL, avg7 REAL, avg30 REAL, avg_foil REAL, low_foil REAL,
+             trend_foil REAL, avg1_foil REAL, avg7_foil REAL, avg30_foil REAL);
+         INSERT INTO products VALUES (1, 'Lightning Bolt', 10);
+         INSERT INTO expansion_names VALUES (10, 'Alpha');
+         INSERT INTO price_history (id_product, price_date, avg, trend)
+         VALUES (1, '2026-03-01', 2.0, 2.1), (1, '2026-03-03', 2.4, 2.3),
+                (1, '2026-03-02', 2.2, 2.2), (2, '2026-03-02', 9.0, 9.0);",
+    )
+    .unwrap();
+    file
+}
+
+#[test]
+fn loads_rows_since_the_cutoff_oldest_first() {
+    let db = sync_db();
+
+    let history = load_price_history(db.path(), 1, "2026-03-02").unwrap();
+
+    assert_eq!(history.expansion_name.as_deref(), Some("Alpha"));
+    let dates: Vec<&str> = history
+        .points
+        .iter()
+        .map(|p| p.price_date.as_str())
+        .collect();
+    assert_eq!(dates, ["2026-03-02", "2026-03-03"]);
+    assert_eq!(history.points[1].avg, Some(2.4));
+    assert_eq!(history.points[1].trend, Some(2.3));
+}
+
+#[test]
+fn unknown_product_is_empty() {
+    let db = sync_db();
+
+    let history = load_price_history(db.path(), 99, "2026-01-01").unwrap();
+
+    assert!(history.expansion_name.is_none());
+    assert!(history.points.is_empty());
+}
+
+#[test]
+fn missing_database_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert!(load_price_history(&dir.path().join("none.db"), 1, "2026-01-01").is_err());
+}
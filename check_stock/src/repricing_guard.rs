@@ -0,0 +1,110 @@
+//! Repricing guardrails from the sales history and price guide — pure logic.
+//!
+//! Three checks keep suggested prices profitable:
+//! - **Sale floor** — the rolling average price a card actually sold at
+//!   (same condition and foil, from the inventory DB's `sold_events`). The
+//!   pricing graph's *Sales Floor* node never lets a suggestion drop below it.
+//! - **Below cost** — a card whose market trend has fallen under its
+//!   per-copy acquisition cost (from the recorded lot costs) is flagged, since
+//!   following the market would now mean selling at a loss.
+//! - **Thin market** — a wide spread between the price guide's low and trend
+//!   means few competing listings, so the lowest offer says little about what
+//!   the card sells for. The *Thin Market Guard* node damps price cuts on such
+//!   cards instead of following the market down.
+//!
+//! Like the other report modules this is free of database and wall-clock
+//! access; the caller passes the events and today's date.
+
+use crate::inventory_db::SoldEvent;
+use crate::models::{canonical_condition, Card};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Sales older than this many days do not count towards the sale floor.
+pub const SALE_FLOOR_WINDOW_DAYS: i64 = 90;
+
+/// Identifies what a sale price is comparable to: product, condition, foil.
+pub type SaleKey = (String, String, bool);
+
+fn sale_key(cardmarket_id: &str, condition: &str, is_foil: bool) -> SaleKey {
+    (
+        cardmarket_id.to_string(),
+        canonical_condition(condition),
+        is_foil,
+    )
+}
+
+/// Copy-weighted average sale price per product/condition/foil over the
+/// `window_days` up to `today`. Events with unparseable dates are skipped.
+pub fn rolling_sale_averages(
+    events: &[SoldEvent],
+    today: NaiveDate,
+    window_days: i64,
+) -> HashMap<SaleKey, f64> {
+    let since = today - chrono::Duration::days(window_days);
+    let mut totals: HashMap<SaleKey, (f64, i64)> = HashMap::new();
+    for event in events {
+        let Ok(date) = NaiveDate::parse_from_str(&event.date, "%Y-%m-%d") else {
+            continue;
+        };
+        if date < since || date > today || event.copies <= 0 {
+            continue;
+        }
+        let key = sale_key(&event.cardmarket_id, &event.condition, event.is_foil == "1");
+        let entry = totals.entry(key).or_insert((0.0, 0));
+        entry.0 += event.price * event.copies as f64;
+        entry.1 += event.copies;
+    }
+    totals
+        .into_iter()
+        .map(|(key, (revenue, copies))| (key, revenue / copies as f64))
+        .collect()
+}
+
+/// The sale floor for each card that has sales in `averages`, keyed by the
+/// card's index in `cards`.
+pub fn sale_floors(cards: &[Card], averages: &HashMap<SaleKey, f64>) -> HashMap<usize, f64> {
+    cards
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, card)| {
+            averages
+                .get(&sale_key(
+                    &card.cardmarket_id,
+                    &card.condition,
+                    card.is_foil_card(),
+                ))
+                .map(|&avg| (idx, avg))
+        })
+        .collect()
+}
+
+/// True when the market trend is known and below the copy's acquisition cost.
+pub fn trend_below_cost(trend: Option<f64>, copy_cost: Option<f64>) -> bool {
+    matches!((trend, copy_cost), (Some(trend), Some(cost)) if trend < cost)
+}
+
+/// Market depth estimate from the price guide: how far the lowest listing is
+/// below the trend, as a fraction of the trend (0.0 = low at trend). `None`
+/// without both prices. A large spread means a thin, low-liquidity market.
+pub fn market_spread(low: Option<f64>, trend: Option<f64>) -> Option<f64> {
+    match (low, trend) {
+        (Some(low), Some(trend)) if trend > 0.0 => Some(((trend - low) / trend).max(0.0)),
+        _ => None,
+    }
+}
+
+/// Damps a price cut from `current` to `suggested` when the market `spread`
+/// exceeds `max_spread`: only the fraction `max_spread / spread` of the cut is
+/// applied, so the thinner the market the smaller the step down. Raises and
+/// cuts in liquid markets pass through unchanged.
+pub fn thin_market_price(current: f64, suggested: f64, spread: f64, max_spread: f64) -> f64 {
+    if suggested >= current || spread <= max_spread {
+        return suggested;
+    }
+    current - (current - suggested) * (max_spread / spread)
+}
+
+#[cfg(test)]
+#[path = "repricing_guard_tests.rs"]
+mod tests;
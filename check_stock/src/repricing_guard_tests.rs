@@ -0,0 +1,96 @@
+//! Tests for repricing guardrails.
+
+use super::*;
+
+fn event(date: &str, id: &str, condition: &str, foil: &str, copies: i64, price: f64) -> SoldEvent {
+    SoldEvent {
+        date: date.to_string(),
+        cardmarket_id: id.to_string(),
+        condition: condition.to_string(),
+        language: "English".to_string(),
+        is_foil: foil.to_string(),
+        is_signed: String::new(),
+        copies,
+        price,
+    }
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 3, 31).unwrap()
+}
+
+#[test]
+fn averages_are_weighted_by_copies_within_the_window() {
+    let events = vec![
+        event("2026-03-01", "1", "NM", "", 3, 2.00),
+        event("2026-03-20", "1", "near_mint", "", 1, 6.00),
+        // Outside the 90-day window
+        event("2025-11-01", "1", "NM", "", 5, 100.0),
+    ];
+
+    let averages = rolling_sale_averages(&events, today(), 90);
+
+    assert_eq!(averages.len(), 1);
+    let avg = averages[&("1".to_string(), "NM".to_string(), false)];
+    assert!((avg - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn condition_and_foil_are_averaged_separately() {
+    let events = vec![
+        event("2026-03-01", "1", "NM", "", 1, 2.00),
+        event("2026-03-01", "1", "LP", "", 1, 1.00),
+        event("2026-03-01", "1", "NM", "1", 1, 9.00),
+    ];
+
+    let averages = rolling_sale_averages(&events, today(), 90);
+
+    assert_eq!(averages.len(), 3);
+    assert_eq!(averages[&("1".to_string(), "NM".to_string(), true)], 9.0);
+}
+
+#[test]
+fn sale_floors_match_cards_by_variant() {
+    let events = vec![event("2026-03-01", "7", "NM", "", 2, 4.00)];
+    let averages = rolling_sale_averages(&events, today(), 90);
+    let nm = Card {
+        cardmarket_id: "7".to_string(),
+        condition: "NM".to_string(),
+        ..Card::test_default()
+    };
+    let lp = Card {
+        condition: "LP".to_string(),
+        ..nm.clone()
+    };
+
+    let floors = sale_floors(&[lp, nm], &averages);
+
+    assert_eq!(floors, HashMap::from([(1, 4.0)]));
+}
+
+#[test]
+fn below_cost_needs_both_figures() {
+    assert!(trend_below_cost(Some(0.80), Some(1.00)));
+    assert!(!trend_below_cost(Some(1.20), Some(1.00)));
+    assert!(!trend_below_cost(None, Some(1.00)));
+    assert!(!trend_below_cost(Some(0.10), None));
+}
+
+#[test]
+fn spread_needs_a_positive_trend() {
+    assert_eq!(market_spread(Some(1.0), Some(4.0)), Some(0.75));
+    // A low above the trend is a deep market, not a negative spread
+    assert_eq!(market_spread(Some(5.0), Some(4.0)), Some(0.0));
+    assert_eq!(market_spread(None, Some(4.0)), None);
+    assert_eq!(market_spread(Some(1.0), Some(0.0)), None);
+}
+
+#[test]
+fn thin_markets_only_take_part_of_a_cut() {
+    // Liquid: the full cut from 10 to 6
+    assert_eq!(thin_market_price(10.0, 6.0, 0.2, 0.4), 6.0);
+    // Twice the allowed spread: half the cut
+    assert!((thin_market_price(10.0, 6.0, 0.8, 0.4) - 8.0).abs() < 1e-9);
+    // Raises are never damped
+    assert_eq!(thin_market_price(10.0, 12.0, 0.8, 0.4), 12.0);
+}
@@ -0,0 +1,80 @@
+//! Set symbols and rarity colour coding for card tables.
+//!
+//! Scryfall publishes every set symbol as a single-colour (black) SVG. The UI
+//! loads it through egui's SVG loader with every shape filled white, then
+//! tints it with the card's rarity colour, just like the symbol printed on the
+//! card.
+
+/// Card rarity, as far as colour coding is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Mythic,
+    /// Special, bonus, timeshifted and masterpiece printings.
+    Special,
+    /// Lands, tokens and anything unrecognised.
+    Other,
+}
+
+impl Rarity {
+    /// The rarities shown in the colour legend, lowest first.
+    pub const LEGEND: [Rarity; 5] = [
+        Rarity::Common,
+        Rarity::Uncommon,
+        Rarity::Rare,
+        Rarity::Mythic,
+        Rarity::Special,
+    ];
+
+    /// Parses a Cardmarket (`"Mythic"`, `"Time Shifted"`) or Scryfall
+    /// (`"mythic"`, `"bonus"`) rarity name, case-insensitively.
+    pub fn parse(rarity: &str) -> Self {
+        match rarity.trim().to_lowercase().as_str() {
+            "common" | "c" => Rarity::Common,
+            "uncommon" | "u" => Rarity::Uncommon,
+            "rare" | "r" => Rarity::Rare,
+            "mythic" | "mythic rare" | "m" => Rarity::Mythic,
+            "special" | "bonus" | "time shifted" | "timeshifted" | "masterpiece" | "s" => {
+                Rarity::Special
+            }
+            _ => Rarity::Other,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Rarity::Common => "Common",
+            Rarity::Uncommon => "Uncommon",
+            Rarity::Rare => "Rare",
+            Rarity::Mythic => "Mythic",
+            Rarity::Special => "Special",
+            Rarity::Other => "Other",
+        }
+    }
+}
+
+/// Fills every shape of a monochrome SVG white, so tinting the rendered
+/// symbol yields the tint colour instead of black.
+///
+/// Injects a style rule right after the opening `<svg …>` tag; CSS beats the
+/// documents' own `fill` attributes. Returns `None` if there is no root
+/// element to add it to.
+pub fn white_svg(svg: &[u8]) -> Option<Vec<u8>> {
+    let text = std::str::from_utf8(svg).ok()?;
+    let root = text.find("<svg")?;
+    let open_end = root + text[root..].find('>')? + 1;
+    if text[..open_end].ends_with("/>") {
+        return None;
+    }
+    let mut out = String::with_capacity(text.len() + 48);
+    out.push_str(&text[..open_end]);
+    out.push_str("<style>*{fill:#fff;stroke:none}</style>");
+    out.push_str(&text[open_end..]);
+    Some(out.into_bytes())
+}
+
+#[cfg(test)]
+#[path = "set_symbols_tests.rs"]
+mod tests;
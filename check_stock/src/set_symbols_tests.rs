@@ -0,0 +1,35 @@
+//! Tests for set_symbols.
+
+use super::*;
+
+#[test]
+fn parses_cardmarket_and_scryfall_rarities() {
+    assert_eq!(Rarity::parse("Common"), Rarity::Common);
+    assert_eq!(Rarity::parse("uncommon"), Rarity::Uncommon);
+    assert_eq!(Rarity::parse(" Rare "), Rarity::Rare);
+    assert_eq!(Rarity::parse("Mythic"), Rarity::Mythic);
+    assert_eq!(Rarity::parse("Time Shifted"), Rarity::Special);
+    assert_eq!(Rarity::parse("bonus"), Rarity::Special);
+    assert_eq!(Rarity::parse("Token"), Rarity::Other);
+    assert_eq!(Rarity::parse(""), Rarity::Other);
+}
+
+#[test]
+fn white_svg_renders_shapes_white() {
+    let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 32 32">
+        <path d="M8 8H24V24H8Z" fill="black"/></svg>"#;
+    let image = egui_extras::image::load_svg_bytes(&white_svg(svg).unwrap()).unwrap();
+    let [w, h] = image.size;
+    assert_eq!(
+        image.pixels[h / 2 * w + w / 2],
+        eframe::egui::Color32::WHITE
+    );
+    assert_eq!(image.pixels[0].a(), 0);
+}
+
+#[test]
+fn white_svg_needs_a_root_element() {
+    assert!(white_svg(b"<path d=\"M0 0H1V1Z\"/>").is_none());
+    assert!(white_svg(b"<svg/>").is_none());
+    assert!(white_svg(&[0xff, 0xfe]).is_none());
+}
@@ -0,0 +1,60 @@
+//! Tests for slow_queries.
+
+use super::*;
+use tempfile::TempDir;
+
+#[test]
+fn plans_and_indexes_come_from_the_schema() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE listings (id INTEGER PRIMARY KEY, name TEXT, bin TEXT);
+         CREATE INDEX idx_listings_bin_name ON listings(bin, name);",
+    )
+    .unwrap();
+
+    let plan = explain_query_plan(&conn, "SELECT * FROM listings WHERE bin = ?1").unwrap();
+    assert_eq!(plan.len(), 1);
+    assert!(plan[0].starts_with("SEARCH listings USING"));
+
+    let indexes = existing_indexes(&conn).unwrap();
+    assert_eq!(
+        indexes,
+        vec![ExistingIndex {
+            table: "listings".to_string(),
+            columns: vec!["bin".to_string(), "name".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn slow_statements_are_logged_with_their_plan() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("inventory.db");
+    let mut conn = Connection::open(&path).unwrap();
+    conn.execute_batch("CREATE TABLE slow_probe (id INTEGER PRIMARY KEY, label TEXT);")
+        .unwrap();
+
+    // SQLite times statements in whole milliseconds, so only a zero
+    // threshold catches a query on an empty table
+    sql_profile::set_slow_query_threshold(Some(Duration::ZERO));
+    instrument(&mut conn, &path);
+    conn.query_row(
+        "SELECT COUNT(*) FROM slow_probe WHERE label = ?1",
+        ["x"],
+        |r| r.get::<_, i64>(0),
+    )
+    .unwrap();
+    sql_profile::set_slow_query_threshold(None);
+
+    let logged = sql_profile::slow_queries();
+    let probe = logged
+        .iter()
+        .find(|q| q.sql.contains("slow_probe WHERE"))
+        .unwrap();
+    assert_eq!(probe.plan, vec!["SCAN slow_probe"]);
+
+    let advice = index_advice();
+    assert!(advice
+        .iter()
+        .any(|a| a.table == "slow_probe" && a.columns == ["label"]));
+}
@@ -1,4 +1,5 @@
 use crate::models::Card;
+use rayon::prelude::*;
 use std::collections::HashMap;
 
 #[derive(PartialEq, Clone, Copy)]
@@ -26,20 +27,30 @@ impl StockAnalysis {
     pub fn analyze_with_free_slots(&self, min_free_slots: i32) -> StockStats {
         let mut stats = StockStats::default();
 
-        // Collect all cards by their bin location
-        let mut bin_counts: HashMap<String, i32> = HashMap::new();
-
-        for card in &self.cards {
-            if let Some(loc) = &card.location {
-                if !loc.trim().is_empty() {
-                    // Extract and count by base location (ignoring L0, R, etc.)
-                    if let Some(bin_loc) = Self::extract_bin_location(loc) {
-                        let quantity = card.quantity.parse::<i32>().unwrap_or(0);
-                        *bin_counts.entry(bin_loc).or_insert(0) += quantity;
-                    }
+        // Collect all cards by their bin location, counting chunks of the
+        // stock in parallel and merging the per-chunk counts
+        let bin_counts: HashMap<String, i32> = self
+            .cards
+            .par_iter()
+            .filter_map(|card| {
+                let loc = card.location.as_deref()?;
+                if loc.trim().is_empty() {
+                    return None;
                 }
-            }
-        }
+                // Extract and count by base location (ignoring L0, R, etc.)
+                let bin_loc = Self::extract_bin_location(loc)?;
+                Some((bin_loc, card.quantity.parse::<i32>().unwrap_or(0)))
+            })
+            .fold(HashMap::new, |mut counts, (bin_loc, quantity)| {
+                *counts.entry(bin_loc).or_insert(0) += quantity;
+                counts
+            })
+            .reduce(HashMap::new, |mut merged, counts| {
+                for (bin_loc, quantity) in counts {
+                    *merged.entry(bin_loc).or_insert(0) += quantity;
+                }
+                merged
+            });
 
         // Store bins that have the required number of free slots or more
         stats.available_bins = bin_counts
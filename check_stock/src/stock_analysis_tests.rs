@@ -203,3 +203,38 @@ fn test_format_stock_analysis_shows_free_slots() {
     assert!(output.contains("20 cards"));
     assert!(output.contains("40 slots free"));
 }
+
+// Whole outputs, so formatting changes show up as snapshot diffs (review
+// with `cargo insta review`).
+
+fn snapshot_stats() -> StockStats {
+    let cards = vec![
+        create_card_at_location("A-0-1-1", 12),
+        create_card_at_location("A-0-1-1-L0", 3),
+        create_card_at_location("A-0-1-10", 59),
+        create_card_at_location("A-0-1-2", 30),
+        create_card_at_location("B-1-0-4", 30),
+        create_card_at_location("Schublade", 5),
+        Card {
+            location: None,
+            ..create_card_at_location("", 7)
+        },
+    ];
+    StockAnalysis::new(cards).analyze_with_free_slots(1)
+}
+
+#[test]
+fn snapshot_stock_analysis_by_location() {
+    insta::assert_snapshot!(format_stock_analysis_with_sort(
+        &snapshot_stats(),
+        SortOrder::ByLocation
+    ));
+}
+
+#[test]
+fn snapshot_stock_analysis_by_free_slots() {
+    insta::assert_snapshot!(format_stock_analysis_with_sort(
+        &snapshot_stats(),
+        SortOrder::ByFreeSlots
+    ));
+}
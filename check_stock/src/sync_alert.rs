@@ -0,0 +1,67 @@
+//! Webhook notification for blocked inventory imports.
+//!
+//! When the import safety check stops a sync (see
+//! [`SyncPreview::is_suspicious`]), the app can also post a short report to a
+//! webhook so the problem is noticed even if nobody is watching the screen —
+//! e.g. when an automated export broke overnight. The URL comes from
+//! `[check_stock] sync_webhook_url` in the shared config (or
+//! `CHECK_STOCK_SYNC_WEBHOOK`).
+//!
+//! The JSON body carries the message as both `text` (Slack, Mattermost) and
+//! `content` (Discord), plus the raw figures for other receivers.
+
+use crate::inventory_db::SyncPreview;
+use std::time::Duration;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One-line summary of a blocked sync.
+pub fn anomaly_message(preview: &SyncPreview) -> String {
+    format!(
+        "Inventory import blocked: {} of {} in-stock variants would be zeroed ({:.0}%) \
+         and {} of {} copies recorded as sold. Check the CSV export before syncing.",
+        preview.zeroed_variants,
+        preview.variants_before,
+        preview.zeroed_fraction() * 100.0,
+        preview.copies_sold,
+        preview.copies_before,
+    )
+}
+
+/// The JSON body posted to the webhook.
+pub fn webhook_payload(preview: &SyncPreview) -> serde_json::Value {
+    let message = anomaly_message(preview);
+    serde_json::json!({
+        "text": message,
+        "content": message,
+        "event": "inventory_sync_blocked",
+        "variants_before": preview.variants_before,
+        "zeroed_variants": preview.zeroed_variants,
+        "copies_before": preview.copies_before,
+        "copies_after": preview.copies_after,
+        "copies_sold": preview.copies_sold,
+        "new_variants": preview.new_variants,
+        "price_changes": preview.price_changes,
+    })
+}
+
+/// Posts the report to `url`. Blocking; run it off the UI thread.
+pub fn notify_webhook(url: &str, preview: &SyncPreview) -> Result<(), String> {
+    let client = mtg_common::http::blocking_client_builder(WEBHOOK_TIMEOUT)
+        .and_then(|builder| Ok(builder.build()?))
+        .map_err(|e| e.to_string())?;
+    let resp = client
+        .post(url)
+        .header("User-Agent", mtg_common::USER_AGENT)
+        .json(&webhook_payload(preview))
+        .send()
+        .map_err(|e| format!("Could not reach webhook: {e}"))?;
+    if !resp.status().is_success() {
+        return Err(format!("Webhook returned HTTP {}", resp.status()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "sync_alert_tests.rs"]
+mod tests;
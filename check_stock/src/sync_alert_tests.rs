@@ -0,0 +1,69 @@
+//! Tests for sync_alert.
+
+use super::*;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn blocked() -> SyncPreview {
+    SyncPreview {
+        variants_before: 400,
+        zeroed_variants: 100,
+        copies_before: 1000,
+        copies_after: 900,
+        copies_sold: 100,
+        ..SyncPreview::default()
+    }
+}
+
+#[test]
+fn message_names_the_zeroed_share() {
+    let message = anomaly_message(&blocked());
+    assert!(message.contains("100 of 400 in-stock variants"));
+    assert!(message.contains("(25%)"));
+    assert!(message.contains("100 of 1000 copies"));
+}
+
+#[test]
+fn payload_works_for_slack_and_discord() {
+    let payload = webhook_payload(&blocked());
+    assert_eq!(payload["text"], payload["content"]);
+    assert_eq!(payload["event"], "inventory_sync_blocked");
+    assert_eq!(payload["zeroed_variants"], 100);
+}
+
+#[tokio::test]
+async fn notify_posts_payload() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({
+            "event": "inventory_sync_blocked",
+            "copies_sold": 100
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let url = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || notify_webhook(&url, &blocked()))
+        .await
+        .unwrap();
+
+    assert_eq!(result, Ok(()));
+}
+
+#[tokio::test]
+async fn notify_reports_http_errors() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&mock_server)
+        .await;
+
+    let url = mock_server.uri();
+    let result = tokio::task::spawn_blocking(move || notify_webhook(&url, &blocked()))
+        .await
+        .unwrap();
+
+    assert!(result.unwrap_err().contains("404"));
+}
@@ -0,0 +1,183 @@
+//! Wantslist translation — card names in the language of the quote.
+//!
+//! Customers send wantslists in their own language and the matcher finds the
+//! cards by any localized name, but quotes and invoices should name every
+//! card in one chosen language. Names come from the listings' localized
+//! columns (`nameDE`, `nameES`, … — also stored in the inventory DB); a card
+//! without one is looked up on Scryfall by set code and collector number
+//! (see [`scryfall_name`]). Cards neither source knows keep their English
+//! name and are reported as untranslated.
+//!
+//! Scryfall calls block, so the stock checker builds the translation from
+//! the stored names first ([`NameTranslation::from_stored`]), looks the
+//! [`missing`](NameTranslation::missing) cards up on a background task
+//! ([`look_up_names`]) and merges the answers when they arrive
+//! ([`NameTranslation::apply_lookups`]).
+
+use crate::api::scryfall::fetch_printed_name;
+use crate::models::{Card, Language};
+use log::warn;
+use std::collections::{HashMap, HashSet};
+
+/// The name of `card` in `language` from its own columns, if it has one.
+pub fn stored_name(card: &Card, language: Language) -> Option<&str> {
+    let name = match language {
+        Language::English => &card.name,
+        Language::German => &card.name_de,
+        Language::Spanish => &card.name_es,
+        Language::French => &card.name_fr,
+        Language::Italian => &card.name_it,
+    };
+    Some(name.trim()).filter(|name| !name.is_empty())
+}
+
+/// Names of a stock check's cards in one language, by Cardmarket product ID.
+#[derive(Debug, Clone)]
+pub struct NameTranslation {
+    pub language: Language,
+    names: HashMap<String, String>,
+    /// How many of the names came from Scryfall
+    pub from_scryfall: usize,
+    /// English names of the cards left untranslated
+    pub untranslated: Vec<String>,
+    /// Cards without a stored name, one per product, still to be looked up
+    pub missing: Vec<Card>,
+}
+
+/// Answers of a name lookup: product ID and the name found, if any.
+pub type NameLookups = Vec<(String, Option<String>)>;
+
+impl NameTranslation {
+    /// Translates the names of `cards`: the stored names, and `lookup` for
+    /// cards without one. Listings of one product share a name, so the first
+    /// listing of each product decides and `lookup` runs once per product.
+    pub fn build<'a>(
+        cards: impl IntoIterator<Item = &'a Card>,
+        language: Language,
+        lookup: impl FnMut(&Card, Language) -> Option<String>,
+    ) -> Self {
+        let mut translation = Self::from_stored(cards, language);
+        let found = look_up_names(&translation.missing, language, lookup);
+        translation.apply_lookups(found);
+        translation
+    }
+
+    /// Translates the names of `cards` that have a stored name and keeps the
+    /// first listing of every other product in [`missing`](Self::missing).
+    pub fn from_stored<'a>(cards: impl IntoIterator<Item = &'a Card>, language: Language) -> Self {
+        let mut translation = Self {
+            language,
+            names: HashMap::new(),
+            from_scryfall: 0,
+            untranslated: Vec::new(),
+            missing: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+        for card in cards {
+            let id = card.cardmarket_id.trim();
+            if !seen.insert(id) {
+                continue;
+            }
+            match stored_name(card, language) {
+                Some(name) => {
+                    translation.names.insert(id.to_string(), name.to_string());
+                }
+                None => translation.missing.push(card.clone()),
+            }
+        }
+        translation
+    }
+
+    /// Merges the answers for the [`missing`](Self::missing) cards; cards
+    /// without an answer (a miss, or a cancelled lookup) stay untranslated.
+    pub fn apply_lookups(&mut self, found: NameLookups) {
+        let mut found: HashMap<String, String> = found
+            .into_iter()
+            .filter_map(|(id, name)| Some((id, name?)))
+            .collect();
+        for card in std::mem::take(&mut self.missing) {
+            let id = card.cardmarket_id.trim();
+            match found.remove(id) {
+                Some(name) => {
+                    self.names.insert(id.to_string(), name);
+                    self.from_scryfall += 1;
+                }
+                None => self.untranslated.push(card.name),
+            }
+        }
+    }
+
+    /// The name of `card` in the output language; its English name when it
+    /// could not be translated.
+    pub fn name_of<'a>(&'a self, card: &'a Card) -> &'a str {
+        self.names
+            .get(card.cardmarket_id.trim())
+            .map_or(card.name.as_str(), String::as_str)
+    }
+
+    /// Summary line for the quote, e.g. `Names in German (2 from Scryfall; 1
+    /// untranslated: Sol Ring)`.
+    pub fn summary(&self) -> String {
+        let mut notes = Vec::new();
+        if self.from_scryfall > 0 {
+            notes.push(format!("{} from Scryfall", self.from_scryfall));
+        }
+        if !self.missing.is_empty() {
+            notes.push(format!("{} pending from Scryfall", self.missing.len()));
+        }
+        if !self.untranslated.is_empty() {
+            notes.push(format!(
+                "{} untranslated: {}",
+                self.untranslated.len(),
+                self.untranslated.join(", ")
+            ));
+        }
+        if notes.is_empty() {
+            format!("Names in {}", self.language.as_str())
+        } else {
+            format!("Names in {} ({})", self.language.as_str(), notes.join("; "))
+        }
+    }
+}
+
+/// Runs `lookup` for each of `missing`, e.g. on a background task.
+pub fn look_up_names(
+    missing: &[Card],
+    language: Language,
+    mut lookup: impl FnMut(&Card, Language) -> Option<String>,
+) -> NameLookups {
+    missing
+        .iter()
+        .map(|card| {
+            (
+                card.cardmarket_id.trim().to_string(),
+                lookup(card, language),
+            )
+        })
+        .collect()
+}
+
+/// Looks up a card's printed name in `language` on Scryfall. Failures are
+/// logged and count as misses.
+pub fn scryfall_name(card: &Card, language: Language) -> Option<String> {
+    if language == Language::English || card.set_code.trim().is_empty() {
+        return None;
+    }
+    match fetch_printed_name(card.set_code.trim(), card.cn.trim(), language.code()) {
+        Ok(name) => name,
+        Err(e) => {
+            warn!(
+                "No {} name for {} ({} #{}): {e}",
+                language.as_str(),
+                card.name,
+                card.set_code,
+                card.cn
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "translation_tests.rs"]
+mod tests;
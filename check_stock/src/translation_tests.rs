@@ -0,0 +1,85 @@
+//! Tests for translation.
+
+use super::*;
+
+fn card(id: &str, name: &str, name_de: &str) -> Card {
+    Card {
+        cardmarket_id: id.into(),
+        name: name.into(),
+        name_de: name_de.into(),
+        ..Card::test_default()
+    }
+}
+
+#[test]
+fn stored_names_need_no_lookup() {
+    let cards = [
+        card("1", "Lightning Bolt", "Blitzschlag"),
+        card("1", "Lightning Bolt", "Blitzschlag"),
+    ];
+    let translation = NameTranslation::build(&cards, Language::German, |_, _| {
+        panic!("stored names must not be looked up")
+    });
+
+    assert_eq!(translation.name_of(&cards[0]), "Blitzschlag");
+    assert_eq!(translation.summary(), "Names in German");
+}
+
+#[test]
+fn misses_are_looked_up_once_per_product() {
+    let cards = [
+        card("1", "Sol Ring", ""),
+        card("1", "Sol Ring", " "),
+        card("2", "Counterspell", ""),
+    ];
+    let mut lookups = Vec::new();
+    let translation = NameTranslation::build(&cards, Language::German, |card, lang| {
+        assert_eq!(lang, Language::German);
+        lookups.push(card.cardmarket_id.clone());
+        (card.cardmarket_id == "2").then(|| "Gegenzauber".to_string())
+    });
+
+    assert_eq!(lookups, ["1", "2"]);
+    assert_eq!(translation.name_of(&cards[2]), "Gegenzauber");
+    // Untranslated cards keep their English name
+    assert_eq!(translation.name_of(&cards[1]), "Sol Ring");
+    assert_eq!(
+        translation.summary(),
+        "Names in German (1 from Scryfall; 1 untranslated: Sol Ring)"
+    );
+}
+
+#[test]
+fn english_output_uses_the_english_name() {
+    let bolt = card("1", "Lightning Bolt", "Blitzschlag");
+    let translation = NameTranslation::build([&bolt], Language::English, |_, _| None);
+
+    assert_eq!(translation.name_of(&bolt), "Lightning Bolt");
+    assert_eq!(stored_name(&bolt, Language::French), None);
+}
+
+#[test]
+fn lookups_arrive_after_the_stored_names() {
+    let cards = [
+        card("1", "Lightning Bolt", "Blitzschlag"),
+        card("2", "Counterspell", ""),
+        card("3", "Sol Ring", ""),
+    ];
+    let mut translation = NameTranslation::from_stored(&cards, Language::German);
+    assert_eq!(translation.name_of(&cards[0]), "Blitzschlag");
+    assert_eq!(translation.missing.len(), 2);
+    assert_eq!(
+        translation.summary(),
+        "Names in German (2 pending from Scryfall)"
+    );
+
+    // Only Counterspell was answered before the lookup was cancelled
+    translation.apply_lookups(vec![("2".to_string(), Some("Gegenzauber".to_string()))]);
+    assert!(translation.missing.is_empty());
+    assert_eq!(translation.name_of(&cards[1]), "Gegenzauber");
+    assert_eq!(translation.name_of(&cards[2]), "Sol Ring");
+    assert_eq!(
+        translation.summary(),
+        "Names in German (1 from Scryfall; 1 untranslated: Sol Ring)"
+    );
+}
@@ -2,14 +2,17 @@ use eframe::{self, egui};
 use egui::ViewportBuilder;
 
 use super::{
+    components::{Command, CommandPalette, TaskPanel},
     screens::{
         BinAnalysisScreen, BuyHelperScreen, ConsolidationScreen, ConsolidationState,
-        MispricingScreen, MoversScreen, PickingScreen, PickingState, PricingScreen, RestockScreen,
-        SearchScreen, StockAnalysisScreen, StockCheckerScreen, StockListingScreen, WelcomeScreen,
+        MispricingScreen, MoversScreen, PickingScreen, PickingSessionsScreen, PickingState,
+        PricingScreen, RestockScreen, SearchScreen, SettingsScreen, StockAnalysisScreen,
+        StockCheckerScreen, StockListingScreen, WelcomeScreen,
     },
     state::{
-        AppState, BinAnalysisState, BuyHelperState, MispricingState, MoversState, PricingState,
-        RestockState, Screen, SearchState, StockAnalysisState, StockListingState,
+        AppState, BinAnalysisState, BuyHelperState, MispricingState, MoversState,
+        PickingSessionsState, PricingState, RestockState, Screen, SearchState, SettingsState,
+        StockAnalysisState, StockListingState,
     },
 };
 
@@ -21,16 +24,26 @@ pub struct StockCheckerApp {
     listing_state: StockListingState,
     search_state: SearchState,
     picking_state: PickingState,
+    picking_sessions_state: PickingSessionsState,
     pricing_state: PricingState,
     buy_helper_state: BuyHelperState,
     mispricing_state: MispricingState,
     movers_state: MoversState,
     consolidation_state: ConsolidationState,
     restock_state: RestockState,
+    settings_state: SettingsState,
+    command_palette: CommandPalette,
 }
 
 impl eframe::App for StockCheckerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // First, so Ctrl+K and the palette's keys win over focused text fields
+        if let Some(command) = self.command_palette.show(ctx) {
+            self.run_command(command);
+        }
+        // Before the screens, which fill the remaining space with a central panel
+        TaskPanel::show(ctx);
+
         match self.app_state.current_screen {
             Screen::Welcome => {
                 egui::CentralPanel::default().show(ctx, |ui| {
@@ -73,6 +86,14 @@ impl eframe::App for StockCheckerApp {
                     &mut self.picking_state,
                 );
             }
+            Screen::PickingSessions => {
+                PickingSessionsScreen::show(
+                    ctx,
+                    &mut self.app_state.current_screen,
+                    &mut self.picking_sessions_state,
+                    &mut self.picking_state,
+                );
+            }
             Screen::Pricing => {
                 PricingScreen::show(ctx, &mut self.app_state, &mut self.pricing_state);
             }
@@ -103,18 +124,105 @@ impl eframe::App for StockCheckerApp {
                     &mut self.restock_state,
                 );
             }
+            Screen::Settings => {
+                SettingsScreen::show(
+                    ctx,
+                    &mut self.app_state.current_screen,
+                    &mut self.settings_state,
+                );
+            }
         }
 
         show_sync_guard_modal(ctx, &mut self.app_state);
+        if let Some(report) = crate::io::take_load_report() {
+            self.app_state.csv_load_report = Some(report);
+        }
+        show_csv_report_modal(ctx, &mut self.app_state);
+    }
+}
+
+impl StockCheckerApp {
+    /// Executes a command picked in the Ctrl+K palette.
+    fn run_command(&mut self, command: Command) {
+        log::info!("Command palette: {}", command.label());
+        let state = &mut self.app_state;
+        match command {
+            Command::GoTo(screen) => state.current_screen = screen,
+            Command::LoadInventory => {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CSV", crate::io::CSV_EXTENSIONS)
+                    .pick_file()
+                {
+                    state.inventory_path = path.display().to_string();
+                    Self::sync_inventory(state);
+                }
+                state.current_screen = Screen::StockChecker;
+            }
+            Command::LoadWantslist => {
+                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                    state.wantslist_path = path.display().to_string();
+                }
+                state.current_screen = Screen::StockChecker;
+            }
+            Command::CheckStock => {
+                state.current_screen = Screen::StockChecker;
+                if let Err(e) = StockCheckerScreen::check_stock(state) {
+                    state.output = format!("Error: {e}");
+                }
+            }
+            Command::ExportOutput => {
+                if state.output.is_empty() {
+                    log::warn!("Nothing to export: run a stock check first");
+                    return;
+                }
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name("stock_check.txt")
+                    .add_filter("Text Files", &["txt"])
+                    .save_file()
+                {
+                    if let Err(e) = std::fs::write(&path, &state.output) {
+                        log::error!("Failed to export stock check results: {e}");
+                    }
+                }
+            }
+            Command::SyncInventory => Self::sync_inventory(state),
+        }
+    }
+
+    fn sync_inventory(state: &mut AppState) {
+        if state.inventory_path.is_empty() {
+            log::warn!("No inventory CSV loaded to sync");
+            return;
+        }
+        match crate::io::read_csv(&state.inventory_path) {
+            Ok(inventory) => state.sync_inventory_guarded(&inventory),
+            Err(e) => log::error!("Failed to read {}: {e}", state.inventory_path),
+        }
     }
 }
 
 /// Modal shown (on any screen) when the import safety check blocked a CSV
 /// sync. Nothing has been written yet; the user chooses to apply or drop it.
 fn show_sync_guard_modal(ctx: &egui::Context, app_state: &mut AppState) {
-    let Some(guard) = &app_state.sync_guard else {
+    let Some(guard) = &mut app_state.sync_guard else {
         return;
     };
+    if let Some(rx) = &guard.webhook_rx {
+        match rx.try_recv() {
+            Ok(result) => {
+                guard.webhook_result = Some(result);
+                guard.webhook_rx = None;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => guard.webhook_rx = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+        }
+    }
+    let webhook_note = match (&guard.webhook_rx, &guard.webhook_result) {
+        (Some(_), _) => Some("The configured sync webhook is being notified…".to_string()),
+        (None, Some(Ok(()))) => Some("The configured sync webhook was notified.".to_string()),
+        (None, Some(Err(e))) => Some(format!("Notifying the sync webhook failed: {e}")),
+        (None, None) => None,
+    };
     let p = guard.preview.clone();
     let mut confirm = false;
     let mut cancel = false;
@@ -124,7 +232,8 @@ fn show_sync_guard_modal(ctx: &egui::Context, app_state: &mut AppState) {
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
             ui.label(
-                "This inventory import would remove a large share of your stock. \
+                "This inventory import would remove a large share of your stock \
+                 (over half the copies, or over 20% of the listed variants). \
                  That usually means a truncated or wrong CSV — applying it would \
                  record the missing copies as sales.",
             );
@@ -140,7 +249,12 @@ fn show_sync_guard_modal(ctx: &egui::Context, app_state: &mut AppState) {
                     ui.label(format!("{} copies", p.copies_sold));
                     ui.end_row();
                     ui.label("Variants zeroed out:");
-                    ui.label(format!("{}", p.zeroed_variants));
+                    ui.label(format!(
+                        "{} of {} ({:.0}%)",
+                        p.zeroed_variants,
+                        p.variants_before,
+                        p.zeroed_fraction() * 100.0
+                    ));
                     ui.end_row();
                     ui.label("New variants:");
                     ui.label(format!("{}", p.new_variants));
@@ -166,6 +280,9 @@ fn show_sync_guard_modal(ctx: &egui::Context, app_state: &mut AppState) {
                     .size(10.0)
                     .weak(),
             );
+            if let Some(note) = &webhook_note {
+                ui.label(egui::RichText::new(note).size(10.0).weak());
+            }
         });
 
     if confirm {
@@ -185,6 +302,69 @@ fn show_sync_guard_modal(ctx: &egui::Context, app_state: &mut AppState) {
     }
 }
 
+/// Shown after a lenient CSV load skipped malformed rows: what was loaded,
+/// the first few errors and a button to save the full report.
+fn show_csv_report_modal(ctx: &egui::Context, app_state: &mut AppState) {
+    const PREVIEW_ERRORS: usize = 10;
+    let Some(report) = &app_state.csv_load_report else {
+        return;
+    };
+    let mut close = false;
+    egui::Window::new("⚠ Malformed CSV rows skipped")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Loaded {} cards from {}; {} malformed rows were skipped.",
+                report.loaded,
+                report.path,
+                report.errors.len()
+            ));
+            ui.add_space(6.0);
+            egui::Grid::new("csv_report_errors")
+                .num_columns(2)
+                .striped(true)
+                .spacing([16.0, 2.0])
+                .show(ui, |ui| {
+                    for error in report.errors.iter().take(PREVIEW_ERRORS) {
+                        ui.label(format!("Line {}", error.line));
+                        ui.label(&error.message);
+                        ui.end_row();
+                    }
+                });
+            if report.errors.len() > PREVIEW_ERRORS {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "… and {} more in the report",
+                        report.errors.len() - PREVIEW_ERRORS
+                    ))
+                    .weak(),
+                );
+            }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("💾 Save report…").clicked() {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .set_file_name("csv_errors.csv")
+                        .add_filter("CSV Files", &["csv"])
+                        .save_file()
+                    {
+                        if let Err(e) = std::fs::write(&path, report.to_csv()) {
+                            log::error!("Failed to save CSV error report: {e}");
+                        }
+                    }
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+    if close {
+        app_state.csv_load_report = None;
+    }
+}
+
 pub fn launch_gui() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder::default().with_inner_size([800.0, 600.0]),
@@ -194,6 +374,10 @@ pub fn launch_gui() -> Result<(), eframe::Error> {
     eframe::run_native(
         "MTG Stock Checker",
         options,
-        Box::new(|_cc| Ok(Box::new(StockCheckerApp::default()))),
+        Box::new(|cc| {
+            // Set symbols are drawn through egui_extras' SVG loader
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            Ok(Box::new(StockCheckerApp::default()))
+        }),
     )
 }
@@ -0,0 +1,201 @@
+//! Ctrl+K command palette.
+//!
+//! A search box over every app-level action — switching screens, loading the
+//! inventory CSV or wantslist, running the stock check, exporting its output
+//! and re-syncing the inventory DB. Typing filters the list with a fuzzy
+//! subsequence match; arrow keys move the selection and Enter runs it. The
+//! palette only picks a [`Command`]; the app shell executes it, since most
+//! commands touch state the palette does not own.
+
+use crate::ui::state::Screen;
+use crate::ui::style;
+use eframe::egui;
+
+/// Screens reachable from the palette, in welcome-screen order.
+const SCREENS: [Screen; 13] = [
+    Screen::Welcome,
+    Screen::StockChecker,
+    Screen::StockAnalysis,
+    Screen::BinAnalysis,
+    Screen::StockListing,
+    Screen::Search,
+    Screen::Pricing,
+    Screen::BuyHelper,
+    Screen::Mispricing,
+    Screen::Restock,
+    Screen::Movers,
+    Screen::PickingSessions,
+    Screen::Settings,
+];
+
+/// An action the palette can run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    GoTo(Screen),
+    LoadInventory,
+    LoadWantslist,
+    CheckStock,
+    ExportOutput,
+    SyncInventory,
+}
+
+impl Command {
+    /// Every command, actions first, then screens.
+    pub fn all() -> Vec<Command> {
+        let mut commands = vec![
+            Command::LoadInventory,
+            Command::LoadWantslist,
+            Command::CheckStock,
+            Command::ExportOutput,
+            Command::SyncInventory,
+        ];
+        commands.extend(SCREENS.into_iter().map(Command::GoTo));
+        commands
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Command::GoTo(screen) => format!("Go to {}", screen.title()),
+            Command::LoadInventory => "Load inventory CSV…".to_string(),
+            Command::LoadWantslist => "Load wantslist…".to_string(),
+            Command::CheckStock => "Check stock against wantslist".to_string(),
+            Command::ExportOutput => "Export stock check results…".to_string(),
+            Command::SyncInventory => "Sync inventory CSV into database".to_string(),
+        }
+    }
+}
+
+/// Scores how well `query` matches `label`: every query character must
+/// appear in order (case-insensitive). Consecutive characters and matches at
+/// word starts score higher. `None` if it does not match; an empty query
+/// matches everything with score 0.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = label[pos..].iter().position(|&c| c == q)?;
+        let idx = pos + offset;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || !label[idx - 1].is_alphanumeric() {
+            score += 3;
+        }
+        // Skipped characters cost a little, so tighter matches win
+        score -= offset.min(10) as i32 / 2;
+        previous = Some(idx);
+        pos = idx + 1;
+    }
+    Some(score)
+}
+
+/// Commands matching `query`, best first; ties keep [`Command::all`] order.
+pub fn filter_commands(query: &str) -> Vec<Command> {
+    let mut scored: Vec<(i32, usize, Command)> = Command::all()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, cmd)| fuzzy_score(query, &cmd.label()).map(|s| (s, i, cmd)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, cmd)| cmd).collect()
+}
+
+#[derive(Default)]
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Handles the Ctrl+K toggle and, while open, draws the palette.
+    /// Returns the command the user picked this frame.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Command> {
+        let toggle = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::K);
+        if ctx.input_mut(|i| i.consume_shortcut(&toggle)) {
+            self.open = !self.open;
+            self.query.clear();
+            self.selected = 0;
+        }
+        if !self.open {
+            return None;
+        }
+
+        let commands = filter_commands(&self.query);
+        let (escape, up, down, enter) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+        if escape {
+            self.open = false;
+            return None;
+        }
+        if down && self.selected + 1 < commands.len() {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(commands.len().saturating_sub(1));
+
+        let mut picked = enter
+            .then(|| commands.get(self.selected).copied())
+            .flatten();
+        egui::Window::new("Command Palette")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size([420.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("Type a command…")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    self.selected = 0;
+                }
+                ui.add_space(4.0);
+
+                if commands.is_empty() {
+                    ui.label(egui::RichText::new("No matching command").color(style::TEXT_MUTED));
+                }
+                egui::ScrollArea::vertical()
+                    .max_height(320.0)
+                    .show(ui, |ui| {
+                        for (i, cmd) in commands.iter().enumerate() {
+                            let row = ui.selectable_label(i == self.selected, cmd.label());
+                            if i == self.selected && (up || down) {
+                                row.scroll_to_me(None);
+                            }
+                            if row.clicked() {
+                                picked = Some(*cmd);
+                            }
+                        }
+                    });
+                ui.label(
+                    egui::RichText::new("↑↓ select · Enter run · Esc close")
+                        .size(11.0)
+                        .color(style::TEXT_MUTED),
+                );
+            });
+
+        if picked.is_some() {
+            self.open = false;
+        }
+        picked
+    }
+}
+
+#[cfg(test)]
+#[path = "command_palette_tests.rs"]
+mod tests;
@@ -0,0 +1,34 @@
+//! Tests for the command palette.
+
+use super::*;
+
+#[test]
+fn fuzzy_score_requires_characters_in_order() {
+    assert!(fuzzy_score("lwl", "Load wantslist…").is_some());
+    assert!(fuzzy_score("wll", "Load wantslist…").is_none());
+    assert!(fuzzy_score("xyz", "Go to Pricing").is_none());
+    assert_eq!(fuzzy_score("", "Go to Pricing"), Some(0));
+}
+
+#[test]
+fn fuzzy_score_prefers_tight_word_start_matches() {
+    let tight = fuzzy_score("pric", "Go to Pricing").unwrap();
+    let scattered = fuzzy_score("pric", "Go to Mispricing Report").unwrap();
+    assert!(tight > scattered);
+    assert!(fuzzy_score("SA", "Go to Stock Analysis") > fuzzy_score("SA", "Go to Search Cards"));
+}
+
+#[test]
+fn filter_ranks_best_match_first() {
+    assert_eq!(filter_commands("wants")[0], Command::LoadWantslist);
+    assert_eq!(filter_commands("movers")[0], Command::GoTo(Screen::Movers));
+    assert_eq!(filter_commands("sync")[0], Command::SyncInventory);
+}
+
+#[test]
+fn empty_query_lists_every_command() {
+    let all = filter_commands("");
+    assert_eq!(all, Command::all());
+    assert!(all.contains(&Command::GoTo(Screen::Settings)));
+    assert!(!all.contains(&Command::GoTo(Screen::Picking)));
+}
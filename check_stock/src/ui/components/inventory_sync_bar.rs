@@ -7,6 +7,7 @@
 
 use crate::api::inventory_sync::InventorySyncClient;
 use crate::ui::{
+    components::spawn_task,
     state::{AppState, ConnectionStatus},
     style,
 };
@@ -25,27 +26,31 @@ impl InventorySyncBar {
     ) {
         // Poll health-check channel
         if let Some(rx) = &app_state.inventory_health_rx {
-            if let Ok(result) = rx.try_recv() {
-                match &result {
-                    Ok(()) => info!(
-                        "Inventory sync health check succeeded ({})",
-                        app_state.inventory_sync_url
-                    ),
-                    Err(e) => error!(
-                        "Inventory sync health check failed ({}): {e}",
-                        app_state.inventory_sync_url
-                    ),
+            match rx.try_recv() {
+                Ok(result) => {
+                    match &result {
+                        Ok(()) => info!(
+                            "Inventory sync health check succeeded ({})",
+                            app_state.inventory_sync_url
+                        ),
+                        Err(e) => error!(
+                            "Inventory sync health check failed ({}): {e}",
+                            app_state.inventory_sync_url
+                        ),
+                    }
+                    app_state.inventory_sync_status = match result {
+                        Ok(()) => ConnectionStatus::Connected,
+                        Err(e) => ConnectionStatus::Failed(e),
+                    };
+                    app_state.inventory_health_rx = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    app_state.inventory_sync_status = ConnectionStatus::Unchecked;
+                    app_state.inventory_health_rx = None;
                 }
-                app_state.inventory_sync_status = match result {
-                    Ok(()) => ConnectionStatus::Connected,
-                    Err(e) => ConnectionStatus::Failed(e),
-                };
-                app_state.inventory_health_rx = None;
+                Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
             }
         }
-        if app_state.inventory_health_rx.is_some() {
-            ctx.request_repaint();
-        }
 
         style::section_frame().show(ui, |ui| {
             ui.horizontal(|ui| {
@@ -76,9 +81,7 @@ impl InventorySyncBar {
                                 .size(11.0),
                         );
                     }
-                    ConnectionStatus::Checking => {
-                        ui.spinner();
-                    }
+                    ConnectionStatus::Checking => {}
                     ConnectionStatus::Connected => {
                         connected = true;
                         ui.label(
@@ -108,11 +111,10 @@ impl InventorySyncBar {
             app_state.inventory_sync_url
         );
         app_state.inventory_sync_status = ConnectionStatus::Checking;
-        let (tx, rx) = std::sync::mpsc::channel();
-        app_state.inventory_health_rx = Some(rx);
         let client = InventorySyncClient::new(&app_state.inventory_sync_url);
-        std::thread::spawn(move || {
-            let _ = tx.send(client.health_blocking().map_err(|e| e.to_string()));
-        });
+        app_state.inventory_health_rx =
+            Some(spawn_task("Inventory sync health check", move |_, tx| {
+                let _ = tx.send(client.health_blocking().map_err(|e| e.to_string()));
+            }));
     }
 }
@@ -0,0 +1,188 @@
+//! Photo window for one listing (see [`crate::listing_photos`]).
+//!
+//! Opened from the search results and the picking list. Shows the stored
+//! photo, imports a new one from a file or as the newest image in the last
+//! camera folder, and opens or copies it for a dispute reply.
+
+use crate::inventory_db;
+use crate::listing_photos::{import_photo, newest_image_in, open_in_default_app, IMAGE_EXTENSIONS};
+use crate::models::Card;
+use crate::ui::style;
+use eframe::egui;
+use std::path::{Path, PathBuf};
+
+/// Longest side of the preview texture; phone photos are scaled down.
+const PREVIEW_PIXELS: u32 = 1024;
+const PREVIEW_HEIGHT: f32 = 420.0;
+
+/// The photo window and the listing it shows.
+#[derive(Default)]
+pub struct ListingPhotoWindow {
+    card: Option<Card>,
+    path: Option<PathBuf>,
+    texture: Option<egui::TextureHandle>,
+    /// Folder the last photo was imported from, for "Newest in folder"
+    camera_dir: Option<PathBuf>,
+    status: Option<Result<String, String>>,
+}
+
+impl ListingPhotoWindow {
+    /// Shows the window for `card`, with its photo if one was imported.
+    pub fn open(&mut self, card: &Card) {
+        self.card = Some(card.clone());
+        self.texture = None;
+        self.status = None;
+        self.path = match inventory_db::listing_photos(&[card]) {
+            Ok(paths) => paths.into_iter().next().flatten(),
+            Err(e) => {
+                self.status = Some(Err(format!("Failed to look up photo: {e}")));
+                None
+            }
+        };
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let Some(card) = self.card.clone() else {
+            return;
+        };
+        if self.texture.is_none() {
+            if let Some(path) = self.path.clone() {
+                match load_preview(ctx, &path) {
+                    Ok(texture) => self.texture = Some(texture),
+                    Err(e) => {
+                        self.status = Some(Err(e));
+                        self.path = None;
+                    }
+                }
+            }
+        }
+
+        let mut open = true;
+        egui::Window::new(format!("📷 {}", card.name))
+            .id(egui::Id::new("listing_photo_window"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} #{} · {} · {}{} · {}€",
+                    card.set_code.to_uppercase(),
+                    card.cn,
+                    card.language,
+                    card.condition,
+                    if card.is_foil_card() { " · Foil" } else { "" },
+                    card.price
+                ));
+                ui.add_space(6.0);
+
+                match &self.texture {
+                    Some(texture) => {
+                        let [w, h] = texture.size();
+                        let height = PREVIEW_HEIGHT.min(h as f32);
+                        let size = egui::vec2(height * w as f32 / h as f32, height);
+                        ui.image((texture.id(), size));
+                    }
+                    None => {
+                        ui.weak("No photo for this listing yet.");
+                    }
+                }
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    if style::primary_button(ui, "Import photo…").clicked() {
+                        let mut dialog =
+                            rfd::FileDialog::new().add_filter("Photos", &IMAGE_EXTENSIONS);
+                        if let Some(dir) = &self.camera_dir {
+                            dialog = dialog.set_directory(dir);
+                        }
+                        if let Some(source) = dialog.pick_file() {
+                            self.camera_dir = source.parent().map(Path::to_path_buf);
+                            self.import(&card, &source);
+                        }
+                    }
+                    if let Some(dir) = self.camera_dir.clone() {
+                        if style::secondary_button(ui, "Newest in folder")
+                            .on_hover_text(format!(
+                                "Import the most recent photo in {}",
+                                dir.display()
+                            ))
+                            .clicked()
+                        {
+                            match newest_image_in(&dir) {
+                                Some(source) => self.import(&card, &source),
+                                None => {
+                                    self.status =
+                                        Some(Err(format!("No photos in {}", dir.display())))
+                                }
+                            }
+                        }
+                    }
+                });
+
+                if let Some(path) = self.path.clone() {
+                    ui.horizontal(|ui| {
+                        if style::secondary_button(ui, "Open")
+                            .on_hover_text("Open in the image viewer, e.g. to attach it to a mail")
+                            .clicked()
+                        {
+                            if let Err(e) = open_in_default_app(&path) {
+                                self.status = Some(Err(e));
+                            }
+                        }
+                        if style::secondary_button(ui, "Copy path").clicked() {
+                            ui.ctx().copy_text(path.display().to_string());
+                            self.status = Some(Ok("Path copied".to_string()));
+                        }
+                        if style::secondary_button(ui, "Remove")
+                            .on_hover_text("Forget the photo; the image file is kept")
+                            .clicked()
+                        {
+                            match inventory_db::remove_listing_photo(&card) {
+                                Ok(()) => {
+                                    self.path = None;
+                                    self.texture = None;
+                                    self.status = Some(Ok("Photo removed".to_string()));
+                                }
+                                Err(e) => self.status = Some(Err(e.to_string())),
+                            }
+                        }
+                    });
+                    ui.weak(path.display().to_string());
+                }
+
+                match &self.status {
+                    Some(Ok(msg)) => style::status_ok(ui, msg),
+                    Some(Err(msg)) => style::status_error(ui, msg),
+                    None => {}
+                }
+            });
+        if !open {
+            self.card = None;
+            self.texture = None;
+        }
+    }
+
+    fn import(&mut self, card: &Card, source: &Path) {
+        match import_photo(card, source) {
+            Ok(stored) => {
+                self.path = Some(stored);
+                self.texture = None;
+                self.status = Some(Ok("Photo saved".to_string()));
+            }
+            Err(e) => self.status = Some(Err(e)),
+        }
+    }
+}
+
+fn load_preview(ctx: &egui::Context, path: &Path) -> Result<egui::TextureHandle, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("Failed to load {}: {e}", path.display()))?
+        .thumbnail(PREVIEW_PIXELS, PREVIEW_PIXELS)
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, &image.into_raw());
+    Ok(ctx.load_texture(
+        format!("listing_photo_{}", path.display()),
+        color_image,
+        egui::TextureOptions::LINEAR,
+    ))
+}
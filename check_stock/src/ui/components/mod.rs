@@ -1,7 +1,15 @@
+mod command_palette;
 mod file_picker;
 mod inventory_sync_bar;
+mod listing_photo;
 mod output_window;
+mod set_symbol;
+mod task_manager;
 
+pub use command_palette::{Command, CommandPalette};
 pub use file_picker::FilePicker;
 pub use inventory_sync_bar::InventorySyncBar;
+pub use listing_photo::ListingPhotoWindow;
 pub use output_window::OutputWindow;
+pub use set_symbol::SetSymbols;
+pub use task_manager::{spawn_task, TaskPanel, TaskReceiver};
@@ -0,0 +1,138 @@
+//! Set symbol icons tinted by rarity.
+//!
+//! Symbols are fetched from Scryfall on a single background worker (cached on
+//! disk by [`SetSymbolCache`]) and handed to egui's SVG loader, which renders
+//! them at the display size. Until a symbol is ready — or if it cannot be
+//! fetched or drawn — the set code is shown as text in the rarity colour
+//! instead, so tables never wait on the network.
+
+use crate::cache::{fetch_set_symbol_cached, SetSymbolCache};
+use crate::set_symbols::{white_svg, Rarity};
+use crate::ui::style;
+use eframe::egui;
+use log::warn;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+const SYMBOL_SIZE: f32 = 16.0;
+
+enum SymbolEntry {
+    Loading,
+    /// `bytes://` URI of the symbol's SVG, registered with the context
+    Ready(String),
+    Missing,
+}
+
+type SymbolResult = (String, Option<Vec<u8>>);
+
+/// Per-screen set symbols and their background loader.
+#[derive(Default)]
+pub struct SetSymbols {
+    entries: HashMap<String, SymbolEntry>,
+    /// Started on first use: (request sender, result receiver).
+    worker: Option<(Sender<String>, Receiver<SymbolResult>)>,
+}
+
+impl SetSymbols {
+    /// Draws the symbol for `set_code` in the rarity colour, or the set code
+    /// as text while loading or when no symbol is available.
+    pub fn show(&mut self, ui: &mut egui::Ui, set_code: &str, rarity: Rarity) -> egui::Response {
+        self.poll(ui.ctx());
+
+        let key = set_code.trim().to_lowercase();
+        let color = style::rarity_color(rarity);
+        let hover = format!("{} · {}", set_code.trim().to_uppercase(), rarity.label());
+
+        if !key.is_empty() && !self.entries.contains_key(&key) {
+            self.request(ui.ctx(), &key);
+        }
+        if let Some(SymbolEntry::Ready(uri)) = self.entries.get(&key) {
+            let size = egui::vec2(SYMBOL_SIZE, SYMBOL_SIZE);
+            let image = egui::Image::new(uri.clone())
+                .fit_to_exact_size(size)
+                .tint(color);
+            match image.load_for_size(ui.ctx(), size) {
+                Ok(_) => return ui.add(image).on_hover_text(hover),
+                Err(e) => {
+                    warn!("Set symbol for {key} could not be drawn: {e}");
+                    self.entries.insert(key, SymbolEntry::Missing);
+                }
+            }
+        }
+        ui.label(
+            egui::RichText::new(set_code.trim().to_uppercase())
+                .size(11.0)
+                .strong()
+                .color(color),
+        )
+        .on_hover_text(hover)
+    }
+
+    /// One-line colour key for the rarity tints.
+    pub fn legend(ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Rarity:")
+                    .color(style::TEXT_MUTED)
+                    .size(12.0),
+            );
+            for rarity in Rarity::LEGEND {
+                ui.label(
+                    egui::RichText::new(format!("● {}", rarity.label()))
+                        .color(style::rarity_color(rarity))
+                        .size(12.0),
+                );
+            }
+        });
+    }
+
+    fn request(&mut self, ctx: &egui::Context, key: &str) {
+        let (requests, _) = self.worker.get_or_insert_with(|| spawn_worker(ctx.clone()));
+        if requests.send(key.to_string()).is_ok() {
+            self.entries.insert(key.to_string(), SymbolEntry::Loading);
+        } else {
+            self.entries.insert(key.to_string(), SymbolEntry::Missing);
+        }
+    }
+
+    /// Hands finished worker results to egui's loaders. Never blocks.
+    fn poll(&mut self, ctx: &egui::Context) {
+        let Some((_, results)) = &self.worker else {
+            return;
+        };
+        while let Ok((key, svg)) = results.try_recv() {
+            let entry = match svg {
+                Some(svg) => {
+                    // The `.svg` extension selects egui_extras' SVG loader
+                    let uri = format!("bytes://set_symbol/{key}.svg");
+                    ctx.include_bytes(uri.clone(), svg);
+                    SymbolEntry::Ready(uri)
+                }
+                None => SymbolEntry::Missing,
+            };
+            self.entries.insert(key, entry);
+        }
+    }
+}
+
+/// Fetches requested symbols one at a time until the requesting
+/// [`SetSymbols`] is dropped.
+fn spawn_worker(ctx: egui::Context) -> (Sender<String>, Receiver<SymbolResult>) {
+    let (request_tx, request_rx) = channel::<String>();
+    let (result_tx, result_rx) = channel();
+    std::thread::spawn(move || {
+        let cache = SetSymbolCache::new();
+        // Uncached fetches are paced by the shared Scryfall rate limiter
+        for key in request_rx {
+            let svg = fetch_set_symbol_cached(&cache, &key)
+                .map_err(|e| warn!("No set symbol for {key}: {e}"))
+                .ok()
+                .and_then(|svg| white_svg(&svg));
+            if result_tx.send((key, svg)).is_err() {
+                break;
+            }
+            ctx.request_repaint();
+        }
+    });
+    (request_tx, result_rx)
+}
@@ -0,0 +1,233 @@
+//! Background task registry and the task panel.
+//!
+//! Screens start their long-running work (server fetches, price guide
+//! downloads, database compaction) through [`spawn_task`], which registers
+//! the work in the process-wide [`tasks`] registry and runs it on its own
+//! thread. The [`TaskPanel`] at the bottom of the window lists every running
+//! task with its status, progress and a cancel button, so screens no longer
+//! draw their own spinners.
+//!
+//! Blocking HTTP calls can't be interrupted, so cancelling is cooperative:
+//! the task disappears from the panel at once, its [`TaskReceiver`] reports
+//! `Disconnected` so the screen resets, and the worker may check
+//! [`TaskHandle::is_cancelled`] to stop between steps. Whatever it still
+//! sends is dropped.
+
+use crate::ui::style;
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// The registry shared by all screens.
+pub fn tasks() -> &'static TaskRegistry {
+    static REGISTRY: OnceLock<TaskRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(TaskRegistry::default)
+}
+
+/// Runs `work` on a new thread as a registered task named `name`.
+///
+/// `work` gets the task's handle (for status, progress and cancellation)
+/// and the sending half of the returned receiver. The task is unregistered
+/// when `work` returns.
+pub fn spawn_task<T, F>(name: impl Into<String>, work: F) -> TaskReceiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&TaskHandle, &Sender<T>) + Send + 'static,
+{
+    let handle = tasks().start(name);
+    let entry = Arc::clone(&handle.entry);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || work(&handle, &tx));
+    TaskReceiver { rx, entry }
+}
+
+struct TaskEntry {
+    id: u64,
+    name: String,
+    started: Instant,
+    cancelled: AtomicBool,
+    progress: Mutex<TaskProgress>,
+}
+
+#[derive(Default)]
+struct TaskProgress {
+    status: String,
+    fraction: Option<f32>,
+}
+
+/// A running task as shown in the panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskSnapshot {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    /// Completed share in `0.0..=1.0`; `None` while unknown
+    pub fraction: Option<f32>,
+    pub elapsed: Duration,
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: u64,
+    tasks: Vec<Arc<TaskEntry>>,
+}
+
+/// Running background tasks, oldest first.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    inner: Arc<Mutex<Registry>>,
+}
+
+impl TaskRegistry {
+    /// Registers a task; it stays listed until the handle is dropped or the
+    /// task is cancelled.
+    pub fn start(&self, name: impl Into<String>) -> TaskHandle {
+        let mut inner = self.inner.lock().unwrap();
+        inner.next_id += 1;
+        let entry = Arc::new(TaskEntry {
+            id: inner.next_id,
+            name: name.into(),
+            started: Instant::now(),
+            cancelled: AtomicBool::new(false),
+            progress: Mutex::new(TaskProgress::default()),
+        });
+        inner.tasks.push(Arc::clone(&entry));
+        TaskHandle {
+            entry,
+            registry: self.clone(),
+        }
+    }
+
+    /// Flags the task as cancelled and removes it from the list.
+    pub fn cancel(&self, id: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(pos) = inner.tasks.iter().position(|t| t.id == id) {
+            let entry = inner.tasks.remove(pos);
+            entry.cancelled.store(true, Ordering::Relaxed);
+            log::info!("Cancelled background task: {}", entry.name);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<TaskSnapshot> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .tasks
+            .iter()
+            .map(|t| {
+                let progress = t.progress.lock().unwrap();
+                TaskSnapshot {
+                    id: t.id,
+                    name: t.name.clone(),
+                    status: progress.status.clone(),
+                    fraction: progress.fraction,
+                    elapsed: t.started.elapsed(),
+                }
+            })
+            .collect()
+    }
+
+    fn remove(&self, id: u64) {
+        self.inner.lock().unwrap().tasks.retain(|t| t.id != id);
+    }
+}
+
+/// The worker's side of a registered task.
+pub struct TaskHandle {
+    entry: Arc<TaskEntry>,
+    registry: TaskRegistry,
+}
+
+impl TaskHandle {
+    /// Short description of the current step, shown next to the name.
+    pub fn set_status(&self, status: impl Into<String>) {
+        self.entry.progress.lock().unwrap().status = status.into();
+    }
+
+    /// Completed share of the work, clamped to `0.0..=1.0`.
+    pub fn set_progress(&self, fraction: f32) {
+        self.entry.progress.lock().unwrap().fraction = Some(fraction.clamp(0.0, 1.0));
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.entry.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.registry.remove(self.entry.id);
+    }
+}
+
+/// Receiving half of a [`spawn_task`] channel. Behaves like
+/// [`Receiver::try_recv`], except that it reports `Disconnected` as soon as
+/// the task is cancelled.
+pub struct TaskReceiver<T> {
+    rx: Receiver<T>,
+    entry: Arc<TaskEntry>,
+}
+
+impl<T> TaskReceiver<T> {
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        if self.entry.cancelled.load(Ordering::Relaxed) {
+            return Err(TryRecvError::Disconnected);
+        }
+        self.rx.try_recv()
+    }
+}
+
+/// Bottom panel listing running tasks; hidden while there are none.
+pub struct TaskPanel;
+
+impl TaskPanel {
+    /// Must run before the screen's central panel.
+    pub fn show(ctx: &egui::Context) {
+        let running = tasks().snapshot();
+        if running.is_empty() {
+            return;
+        }
+        // Progress and elapsed time change without any input
+        ctx.request_repaint_after(Duration::from_millis(250));
+
+        egui::TopBottomPanel::bottom("task_panel").show(ctx, |ui| {
+            ui.add_space(4.0);
+            for task in &running {
+                ui.horizontal(|ui| {
+                    match task.fraction {
+                        Some(fraction) => {
+                            ui.add(
+                                egui::ProgressBar::new(fraction)
+                                    .desired_width(120.0)
+                                    .show_percentage(),
+                            );
+                        }
+                        None => {
+                            ui.spinner();
+                        }
+                    }
+                    ui.label(egui::RichText::new(&task.name).strong());
+                    if !task.status.is_empty() {
+                        ui.label(egui::RichText::new(&task.status).color(style::TEXT_MUTED));
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("Cancel").clicked() {
+                            tasks().cancel(task.id);
+                        }
+                        ui.label(
+                            egui::RichText::new(format!("{}s", task.elapsed.as_secs()))
+                                .color(style::TEXT_MUTED)
+                                .size(11.0),
+                        );
+                    });
+                });
+            }
+            ui.add_space(2.0);
+        });
+    }
+}
+
+#[cfg(test)]
+#[path = "task_manager_tests.rs"]
+mod tests;
@@ -0,0 +1,92 @@
+//! Tests for task_manager.
+
+use super::*;
+
+#[test]
+fn tasks_are_listed_until_their_handle_drops() {
+    let registry = TaskRegistry::default();
+    let first = registry.start("Fetch prices");
+    let second = registry.start("Compact database");
+    second.set_status("Backing up…");
+    second.set_progress(1.5);
+
+    let running = registry.snapshot();
+    assert_eq!(running.len(), 2);
+    assert_eq!(running[0].name, "Fetch prices");
+    assert_eq!(running[0].fraction, None);
+    assert_eq!(running[1].status, "Backing up…");
+    assert_eq!(running[1].fraction, Some(1.0));
+
+    drop(first);
+    let running = registry.snapshot();
+    assert_eq!(running.len(), 1);
+    assert_eq!(running[0].name, "Compact database");
+    drop(second);
+    assert!(registry.snapshot().is_empty());
+}
+
+#[test]
+fn cancel_flags_the_handle_and_unlists_the_task() {
+    let registry = TaskRegistry::default();
+    let handle = registry.start("Fetch price guide");
+    let other = registry.start("Health check");
+    let id = registry.snapshot()[0].id;
+
+    registry.cancel(id);
+
+    assert!(handle.is_cancelled());
+    assert!(!other.is_cancelled());
+    assert_eq!(registry.snapshot().len(), 1);
+    // Dropping the cancelled handle later must not remove anything else
+    drop(handle);
+    assert_eq!(registry.snapshot()[0].name, "Health check");
+}
+
+#[test]
+fn receiver_disconnects_on_cancel() {
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let rx = spawn_task("receiver_disconnects_on_cancel", move |_, tx| {
+        let _ = release_rx.recv();
+        let _ = tx.send(42);
+    });
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    let id = tasks()
+        .snapshot()
+        .into_iter()
+        .find(|t| t.name == "receiver_disconnects_on_cancel")
+        .unwrap()
+        .id;
+    tasks().cancel(id);
+    release_tx.send(()).unwrap();
+
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn spawned_task_delivers_result_and_unregisters() {
+    let rx = spawn_task("spawned_task_delivers_result", |task, tx| {
+        task.set_status("working");
+        let _ = tx.send("done");
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let result = loop {
+        match rx.try_recv() {
+            Ok(result) => break result,
+            Err(TryRecvError::Empty) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(5))
+            }
+            Err(e) => panic!("no result: {e}"),
+        }
+    };
+    assert_eq!(result, "done");
+    while tasks()
+        .snapshot()
+        .iter()
+        .any(|t| t.name == "spawned_task_delivers_result")
+    {
+        assert!(Instant::now() < deadline, "task still registered");
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
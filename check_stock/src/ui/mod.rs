@@ -4,5 +4,8 @@ mod language;
 mod screens;
 mod state;
 pub(crate) mod style;
+#[cfg(test)]
+pub(crate) mod test_harness;
 
 pub use app::launch_gui;
+pub use screens::{reprice_inventory_from_file, BulkRepriceOutcome};
@@ -3,7 +3,7 @@ use crate::{
         fragmented_variants, plan_consolidation, plan_variant_defrag, ConsolidationPlan,
         FragmentedVariant, Move,
     },
-    io::read_csv,
+    io::{read_csv, CSV_EXTENSIONS},
     stock_analysis::{format_stock_analysis_with_sort, SortOrder, StockAnalysis},
     ui::{
         components::FilePicker,
@@ -42,7 +42,7 @@ impl BinAnalysisScreen {
                     // zero every variant not present in a partial file.
                     style::section_frame().show(ui, |ui| {
                         FilePicker::new("Inventory CSV:", &mut state.inventory_path)
-                            .with_filter("CSV", &["csv"])
+                            .with_filter("CSV", CSV_EXTENSIONS)
                             .show(ui);
                     });
 
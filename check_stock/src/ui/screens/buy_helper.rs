@@ -1,6 +1,6 @@
 use crate::{
     buy_helper::{classify, compute_summary, export_csv, CardClass},
-    io::read_csv,
+    io::{read_csv, CSV_EXTENSIONS},
     ui::{
         components::{FilePicker, OutputWindow},
         state::{BuyHelperState, Screen},
@@ -42,7 +42,7 @@ impl BuyHelperScreen {
                     // ── File input ──────────────────────────────────────────
                     style::section_frame().show(ui, |ui| {
                         let picked = FilePicker::new("Card export CSV:", &mut state.csv_path)
-                            .with_filter("CSV", &["csv"])
+                            .with_filter("CSV", CSV_EXTENSIONS)
                             .show(ui);
                         ui.add_space(6.0);
                         let reload = style::secondary_button(ui, "Load CSV").clicked();
@@ -13,11 +13,11 @@
 use crate::{
     api::cardmarket::PriceGuide,
     api::inventory_sync::{InventorySyncClient, PriceFields},
-    inventory_db::{get_in_stock_cards, InStockCard},
+    inventory_db::{get_active_in_stock_cards, InStockCard},
     mispricing::{build_report, Action, MarketData, MispricingReport, PriceVerdict},
     price_trends::{SnapshotSet, TrendChange},
     ui::{
-        components::InventorySyncBar,
+        components::{spawn_task, InventorySyncBar},
         state::{
             AppState, FetchMsg, InventoryPriceSource, MarketSource, MispricingSort,
             MispricingState, Screen, VerdictFilter,
@@ -82,8 +82,7 @@ impl MispricingScreen {
                 state.guide_rx = None;
                 match result {
                     Ok(guide) => {
-                        state.guide_status =
-                            format!("Price guide loaded ({} entries)", guide.len());
+                        state.guide_status = guide_status(&guide);
                         state.price_guide = Some(guide);
                         Self::rebuild(state);
                     }
@@ -139,6 +138,7 @@ impl MispricingScreen {
                 }
                 Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                     state.sync_loading = false;
+                    state.sync_status = "Fetch cancelled".to_string();
                     return;
                 }
             }
@@ -163,9 +163,6 @@ impl MispricingScreen {
                     Self::spawn_sync_fetch(state, &url);
                 }
             }
-            if state.sync_loading {
-                ui.spinner();
-            }
             if !state.sync_status.is_empty() {
                 ui.label(
                     egui::RichText::new(&state.sync_status)
@@ -201,15 +198,14 @@ impl MispricingScreen {
                     }
                     if style::secondary_button(ui, "Load from file…").clicked() {
                         if let Some(path) = rfd::FileDialog::new()
-                            .add_filter("JSON", &["json"])
+                            .add_filter("JSON", &["json", "gz", "zip"])
                             .pick_file()
                         {
                             Self::load_from_file(state, &path.to_string_lossy());
                         }
                     }
                     if state.guide_loading {
-                        ui.spinner();
-                        ui.label("Fetching ~50 MB price guide…");
+                        style::status_loading(ui, "Fetching ~50 MB price guide…");
                     } else if !state.guide_status.is_empty() {
                         ui.label(
                             egui::RichText::new(&state.guide_status).color(style::COLOR_SUCCESS),
@@ -707,22 +703,20 @@ impl MispricingScreen {
     // ── Actions ─────────────────────────────────────────────────────────────
 
     fn spawn_guide_fetch(state: &mut MispricingState) {
-        let (tx, rx) = std::sync::mpsc::channel();
-        state.guide_rx = Some(rx);
         state.guide_loading = true;
         state.error = None;
         state.guide_status = String::new();
-        std::thread::spawn(move || {
+        state.guide_rx = Some(spawn_task("Fetch Cardmarket price guide", |_, tx| {
             let result = PriceGuide::fetch_blocking().map_err(|e| e.to_string());
             let _ = tx.send(result);
-        });
+        }));
     }
 
     /// Fetches latest prices + 7/30-day snapshots for all in-stock cards from
     /// the inventory_sync server. Only raw rows cross the wire; deltas are
     /// derived locally.
     fn spawn_sync_fetch(state: &mut MispricingState, url: &str) {
-        let ids: Vec<u64> = match get_in_stock_cards() {
+        let ids: Vec<u64> = match get_active_in_stock_cards() {
             Ok(cards) => cards
                 .iter()
                 .filter_map(|c| c.cardmarket_id.parse::<u64>().ok())
@@ -743,27 +737,36 @@ impl MispricingScreen {
             "Mispricing: fetching prices + snapshots for {} products from {url}",
             ids.len()
         );
-        let dates = SnapshotSet::request_dates(chrono::Local::now().date_naive());
-        let (tx, rx) = std::sync::mpsc::channel();
-        state.sync_rx = Some(rx);
+        let dates = SnapshotSet::request_dates(mtg_common::dates::market_today());
         state.sync_loading = true;
         state.error = None;
         // Instant feedback before the worker thread even starts.
         state.sync_status = format!("Contacting server ({} products)…", ids.len());
         let client = InventorySyncClient::new(url);
-        std::thread::spawn(move || {
+        let task = spawn_task("Fetch mispricing data", move |task, tx| {
+            let progress = |fraction: f32, msg: String| {
+                task.set_progress(fraction);
+                task.set_status(msg.clone());
+                let _ = tx.send(FetchMsg::Progress(msg));
+            };
             let result = (|| {
-                let _ = tx.send(FetchMsg::Progress(format!(
-                    "Fetching latest prices for {} products…",
-                    ids.len()
-                )));
+                progress(
+                    0.0,
+                    format!("Fetching latest prices for {} products…", ids.len()),
+                );
                 let latest = client
                     .latest_prices_blocking(&ids)
                     .map_err(|e| e.to_string())?;
-                let _ = tx.send(FetchMsg::Progress(format!(
-                    "{} prices received · fetching 90-day snapshots…",
-                    latest.len()
-                )));
+                if task.is_cancelled() {
+                    return Err("cancelled".to_string());
+                }
+                progress(
+                    0.5,
+                    format!(
+                        "{} prices received · fetching 90-day snapshots…",
+                        latest.len()
+                    ),
+                );
                 let snapshots = client
                     .price_snapshots_blocking(&ids, &dates)
                     .map_err(|e| e.to_string())?;
@@ -771,13 +774,14 @@ impl MispricingScreen {
             })();
             let _ = tx.send(FetchMsg::Done(result));
         });
+        state.sync_rx = Some(task);
     }
 
     fn load_from_file(state: &mut MispricingState, path: &str) {
         state.guide_path = path.to_string();
         match PriceGuide::load(path) {
             Ok(guide) => {
-                state.guide_status = format!("Price guide loaded ({} entries)", guide.len());
+                state.guide_status = guide_status(&guide);
                 state.price_guide = Some(guide);
                 state.error = None;
                 Self::rebuild(state);
@@ -790,7 +794,7 @@ impl MispricingScreen {
 
     /// Rebuilds the report from the current DB inventory + the active market source.
     fn rebuild(state: &mut MispricingState) {
-        let cards = match get_in_stock_cards() {
+        let cards = match get_active_in_stock_cards() {
             Ok(c) => c,
             Err(e) => {
                 state.error = Some(format!("Failed to read inventory: {e}"));
@@ -798,7 +802,7 @@ impl MispricingScreen {
             }
         };
         let src = state.ref_source;
-        let today = chrono::Local::now().date_naive();
+        let today = mtg_common::dates::market_today();
         state.consistency = crate::consistency::find_issues(&cards);
         let snapshots = &state.snapshots;
         let report = match state.source {
@@ -852,6 +856,18 @@ impl MispricingScreen {
     }
 }
 
+/// Status line for a loaded guide. Foil-only products are called out since
+/// their non-foil copies in stock show up without market data.
+fn guide_status(guide: &PriceGuide) -> String {
+    let finishes = guide.finish_counts();
+    format!(
+        "Price guide loaded ({} entries, {} foil-only, {} non-foil-only)",
+        guide.len(),
+        finishes.foil_only,
+        finishes.non_foil_only
+    )
+}
+
 /// Resolves the full [`MarketData`] for one card from any row carrying the
 /// standard 12 Cardmarket price columns.
 fn market_data_of<P: PriceFields>(
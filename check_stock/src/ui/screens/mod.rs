@@ -4,9 +4,12 @@ mod consolidation;
 mod mispricing;
 mod movers;
 mod picking;
+mod picking_sessions;
+mod picking_verification;
 mod pricing;
 mod restock;
 mod search;
+mod settings;
 mod stock_analysis;
 mod stock_checker;
 mod stock_listing;
@@ -18,9 +21,11 @@ pub use consolidation::{ConsolidationScreen, ConsolidationState};
 pub use mispricing::MispricingScreen;
 pub use movers::MoversScreen;
 pub use picking::{PickingScreen, PickingState};
-pub use pricing::PricingScreen;
+pub use picking_sessions::PickingSessionsScreen;
+pub use pricing::{reprice_inventory_from_file, BulkRepriceOutcome, PricingScreen};
 pub use restock::RestockScreen;
 pub use search::SearchScreen;
+pub use settings::SettingsScreen;
 pub use stock_analysis::StockAnalysisScreen;
 pub use stock_checker::StockCheckerScreen;
 pub use stock_listing::StockListingScreen;
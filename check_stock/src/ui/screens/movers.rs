@@ -12,10 +12,10 @@
 
 use crate::{
     api::inventory_sync::InventorySyncClient,
-    inventory_db::get_in_stock_cards,
+    inventory_db::get_active_in_stock_cards,
     price_trends::{build_stock_movers, SnapshotSet, StockMover},
     ui::{
-        components::InventorySyncBar,
+        components::{spawn_task, InventorySyncBar},
         state::{AppState, InventoryPriceSource, MoverDirection, MoverSort, MoversState, Screen},
         style,
     },
@@ -120,9 +120,6 @@ impl MoversScreen {
                     Self::spawn_fetch(state, &url);
                 }
             }
-            if state.loading {
-                ui.spinner();
-            }
             if !state.status.is_empty() {
                 ui.label(
                     egui::RichText::new(&state.status)
@@ -360,7 +357,7 @@ impl MoversScreen {
 
     /// Loads the in-stock inventory and fetches raw snapshots for its products.
     fn spawn_fetch(state: &mut MoversState, url: &str) {
-        state.cards = match get_in_stock_cards() {
+        state.cards = match get_active_in_stock_cards() {
             Ok(c) => c,
             Err(e) => {
                 state.error = Some(format!("Failed to read inventory: {e}"));
@@ -383,26 +380,24 @@ impl MoversScreen {
             "Movers: fetching snapshots for {} products from {url}",
             ids.len()
         );
-        let dates = SnapshotSet::request_dates(chrono::Local::now().date_naive());
-        let (tx, rx) = std::sync::mpsc::channel();
-        state.rx = Some(rx);
+        let dates = SnapshotSet::request_dates(mtg_common::dates::market_today());
         state.loading = true;
         state.error = None;
         // Instant feedback — the snapshot fetch can take a while on large stocks.
         state.status = format!("Fetching 90-day snapshots for {} products…", ids.len());
         let client = InventorySyncClient::new(url);
-        std::thread::spawn(move || {
+        state.rx = Some(spawn_task("Fetch price movement", move |_, tx| {
             let result = client
                 .price_snapshots_blocking(&ids, &dates)
                 .map(|snapshots| (snapshots, dates))
                 .map_err(|e| e.to_string());
             let _ = tx.send(result);
-        });
+        }));
     }
 
     /// Rejoins cards × snapshots with the current parameters.
     fn rebuild(state: &mut MoversState) {
-        let today = chrono::Local::now().date_naive();
+        let today = mtg_common::dates::market_today();
         let mut movers = build_stock_movers(
             &state.cards,
             &state.snapshots,
@@ -2,11 +2,47 @@
 //!
 //! Displays cards to pick with images, allowing users to mark items as picked.
 //! Cards are grouped by location for efficient warehouse picking.
-
-use crate::api::scryfall::{fetch_card_async, fetch_image_async};
-use crate::cache::ImageCache;
+//! Each pick records the picker's name and a timestamp so a finished session
+//! can be exported as a pick log when an order is later reported incomplete.
+//! A session can be archived once picked: the picked copies are removed from
+//! the inventory DB (with an audit row each) and a stock-update CSV is exported
+//! so Cardmarket sees the same removals.
+//!
+//! Copies that can't be found are recorded as a short pick with a reason
+//! (missing, damaged, wrong location). Only the copies actually picked are
+//! archived; the short picks go into the pick log and a discrepancies report
+//! listing each one next to the inventory DB's quantity for that variant.
+//!
+//! The list is fully keyboard-driven for warehouse terminals: arrow keys move
+//! a cursor through the grid, Space toggles the card under it, F jumps to the
+//! next unpicked card and `/` focuses a search box that filters the list.
+//!
+//! F11 switches to warehouse mode for a wall-mounted touchscreen: fullscreen,
+//! high contrast, one card at a time in very large type, with touch-sized
+//! buttons. Picking a card there moves straight on to the next unpicked one.
+//!
+//! Sessions started from the stock checker are saved after every change
+//! ([`crate::picking_sessions`]) and can be reopened from the Picking
+//! Sessions screen.
+//!
+//! For valuable orders a second person can verify the picks before shipping:
+//! verification mode shows the picked items one at a time with their image,
+//! and the checker confirms each one or flags it as wrong (see
+//! [`super::picking_verification`]). Wrong items block archiving until they
+//! are checked again.
+
+use super::picking_verification::show_verification;
+use crate::api::scryfall::fetch_image_async;
+use crate::cache::{fetch_card_shared_async, ImageCache};
 use crate::card_matching::{get_card_name, MatchedCard};
-use crate::models::Language;
+use crate::export_order::{compare_cards, item_id};
+use crate::formatters::format_update_stock_csv;
+use crate::models::{Card, Language};
+use crate::picking_sessions::{
+    add_session, replace_session, sessions_path, update_sessions, SavedPickItem, SavedSession,
+    Verification,
+};
+use crate::ui::components::ListingPhotoWindow;
 use crate::ui::state::Screen;
 use eframe::egui;
 use log::{debug, error, info, warn};
@@ -24,6 +60,48 @@ pub struct LoadedImage {
     pub image_data: Vec<u8>,
 }
 
+/// Why copies of an item could not be picked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortPickReason {
+    Missing,
+    Damaged,
+    WrongLocation,
+}
+
+impl ShortPickReason {
+    pub const ALL: [Self; 3] = [Self::Missing, Self::Damaged, Self::WrongLocation];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Missing => "Missing",
+            Self::Damaged => "Damaged",
+            Self::WrongLocation => "Wrong location",
+        }
+    }
+
+    /// Value used in the CSV exports
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Damaged => "damaged",
+            Self::WrongLocation => "wrong_location",
+        }
+    }
+
+    /// Inverse of [`Self::as_str`]
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.as_str() == s)
+    }
+}
+
+/// Copies of an item the picker could not pick, and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortPick {
+    pub reason: ShortPickReason,
+    /// Copies not picked, `1..=quantity`
+    pub missing: i32,
+}
+
 /// A card in the picking list with its picking state
 #[derive(Clone)]
 pub struct PickingItem {
@@ -38,6 +116,18 @@ pub struct PickingItem {
     pub location: String,
     pub is_foil: bool,
     pub picked: bool,
+    /// Name of the picker who marked this item picked (empty if unknown)
+    pub picked_by: String,
+    /// Local timestamp (`YYYY-MM-DD HH:MM:SS`) of when the item was picked
+    pub picked_at: Option<String>,
+    /// Set when the item was picked short; it then counts as picked with
+    /// only the found copies
+    pub short_pick: Option<ShortPick>,
+    /// The second person's check of the picked copies; cleared when the pick
+    /// changes
+    pub verification: Option<Verification>,
+    /// The inventory listing this item is picked from
+    pub card: Card,
 }
 
 impl PickingItem {
@@ -52,17 +142,68 @@ impl PickingItem {
             condition: mc.card.condition.clone(),
             language: mc.card.language.clone(),
             quantity: mc.quantity,
-            price: mc.card.price.parse().unwrap_or(0.0),
+            price: mc.card.price_f64(),
             location: mc.card.location.clone().unwrap_or_default(),
             is_foil: mc.card.is_foil_card(),
             picked: false,
+            picked_by: String::new(),
+            picked_at: None,
+            short_pick: None,
+            verification: None,
+            card: mc.card.clone(),
+        }
+    }
+
+    /// Copies actually picked: the full quantity, less any short-picked
+    /// copies; zero while unpicked
+    pub fn picked_quantity(&self) -> i32 {
+        if !self.picked {
+            return 0;
         }
+        self.quantity - self.short_pick.as_ref().map_or(0, |s| s.missing)
     }
 
     /// Generate a cache key for this card's image
     pub fn image_key(&self) -> String {
         format!("{}_{}", self.set_code.to_lowercase(), self.collector_number)
     }
+
+    /// Restores an item from a saved session, with its pick state
+    pub fn from_saved(saved: &SavedPickItem) -> Self {
+        let mut item = Self::from_matched_card(&MatchedCard {
+            card: &saved.card,
+            quantity: saved.quantity,
+            set_name: saved.set_name.clone(),
+        });
+        item.picked = saved.picked;
+        item.picked_by = saved.picked_by.clone();
+        item.picked_at = saved.picked_at.clone();
+        item.short_pick = saved.short_pick.as_ref().and_then(|(reason, missing)| {
+            Some(ShortPick {
+                reason: ShortPickReason::parse(reason)?,
+                missing: *missing,
+            })
+        });
+        item.verification = saved.verification.clone();
+        item
+    }
+
+    /// The item with its pick state, for saving the session
+    pub fn to_saved(&self) -> SavedPickItem {
+        SavedPickItem {
+            card: self.card.clone(),
+            set_name: self.set_name.clone(),
+            quantity: self.quantity,
+            picked: self.picked,
+            picked_by: self.picked_by.clone(),
+            picked_at: self.picked_at.clone(),
+            short_pick: self
+                .short_pick
+                .as_ref()
+                .map(|s| (s.reason.as_str().to_string(), s.missing)),
+            verification: self.verification.clone(),
+        }
+    }
 }
 
 /// State for the picking screen
@@ -81,6 +222,44 @@ pub struct PickingState {
     pub total_price: f64,
     /// Price of picked items
     pub picked_price: f64,
+    /// Name of the person picking, stamped onto each item as it is picked
+    pub picker_name: String,
+    /// Set once the session's picks were removed from inventory, so they are
+    /// never decremented twice
+    pub archived: bool,
+    /// Why the last archive attempt failed, shown under the header; the
+    /// session stays unarchived so it can be retried
+    pub archive_error: Option<String>,
+    /// Item under the keyboard cursor (index into `items`)
+    pub cursor: Option<usize>,
+    /// Filter on card name, set and location (case-insensitive)
+    pub search: String,
+    /// Fullscreen one-card-at-a-time display for warehouse touchscreens
+    pub warehouse_mode: bool,
+    /// One-at-a-time check of the picked items by a second person
+    pub verify_mode: bool,
+    /// Name of the person checking the picks
+    pub verifier_name: String,
+    /// Picked item being checked (index into `items`)
+    pub verify_cursor: Option<usize>,
+    /// Why the last check was refused, shown in verification mode
+    pub verify_error: Option<String>,
+    /// The saved session this list belongs to; `None` for unsaved lists
+    pub session: Option<SavedSession>,
+    /// Set by every pick change, so the session is saved once per frame
+    pub session_dirty: bool,
+    /// Why the session could not be saved, shown in the header
+    pub session_error: Option<String>,
+    /// Set by `/` to move keyboard focus into the search box
+    focus_search: bool,
+    /// Set when the cursor moved, so the grid scrolls it into view
+    scroll_to_cursor: bool,
+    /// Columns of the last rendered grid, the step for Up/Down
+    grid_cols: usize,
+    /// Photo of a tile's listing, for condition disputes
+    photo: ListingPhotoWindow,
+    /// Copies entered in a tile's short-pick menu
+    short_missing: i32,
     /// Tokio runtime for async operations
     runtime: Runtime,
     /// Channel sender for background image loading
@@ -103,6 +282,24 @@ impl Default for PickingState {
             show_picked: false,
             total_price: 0.0,
             picked_price: 0.0,
+            picker_name: String::new(),
+            archived: false,
+            archive_error: None,
+            cursor: None,
+            search: String::new(),
+            warehouse_mode: false,
+            verify_mode: false,
+            verifier_name: String::new(),
+            verify_cursor: None,
+            verify_error: None,
+            session: None,
+            session_dirty: false,
+            session_error: None,
+            focus_search: false,
+            scroll_to_cursor: false,
+            grid_cols: 1,
+            photo: ListingPhotoWindow::default(),
+            short_missing: 1,
             runtime,
             image_sender: tx,
             image_receiver: rx,
@@ -121,8 +318,8 @@ impl PickingState {
             .flat_map(|(_, _, cards)| cards.iter().map(PickingItem::from_matched_card))
             .collect();
 
-        // Sort by location for efficient picking
-        items.sort_by(|a, b| a.location.cmp(&b.location));
+        // Walk the storage in export order (see crate::export_order)
+        items.sort_by(|a, b| compare_cards(&a.card, &b.card));
 
         let total_price: f64 = items.iter().map(|i| i.price * i.quantity as f64).sum();
 
@@ -134,6 +331,24 @@ impl PickingState {
             show_picked: false,
             total_price,
             picked_price: 0.0,
+            picker_name: String::new(),
+            archived: false,
+            archive_error: None,
+            cursor: None,
+            search: String::new(),
+            warehouse_mode: false,
+            verify_mode: false,
+            verifier_name: String::new(),
+            verify_cursor: None,
+            verify_error: None,
+            session: None,
+            session_dirty: false,
+            session_error: None,
+            focus_search: false,
+            scroll_to_cursor: false,
+            grid_cols: 1,
+            photo: ListingPhotoWindow::default(),
+            short_missing: 1,
             runtime,
             image_sender: tx,
             image_receiver: rx,
@@ -141,6 +356,48 @@ impl PickingState {
         }
     }
 
+    /// Reopens a saved session with its picks
+    pub fn from_saved(session: &SavedSession) -> Self {
+        let mut state = Self {
+            items: session.items.iter().map(PickingItem::from_saved).collect(),
+            total_price: session.total_value(),
+            picker_name: session.picker_name.clone(),
+            archived: session.archived,
+            session: Some(session.clone()),
+            ..Self::default()
+        };
+        state.update_picked_price();
+        state
+    }
+
+    /// Saves the list as a new session named `name` in the sessions file at
+    /// `path`
+    pub fn start_session(&mut self, path: &std::path::Path, name: &str) -> Result<(), String> {
+        let session = SavedSession::new(
+            name,
+            self.items.iter().map(PickingItem::to_saved).collect(),
+            &now_timestamp(),
+        );
+        let (_, id) = update_sessions(path, |sessions| add_session(sessions, session.clone()))?;
+        self.session = Some(SavedSession { id, ..session });
+        Ok(())
+    }
+
+    /// Writes the current picks into the session's entry in the sessions
+    /// file at `path`. A no-op for lists without a session.
+    pub fn save_session(&mut self, path: &std::path::Path) -> Result<(), String> {
+        self.session_dirty = false;
+        let Some(session) = &mut self.session else {
+            return Ok(());
+        };
+        session.items = self.items.iter().map(PickingItem::to_saved).collect();
+        session.picker_name = self.picker_name.trim().to_string();
+        session.archived = self.archived;
+        session.updated_at = now_timestamp();
+        let session = session.clone();
+        update_sessions(path, |sessions| replace_session(sessions, session)).map(|_| ())
+    }
+
     /// Count of picked items
     pub fn picked_count(&self) -> usize {
         self.items.iter().filter(|i| i.picked).count()
@@ -156,10 +413,455 @@ impl PickingState {
         self.picked_price = self
             .items
             .iter()
-            .filter(|i| i.picked)
-            .map(|i| i.price * i.quantity as f64)
+            .map(|i| i.price * i.picked_quantity() as f64)
             .sum();
     }
+
+    /// Count of short-picked items
+    pub fn short_pick_count(&self) -> usize {
+        self.items.iter().filter(|i| i.short_pick.is_some()).count()
+    }
+
+    /// Marks an item picked short: `missing` copies (clamped to the item's
+    /// quantity, at least one) could not be picked for `reason`. Replaces an
+    /// earlier short pick of the same item; the picker and time are only
+    /// stamped if the item wasn't picked yet.
+    pub fn set_short_pick(&mut self, index: usize, reason: ShortPickReason, missing: i32) {
+        let timestamp = now_timestamp();
+        if let Some(item) = self.items.get_mut(index) {
+            apply_pick(item, true, &self.picker_name, &timestamp);
+            // The pile changed, so an earlier check no longer holds
+            item.verification = None;
+            item.short_pick = Some(ShortPick {
+                reason,
+                missing: missing.clamp(1, item.quantity.max(1)),
+            });
+        }
+        self.session_dirty = true;
+        self.update_picked_price();
+    }
+
+    /// Marks a single item picked (stamping picker and time) or un-picked
+    /// (clearing both), then refreshes the picked price.
+    pub fn set_picked(&mut self, index: usize, picked: bool) {
+        let timestamp = now_timestamp();
+        if let Some(item) = self.items.get_mut(index) {
+            apply_pick(item, picked, &self.picker_name, &timestamp);
+        }
+        self.session_dirty = true;
+        self.update_picked_price();
+    }
+
+    /// Marks every item picked or un-picked. Items already in the requested
+    /// state keep their original picker and timestamp.
+    pub fn set_all_picked(&mut self, picked: bool) {
+        let timestamp = now_timestamp();
+        for item in &mut self.items {
+            apply_pick(item, picked, &self.picker_name, &timestamp);
+        }
+        self.session_dirty = true;
+        self.update_picked_price();
+    }
+
+    /// Indices of the items shown in the list: unpicked ones (plus picked ones
+    /// when `show_picked` is set) matching the search text.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        let needle = self.search.trim().to_lowercase();
+        (0..self.items.len())
+            .filter(|&i| {
+                let item = &self.items[i];
+                (!item.picked || self.show_picked)
+                    && (needle.is_empty()
+                        || [&item.card_name, &item.set_name, &item.location]
+                            .iter()
+                            .any(|field| field.to_lowercase().contains(&needle)))
+            })
+            .collect()
+    }
+
+    /// Moves the cursor `delta` positions through the visible list, clamped
+    /// to its ends. Without a cursor (or when its item is hidden) it lands on
+    /// the first visible item.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let visible = self.visible_indices();
+        if visible.is_empty() {
+            self.cursor = None;
+            return;
+        }
+        let next = match self
+            .cursor
+            .and_then(|c| visible.iter().position(|&i| i == c))
+        {
+            Some(pos) => pos.saturating_add_signed(delta).min(visible.len() - 1),
+            None => 0,
+        };
+        self.cursor = Some(visible[next]);
+        self.scroll_to_cursor = true;
+    }
+
+    /// Moves the cursor one grid row up (`rows < 0`) or down.
+    pub fn move_cursor_rows(&mut self, rows: isize) {
+        self.move_cursor(rows * self.grid_cols.max(1) as isize);
+    }
+
+    /// Moves the cursor to the next unpicked visible item after it, wrapping
+    /// around. Leaves the cursor alone when everything is picked.
+    pub fn jump_to_next_unpicked(&mut self) {
+        let visible = self.visible_indices();
+        let start = self
+            .cursor
+            .and_then(|c| visible.iter().position(|&i| i == c))
+            .map_or(0, |pos| pos + 1);
+        let next = (0..visible.len())
+            .map(|offset| visible[(start + offset) % visible.len()])
+            .find(|&i| !self.items[i].picked);
+        if let Some(i) = next {
+            self.cursor = Some(i);
+            self.scroll_to_cursor = true;
+        }
+    }
+
+    /// Toggles the item under the cursor. When picking hides it from the
+    /// list, the cursor moves on to the following visible item.
+    pub fn toggle_cursor(&mut self) {
+        let Some(index) = self.cursor else {
+            return;
+        };
+        let before = self.visible_indices();
+        let Some(pos) = before.iter().position(|&i| i == index) else {
+            return;
+        };
+        let picked = !self.items[index].picked;
+        self.set_picked(index, picked);
+
+        let after = self.visible_indices();
+        if !after.contains(&index) {
+            // The next item in the old order, else the previous one
+            self.cursor = before[pos + 1..]
+                .iter()
+                .chain(before[..pos].iter().rev())
+                .copied()
+                .find(|i| after.contains(i));
+            self.scroll_to_cursor = true;
+        }
+    }
+
+    /// Turns warehouse mode on or off. Entering it puts the cursor on the
+    /// next unpicked item unless it already rests on one.
+    pub fn set_warehouse_mode(&mut self, on: bool) {
+        self.warehouse_mode = on;
+        if on && self.cursor.is_none_or(|i| self.items[i].picked) {
+            self.jump_to_next_unpicked();
+        }
+    }
+
+    /// Warehouse mode's pick: marks the item under the cursor picked (short
+    /// when `short` is given) and moves on to the next unpicked item. The
+    /// cursor is cleared once everything is picked.
+    pub fn pick_and_advance(&mut self, short: Option<(ShortPickReason, i32)>) {
+        let Some(index) = self.cursor.filter(|&i| !self.items[i].picked) else {
+            self.jump_to_next_unpicked();
+            return;
+        };
+        self.jump_to_next_unpicked();
+        match short {
+            Some((reason, missing)) => self.set_short_pick(index, reason, missing),
+            None => self.set_picked(index, true),
+        }
+        if self.cursor == Some(index) {
+            self.cursor = None;
+        }
+    }
+
+    /// Items with copies to check: everything picked except items short of
+    /// every copy.
+    fn verifiable(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.items.len()).filter(|&i| self.items[i].picked_quantity() > 0)
+    }
+
+    /// Picked items confirmed correct, and the number of picked items.
+    pub fn verification_progress(&self) -> (usize, usize) {
+        let verified = self
+            .verifiable()
+            .filter(|&i| {
+                self.items[i]
+                    .verification
+                    .as_ref()
+                    .is_some_and(|v| v.correct)
+            })
+            .count();
+        (verified, self.verifiable().count())
+    }
+
+    /// Picked items the checker flagged as wrong.
+    pub fn rejected_count(&self) -> usize {
+        self.verifiable()
+            .filter(|&i| {
+                self.items[i]
+                    .verification
+                    .as_ref()
+                    .is_some_and(|v| !v.correct)
+            })
+            .count()
+    }
+
+    /// Whether every picked item was confirmed correct.
+    pub fn is_verified(&self) -> bool {
+        let (verified, total) = self.verification_progress();
+        total > 0 && verified == total
+    }
+
+    /// Enters or leaves verification mode. Entering it starts at the first
+    /// picked item not yet confirmed.
+    pub fn set_verify_mode(&mut self, on: bool) {
+        self.verify_mode = on;
+        self.verify_error = None;
+        if on {
+            self.verify_cursor = None;
+            self.next_to_verify();
+        }
+    }
+
+    /// Moves the verification cursor to the next picked item after it that
+    /// is not confirmed yet, wrapping around; `None` once all are confirmed.
+    pub fn next_to_verify(&mut self) {
+        let pending: Vec<usize> = self
+            .verifiable()
+            .filter(|&i| {
+                !self.items[i]
+                    .verification
+                    .as_ref()
+                    .is_some_and(|v| v.correct)
+            })
+            .collect();
+        let after = self.verify_cursor.map_or(0, |c| c + 1);
+        self.verify_cursor = pending
+            .iter()
+            .find(|&&i| i >= after)
+            .or_else(|| pending.first())
+            .copied();
+    }
+
+    /// Records the checker's verdict on the item under the verification
+    /// cursor and moves on. Refused without a checker name, or when the
+    /// checker is the one who picked the item: the point is a second pair of
+    /// eyes.
+    pub fn verify_current(&mut self, correct: bool) -> Result<(), String> {
+        let Some(index) = self.verify_cursor else {
+            return Ok(());
+        };
+        let verifier = self.verifier_name.trim();
+        if verifier.is_empty() {
+            return Err("Enter the checker's name first".to_string());
+        }
+        let item = &mut self.items[index];
+        if item.picked_by.trim().eq_ignore_ascii_case(verifier) {
+            return Err(format!(
+                "{verifier} picked this item; someone else has to check it"
+            ));
+        }
+        item.verification = Some(Verification {
+            verified_by: verifier.to_string(),
+            verified_at: now_timestamp(),
+            correct,
+        });
+        if !correct {
+            warn!(
+                "{verifier} flagged {} at {} as wrongly picked",
+                item.card_name, item.location
+            );
+        }
+        self.session_dirty = true;
+        self.next_to_verify();
+        Ok(())
+    }
+
+    /// Picked items as `(listing, copies)` pairs for the inventory decrement.
+    /// Short-picked copies are left in stock.
+    pub fn picked_removals(&self) -> Vec<(Card, i64)> {
+        self.items
+            .iter()
+            .filter(|i| i.picked_quantity() > 0)
+            .map(|i| (i.card.clone(), i.picked_quantity() as i64))
+            .collect()
+    }
+}
+
+/// What a tile's buttons asked for
+enum TileAction {
+    Pick,
+    Undo,
+    Short(ShortPickReason),
+    Photo,
+}
+
+/// What a warehouse-mode button asked for
+enum WarehouseAction {
+    Pick,
+    Short(ShortPickReason),
+    Browse(isize),
+}
+
+/// Generates the stock-update CSV removing every picked item from Cardmarket
+/// stock (negative quantities, inventory-report schema).
+pub fn format_picked_stock_csv(items: &[PickingItem]) -> String {
+    let matched: Vec<MatchedCard> = items
+        .iter()
+        .filter(|i| i.picked_quantity() > 0)
+        .map(|i| MatchedCard {
+            card: &i.card,
+            quantity: i.picked_quantity(),
+            set_name: i.set_name.clone(),
+        })
+        .collect();
+    format_update_stock_csv(&matched)
+}
+
+/// Returns the current local time in the pick-log timestamp format.
+fn now_timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Applies a pick state change to one item. A no-op when the item is already in
+/// the requested state, so re-picking never overwrites who picked it first.
+fn apply_pick(item: &mut PickingItem, picked: bool, picker: &str, timestamp: &str) {
+    if item.picked == picked {
+        return;
+    }
+    item.picked = picked;
+    if picked {
+        item.picked_by = picker.trim().to_string();
+        item.picked_at = Some(timestamp.to_string());
+    } else {
+        item.picked_by.clear();
+        item.picked_at = None;
+        item.short_pick = None;
+        item.verification = None;
+    }
+}
+
+/// Generates the pick-log CSV for a session: one row per item in picking order,
+/// with the picker and timestamp for picked items and empty columns otherwise.
+/// Checked items also carry the checker, the time and `ok` or `wrong`.
+/// `itemId` is the listing's stable ID (see [`item_id`]).
+pub fn format_pick_log_csv(items: &[PickingItem]) -> String {
+    use csv::WriterBuilder;
+
+    let mut wtr = WriterBuilder::new().has_headers(true).from_writer(vec![]);
+
+    let _ = wtr.write_record([
+        "itemId",
+        "location",
+        "name",
+        "set",
+        "setCode",
+        "cn",
+        "condition",
+        "language",
+        "isFoil",
+        "quantity",
+        "price",
+        "picked",
+        "pickedBy",
+        "pickedAt",
+        "shortPickReason",
+        "missingQuantity",
+        "verifiedBy",
+        "verifiedAt",
+        "verification",
+    ]);
+
+    for item in items {
+        let verification = item.verification.as_ref();
+        let _ = wtr.write_record([
+            item_id(&item.card).as_str(),
+            item.location.as_str(),
+            &item.card_name,
+            &item.set_name,
+            &item.set_code,
+            &item.collector_number,
+            &item.condition,
+            &item.language,
+            if item.is_foil { "true" } else { "false" },
+            &item.quantity.to_string(),
+            &format!("{:.2}", item.price),
+            if item.picked { "true" } else { "false" },
+            &item.picked_by,
+            item.picked_at.as_deref().unwrap_or(""),
+            item.short_pick.as_ref().map_or("", |s| s.reason.as_str()),
+            &item
+                .short_pick
+                .as_ref()
+                .map_or(String::new(), |s| s.missing.to_string()),
+            verification.map_or("", |v| v.verified_by.as_str()),
+            verification.map_or("", |v| v.verified_at.as_str()),
+            verification.map_or("", |v| if v.correct { "ok" } else { "wrong" }),
+        ]);
+    }
+
+    let data = wtr.into_inner().unwrap();
+    String::from_utf8(data).unwrap()
+}
+
+/// Generates the discrepancies report: one row per short-picked item with
+/// the expected and missing copies, the reason, who reported it and the
+/// inventory DB's quantity for the variant (`db_quantities` is parallel to
+/// `items`; empty when the variant isn't in the DB or it couldn't be read).
+pub fn format_discrepancies_csv(items: &[PickingItem], db_quantities: &[Option<i64>]) -> String {
+    use csv::WriterBuilder;
+
+    let mut wtr = WriterBuilder::new().has_headers(true).from_writer(vec![]);
+
+    let _ = wtr.write_record([
+        "itemId",
+        "location",
+        "name",
+        "set",
+        "setCode",
+        "cn",
+        "cardmarketId",
+        "condition",
+        "language",
+        "isFoil",
+        "expectedQuantity",
+        "missingQuantity",
+        "reason",
+        "reportedBy",
+        "reportedAt",
+        "dbQuantity",
+    ]);
+
+    for (index, item) in items.iter().enumerate() {
+        let Some(short) = &item.short_pick else {
+            continue;
+        };
+        let db_quantity = db_quantities
+            .get(index)
+            .copied()
+            .flatten()
+            .map_or(String::new(), |q| q.to_string());
+        let _ = wtr.write_record([
+            item_id(&item.card).as_str(),
+            item.location.as_str(),
+            &item.card_name,
+            &item.set_name,
+            &item.set_code,
+            &item.collector_number,
+            &item.card.cardmarket_id,
+            &item.condition,
+            &item.language,
+            if item.is_foil { "true" } else { "false" },
+            &item.quantity.to_string(),
+            &short.missing.to_string(),
+            short.reason.as_str(),
+            &item.picked_by,
+            item.picked_at.as_deref().unwrap_or(""),
+            &db_quantity,
+        ]);
+    }
+
+    let data = wtr.into_inner().unwrap();
+    String::from_utf8(data).unwrap()
 }
 
 pub struct PickingScreen;
@@ -168,6 +870,21 @@ impl PickingScreen {
     pub fn show(ctx: &egui::Context, current_screen: &mut Screen, state: &mut PickingState) {
         // Poll for loaded images from background tasks (non-blocking)
         Self::poll_loaded_images(ctx, state);
+        if state.session_dirty {
+            state.session_error = state.save_session(&sessions_path()).err();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F11)) {
+            Self::toggle_warehouse_mode(ctx, state);
+        }
+        if state.warehouse_mode {
+            Self::show_warehouse(ctx, state);
+            return;
+        }
+        if state.verify_mode {
+            show_verification(ctx, state);
+            return;
+        }
+        Self::handle_keys(ctx, state);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             // Header with back button and progress
@@ -175,6 +892,13 @@ impl PickingScreen {
                 if ui.button("← Back to Stock Checker").clicked() {
                     *current_screen = Screen::StockChecker;
                 }
+                if ui
+                    .button("Sessions")
+                    .on_hover_text("Saved picking sessions")
+                    .clicked()
+                {
+                    *current_screen = Screen::PickingSessions;
+                }
 
                 ui.add_space(20.0);
 
@@ -202,6 +926,29 @@ impl PickingScreen {
                     state.picked_price, state.total_price
                 ));
 
+                let short = state.short_pick_count();
+                if short > 0 {
+                    ui.add_space(10.0);
+                    ui.label(
+                        egui::RichText::new(format!("{short} short"))
+                            .color(egui::Color32::from_rgb(230, 160, 60)),
+                    );
+                }
+
+                let (verified, verifiable) = state.verification_progress();
+                if verified > 0 {
+                    ui.add_space(10.0);
+                    ui.label(format!("Verified: {verified}/{verifiable}"));
+                }
+                let rejected = state.rejected_count();
+                if rejected > 0 {
+                    ui.add_space(10.0);
+                    ui.label(
+                        egui::RichText::new(format!("{rejected} wrong"))
+                            .color(egui::Color32::from_rgb(210, 75, 75)),
+                    );
+                }
+
                 // Loading indicator
                 let loading_count = state.loading_images.len();
                 if loading_count > 0 {
@@ -215,25 +962,156 @@ impl PickingScreen {
 
             // Controls
             ui.horizontal(|ui| {
-                ui.checkbox(&mut state.show_picked, "Show picked items");
+                ui.label("Picker:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.picker_name)
+                        .hint_text("Name or initials")
+                        .desired_width(120.0),
+                );
+
+                ui.add_space(20.0);
+
+                ui.label("Search:");
+                let search = ui.add(
+                    egui::TextEdit::singleline(&mut state.search)
+                        .hint_text("Name, set or location  ( / )")
+                        .desired_width(180.0),
+                );
+                if std::mem::take(&mut state.focus_search) {
+                    search.request_focus();
+                }
+                if search.changed() {
+                    state.move_cursor(0);
+                }
+                if search.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    state.search.clear();
+                    state.move_cursor(0);
+                }
+
+                ui.add_space(20.0);
+
+                if ui
+                    .checkbox(&mut state.show_picked, "Show picked items")
+                    .changed()
+                {
+                    state.move_cursor(0);
+                }
 
                 ui.add_space(20.0);
 
+                if ui
+                    .button("Warehouse Mode")
+                    .on_hover_text("Fullscreen touchscreen view, one card at a time (F11)")
+                    .clicked()
+                {
+                    Self::toggle_warehouse_mode(ctx, state);
+                }
+
                 if ui.button("Reset All").clicked() {
-                    for item in &mut state.items {
-                        item.picked = false;
-                    }
-                    state.update_picked_price();
+                    state.set_all_picked(false);
                 }
 
                 if ui.button("Mark All Picked").clicked() {
-                    for item in &mut state.items {
-                        item.picked = true;
-                    }
-                    state.update_picked_price();
+                    state.set_all_picked(true);
+                }
+
+                if ui
+                    .add_enabled(
+                        state.picked_count() > 0,
+                        egui::Button::new("Export Pick Log"),
+                    )
+                    .clicked()
+                {
+                    Self::export_pick_log(state);
+                }
+
+                if ui
+                    .add_enabled(
+                        state.short_pick_count() > 0,
+                        egui::Button::new("Export Discrepancies"),
+                    )
+                    .on_hover_text(
+                        "Short picks with their reasons and the inventory DB's quantity, \
+                         to reconcile stock",
+                    )
+                    .clicked()
+                {
+                    Self::export_discrepancies(state);
+                }
+
+                if ui
+                    .add_enabled(state.picked_count() > 0, egui::Button::new("Verify Picks…"))
+                    .on_hover_text(
+                        "Second-person check: go through the picked cards one by one \
+                         and confirm each before shipping",
+                    )
+                    .clicked()
+                {
+                    state.set_verify_mode(true);
+                }
+
+                if ui
+                    .add_enabled(
+                        state.picked_count() > 0 && !state.archived && state.rejected_count() == 0,
+                        egui::Button::new("Archive Session…"),
+                    )
+                    .on_hover_text(
+                        "Remove the picked copies from the inventory DB and export a \
+                         stock-update CSV to import into Cardmarket",
+                    )
+                    .on_disabled_hover_text(
+                        "Nothing picked yet, already archived, or a picked card was \
+                         flagged wrong in verification and needs checking again",
+                    )
+                    .clicked()
+                {
+                    Self::archive_session(state);
                 }
             });
 
+            if let Some(session) = &state.session {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Session \"{}\" started {}",
+                        session.name, session.created_at
+                    ))
+                    .size(11.0)
+                    .color(egui::Color32::GRAY),
+                );
+            }
+            if let Some(err) = &state.session_error {
+                ui.label(egui::RichText::new(err).color(egui::Color32::from_rgb(210, 75, 75)));
+            }
+
+            if let Some(err) = &state.archive_error {
+                ui.label(egui::RichText::new(err).color(egui::Color32::from_rgb(210, 75, 75)));
+            }
+
+            if state.archived {
+                ui.label(
+                    egui::RichText::new(
+                        "Session archived: picked copies were removed from inventory.",
+                    )
+                    .color(egui::Color32::GRAY),
+                );
+            } else if state.total_count() > 0 && state.picked_count() == state.total_count() {
+                let text = if state.is_verified() {
+                    "All items picked and verified. Archive the session to remove them from stock."
+                } else {
+                    "All items picked. Archive the session to remove them from stock."
+                };
+                ui.label(egui::RichText::new(text).color(egui::Color32::LIGHT_GREEN));
+            }
+
+            ui.label(
+                egui::RichText::new(
+                    "Keys: arrows move • Space pick/undo • F next unpicked • / search • \
+                     Esc clear search • F11 warehouse mode",
+                )
+                .size(11.0)
+                .color(egui::Color32::GRAY),
+            );
+
             ui.separator();
 
             // Picking list
@@ -243,18 +1121,251 @@ impl PickingScreen {
                     Self::show_picking_list(ctx, ui, state);
                 });
         });
+        state.photo.show(ctx);
+    }
+
+    /// Applies the list shortcuts, unless a text field has keyboard focus.
+    fn handle_keys(ctx: &egui::Context, state: &mut PickingState) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        // Consume the keys so Space doesn't also click a focused button
+        let pressed = |key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key));
+        if pressed(egui::Key::ArrowRight) {
+            state.move_cursor(1);
+        }
+        if pressed(egui::Key::ArrowLeft) {
+            state.move_cursor(-1);
+        }
+        if pressed(egui::Key::ArrowDown) {
+            state.move_cursor_rows(1);
+        }
+        if pressed(egui::Key::ArrowUp) {
+            state.move_cursor_rows(-1);
+        }
+        if pressed(egui::Key::Space) {
+            if state.cursor.is_some() {
+                state.toggle_cursor();
+            } else {
+                state.move_cursor(0);
+            }
+        }
+        if pressed(egui::Key::F) {
+            state.jump_to_next_unpicked();
+        }
+        if pressed(egui::Key::Slash) {
+            state.focus_search = true;
+        }
+    }
+
+    /// Enters or leaves warehouse mode, taking the window fullscreen with it.
+    fn toggle_warehouse_mode(ctx: &egui::Context, state: &mut PickingState) {
+        state.set_warehouse_mode(!state.warehouse_mode);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(state.warehouse_mode));
+    }
+
+    const WAREHOUSE_BG: egui::Color32 = egui::Color32::BLACK;
+    const WAREHOUSE_FG: egui::Color32 = egui::Color32::WHITE;
+    const WAREHOUSE_ACCENT: egui::Color32 = egui::Color32::from_rgb(255, 215, 0);
+    const WAREHOUSE_BUTTON: egui::Vec2 = egui::vec2(220.0, 90.0);
+
+    /// Warehouse mode: the card under the cursor in very large type on black,
+    /// with touch-sized buttons. Space or Enter picks and advances, the arrow
+    /// keys browse and Esc (or F11) leaves.
+    fn show_warehouse(ctx: &egui::Context, state: &mut PickingState) {
+        if !ctx.wants_keyboard_input() {
+            let pressed = |key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key));
+            if pressed(egui::Key::Space) || pressed(egui::Key::Enter) {
+                state.pick_and_advance(None);
+            }
+            if pressed(egui::Key::ArrowRight) {
+                state.move_cursor(1);
+            }
+            if pressed(egui::Key::ArrowLeft) {
+                state.move_cursor(-1);
+            }
+            if pressed(egui::Key::Escape) {
+                Self::toggle_warehouse_mode(ctx, state);
+                return;
+            }
+        }
+
+        let big_button = |ui: &mut egui::Ui, text: &str, fill: egui::Color32| {
+            ui.add(
+                egui::Button::new(
+                    egui::RichText::new(text)
+                        .size(36.0)
+                        .strong()
+                        .color(Self::WAREHOUSE_FG),
+                )
+                .fill(fill)
+                .min_size(Self::WAREHOUSE_BUTTON),
+            )
+            .clicked()
+        };
+
+        let frame = egui::Frame::central_panel(&ctx.style())
+            .fill(Self::WAREHOUSE_BG)
+            .inner_margin(24.0);
+        egui::CentralPanel::default().frame(frame).show(ctx, |ui| {
+            let picked = state.picked_count();
+            let total = state.total_count();
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{picked} / {total} picked"))
+                        .size(40.0)
+                        .strong()
+                        .color(Self::WAREHOUSE_FG),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if big_button(ui, "Exit", egui::Color32::from_gray(60)) {
+                        Self::toggle_warehouse_mode(ctx, state);
+                    }
+                });
+            });
+            ui.add(
+                egui::ProgressBar::new(if total > 0 {
+                    picked as f32 / total as f32
+                } else {
+                    0.0
+                })
+                .desired_height(24.0)
+                .fill(Self::WAREHOUSE_ACCENT),
+            );
+            ui.add_space(24.0);
+
+            let Some(index) = state.cursor else {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(80.0);
+                    ui.label(
+                        egui::RichText::new("All items picked ✓")
+                            .size(72.0)
+                            .strong()
+                            .color(egui::Color32::LIGHT_GREEN),
+                    );
+                });
+                return;
+            };
+
+            let item = &state.items[index];
+            let image_key = item.image_key();
+            if !state.images.contains_key(&image_key)
+                && !state.loading_images.contains(&image_key)
+                && !item.set_code.is_empty()
+                && !item.collector_number.is_empty()
+            {
+                Self::load_card_image(ctx, state, index);
+            }
+            let item = &state.items[index];
+
+            let mut action = None;
+            ui.horizontal_top(|ui| {
+                if let Some(texture) = state.images.get(&image_key) {
+                    let height = (ui.available_height() - Self::WAREHOUSE_BUTTON.y - 40.0)
+                        .clamp(200.0, 680.0);
+                    let aspect = texture.size()[0] as f32 / texture.size()[1] as f32;
+                    ui.image((texture.id(), egui::vec2(height * aspect, height)));
+                    ui.add_space(32.0);
+                }
+                ui.vertical(|ui| {
+                    let location = if item.location.is_empty() {
+                        "No location"
+                    } else {
+                        item.location.as_str()
+                    };
+                    ui.label(
+                        egui::RichText::new(location)
+                            .size(96.0)
+                            .strong()
+                            .color(Self::WAREHOUSE_ACCENT),
+                    );
+                    ui.label(
+                        egui::RichText::new(&item.card_name)
+                            .size(56.0)
+                            .strong()
+                            .color(Self::WAREHOUSE_FG),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!("Qty {}", item.quantity))
+                            .size(72.0)
+                            .strong()
+                            .color(Self::WAREHOUSE_FG),
+                    );
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} • {} • {}{}",
+                            item.set_name,
+                            item.condition,
+                            item.language,
+                            if item.is_foil { " • FOIL" } else { "" }
+                        ))
+                        .size(36.0)
+                        .color(Self::WAREHOUSE_FG),
+                    );
+                    if item.picked {
+                        ui.label(
+                            egui::RichText::new("Already picked")
+                                .size(36.0)
+                                .color(egui::Color32::LIGHT_GREEN),
+                        );
+                    }
+                    ui.add_space(32.0);
+
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 24.0;
+                        if big_button(ui, "◀", egui::Color32::from_gray(60)) {
+                            action = Some(WarehouseAction::Browse(-1));
+                        }
+                        if !item.picked
+                            && big_button(ui, "PICK", egui::Color32::from_rgb(0, 130, 50))
+                        {
+                            action = Some(WarehouseAction::Pick);
+                        }
+                        if big_button(ui, "▶", egui::Color32::from_gray(60)) {
+                            action = Some(WarehouseAction::Browse(1));
+                        }
+                    });
+                    ui.add_space(16.0);
+                    if !item.picked {
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 24.0;
+                            for reason in ShortPickReason::ALL {
+                                if big_button(
+                                    ui,
+                                    reason.label(),
+                                    egui::Color32::from_rgb(150, 80, 0),
+                                ) {
+                                    action = Some(WarehouseAction::Short(reason));
+                                }
+                            }
+                        });
+                    }
+                });
+            });
+
+            match action {
+                Some(WarehouseAction::Pick) => state.pick_and_advance(None),
+                Some(WarehouseAction::Short(reason)) => {
+                    // No copies found; partial counts need the regular list
+                    let missing = state.items[index].quantity;
+                    state.pick_and_advance(Some((reason, missing)));
+                }
+                Some(WarehouseAction::Browse(delta)) => state.move_cursor(delta),
+                None => {}
+            }
+        });
     }
 
     const CARD_TILE_WIDTH: f32 = 260.0;
     const CARD_IMAGE_HEIGHT: f32 = 360.0;
 
     fn show_picking_list(ctx: &egui::Context, ui: &mut egui::Ui, state: &mut PickingState) {
-        let mut price_changed = false;
+        let mut action: Option<(usize, TileAction)> = None;
 
-        // Collect all visible item indices
-        let visible: Vec<usize> = (0..state.items.len())
-            .filter(|&i| !state.items[i].picked || state.show_picked)
-            .collect();
+        let visible = state.visible_indices();
+        if visible.is_empty() && !state.search.trim().is_empty() {
+            ui.label(egui::RichText::new("No cards match the search.").weak());
+        }
 
         // Trigger image loading for all visible items
         for &i in &visible {
@@ -271,6 +1382,8 @@ impl PickingScreen {
 
         let available_width = ui.available_width();
         let cols = ((available_width / Self::CARD_TILE_WIDTH).floor() as usize).max(1);
+        state.grid_cols = cols;
+        let scroll_to_cursor = std::mem::take(&mut state.scroll_to_cursor);
 
         // Single grid for all cards
         egui::Grid::new("picking_grid")
@@ -286,90 +1399,169 @@ impl PickingScreen {
                     let gray = egui::Color32::GRAY;
 
                     // Each grid cell: vertical group with image on top, info below
-                    let response = ui
-                        .vertical(|ui| {
-                            // Card image
-                            if let Some(texture) = state.images.get(&image_key) {
-                                let aspect = texture.size()[0] as f32 / texture.size()[1] as f32;
-                                let width = Self::CARD_IMAGE_HEIGHT * aspect;
-                                let size = egui::vec2(width, Self::CARD_IMAGE_HEIGHT);
-
-                                if picked {
-                                    ui.add(egui::Image::new((texture.id(), size)).tint(
-                                        egui::Color32::from_rgba_unmultiplied(128, 128, 128, 180),
-                                    ));
-                                } else {
-                                    ui.image((texture.id(), size));
-                                }
-                            } else {
-                                ui.add_sized(
-                                    [Self::CARD_TILE_WIDTH, Self::CARD_IMAGE_HEIGHT],
-                                    egui::Label::new(egui::RichText::new("Loading...").weak()),
-                                );
-                            }
+                    let response = ui.vertical(|ui| {
+                        // Card image
+                        if let Some(texture) = state.images.get(&image_key) {
+                            let aspect = texture.size()[0] as f32 / texture.size()[1] as f32;
+                            let width = Self::CARD_IMAGE_HEIGHT * aspect;
+                            let size = egui::vec2(width, Self::CARD_IMAGE_HEIGHT);
 
-                            // Location (prominent)
-                            let loc_text = if item.location.is_empty() {
-                                "No location".to_string()
-                            } else {
-                                item.location.clone()
-                            };
-                            let loc_style = if picked {
-                                egui::RichText::new(&loc_text)
-                                    .size(15.0)
-                                    .strong()
-                                    .color(gray)
+                            if picked {
+                                ui.add(egui::Image::new((texture.id(), size)).tint(
+                                    egui::Color32::from_rgba_unmultiplied(128, 128, 128, 180),
+                                ));
                             } else {
-                                egui::RichText::new(&loc_text)
-                                    .size(15.0)
-                                    .strong()
-                                    .color(egui::Color32::LIGHT_BLUE)
-                            };
-                            ui.label(loc_style);
-
-                            // Card name
-                            let name_text = if picked {
-                                egui::RichText::new(&item.card_name)
-                                    .size(16.0)
-                                    .strikethrough()
-                                    .color(gray)
+                                ui.image((texture.id(), size));
+                            }
+                        } else {
+                            ui.add_sized(
+                                [Self::CARD_TILE_WIDTH, Self::CARD_IMAGE_HEIGHT],
+                                egui::Label::new(egui::RichText::new("Loading...").weak()),
+                            );
+                        }
+
+                        // Location (prominent)
+                        let loc_text = if item.location.is_empty() {
+                            "No location".to_string()
+                        } else {
+                            item.location.clone()
+                        };
+                        let loc_style = if picked {
+                            egui::RichText::new(&loc_text)
+                                .size(15.0)
+                                .strong()
+                                .color(gray)
+                        } else {
+                            egui::RichText::new(&loc_text)
+                                .size(15.0)
+                                .strong()
+                                .color(egui::Color32::LIGHT_BLUE)
+                        };
+                        ui.label(loc_style);
+
+                        // Card name
+                        let name_text = if picked {
+                            egui::RichText::new(&item.card_name)
+                                .size(16.0)
+                                .strikethrough()
+                                .color(gray)
+                        } else {
+                            egui::RichText::new(&item.card_name).size(16.0).strong()
+                        };
+                        ui.label(name_text);
+
+                        // Card details
+                        let info = format!(
+                            "{} • {}\nQty: {} • {:.2} €\n{}{}",
+                            item.set_name,
+                            item.condition,
+                            item.quantity,
+                            item.price,
+                            item.language,
+                            if item.is_foil { " • Foil" } else { "" }
+                        );
+                        let info_text = if picked {
+                            egui::RichText::new(info).size(14.0).color(gray)
+                        } else {
+                            egui::RichText::new(info).size(14.0)
+                        };
+                        ui.label(info_text);
+
+                        // Who picked it and when
+                        if let Some(at) = &item.picked_at {
+                            let by = if item.picked_by.is_empty() {
+                                "?"
                             } else {
-                                egui::RichText::new(&item.card_name).size(16.0).strong()
+                                item.picked_by.as_str()
                             };
-                            ui.label(name_text);
-
-                            // Card details
-                            let info = format!(
-                                "{} • {}\nQty: {} • {:.2} €\n{}{}",
-                                item.set_name,
-                                item.condition,
-                                item.quantity,
-                                item.price,
-                                item.language,
-                                if item.is_foil { " • Foil" } else { "" }
+                            ui.label(
+                                egui::RichText::new(format!("Picked by {by} at {at}"))
+                                    .size(12.0)
+                                    .color(gray),
                             );
-                            let info_text = if picked {
-                                egui::RichText::new(info).size(14.0).color(gray)
+                        }
+
+                        if let Some(v) = &item.verification {
+                            let (text, color) = if v.correct {
+                                (
+                                    format!("Checked by {} at {}", v.verified_by, v.verified_at),
+                                    egui::Color32::LIGHT_GREEN,
+                                )
                             } else {
-                                egui::RichText::new(info).size(14.0)
+                                (
+                                    format!("Flagged wrong by {}", v.verified_by),
+                                    egui::Color32::from_rgb(210, 75, 75),
+                                )
                             };
-                            ui.label(info_text);
+                            ui.label(egui::RichText::new(text).size(12.0).color(color));
+                        }
+
+                        if let Some(short) = &item.short_pick {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Short {} of {}: {}",
+                                    short.missing,
+                                    item.quantity,
+                                    short.reason.label()
+                                ))
+                                .size(12.0)
+                                .color(egui::Color32::from_rgb(230, 160, 60)),
+                            );
+                        }
 
-                            // Pick/Undo button
+                        // Pick/Undo and short-pick buttons
+                        ui.horizontal(|ui| {
+                            let mut clicked = None;
                             if picked {
                                 if ui.button("Undo").clicked() {
-                                    return Some(false);
+                                    clicked = Some(TileAction::Undo);
                                 }
                             } else if ui.button("Pick").clicked() {
-                                return Some(true);
+                                clicked = Some(TileAction::Pick);
                             }
-                            None
+                            ui.menu_button("Short…", |ui| {
+                                if item.quantity > 1 {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Copies not found:");
+                                        ui.add(
+                                            egui::DragValue::new(&mut state.short_missing)
+                                                .range(1..=item.quantity),
+                                        );
+                                    });
+                                }
+                                for reason in ShortPickReason::ALL {
+                                    if ui.button(reason.label()).clicked() {
+                                        clicked = Some(TileAction::Short(reason));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                            if ui
+                                .small_button("📷")
+                                .on_hover_text("Photo of this listing")
+                                .clicked()
+                            {
+                                clicked = Some(TileAction::Photo);
+                            }
+                            clicked
                         })
-                        .inner;
+                        .inner
+                    });
+
+                    if state.cursor == Some(i) {
+                        ui.painter().rect_stroke(
+                            response.response.rect.expand(4.0),
+                            egui::CornerRadius::same(6),
+                            egui::Stroke::new(2.0_f32, egui::Color32::LIGHT_BLUE),
+                            egui::StrokeKind::Outside,
+                        );
+                        if scroll_to_cursor {
+                            response.response.scroll_to_me(Some(egui::Align::Center));
+                        }
+                    }
 
-                    if let Some(new_picked) = response {
-                        state.items[i].picked = new_picked;
-                        price_changed = true;
+                    if let Some(clicked) = response.inner {
+                        action = Some((i, clicked));
                     }
 
                     // End row after filling all columns
@@ -379,8 +1571,116 @@ impl PickingScreen {
                 }
             });
 
-        if price_changed {
-            state.update_picked_price();
+        match action {
+            Some((i, TileAction::Pick)) => state.set_picked(i, true),
+            Some((i, TileAction::Undo)) => state.set_picked(i, false),
+            Some((i, TileAction::Short(reason))) => {
+                let missing = if state.items[i].quantity > 1 {
+                    state.short_missing
+                } else {
+                    1
+                };
+                state.set_short_pick(i, reason, missing);
+                state.short_missing = 1;
+            }
+            Some((i, TileAction::Photo)) => state.photo.open(&state.items[i].card),
+            None => {}
+        }
+    }
+
+    /// Saves the session's pick log as CSV via a file dialog.
+    fn export_pick_log(state: &PickingState) {
+        let csv = format_pick_log_csv(&state.items);
+        let file_name = format!(
+            "pick_log_{}.csv",
+            chrono::Local::now().format("%Y-%m-%d_%H%M")
+        );
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&file_name)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            info!("Pick log export cancelled: no file chosen");
+            return;
+        };
+        match std::fs::write(&path, csv) {
+            Ok(()) => info!("Pick log exported to {}", path.display()),
+            Err(e) => error!("Failed to save pick log: {e}"),
+        }
+    }
+
+    /// Saves the discrepancies report as CSV via a file dialog. The DB
+    /// quantities are left empty if the inventory DB can't be read.
+    fn export_discrepancies(state: &PickingState) {
+        let cards: Vec<&Card> = state.items.iter().map(|i| &i.card).collect();
+        let db_quantities = crate::inventory_db::variant_quantities(&cards).unwrap_or_else(|e| {
+            warn!("Inventory DB lookup for discrepancies failed: {e}");
+            Vec::new()
+        });
+        let csv = format_discrepancies_csv(&state.items, &db_quantities);
+        let file_name = format!(
+            "pick_discrepancies_{}.csv",
+            chrono::Local::now().format("%Y-%m-%d_%H%M")
+        );
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&file_name)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            info!("Discrepancies export cancelled: no file chosen");
+            return;
+        };
+        match std::fs::write(&path, csv) {
+            Ok(()) => info!("Discrepancies report exported to {}", path.display()),
+            Err(e) => error!("Failed to save discrepancies report: {e}"),
+        }
+    }
+
+    /// Archives the session: exports the stock-update CSV for the picked items
+    /// (the save dialog acts as the confirmation gate), then decrements the
+    /// inventory DB with one audit row per variant.
+    fn archive_session(state: &mut PickingState) {
+        let csv = format_picked_stock_csv(&state.items);
+        let file_name = format!(
+            "picked_stock_update_{}.csv",
+            chrono::Local::now().format("%Y-%m-%d_%H%M")
+        );
+
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&file_name)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            info!("Session archive cancelled: no file chosen");
+            return;
+        };
+        if let Err(e) = std::fs::write(&path, csv) {
+            error!("Failed to save picked stock update: {e}");
+            return;
+        }
+        info!("Picked stock update exported to {}", path.display());
+
+        match crate::inventory_db::decrement_picked_cards(
+            &state.picked_removals(),
+            &state.picker_name,
+        ) {
+            Ok(stats) => {
+                info!(
+                    "Archived picking session: {} copies removed across {} variants",
+                    stats.copies_discarded, stats.variants_updated
+                );
+                state.archived = true;
+                state.archive_error = None;
+                state.session_dirty = true;
+            }
+            Err(e) => {
+                warn!("Inventory DB pick decrement failed: {e}");
+                state.archive_error = Some(format!(
+                    "Archive failed, nothing was removed from inventory: {e}"
+                ));
+            }
         }
     }
 
@@ -428,7 +1728,11 @@ impl PickingScreen {
     }
 
     /// Spawn a tokio task to load a card image
-    fn load_card_image(ctx: &egui::Context, state: &mut PickingState, item_index: usize) {
+    pub(super) fn load_card_image(
+        ctx: &egui::Context,
+        state: &mut PickingState,
+        item_index: usize,
+    ) {
         let item = &state.items[item_index];
         let set_code = item.set_code.clone();
         let collector_number = item.collector_number.clone();
@@ -512,8 +1816,9 @@ impl PickingScreen {
             set_code, collector_number
         );
 
-        // Fetch card metadata to get the image URL (handles DFCs via ScryfallCard::image_url())
-        let card = match fetch_card_async(&set_code, &collector_number).await {
+        // Card metadata for the image URL (handles DFCs via ScryfallCard::image_url());
+        // usually answered by the on-disk card cache
+        let card = match fetch_card_shared_async(&set_code, &collector_number).await {
             Ok(c) => c,
             Err(e) => {
                 error!(
@@ -0,0 +1,213 @@
+//! Saved Picking Sessions screen.
+//!
+//! Lists the picking sessions saved by the interactive picking list, newest
+//! first, with their wantslist, progress and value. A session can be
+//! reopened where it stopped, duplicated with its picks reset (for recurring
+//! picks such as weekly store orders) or deleted.
+
+use crate::{
+    picking_sessions::{
+        add_session, delete_session, load_sessions, sessions_path, update_sessions, SavedSession,
+    },
+    ui::{
+        screens::PickingState,
+        state::{PickingSessionsState, Screen},
+        style,
+    },
+};
+use eframe::egui;
+use log::info;
+
+/// What a row's buttons asked for
+enum SessionAction {
+    Reopen(usize),
+    Duplicate(usize),
+    Delete(String),
+}
+
+pub struct PickingSessionsScreen;
+
+impl PickingSessionsScreen {
+    pub fn show(
+        ctx: &egui::Context,
+        current_screen: &mut Screen,
+        state: &mut PickingSessionsState,
+        picking_state: &mut PickingState,
+    ) {
+        // The picking list saves after every pick, so re-read when it changed
+        let path = sessions_path();
+        let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if !state.loaded || mtime != state.loaded_mtime {
+            state.loaded = true;
+            state.loaded_mtime = mtime;
+            match load_sessions(&path) {
+                Ok(sessions) => {
+                    state.sessions = sessions;
+                    state.error = None;
+                }
+                Err(e) => state.error = Some(e),
+            }
+        }
+
+        let mut action = None;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .id_salt("picking_sessions_scroll")
+                .show(ui, |ui| {
+                    if style::back_button(ui, "Back") {
+                        *current_screen = Screen::Welcome;
+                    }
+                    ui.add_space(8.0);
+                    style::screen_heading(ui, "Picking Sessions");
+
+                    if let Some(err) = &state.error {
+                        style::status_error(ui, err);
+                        ui.add_space(6.0);
+                    }
+
+                    style::section_frame().show(ui, |ui| {
+                        if state.sessions.is_empty() {
+                            ui.label(
+                                egui::RichText::new(
+                                    "No saved sessions yet. Sessions are saved when an \
+                                     interactive picking list is opened from the Stock Checker.",
+                                )
+                                .color(style::TEXT_MUTED),
+                            );
+                        } else {
+                            action = Self::show_table(ui, state, picking_state);
+                        }
+                    });
+                });
+        });
+
+        match action {
+            Some(SessionAction::Reopen(index)) => {
+                let session = &state.sessions[index];
+                info!(
+                    "Reopening picking session {} ({})",
+                    session.id, session.name
+                );
+                *picking_state = PickingState::from_saved(session);
+                *current_screen = Screen::Picking;
+            }
+            Some(SessionAction::Duplicate(index)) => {
+                let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                let copy = state.sessions[index].duplicate(&now);
+                Self::update(state, |sessions| {
+                    add_session(sessions, copy);
+                });
+            }
+            Some(SessionAction::Delete(id)) => {
+                state.confirm_delete = None;
+                // The open picking list must not re-create the deleted entry
+                if picking_state.session.as_ref().is_some_and(|s| s.id == id) {
+                    picking_state.session = None;
+                }
+                Self::update(state, |sessions| {
+                    delete_session(sessions, &id);
+                });
+            }
+            None => {}
+        }
+    }
+
+    /// Applies a change to the saved sessions and shows the result.
+    fn update(state: &mut PickingSessionsState, change: impl FnOnce(&mut Vec<SavedSession>)) {
+        let path = sessions_path();
+        match update_sessions(&path, change) {
+            Ok((sessions, ())) => {
+                state.sessions = sessions;
+                state.loaded_mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                state.error = None;
+            }
+            Err(e) => state.error = Some(e),
+        }
+    }
+
+    fn show_table(
+        ui: &mut egui::Ui,
+        state: &mut PickingSessionsState,
+        picking_state: &PickingState,
+    ) -> Option<SessionAction> {
+        let open_id = picking_state.session.as_ref().map(|s| s.id.as_str());
+        let mut action = None;
+        egui::Grid::new("picking_sessions_grid")
+            .num_columns(6)
+            .striped(true)
+            .spacing([14.0, 6.0])
+            .show(ui, |ui| {
+                for header in [
+                    "Wantslist",
+                    "Started",
+                    "Last change",
+                    "Progress",
+                    "Value",
+                    "",
+                ] {
+                    ui.label(egui::RichText::new(header).strong());
+                }
+                ui.end_row();
+
+                for (index, session) in state.sessions.iter().enumerate() {
+                    let mut name = egui::RichText::new(&session.name).color(style::TEXT_PRIMARY);
+                    if open_id == Some(session.id.as_str()) {
+                        name = name.strong();
+                    }
+                    ui.label(name);
+                    ui.label(&session.created_at);
+                    ui.label(&session.updated_at);
+
+                    let (picked, total) = session.progress();
+                    ui.horizontal(|ui| {
+                        let fraction = if total > 0 {
+                            picked as f32 / total as f32
+                        } else {
+                            0.0
+                        };
+                        ui.add(egui::ProgressBar::new(fraction).desired_width(90.0));
+                        let status = if session.archived {
+                            format!("{picked}/{total} · archived")
+                        } else if session.is_complete() {
+                            format!("{picked}/{total} · picked")
+                        } else {
+                            format!("{picked}/{total}")
+                        };
+                        ui.label(status);
+                    });
+                    ui.label(format!(
+                        "€{:.2} / €{:.2}",
+                        session.picked_value(),
+                        session.total_value()
+                    ));
+
+                    ui.horizontal(|ui| {
+                        if state.confirm_delete.as_deref() == Some(session.id.as_str()) {
+                            ui.label(egui::RichText::new("Delete?").color(style::COLOR_ERROR));
+                            if style::secondary_button(ui, "Yes").clicked() {
+                                action = Some(SessionAction::Delete(session.id.clone()));
+                            }
+                            if style::secondary_button(ui, "No").clicked() {
+                                state.confirm_delete = None;
+                            }
+                            return;
+                        }
+                        if style::primary_button(ui, "Reopen").clicked() {
+                            action = Some(SessionAction::Reopen(index));
+                        }
+                        if style::secondary_button(ui, "Duplicate")
+                            .on_hover_text("Start the same pick again with nothing picked")
+                            .clicked()
+                        {
+                            action = Some(SessionAction::Duplicate(index));
+                        }
+                        if style::secondary_button(ui, "Delete").clicked() {
+                            state.confirm_delete = Some(session.id.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        action
+    }
+}
@@ -229,7 +229,7 @@ mod picking_state_tests {
     }
 
     #[test]
-    fn test_from_matched_cards_empty_locations_first() {
+    fn test_from_matched_cards_empty_locations_last() {
         let card1 = create_test_card("Card A", "tst", "1", "1.00", Some("A1_S1_R1_C1"), false);
         let card2 = create_test_card("Card B", "tst", "2", "1.00", None, false);
         let mc1 = create_matched_card(&card1, "Test", 1);
@@ -241,9 +241,9 @@ mod picking_state_tests {
 
         let state = PickingState::from_matched_cards(&matches);
 
-        // Empty string sorts before "A1..."
-        assert_eq!(state.items[0].card_name, "Card B"); // empty location
-        assert_eq!(state.items[1].card_name, "Card A"); // A1
+        // Unlocated cards come after every bin
+        assert_eq!(state.items[0].card_name, "Card A"); // A1
+        assert_eq!(state.items[1].card_name, "Card B"); // empty location
     }
 
     #[test]
@@ -436,6 +436,142 @@ mod loaded_image_tests {
     }
 }
 
+// ============================================================================
+// Pick Log Tests
+// ============================================================================
+
+mod pick_log_tests {
+    use super::*;
+
+    fn two_item_state() -> PickingState {
+        let card1 = create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false);
+        let card2 = create_test_card("Counterspell", "7ed", "67", "1.50", Some("B2"), true);
+        let mc1 = create_matched_card(&card1, "Commander", 1);
+        let mc2 = create_matched_card(&card2, "Seventh Edition", 2);
+        let matches = vec![
+            ("Sol Ring".to_string(), 1, vec![mc1]),
+            ("Counterspell".to_string(), 2, vec![mc2]),
+        ];
+        PickingState::from_matched_cards(&matches)
+    }
+
+    #[test]
+    fn test_new_items_have_no_pick_record() {
+        let state = two_item_state();
+        assert!(state.picker_name.is_empty());
+        for item in &state.items {
+            assert!(item.picked_by.is_empty());
+            assert!(item.picked_at.is_none());
+        }
+    }
+
+    #[test]
+    fn test_set_picked_records_picker_and_timestamp() {
+        let mut state = two_item_state();
+        state.picker_name = "  JD ".to_string();
+
+        state.set_picked(0, true);
+
+        let item = &state.items[0];
+        assert!(item.picked);
+        assert_eq!(item.picked_by, "JD");
+        let at = item.picked_at.as_deref().unwrap();
+        assert_eq!(at.len(), "YYYY-MM-DD HH:MM:SS".len());
+        assert!((state.picked_price - 2.0).abs() < 0.001);
+        assert!(!state.items[1].picked);
+    }
+
+    #[test]
+    fn test_unpick_clears_record() {
+        let mut state = two_item_state();
+        state.picker_name = "JD".to_string();
+        state.set_picked(1, true);
+        state.set_picked(1, false);
+
+        let item = &state.items[1];
+        assert!(!item.picked);
+        assert!(item.picked_by.is_empty());
+        assert!(item.picked_at.is_none());
+        assert!((state.picked_price - 0.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_repick_keeps_original_picker() {
+        let mut state = two_item_state();
+        state.picker_name = "AB".to_string();
+        state.set_picked(0, true);
+        let first_at = state.items[0].picked_at.clone();
+
+        state.picker_name = "CD".to_string();
+        state.set_picked(0, true);
+
+        assert_eq!(state.items[0].picked_by, "AB");
+        assert_eq!(state.items[0].picked_at, first_at);
+    }
+
+    #[test]
+    fn test_set_all_picked_only_stamps_unpicked_items() {
+        let mut state = two_item_state();
+        state.picker_name = "AB".to_string();
+        state.set_picked(0, true);
+
+        state.picker_name = "CD".to_string();
+        state.set_all_picked(true);
+
+        assert_eq!(state.items[0].picked_by, "AB");
+        assert_eq!(state.items[1].picked_by, "CD");
+        assert_eq!(state.picked_count(), 2);
+
+        state.set_all_picked(false);
+        assert_eq!(state.picked_count(), 0);
+        assert!(state.items.iter().all(|i| i.picked_at.is_none()));
+    }
+
+    #[test]
+    fn test_set_picked_out_of_range_is_ignored() {
+        let mut state = two_item_state();
+        state.set_picked(99, true);
+        assert_eq!(state.picked_count(), 0);
+    }
+
+    #[test]
+    fn test_pick_log_csv_header_and_rows() {
+        let mut state = two_item_state();
+        state.items[0].picked = true;
+        state.items[0].picked_by = "JD".to_string();
+        state.items[0].picked_at = Some("2024-05-01 10:15:00".to_string());
+
+        let csv = format_pick_log_csv(&state.items);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "itemId,location,name,set,setCode,cn,condition,language,isFoil,quantity,price,picked,pickedBy,pickedAt,shortPickReason,missingQuantity,verifiedBy,verifiedAt,verification"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{},A1,Sol Ring,Commander,cmd,237,NM,English,false,1,2.00,true,JD,2024-05-01 10:15:00,,,,,",
+                item_id(&state.items[0].card)
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!(
+                "{},B2,Counterspell,Seventh Edition,7ed,67,NM,English,true,2,1.50,false,,,,,,,",
+                item_id(&state.items[1].card)
+            )
+        );
+    }
+
+    #[test]
+    fn test_pick_log_csv_empty() {
+        let csv = format_pick_log_csv(&[]);
+        assert_eq!(csv.lines().count(), 1);
+    }
+}
+
 // ============================================================================
 // Integration-style Tests
 // ============================================================================
@@ -538,3 +674,691 @@ mod integration_tests {
         assert!(state.items[0].is_foil || state.items[1].is_foil); // One is foil
     }
 }
+
+// ============================================================================
+// Session archive Tests
+// ============================================================================
+
+mod archive_tests {
+    use super::*;
+
+    fn state_with_one_picked() -> PickingState {
+        let mut card1 = create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false);
+        card1.cardmarket_id = "111".to_string();
+        let mut card2 = create_test_card("Counterspell", "7ed", "67", "1.50", Some("B2"), true);
+        card2.cardmarket_id = "222".to_string();
+        let mc1 = create_matched_card(&card1, "Commander", 1);
+        let mc2 = create_matched_card(&card2, "Seventh Edition", 2);
+        let matches = vec![
+            ("Sol Ring".to_string(), 1, vec![mc1]),
+            ("Counterspell".to_string(), 2, vec![mc2]),
+        ];
+        let mut state = PickingState::from_matched_cards(&matches);
+        state.set_picked(1, true);
+        state
+    }
+
+    #[test]
+    fn test_item_keeps_source_listing() {
+        let state = state_with_one_picked();
+        assert_eq!(state.items[0].card.cardmarket_id, "111");
+        assert_eq!(state.items[1].card.name, "Counterspell");
+        assert!(!state.archived);
+    }
+
+    #[test]
+    fn test_picked_removals_only_picked_items() {
+        let state = state_with_one_picked();
+        let removals = state.picked_removals();
+        assert_eq!(removals.len(), 1);
+        assert_eq!(removals[0].0.cardmarket_id, "222");
+        assert_eq!(removals[0].1, 2);
+    }
+
+    #[test]
+    fn test_picked_stock_csv_has_negative_quantities_for_picked_only() {
+        let state = state_with_one_picked();
+        let csv = format_picked_stock_csv(&state.items);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2, "header + one picked row");
+        assert!(lines[0].starts_with("cardmarketId,quantity,name"));
+        assert!(lines[1].starts_with("222,-2,Counterspell"));
+    }
+
+    #[test]
+    fn test_picked_stock_csv_empty_when_nothing_picked() {
+        let mut state = state_with_one_picked();
+        state.set_all_picked(false);
+        assert_eq!(format_picked_stock_csv(&state.items).lines().count(), 1);
+        assert!(state.picked_removals().is_empty());
+    }
+}
+
+// ============================================================================
+// Short Pick Tests
+// ============================================================================
+
+mod short_pick_tests {
+    use super::*;
+
+    /// Sol Ring x1 at 2.00 (A1), Counterspell x3 at 1.50 (B2)
+    fn state() -> PickingState {
+        let mut card1 = create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false);
+        card1.cardmarket_id = "111".to_string();
+        let mut card2 = create_test_card("Counterspell", "7ed", "67", "1.50", Some("B2"), false);
+        card2.cardmarket_id = "222".to_string();
+        let mc1 = create_matched_card(&card1, "Commander", 1);
+        let mc2 = create_matched_card(&card2, "Seventh Edition", 3);
+        let matches = vec![
+            ("Sol Ring".to_string(), 1, vec![mc1]),
+            ("Counterspell".to_string(), 3, vec![mc2]),
+        ];
+        let mut state = PickingState::from_matched_cards(&matches);
+        state.picker_name = "JD".to_string();
+        state
+    }
+
+    #[test]
+    fn test_short_pick_marks_item_picked_with_found_copies() {
+        let mut state = state();
+        state.set_short_pick(1, ShortPickReason::Damaged, 1);
+
+        let item = &state.items[1];
+        assert!(item.picked);
+        assert_eq!(item.picked_by, "JD");
+        assert_eq!(item.picked_quantity(), 2);
+        assert_eq!(state.short_pick_count(), 1);
+        assert!((state.picked_price - 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_short_pick_clamps_missing_copies() {
+        let mut state = state();
+        state.set_short_pick(1, ShortPickReason::Missing, 10);
+        assert_eq!(state.items[1].short_pick.as_ref().unwrap().missing, 3);
+        assert_eq!(state.items[1].picked_quantity(), 0);
+
+        state.set_short_pick(0, ShortPickReason::Missing, 0);
+        assert_eq!(state.items[0].short_pick.as_ref().unwrap().missing, 1);
+    }
+
+    #[test]
+    fn test_undo_clears_short_pick() {
+        let mut state = state();
+        state.set_short_pick(1, ShortPickReason::WrongLocation, 2);
+        state.set_picked(1, false);
+
+        assert!(state.items[1].short_pick.is_none());
+        assert_eq!(state.short_pick_count(), 0);
+        assert_eq!(state.picked_price, 0.0);
+    }
+
+    #[test]
+    fn test_archive_removes_only_found_copies() {
+        let mut state = state();
+        state.set_short_pick(0, ShortPickReason::Missing, 1);
+        state.set_short_pick(1, ShortPickReason::Damaged, 1);
+
+        let removals = state.picked_removals();
+        assert_eq!(removals.len(), 1, "fully missing item is not removed");
+        assert_eq!(removals[0].0.cardmarket_id, "222");
+        assert_eq!(removals[0].1, 2);
+
+        let csv = format_picked_stock_csv(&state.items);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("222,-2,Counterspell"));
+    }
+
+    #[test]
+    fn test_pick_log_includes_short_pick() {
+        let mut state = state();
+        state.set_short_pick(1, ShortPickReason::WrongLocation, 2);
+
+        let csv = format_pick_log_csv(&state.items);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[1].ends_with(",false,,,,,,,"));
+        assert!(lines[2].ends_with(",wrong_location,2,,,"), "{}", lines[2]);
+    }
+
+    #[test]
+    fn test_discrepancies_csv_lists_short_picks_with_db_quantity() {
+        let mut state = state();
+        state.set_picked(0, true);
+        state.set_short_pick(1, ShortPickReason::Damaged, 1);
+        state.items[1].picked_at = Some("2024-05-01 10:15:00".to_string());
+
+        let csv = format_discrepancies_csv(&state.items, &[Some(1), Some(5)]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2, "header + one short pick");
+        assert_eq!(
+            lines[0],
+            "itemId,location,name,set,setCode,cn,cardmarketId,condition,language,isFoil,\
+             expectedQuantity,missingQuantity,reason,reportedBy,reportedAt,dbQuantity"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{},B2,Counterspell,Seventh Edition,7ed,67,222,NM,English,false,3,1,damaged,JD,\
+                 2024-05-01 10:15:00,5",
+                item_id(&state.items[1].card)
+            )
+        );
+    }
+
+    #[test]
+    fn test_discrepancies_csv_without_db_quantities() {
+        let mut state = state();
+        state.set_short_pick(0, ShortPickReason::Missing, 1);
+
+        let csv = format_discrepancies_csv(&state.items, &[]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains(",missing,JD,"), "{}", lines[1]);
+        assert!(lines[1].ends_with(','), "empty dbQuantity: {}", lines[1]);
+    }
+}
+
+// ============================================================================
+// Keyboard Navigation Tests
+// ============================================================================
+
+mod keyboard_nav_tests {
+    use super::*;
+
+    /// Four unpicked items, sorted by location A1..A4.
+    fn four_item_state() -> PickingState {
+        let cards = [
+            create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false),
+            create_test_card("Counterspell", "7ed", "67", "1.50", Some("A2"), false),
+            create_test_card("Lightning Bolt", "m10", "146", "1.00", Some("A3"), false),
+            create_test_card("Dark Ritual", "7ed", "127", "0.50", Some("A4"), false),
+        ];
+        let matches: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    1,
+                    vec![create_matched_card(c, "Some Set", 1)],
+                )
+            })
+            .collect();
+        PickingState::from_matched_cards(&matches)
+    }
+
+    #[test]
+    fn test_move_cursor_starts_at_first_and_clamps() {
+        let mut state = four_item_state();
+        assert_eq!(state.cursor, None);
+
+        state.move_cursor(1);
+        assert_eq!(state.cursor, Some(0), "first key press lands on first item");
+        state.move_cursor(2);
+        assert_eq!(state.cursor, Some(2));
+        state.move_cursor(10);
+        assert_eq!(state.cursor, Some(3));
+        state.move_cursor(-10);
+        assert_eq!(state.cursor, Some(0));
+    }
+
+    #[test]
+    fn test_move_cursor_rows_uses_grid_columns() {
+        let mut state = four_item_state();
+        state.grid_cols = 2;
+        state.move_cursor(0);
+        state.move_cursor_rows(1);
+        assert_eq!(state.cursor, Some(2));
+        state.move_cursor_rows(-1);
+        assert_eq!(state.cursor, Some(0));
+    }
+
+    #[test]
+    fn test_move_cursor_skips_hidden_picked_items() {
+        let mut state = four_item_state();
+        state.set_picked(1, true);
+        state.cursor = Some(0);
+        state.move_cursor(1);
+        assert_eq!(state.cursor, Some(2));
+
+        state.show_picked = true;
+        state.move_cursor(-1);
+        assert_eq!(state.cursor, Some(1));
+    }
+
+    #[test]
+    fn test_toggle_cursor_moves_to_next_visible() {
+        let mut state = four_item_state();
+        state.cursor = Some(1);
+        state.toggle_cursor();
+        assert!(state.items[1].picked);
+        assert_eq!(state.cursor, Some(2), "picked item hides, cursor moves on");
+
+        // At the end of the list the cursor falls back to the previous item
+        state.cursor = Some(3);
+        state.toggle_cursor();
+        assert_eq!(state.cursor, Some(2));
+    }
+
+    #[test]
+    fn test_toggle_cursor_with_show_picked_stays_put() {
+        let mut state = four_item_state();
+        state.show_picked = true;
+        state.cursor = Some(1);
+        state.toggle_cursor();
+        assert!(state.items[1].picked);
+        assert_eq!(state.cursor, Some(1));
+
+        state.toggle_cursor();
+        assert!(!state.items[1].picked);
+        assert_eq!(state.picked_count(), 0);
+    }
+
+    #[test]
+    fn test_toggle_last_item_clears_cursor() {
+        let mut state = four_item_state();
+        state.set_all_picked(true);
+        state.set_picked(2, false);
+        state.cursor = Some(2);
+        state.toggle_cursor();
+        assert_eq!(state.cursor, None);
+    }
+
+    #[test]
+    fn test_jump_to_next_unpicked_wraps() {
+        let mut state = four_item_state();
+        state.show_picked = true;
+        state.set_picked(1, true);
+        state.set_picked(2, true);
+
+        state.cursor = Some(0);
+        state.jump_to_next_unpicked();
+        assert_eq!(state.cursor, Some(3));
+        state.jump_to_next_unpicked();
+        assert_eq!(state.cursor, Some(0), "wraps around to the start");
+
+        state.set_all_picked(true);
+        state.jump_to_next_unpicked();
+        assert_eq!(state.cursor, Some(0), "nothing left to jump to");
+    }
+
+    #[test]
+    fn test_search_filters_visible_items() {
+        let mut state = four_item_state();
+        state.search = "7ED".to_string();
+        assert!(
+            state.visible_indices().is_empty(),
+            "set code is not searched"
+        );
+
+        state.search = "  bolt ".to_string();
+        assert_eq!(state.visible_indices(), vec![2]);
+
+        state.search = "a4".to_string();
+        assert_eq!(state.visible_indices(), vec![3], "matches location");
+
+        state.search = "some set".to_string();
+        assert_eq!(state.visible_indices().len(), 4, "matches set name");
+
+        state.search = "bolt".to_string();
+        state.cursor = Some(0);
+        state.move_cursor(0);
+        assert_eq!(
+            state.cursor,
+            Some(2),
+            "hidden cursor jumps into the results"
+        );
+    }
+}
+
+mod warehouse_mode_tests {
+    use super::*;
+
+    /// Three unpicked items, sorted by location A1..A3.
+    fn three_item_state() -> PickingState {
+        let cards = [
+            create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false),
+            create_test_card("Counterspell", "7ed", "67", "1.50", Some("A2"), false),
+            create_test_card("Lightning Bolt", "m10", "146", "1.00", Some("A3"), false),
+        ];
+        let matches: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    1,
+                    vec![create_matched_card(c, "Some Set", 1)],
+                )
+            })
+            .collect();
+        PickingState::from_matched_cards(&matches)
+    }
+
+    #[test]
+    fn test_entering_lands_on_first_unpicked() {
+        let mut state = three_item_state();
+        state.set_picked(0, true);
+
+        state.set_warehouse_mode(true);
+
+        assert!(state.warehouse_mode);
+        assert_eq!(state.cursor, Some(1));
+    }
+
+    #[test]
+    fn test_pick_advances_to_next_unpicked() {
+        let mut state = three_item_state();
+        state.set_warehouse_mode(true);
+        state.set_picked(1, true);
+
+        state.pick_and_advance(None);
+
+        assert!(state.items[0].picked);
+        assert_eq!(state.cursor, Some(2), "skips the already picked item");
+    }
+
+    #[test]
+    fn test_short_pick_advances_too() {
+        let mut state = three_item_state();
+        state.set_warehouse_mode(true);
+
+        state.pick_and_advance(Some((ShortPickReason::Damaged, 1)));
+
+        assert_eq!(state.items[0].picked_quantity(), 0);
+        assert_eq!(state.short_pick_count(), 1);
+        assert_eq!(state.cursor, Some(1));
+    }
+
+    #[test]
+    fn test_last_pick_clears_cursor() {
+        let mut state = three_item_state();
+        state.set_warehouse_mode(true);
+
+        for _ in 0..3 {
+            state.pick_and_advance(None);
+        }
+
+        assert_eq!(state.picked_count(), 3);
+        assert_eq!(state.cursor, None);
+    }
+}
+
+// ============================================================================
+// Saved Session Tests
+// ============================================================================
+
+mod saved_session_tests {
+    use super::*;
+
+    fn two_item_state() -> PickingState {
+        let cards = [
+            create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false),
+            create_test_card("Counterspell", "7ed", "67", "1.50", Some("A2"), true),
+        ];
+        let matches: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    2,
+                    vec![create_matched_card(c, "Some Set", 2)],
+                )
+            })
+            .collect();
+        PickingState::from_matched_cards(&matches)
+    }
+
+    #[test]
+    fn test_picks_survive_save_and_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let mut state = two_item_state();
+        state.start_session(&path, "weekly.txt").unwrap();
+        state.picker_name = "AB".to_string();
+        state.set_picked(0, true);
+        state.set_short_pick(1, ShortPickReason::Damaged, 1);
+        assert!(state.session_dirty);
+
+        state.save_session(&path).unwrap();
+        assert!(!state.session_dirty);
+
+        let saved = crate::picking_sessions::load_sessions(&path).unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].name, "weekly.txt");
+        assert_eq!(saved[0].picker_name, "AB");
+
+        let reopened = PickingState::from_saved(&saved[0]);
+        assert_eq!(reopened.picked_count(), 2);
+        assert_eq!(reopened.items[0].picked_by, "AB");
+        assert!(reopened.items[1].is_foil);
+        assert_eq!(
+            reopened.items[1].short_pick,
+            Some(ShortPick {
+                reason: ShortPickReason::Damaged,
+                missing: 1
+            })
+        );
+        assert!((reopened.picked_price - 5.5).abs() < 0.001);
+        assert!(!reopened.session_dirty);
+    }
+
+    #[test]
+    fn test_unsaved_list_is_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let mut state = two_item_state();
+        state.set_picked(0, true);
+
+        state.save_session(&path).unwrap();
+
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_short_pick_reason_round_trips() {
+        for reason in ShortPickReason::ALL {
+            assert_eq!(ShortPickReason::parse(reason.as_str()), Some(reason));
+        }
+        assert_eq!(ShortPickReason::parse("lost"), None);
+    }
+}
+
+// ============================================================================
+// Verification by a second person
+// ============================================================================
+
+mod verification_tests {
+    use super::*;
+
+    /// Three items picked by AB, sorted by location A1..A3.
+    fn picked_state() -> PickingState {
+        let cards = [
+            create_test_card("Sol Ring", "cmd", "237", "2.00", Some("A1"), false),
+            create_test_card("Counterspell", "7ed", "67", "1.50", Some("A2"), false),
+            create_test_card("Lightning Bolt", "m10", "146", "1.00", Some("A3"), false),
+        ];
+        let matches: Vec<_> = cards
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    1,
+                    vec![create_matched_card(c, "Some Set", 1)],
+                )
+            })
+            .collect();
+        let mut state = PickingState::from_matched_cards(&matches);
+        state.picker_name = "AB".to_string();
+        state.set_all_picked(true);
+        state
+    }
+
+    #[test]
+    fn test_checks_walk_the_picked_items_in_order() {
+        let mut state = picked_state();
+        state.set_picked(1, false);
+        state.verifier_name = "CD".to_string();
+
+        state.set_verify_mode(true);
+        assert_eq!(state.verify_cursor, Some(0));
+
+        state.verify_current(true).unwrap();
+        assert_eq!(state.verify_cursor, Some(2), "unpicked items are skipped");
+        let check = state.items[0].verification.as_ref().unwrap();
+        assert_eq!(check.verified_by, "CD");
+        assert!(check.correct);
+
+        state.verify_current(true).unwrap();
+        assert_eq!(state.verify_cursor, None);
+        assert_eq!(state.verification_progress(), (2, 2));
+        assert!(state.is_verified());
+    }
+
+    #[test]
+    fn test_picker_cannot_check_their_own_picks() {
+        let mut state = picked_state();
+        state.set_verify_mode(true);
+
+        assert!(state.verify_current(true).is_err(), "no checker name");
+        state.verifier_name = " ab ".to_string();
+        assert!(state.verify_current(true).is_err());
+        assert!(state.items[0].verification.is_none());
+        assert_eq!(state.verify_cursor, Some(0));
+    }
+
+    #[test]
+    fn test_wrong_items_come_back_around_and_clear_on_repick() {
+        let mut state = picked_state();
+        state.verifier_name = "CD".to_string();
+        state.set_verify_mode(true);
+
+        state.verify_current(false).unwrap();
+        state.verify_current(true).unwrap();
+        state.verify_current(true).unwrap();
+        assert_eq!(state.rejected_count(), 1);
+        assert_eq!(
+            state.verify_cursor,
+            Some(0),
+            "the wrong item is checked again"
+        );
+        assert!(!state.is_verified());
+
+        // Picking the item again throws the old verdict away
+        state.set_picked(0, false);
+        state.set_picked(0, true);
+        assert_eq!(state.rejected_count(), 0);
+        assert_eq!(state.verification_progress(), (2, 3));
+    }
+
+    #[test]
+    fn test_checks_survive_save_and_reach_the_pick_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let mut state = picked_state();
+        state.start_session(&path, "big order").unwrap();
+        state.verifier_name = "CD".to_string();
+        state.set_verify_mode(true);
+        state.verify_current(false).unwrap();
+        state.save_session(&path).unwrap();
+
+        let saved = crate::picking_sessions::load_sessions(&path).unwrap();
+        let reopened = PickingState::from_saved(&saved[0]);
+        assert_eq!(reopened.rejected_count(), 1);
+
+        let csv = format_pick_log_csv(&reopened.items);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.contains(",CD,"), "{row}");
+        assert!(row.ends_with(",wrong"), "{row}");
+    }
+}
+
+// ============================================================================
+// Screen, driven through the headless UI harness
+// ============================================================================
+
+mod screen_tests {
+    use super::*;
+    use crate::ui::test_harness::Harness;
+
+    #[test]
+    fn empty_list_renders() {
+        let mut ui = Harness::new();
+        let mut screen = Screen::Picking;
+        let mut state = PickingState::default();
+
+        ui.run_frames(2, |ctx| PickingScreen::show(ctx, &mut screen, &mut state));
+        ui.press_key(egui::Key::Space);
+        ui.press_key(egui::Key::F);
+        ui.run(|ctx| PickingScreen::show(ctx, &mut screen, &mut state));
+
+        assert!(ui.shows("Progress: 0/0"), "{:?}", ui.texts());
+        assert_eq!(state.cursor, None);
+        assert_eq!(screen, Screen::Picking);
+    }
+
+    #[test]
+    fn pick_button_marks_the_tile_picked() {
+        let mut ui = Harness::new();
+        let card = create_test_card("Sol Ring", "c21", "", "1.00", Some("A1_S1_R1_C1"), false);
+        let matches = vec![(
+            "Sol Ring".to_string(),
+            1,
+            vec![create_matched_card(&card, "Commander 2021", 1)],
+        )];
+        let mut screen = Screen::Picking;
+        let mut state = PickingState::from_matched_cards(&matches);
+        state.picker_name = "EH".to_string();
+        let mut show = |ctx: &egui::Context| PickingScreen::show(ctx, &mut screen, &mut state);
+
+        ui.run_frames(2, &mut show);
+        ui.click("Pick");
+        ui.run_frames(2, &mut show);
+
+        assert!(ui.shows("Progress: 1/1"), "{:?}", ui.texts());
+        assert!(!ui.has_button("Undo"));
+
+        // Picked tiles are only listed on request
+        ui.click("Show picked items");
+        ui.run_frames(2, &mut show);
+        assert!(ui.shows("Picked by EH at"), "{:?}", ui.texts());
+        assert!(ui.has_button("Undo"));
+        assert!(state.items[0].picked);
+    }
+
+    #[test]
+    fn verification_mode_records_the_check() {
+        let mut ui = Harness::new();
+        let card = create_test_card("Sol Ring", "", "", "1.00", Some("A1_S1_R1_C1"), false);
+        let matches = vec![(
+            "Sol Ring".to_string(),
+            1,
+            vec![create_matched_card(&card, "Commander 2021", 1)],
+        )];
+        let mut screen = Screen::Picking;
+        let mut state = PickingState::from_matched_cards(&matches);
+        state.picker_name = "EH".to_string();
+        state.set_picked(0, true);
+        let mut show = |ctx: &egui::Context| PickingScreen::show(ctx, &mut screen, &mut state);
+
+        ui.run_frames(2, &mut show);
+        ui.click("Verify Picks…");
+        ui.run_frames(2, &mut show);
+        assert!(ui.shows("Verified: 0/1"), "{:?}", ui.texts());
+
+        // The picker's own check is refused
+        ui.click("✓ Correct");
+        ui.run_frames(2, &mut show);
+        assert!(ui.shows("Verified: 0/1"), "{:?}", ui.texts());
+
+        state.verifier_name = "MK".to_string();
+        let mut show = |ctx: &egui::Context| PickingScreen::show(ctx, &mut screen, &mut state);
+        ui.run_frames(2, &mut show);
+        ui.click("✓ Correct");
+        ui.run_frames(2, &mut show);
+        assert!(ui.shows("All picked items verified ✓"), "{:?}", ui.texts());
+        assert_eq!(
+            state.items[0].verification.as_ref().unwrap().verified_by,
+            "MK"
+        );
+    }
+}
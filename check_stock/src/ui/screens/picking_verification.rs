@@ -0,0 +1,184 @@
+//! Verification mode of the picking screen: a second person checks the
+//! picked items one at a time before a valuable order ships.
+//!
+//! Each picked item is shown with its card image, printing details and
+//! count; the checker confirms it (Enter) or flags it as wrong (W). Every
+//! verdict is stamped with the checker's name and time and saved with the
+//! session. The picker of an item can't check it.
+
+use super::picking::{PickingScreen, PickingState};
+use crate::ui::style;
+use eframe::egui;
+
+/// The verification view. Esc goes back to the picking list.
+pub(super) fn show_verification(ctx: &egui::Context, state: &mut PickingState) {
+    let mut verdict = None;
+    if !ctx.wants_keyboard_input() {
+        let pressed = |key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key));
+        if pressed(egui::Key::Enter) {
+            verdict = Some(true);
+        }
+        if pressed(egui::Key::W) {
+            verdict = Some(false);
+        }
+        if pressed(egui::Key::ArrowRight) {
+            state.next_to_verify();
+        }
+        if pressed(egui::Key::Escape) {
+            state.set_verify_mode(false);
+            return;
+        }
+    }
+
+    egui::CentralPanel::default().show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("← Back to Picking List").clicked() {
+                state.set_verify_mode(false);
+            }
+            ui.add_space(20.0);
+            let (verified, total) = state.verification_progress();
+            ui.label(format!("Verified: {verified}/{total}"));
+            ui.add(
+                egui::ProgressBar::new(if total > 0 {
+                    verified as f32 / total as f32
+                } else {
+                    0.0
+                })
+                .desired_width(150.0)
+                .show_percentage(),
+            );
+            let rejected = state.rejected_count();
+            if rejected > 0 {
+                ui.add_space(10.0);
+                style::status_error(ui, &format!("{rejected} wrong"));
+            }
+        });
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label("Checked by:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.verifier_name)
+                    .hint_text("Name or initials")
+                    .desired_width(120.0),
+            );
+        });
+        ui.label(
+            egui::RichText::new("Keys: Enter correct • W wrong • → skip • Esc back to the list")
+                .size(11.0)
+                .color(egui::Color32::GRAY),
+        );
+        if let Some(err) = &state.verify_error {
+            style::status_error(ui, err);
+        }
+        ui.separator();
+
+        let Some(index) = state.verify_cursor else {
+            ui.vertical_centered(|ui| {
+                ui.add_space(60.0);
+                let text = if state.verification_progress().1 == 0 {
+                    "Nothing picked to verify yet"
+                } else {
+                    "All picked items verified ✓"
+                };
+                ui.label(
+                    egui::RichText::new(text)
+                        .size(32.0)
+                        .strong()
+                        .color(egui::Color32::LIGHT_GREEN),
+                );
+            });
+            return;
+        };
+
+        let image_key = state.items[index].image_key();
+        let item = &state.items[index];
+        if !state.images.contains_key(&image_key)
+            && !state.loading_images.contains(&image_key)
+            && !item.set_code.is_empty()
+            && !item.collector_number.is_empty()
+        {
+            PickingScreen::load_card_image(ctx, state, index);
+        }
+        let item = &state.items[index];
+
+        let mut skip = false;
+        ui.horizontal_top(|ui| {
+            match state.images.get(&image_key) {
+                Some(texture) => {
+                    let height = 420.0;
+                    let aspect = texture.size()[0] as f32 / texture.size()[1] as f32;
+                    ui.image((texture.id(), egui::vec2(height * aspect, height)));
+                }
+                None => {
+                    ui.spinner();
+                }
+            }
+            ui.add_space(24.0);
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new(&item.card_name).size(32.0).strong());
+                ui.label(
+                    egui::RichText::new(format!("{} copies", item.picked_quantity()))
+                        .size(28.0)
+                        .strong(),
+                );
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} ({} #{}) • {} • {}{}",
+                        item.set_name,
+                        item.set_code,
+                        item.collector_number,
+                        item.condition,
+                        item.language,
+                        if item.is_foil { " • FOIL" } else { "" }
+                    ))
+                    .size(18.0),
+                );
+                ui.label(format!("{:.2} € each", item.price));
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Picked by {} from {}",
+                        if item.picked_by.is_empty() {
+                            "unknown"
+                        } else {
+                            &item.picked_by
+                        },
+                        if item.location.is_empty() {
+                            "no location"
+                        } else {
+                            &item.location
+                        }
+                    ))
+                    .color(egui::Color32::GRAY),
+                );
+                if let Some(v) = item.verification.as_ref().filter(|v| !v.correct) {
+                    style::status_error(
+                        ui,
+                        &format!("Flagged wrong by {} at {}", v.verified_by, v.verified_at),
+                    );
+                }
+                ui.add_space(24.0);
+                ui.horizontal(|ui| {
+                    if style::primary_button(ui, "✓ Correct").clicked() {
+                        verdict = Some(true);
+                    }
+                    if style::secondary_button(ui, "✗ Wrong card")
+                        .on_hover_text("The pile doesn't match: wrong card, printing or count")
+                        .clicked()
+                    {
+                        verdict = Some(false);
+                    }
+                    if style::secondary_button(ui, "Skip").clicked() {
+                        skip = true;
+                    }
+                });
+            });
+        });
+        if skip {
+            state.next_to_verify();
+        }
+    });
+
+    if let Some(correct) = verdict {
+        state.verify_error = state.verify_current(correct).err();
+    }
+}
@@ -0,0 +1,223 @@
+//! Whole-inventory repricing: the pricing graph applied to every in-stock
+//! variant in the inventory DB rather than to the loaded CSV.
+//!
+//! Planning reads the stock, fetches market prices when the graph uses them
+//! and evaluates the graph; nothing is written until the changes are applied
+//! (see [`crate::bulk_repricing`]).
+
+use super::eval::evaluate_all;
+use crate::{
+    api::inventory_sync::InventorySyncClient,
+    bulk_repricing::{format_bulk_price_csv, price_changes, summarize, PriceChange},
+    inventory_db,
+    repricing_guard::{rolling_sale_averages, sale_floors, SALE_FLOOR_WINDOW_DAYS},
+    ui::{
+        components::spawn_task,
+        state::{GraphNode, NodeGraph, NodeKind, PricingState, SavedGraph, Wire},
+        style,
+    },
+};
+use eframe::egui;
+use log::{error, info};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Result of a repricing run from the command line.
+pub struct BulkRepriceOutcome {
+    pub changes: Vec<PriceChange>,
+    /// Variants written to the DB (0 for a dry run)
+    pub applied: usize,
+    /// Cardmarket stock CSV with the new prices
+    pub csv: String,
+}
+
+/// Plans the repricing of the whole in-stock inventory with the graph's rules.
+pub(super) fn plan_inventory_repricing(
+    nodes: &[GraphNode],
+    wires: &[Wire],
+    inventory_sync_url: &str,
+) -> Result<Vec<PriceChange>, String> {
+    let output_id = nodes
+        .iter()
+        .find(|n| matches!(n.kind, NodeKind::Output))
+        .map(|n| n.id)
+        .ok_or("The pricing graph has no Output node")?;
+    // Applying writes to the active inventory, so only its stock is repriced
+    let cards = inventory_db::active_inventory()
+        .and_then(|inventory| inventory_db::get_in_stock_inventory(Some(&inventory)))
+        .map_err(|e| format!("Inventory DB: {e}"))?;
+
+    let mut prices = HashMap::new();
+    let uses_market = nodes.iter().any(|n| {
+        matches!(
+            n.kind,
+            NodeKind::InventoryPrice { .. } | NodeKind::ThinMarketGuard { .. }
+        )
+    });
+    if uses_market && !cards.is_empty() {
+        let mut ids: Vec<u64> = cards
+            .iter()
+            .filter_map(|c| c.cardmarket_id.parse().ok())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let fetched = InventorySyncClient::new(inventory_sync_url)
+            .latest_prices_blocking(&ids)
+            .map_err(|e| format!("Price fetch from {inventory_sync_url} failed: {e}"))?;
+        prices = fetched.into_iter().map(|p| (p.id_product, p)).collect();
+    }
+
+    let floors = match inventory_db::get_sold_events() {
+        Ok(events) => sale_floors(
+            &cards,
+            &rolling_sale_averages(
+                &events,
+                mtg_common::dates::market_today(),
+                SALE_FLOOR_WINDOW_DAYS,
+            ),
+        ),
+        Err(e) => return Err(format!("Sales history: {e}")),
+    };
+
+    let mut outputs = evaluate_all(nodes, wires, &cards, &prices, &floors);
+    let Some(output) = outputs.remove(&output_id) else {
+        return Ok(Vec::new());
+    };
+    let titles: HashMap<_, _> = nodes.iter().map(|n| (n.id, n.kind.title())).collect();
+    let rules: HashMap<usize, String> = output
+        .rules
+        .iter()
+        .filter_map(|(&idx, node)| Some((idx, titles.get(node)?.to_string())))
+        .collect();
+    Ok(price_changes(
+        &cards,
+        &output.indices,
+        &output.overrides,
+        &rules,
+    ))
+}
+
+/// Command-line repricing: applies the graph saved at `graph_path` to the
+/// whole inventory. The graph's saved inventory_sync URL wins over
+/// `inventory_sync_url`, as when loading it in the app. With `dry_run` the DB
+/// is left untouched.
+pub fn reprice_inventory_from_file(
+    graph_path: &Path,
+    inventory_sync_url: &str,
+    dry_run: bool,
+) -> Result<BulkRepriceOutcome, String> {
+    let json = std::fs::read_to_string(graph_path)
+        .map_err(|e| format!("{}: {e}", graph_path.display()))?;
+    let saved: SavedGraph =
+        serde_json::from_str(&json).map_err(|e| format!("{}: {e}", graph_path.display()))?;
+    let url = saved
+        .inventory_sync_url
+        .clone()
+        .unwrap_or_else(|| inventory_sync_url.to_string());
+    let graph = NodeGraph::load(saved);
+
+    let changes = plan_inventory_repricing(&graph.nodes, &graph.wires, &url)?;
+    let applied = if dry_run {
+        0
+    } else {
+        inventory_db::apply_price_changes(&changes).map_err(|e| format!("Inventory DB: {e}"))?
+    };
+    Ok(BulkRepriceOutcome {
+        csv: format_bulk_price_csv(&changes),
+        changes,
+        applied,
+    })
+}
+
+/// "Reprice Whole Inventory" row: plans in the background, then asks for
+/// confirmation before writing the prices and showing the bulk CSV.
+pub(super) fn show_bulk_reprice_controls(
+    ui: &mut egui::Ui,
+    ctx: &egui::Context,
+    inventory_sync_url: &str,
+    state: &mut PricingState,
+) {
+    if let Some(rx) = &state.bulk_reprice_rx {
+        match rx.try_recv() {
+            Ok(Ok(changes)) => {
+                info!("Whole-inventory repricing planned: {}", summarize(&changes));
+                state.bulk_reprice_status = None;
+                state.bulk_reprice_changes = Some(changes);
+                state.bulk_reprice_rx = None;
+            }
+            Ok(Err(e)) => {
+                error!("Whole-inventory repricing failed: {e}");
+                state.bulk_reprice_status = Some(Err(e));
+                state.bulk_reprice_rx = None;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => state.bulk_reprice_rx = None,
+            Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+        }
+    }
+
+    ui.horizontal(|ui| {
+        let planning = state.bulk_reprice_rx.is_some();
+        let label = if planning {
+            "Planning…"
+        } else {
+            "Reprice Whole Inventory"
+        };
+        if style::secondary_button(ui, label)
+            .on_hover_text(
+                "Apply this graph to every in-stock card in the inventory DB and export \
+                 a bulk price-change CSV",
+            )
+            .clicked()
+            && !planning
+        {
+            let nodes: Vec<GraphNode> = state
+                .graph
+                .nodes
+                .iter()
+                .map(|n| GraphNode {
+                    id: n.id,
+                    kind: n.kind.clone(),
+                    pos: n.pos,
+                })
+                .collect();
+            let wires = state.graph.wires.clone();
+            let url = inventory_sync_url.to_string();
+            state.bulk_reprice_changes = None;
+            state.bulk_reprice_status = None;
+            state.bulk_reprice_rx = Some(spawn_task("Plan inventory repricing", move |_, tx| {
+                let _ = tx.send(plan_inventory_repricing(&nodes, &wires, &url));
+            }));
+        }
+
+        if let Some(changes) = &state.bulk_reprice_changes {
+            ui.label(egui::RichText::new(summarize(changes)).color(style::TEXT_MUTED));
+            let apply =
+                style::primary_button_enabled(ui, "Apply & Export CSV", !changes.is_empty());
+            if apply.clicked() {
+                match inventory_db::apply_price_changes(changes) {
+                    Ok(applied) => {
+                        info!("Repriced {applied} inventory variants");
+                        state.diff_output_content = format_bulk_price_csv(changes);
+                        state.show_diff_output = true;
+                        state.bulk_reprice_status = Some(Ok(format!(
+                            "Repriced {applied} listings in the inventory DB"
+                        )));
+                        state.bulk_reprice_changes = None;
+                    }
+                    Err(e) => {
+                        error!("Failed to write repricing: {e}");
+                        state.bulk_reprice_status = Some(Err(format!("Inventory DB: {e}")));
+                    }
+                }
+            } else if style::secondary_button(ui, "Discard").clicked() {
+                state.bulk_reprice_changes = None;
+            }
+        }
+
+        match &state.bulk_reprice_status {
+            Some(Ok(msg)) => style::status_ok(ui, msg),
+            Some(Err(msg)) => style::status_error(ui, msg),
+            None => {}
+        }
+    });
+}
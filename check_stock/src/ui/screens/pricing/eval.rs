@@ -1,7 +1,8 @@
-use crate::models::{canonical_condition, Card};
+use crate::models::{canonical_condition, canonical_language, Card};
+use crate::repricing_guard::{market_spread, thin_market_price};
 use crate::ui::state::{
-    ConditionFilter, FoilFilter, GraphNode, LanguageFilter, LatestPrice, NodeId, NodeKind,
-    PriceFields, RarityFilter, Wire,
+    ConditionFilter, FoilFilter, GraphNode, InventoryPriceSource, LanguageFilter, LatestPrice,
+    NodeId, NodeKind, PriceFields, RarityFilter, Wire,
 };
 use std::collections::{HashMap, HashSet, VecDeque};
 
@@ -13,6 +14,9 @@ pub(super) struct NodeOutput {
     /// Maps card index → effective (floored) price. Only present when a PriceFloor node
     /// raised the card's price above its CSV value.
     pub overrides: HashMap<usize, f64>,
+    /// Maps card index → the price node that last set its override, for the
+    /// repricing audit trail.
+    pub rules: HashMap<usize, NodeId>,
 }
 
 /// Evaluates the full graph and returns a `NodeOutput` for every node.
@@ -22,6 +26,7 @@ pub(super) fn evaluate_all(
     wires: &[Wire],
     all_cards: &[Card],
     inventory_prices: &HashMap<u64, LatestPrice>,
+    sale_floors: &HashMap<usize, f64>,
 ) -> HashMap<NodeId, NodeOutput> {
     if all_cards.is_empty() {
         return HashMap::new();
@@ -72,7 +77,7 @@ pub(super) fn evaluate_all(
         let output = if node.kind.input_count() == 0 {
             NodeOutput {
                 indices: filter_indices(&node.kind, all_indices.clone(), all_cards),
-                overrides: HashMap::new(),
+                ..NodeOutput::default()
             }
         } else {
             let inputs: Vec<NodeOutput> = (0..node.kind.input_count())
@@ -83,6 +88,7 @@ pub(super) fn evaluate_all(
                         .map(|out| NodeOutput {
                             indices: out.indices.clone(),
                             overrides: out.overrides.clone(),
+                            rules: out.rules.clone(),
                         })
                         .unwrap_or_default()
                 })
@@ -96,10 +102,11 @@ pub(super) fn evaluate_all(
                         result.retain(|i| set.contains(i));
                     }
                     let surviving: HashSet<usize> = result.iter().copied().collect();
-                    let overrides = merge_overrides(&inputs, &surviving);
+                    let (overrides, rules) = merge_overrides(&inputs, &surviving);
                     NodeOutput {
                         indices: result,
                         overrides,
+                        rules,
                     }
                 }
                 NodeKind::LogicalOr => {
@@ -114,10 +121,11 @@ pub(super) fn evaluate_all(
                     }
                     result.sort_unstable();
                     let surviving: HashSet<usize> = result.iter().copied().collect();
-                    let overrides = merge_overrides(&inputs, &surviving);
+                    let (overrides, rules) = merge_overrides(&inputs, &surviving);
                     NodeOutput {
                         indices: result,
                         overrides,
+                        rules,
                     }
                 }
                 NodeKind::LogicalNot => {
@@ -130,7 +138,7 @@ pub(super) fn evaluate_all(
                     // Cards coming out of NOT didn't flow through any PriceFloor on this path.
                     NodeOutput {
                         indices: result,
-                        overrides: HashMap::new(),
+                        ..NodeOutput::default()
                     }
                 }
                 NodeKind::PriceFloor {
@@ -141,6 +149,7 @@ pub(super) fn evaluate_all(
                 } => {
                     let input = inputs.into_iter().next().unwrap_or_default();
                     let mut overrides = input.overrides;
+                    let mut rules = input.rules;
                     for &idx in &input.indices {
                         let card = &all_cards[idx];
                         let floor = match card.rarity.to_lowercase().as_str() {
@@ -156,16 +165,19 @@ pub(super) fn evaluate_all(
                             .unwrap_or_else(|| card.price_f64());
                         if current < floor {
                             overrides.insert(idx, floor);
+                            rules.insert(idx, id);
                         }
                     }
                     NodeOutput {
                         indices: input.indices,
                         overrides,
+                        rules,
                     }
                 }
                 NodeKind::InventoryPrice { source } => {
                     let input = inputs.into_iter().next().unwrap_or_default();
                     let mut overrides = input.overrides;
+                    let mut rules = input.rules;
                     for &idx in &input.indices {
                         let card = &all_cards[idx];
                         if let Ok(product_id) = card.cardmarket_id.parse::<u64>() {
@@ -173,6 +185,7 @@ pub(super) fn evaluate_all(
                                 let is_foil = card.is_foil_card();
                                 if let Some(price) = cached.price_for(*source, is_foil) {
                                     overrides.insert(idx, price);
+                                    rules.insert(idx, id);
                                 }
                             }
                         }
@@ -180,6 +193,80 @@ pub(super) fn evaluate_all(
                     NodeOutput {
                         indices: input.indices,
                         overrides,
+                        rules,
+                    }
+                }
+                NodeKind::SalesFloor => {
+                    let input = inputs.into_iter().next().unwrap_or_default();
+                    let mut overrides = input.overrides;
+                    let mut rules = input.rules;
+                    for &idx in &input.indices {
+                        let Some(&floor) = sale_floors.get(&idx) else {
+                            continue;
+                        };
+                        let current = overrides
+                            .get(&idx)
+                            .copied()
+                            .unwrap_or_else(|| all_cards[idx].price_f64());
+                        if current < floor {
+                            overrides.insert(idx, floor);
+                            rules.insert(idx, id);
+                        }
+                    }
+                    NodeOutput {
+                        indices: input.indices,
+                        overrides,
+                        rules,
+                    }
+                }
+                NodeKind::ThinMarketGuard {
+                    common,
+                    uncommon,
+                    rare,
+                    mythic,
+                } => {
+                    let input = inputs.into_iter().next().unwrap_or_default();
+                    let mut overrides = input.overrides;
+                    let mut rules = input.rules;
+                    for &idx in &input.indices {
+                        let card = &all_cards[idx];
+                        // Only an upstream price change can be a cut to damp
+                        let Some(&suggested) = overrides.get(&idx) else {
+                            continue;
+                        };
+                        let max_spread = match card.rarity.to_lowercase().as_str() {
+                            "common" => *common,
+                            "uncommon" => *uncommon,
+                            "rare" => *rare,
+                            "mythic" => *mythic,
+                            _ => continue,
+                        } / 100.0;
+                        let Some(spread) = card
+                            .cardmarket_id
+                            .parse::<u64>()
+                            .ok()
+                            .and_then(|id| inventory_prices.get(&id))
+                            .and_then(|cached| {
+                                let is_foil = card.is_foil_card();
+                                market_spread(
+                                    cached.price_for(InventoryPriceSource::Low, is_foil),
+                                    cached.price_for(InventoryPriceSource::Trend, is_foil),
+                                )
+                            })
+                        else {
+                            continue;
+                        };
+                        let damped =
+                            thin_market_price(card.price_f64(), suggested, spread, max_spread);
+                        if damped != suggested {
+                            overrides.insert(idx, damped);
+                            rules.insert(idx, id);
+                        }
+                    }
+                    NodeOutput {
+                        indices: input.indices,
+                        overrides,
+                        rules,
                     }
                 }
                 _ => {
@@ -192,9 +279,15 @@ pub(super) fn evaluate_all(
                         .into_iter()
                         .filter(|(k, _)| surviving.contains(k))
                         .collect();
+                    let rules = input
+                        .rules
+                        .into_iter()
+                        .filter(|(k, _)| surviving.contains(k))
+                        .collect();
                     NodeOutput {
                         indices: filtered,
                         overrides,
+                        rules,
                     }
                 }
             }
@@ -207,20 +300,29 @@ pub(super) fn evaluate_all(
 }
 
 /// Merge price overrides from multiple inputs, keeping only entries for `surviving` indices.
-/// When two inputs both override the same card, the higher floor wins.
-fn merge_overrides(inputs: &[NodeOutput], surviving: &HashSet<usize>) -> HashMap<usize, f64> {
+/// When two inputs both override the same card, the higher floor wins, together with the
+/// node that set it.
+fn merge_overrides(
+    inputs: &[NodeOutput],
+    surviving: &HashSet<usize>,
+) -> (HashMap<usize, f64>, HashMap<usize, NodeId>) {
     let mut merged: HashMap<usize, f64> = HashMap::new();
+    let mut rules: HashMap<usize, NodeId> = HashMap::new();
     for input in inputs {
         for (&idx, &price) in &input.overrides {
-            if surviving.contains(&idx) {
-                let entry = merged.entry(idx).or_insert(price);
-                if price > *entry {
-                    *entry = price;
-                }
+            if !surviving.contains(&idx) {
+                continue;
+            }
+            if merged.get(&idx).is_none_or(|&best| price > best) {
+                merged.insert(idx, price);
+                match input.rules.get(&idx) {
+                    Some(&rule) => rules.insert(idx, rule),
+                    None => rules.remove(&idx),
+                };
             }
         }
     }
-    merged
+    (merged, rules)
 }
 
 #[cfg(test)]
@@ -229,7 +331,7 @@ pub(super) fn evaluate_counts(
     wires: &[Wire],
     all_cards: &[Card],
 ) -> HashMap<NodeId, usize> {
-    evaluate_all(nodes, wires, all_cards, &HashMap::new())
+    evaluate_all(nodes, wires, all_cards, &HashMap::new(), &HashMap::new())
         .into_iter()
         .map(|(id, out)| (id, out.indices.len()))
         .collect()
@@ -246,7 +348,9 @@ pub(super) fn filter_indices(kind: &NodeKind, indices: Vec<usize>, cards: &[Card
         | NodeKind::LogicalOr
         | NodeKind::LogicalNot
         | NodeKind::PriceFloor { .. }
-        | NodeKind::InventoryPrice { .. } => indices,
+        | NodeKind::InventoryPrice { .. }
+        | NodeKind::SalesFloor
+        | NodeKind::ThinMarketGuard { .. } => indices,
 
         NodeKind::FilterCondition { condition } => {
             if matches!(condition, ConditionFilter::Any) {
@@ -266,7 +370,7 @@ pub(super) fn filter_indices(kind: &NodeKind, indices: Vec<usize>, cards: &[Card
             let t = language.as_str();
             indices
                 .into_iter()
-                .filter(|&i| cards[i].language.eq_ignore_ascii_case(t))
+                .filter(|&i| canonical_language(&cards[i].language) == t)
                 .collect()
         }
 
@@ -285,7 +389,7 @@ pub(super) fn filter_indices(kind: &NodeKind, indices: Vec<usize>, cards: &[Card
         NodeKind::FilterPrice { min, max } => indices
             .into_iter()
             .filter(|&i| {
-                let p = cards[i].price.parse::<f64>().unwrap_or(0.0);
+                let p = cards[i].price_f64();
                 p >= *min && p <= *max
             })
             .collect(),
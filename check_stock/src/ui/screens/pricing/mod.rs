@@ -2,6 +2,7 @@
 #[path = "mod_tests.rs"]
 mod tests;
 
+mod bulk;
 mod constants;
 mod draw;
 mod eval;
@@ -13,25 +14,30 @@ mod toolbar;
 
 use crate::{
     api::inventory_sync::InventorySyncClient,
+    inventory_db,
     io::read_csv,
+    repricing_guard::{rolling_sale_averages, sale_floors, SALE_FLOOR_WINDOW_DAYS},
     ui::{
-        components::{FilePicker, InventorySyncBar, OutputWindow},
+        components::{spawn_task, FilePicker, InventorySyncBar, OutputWindow},
         state::{AppState, NodeId, NodeKind, PricingState, Screen},
         style,
     },
 };
+use bulk::show_bulk_reprice_controls;
 use constants::{CANVAS_BG, WIRE_COLOR, WIRE_PENDING_COLOR};
 use draw::{draw_bezier, draw_grid, draw_node_chrome};
 use eframe::egui;
 use eval::evaluate_all;
 use geometry::{in_port_pos, node_size, out_port_pos};
 use interaction::handle_interactions;
-use log::{error, info};
+use log::{error, info, warn};
 use params::show_node_params;
 use preview::{show_preview_window, sort_preview};
 use std::collections::HashMap;
 use toolbar::{show_add_toolbar, show_save_load_toolbar};
 
+pub use bulk::{reprice_inventory_from_file, BulkRepriceOutcome};
+
 // Re-exported for the tests submodule via `super::<name>`
 #[cfg(test)]
 use eval::{evaluate_counts, filter_indices};
@@ -85,6 +91,9 @@ impl PricingScreen {
             // ── Graph save / load ────────────────────────────────────────────
             show_save_load_toolbar(ui, &mut app_state.inventory_sync_url, state);
 
+            // ── Whole-inventory repricing ────────────────────────────────────
+            show_bulk_reprice_controls(ui, ctx, &app_state.inventory_sync_url, state);
+
             ui.add_space(2.0);
 
             // ── Add-node toolbar (only when CSV loaded) ──────────────────────
@@ -122,6 +131,7 @@ impl PricingScreen {
             Ok(cards) => {
                 info!("Loaded {} cards for pricing", cards.len());
                 state.cards = cards;
+                Self::load_sales_guardrails(state);
             }
             Err(e) => {
                 error!("Error loading CSV for pricing: {}", e);
@@ -131,6 +141,33 @@ impl PricingScreen {
     }
 }
 
+impl PricingScreen {
+    /// Loads sale floors and lot costs from the inventory DB. Without a DB
+    /// (or sales history) the guardrails simply have nothing to act on.
+    fn load_sales_guardrails(state: &mut PricingState) {
+        let today = mtg_common::dates::market_today();
+        state.sale_floors = match inventory_db::get_sold_events() {
+            Ok(events) => sale_floors(
+                &state.cards,
+                &rolling_sale_averages(&events, today, SALE_FLOOR_WINDOW_DAYS),
+            ),
+            Err(e) => {
+                warn!("Could not load sales history for pricing: {e}");
+                HashMap::new()
+            }
+        };
+        state.lot_copy_costs = inventory_db::lot_copy_costs().unwrap_or_else(|e| {
+            warn!("Could not load lot costs for pricing: {e}");
+            HashMap::new()
+        });
+        info!(
+            "Pricing guardrails: {} cards with a sale floor, {} lots with costs",
+            state.sale_floors.len(),
+            state.lot_copy_costs.len()
+        );
+    }
+}
+
 // ── Canvas ────────────────────────────────────────────────────────────────────
 
 fn show_canvas(ui: &mut egui::Ui, ctx: &egui::Context, state: &mut PricingState) {
@@ -166,6 +203,7 @@ fn show_canvas(ui: &mut egui::Ui, ctx: &egui::Context, state: &mut PricingState)
         &state.graph.wires,
         &state.cards,
         &state.inventory_prices,
+        &state.sale_floors,
     );
     let counts: HashMap<NodeId, usize> = all_outputs
         .iter()
@@ -281,28 +319,35 @@ fn show_inventory_sync_bar(
 ) {
     // Poll bulk-prices channel
     if let Some(rx) = &state.prices_rx {
-        if let Ok(result) = rx.try_recv() {
-            match result {
-                Ok(prices) => {
-                    let count = prices.len();
-                    for p in prices {
-                        state.inventory_prices.insert(p.id_product, p);
+        match rx.try_recv() {
+            Ok(result) => {
+                match result {
+                    Ok(prices) => {
+                        let count = prices.len();
+                        for p in prices {
+                            state.inventory_prices.insert(p.id_product, p);
+                        }
+                        info!(
+                            "Inventory sync price fetch succeeded: received {count} prices ({})",
+                            app_state.inventory_sync_url
+                        );
+                    }
+                    Err(e) => {
+                        error!(
+                            "Inventory sync price fetch failed ({}): {e}",
+                            app_state.inventory_sync_url
+                        );
+                        state.load_error = Some(format!("Price fetch failed: {e}"));
                     }
-                    info!(
-                        "Inventory sync price fetch succeeded: received {count} prices ({})",
-                        app_state.inventory_sync_url
-                    );
-                }
-                Err(e) => {
-                    error!(
-                        "Inventory sync price fetch failed ({}): {e}",
-                        app_state.inventory_sync_url
-                    );
-                    state.load_error = Some(format!("Price fetch failed: {e}"));
                 }
+                state.prices_rx = None;
+                state.prices_fetching = false;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                state.prices_rx = None;
+                state.prices_fetching = false;
             }
-            state.prices_rx = None;
-            state.prices_fetching = false;
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
         }
     }
     if state.prices_rx.is_some() {
@@ -323,7 +368,6 @@ fn show_inventory_sync_bar(
             }
 
             if state.prices_fetching {
-                ui.spinner();
                 ui.label(
                     egui::RichText::new("Fetching latest prices from inventory_sync…")
                         .color(style::TEXT_MUTED)
@@ -358,14 +402,12 @@ fn start_price_fetch(state: &mut PricingState, url: &str) {
         ids.len(),
     );
     state.prices_fetching = true;
-    let (tx, rx) = std::sync::mpsc::channel();
-    state.prices_rx = Some(rx);
     let client = InventorySyncClient::new(url);
-    std::thread::spawn(move || {
+    state.prices_rx = Some(spawn_task("Fetch inventory prices", move |_, tx| {
         let _ = tx.send(
             client
                 .latest_prices_blocking(&ids)
                 .map_err(|e| e.to_string()),
         );
-    });
+    }));
 }
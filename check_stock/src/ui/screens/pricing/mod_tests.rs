@@ -1972,7 +1972,13 @@ fn price_floor_adds_override_when_price_below_floor() {
         ),
     ];
     let wires = vec![make_wire(0, 1)];
-    let outputs = evaluate_all(&nodes, &wires, &cards, &std::collections::HashMap::new());
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &std::collections::HashMap::new(),
+    );
     let floor_out = &outputs[&1];
     assert_eq!(floor_out.indices, vec![0]);
     assert!((floor_out.overrides[&0] - 1.0).abs() < 0.001);
@@ -1997,11 +2003,122 @@ fn price_floor_no_override_when_price_above_floor() {
         ),
     ];
     let wires = vec![make_wire(0, 1)];
-    let outputs = evaluate_all(&nodes, &wires, &cards, &std::collections::HashMap::new());
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &std::collections::HashMap::new(),
+    );
     let floor_out = &outputs[&1];
     assert!(!floor_out.overrides.contains_key(&0));
 }
 
+#[test]
+fn sales_floor_raises_only_cards_sold_higher() {
+    // Card 0 sold at 2.00 € on average → raised; card 1 sold at 1.00 € but is
+    // listed at 3.00 € → unchanged; card 2 has no sales → unchanged.
+    let cards = vec![
+        make_card(
+            "A", "NM", "English", "false", "0.50", "Common", "Set", "s1", None,
+        ),
+        make_card(
+            "B", "NM", "English", "false", "3.00", "Rare", "Set", "s1", None,
+        ),
+        make_card(
+            "C", "NM", "English", "false", "0.10", "Common", "Set", "s1", None,
+        ),
+    ];
+    let nodes = vec![
+        make_node(0, NodeKind::CsvSource),
+        make_node(1, NodeKind::SalesFloor),
+    ];
+    let wires = vec![make_wire(0, 1)];
+    let sale_floors = std::collections::HashMap::from([(0, 2.0), (1, 1.0)]);
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &sale_floors,
+    );
+    let floor_out = &outputs[&1];
+    assert_eq!(floor_out.indices, vec![0, 1, 2]);
+    assert_eq!(floor_out.overrides.len(), 1);
+    assert!((floor_out.overrides[&0] - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn thin_market_guard_damps_cuts_on_wide_spreads() {
+    // Inventory Price (low) cuts both rares from 10.00 € to 6.00 €. Card 0's
+    // market is liquid (trend 7.50 → 20% spread) and takes the full cut; card
+    // 1's is thin (trend 30 → 80% spread, twice the 40% limit) and only takes
+    // half of it. Card 2 has no market data, so nothing changes upstream.
+    let mut cards = vec![
+        make_card(
+            "A", "NM", "English", "false", "10.00", "Rare", "Set", "s1", None,
+        ),
+        make_card(
+            "B", "NM", "English", "false", "10.00", "Rare", "Set", "s1", None,
+        ),
+        make_card(
+            "C", "NM", "English", "false", "10.00", "Rare", "Set", "s1", None,
+        ),
+    ];
+    for (i, card) in cards.iter_mut().enumerate() {
+        card.cardmarket_id = (i + 1).to_string();
+    }
+    let price = |id: u64, low: f64, trend: f64| crate::ui::state::LatestPrice {
+        id_product: id,
+        price_date: "2026-03-01".into(),
+        avg: None,
+        low: Some(low),
+        trend: Some(trend),
+        avg1: None,
+        avg7: None,
+        avg30: None,
+        avg_foil: None,
+        low_foil: None,
+        trend_foil: None,
+        avg1_foil: None,
+        avg7_foil: None,
+        avg30_foil: None,
+    };
+    let prices =
+        std::collections::HashMap::from([(1, price(1, 6.0, 7.5)), (2, price(2, 6.0, 30.0))]);
+    let nodes = vec![
+        make_node(0, NodeKind::CsvSource),
+        make_node(
+            1,
+            NodeKind::InventoryPrice {
+                source: crate::ui::state::InventoryPriceSource::Low,
+            },
+        ),
+        make_node(
+            2,
+            NodeKind::ThinMarketGuard {
+                common: 80.0,
+                uncommon: 70.0,
+                rare: 40.0,
+                mythic: 30.0,
+            },
+        ),
+    ];
+    let wires = vec![make_wire(0, 1), make_wire(1, 2)];
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &prices,
+        &std::collections::HashMap::new(),
+    );
+    let guard_out = &outputs[&2];
+    assert_eq!(guard_out.indices, vec![0, 1, 2]);
+    assert_eq!(guard_out.overrides.len(), 2);
+    assert!((guard_out.overrides[&0] - 6.0).abs() < 0.001);
+    assert!((guard_out.overrides[&1] - 8.0).abs() < 0.001);
+}
+
 #[test]
 fn price_floor_respects_rarity_per_card() {
     // Common gets 1.0 floor, Uncommon gets 2.0, Rare gets 5.0, Mythic gets 10.0.
@@ -2033,7 +2150,13 @@ fn price_floor_respects_rarity_per_card() {
         ),
     ];
     let wires = vec![make_wire(0, 1)];
-    let outputs = evaluate_all(&nodes, &wires, &cards, &std::collections::HashMap::new());
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &std::collections::HashMap::new(),
+    );
     let floor_out = &outputs[&1];
     assert!((floor_out.overrides[&0] - 1.0).abs() < 0.001);
     assert!((floor_out.overrides[&1] - 2.0).abs() < 0.001);
@@ -2073,7 +2196,13 @@ fn price_floor_downstream_filter_drops_override_with_card() {
         ),
     ];
     let wires = vec![make_wire(0, 1), make_wire(1, 2)];
-    let outputs = evaluate_all(&nodes, &wires, &cards, &std::collections::HashMap::new());
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &std::collections::HashMap::new(),
+    );
     let filter_out = &outputs[&2];
     assert_eq!(filter_out.indices, vec![0]); // only NM card survives
     assert!(filter_out.overrides.contains_key(&0)); // override propagated
@@ -2119,3 +2248,42 @@ fn price_floor_input_output_counts() {
     assert_eq!(kind.input_count(), 1);
     assert_eq!(kind.output_count(), 1);
 }
+
+#[test]
+fn overrides_remember_the_node_that_set_them() {
+    // CsvSource(0) → PriceFloor(1) → SalesFloor(2): card B sold for more than
+    // the floor, so the sales floor sets its final price.
+    let cards = vec![
+        make_card(
+            "A", "NM", "English", "false", "0.10", "Common", "Set", "s1", None,
+        ),
+        make_card(
+            "B", "NM", "English", "false", "0.10", "Common", "Set", "s1", None,
+        ),
+    ];
+    let nodes = vec![
+        make_node(0, NodeKind::CsvSource),
+        make_node(
+            1,
+            NodeKind::PriceFloor {
+                common: 1.0,
+                uncommon: 0.0,
+                rare: 0.0,
+                mythic: 0.0,
+            },
+        ),
+        make_node(2, NodeKind::SalesFloor),
+    ];
+    let wires = vec![make_wire(0, 1), make_wire(1, 2)];
+    let outputs = evaluate_all(
+        &nodes,
+        &wires,
+        &cards,
+        &std::collections::HashMap::new(),
+        &std::collections::HashMap::from([(1, 3.0)]),
+    );
+    let out = &outputs[&2];
+    assert!((out.overrides[&1] - 3.0).abs() < 0.001);
+    assert_eq!(out.rules[&0], 1);
+    assert_eq!(out.rules[&1], 2);
+}
@@ -159,6 +159,31 @@ pub(super) fn show_node_params(
                     .range(0.0..=99999.0),
             );
         }
+        NodeKind::ThinMarketGuard {
+            common,
+            uncommon,
+            rare,
+            mythic,
+        } => {
+            for (row, (value, prefix)) in [
+                (common, "C spread ≤ "),
+                (uncommon, "U spread ≤ "),
+                (rare, "R spread ≤ "),
+                (mythic, "M spread ≤ "),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                ui.put(
+                    param_row_rect(rect, port_rows, row, zoom),
+                    egui::DragValue::new(value)
+                        .prefix(prefix)
+                        .suffix(" %")
+                        .speed(0.5)
+                        .range(1.0..=100.0),
+                );
+            }
+        }
         NodeKind::InventoryPrice { source } => {
             let r = param_row_rect(rect, port_rows, 0, zoom);
             ui.allocate_new_ui(egui::UiBuilder::new().max_rect(r), |ui| {
@@ -176,6 +201,7 @@ pub(super) fn show_node_params(
         | NodeKind::Output
         | NodeKind::LogicalAnd
         | NodeKind::LogicalOr
-        | NodeKind::LogicalNot => {}
+        | NodeKind::LogicalNot
+        | NodeKind::SalesFloor => {}
     }
 }
@@ -1,5 +1,8 @@
 use crate::formatters::format_price_diff_csv;
+use crate::inventory_db::copy_cost_for;
 use crate::models::{canonical_condition, Card};
+use crate::repricing_guard::trend_below_cost;
+use crate::ui::state::{InventoryPriceSource, PriceFields};
 use crate::ui::{state::PricingState, style};
 use eframe::egui;
 use std::collections::HashMap;
@@ -67,6 +70,18 @@ pub(super) fn sort_preview(
     });
 }
 
+/// True when the card's market trend (inventory_sync prices) is below what
+/// its copy cost in its lot.
+fn is_below_cost(state: &PricingState, card: &Card) -> bool {
+    let trend = card
+        .cardmarket_id
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| state.inventory_prices.get(&id))
+        .and_then(|p| p.price_for(InventoryPriceSource::Trend, card.is_foil_card()));
+    trend_below_cost(trend, copy_cost_for(&state.lot_copy_costs, card))
+}
+
 pub(super) fn show_preview_window(ctx: &egui::Context, state: &mut PricingState) {
     // Clone indices so the window closure can also mutate state (sort on header click)
     let card_indices = state.cached_output.clone();
@@ -144,9 +159,15 @@ pub(super) fn show_preview_window(ctx: &egui::Context, state: &mut PricingState)
             });
             ui.separator();
 
+            let below_cost_count = card_indices
+                .iter()
+                .filter(|&&idx| is_below_cost(state, &state.cards[idx]))
+                .count();
+
             // ── Generate Diff CSV button (pinned to bottom) ────────────
             let changed_count = state.cached_price_overrides.len();
-            let button_height = 36.0;
+            let warning_height = if below_cost_count > 0 { 20.0 } else { 0.0 };
+            let button_height = 36.0 + warning_height;
             let available = ui.available_height();
             let scroll_height = (available - button_height - 14.0).max(60.0);
 
@@ -170,7 +191,7 @@ pub(super) fn show_preview_window(ctx: &egui::Context, state: &mut PricingState)
                         }
                         // Place cells inside the allocated row rect
                         let mut x = row_rect.min.x;
-                        let (price_str, price_color) =
+                        let (mut price_str, mut price_color) =
                             if let Some(&floor) = state.cached_price_overrides.get(&idx) {
                                 (
                                     format!("{} → {:.2}*", c.price, floor),
@@ -179,6 +200,10 @@ pub(super) fn show_preview_window(ctx: &egui::Context, state: &mut PricingState)
                             } else {
                                 (c.price.clone(), egui::Color32::from_rgb(160, 215, 140))
                             };
+                        if is_below_cost(state, c) {
+                            price_str.push_str(" ⚠");
+                            price_color = style::COLOR_ERROR;
+                        }
                         let cond_display = canonical_condition(&c.condition);
                         let cells: [(&str, egui::Color32); 8] = [
                             (c.name.as_str(), egui::Color32::WHITE),
@@ -216,6 +241,15 @@ pub(super) fn show_preview_window(ctx: &egui::Context, state: &mut PricingState)
             ui.add_space(6.0);
             ui.separator();
             ui.add_space(2.0);
+            if below_cost_count > 0 {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "⚠ {below_cost_count} cards have a market trend below their lot cost"
+                    ))
+                    .color(style::COLOR_ERROR),
+                )
+                .on_hover_text("Selling these at the market price would mean a loss");
+            }
             let label = format!("Generate Diff CSV ({changed_count} changed)");
             if style::primary_button_enabled(ui, &label, changed_count > 0).clicked() {
                 state.diff_output_content = format_price_diff_csv(
@@ -212,6 +212,33 @@ pub(super) fn show_add_toolbar(ui: &mut egui::Ui, graph: &mut NodeGraph) {
                 free_pos(graph),
             );
         }
+        if style::secondary_button(ui, "⌊ Sales Floor")
+            .on_hover_text(
+                "Never price below the average price a card actually sold at (same condition \
+                 and foil, last 90 days of sales in the inventory DB)",
+            )
+            .clicked()
+        {
+            graph.add_node(NodeKind::SalesFloor, free_pos(graph));
+        }
+        if style::secondary_button(ui, "≈ Thin Market Guard")
+            .on_hover_text(
+                "Damp price cuts on cards with few competing listings: when the gap between \
+                 the low and trend price exceeds the rarity's limit, only part of the cut is \
+                 applied (needs inventory_sync prices)",
+            )
+            .clicked()
+        {
+            graph.add_node(
+                NodeKind::ThinMarketGuard {
+                    common: 80.0,
+                    uncommon: 70.0,
+                    rare: 50.0,
+                    mythic: 40.0,
+                },
+                free_pos(graph),
+            );
+        }
 
         ui.add_space(16.0);
         ui.label(
@@ -1,25 +1,44 @@
 use crate::{
-    api::inventory_sync::{InventorySyncClient, PriceField, PriceFields},
+    api::{
+        fetch_card,
+        inventory_sync::{InventorySyncClient, PriceField, PriceFields},
+    },
     card_matching::MatchedCard,
+    collector_search::{card_matches_printing, parse_printing_query, PrintingQuery},
+    exclusions::is_excluded,
     formatters::format_update_stock_csv,
-    io::read_csv,
+    inventory_db,
+    io::{read_csv, CSV_EXTENSIONS},
+    playset::{
+        apply_conversion_to_listings, format_playset_conversion_csv, plan_merge, plan_split,
+        PlaysetConversion,
+    },
+    price_history_db::{load_price_history, PriceHistory},
     price_trends::roc_from_history,
+    set_symbols::Rarity,
     ui::{
-        components::{FilePicker, InventorySyncBar},
+        components::{spawn_task, FilePicker, InventorySyncBar, SetSymbols},
         screens::PickingState,
-        state::{AppState, Screen, SearchAction, SearchState, SelectedSearchCard},
+        state::{
+            AppState, PrintingLookupState, Screen, SearchAction, SearchState, SelectedSearchCard,
+        },
         style,
     },
 };
 use eframe::egui;
+use egui_extras::{Column, TableBuilder};
 use log::{debug, error, info};
 use std::collections::HashMap;
 use std::time::Instant;
 
+/// One price chart line: legend name, colour and `(date, price)` points.
+type ChartSeries<'a> = (&'a str, egui::Color32, &'a [(&'a str, f64)]);
+
 pub struct SearchScreen;
 
 impl SearchScreen {
     const SEARCH_DEBOUNCE_MS: u64 = 300; // Wait 300ms after user stops typing
+    const RESULT_ROW_HEIGHT: f32 = 22.0;
 
     fn check_delayed_search(state: &mut SearchState) {
         if state.search_needs_update {
@@ -55,7 +74,7 @@ impl SearchScreen {
             // ── File picker ─────────────────────────────────────────────────
             style::section_frame().show(ui, |ui| {
                 let browsed = FilePicker::new("CSV File:", &mut state.csv_path)
-                    .with_filter("CSV", &["csv"])
+                    .with_filter("CSV", CSV_EXTENSIONS)
                     .show(ui);
                 ui.add_space(6.0);
                 if (style::primary_button(ui, "Load CSV").clicked() || browsed)
@@ -63,12 +82,17 @@ impl SearchScreen {
                 {
                     Self::load_csv(app_state, state);
                 }
+                ui.add_space(6.0);
+                Self::show_db_source(ui, state);
             });
 
             ui.add_space(6.0);
 
             // ── Inventory Sync (enables the per-card price-history windows) ──
             InventorySyncBar::show(ui, ctx, app_state, |_, _| {});
+            FilePicker::new("History DB:", &mut app_state.price_history_db)
+                .with_filter("SQLite", &["db", "sqlite"])
+                .show(ui);
             ui.add_space(10.0);
 
             if !state.cards.is_empty() {
@@ -79,6 +103,9 @@ impl SearchScreen {
                 Self::show_search_controls(ui, state);
                 ui.add_space(10.0);
 
+                // Scryfall card for a "set number" search
+                Self::show_printing_panel(ui, state);
+
                 // Selected cards panel
                 Self::show_selected_cards_panel(ui, app_state, state, picking_state);
                 ui.add_space(10.0);
@@ -89,6 +116,7 @@ impl SearchScreen {
         });
 
         Self::show_history_window(ctx, state);
+        state.photo.show(ctx);
     }
 
     fn show_search_controls(ui: &mut egui::Ui, state: &mut SearchState) {
@@ -119,6 +147,7 @@ impl SearchScreen {
                     state.search_term.clear();
                     state.last_search_term.clear();
                     state.filtered_cards = state.cards.clone();
+                    state.printing = PrintingLookupState::default();
                     state.search_needs_update = false;
                 }
             });
@@ -193,7 +222,7 @@ impl SearchScreen {
         let total_price: f64 = state
             .selected_cards
             .iter()
-            .map(|sc| sc.card.price.parse::<f64>().unwrap_or(0.0) * sc.quantity as f64)
+            .map(|sc| sc.card.price_f64() * sc.quantity as f64)
             .sum();
 
         let card_count: i32 = state.selected_cards.iter().map(|sc| sc.quantity).sum();
@@ -210,7 +239,7 @@ impl SearchScreen {
                     .show(ui, |ui| {
                         for (i, sc) in state.selected_cards.iter().enumerate() {
                             ui.horizontal(|ui| {
-                                let price = sc.card.price.parse::<f64>().unwrap_or(0.0);
+                                let price = sc.card.price_f64();
                                 ui.label(format!(
                                     "{}x {} [{}] {} - {:.2} €",
                                     sc.quantity, sc.card.name, sc.card.language, sc.card.set, price
@@ -229,6 +258,9 @@ impl SearchScreen {
                 ui.add_space(5.0);
 
                 // Mode selector: send to lists, or write off as discarded stock.
+                if state.read_only_source {
+                    state.action_mode = SearchAction::AddToLists;
+                }
                 ui.horizontal(|ui| {
                     ui.label("Action:");
                     ui.radio_value(
@@ -236,10 +268,27 @@ impl SearchScreen {
                         SearchAction::AddToLists,
                         "Add to lists",
                     );
-                    ui.radio_value(
-                        &mut state.action_mode,
-                        SearchAction::Discard,
-                        "Discard (remove without affecting revenue)",
+                    ui.add_enabled_ui(!state.read_only_source, |ui| {
+                        ui.radio_value(
+                            &mut state.action_mode,
+                            SearchAction::Discard,
+                            "Discard (remove without affecting revenue)",
+                        );
+                        ui.radio_value(
+                            &mut state.action_mode,
+                            SearchAction::SplitPlaysets,
+                            "Split playsets",
+                        );
+                        ui.radio_value(
+                            &mut state.action_mode,
+                            SearchAction::MergePlaysets,
+                            "Merge into playsets",
+                        );
+                    })
+                    .response
+                    .on_disabled_hover_text(
+                        "Stock changes apply to the inventory loaded CSVs sync into; \
+                         load that inventory (or a CSV) to use them",
                     );
                 });
 
@@ -274,6 +323,31 @@ impl SearchScreen {
                             }
                         });
                     }
+                    SearchAction::SplitPlaysets | SearchAction::MergePlaysets => {
+                        let hint = if state.action_mode == SearchAction::SplitPlaysets {
+                            "Each selected playset becomes four single listings at a quarter of \
+                             the price. Singles in the selection are skipped."
+                        } else {
+                            "Every four selected singles become one playset at four times the \
+                             price; a remainder stays single. Playsets in the selection are skipped."
+                        };
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{hint} Updates the inventory DB (no sale recorded) and exports a \
+                                 stock-update CSV — import it into Cardmarket before your next sync."
+                            ))
+                            .color(style::TEXT_MUTED),
+                        );
+                        ui.add_space(4.0);
+                        ui.horizontal(|ui| {
+                            if style::primary_button(ui, "Convert & Export CSV…").clicked() {
+                                Self::perform_playset_conversion(state);
+                            }
+                            if style::secondary_button(ui, "Clear All").clicked() {
+                                state.selected_cards.clear();
+                            }
+                        });
+                    }
                 }
             });
     }
@@ -353,167 +427,233 @@ impl SearchScreen {
         state.quantity_inputs.clear();
     }
 
-    fn show_search_results(ui: &mut egui::Ui, app_state: &AppState, state: &mut SearchState) {
-        let total_results = state.filtered_cards.len();
-        let total_pages = total_results.div_ceil(state.results_per_page);
-
-        // Ensure current page is valid
-        if state.current_page >= total_pages && total_pages > 0 {
-            state.current_page = total_pages - 1;
+    /// Converts the selected listings between playsets and singles: exports the
+    /// conversion as a stock-update CSV (the save dialog is the confirmation
+    /// gate), shifts the inventory DB's listing counts without recording sales,
+    /// mirrors the change in the loaded card lists, and clears the selection.
+    fn perform_playset_conversion(state: &mut SearchState) {
+        let split = state.action_mode == SearchAction::SplitPlaysets;
+        let conversions: Vec<PlaysetConversion> = state
+            .selected_cards
+            .iter()
+            .filter_map(|sc| {
+                if split {
+                    plan_split(&sc.card, sc.quantity as i64)
+                } else {
+                    plan_merge(&sc.card, sc.quantity as i64)
+                }
+            })
+            .collect();
+        if conversions.is_empty() {
+            info!("Playset conversion: nothing in the selection can be converted");
+            return;
         }
 
-        // Calculate the range of results to display
-        let start_idx = state.current_page * state.results_per_page;
-        let end_idx = std::cmp::min(start_idx + state.results_per_page, total_results);
+        let csv = format_playset_conversion_csv(&conversions);
+        let file_name = if split {
+            "split_playsets.csv"
+        } else {
+            "merged_playsets.csv"
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(file_name)
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            info!("Playset conversion cancelled: no export file chosen");
+            return;
+        };
 
-        ui.horizontal(|ui| {
-            ui.label(format!(
-                "Found {} cards (showing {}-{} of {})",
-                total_results,
-                if total_results > 0 { start_idx + 1 } else { 0 },
-                end_idx,
-                total_results
-            ));
-        });
+        if let Err(e) = std::fs::write(&path, csv) {
+            error!("Failed to save playset conversion CSV: {e}");
+            return;
+        }
 
-        // Pagination controls
-        if total_pages > 1 {
-            ui.horizontal(|ui| {
-                ui.label("Results per page:");
-                egui::ComboBox::from_id_salt("results_per_page")
-                    .selected_text(format!("{}", state.results_per_page))
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut state.results_per_page, 50, "50");
-                        ui.selectable_value(&mut state.results_per_page, 100, "100");
-                        ui.selectable_value(&mut state.results_per_page, 200, "200");
-                        ui.selectable_value(&mut state.results_per_page, 500, "500");
-                    });
+        match crate::inventory_db::apply_playset_conversions(&conversions) {
+            Ok(updated) => info!(
+                "Converted {} listings across {updated} variants",
+                conversions.len()
+            ),
+            Err(e) => log::warn!("Inventory DB playset conversion failed: {e}"),
+        }
 
-                ui.add_space(20.0);
+        // Reflect the conversion in the loaded lists
+        for conversion in &conversions {
+            apply_conversion_to_listings(&mut state.cards, conversion);
+            apply_conversion_to_listings(&mut state.filtered_cards, conversion);
+        }
 
-                if style::secondary_button_enabled(ui, "⏮", state.current_page > 0).clicked() {
-                    state.current_page = 0;
-                }
-                if style::secondary_button_enabled(ui, "⏪", state.current_page > 0).clicked() {
-                    state.current_page -= 1;
-                }
+        state.selected_cards.clear();
+        state.quantity_inputs.clear();
+    }
 
-                ui.label(
-                    egui::RichText::new(format!(
-                        "Page {} of {}",
-                        state.current_page + 1,
-                        total_pages
-                    ))
-                    .color(style::TEXT_MUTED),
-                );
+    fn show_search_results(ui: &mut egui::Ui, app_state: &AppState, state: &mut SearchState) {
+        let total_results = state.filtered_cards.len();
 
-                if style::secondary_button_enabled(ui, "⏩", state.current_page < total_pages - 1)
-                    .clicked()
-                {
-                    state.current_page += 1;
+        ui.horizontal(|ui| {
+            ui.label(format!("Found {total_results} cards"));
+            if total_results > 0 {
+                ui.add_space(10.0);
+                if style::secondary_button(ui, "⏮ Top").clicked() {
+                    state.scroll_to_result = Some(0);
                 }
-                if style::secondary_button_enabled(ui, "⏭", state.current_page < total_pages - 1)
-                    .clicked()
-                {
-                    state.current_page = total_pages - 1;
+                if style::secondary_button(ui, "⏭ Bottom").clicked() {
+                    state.scroll_to_result = Some(total_results - 1);
                 }
-            });
-        }
+            }
+            ui.add_space(20.0);
+            SetSymbols::legend(ui);
+        });
 
         ui.add_space(5.0);
 
-        // Collect actions to apply after the grid (avoids borrow conflicts)
+        // Collect actions to apply after the table (avoids borrow conflicts)
         let mut add_actions: Vec<(usize, i32)> = Vec::new();
         let mut history_action: Option<usize> = None;
+        let mut photo_action: Option<usize> = None;
+
+        // Only the rows in view are laid out, so frame time does not grow with
+        // the number of results; the header stays put while scrolling.
+        let scroll_height = ui.available_height() - 20.0;
+        let mut table = TableBuilder::new(ui)
+            .id_salt("search_results")
+            .striped(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .max_scroll_height(scroll_height)
+            .column(Column::exact(44.0))
+            .column(Column::exact(56.0))
+            .column(Column::exact(44.0))
+            .column(Column::initial(220.0).resizable(true).clip(true))
+            .column(Column::initial(170.0).resizable(true).clip(true))
+            .column(Column::initial(80.0).resizable(true).clip(true))
+            .column(Column::initial(70.0).resizable(true).clip(true))
+            .column(Column::initial(70.0).resizable(true))
+            .column(Column::initial(110.0).resizable(true).clip(true))
+            .column(Column::initial(70.0).resizable(true).clip(true))
+            .column(Column::remainder().at_least(60.0));
+        if let Some(row) = state.scroll_to_result.take() {
+            table = table.scroll_to_row(row, Some(egui::Align::TOP));
+        }
 
-        egui::ScrollArea::vertical()
-            .max_height(ui.available_height() - 20.0)
-            .show(ui, |ui| {
-                egui::Grid::new("search_results")
-                    .num_columns(11)
-                    .spacing([10.0, 4.0])
-                    .striped(true)
-                    .show(ui, |ui| {
-                        // Header
-                        ui.strong("");
-                        ui.strong("Qty");
-                        ui.strong("Stock");
-                        ui.strong("Name");
-                        ui.strong("Set");
-                        ui.strong("Language");
-                        ui.strong("Condition");
-                        ui.strong("Price");
-                        ui.strong("Location");
-                        ui.strong("Rarity");
-                        ui.strong("");
-                        ui.end_row();
-
-                        // Results - only show the current page
-                        for (rel_idx, card) in
-                            state.filtered_cards[start_idx..end_idx].iter().enumerate()
-                        {
-                            let abs_idx = start_idx + rel_idx;
-                            let available: i32 = card.quantity.parse().unwrap_or(1).max(1);
-
-                            // Already selected quantity for this card
-                            let already_selected: i32 = state
-                                .selected_cards
-                                .iter()
-                                .find(|sc| sc.card.cardmarket_id == card.cardmarket_id)
-                                .map(|sc| sc.quantity)
-                                .unwrap_or(0);
-                            let remaining = (available - already_selected).max(0);
-
-                            // Add button (disabled when no remaining stock)
-                            let add_btn = ui.add_enabled(remaining > 0, egui::Button::new("Add"));
-                            if add_btn.clicked() {
-                                let qty = state.quantity_inputs.entry(abs_idx).or_insert(1);
-                                add_actions.push((abs_idx, *qty));
-                            }
-
-                            // Quantity input (capped to remaining stock)
+        table
+            .header(Self::RESULT_ROW_HEIGHT, |mut header| {
+                for title in [
+                    "",
+                    "Qty",
+                    "Stock",
+                    "Name",
+                    "Set",
+                    "Language",
+                    "Condition",
+                    "Price",
+                    "Location",
+                    "Rarity",
+                    "",
+                ] {
+                    header.col(|ui| {
+                        ui.strong(title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(Self::RESULT_ROW_HEIGHT, total_results, |mut row| {
+                    let abs_idx = row.index();
+                    let card = &state.filtered_cards[abs_idx];
+                    let available: i32 = card.quantity.parse().unwrap_or(1).max(1);
+
+                    // Already selected quantity for this card
+                    let already_selected: i32 = state
+                        .selected_cards
+                        .iter()
+                        .find(|sc| sc.card.cardmarket_id == card.cardmarket_id)
+                        .map(|sc| sc.quantity)
+                        .unwrap_or(0);
+                    let remaining = (available - already_selected).max(0);
+
+                    // Add button (disabled when no remaining stock)
+                    row.col(|ui| {
+                        let add_btn = ui.add_enabled(remaining > 0, egui::Button::new("Add"));
+                        if add_btn.clicked() {
                             let qty = state.quantity_inputs.entry(abs_idx).or_insert(1);
-                            if *qty > remaining {
-                                *qty = remaining.max(1);
-                            }
-                            ui.add(
-                                egui::DragValue::new(qty)
-                                    .range(1..=remaining.max(1))
-                                    .speed(0.1),
-                            );
-
-                            // Stock count right next to qty
-                            ui.label(&card.quantity);
-
-                            ui.label(&card.name);
-                            ui.label(&card.set);
-                            ui.label(&card.language);
-                            ui.label(&card.condition);
-                            ui.label(format!("{}€", card.price));
-                            ui.label(card.location.as_deref().unwrap_or(""));
-                            ui.label(&card.rarity);
-
-                            // Price-history window (needs the inventory_sync server)
-                            if ui
-                                .add(egui::Button::new("📈").small())
-                                .on_hover_text("Price history from inventory_sync")
-                                .clicked()
-                            {
-                                history_action = Some(abs_idx);
-                            }
+                            add_actions.push((abs_idx, *qty));
+                        }
+                    });
 
-                            ui.end_row();
+                    // Quantity input (capped to remaining stock)
+                    row.col(|ui| {
+                        let qty = state.quantity_inputs.entry(abs_idx).or_insert(1);
+                        if *qty > remaining {
+                            *qty = remaining.max(1);
                         }
+                        ui.add(
+                            egui::DragValue::new(qty)
+                                .range(1..=remaining.max(1))
+                                .speed(0.1),
+                        );
+                    });
+
+                    // Stock count right next to qty
+                    row.col(|ui| {
+                        ui.label(&card.quantity);
+                    });
+
+                    let rarity = Rarity::parse(&card.rarity);
+                    row.col(|ui| {
+                        ui.label(&card.name);
+                    });
+                    row.col(|ui| {
+                        state.set_symbols.show(ui, &card.set_code, rarity);
+                        ui.label(&card.set);
+                    });
+                    row.col(|ui| {
+                        ui.label(&card.language);
+                    });
+                    row.col(|ui| {
+                        ui.label(&card.condition);
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{}€", card.price));
                     });
+                    row.col(|ui| {
+                        ui.label(card.location.as_deref().unwrap_or(""));
+                    });
+                    row.col(|ui| {
+                        ui.label(
+                            egui::RichText::new(&card.rarity).color(style::rarity_color(rarity)),
+                        );
+                    });
+
+                    // Price-history window (inventory_sync database or server)
+                    row.col(|ui| {
+                        if ui
+                            .add(egui::Button::new("📈").small())
+                            .on_hover_text("Price history from inventory_sync")
+                            .clicked()
+                        {
+                            history_action = Some(abs_idx);
+                        }
+                        if ui
+                            .add(egui::Button::new("📷").small())
+                            .on_hover_text("Photo of this listing")
+                            .clicked()
+                        {
+                            photo_action = Some(abs_idx);
+                        }
+                    });
+                });
             });
 
         if let Some(abs_idx) = history_action {
             if let Some(card) = state.filtered_cards.get(abs_idx) {
                 let card = card.clone();
-                Self::spawn_history_fetch(state, &app_state.inventory_sync_url, &card);
+                Self::spawn_history_fetch(state, app_state, &card);
             }
         }
 
+        if let Some(card) = photo_action.and_then(|i| state.filtered_cards.get(i)) {
+            state.photo.open(card);
+        }
+
         // Apply add actions (capped to available stock)
         for (abs_idx, qty) in add_actions {
             if let Some(card) = state.filtered_cards.get(abs_idx) {
@@ -542,9 +682,28 @@ impl SearchScreen {
         state: &mut SearchState,
         _picking_state: &mut PickingState,
     ) {
+        // Cards on the do-not-sell list never go on a picking or invoice list
+        let exclusions = match inventory_db::get_exclusions() {
+            Ok(exclusions) => exclusions,
+            Err(e) => {
+                error!("Could not load the do-not-sell list: {e}");
+                return;
+            }
+        };
+        let (sellable, withheld): (Vec<_>, Vec<_>) = state
+            .selected_cards
+            .iter()
+            .partition(|sc| !is_excluded(&sc.card, &exclusions));
+        if !withheld.is_empty() {
+            info!(
+                "Leaving {} selected cards on the do-not-sell list off the lists",
+                withheld.len()
+            );
+        }
+
         // Group selected cards by name
         let mut groups: HashMap<String, Vec<SelectedSearchCard>> = HashMap::new();
-        for sc in &state.selected_cards {
+        for sc in sellable {
             groups
                 .entry(sc.card.name.clone())
                 .or_default()
@@ -595,8 +754,13 @@ impl SearchScreen {
     /// Days of history to chart in the per-card window.
     const HISTORY_DAYS: u32 = 120;
 
-    /// Kicks off a background `GET /api/prices/{id}` fetch for one card.
-    fn spawn_history_fetch(state: &mut SearchState, url: &str, card: &crate::models::Card) {
+    /// Kicks off a background history load for one card: read from the
+    /// inventory_sync database when one is set, else `GET /api/prices/{id}`.
+    fn spawn_history_fetch(
+        state: &mut SearchState,
+        app_state: &AppState,
+        card: &crate::models::Card,
+    ) {
         let Ok(id) = card.cardmarket_id.parse::<u64>() else {
             state.history.open = true;
             state.history.title = card.name.clone();
@@ -605,34 +769,66 @@ impl SearchScreen {
             state.history.loading = false;
             return;
         };
-        info!("Search: fetching price history for product {id} from {url}");
         state.history.open = true;
         state.history.title = card.name.clone();
         state.history.is_foil = card.is_foil_card();
         state.history.error = None;
         state.history.data = None;
         state.history.loading = true;
-        let (tx, rx) = std::sync::mpsc::channel();
-        state.history.rx = Some(rx);
+        let db_path = app_state.price_history_db.trim();
+        if !db_path.is_empty() {
+            let path = std::path::PathBuf::from(db_path);
+            info!(
+                "Search: reading price history for product {id} from {}",
+                path.display()
+            );
+            let since = (mtg_common::dates::market_today()
+                - chrono::Days::new(u64::from(Self::HISTORY_DAYS)))
+            .format("%Y-%m-%d")
+            .to_string();
+            state.history.rx = Some(spawn_task(
+                format!("Price history for {}", card.name),
+                move |_, tx| {
+                    let result = load_price_history(&path, id, &since)
+                        .map_err(|e| format!("{}: {e}", path.display()));
+                    let _ = tx.send(result);
+                },
+            ));
+            return;
+        }
+        let url = &app_state.inventory_sync_url;
+        info!("Search: fetching price history for product {id} from {url}");
         let client = InventorySyncClient::new(url);
-        std::thread::spawn(move || {
-            let result = client
-                .price_history_blocking(id, Some(Self::HISTORY_DAYS))
-                .map_err(|e| e.to_string());
-            let _ = tx.send(result);
-        });
+        state.history.rx = Some(spawn_task(
+            format!("Price history for {}", card.name),
+            move |_, tx| {
+                let result = client
+                    .price_history_blocking(id, Some(Self::HISTORY_DAYS))
+                    .map(PriceHistory::from)
+                    .map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            },
+        ));
     }
 
     /// Polls the fetch channel and renders the floating history window.
     fn show_history_window(ctx: &egui::Context, state: &mut SearchState) {
         if let Some(rx) = &state.history.rx {
-            if let Ok(result) = rx.try_recv() {
-                state.history.loading = false;
-                state.history.rx = None;
-                match result {
-                    Ok(data) => state.history.data = Some(data),
-                    Err(e) => state.history.error = Some(format!("History fetch failed: {e}")),
+            match rx.try_recv() {
+                Ok(result) => {
+                    state.history.loading = false;
+                    state.history.rx = None;
+                    match result {
+                        Ok(data) => state.history.data = Some(data),
+                        Err(e) => state.history.error = Some(format!("History fetch failed: {e}")),
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    state.history.loading = false;
+                    state.history.rx = None;
+                    state.history.error = Some("History fetch cancelled.".to_string());
                 }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
             }
         }
         if state.history.loading {
@@ -654,10 +850,7 @@ impl SearchScreen {
             .default_width(470.0)
             .show(ctx, |ui| {
                 if state.history.loading {
-                    ui.horizontal(|ui| {
-                        ui.spinner();
-                        ui.label("Fetching history…");
-                    });
+                    style::status_loading(ui, "Fetching history…");
                     return;
                 }
                 if let Some(err) = &state.history.error {
@@ -668,9 +861,9 @@ impl SearchScreen {
                     return;
                 };
 
-                if let Some(expansion) = &data.product.expansion_name {
+                if let Some(expansion) = &data.expansion_name {
                     ui.label(
-                        egui::RichText::new(format!("{} · {}", data.product.name, expansion))
+                        egui::RichText::new(format!("{} · {}", state.history.title, expansion))
                             .color(style::TEXT_MUTED)
                             .size(11.0),
                     );
@@ -678,14 +871,17 @@ impl SearchScreen {
                 }
 
                 let is_foil = state.history.is_foil;
-                let points: Vec<(&str, f64)> = data
-                    .history
-                    .iter()
-                    .filter_map(|p| {
-                        p.price_for(PriceField::Trend, is_foil)
-                            .map(|v| (p.price_date.as_str(), v))
-                    })
-                    .collect();
+                let series = |field| -> Vec<(&str, f64)> {
+                    data.points
+                        .iter()
+                        .filter_map(|p| {
+                            p.price_for(field, is_foil)
+                                .map(|v| (p.price_date.as_str(), v))
+                        })
+                        .collect()
+                };
+                let points = series(PriceField::Trend);
+                let avg = series(PriceField::Avg);
 
                 if points.len() < 2 {
                     ui.label(
@@ -695,7 +891,13 @@ impl SearchScreen {
                     return;
                 }
 
-                Self::draw_sparkline(ui, &points);
+                Self::draw_chart(
+                    ui,
+                    &[
+                        ("Trend", style::ACCENT, &points),
+                        ("Avg", Self::AVG_COLOR, &avg),
+                    ],
+                );
                 ui.add_space(6.0);
 
                 // Stats row: current trend + 7/30-day movement, computed
@@ -713,10 +915,10 @@ impl SearchScreen {
                     );
                     ui.add_space(10.0);
                     ui.label("Δ7d:");
-                    style::change_pct_label(ui, roc_from_history(&data.history, 7, is_foil));
+                    style::change_pct_label(ui, roc_from_history(&data.points, 7, is_foil));
                     ui.add_space(10.0);
                     ui.label("Δ30d:");
-                    style::change_pct_label(ui, roc_from_history(&data.history, 30, is_foil));
+                    style::change_pct_label(ui, roc_from_history(&data.points, 30, is_foil));
                     ui.add_space(10.0);
                     ui.label(
                         egui::RichText::new(format!("{} days shown", points.len()))
@@ -728,16 +930,29 @@ impl SearchScreen {
         state.history.open = open;
     }
 
-    /// Draws the trend-price line chart into an allocated rect.
-    fn draw_sparkline(ui: &mut egui::Ui, points: &[(&str, f64)]) {
-        let desired = egui::vec2(ui.available_width().min(440.0), 120.0);
+    /// Line colour of the average sell price series.
+    const AVG_COLOR: egui::Color32 = egui::Color32::from_rgb(218, 182, 96);
+
+    /// Draws the price lines into an allocated rect, sharing one price scale
+    /// and one date axis (every date any series has a price for), with a
+    /// legend in the top-right corner.
+    fn draw_chart(ui: &mut egui::Ui, series: &[ChartSeries]) {
+        let desired = egui::vec2(ui.available_width().min(440.0), 140.0);
         let (rect, _) = ui.allocate_exact_size(desired, egui::Sense::hover());
         let painter = ui.painter_at(rect);
         painter.rect_filled(rect, 4.0, style::PANEL_BG);
 
-        let values: Vec<f64> = points.iter().map(|(_, v)| *v).collect();
-        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
-        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let mut dates: Vec<&str> = series
+            .iter()
+            .flat_map(|(_, _, points)| points.iter().map(|(d, _)| *d))
+            .collect();
+        dates.sort_unstable();
+        dates.dedup();
+        let values = series
+            .iter()
+            .flat_map(|(_, _, points)| points.iter().map(|(_, v)| *v));
+        let min = values.clone().fold(f64::INFINITY, f64::min);
+        let max = values.fold(f64::NEG_INFINITY, f64::max);
         // Flat series still needs a visible band to map into.
         let (min, max) = if (max - min).abs() < 1e-9 {
             (min - 0.5, max + 0.5)
@@ -747,50 +962,190 @@ impl SearchScreen {
 
         let pad = 8.0;
         let inner = rect.shrink(pad);
-        let n = points.len();
-        let line: Vec<egui::Pos2> = values
-            .iter()
-            .enumerate()
-            .map(|(i, v)| {
-                let x = inner.left() + inner.width() * i as f32 / (n - 1) as f32;
-                let t = ((v - min) / (max - min)) as f32;
-                let y = inner.bottom() - inner.height() * t;
-                egui::pos2(x, y)
-            })
-            .collect();
-        painter.add(egui::Shape::line(
-            line,
-            egui::Stroke::new(1.5_f32, style::ACCENT),
-        ));
+        let span = dates.len().saturating_sub(1).max(1) as f32;
+        for (_, color, points) in series {
+            let line: Vec<egui::Pos2> = points
+                .iter()
+                .filter_map(|(date, v)| {
+                    let i = dates.binary_search(date).ok()?;
+                    let x = inner.left() + inner.width() * i as f32 / span;
+                    let t = ((v - min) / (max - min)) as f32;
+                    Some(egui::pos2(x, inner.bottom() - inner.height() * t))
+                })
+                .collect();
+            painter.add(egui::Shape::line(line, egui::Stroke::new(1.5_f32, *color)));
+        }
 
-        let label = |pos: egui::Pos2, align: egui::Align2, text: String| {
-            painter.text(
-                pos,
-                align,
-                text,
-                egui::FontId::proportional(10.0),
-                style::TEXT_MUTED,
-            );
+        let label = |pos: egui::Pos2, align: egui::Align2, text: String, color| {
+            painter.text(pos, align, text, egui::FontId::proportional(10.0), color)
         };
         label(
             rect.left_top() + egui::vec2(4.0, 2.0),
             egui::Align2::LEFT_TOP,
             format!("€{max:.2}"),
+            style::TEXT_MUTED,
         );
         label(
             rect.left_bottom() + egui::vec2(4.0, -2.0),
             egui::Align2::LEFT_BOTTOM,
             format!("€{min:.2}"),
+            style::TEXT_MUTED,
         );
         label(
             rect.right_bottom() + egui::vec2(-4.0, -2.0),
             egui::Align2::RIGHT_BOTTOM,
             format!(
                 "{} → {}",
-                points.first().map(|(d, _)| *d).unwrap_or_default(),
-                points.last().map(|(d, _)| *d).unwrap_or_default()
+                dates.first().copied().unwrap_or_default(),
+                dates.last().copied().unwrap_or_default()
             ),
+            style::TEXT_MUTED,
+        );
+        let mut legend = rect.right_top() + egui::vec2(-4.0, 2.0);
+        for (name, color, points) in series.iter().rev() {
+            if points.is_empty() {
+                continue;
+            }
+            let drawn = label(legend, egui::Align2::RIGHT_TOP, format!("— {name}"), *color);
+            legend.x = drawn.left() - 8.0;
+        }
+    }
+
+    // ── Printing lookup ─────────────────────────────────────────────────────
+
+    /// Kicks off a background Scryfall fetch for the printing in the search term.
+    fn spawn_printing_lookup(state: &mut SearchState, query: PrintingQuery) {
+        info!(
+            "Search: looking up {} #{} on Scryfall",
+            query.set_code, query.collector_number
+        );
+        let (set_code, collector_number) = (query.set_code.clone(), query.collector_number.clone());
+        state.printing.query = Some(query);
+        state.printing.rx = Some(spawn_task(
+            format!("Scryfall lookup {set_code} #{collector_number}"),
+            move |_, tx| {
+                let result = fetch_card(&set_code, &collector_number).map_err(|e| e.to_string());
+                let _ = tx.send(result);
+            },
+        ));
+    }
+
+    /// Polls the lookup channel and shows the Scryfall card with its stock count.
+    fn show_printing_panel(ui: &mut egui::Ui, state: &mut SearchState) {
+        if let Some(rx) = &state.printing.rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    state.printing.rx = None;
+                    match result {
+                        Ok(card) => state.printing.card = Some(card),
+                        Err(e) => {
+                            // Usually a term that only looks like "set number".
+                            debug!("Scryfall printing lookup failed: {e}");
+                            state.printing.error = Some(e);
+                        }
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => state.printing.rx = None,
+                Err(std::sync::mpsc::TryRecvError::Empty) => ui.ctx().request_repaint(),
+            }
+        }
+        let Some(query) = &state.printing.query else {
+            return;
+        };
+        let heading = format!(
+            "{} #{}",
+            query.set_code.to_uppercase(),
+            query.collector_number
         );
+        let in_stock: i32 = state
+            .cards
+            .iter()
+            .filter(|card| card_matches_printing(card, query))
+            .map(|card| card.quantity.parse::<i32>().unwrap_or(0))
+            .sum();
+
+        if state.printing.rx.is_some() {
+            style::status_loading(ui, &format!("Looking up {heading} on Scryfall…"));
+        } else if let Some(card) = &state.printing.card {
+            style::section_frame().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(&card.name).strong());
+                    ui.label(format!(
+                        "{} ({}) #{} · {}",
+                        card.set_name,
+                        card.set.to_uppercase(),
+                        card.collector_number,
+                        card.rarity
+                    ));
+                });
+                if in_stock > 0 {
+                    style::status_ok(ui, &format!("{in_stock} in stock"));
+                } else {
+                    ui.label("Not in stock");
+                }
+            });
+        } else {
+            return;
+        }
+        ui.add_space(10.0);
+    }
+
+    /// "Search the inventory DB" row: one inventory or all of them together.
+    fn show_db_source(ui: &mut egui::Ui, state: &mut SearchState) {
+        if state.inventories.is_empty() {
+            match inventory_db::list_inventories() {
+                Ok(inventories) => {
+                    state.inventories = inventories.into_iter().map(|i| i.id).collect()
+                }
+                Err(e) => log::warn!("Failed to list inventories: {e}"),
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label("Or search the inventory DB:");
+            let selected = state
+                .db_inventory
+                .clone()
+                .unwrap_or_else(|| "All inventories".to_string());
+            egui::ComboBox::from_id_salt("search_db_inventory")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut state.db_inventory, None, "All inventories");
+                    for id in &state.inventories {
+                        ui.selectable_value(&mut state.db_inventory, Some(id.clone()), id);
+                    }
+                });
+            if style::secondary_button(ui, "Load from DB").clicked() {
+                Self::load_from_db(state);
+            }
+        });
+    }
+
+    /// Loads the in-stock cards of the selected inventory (or all of them)
+    /// for searching. Nothing is synced.
+    fn load_from_db(state: &mut SearchState) {
+        let inventory = state.db_inventory.as_deref();
+        match inventory_db::get_in_stock_inventory(inventory) {
+            Ok(cards) => {
+                info!(
+                    "Loaded {} in-stock variants from inventory {} for searching",
+                    cards.len(),
+                    inventory.unwrap_or("(all)")
+                );
+                let active = inventory_db::active_inventory().ok();
+                let is_active = |id: &str| Some(id) == active.as_deref();
+                state.read_only_source = match inventory {
+                    Some(id) => !is_active(id),
+                    None => state.inventories.iter().any(|id| !is_active(id)),
+                };
+                state.cards = cards.clone();
+                state.filtered_cards = cards;
+                state.quantity_inputs.clear();
+                if !state.search_term.is_empty() {
+                    Self::perform_search(state);
+                }
+            }
+            Err(e) => error!("Failed to load inventory DB for search: {e}"),
+        }
     }
 
     fn load_csv(app_state: &mut AppState, state: &mut SearchState) {
@@ -799,6 +1154,7 @@ impl SearchScreen {
             Ok(cards) => {
                 info!("Loaded {} cards for searching", cards.len());
                 app_state.sync_inventory_guarded(&cards);
+                state.read_only_source = false;
                 state.cards = cards.clone();
                 state.filtered_cards = cards;
                 state.quantity_inputs.clear();
@@ -816,13 +1172,40 @@ impl SearchScreen {
     fn perform_search(state: &mut SearchState) {
         if state.search_term.is_empty() {
             state.filtered_cards = state.cards.clone();
-            state.current_page = 0; // Reset to first page
+            state.printing = PrintingLookupState::default();
+            state.scroll_to_result = Some(0);
             state.quantity_inputs.clear();
             return;
         }
 
         debug!("Performing search for: '{}'", state.search_term);
 
+        // "mh2 261" / "261 MH2" names one printing: look it up on Scryfall and,
+        // if the inventory has that set at all, show only that printing.
+        let printing = parse_printing_query(&state.search_term);
+        if printing != state.printing.query {
+            state.printing = PrintingLookupState::default();
+            if let Some(query) = &printing {
+                Self::spawn_printing_lookup(state, query.clone());
+            }
+        }
+        if let Some(query) = printing.filter(|query| {
+            state
+                .cards
+                .iter()
+                .any(|card| card.set_code.eq_ignore_ascii_case(&query.set_code))
+        }) {
+            state.filtered_cards = state
+                .cards
+                .iter()
+                .filter(|card| card_matches_printing(card, &query))
+                .cloned()
+                .collect();
+            state.scroll_to_result = Some(0);
+            state.quantity_inputs.clear();
+            return;
+        }
+
         let search_term = if state.search_case_sensitive {
             state.search_term.clone()
         } else {
@@ -836,7 +1219,7 @@ impl SearchScreen {
             .cloned()
             .collect();
 
-        state.current_page = 0; // Reset to first page on new search
+        state.scroll_to_result = Some(0); // Back to the top on a new search
         state.quantity_inputs.clear();
     }
 
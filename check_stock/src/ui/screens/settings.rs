@@ -0,0 +1,291 @@
+//! Settings screen.
+//!
+//! Hosts inventory DB maintenance: file size, rows per table and free-page
+//! fragmentation, plus a compaction (VACUUM) that runs on a worker thread
+//! with stage-by-stage progress. The DB grows after months of daily syncs;
+//! compacting gives the free pages back to the file system.
+//!
+//! With `[sql] slow_query_ms` set, the slow query log and the indexes the
+//! advisor suggests for it are shown here too (see [`crate::slow_queries`]).
+//!
+//! Also picks how inventory CSVs with malformed rows are loaded (strict or
+//! lenient, see [`crate::io::CsvLoadMode`]) for the rest of the session.
+
+use crate::{
+    inventory_db::{get_db_size_report, vacuum_db, DbSizeReport},
+    io::{csv_load_mode, set_csv_load_mode, CsvLoadMode},
+    slow_queries::index_advice,
+    ui::{
+        components::spawn_task,
+        state::{FetchMsg, Screen, SettingsState},
+        style,
+    },
+};
+use eframe::egui;
+use log::error;
+use mtg_common::sql_profile::{format_index_report, slow_queries, slow_query_threshold};
+
+/// Above this share of free pages the compact button is highlighted.
+const FRAGMENTATION_HINT: f64 = 0.2;
+
+pub struct SettingsScreen;
+
+impl SettingsScreen {
+    pub fn show(ctx: &egui::Context, current_screen: &mut Screen, state: &mut SettingsState) {
+        if !state.loaded {
+            state.loaded = true;
+            Self::refresh(state);
+        }
+        Self::poll_vacuum(ctx, state);
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .id_salt("settings_scroll")
+                .show(ui, |ui| {
+                    if style::back_button(ui, "Back") {
+                        *current_screen = Screen::Welcome;
+                    }
+                    ui.add_space(8.0);
+                    style::screen_heading(ui, "Settings");
+
+                    Self::show_database(ui, state);
+                    ui.add_space(8.0);
+                    Self::show_slow_queries(ui, state);
+                    ui.add_space(8.0);
+                    Self::show_csv_import(ui);
+                });
+        });
+    }
+
+    fn show_database(ui: &mut egui::Ui, state: &mut SettingsState) {
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Inventory database")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(4.0);
+
+            if let Some(err) = &state.error {
+                style::status_error(ui, err);
+                ui.add_space(4.0);
+            }
+
+            if let Some(report) = &state.db_report {
+                Self::show_report(ui, report);
+                ui.add_space(8.0);
+            }
+
+            let running = state.vacuum_rx.is_some();
+            let fragmented = state
+                .db_report
+                .as_ref()
+                .is_some_and(|r| r.fragmentation() >= FRAGMENTATION_HINT);
+            ui.horizontal(|ui| {
+                if style::secondary_button_enabled(ui, "Refresh", !running).clicked() {
+                    Self::refresh(state);
+                }
+                ui.add_space(12.0);
+                let compact = if fragmented {
+                    style::primary_button_enabled(ui, "Compact database", !running)
+                } else {
+                    style::secondary_button_enabled(ui, "Compact database", !running)
+                };
+                if compact.clicked() {
+                    Self::start_vacuum(state);
+                }
+                if !state.vacuum_status.is_empty() {
+                    style::status_loading(ui, &state.vacuum_status);
+                }
+            });
+
+            ui.add_space(4.0);
+            ui.label(
+                egui::RichText::new(
+                    "Compacting rewrites the database file without its free pages. \
+                     A dated backup is taken first.",
+                )
+                .size(11.0)
+                .color(style::TEXT_MUTED),
+            );
+        });
+    }
+
+    fn show_slow_queries(ui: &mut egui::Ui, state: &mut SettingsState) {
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Slow queries")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(4.0);
+            if slow_query_threshold().is_none() {
+                ui.label(
+                    egui::RichText::new(
+                        "Off. Set slow_query_ms in the [sql] section of the config file \
+                         (or D2D_SQL_SLOW_QUERY_MS) to log slow queries with their plans.",
+                    )
+                    .size(11.0)
+                    .color(style::TEXT_MUTED),
+                );
+                return;
+            }
+            if style::secondary_button(ui, "Build index report")
+                .on_hover_text("Suggest indexes for the full table scans logged so far")
+                .clicked()
+            {
+                state.slow_query_report = format_index_report(&slow_queries(), &index_advice());
+            }
+            if !state.slow_query_report.is_empty() {
+                ui.add_space(4.0);
+                ui.add(
+                    egui::TextEdit::multiline(&mut state.slow_query_report)
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(12)
+                        .font(egui::TextStyle::Monospace),
+                );
+            }
+        });
+    }
+
+    fn show_csv_import(ui: &mut egui::Ui) {
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Inventory CSV import")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(4.0);
+            let mut mode = csv_load_mode();
+            ui.radio_value(
+                &mut mode,
+                CsvLoadMode::Strict,
+                "Strict — a malformed row fails the whole load",
+            );
+            ui.radio_value(
+                &mut mode,
+                CsvLoadMode::Lenient,
+                "Lenient — skip malformed rows and show an error report",
+            );
+            if mode != csv_load_mode() {
+                set_csv_load_mode(mode);
+            }
+        });
+    }
+
+    fn show_report(ui: &mut egui::Ui, report: &DbSizeReport) {
+        egui::Grid::new("db_size_report")
+            .num_columns(2)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("File size:").strong());
+                ui.label(format_bytes(report.file_bytes));
+                ui.end_row();
+                ui.label(egui::RichText::new("Fragmentation:").strong());
+                ui.label(format!(
+                    "{:.1}% free ({} of {} pages, {} reclaimable)",
+                    report.fragmentation() * 100.0,
+                    report.free_pages,
+                    report.page_count,
+                    format_bytes(report.reclaimable_bytes().max(0) as u64)
+                ));
+                ui.end_row();
+            });
+
+        ui.add_space(6.0);
+        egui::Grid::new("db_table_rows")
+            .num_columns(2)
+            .striped(true)
+            .spacing([16.0, 2.0])
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Table").strong());
+                ui.label(egui::RichText::new("Rows").strong());
+                ui.end_row();
+                for (table, rows) in &report.table_rows {
+                    ui.label(table);
+                    ui.label(rows.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn refresh(state: &mut SettingsState) {
+        match get_db_size_report() {
+            Ok(report) => {
+                state.db_report = Some(report);
+                state.error = None;
+            }
+            Err(e) => {
+                error!("Failed to read inventory DB size: {e}");
+                state.error = Some(format!("Failed to read database: {e}"));
+            }
+        }
+    }
+
+    fn start_vacuum(state: &mut SettingsState) {
+        state.vacuum_status = "Starting…".to_string();
+        state.error = None;
+        state.vacuum_rx = Some(spawn_task("Compact database", |task, tx| {
+            let result = vacuum_db(|stage| {
+                task.set_status(stage.to_string());
+                let _ = tx.send(FetchMsg::Progress(stage.to_string()));
+            })
+            .map_err(|e| e.to_string());
+            let _ = tx.send(FetchMsg::Done(result));
+        }));
+    }
+
+    /// Drains the compaction channel; the final result refreshes the report.
+    fn poll_vacuum(ctx: &egui::Context, state: &mut SettingsState) {
+        let Some(rx) = state.vacuum_rx.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(FetchMsg::Progress(stage)) => state.vacuum_status = stage,
+                Ok(FetchMsg::Done(Ok(result))) => {
+                    state.vacuum_status = format!(
+                        "Compacted: {} → {}",
+                        format_bytes(result.bytes_before),
+                        format_bytes(result.bytes_after)
+                    );
+                    Self::refresh(state);
+                    return;
+                }
+                Ok(FetchMsg::Done(Err(e))) => {
+                    state.vacuum_status = String::new();
+                    state.error = Some(format!("Compaction failed: {e}"));
+                    return;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    state.vacuum_rx = Some(rx);
+                    ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                    return;
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    state.vacuum_status = String::new();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Human-readable file size (`"12.3 MB"`).
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
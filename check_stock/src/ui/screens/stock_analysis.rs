@@ -1,13 +1,29 @@
 use crate::{
-    inventory_db::{AgingBucket, DbStats, LotBreakdown, OldestInStockEntry, SalesVelocity},
-    io::read_csv,
+    api::inventory_sync::{InventorySyncClient, PriceFields},
+    duplicates::{find_duplicates, format_merge_csv},
+    inventory_db::{
+        self, get_in_stock_cards, normalize_inventory_id, AgingBucket, DbStats, LotBreakdown,
+        OldestInStockEntry, SalesVelocity,
+    },
+    inventory_heatmap::{build_heatmap, cards_in_cell, max_cell, HeatmapMetric, RARITY_COLUMNS},
+    io::{read_csv, CSV_EXTENSIONS},
+    set_symbols::Rarity,
     ui::{
-        components::FilePicker,
-        state::{AppState, LotSortColumn, Screen, StockAnalysisState},
+        components::{spawn_task, FilePicker, InventorySyncBar},
+        state::{AppState, FetchMsg, LotSortColumn, Screen, StockAnalysisState},
         style,
     },
+    valuation::{value_inventory, MarketBasis, Valuation},
 };
 use eframe::egui;
+use log::info;
+use mtg_common::{parse_decimal, DecimalLocale};
+
+/// Per-card rows shown in the valuation delta table.
+const VALUATION_ROWS: usize = 50;
+
+/// Size of one heatmap cell.
+const HEATMAP_CELL: egui::Vec2 = egui::vec2(72.0, 20.0);
 
 pub struct StockAnalysisScreen;
 
@@ -16,8 +32,10 @@ impl StockAnalysisScreen {
         // Load stats once on first render (non-blocking, DB is local SQLite)
         if !state.stats_loaded {
             state.stats_loaded = true;
+            state.stats_inventory = inventory_db::active_inventory().ok();
             Self::refresh_stats(state);
         }
+        Self::poll_market_fetch(ctx, state);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             egui::ScrollArea::vertical()
@@ -33,11 +51,13 @@ impl StockAnalysisScreen {
                     // ── File picker ─────────────────────────────────────────
                     style::section_frame().show(ui, |ui| {
                         if FilePicker::new("Inventory CSV:", &mut state.inventory_path)
-                            .with_filter("CSV", &["csv"])
+                            .with_filter("CSV", CSV_EXTENSIONS)
                             .show(ui)
                         {
                             if let Ok(inventory) = read_csv(&state.inventory_path) {
                                 app_state.sync_inventory_guarded(&inventory);
+                                state.duplicates = find_duplicates(&inventory);
+                                state.duplicates_status = None;
                             }
                             Self::refresh_stats(state);
                         }
@@ -45,6 +65,15 @@ impl StockAnalysisScreen {
 
                     ui.add_space(10.0);
 
+                    // ── Inventories ─────────────────────────────────────────
+                    if Self::show_inventories(ui, state) {
+                        state.market_prices.clear();
+                        state.valuation = None;
+                        Self::refresh_stats(state);
+                    }
+
+                    ui.add_space(10.0);
+
                     // ── Database stats panel ────────────────────────────────
                     if let Some(db_stats) = state.db_stats.clone() {
                         if Self::show_db_stats(ui, &db_stats, state) {
@@ -53,12 +82,475 @@ impl StockAnalysisScreen {
                     } else if let Some(err) = &state.db_stats_error {
                         style::status_error(ui, &format!("Stats error: {err}"));
                     }
+
+                    // ── Set/rarity heatmap ──────────────────────────────────
+                    if !state.heatmap_rows.is_empty() {
+                        ui.add_space(10.0);
+                        Self::show_heatmap(ui, state);
+                    }
+
+                    // ── Duplicate listings ──────────────────────────────────
+                    if !state.duplicates.is_empty() {
+                        ui.add_space(10.0);
+                        Self::show_duplicates(ui, state);
+                    }
+
+                    // ── Market valuation ────────────────────────────────────
+                    ui.add_space(10.0);
+                    Self::show_valuation(ui, ctx, app_state, state);
+                });
+        });
+    }
+
+    /// Articles listed in several CSV rows, with a merge CSV export.
+    fn show_duplicates(ui: &mut egui::Ui, state: &mut StockAnalysisState) {
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(format!("Duplicate Listings ({})", state.duplicates.len()))
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(2.0);
+            ui.label(
+                egui::RichText::new(
+                    "Same article split over several rows — merge them before uploading back to Cardmarket",
+                )
+                .size(11.0)
+                .color(style::TEXT_MUTED),
+            );
+            ui.add_space(4.0);
+
+            egui::ScrollArea::vertical()
+                .id_salt("duplicates_rows")
+                .max_height(250.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("duplicates_grid")
+                        .num_columns(6)
+                        .striped(true)
+                        .spacing([12.0, 2.0])
+                        .show(ui, |ui| {
+                            for header in ["Card", "Rows", "Qty", "Prices", "Differs in", "Merged"] {
+                                ui.label(egui::RichText::new(header).strong());
+                            }
+                            ui.end_row();
+                            for group in &state.duplicates {
+                                let card = &group.merged;
+                                let foil = if card.is_foil_card() { " ✦" } else { "" };
+                                ui.label(format!(
+                                    "{} ({}, {}, {}){foil}",
+                                    card.name, card.set_code, card.condition, card.language
+                                ));
+                                ui.label(group.rows.len().to_string());
+                                ui.label(format!("×{}", group.total_quantity()));
+                                let (low, high) = group.price_range;
+                                if high - low > 0.005 {
+                                    ui.label(format!(
+                                        "€{low:.2}–€{high:.2} ({:+.0}%)",
+                                        group.price_spread_pct()
+                                    ));
+                                } else {
+                                    ui.label(format!("€{low:.2}"));
+                                }
+                                ui.label(group.differing.join(", "));
+                                ui.label(format!("×{} @ €{}", card.quantity, card.price));
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if style::secondary_button(ui, "Export merge CSV").clicked() {
+                    Self::export_merge_csv(state);
+                }
+                match &state.duplicates_status {
+                    Some(Ok(msg)) => style::status_ok(ui, msg),
+                    Some(Err(msg)) => style::status_error(ui, msg),
+                    None => {}
+                }
+            });
+        });
+    }
+
+    fn export_merge_csv(state: &mut StockAnalysisState) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name("merged_duplicates.csv")
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            info!("Duplicate merge export cancelled");
+            return;
+        };
+        let csv = format_merge_csv(&state.duplicates);
+        state.duplicates_status = Some(match std::fs::write(&path, csv) {
+            Ok(()) => {
+                info!(
+                    "Exported {} duplicate merges to {}",
+                    state.duplicates.len(),
+                    path.display()
+                );
+                Ok(format!("Saved {}", path.display()))
+            }
+            Err(e) => Err(format!("Failed to save merge CSV: {e}")),
+        });
+    }
+
+    /// Listed vs. market value of the in-stock inventory, with per-card deltas.
+    fn show_valuation(
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        app_state: &mut AppState,
+        state: &mut StockAnalysisState,
+    ) {
+        let url = app_state.inventory_sync_url.clone();
+        let loading = state.market_rx.is_some();
+        InventorySyncBar::show(ui, ctx, app_state, |ui, connected| {
+            if connected {
+                let label = if loading {
+                    "Fetching…"
+                } else {
+                    "Fetch market prices"
+                };
+                if style::secondary_button(ui, label).clicked() && !loading {
+                    Self::spawn_market_fetch(state, &url);
+                }
+            }
+            if !state.market_status.is_empty() {
+                ui.label(
+                    egui::RichText::new(&state.market_status)
+                        .color(style::TEXT_MUTED)
+                        .size(11.0),
+                );
+            }
+        });
+
+        ui.add_space(6.0);
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Market Valuation")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(2.0);
+            ui.label(
+                egui::RichText::new(
+                    "Listed (asking) value next to market value — insurers want the latter",
+                )
+                .size(11.0)
+                .color(style::TEXT_MUTED),
+            );
+            ui.add_space(4.0);
+
+            let before = state.valuation_basis;
+            ui.horizontal(|ui| {
+                ui.label("Market price:");
+                egui::ComboBox::from_id_salt("valuation_basis")
+                    .selected_text(state.valuation_basis.as_str())
+                    .show_ui(ui, |ui| {
+                        for basis in MarketBasis::all() {
+                            ui.selectable_value(&mut state.valuation_basis, *basis, basis.as_str());
+                        }
+                    });
+            });
+            if state.valuation_basis != before && !state.market_prices.is_empty() {
+                Self::rebuild_valuation(state);
+            }
+
+            if let Some(err) = &state.market_error {
+                style::status_error(ui, err);
+            }
+
+            match &state.valuation {
+                Some(valuation) => {
+                    ui.add_space(6.0);
+                    Self::show_valuation_table(ui, valuation);
+                }
+                None => {
+                    ui.label(
+                        egui::RichText::new("Fetch market prices to value the inventory.")
+                            .color(style::TEXT_MUTED),
+                    );
+                }
+            }
+        });
+    }
+
+    fn show_valuation_table(ui: &mut egui::Ui, v: &Valuation) {
+        egui::Grid::new("valuation_summary")
+            .num_columns(4)
+            .spacing([16.0, 4.0])
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("Listed value:").strong());
+                ui.label(format!("€{:.2}", v.listed_total));
+                ui.label(
+                    egui::RichText::new(format!("Market value ({}):", v.basis.as_str())).strong(),
+                );
+                ui.label(format!("€{:.2}", v.market_total));
+                ui.end_row();
+
+                ui.label(egui::RichText::new("Listed (priced cards):").strong());
+                ui.label(format!("€{:.2}", v.priced_listed_total()));
+                ui.label(egui::RichText::new("Market − listed:").strong());
+                ui.label(
+                    egui::RichText::new(format!("{:+.2} €", v.delta()))
+                        .color(delta_color(v.delta())),
+                );
+                ui.end_row();
+            });
+        if v.unpriced_copies > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} copies without a market price (listed at €{:.2}) are left out of the market value.",
+                    v.unpriced_copies, v.unpriced_listed_value
+                ))
+                .size(11.0)
+                .color(style::TEXT_MUTED),
+            );
+        }
+
+        ui.add_space(6.0);
+        ui.label(egui::RichText::new("Largest per-card differences").strong());
+        egui::ScrollArea::vertical()
+            .id_salt("valuation_rows")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("valuation_rows_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .spacing([12.0, 2.0])
+                    .show(ui, |ui| {
+                        for header in ["Card", "Qty", "Listed", "Market", "Listed value", "Delta"] {
+                            ui.label(egui::RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for row in v.rows.iter().take(VALUATION_ROWS) {
+                            let foil = if row.is_foil { " ✦" } else { "" };
+                            ui.label(format!(
+                                "{} ({}, {}){foil}",
+                                row.name, row.set_code, row.condition
+                            ));
+                            ui.label(format!("×{}", row.quantity));
+                            ui.label(format!("€{:.2}", row.listed_price));
+                            ui.label(
+                                row.market_price
+                                    .map(|p| format!("€{p:.2}"))
+                                    .unwrap_or_else(|| "—".to_string()),
+                            );
+                            ui.label(format!("€{:.2}", row.listed_value()));
+                            match row.delta() {
+                                Some(d) => ui.label(
+                                    egui::RichText::new(format!("{d:+.2} €")).color(delta_color(d)),
+                                ),
+                                None => ui.label("—"),
+                            };
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Inventory selection: which inventory the stats cover, which one loaded
+    /// CSVs sync into, and a size overview per inventory. Returns whether the
+    /// stats need reloading.
+    fn show_inventories(ui: &mut egui::Ui, state: &mut StockAnalysisState) -> bool {
+        let mut changed = false;
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Inventories")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(4.0);
+
+            ui.horizontal(|ui| {
+                ui.label("Show stats for:");
+                let selected = state
+                    .stats_inventory
+                    .clone()
+                    .unwrap_or_else(|| "All inventories".to_string());
+                egui::ComboBox::from_id_salt("stats_inventory")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut state.stats_inventory, None, "All inventories")
+                            .changed();
+                        for inventory in &state.inventories {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut state.stats_inventory,
+                                    Some(inventory.id.clone()),
+                                    &inventory.id,
+                                )
+                                .changed();
+                        }
+                    });
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Loaded CSVs sync into:");
+                let mut active = state.active_inventory.clone();
+                egui::ComboBox::from_id_salt("active_inventory")
+                    .selected_text(&active)
+                    .show_ui(ui, |ui| {
+                        for inventory in &state.inventories {
+                            ui.selectable_value(&mut active, inventory.id.clone(), &inventory.id);
+                        }
+                    });
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.new_inventory_name)
+                        .desired_width(140.0)
+                        .hint_text("new inventory"),
+                );
+                if style::secondary_button(ui, "Add").clicked() {
+                    match normalize_inventory_id(&state.new_inventory_name) {
+                        Some(id) => {
+                            active = id;
+                            state.new_inventory_name.clear();
+                        }
+                        None => {
+                            state.inventory_error =
+                                Some("Inventory names use letters, digits, '-' and '_'".to_string())
+                        }
+                    }
+                }
+                if active != state.active_inventory {
+                    match inventory_db::set_active_inventory(&active) {
+                        Ok(()) => {
+                            info!("Loaded CSVs now sync into inventory '{active}'");
+                            state.active_inventory = active;
+                            state.inventory_error = None;
+                            changed = true;
+                        }
+                        Err(e) => state.inventory_error = Some(e.to_string()),
+                    }
+                }
+            });
+            if let Some(err) = &state.inventory_error {
+                style::status_error(ui, err);
+            }
+
+            ui.add_space(4.0);
+            egui::Grid::new("inventories_grid")
+                .num_columns(5)
+                .striped(true)
+                .spacing([16.0, 2.0])
+                .show(ui, |ui| {
+                    for header in ["Inventory", "In Stock", "Copies", "Value", "Last Sync"] {
+                        ui.label(egui::RichText::new(header).strong());
+                    }
+                    ui.end_row();
+                    for inventory in &state.inventories {
+                        let name = if inventory.id == state.active_inventory {
+                            format!("{} (syncs CSVs)", inventory.id)
+                        } else {
+                            inventory.id.clone()
+                        };
+                        ui.label(name);
+                        ui.label(inventory.in_stock_variants.to_string());
+                        ui.label(inventory.in_stock_copies.to_string());
+                        ui.label(format!("€{:.2}", inventory.in_stock_value));
+                        ui.label(inventory.last_sync.as_deref().unwrap_or("—"));
+                        ui.end_row();
+                    }
                 });
         });
+        changed
+    }
+
+    /// Fetches the latest price rows for every in-stock card.
+    fn spawn_market_fetch(state: &mut StockAnalysisState, url: &str) {
+        let ids: Vec<u64> = match get_in_stock_cards(state.stats_inventory.as_deref()) {
+            Ok(cards) => cards
+                .iter()
+                .filter_map(|c| c.cardmarket_id.parse::<u64>().ok())
+                .collect::<std::collections::HashSet<u64>>()
+                .into_iter()
+                .collect(),
+            Err(e) => {
+                state.market_error = Some(format!("Failed to read inventory: {e}"));
+                return;
+            }
+        };
+        if ids.is_empty() {
+            state.market_error = Some("No in-stock cards with cardmarket IDs found.".to_string());
+            return;
+        }
+
+        info!(
+            "Valuation: fetching prices for {} products from {url}",
+            ids.len()
+        );
+        state.market_error = None;
+        state.market_status = format!("Fetching prices for {} products…", ids.len());
+        let client = InventorySyncClient::new(url);
+        state.market_rx = Some(spawn_task("Fetch market prices", move |_, tx| {
+            let result = client
+                .latest_prices_blocking(&ids)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(FetchMsg::Done(result));
+        }));
+    }
+
+    fn poll_market_fetch(ctx: &egui::Context, state: &mut StockAnalysisState) {
+        let Some(rx) = state.market_rx.take() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(FetchMsg::Progress(msg)) => {
+                state.market_status = msg;
+                state.market_rx = Some(rx);
+            }
+            Ok(FetchMsg::Done(Ok(prices))) => {
+                state.market_status = format!("{} market prices", prices.len());
+                state.market_prices = prices.into_iter().map(|p| (p.id_product, p)).collect();
+                Self::rebuild_valuation(state);
+            }
+            Ok(FetchMsg::Done(Err(e))) => {
+                state.market_status = String::new();
+                state.market_error = Some(format!("Market price fetch failed: {e}"));
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                state.market_rx = Some(rx);
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                state.market_status = String::new();
+            }
+        }
+    }
+
+    /// Re-values the current DB inventory against the fetched market prices.
+    fn rebuild_valuation(state: &mut StockAnalysisState) {
+        let cards = match get_in_stock_cards(state.stats_inventory.as_deref()) {
+            Ok(cards) => cards,
+            Err(e) => {
+                state.market_error = Some(format!("Failed to read inventory: {e}"));
+                return;
+            }
+        };
+        let field = state.valuation_basis.field();
+        let prices = &state.market_prices;
+        state.valuation = Some(value_inventory(&cards, state.valuation_basis, |card| {
+            let id = card.cardmarket_id.parse::<u64>().ok()?;
+            prices.get(&id)?.price_for(field, card.is_foil)
+        }));
     }
 
     fn refresh_stats(state: &mut StockAnalysisState) {
-        match crate::inventory_db::get_db_stats() {
+        match inventory_db::list_inventories()
+            .and_then(|inventories| Ok((inventories, inventory_db::active_inventory()?)))
+        {
+            Ok((inventories, active)) => {
+                state.inventories = inventories;
+                state.active_inventory = active;
+            }
+            Err(e) => log::warn!("Failed to list inventories: {e}"),
+        }
+        let inventory = state.stats_inventory.as_deref();
+        match inventory_db::get_db_stats(inventory) {
             Ok(stats) => {
                 state.db_stats = Some(stats);
                 state.db_stats_error = None;
@@ -67,6 +559,149 @@ impl StockAnalysisScreen {
                 state.db_stats_error = Some(e.to_string());
             }
         }
+        state.heatmap_cards = get_in_stock_cards(inventory).unwrap_or_else(|e| {
+            log::warn!("Heatmap: failed to read in-stock cards: {e}");
+            Vec::new()
+        });
+        state.heatmap_rows = build_heatmap(&state.heatmap_cards, state.heatmap_metric);
+    }
+
+    /// Copies or value per set (rows) and rarity (columns); clicking a cell
+    /// lists its cards.
+    fn show_heatmap(ui: &mut egui::Ui, state: &mut StockAnalysisState) {
+        style::section_frame().show(ui, |ui| {
+            ui.label(
+                egui::RichText::new("Set × Rarity Heatmap")
+                    .strong()
+                    .size(14.0)
+                    .color(style::TEXT_PRIMARY),
+            );
+            ui.add_space(2.0);
+            ui.label(
+                egui::RichText::new(
+                    "In-stock copies or listed value per set — spot sets overstuffed with bulk",
+                )
+                .size(11.0)
+                .color(style::TEXT_MUTED),
+            );
+            ui.add_space(4.0);
+
+            let before = state.heatmap_metric;
+            ui.horizontal(|ui| {
+                ui.label("Shade by:");
+                for metric in [HeatmapMetric::Copies, HeatmapMetric::Value] {
+                    ui.selectable_value(&mut state.heatmap_metric, metric, metric.label());
+                }
+            });
+            if state.heatmap_metric != before {
+                state.heatmap_rows = build_heatmap(&state.heatmap_cards, state.heatmap_metric);
+            }
+            ui.add_space(4.0);
+
+            let metric = state.heatmap_metric;
+            let max = max_cell(&state.heatmap_rows, metric);
+            let mut clicked = None;
+            egui::ScrollArea::vertical()
+                .id_salt("heatmap_rows")
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("heatmap_grid")
+                        .num_columns(RARITY_COLUMNS.len() + 2)
+                        .spacing([4.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.label(egui::RichText::new("Set").strong());
+                            for rarity in RARITY_COLUMNS {
+                                ui.label(
+                                    egui::RichText::new(rarity.label())
+                                        .strong()
+                                        .color(style::rarity_color(rarity)),
+                                );
+                            }
+                            ui.label(egui::RichText::new("Total").strong());
+                            ui.end_row();
+
+                            for row in &state.heatmap_rows {
+                                ui.label(&row.set_code);
+                                for (rarity, cell) in RARITY_COLUMNS.iter().zip(&row.cells) {
+                                    let selected =
+                                        state.heatmap_selection.as_ref().is_some_and(|(set, r)| {
+                                            *set == row.set_code && r == rarity
+                                        });
+                                    let intensity = if max > 0.0 {
+                                        (metric.of(cell) / max) as f32
+                                    } else {
+                                        0.0
+                                    };
+                                    let text = match metric {
+                                        _ if cell.copies == 0 => String::new(),
+                                        HeatmapMetric::Copies => cell.copies.to_string(),
+                                        HeatmapMetric::Value => format!("€{:.0}", cell.value),
+                                    };
+                                    if heatmap_cell(ui, *rarity, intensity, &text, selected)
+                                        .clicked()
+                                        && cell.copies > 0
+                                    {
+                                        clicked = Some((row.set_code.clone(), *rarity));
+                                    }
+                                }
+                                let total = row.total();
+                                ui.label(match metric {
+                                    HeatmapMetric::Copies => format!("×{}", total.copies),
+                                    HeatmapMetric::Value => format!("€{:.2}", total.value),
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(cell) = clicked {
+                state.heatmap_selection = if state.heatmap_selection.as_ref() == Some(&cell) {
+                    None
+                } else {
+                    Some(cell)
+                };
+            }
+
+            if let Some((set_code, rarity)) = &state.heatmap_selection {
+                let cards = cards_in_cell(&state.heatmap_cards, set_code, *rarity);
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{set_code} · {} ({} listings)",
+                        rarity.label(),
+                        cards.len()
+                    ))
+                    .strong(),
+                );
+                egui::ScrollArea::vertical()
+                    .id_salt("heatmap_cards")
+                    .max_height(220.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("heatmap_cards_grid")
+                            .num_columns(5)
+                            .striped(true)
+                            .spacing([12.0, 2.0])
+                            .show(ui, |ui| {
+                                for header in ["Card", "Condition", "Qty", "Price", "Location"] {
+                                    ui.label(egui::RichText::new(header).strong());
+                                }
+                                ui.end_row();
+                                for card in cards {
+                                    let foil = if card.is_foil { " ✦" } else { "" };
+                                    ui.label(format!("{} ({}){foil}", card.name, card.language));
+                                    ui.label(&card.condition);
+                                    ui.label(format!("×{}", card.quantity));
+                                    ui.label(format!("€{:.2}", card.price));
+                                    ui.label(if card.location.is_empty() {
+                                        "—"
+                                    } else {
+                                        &card.location
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+        });
     }
 
     /// Renders the stats panel. Returns `true` when a lot cost was edited and the
@@ -496,10 +1131,9 @@ impl StockAnalysisScreen {
                         // another cell). An empty value clears the recorded cost.
                         let enter_pressed = ui.input(|i| i.key_pressed(egui::Key::Enter));
                         if resp.lost_focus() || enter_pressed {
-                            let trimmed = buf.trim().replace(',', ".");
-                            if trimmed.is_empty() {
+                            if buf.trim().is_empty() {
                                 clear = Some(lot.lot.clone());
-                            } else if let Ok(v) = trimmed.parse::<f64>() {
+                            } else if let Some(v) = parse_decimal(buf, DecimalLocale::Auto) {
                                 if v >= 0.0 {
                                     save = Some((lot.lot.clone(), v));
                                 }
@@ -625,3 +1259,54 @@ impl StockAnalysisScreen {
         needs_refresh
     }
 }
+
+/// One clickable heatmap cell, shaded in the rarity's colour by `intensity`
+/// (0–1).
+fn heatmap_cell(
+    ui: &mut egui::Ui,
+    rarity: Rarity,
+    intensity: f32,
+    text: &str,
+    selected: bool,
+) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(HEATMAP_CELL, egui::Sense::click());
+    let fill = if text.is_empty() {
+        style::PANEL_BG
+    } else {
+        style::rarity_color(rarity).gamma_multiply(0.15 + 0.85 * intensity)
+    };
+    let painter = ui.painter();
+    painter.rect_filled(rect, 3.0, fill);
+    if selected || response.hovered() {
+        painter.rect_stroke(
+            rect,
+            3.0,
+            egui::Stroke::new(1.5_f32, style::ACCENT),
+            egui::StrokeKind::Inside,
+        );
+    }
+    if !text.is_empty() {
+        let color = if intensity > 0.5 {
+            style::PANEL_BG
+        } else {
+            style::TEXT_PRIMARY
+        };
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(11.0),
+            color,
+        );
+    }
+    response.on_hover_cursor(egui::CursorIcon::PointingHand)
+}
+
+/// Green when the market values a listing above its asking price, red below.
+fn delta_color(delta: f64) -> egui::Color32 {
+    if delta >= 0.0 {
+        egui::Color32::from_rgb(60, 190, 90)
+    } else {
+        egui::Color32::from_rgb(220, 60, 60)
+    }
+}
@@ -1,20 +1,26 @@
 use crate::{
-    card_matching::{find_matching_cards, MatchedCard},
+    card_matching::{find_matches_for_wantslist, MatchedCard},
+    exclusions::without_excluded,
     formatters::{
-        format_invoice_list, format_picking_list, format_regular_output, format_update_stock_csv,
+        format_invoice_list_in, format_picking_list_in, format_regular_output,
+        format_update_stock_csv,
     },
-    io::{load_wantslist, read_csv},
+    fulfillment::{fulfillment_trends, FulfillmentRecord},
+    inventory_db,
+    io::{load_wantslist, read_csv, CSV_EXTENSIONS},
     models::{Card, WantsEntry},
+    translation::{look_up_names, scryfall_name, NameLookups, NameTranslation},
     ui::{
-        components::{FilePicker, OutputWindow},
+        components::{spawn_task, FilePicker, OutputWindow, TaskReceiver},
         language::Language,
         screens::PickingState,
         state::{AppState, OutputFormat, Screen},
         style,
     },
+    wantslist_optimizer::{optimize_wantslist, OptimizationGoal},
 };
 use eframe::egui;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 
 // ============================================================================
 // Business logic — free functions, no egui dependency, fully testable
@@ -39,21 +45,44 @@ pub(super) fn perform_stock_check(
     wantslist: &[WantsEntry],
     language: Language,
     language_only: bool,
+) -> StockCheckResult {
+    let per_entry = find_matches_for_wantslist(wantslist, inventory, Some(language), language_only);
+    summarize_matches(wantslist, per_entry)
+}
+
+/// Like [`perform_stock_check`], but allocates listings across the whole
+/// wantslist at once to minimize total cost or maximize margin.
+/// `cost_basis` is the per-copy acquisition cost used for the margin goal.
+pub(super) fn perform_optimized_stock_check(
+    inventory: &[Card],
+    wantslist: &[WantsEntry],
+    language: Language,
+    language_only: bool,
+    goal: OptimizationGoal,
+    cost_basis: impl Fn(&Card) -> f64,
+) -> StockCheckResult {
+    let allocation = optimize_wantslist(
+        wantslist,
+        inventory,
+        Some(language),
+        language_only,
+        goal,
+        cost_basis,
+    );
+    summarize_matches(wantslist, allocation)
+}
+
+/// Builds the result totals from the matched listings of each wantslist entry.
+fn summarize_matches<'a>(
+    wantslist: &[WantsEntry],
+    per_entry: impl IntoIterator<Item = Vec<MatchedCard<'a>>>,
 ) -> StockCheckResult {
     let mut all_matches = Vec::new();
     let mut total_found = 0;
     let mut total_wanted = 0;
     let mut missing_cards = Vec::new();
 
-    for wants_entry in wantslist {
-        let matched_cards = find_matching_cards(
-            &wants_entry.name,
-            wants_entry.quantity,
-            inventory,
-            Some(language),
-            language_only,
-        );
-
+    for (wants_entry, matched_cards) in wantslist.iter().zip(per_entry) {
         let found_qty: i32 = matched_cards.iter().map(|mc| mc.quantity).sum();
         total_found += found_qty;
         total_wanted += wants_entry.quantity;
@@ -86,6 +115,63 @@ pub(super) fn perform_stock_check(
     }
 }
 
+/// Renames every entry that found cards after its first card's name in
+/// `translation`; entries without cards keep the wantslist's name.
+pub(super) fn translate_match_names(
+    all_matches: &mut [CardMatchGroup],
+    translation: &NameTranslation,
+) {
+    for (name, _, cards) in all_matches {
+        if let Some((card, _, _)) = cards.first() {
+            *name = translation.name_of(card).to_string();
+        }
+    }
+}
+
+/// Summarizes a stock check for the fulfillment history. `wantslist` is the
+/// path or deck link the check ran against; files are recorded by name.
+pub(super) fn fulfillment_record(
+    result: &StockCheckResult,
+    wantslist: &str,
+    discount_percent: f32,
+    checked_at: &str,
+) -> FulfillmentRecord {
+    let found_value: f64 = result
+        .all_matches
+        .iter()
+        .flat_map(|(_, _, cards)| cards)
+        .map(|(card, quantity, _)| card.price_f64() * *quantity as f64)
+        .sum();
+    FulfillmentRecord {
+        checked_at: checked_at.to_string(),
+        wantslist: wantslist_label(wantslist),
+        wanted: result.total_wanted as i64,
+        found: result.total_found as i64,
+        missing_cards: result.missing_cards.len() as i64,
+        quoted_value: found_value * (1.0 - discount_percent as f64 / 100.0),
+    }
+}
+
+/// A wantslist file by its name; deck links as they are.
+fn wantslist_label(wantslist: &str) -> String {
+    if wantslist.contains("://") {
+        wantslist.trim().to_string()
+    } else {
+        std::path::Path::new(wantslist.trim())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| wantslist.trim().to_string())
+    }
+}
+
+/// Name of a picking session started from the wantslist at `wantslist`.
+pub(super) fn session_name(wantslist: &str) -> String {
+    match wantslist_label(wantslist) {
+        label if label.is_empty() => "Picking list".to_string(),
+        label => label,
+    }
+}
+
 /// Maps all match groups into MatchedCard references without filtering.
 pub(super) fn all_as_matched_cards<'a>(
     all_matches: &'a [CardMatchGroup],
@@ -154,11 +240,12 @@ impl StockCheckerScreen {
             ui.add_space(8.0);
 
             style::screen_heading(ui, "MTG Stock Checker");
+            Self::poll_name_lookups(ui.ctx(), state);
 
             // ── File pickers ────────────────────────────────────────────────
             style::section_frame().show(ui, |ui| {
                 if FilePicker::new("Inventory CSV:", &mut state.inventory_path)
-                    .with_filter("CSV", &["csv"])
+                    .with_filter("CSV", CSV_EXTENSIONS)
                     .show(ui)
                 {
                     if let Ok(inventory) = read_csv(&state.inventory_path) {
@@ -231,6 +318,65 @@ impl StockCheckerScreen {
                         state.discount_percent = discount;
                     }
                 });
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label("Listing selection:");
+                    let selected_text = state
+                        .optimization_goal
+                        .map(|goal| goal.label())
+                        .unwrap_or("Per entry");
+                    egui::ComboBox::new("optimization_goal_selector", "")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut state.optimization_goal, None, "Per entry");
+                            for goal in [
+                                OptimizationGoal::MinimizeCost,
+                                OptimizationGoal::MaximizeMargin,
+                            ] {
+                                ui.selectable_value(
+                                    &mut state.optimization_goal,
+                                    Some(goal),
+                                    goal.label(),
+                                );
+                            }
+                        });
+                })
+                .response
+                .on_hover_text(
+                    "Per entry picks listings for each wantslist line on its own. The \
+                     optimizing modes allocate the whole wantslist at once, so lines never \
+                     share a listing's stock; margin uses recorded lot costs.",
+                );
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    ui.label("Card names in:");
+                    let selected_text = state
+                        .output_name_language
+                        .map(|lang| lang.as_str())
+                        .unwrap_or("Wantslist language");
+                    egui::ComboBox::new("output_name_language_selector", "")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut state.output_name_language,
+                                None,
+                                "Wantslist language",
+                            );
+                            for lang in Language::all() {
+                                ui.selectable_value(
+                                    &mut state.output_name_language,
+                                    Some(*lang),
+                                    lang.as_str(),
+                                );
+                            }
+                        });
+                })
+                .response
+                .on_hover_text(
+                    "Names the found cards on the quote, picking and invoice lists in this \
+                     language, from the inventory's localized names or Scryfall when those \
+                     are empty",
+                );
             });
 
             ui.add_space(10.0);
@@ -244,6 +390,7 @@ impl StockCheckerScreen {
             });
 
             ui.add_space(6.0);
+            Self::show_fulfillment_trends(ui, state);
             ui.separator();
 
             if state.show_selection || state.selection_mode {
@@ -258,7 +405,7 @@ impl StockCheckerScreen {
         });
     }
 
-    fn check_stock(state: &mut AppState) -> Result<(), Box<dyn std::error::Error>> {
+    pub(crate) fn check_stock(state: &mut AppState) -> Result<(), Box<dyn std::error::Error>> {
         if state.inventory_path.is_empty() || state.wantslist_path.is_empty() {
             error!(
                 "Missing file paths - inventory: '{}', wantslist: '{}'",
@@ -275,18 +422,47 @@ impl StockCheckerScreen {
 
         let inventory = read_csv(&state.inventory_path)?;
         let wantslist = load_wantslist(&state.wantslist_path)?;
+        // Reserved copies must never reach a quote, so a missing list is an error
+        let exclusions = inventory_db::get_exclusions()
+            .map_err(|e| format!("Could not load the do-not-sell list: {e}"))?;
+        let (inventory, withheld) = without_excluded(&inventory, &exclusions);
+        if withheld > 0 {
+            info!("Holding back {withheld} listings on the do-not-sell list");
+        }
 
         state.all_matches.clear();
         state.selected.clear();
         state.show_selection = false;
         state.selection_mode = false;
 
-        let result = perform_stock_check(
-            &inventory,
-            &wantslist,
-            state.preferred_language,
-            state.preferred_language_only,
-        );
+        let result = match state.optimization_goal {
+            None => perform_stock_check(
+                &inventory,
+                &wantslist,
+                state.preferred_language,
+                state.preferred_language_only,
+            ),
+            Some(goal) => {
+                info!("Optimizing whole wantslist: {}", goal.label());
+                // Margin needs per-copy costs; without the DB every copy costs 0.
+                let costs = match goal {
+                    OptimizationGoal::MaximizeMargin => inventory_db::lot_copy_costs()
+                        .unwrap_or_else(|e| {
+                            warn!("Could not load lot costs, assuming zero cost: {e}");
+                            Default::default()
+                        }),
+                    OptimizationGoal::MinimizeCost => Default::default(),
+                };
+                perform_optimized_stock_check(
+                    &inventory,
+                    &wantslist,
+                    state.preferred_language,
+                    state.preferred_language_only,
+                    goal,
+                    |card| inventory_db::copy_cost_for(&costs, card).unwrap_or(0.0),
+                )
+            }
+        };
 
         info!(
             "Stock check complete: found {} of {} cards ({} card types checked)",
@@ -308,11 +484,190 @@ impl StockCheckerScreen {
             );
         }
 
+        let record = fulfillment_record(
+            &result,
+            &state.wantslist_path,
+            state.discount_percent,
+            &chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        );
+        if let Err(e) = inventory_db::record_fulfillment(&record) {
+            warn!("Could not record fulfillment history: {e}");
+        }
+        // Reloaded the next time the trends section is shown
+        state.fulfillment_history = None;
+
         state.all_matches = result.all_matches;
-        Self::generate_regular_output(state);
+        state.withheld_listings = withheld;
+        // Stored names now, Scryfall for the rest in the background
+        state.name_lookup_rx = None;
+        state.name_translation = state.output_name_language.map(|language| {
+            let cards = state
+                .all_matches
+                .iter()
+                .flat_map(|(_, _, cards)| cards.iter().map(|(card, _, _)| card));
+            NameTranslation::from_stored(cards, language)
+        });
+        if let Some(translation) = &state.name_translation {
+            if !translation.missing.is_empty() {
+                state.name_lookup_rx = Some(Self::spawn_name_lookups(translation));
+            }
+        }
+        Self::apply_name_translation(state);
         Ok(())
     }
 
+    /// Looks the names of the translation's missing cards up on Scryfall.
+    fn spawn_name_lookups(translation: &NameTranslation) -> TaskReceiver<NameLookups> {
+        let (missing, language) = (translation.missing.clone(), translation.language);
+        spawn_task(
+            format!("Scryfall names in {}", language.as_str()),
+            move |handle, tx| {
+                let total = missing.len();
+                let mut done = 0;
+                let found = look_up_names(&missing, language, |card, language| {
+                    if handle.is_cancelled() {
+                        return None;
+                    }
+                    handle.set_status(format!("{} of {total}", done + 1));
+                    let name = scryfall_name(card, language);
+                    done += 1;
+                    handle.set_progress(done as f32 / total as f32);
+                    name
+                });
+                let _ = tx.send(found);
+            },
+        )
+    }
+
+    /// Merges the Scryfall names once they arrive; a cancelled lookup leaves
+    /// the missing cards untranslated.
+    fn poll_name_lookups(ctx: &egui::Context, state: &mut AppState) {
+        let Some(rx) = &state.name_lookup_rx else {
+            return;
+        };
+        let found = match rx.try_recv() {
+            Ok(found) => found,
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => Vec::new(),
+        };
+        state.name_lookup_rx = None;
+        if let Some(translation) = &mut state.name_translation {
+            translation.apply_lookups(found);
+        }
+        Self::apply_name_translation(state);
+        // An open picking or invoice list picks up the new names
+        if state.show_output_window {
+            for format in [OutputFormat::PickingList, OutputFormat::InvoiceList] {
+                if state.output_window_title == format.title() {
+                    Self::generate_selected_output(state, format);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Renames the matches after the current translation and rebuilds the
+    /// check's output with its notes.
+    fn apply_name_translation(state: &mut AppState) {
+        if let Some(translation) = &state.name_translation {
+            info!("{}", translation.summary());
+            translate_match_names(&mut state.all_matches, translation);
+        }
+        Self::generate_regular_output(state);
+        if let Some(translation) = &state.name_translation {
+            state
+                .output
+                .push_str(&format!("\n{}\n", translation.summary()));
+        }
+        if state.withheld_listings > 0 {
+            state.output.push_str(&format!(
+                "\n{} listings held back by the do-not-sell list\n",
+                state.withheld_listings
+            ));
+        }
+    }
+
+    /// Collapsible table of how well each wantslist was covered over time.
+    fn show_fulfillment_trends(ui: &mut egui::Ui, state: &mut AppState) {
+        egui::CollapsingHeader::new("Fulfillment trends")
+            .id_salt("fulfillment_trends")
+            .show(ui, |ui| {
+                let history = state.fulfillment_history.get_or_insert_with(|| {
+                    inventory_db::get_fulfillment_history().map_err(|e| e.to_string())
+                });
+                let records = match history {
+                    Ok(records) if records.is_empty() => {
+                        ui.label("No stock checks recorded yet.");
+                        return;
+                    }
+                    Ok(records) => records,
+                    Err(e) => {
+                        style::status_error(ui, &format!("Could not load history: {e}"));
+                        return;
+                    }
+                };
+
+                ui.label(
+                    egui::RichText::new(
+                        "Share of wanted copies found per check, averaged over all checks of a \
+                         wantslist; the change compares the latest check with the first.",
+                    )
+                    .size(11.0)
+                    .color(style::TEXT_MUTED),
+                );
+                egui::Grid::new("fulfillment_trends_grid")
+                    .striped(true)
+                    .num_columns(7)
+                    .show(ui, |ui| {
+                        for heading in [
+                            "Wantslist",
+                            "Checks",
+                            "Avg fulfillment",
+                            "Latest",
+                            "Change",
+                            "Avg missing",
+                            "Avg quoted value",
+                        ] {
+                            ui.strong(heading);
+                        }
+                        ui.end_row();
+                        for trend in fulfillment_trends(records) {
+                            ui.label(&trend.wantslist)
+                                .on_hover_text(format!("Last checked {}", trend.last_checked));
+                            ui.label(trend.checks.to_string());
+                            ui.label(format!("{:.0}%", trend.average_rate * 100.0));
+                            ui.label(format!("{:.0}%", trend.latest_rate * 100.0));
+                            match trend.rate_change {
+                                Some(change) if change > 0.0 => {
+                                    ui.colored_label(
+                                        style::COLOR_SUCCESS,
+                                        format!("+{:.0} pp", change * 100.0),
+                                    );
+                                }
+                                Some(change) if change < 0.0 => {
+                                    ui.colored_label(
+                                        style::COLOR_ERROR,
+                                        format!("{:.0} pp", change * 100.0),
+                                    );
+                                }
+                                Some(_) => {
+                                    ui.label("±0 pp");
+                                }
+                                None => {
+                                    ui.label("—");
+                                }
+                            }
+                            ui.label(format!("{:.1}", trend.average_missing_cards));
+                            ui.label(format!("{:.2} €", trend.average_quoted_value));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
     fn show_selection_view(
         ui: &mut egui::Ui,
         state: &mut AppState,
@@ -346,7 +701,7 @@ impl StockCheckerScreen {
                                 card.language,
                                 set_name,
                                 card.condition,
-                                card.price.parse::<f64>().unwrap_or(0.0),
+                                card.price_f64(),
                                 location_info
                             );
                             if ui.checkbox(&mut checked, label).changed() {
@@ -402,7 +757,12 @@ impl StockCheckerScreen {
                 );
             });
 
-        if !state.all_matches.is_empty() {
+        // Nothing to pick or select when no wanted card is in stock
+        if state
+            .all_matches
+            .iter()
+            .any(|(_, _, cards)| !cards.is_empty())
+        {
             ui.separator();
             ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
                 ui.horizontal(|ui| {
@@ -451,6 +811,13 @@ impl StockCheckerScreen {
         };
 
         *picking_state = PickingState::from_matched_cards(&selected_matches);
+        let name = session_name(&state.wantslist_path);
+        if let Err(e) =
+            picking_state.start_session(&crate::picking_sessions::sessions_path(), &name)
+        {
+            warn!("Picking session not saved: {e}");
+            picking_state.session_error = Some(e);
+        }
         state.current_screen = Screen::Picking;
         info!(
             "Starting interactive picking with {} items",
@@ -473,11 +840,12 @@ impl StockCheckerScreen {
                     .iter()
                     .flat_map(|(_, _, cards)| cards.iter().cloned())
                     .collect();
-                let mut output = format_picking_list(&all_cards);
+                let mut output =
+                    format_picking_list_in(&all_cards, state.name_translation.as_ref());
                 if discount_percent > 0.0 {
                     let total_price: f64 = all_cards
                         .iter()
-                        .map(|mc| mc.card.price.parse::<f64>().unwrap_or(0.0) * mc.quantity as f64)
+                        .map(|mc| mc.card.price_f64() * mc.quantity as f64)
                         .sum();
                     let discounted = total_price * (1.0 - discount_percent as f64 / 100.0);
                     output.push_str(&format!(
@@ -491,11 +859,12 @@ impl StockCheckerScreen {
                     .iter()
                     .flat_map(|(_, _, cards)| cards.iter().cloned())
                     .collect();
-                let mut output = format_invoice_list(&all_cards);
+                let mut output =
+                    format_invoice_list_in(&all_cards, state.name_translation.as_ref());
                 if discount_percent > 0.0 {
                     let total_price: f64 = all_cards
                         .iter()
-                        .map(|mc| mc.card.price.parse::<f64>().unwrap_or(0.0) * mc.quantity as f64)
+                        .map(|mc| mc.card.price_f64() * mc.quantity as f64)
                         .sum();
                     let discounted = total_price * (1.0 - discount_percent as f64 / 100.0);
                     output.push_str(&format!(
@@ -270,10 +270,7 @@ mod perform_stock_check_tests {
     }
 
     fn wants(name: &str, qty: i32) -> WantsEntry {
-        WantsEntry {
-            name: name.to_string(),
-            quantity: qty,
-        }
+        WantsEntry::new(qty, name)
     }
 
     #[test]
@@ -354,3 +351,311 @@ mod perform_stock_check_tests {
         assert!(!cards.is_empty());
     }
 }
+
+// ============================================================================
+// perform_optimized_stock_check
+// ============================================================================
+
+mod perform_optimized_stock_check_tests {
+    use super::*;
+    use crate::models::WantsEntry;
+
+    fn listing(id: &str, name: &str, name_de: &str, quantity: i32) -> Card {
+        Card {
+            cardmarket_id: id.to_string(),
+            name: name.to_string(),
+            name_de: name_de.to_string(),
+            quantity: quantity.to_string(),
+            ..Card::test_default()
+        }
+    }
+
+    fn wants(name: &str, qty: i32) -> WantsEntry {
+        WantsEntry::new(qty, name)
+    }
+
+    #[test]
+    fn totals_count_shared_stock_once() {
+        let inventory = vec![listing("1", "Lightning Bolt", "Blitzschlag", 2)];
+        let wantslist = vec![wants("Lightning Bolt", 2), wants("Blitzschlag", 2)];
+
+        let per_entry = perform_stock_check(&inventory, &wantslist, Language::English, false);
+        let optimized = perform_optimized_stock_check(
+            &inventory,
+            &wantslist,
+            Language::English,
+            false,
+            OptimizationGoal::MinimizeCost,
+            |_| 0.0,
+        );
+
+        // Per-entry matching promises the same two copies to both lines.
+        assert_eq!(per_entry.total_found, 4);
+        assert_eq!(optimized.total_found, 2);
+        assert_eq!(optimized.total_wanted, 4);
+        assert_eq!(
+            optimized.missing_cards,
+            vec![("Blitzschlag".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn keeps_one_group_per_wantslist_entry() {
+        let inventory = vec![listing("1", "Sol Ring", "", 1)];
+        let wantslist = vec![wants("Sol Ring", 1), wants("Opt", 1)];
+
+        let result = perform_optimized_stock_check(
+            &inventory,
+            &wantslist,
+            Language::English,
+            false,
+            OptimizationGoal::MaximizeMargin,
+            |_| 0.0,
+        );
+
+        assert_eq!(result.all_matches.len(), 2);
+        assert_eq!(result.all_matches[0].2.len(), 1);
+        assert!(result.all_matches[1].2.is_empty());
+    }
+}
+
+// ============================================================================
+// fulfillment_record
+// ============================================================================
+
+mod fulfillment_record_tests {
+    use super::*;
+
+    fn result_with(
+        all_matches: Vec<CardMatchGroup>,
+        wanted: i32,
+        missing: usize,
+    ) -> StockCheckResult {
+        let total_found = all_matches
+            .iter()
+            .flat_map(|(_, _, cards)| cards)
+            .map(|(_, qty, _)| qty)
+            .sum();
+        StockCheckResult {
+            all_matches,
+            total_found,
+            total_wanted: wanted,
+            missing_cards: vec![("Missing".to_string(), 1); missing],
+        }
+    }
+
+    #[test]
+    fn summarizes_counts_and_discounted_value() {
+        let result = result_with(
+            vec![
+                make_group("Bolt", 4, vec![(make_card("Bolt", "2.00"), 3, "M10")]),
+                make_group("Opt", 2, vec![]),
+            ],
+            6,
+            2,
+        );
+
+        let record = fulfillment_record(
+            &result,
+            "/home/me/lists/cube.txt",
+            10.0,
+            "2026-01-01 12:00:00",
+        );
+
+        assert_eq!(record.wantslist, "cube.txt");
+        assert_eq!(record.checked_at, "2026-01-01 12:00:00");
+        assert_eq!(
+            (record.wanted, record.found, record.missing_cards),
+            (6, 3, 2)
+        );
+        assert!((record.quoted_value - 5.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deck_links_are_recorded_as_given() {
+        let result = result_with(vec![], 0, 0);
+        let link = "https://www.moxfield.com/decks/abc123";
+
+        assert_eq!(
+            fulfillment_record(&result, link, 0.0, "now").wantslist,
+            link
+        );
+    }
+}
+
+// ============================================================================
+// session_name
+// ============================================================================
+
+mod session_name_tests {
+    use super::*;
+
+    #[test]
+    fn session_name_uses_the_wantslist_file_name() {
+        assert_eq!(
+            session_name("/tmp/orders/weekly store.txt"),
+            "weekly store.txt"
+        );
+        assert_eq!(
+            session_name("https://moxfield.com/decks/abc"),
+            "https://moxfield.com/decks/abc"
+        );
+        assert_eq!(session_name(""), "Picking list");
+    }
+}
+
+// ============================================================================
+// Screen flows, driven through the headless UI harness
+// ============================================================================
+
+mod ui_flow_tests {
+    use super::*;
+    use crate::ui::screens::PickingScreen;
+    use crate::ui::test_harness::Harness;
+    use tempfile::TempDir;
+
+    const INVENTORY_HEADER: &str = "cardmarketId,quantity,name,set,setCode,cn,condition,\
+                                    language,isFoil,isPlayset,isSigned,price,comment,location,\
+                                    nameDE,nameES,nameFR,nameIT,rarity,listedAt";
+
+    /// Inventory and wantslist files in a temp dir. Collector numbers are left
+    /// empty so the picking screen does not fetch card images.
+    fn files(inventory_rows: &[&str], wantslist: &str) -> (TempDir, AppState) {
+        let dir = TempDir::new().unwrap();
+        let inventory = dir.path().join("inventory.csv");
+        let mut csv = format!("{INVENTORY_HEADER}\n");
+        for row in inventory_rows {
+            csv.push_str(row);
+            csv.push('\n');
+        }
+        std::fs::write(&inventory, csv).unwrap();
+        let wants = dir.path().join("wants.txt");
+        std::fs::write(&wants, wantslist).unwrap();
+
+        let state = AppState {
+            current_screen: Screen::StockChecker,
+            inventory_path: inventory.display().to_string(),
+            wantslist_path: wants.display().to_string(),
+            ..AppState::default()
+        };
+        (dir, state)
+    }
+
+    #[test]
+    fn load_match_and_pick() {
+        let mut ui = Harness::new();
+        let (_dir, mut state) = files(
+            &[
+                "1,3,Lightning Bolt,Magic 2010,M10,,NM,English,false,,false,1.50,,A1_S1_R1_C1,,,,,common,",
+                "2,1,Counterspell,Ice Age,ICE,,EX,English,false,,false,2.00,,A1_S1_R1_C2,,,,,common,",
+            ],
+            "2 Lightning Bolt\n1 Counterspell\n1 Black Lotus\n",
+        );
+        let mut picking = PickingState::default();
+        let mut checker = |ctx: &egui::Context| {
+            StockCheckerScreen::show(ctx, &mut state, &mut picking);
+        };
+
+        ui.run(&mut checker);
+        assert!(!ui.has_button("🎴 Interactive Picking List"));
+        ui.click("Check Stock");
+        ui.run_frames(2, &mut checker);
+        ui.click("🎴 Interactive Picking List");
+        ui.run(&mut checker);
+
+        assert_eq!(state.all_matches.len(), 3);
+        assert!(state.output.contains("Lightning Bolt"), "{}", state.output);
+        assert_eq!(state.current_screen, Screen::Picking);
+        assert_eq!(picking.total_count(), 2);
+
+        let mut screen = state.current_screen;
+        let mut picker = |ctx: &egui::Context| PickingScreen::show(ctx, &mut screen, &mut picking);
+        ui.run_frames(2, &mut picker);
+        assert!(ui.shows("Progress: 0/2"), "{:?}", ui.texts());
+
+        // Space first selects a tile, then picks it
+        ui.press_key(egui::Key::Space);
+        ui.run(&mut picker);
+        ui.press_key(egui::Key::Space);
+        ui.run_frames(2, &mut picker);
+        assert!(ui.shows("Progress: 1/2"), "{:?}", ui.texts());
+
+        ui.click("Pick");
+        ui.run_frames(2, &mut picker);
+        assert!(ui.shows("Progress: 2/2"), "{:?}", ui.texts());
+
+        ui.click("← Back to Stock Checker");
+        ui.run(&mut picker);
+        assert_eq!(screen, Screen::StockChecker);
+    }
+
+    #[test]
+    fn empty_inventory_finds_nothing() {
+        let mut ui = Harness::new();
+        let (_dir, mut state) = files(&[], "4 Lightning Bolt\n");
+        let mut picking = PickingState::default();
+        let mut checker = |ctx: &egui::Context| {
+            StockCheckerScreen::show(ctx, &mut state, &mut picking);
+        };
+
+        ui.run(&mut checker);
+        ui.click("Check Stock");
+        ui.run_frames(2, &mut checker);
+        assert!(!ui.has_button("🎴 Interactive Picking List"));
+
+        assert!(state
+            .all_matches
+            .iter()
+            .all(|(_, _, cards)| cards.is_empty()));
+        assert!(!state.output.starts_with("Error"), "{}", state.output);
+        assert_eq!(state.current_screen, Screen::StockChecker);
+    }
+
+    #[test]
+    fn missing_files_are_reported() {
+        let mut ui = Harness::new();
+        let mut state = AppState {
+            current_screen: Screen::StockChecker,
+            ..AppState::default()
+        };
+        let mut picking = PickingState::default();
+        let mut checker = |ctx: &egui::Context| {
+            StockCheckerScreen::show(ctx, &mut state, &mut picking);
+        };
+
+        ui.run(&mut checker);
+        ui.click("Check Stock");
+        ui.run_frames(2, &mut checker);
+
+        assert_eq!(
+            state.output,
+            "Error: Please select both inventory and wantslist files"
+        );
+    }
+}
+
+// ============================================================================
+// translate_match_names
+// ============================================================================
+
+mod translate_match_names_tests {
+    use super::*;
+
+    #[test]
+    fn entries_with_cards_take_the_translated_name() {
+        let mut bolt = make_card("Lightning Bolt", "1.00");
+        bolt.cardmarket_id = "1".to_string();
+        bolt.name_de = "Blitzschlag".to_string();
+        let mut matches = vec![
+            make_group("Blitzschlag", 1, vec![(bolt.clone(), 1, "M10")]),
+            make_group("Schwarzer Lotus", 1, vec![]),
+        ];
+
+        let english = NameTranslation::build([&bolt], Language::English, |_, _| None);
+        translate_match_names(&mut matches, &english);
+
+        assert_eq!(matches[0].0, "Lightning Bolt");
+        // Nothing to translate from without a card
+        assert_eq!(matches[1].0, "Schwarzer Lotus");
+    }
+}
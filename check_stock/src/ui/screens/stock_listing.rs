@@ -1,8 +1,13 @@
 use crate::api::scryfall::{fetch_card_async, fetch_image_async};
+use crate::cache::card_cache;
+use crate::exclusions::{ExcludedVariant, Exclusion};
+use crate::inventory_db;
+use crate::set_symbols::Rarity;
 use crate::ui::{
+    components::spawn_task,
+    language::Language,
     state::{
-        CardFetchMessage, CardFetchResult, FocusRequest, PriceGuideMessage, Screen,
-        StockListingState,
+        CardFetchMessage, CardFetchResult, ExclusionForm, FocusRequest, Screen, StockListingState,
     },
     style,
 };
@@ -71,19 +76,13 @@ impl StockListingScreen {
                         if style::secondary_button(ui, "Load Cardmarket Prices").clicked() {
                             state.error = None;
                             state.price_guide_loading = true;
-                            let tx = state.price_guide_tx.clone();
-                            let ctx_clone = ctx.clone();
-                            state.runtime.spawn(async move {
-                                match crate::api::cardmarket::PriceGuide::fetch().await {
-                                    Ok(guide) => {
-                                        tx.send(PriceGuideMessage::Success(guide)).ok();
-                                    }
-                                    Err(e) => {
-                                        tx.send(PriceGuideMessage::Error(e.to_string())).ok();
-                                    }
-                                }
-                                ctx_clone.request_repaint();
-                            });
+                            state.price_guide_rx =
+                                Some(spawn_task("Fetch Cardmarket price guide", |_, tx| {
+                                    let result =
+                                        crate::api::cardmarket::PriceGuide::fetch_blocking()
+                                            .map_err(|e| e.to_string());
+                                    let _ = tx.send(result);
+                                }));
                         }
                         ui.label(
                             egui::RichText::new("Downloads ~50MB price data from Cardmarket")
@@ -246,6 +245,9 @@ impl StockListingScreen {
             if let Some(ref card) = state.card {
                 Self::show_card_details(ui, state, card.clone());
             }
+
+            ui.add_space(10.0);
+            Self::show_exclusion_list(ui, &mut state.exclusions);
         });
     }
 
@@ -292,9 +294,7 @@ impl StockListingScreen {
         state.image_loading = true;
 
         // Fast synchronous in-memory cache check (no I/O)
-        if let Some(cached_card) = state.card_cache.get(set_code, collector_number) {
-            let cached_card = cached_card.clone();
-
+        if let Some(cached_card) = card_cache::shared_get(set_code, collector_number) {
             // Also check image cache (disk read, fast)
             if let Some(image_bytes) = state.image_cache.get(set_code, collector_number) {
                 // Both fully cached — resolve immediately, no background task needed
@@ -376,14 +376,11 @@ impl StockListingScreen {
                     );
 
                     // Persist card to in-memory + disk cache
-                    state.card_cache.insert(
+                    card_cache::shared_insert(
                         &result.set_code,
                         &result.collector_number,
                         result.card.clone(),
                     );
-                    if let Err(e) = state.card_cache.save() {
-                        error!("Failed to save card cache: {}", e);
-                    }
 
                     // Persist image to disk cache and convert to texture
                     if let Some(ref bytes) = result.image_bytes {
@@ -432,25 +429,28 @@ impl StockListingScreen {
 
     /// Drain the price guide result channel. Called every frame — never blocks.
     fn poll_price_guide(state: &mut StockListingState) {
-        while let Ok(msg) = state.price_guide_rx.try_recv() {
-            match msg {
-                PriceGuideMessage::Success(guide) => {
-                    info!("Fetched price guide with {} entries", guide.len());
-                    state.price_guide = Some(guide);
-                    state.price_guide_loading = false;
-                }
-                PriceGuideMessage::Error(e) => {
-                    error!("Failed to fetch price guide: {}", e);
-                    state.error = Some(format!("Price guide error: {}", e));
-                    state.price_guide_loading = false;
-                }
+        let Some(rx) = &state.price_guide_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(guide)) => {
+                info!("Fetched price guide with {} entries", guide.len());
+                state.price_guide = Some(guide);
             }
+            Ok(Err(e)) => {
+                error!("Failed to fetch price guide: {}", e);
+                state.error = Some(format!("Price guide error: {}", e));
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {}
         }
+        state.price_guide_rx = None;
+        state.price_guide_loading = false;
     }
 
     fn show_card_details(
         ui: &mut egui::Ui,
-        state: &StockListingState,
+        state: &mut StockListingState,
         card: crate::api::scryfall::ScryfallCard,
     ) {
         ui.add_space(10.0);
@@ -479,22 +479,28 @@ impl StockListingScreen {
                     );
                     ui.add_space(5.0);
 
-                    ui.label(
-                        egui::RichText::new(format!(
-                            "{} ({})",
-                            card.set_name,
-                            card.set.to_uppercase()
-                        ))
-                        .color(style::TEXT_MUTED),
-                    );
-                    ui.label(
-                        egui::RichText::new(format!(
-                            "#{} • {}",
-                            card.collector_number,
-                            card.rarity.to_uppercase()
-                        ))
-                        .color(style::TEXT_MUTED),
-                    );
+                    let rarity = Rarity::parse(&card.rarity);
+                    ui.horizontal(|ui| {
+                        state.set_symbols.show(ui, &card.set, rarity);
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} ({})",
+                                card.set_name,
+                                card.set.to_uppercase()
+                            ))
+                            .color(style::TEXT_MUTED),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!("#{} •", card.collector_number))
+                                .color(style::TEXT_MUTED),
+                        );
+                        ui.label(
+                            egui::RichText::new(card.rarity.to_uppercase())
+                                .color(style::rarity_color(rarity)),
+                        );
+                    });
 
                     if let Some(ref mana_cost) = card.mana_cost {
                         ui.label(format!("Mana: {}", mana_cost));
@@ -589,15 +595,188 @@ impl StockListingScreen {
                                 );
                             }
                         }
+
+                        ui.add_space(10.0);
+                        Self::show_exclusion_controls(
+                            ui,
+                            &mut state.exclusions,
+                            &card.name,
+                            cardmarket_id,
+                        );
                     }
                 });
             });
         });
     }
+
+    /// Loads the do-not-sell list the first time it is needed.
+    fn exclusion_list(form: &mut ExclusionForm) -> &Result<Vec<Exclusion>, String> {
+        form.list
+            .get_or_insert_with(|| inventory_db::get_exclusions().map_err(|e| e.to_string()))
+    }
+
+    /// "Do Not Sell" controls for the shown card: hold back the whole card or
+    /// one variant from the stock checker's matches and exports.
+    fn show_exclusion_controls(
+        ui: &mut egui::Ui,
+        form: &mut ExclusionForm,
+        name: &str,
+        cardmarket_id: u64,
+    ) {
+        let id = cardmarket_id.to_string();
+        ui.label(
+            egui::RichText::new("Do Not Sell")
+                .strong()
+                .color(style::TEXT_PRIMARY),
+        );
+        if let Ok(list) = Self::exclusion_list(form) {
+            let held: Vec<String> = list
+                .iter()
+                .filter(|e| e.cardmarket_id == id)
+                .map(Exclusion::scope_label)
+                .collect();
+            if !held.is_empty() {
+                ui.label(
+                    egui::RichText::new(format!("Held back: {}", held.join(", ")))
+                        .color(style::COLOR_ERROR),
+                );
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut form.whole_card, true, "Whole card");
+            ui.radio_value(&mut form.whole_card, false, "Variant:");
+            ui.add_enabled_ui(!form.whole_card, |ui| {
+                egui::ComboBox::new("exclusion_condition", "")
+                    .selected_text(form.condition.as_str())
+                    .show_ui(ui, |ui| {
+                        for condition in ["MT", "NM", "EX", "GD", "LP", "PL", "PO"] {
+                            ui.selectable_value(
+                                &mut form.condition,
+                                condition.to_string(),
+                                condition,
+                            );
+                        }
+                    });
+                egui::ComboBox::new("exclusion_language", "")
+                    .selected_text(form.language.as_str())
+                    .show_ui(ui, |ui| {
+                        for language in Language::all() {
+                            ui.selectable_value(&mut form.language, *language, language.as_str());
+                        }
+                    });
+                ui.checkbox(&mut form.foil, "Foil");
+                ui.checkbox(&mut form.signed, "Signed");
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Note:");
+            ui.add(
+                egui::TextEdit::singleline(&mut form.note)
+                    .desired_width(200.0)
+                    .hint_text("e.g. reserved, display piece"),
+            );
+            if style::secondary_button(ui, "Add to Do-Not-Sell List").clicked() {
+                let exclusion = Exclusion {
+                    cardmarket_id: id.clone(),
+                    name: name.to_string(),
+                    variant: (!form.whole_card).then(|| ExcludedVariant {
+                        condition: form.condition.clone(),
+                        language: form.language.as_str().to_string(),
+                        foil: form.foil,
+                        signed: form.signed,
+                    }),
+                    note: form.note.trim().to_string(),
+                };
+                form.status = Some(match inventory_db::add_exclusion(&exclusion) {
+                    Ok(()) => {
+                        info!(
+                            "Added {name} ({}) to the do-not-sell list",
+                            exclusion.scope_label()
+                        );
+                        form.note.clear();
+                        form.list = None;
+                        Ok(format!(
+                            "{name} ({}) will not be sold",
+                            exclusion.scope_label()
+                        ))
+                    }
+                    Err(e) => {
+                        error!("Failed to update the do-not-sell list: {e}");
+                        Err(format!("Failed to update the do-not-sell list: {e}"))
+                    }
+                });
+            }
+        });
+        match &form.status {
+            Some(Ok(msg)) => style::status_ok(ui, msg),
+            Some(Err(msg)) => style::status_error(ui, msg),
+            None => {}
+        }
+    }
+
+    /// Collapsible do-not-sell list with a remove button per entry.
+    fn show_exclusion_list(ui: &mut egui::Ui, form: &mut ExclusionForm) {
+        let mut remove = None;
+        let header = match Self::exclusion_list(form) {
+            Ok(list) => format!("Do-Not-Sell List ({})", list.len()),
+            Err(_) => "Do-Not-Sell List".to_string(),
+        };
+        egui::CollapsingHeader::new(header)
+            .id_salt("do_not_sell_list")
+            .show(ui, |ui| match Self::exclusion_list(form) {
+                Err(e) => style::status_error(ui, &format!("Inventory DB: {e}")),
+                Ok(list) if list.is_empty() => {
+                    ui.label(
+                        egui::RichText::new(
+                            "No cards held back. Look up a card above to reserve it or mark it \
+                             as a display piece.",
+                        )
+                        .color(style::TEXT_MUTED),
+                    );
+                }
+                Ok(list) => {
+                    egui::Grid::new("do_not_sell_grid")
+                        .num_columns(5)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Card");
+                            ui.strong("Cardmarket ID");
+                            ui.strong("Variant");
+                            ui.strong("Note");
+                            ui.label("");
+                            ui.end_row();
+                            for exclusion in list {
+                                ui.label(&exclusion.name);
+                                ui.label(&exclusion.cardmarket_id);
+                                ui.label(exclusion.scope_label());
+                                ui.label(&exclusion.note);
+                                if style::secondary_button(ui, "Remove").clicked() {
+                                    remove = Some(exclusion.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+        if let Some(exclusion) = remove {
+            form.status = Some(match inventory_db::remove_exclusion(&exclusion) {
+                Ok(()) => {
+                    info!("Removed {} from the do-not-sell list", exclusion.name);
+                    Ok(format!("{} can be sold again", exclusion.name))
+                }
+                Err(e) => Err(format!("Failed to update the do-not-sell list: {e}")),
+            });
+            form.list = None;
+        }
+    }
 }
 
 fn format_price(price: Option<f64>) -> String {
-    match price {
+    // A zero in the guide means "no price for this finish", not 0 €
+    match mtg_common::cardmarket::priced(price) {
         Some(p) => format!("{:.2} €", p),
         None => "—".to_string(),
     }
@@ -4,7 +4,7 @@ use eframe::egui;
 
 pub struct WelcomeScreen;
 
-const TILES: [(&str, &str); 10] = [
+const TILES: [(&str, &str); 12] = [
     ("Stock Checker", "Verify card stock\nagainst order lists"),
     ("Stock Analysis", "Analyse inventory\ntrends and signals"),
     ("Bin Analysis", "Bin capacity and\nfree-slot analysis"),
@@ -18,6 +18,8 @@ const TILES: [(&str, &str); 10] = [
         "Sold-out fast sellers\nworth buying again",
     ),
     ("Price Movers", "7/30-day market moves\nacross your stock"),
+    ("Picking Sessions", "Reopen or repeat\nsaved picks"),
+    ("Settings", "Inventory database\nsize and compaction"),
 ];
 
 impl WelcomeScreen {
@@ -83,7 +85,9 @@ impl WelcomeScreen {
                     6 => Screen::BuyHelper,
                     7 => Screen::Mispricing,
                     8 => Screen::Restock,
-                    _ => Screen::Movers,
+                    9 => Screen::Movers,
+                    10 => Screen::PickingSessions,
+                    _ => Screen::Settings,
                 };
             }
         });
@@ -119,7 +123,7 @@ impl WelcomeScreen {
 
         // Freshness of the local inventory mirror — the one thing worth a
         // warning color, since every report depends on it.
-        let today = chrono::Local::now().date_naive();
+        let today = mtg_common::dates::market_today();
         let sync_age = d.last_sync.as_deref().and_then(|s| {
             chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
                 .ok()
@@ -1,3 +1,4 @@
+use super::components::{spawn_task, TaskReceiver};
 use super::language::Language;
 use crate::models::Card;
 use crate::stock_analysis::SortOrder;
@@ -8,7 +9,7 @@ type CardMatch = (Card, i32, String);
 /// (card_name, needed_quantity, matched_cards)
 type CardMatchGroup = (String, i32, Vec<CardMatch>);
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Screen {
     Welcome,
     StockChecker,
@@ -17,12 +18,37 @@ pub enum Screen {
     StockListing,
     Search,
     Picking,
+    PickingSessions,
     Pricing,
     BuyHelper,
     Mispricing,
     Movers,
     Consolidation,
     Restock,
+    Settings,
+}
+
+impl Screen {
+    /// Name shown on the welcome tiles and in the command palette.
+    pub fn title(&self) -> &'static str {
+        match self {
+            Screen::Welcome => "Home",
+            Screen::StockChecker => "Stock Checker",
+            Screen::StockAnalysis => "Stock Analysis",
+            Screen::BinAnalysis => "Bin Analysis",
+            Screen::StockListing => "Magic Singles Listing",
+            Screen::Search => "Search Cards",
+            Screen::Picking => "Picking List",
+            Screen::PickingSessions => "Picking Sessions",
+            Screen::Pricing => "Pricing",
+            Screen::BuyHelper => "Card Buy Helper",
+            Screen::Mispricing => "Mispricing Report",
+            Screen::Movers => "Price Movers",
+            Screen::Consolidation => "Bin Consolidation",
+            Screen::Restock => "Restock Report",
+            Screen::Settings => "Settings",
+        }
+    }
 }
 
 #[derive(PartialEq)]
@@ -50,6 +76,17 @@ pub struct AppState {
     pub output: String,
     pub preferred_language: Language,
     pub preferred_language_only: bool,
+    /// Whole-wantslist optimization; `None` matches each entry on its own.
+    pub optimization_goal: Option<crate::wantslist_optimizer::OptimizationGoal>,
+    /// Language to name the cards in on quotes, picking lists and invoices;
+    /// `None` keeps the wantslist's names.
+    pub output_name_language: Option<Language>,
+    /// Names of the last check's cards in `output_name_language`.
+    pub name_translation: Option<crate::translation::NameTranslation>,
+    /// Receives the Scryfall names of the cards without a stored one.
+    pub name_lookup_rx: Option<TaskReceiver<crate::translation::NameLookups>>,
+    /// Listings the last check held back for the do-not-sell list.
+    pub withheld_listings: usize,
     pub all_matches: Vec<CardMatchGroup>,
     pub selected: Vec<bool>,
     pub show_selection: bool,
@@ -59,15 +96,23 @@ pub struct AppState {
     pub output_window_title: String,
     // ── Inventory Sync connection (shared by all screens) ─────────────────
     pub inventory_sync_url: String,
+    /// inventory_sync database to read price history from; the server is
+    /// asked when empty
+    pub price_history_db: String,
     pub inventory_sync_status: ConnectionStatus,
     /// Receives the health-check result from a background thread.
-    pub inventory_health_rx: Option<std::sync::mpsc::Receiver<Result<(), String>>>,
+    pub inventory_health_rx: Option<TaskReceiver<Result<(), String>>>,
     /// A CSV import the safety check blocked, awaiting user confirmation.
     /// Rendered as a modal by the app shell on every screen.
     pub sync_guard: Option<SyncGuard>,
     /// Since-last-visit digest, computed once per app run on the welcome
     /// screen (`None` = not yet computed; `Err` = DB unavailable).
     pub digest: Option<Result<crate::inventory_db::VisitDigest, String>>,
+    /// Recorded stock checks for the trends section, loaded when first shown
+    /// and dropped after every check (`Err` = DB unavailable).
+    pub fulfillment_history: Option<Result<Vec<crate::fulfillment::FulfillmentRecord>, String>>,
+    /// Malformed rows a lenient CSV load skipped, shown until dismissed.
+    pub csv_load_report: Option<crate::io::CsvLoadReport>,
 }
 
 /// A blocked inventory import held for the confirmation dialog.
@@ -76,6 +121,11 @@ pub struct SyncGuard {
     pub cards: Vec<Card>,
     /// What the sync would have changed.
     pub preview: crate::inventory_db::SyncPreview,
+    /// Receives the outcome of the webhook notification while it is sent.
+    pub webhook_rx: Option<TaskReceiver<Result<(), String>>>,
+    /// Outcome of the webhook notification once it arrived; `None` while
+    /// sending or when no webhook is configured.
+    pub webhook_result: Option<Result<(), String>>,
 }
 
 impl AppState {
@@ -87,9 +137,26 @@ impl AppState {
         match crate::inventory_db::sync_inventory(cards) {
             Ok(crate::inventory_db::SyncOutcome::Synced(_)) => {}
             Ok(crate::inventory_db::SyncOutcome::Blocked(preview)) => {
+                let webhook_rx = mtg_common::config::config()
+                    .check_stock
+                    .sync_webhook_url
+                    .clone()
+                    .map(|url| {
+                        let preview = preview.clone();
+                        spawn_task("Notify sync webhook", move |_, tx| {
+                            let result = crate::sync_alert::notify_webhook(&url, &preview);
+                            match &result {
+                                Ok(()) => log::info!("Sync anomaly reported to webhook"),
+                                Err(e) => log::warn!("Sync anomaly webhook failed: {e}"),
+                            }
+                            let _ = tx.send(result);
+                        })
+                    });
                 self.sync_guard = Some(SyncGuard {
                     cards: cards.to_vec(),
                     preview,
+                    webhook_rx,
+                    webhook_result: None,
                 });
             }
             Err(e) => log::warn!("Inventory DB sync failed: {e}"),
@@ -106,6 +173,11 @@ impl Default for AppState {
             output: String::new(),
             preferred_language: Language::English,
             preferred_language_only: false,
+            optimization_goal: None,
+            output_name_language: None,
+            name_translation: None,
+            name_lookup_rx: None,
+            withheld_listings: 0,
             all_matches: Vec::new(),
             selected: Vec::new(),
             show_selection: false,
@@ -114,11 +186,22 @@ impl Default for AppState {
             output_window_content: String::new(),
             output_window_title: String::new(),
             discount_percent: 10.0,
-            inventory_sync_url: "http://cardscanner.local:3000".to_string(),
+            inventory_sync_url: mtg_common::config::config()
+                .check_stock
+                .inventory_sync_url
+                .clone(),
+            price_history_db: mtg_common::config::config()
+                .check_stock
+                .price_history_db
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
             inventory_sync_status: ConnectionStatus::Unchecked,
             inventory_health_rx: None,
             sync_guard: None,
             digest: None,
+            fulfillment_history: None,
+            csv_load_report: None,
         }
     }
 }
@@ -150,6 +233,34 @@ pub struct StockAnalysisState {
     pub lot_cost_edit: Option<(String, String)>,
     /// Surfaced when saving or clearing a lot cost fails; shown near the table.
     pub lot_cost_error: Option<String>,
+    /// Market price the valuation panel compares listed prices against.
+    pub valuation_basis: crate::valuation::MarketBasis,
+    /// Latest inventory_sync price rows for in-stock cards, by product ID.
+    pub market_prices: std::collections::HashMap<u64, LatestPrice>,
+    /// In-flight market price fetch for the valuation panel.
+    pub market_rx: Option<TaskReceiver<FetchMsg<Vec<LatestPrice>>>>,
+    pub market_status: String,
+    pub market_error: Option<String>,
+    pub valuation: Option<crate::valuation::Valuation>,
+    /// Articles split over several rows of the loaded inventory CSV.
+    pub duplicates: Vec<crate::duplicates::DuplicateGroup>,
+    /// Result of the last merge CSV export.
+    pub duplicates_status: Option<Result<String, String>>,
+    /// In-stock cards behind the set/rarity heatmap, loaded with the stats.
+    pub heatmap_cards: Vec<crate::inventory_db::InStockCard>,
+    pub heatmap_rows: Vec<crate::inventory_heatmap::HeatmapRow>,
+    pub heatmap_metric: crate::inventory_heatmap::HeatmapMetric,
+    /// Heatmap cell (set code, rarity) whose cards are listed below it.
+    pub heatmap_selection: Option<(String, crate::set_symbols::Rarity)>,
+    /// Inventories in the DB, loaded with the stats.
+    pub inventories: Vec<crate::inventory_db::InventorySummary>,
+    /// Inventory loaded CSVs sync into.
+    pub active_inventory: String,
+    /// Inventory the stats, heatmap and valuation cover; `None` for all.
+    pub stats_inventory: Option<String>,
+    /// Name typed into the "new inventory" field.
+    pub new_inventory_name: String,
+    pub inventory_error: Option<String>,
 }
 
 pub struct BinAnalysisState {
@@ -190,7 +301,8 @@ impl Default for BinAnalysisState {
 
 use crate::api::cardmarket::PriceGuide;
 use crate::api::scryfall::ScryfallCard;
-use crate::cache::{CardCache, ImageCache};
+use crate::cache::ImageCache;
+use crate::ui::components::{ListingPhotoWindow, SetSymbols};
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
@@ -216,11 +328,6 @@ pub enum CardFetchMessage {
     Error(String),
 }
 
-pub enum PriceGuideMessage {
-    Success(PriceGuide),
-    Error(String),
-}
-
 pub struct StockListingState {
     pub default_set: String,         // Default set code, e.g. "hou"
     pub default_language: String,    // Default language, e.g. "EN"
@@ -233,20 +340,19 @@ pub struct StockListingState {
     pub error: Option<String>,
     pub price_guide: Option<PriceGuide>,
     pub price_guide_loading: bool,
-    pub card_cache: CardCache,
     pub image_cache: ImageCache,
+    pub set_symbols: SetSymbols,
+    pub exclusions: ExclusionForm,
     // Async runtime + channels — private, mirroring the PickingState pattern
     pub(super) runtime: Runtime,
     pub(super) card_tx: UnboundedSender<CardFetchMessage>,
     pub(super) card_rx: UnboundedReceiver<CardFetchMessage>,
-    pub(super) price_guide_tx: UnboundedSender<PriceGuideMessage>,
-    pub(super) price_guide_rx: UnboundedReceiver<PriceGuideMessage>,
+    pub(super) price_guide_rx: Option<TaskReceiver<Result<PriceGuide, String>>>,
 }
 
 impl Default for StockListingState {
     fn default() -> Self {
         let (card_tx, card_rx) = unbounded_channel();
-        let (price_guide_tx, price_guide_rx) = unbounded_channel();
         Self {
             default_set: String::new(),
             default_language: String::from("EN"),
@@ -259,13 +365,42 @@ impl Default for StockListingState {
             error: None,
             price_guide: None,
             price_guide_loading: false,
-            card_cache: CardCache::load(), // Load from disk on startup
             image_cache: ImageCache::new(),
+            set_symbols: SetSymbols::default(),
+            exclusions: ExclusionForm::default(),
             runtime: Runtime::new().expect("Failed to create Tokio runtime for StockListing"),
             card_tx,
             card_rx,
-            price_guide_tx,
-            price_guide_rx,
+            price_guide_rx: None,
+        }
+    }
+}
+
+/// The listing screen's do-not-sell controls and the list they edit.
+pub struct ExclusionForm {
+    /// Hold back every variant of the card rather than the one below
+    pub whole_card: bool,
+    pub condition: String,
+    pub language: Language,
+    pub foil: bool,
+    pub signed: bool,
+    pub note: String,
+    /// The do-not-sell list, loaded when first shown (`Err` = DB unavailable)
+    pub list: Option<Result<Vec<crate::exclusions::Exclusion>, String>>,
+    pub status: Option<Result<String, String>>,
+}
+
+impl Default for ExclusionForm {
+    fn default() -> Self {
+        Self {
+            whole_card: true,
+            condition: "NM".to_string(),
+            language: Language::English,
+            foil: false,
+            signed: false,
+            note: String::new(),
+            list: None,
+            status: None,
         }
     }
 }
@@ -283,10 +418,14 @@ pub enum SearchAction {
     /// Write the selection off as discarded: reduce inventory without counting
     /// it as sold, and export a negative-delta stock-update CSV.
     Discard,
+    /// Relist selected playset listings as four singles each.
+    SplitPlaysets,
+    /// Bundle selected singles into playsets (whole playsets only).
+    MergePlaysets,
 }
 
 /// Floating per-card price-history window on the Search screen
-/// (data fetched from inventory_sync).
+/// (read from the inventory_sync database or server).
 #[derive(Default)]
 pub struct CardHistoryState {
     pub open: bool,
@@ -296,14 +435,31 @@ pub struct CardHistoryState {
     pub is_foil: bool,
     pub loading: bool,
     pub error: Option<String>,
-    pub data: Option<crate::api::inventory_sync::PriceData>,
+    pub data: Option<crate::price_history_db::PriceHistory>,
     /// Receiver for the background history fetch, if one is in flight.
-    pub rx:
-        Option<std::sync::mpsc::Receiver<Result<crate::api::inventory_sync::PriceData, String>>>,
+    pub rx: Option<TaskReceiver<Result<crate::price_history_db::PriceHistory, String>>>,
+}
+
+/// Scryfall lookup for a `set number` search term on the Search screen.
+#[derive(Default)]
+pub struct PrintingLookupState {
+    /// The printing the current search term names, if it names one.
+    pub query: Option<crate::collector_search::PrintingQuery>,
+    pub card: Option<crate::api::ScryfallCard>,
+    pub error: Option<String>,
+    /// Receiver for the background Scryfall fetch, if one is in flight.
+    pub rx: Option<TaskReceiver<Result<crate::api::ScryfallCard, String>>>,
 }
 
 pub struct SearchState {
     pub csv_path: String,
+    /// Inventories offered for a DB search, loaded when the screen opens.
+    pub inventories: Vec<String>,
+    /// Inventory "Load from DB" reads; `None` searches all of them together.
+    pub db_inventory: Option<String>,
+    /// The cards came from the DB but not (only) from the active inventory,
+    /// so discards and playset conversions would write to the wrong rows.
+    pub read_only_source: bool,
     pub search_term: String,
     pub last_search_term: String,
     pub cards: Vec<Card>,
@@ -313,14 +469,20 @@ pub struct SearchState {
     pub selected_fields: SearchFields,
     pub last_search_time: std::time::Instant,
     pub search_needs_update: bool,
-    pub current_page: usize,
-    pub results_per_page: usize,
+    /// Result row to scroll the results table to on the next frame.
+    pub scroll_to_result: Option<usize>,
     pub selected_cards: Vec<SelectedSearchCard>,
     pub quantity_inputs: std::collections::HashMap<usize, i32>,
     /// Whether the selection is sent to lists or written off as discarded.
     pub action_mode: SearchAction,
     /// Floating per-card price-history window.
     pub history: CardHistoryState,
+    /// Scryfall card for a `set number` search term.
+    pub printing: PrintingLookupState,
+    /// Set symbol icons for the results table.
+    pub set_symbols: SetSymbols,
+    /// Photo of a result row, for condition disputes.
+    pub photo: ListingPhotoWindow,
 }
 
 #[derive(Default)]
@@ -519,6 +681,16 @@ pub enum NodeKind {
     InventoryPrice {
         source: InventoryPriceSource,
     },
+    /// Raise prices to the rolling average achieved sale price (sales history).
+    SalesFloor,
+    /// Damp price cuts on cards whose low/trend spread (percent of trend)
+    /// exceeds the rarity's limit — a thin market.
+    ThinMarketGuard {
+        common: f64,
+        uncommon: f64,
+        rare: f64,
+        mythic: f64,
+    },
 }
 
 impl NodeKind {
@@ -539,6 +711,8 @@ impl NodeKind {
             Self::LogicalNot => "NOT",
             Self::PriceFloor { .. } => "Price Floor",
             Self::InventoryPrice { .. } => "Inventory Price",
+            Self::SalesFloor => "Sales Floor",
+            Self::ThinMarketGuard { .. } => "Thin Market Guard",
         }
     }
 
@@ -559,6 +733,8 @@ impl NodeKind {
             Self::LogicalNot => egui::Color32::from_rgb(170, 55, 55),
             Self::PriceFloor { .. } => egui::Color32::from_rgb(185, 145, 30),
             Self::InventoryPrice { .. } => egui::Color32::from_rgb(60, 160, 180),
+            Self::SalesFloor => egui::Color32::from_rgb(175, 110, 60),
+            Self::ThinMarketGuard { .. } => egui::Color32::from_rgb(120, 125, 60),
         }
     }
 
@@ -580,9 +756,9 @@ impl NodeKind {
     pub fn param_count(&self) -> usize {
         match self {
             Self::CsvSource | Self::Output => 0,
-            Self::LogicalAnd | Self::LogicalOr | Self::LogicalNot => 0,
+            Self::LogicalAnd | Self::LogicalOr | Self::LogicalNot | Self::SalesFloor => 0,
             Self::FilterPrice { .. } => 2,
-            Self::PriceFloor { .. } => 4,
+            Self::PriceFloor { .. } | Self::ThinMarketGuard { .. } => 4,
             _ => 1,
         }
     }
@@ -734,19 +910,35 @@ pub struct PricingState {
     pub preview_sort_col: Option<usize>,
     pub preview_sort_asc: bool,
 
+    // ── Sales history guardrails (inventory DB, loaded with the CSV) ──────
+    /// Rolling average sale price per card index, for Sales Floor nodes.
+    pub sale_floors: std::collections::HashMap<usize, f64>,
+    /// Per-copy acquisition cost by lot, for the below-cost flag.
+    pub lot_copy_costs: std::collections::HashMap<String, f64>,
+
     // ── Inventory Sync ────────────────────────────────────────────────────
     /// Cached latest prices keyed by cardmarket product ID.
     pub inventory_prices: std::collections::HashMap<u64, LatestPrice>,
     /// Receives bulk price results from background fetch.
-    pub prices_rx: Option<std::sync::mpsc::Receiver<PriceFetchResult>>,
+    pub prices_rx: Option<TaskReceiver<PriceFetchResult>>,
     /// True while a bulk price fetch is in flight.
     pub prices_fetching: bool,
 
     // ── Diff CSV output ───────────────────────────────────────────────────
     pub show_diff_output: bool,
     pub diff_output_content: String,
+
+    // ── Whole-inventory repricing ─────────────────────────────────────────
+    /// Receives the planned changes of a whole-inventory repricing.
+    pub bulk_reprice_rx: Option<TaskReceiver<BulkRepricePlan>>,
+    /// Planned changes awaiting confirmation.
+    pub bulk_reprice_changes: Option<Vec<crate::bulk_repricing::PriceChange>>,
+    /// Outcome of the last planning or apply step.
+    pub bulk_reprice_status: Option<Result<String, String>>,
 }
 
+pub type BulkRepricePlan = Result<Vec<crate::bulk_repricing::PriceChange>, String>;
+
 /// State for the Card Buy Helper screen.
 ///
 /// Holds the loaded card export plus the adjustable offer parameters. This
@@ -924,7 +1116,7 @@ pub struct MispricingState {
     pub sort_desc: bool,
     pub filter: VerdictFilter,
     /// Receiver for the background price-guide fetch, if one is in flight.
-    pub guide_rx: Option<std::sync::mpsc::Receiver<Result<PriceGuide, String>>>,
+    pub guide_rx: Option<TaskReceiver<Result<PriceGuide, String>>>,
     // ── Inventory Sync data ─────────────────────────────────────────────────
     /// Latest prices from inventory_sync, keyed by cardmarket product ID.
     pub inventory_prices: std::collections::HashMap<u64, LatestPrice>,
@@ -935,7 +1127,7 @@ pub struct MispricingState {
     pub sync_loading: bool,
     /// Receiver for the background inventory_sync fetch, if one is in flight.
     /// Streams progress messages, then the final result.
-    pub sync_rx: Option<std::sync::mpsc::Receiver<FetchMsg<MispricingSyncData>>>,
+    pub sync_rx: Option<TaskReceiver<FetchMsg<MispricingSyncData>>>,
 }
 
 impl Default for MispricingState {
@@ -1034,7 +1226,7 @@ pub struct MoversState {
     pub error: Option<String>,
     pub loading: bool,
     /// Receiver for the background snapshot fetch, if one is in flight.
-    pub rx: Option<std::sync::mpsc::Receiver<Result<MoversSyncData, String>>>,
+    pub rx: Option<TaskReceiver<Result<MoversSyncData, String>>>,
 }
 
 impl Default for MoversState {
@@ -1072,6 +1264,20 @@ pub enum RestockSort {
     Name,
 }
 
+/// State for the saved picking sessions screen.
+#[derive(Default)]
+pub struct PickingSessionsState {
+    /// Saved sessions, newest first
+    pub sessions: Vec<crate::picking_sessions::SavedSession>,
+    /// Modification time of the sessions file when it was last read; the
+    /// list is re-read when the file changes (e.g. after picking)
+    pub loaded_mtime: Option<std::time::SystemTime>,
+    pub loaded: bool,
+    pub error: Option<String>,
+    /// Session whose Delete button was clicked, awaiting confirmation
+    pub confirm_delete: Option<String>,
+}
+
 /// State for the read-only Restock Recommendations screen.
 pub struct RestockState {
     /// Minimum lifetime sold copies for a variant to count (filters one-offs).
@@ -1098,10 +1304,27 @@ impl Default for RestockState {
     }
 }
 
+/// State for the Settings screen (inventory DB maintenance).
+#[derive(Default)]
+pub struct SettingsState {
+    pub db_report: Option<crate::inventory_db::DbSizeReport>,
+    pub error: Option<String>,
+    /// Guards the one-shot auto-load when the screen is first shown.
+    pub loaded: bool,
+    /// Stage updates and the final result of a running compaction.
+    pub vacuum_rx: Option<TaskReceiver<FetchMsg<crate::inventory_db::VacuumResult>>>,
+    pub vacuum_status: String,
+    /// Slow query and index advisor report, built on request.
+    pub slow_query_report: String,
+}
+
 impl Default for SearchState {
     fn default() -> Self {
         Self {
             csv_path: String::new(),
+            inventories: Vec::new(),
+            db_inventory: None,
+            read_only_source: false,
             search_term: String::new(),
             last_search_term: String::new(),
             cards: Vec::new(),
@@ -1110,8 +1333,7 @@ impl Default for SearchState {
             search_in_all_languages: true,
             last_search_time: std::time::Instant::now(),
             search_needs_update: false,
-            current_page: 0,
-            results_per_page: 100,
+            scroll_to_result: None,
             selected_fields: SearchFields {
                 name: true,
                 set: true,
@@ -1126,6 +1348,9 @@ impl Default for SearchState {
             quantity_inputs: std::collections::HashMap::new(),
             action_mode: SearchAction::AddToLists,
             history: CardHistoryState::default(),
+            printing: PrintingLookupState::default(),
+            set_symbols: SetSymbols::default(),
+            photo: ListingPhotoWindow::default(),
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Shared visual style helpers.
 //!
 //! Pure presentation utilities — no business logic, no state.
+use crate::set_symbols::Rarity;
 use eframe::egui;
 
 // ── Palette ──────────────────────────────────────────────────────────────────
@@ -14,6 +15,19 @@ pub const PANEL_BORDER: egui::Color32 = egui::Color32::from_rgb(52, 62, 88);
 pub const COLOR_SUCCESS: egui::Color32 = egui::Color32::from_rgb(75, 175, 115);
 pub const COLOR_ERROR: egui::Color32 = egui::Color32::from_rgb(210, 75, 75);
 
+/// Set-symbol colour per rarity, as printed on cards (commons lightened to
+/// stay readable on the dark panels).
+pub fn rarity_color(rarity: Rarity) -> egui::Color32 {
+    match rarity {
+        Rarity::Common => egui::Color32::from_rgb(205, 205, 212),
+        Rarity::Uncommon => egui::Color32::from_rgb(150, 182, 204),
+        Rarity::Rare => egui::Color32::from_rgb(218, 182, 96),
+        Rarity::Mythic => egui::Color32::from_rgb(236, 112, 44),
+        Rarity::Special => egui::Color32::from_rgb(176, 118, 214),
+        Rarity::Other => TEXT_MUTED,
+    }
+}
+
 // ── Navigation ───────────────────────────────────────────────────────────────
 
 /// Frameless back-navigation text button.
@@ -0,0 +1,174 @@
+//! Headless harness for UI tests of whole screens.
+//!
+//! Runs a screen's `show` function frame by frame on a plain
+//! [`egui::Context`] — no window or GPU — and reads the widgets back from the
+//! AccessKit tree egui builds each frame. Tests find widgets by their text
+//! and click them by sending pointer events to the widget's position, so
+//! they exercise the same code paths as a user would:
+//!
+//! ```ignore
+//! let mut ui = Harness::new();
+//! ui.run(|ctx| StockCheckerScreen::show(ctx, &mut state, &mut picking));
+//! ui.click("Check Stock");
+//! ui.run(|ctx| StockCheckerScreen::show(ctx, &mut state, &mut picking));
+//! ```
+//!
+//! Screens read and write the inventory database, the picking sessions file
+//! and the image cache; [`Harness::new`] points the data, cache and config
+//! directories at a temporary directory first, so tests never touch the
+//! user's files.
+//!
+//! This is a small stand-in for `egui_kittest`, egui's own test harness,
+//! which builds can't use yet: the crate (0.31, matching our egui) is not
+//! in the vendored registry the offline builds resolve from. Both drive
+//! the screen through the AccessKit tree, so tests written against
+//! [`Harness::click`] and friends move over to kittest's `get_by_label`
+//! queries once it can be added as a dev-dependency.
+
+use eframe::egui;
+use egui::accesskit::{Node, Role};
+use std::sync::Once;
+
+/// Window size the screens are laid out in.
+const SCREEN_SIZE: egui::Vec2 = egui::vec2(1600.0, 1200.0);
+
+/// Redirects every per-user directory to a temporary one for the rest of the
+/// test process. Must run before the shared config is first read.
+fn isolate_user_dirs() {
+    static ISOLATE: Once = Once::new();
+    ISOLATE.call_once(|| {
+        let dir = std::env::temp_dir().join(format!("check_stock_ui_tests_{}", std::process::id()));
+        for (var, sub) in [
+            ("XDG_DATA_HOME", "data"),
+            ("XDG_CACHE_HOME", "cache"),
+            ("XDG_CONFIG_HOME", "config"),
+        ] {
+            std::env::set_var(var, dir.join(sub));
+        }
+        std::env::remove_var(mtg_common::config::CONFIG_FILE_ENV);
+    });
+}
+
+/// A headless egui context plus the widgets of its last frame.
+pub(crate) struct Harness {
+    ctx: egui::Context,
+    /// Input events delivered with the next frame
+    events: Vec<egui::Event>,
+    /// Widgets of the last frame, in paint order
+    nodes: Vec<Node>,
+}
+
+impl Harness {
+    pub(crate) fn new() -> Self {
+        isolate_user_dirs();
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        Self {
+            ctx,
+            events: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Runs one frame of `show`, delivering the queued input.
+    pub(crate) fn run(&mut self, mut show: impl FnMut(&egui::Context)) {
+        let mut input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN_SIZE)),
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        input
+            .viewports
+            .entry(egui::ViewportId::ROOT)
+            .or_default()
+            .native_pixels_per_point = Some(1.0);
+        let output = self.ctx.run(input, |ctx| show(ctx));
+        self.nodes = output
+            .platform_output
+            .accesskit_update
+            .map(|update| update.nodes.into_iter().map(|(_, node)| node).collect())
+            .unwrap_or_default();
+    }
+
+    /// Runs `frames` frames without input, e.g. to let a screen settle after
+    /// a click changed its state.
+    pub(crate) fn run_frames(&mut self, frames: usize, mut show: impl FnMut(&egui::Context)) {
+        for _ in 0..frames {
+            self.run(&mut show);
+        }
+    }
+
+    fn text(node: &Node) -> Option<&str> {
+        node.label().or_else(|| node.value())
+    }
+
+    /// Texts of all widgets of the last frame, for assertions and failure
+    /// messages.
+    pub(crate) fn texts(&self) -> Vec<&str> {
+        self.nodes.iter().filter_map(Self::text).collect()
+    }
+
+    /// Whether a widget's text of the last frame contains `text`.
+    pub(crate) fn shows(&self, text: &str) -> bool {
+        self.texts().iter().any(|t| t.contains(text))
+    }
+
+    /// The button (or checkbox) labelled exactly `label`. Panics when there
+    /// is none, or when several are at different places.
+    fn button(&self, label: &str) -> &Node {
+        let matches: Vec<&Node> = self
+            .nodes
+            .iter()
+            .filter(|node| {
+                matches!(node.role(), Role::Button | Role::CheckBox) && node.label() == Some(label)
+            })
+            .collect();
+        let Some(node) = matches.first() else {
+            panic!("no button {label:?} on screen; widgets: {:?}", self.texts());
+        };
+        assert!(
+            matches.iter().all(|other| other.bounds() == node.bounds()),
+            "several buttons labelled {label:?}"
+        );
+        node
+    }
+
+    /// Whether a button labelled `label` is on screen.
+    pub(crate) fn has_button(&self, label: &str) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| node.role() == Role::Button && node.label() == Some(label))
+    }
+
+    /// Clicks the button labelled `label` with the next frame.
+    pub(crate) fn click(&mut self, label: &str) {
+        let node = self.button(label);
+        assert!(!node.is_disabled(), "button {label:?} is disabled");
+        let bounds = node.bounds().expect("widget without bounds");
+        let pos = egui::pos2(
+            ((bounds.x0 + bounds.x1) / 2.0) as f32,
+            ((bounds.y0 + bounds.y1) / 2.0) as f32,
+        );
+        let button = |pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        };
+        self.events
+            .extend([egui::Event::PointerMoved(pos), button(true), button(false)]);
+    }
+
+    /// Presses `key` with the next frame.
+    pub(crate) fn press_key(&mut self, key: egui::Key) {
+        for pressed in [true, false] {
+            self.events.push(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            });
+        }
+    }
+}
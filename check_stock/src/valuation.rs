@@ -0,0 +1,145 @@
+//! Inventory valuation — listed (asking) value side by side with market value.
+//!
+//! [`DbStats::total_value`](crate::inventory_db::DbStats::total_value) sums the
+//! seller's own listed prices. Insurers and accountants want market value
+//! instead, so this module re-values every in-stock listing against a market
+//! reference (Cardmarket trend or 7-day average) and reports the per-card
+//! delta. Like [`crate::mispricing`], resolving the market price for a card is
+//! the caller's job, supplied as a closure, which keeps this module pure.
+
+use crate::api::inventory_sync::PriceField;
+use crate::inventory_db::InStockCard;
+
+/// Which market price a valuation is based on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketBasis {
+    /// Cardmarket's trend price.
+    #[default]
+    Trend,
+    /// Cardmarket's 7-day sales average — steadier for thinly traded cards.
+    Avg7,
+}
+
+impl MarketBasis {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trend => "Trend",
+            Self::Avg7 => "Avg 7-day",
+        }
+    }
+
+    pub fn all() -> &'static [Self] {
+        &[Self::Trend, Self::Avg7]
+    }
+
+    /// The price-guide column this basis reads.
+    pub fn field(self) -> PriceField {
+        match self {
+            Self::Trend => PriceField::Trend,
+            Self::Avg7 => PriceField::Avg7,
+        }
+    }
+}
+
+/// One in-stock listing valued at its listed and its market price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuationRow {
+    pub cardmarket_id: String,
+    pub name: String,
+    pub set_code: String,
+    pub condition: String,
+    pub is_foil: bool,
+    pub quantity: i64,
+    /// Listed unit price, in EUR.
+    pub listed_price: f64,
+    /// Market unit price, in EUR; `None` when the market has no price.
+    pub market_price: Option<f64>,
+}
+
+impl ValuationRow {
+    pub fn listed_value(&self) -> f64 {
+        self.listed_price * self.quantity as f64
+    }
+
+    pub fn market_value(&self) -> Option<f64> {
+        self.market_price.map(|p| p * self.quantity as f64)
+    }
+
+    /// Market value minus listed value; positive when the market values the
+    /// listing higher than its asking price.
+    pub fn delta(&self) -> Option<f64> {
+        self.market_value().map(|m| m - self.listed_value())
+    }
+}
+
+/// Inventory valued both ways.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Valuation {
+    pub basis: MarketBasis,
+    /// Rows sorted by absolute delta, largest first; unpriced rows last.
+    pub rows: Vec<ValuationRow>,
+    /// Σ listed price × quantity over every listing.
+    pub listed_total: f64,
+    /// Σ market price × quantity over the listings with a market price.
+    pub market_total: f64,
+    /// Σ listed value of the listings without a market price.
+    pub unpriced_listed_value: f64,
+    /// Copies without a market price.
+    pub unpriced_copies: i64,
+}
+
+impl Valuation {
+    /// Listed value of the listings that do have a market price — the
+    /// like-for-like counterpart of [`Valuation::market_total`].
+    pub fn priced_listed_total(&self) -> f64 {
+        self.listed_total - self.unpriced_listed_value
+    }
+
+    /// Market minus listed value over the priced listings.
+    pub fn delta(&self) -> f64 {
+        self.market_total - self.priced_listed_total()
+    }
+}
+
+/// Values `cards` against the market price returned by `market` (unit price in
+/// EUR, `None` when unknown). Non-positive market prices count as unknown.
+pub fn value_inventory(
+    cards: &[InStockCard],
+    basis: MarketBasis,
+    market: impl Fn(&InStockCard) -> Option<f64>,
+) -> Valuation {
+    let mut valuation = Valuation {
+        basis,
+        ..Valuation::default()
+    };
+    for card in cards.iter().filter(|c| c.quantity > 0) {
+        let row = ValuationRow {
+            cardmarket_id: card.cardmarket_id.clone(),
+            name: card.name.clone(),
+            set_code: card.set_code.clone(),
+            condition: card.condition.clone(),
+            is_foil: card.is_foil,
+            quantity: card.quantity,
+            listed_price: card.price,
+            market_price: market(card).filter(|p| *p > 0.0),
+        };
+        valuation.listed_total += row.listed_value();
+        match row.market_value() {
+            Some(value) => valuation.market_total += value,
+            None => {
+                valuation.unpriced_listed_value += row.listed_value();
+                valuation.unpriced_copies += row.quantity;
+            }
+        }
+        valuation.rows.push(row);
+    }
+    valuation.rows.sort_by(|a, b| {
+        let key = |r: &ValuationRow| r.delta().map(f64::abs).unwrap_or(-1.0);
+        key(b).total_cmp(&key(a)).then_with(|| a.name.cmp(&b.name))
+    });
+    valuation
+}
+
+#[cfg(test)]
+#[path = "valuation_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+//! Tests for valuation.
+
+use super::*;
+
+fn card(id: &str, price: f64, quantity: i64) -> InStockCard {
+    InStockCard {
+        cardmarket_id: id.to_string(),
+        name: format!("Card {id}"),
+        set_code: "TST".to_string(),
+        cn: "1".to_string(),
+        condition: "NM".to_string(),
+        language: "English".to_string(),
+        is_foil: false,
+        rarity: "rare".to_string(),
+        quantity,
+        price,
+        location: "A-0-1-1".to_string(),
+        effective_date: "2026-01-01".to_string(),
+    }
+}
+
+fn market(card: &InStockCard) -> Option<f64> {
+    match card.cardmarket_id.as_str() {
+        "1" => Some(3.0),
+        "2" => Some(8.0),
+        "4" => Some(0.0),
+        _ => None,
+    }
+}
+
+#[test]
+fn values_listed_and_market_side_by_side() {
+    let cards = [card("1", 2.0, 4), card("2", 10.0, 1)];
+    let v = value_inventory(&cards, MarketBasis::Trend, market);
+    assert_eq!(v.listed_total, 18.0);
+    assert_eq!(v.market_total, 20.0);
+    assert_eq!(v.delta(), 2.0);
+    assert_eq!(v.unpriced_copies, 0);
+}
+
+#[test]
+fn rows_sorted_by_absolute_delta() {
+    // Card 1: +4 (market 12 vs listed 8), card 2: −2 (8 vs 10)
+    let cards = [card("2", 10.0, 1), card("1", 2.0, 4)];
+    let v = value_inventory(&cards, MarketBasis::Avg7, market);
+    assert_eq!(v.basis, MarketBasis::Avg7);
+    assert_eq!(v.rows[0].cardmarket_id, "1");
+    assert_eq!(v.rows[0].delta(), Some(4.0));
+    assert_eq!(v.rows[1].delta(), Some(-2.0));
+}
+
+#[test]
+fn unpriced_cards_kept_apart_from_market_total() {
+    // Card 3 has no market row, card 4 a zero price; both count as unpriced
+    let cards = [card("1", 2.0, 1), card("3", 5.0, 2), card("4", 1.0, 3)];
+    let v = value_inventory(&cards, MarketBasis::Trend, market);
+    assert_eq!(v.listed_total, 15.0);
+    assert_eq!(v.market_total, 3.0);
+    assert_eq!(v.unpriced_copies, 5);
+    assert_eq!(v.unpriced_listed_value, 13.0);
+    assert_eq!(v.priced_listed_total(), 2.0);
+    assert_eq!(v.delta(), 1.0);
+    // Unpriced rows sort last
+    assert_eq!(v.rows[0].cardmarket_id, "1");
+    assert_eq!(v.rows[1].delta(), None);
+}
+
+#[test]
+fn sold_out_listings_ignored() {
+    let v = value_inventory(&[card("1", 2.0, 0)], MarketBasis::Trend, market);
+    assert!(v.rows.is_empty());
+    assert_eq!(v.listed_total, 0.0);
+}
+
+#[test]
+fn basis_maps_to_price_field() {
+    assert_eq!(MarketBasis::Trend.field(), PriceField::Trend);
+    assert_eq!(MarketBasis::Avg7.field(), PriceField::Avg7);
+    assert_eq!(MarketBasis::default(), MarketBasis::Trend);
+}
@@ -16,9 +16,16 @@
 //! to strip every set code, collector number, foil marker, category and tag and
 //! leave just the card name. Cards that appear more than once (e.g. maindeck and
 //! sideboard) are merged, summing their quantities.
+//!
+//! Power users who need exact printings use a **CSV wantslist** instead: a
+//! header row with a name column and any of set code, collector number,
+//! quantity, language, minimum condition and max price (`,` or `;`
+//! separated). Those columns become the entry's [`WantsConstraints`], which the
+//! matcher honours, and rows are only merged when their constraints agree.
 
-use crate::models::WantsEntry;
+use crate::models::{condition_rank, WantsConstraints, WantsEntry};
 use lazy_static::lazy_static;
+use mtg_common::{find_column, normalize_header, parse_decimal, DecimalLocale};
 use regex::Regex;
 
 lazy_static! {
@@ -74,13 +81,14 @@ pub struct WantslistParse {
     pub unparseable: Vec<String>,
 }
 
-/// Removes set/collector/foil/category/tag annotations, leaving the bare name.
+/// Removes set/collector/foil/category/tag annotations, leaving the bare name
+/// with runs of whitespace (tabs, non-breaking spaces) collapsed to one space.
 fn clean_name(raw: &str) -> String {
     let s = TAG_RE.replace_all(raw, "");
     let s = CAT_RE.replace_all(&s, "");
     let s = FOIL_RE.replace_all(&s, "");
     let s = SET_RE.replace(&s, "");
-    s.trim().to_string()
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 /// Parses one line into a [`ParsedLine`].
@@ -117,7 +125,8 @@ pub fn parse_line(line: &str) -> ParsedLine {
 /// Parses a full wantslist, merging duplicate card names.
 ///
 /// Duplicates are merged case-insensitively but the first-seen spelling and
-/// order are preserved, so the output is stable and human-readable.
+/// order are preserved, so the output is stable and human-readable. Merged
+/// quantities saturate at `i32::MAX` rather than overflowing.
 pub fn parse_wantslist(content: &str) -> WantslistParse {
     let mut result = WantslistParse::default();
     // Maps a lower-cased name to its index in `result.entries` for O(1) merging.
@@ -128,10 +137,11 @@ pub fn parse_wantslist(content: &str) -> WantslistParse {
             ParsedLine::Entry { quantity, name } => {
                 let key = name.to_lowercase();
                 if let Some(&i) = index.get(&key) {
-                    result.entries[i].quantity += quantity;
+                    result.entries[i].quantity =
+                        result.entries[i].quantity.saturating_add(quantity);
                 } else {
                     index.insert(key, result.entries.len());
-                    result.entries.push(WantsEntry { quantity, name });
+                    result.entries.push(WantsEntry::new(quantity, name));
                 }
             }
             ParsedLine::Skip => {}
@@ -142,6 +152,151 @@ pub fn parse_wantslist(content: &str) -> WantslistParse {
     result
 }
 
+const NAME_HEADERS: [&str; 3] = ["name", "card", "cardname"];
+const SET_HEADERS: [&str; 4] = ["set", "setcode", "edition", "expansion"];
+const CN_HEADERS: [&str; 4] = ["cn", "collectornumber", "number", "no"];
+const QUANTITY_HEADERS: [&str; 4] = ["quantity", "qty", "count", "amount"];
+const LANGUAGE_HEADERS: [&str; 2] = ["language", "lang"];
+const CONDITION_HEADERS: [&str; 4] = [
+    "condition",
+    "mincondition",
+    "conditionmin",
+    "minimumcondition",
+];
+const MAX_PRICE_HEADERS: [&str; 3] = ["maxprice", "pricemax", "maximumprice"];
+
+/// Field delimiter of a CSV wantslist, judged from its header line.
+fn csv_delimiter(header_line: &str) -> u8 {
+    if header_line.contains(';') {
+        b';'
+    } else {
+        b','
+    }
+}
+
+fn csv_headers(header_line: &str) -> Vec<String> {
+    header_line
+        .split(csv_delimiter(header_line) as char)
+        .map(|h| normalize_header(h.trim_matches('"')))
+        .collect()
+}
+
+/// Whether `content` is a CSV wantslist: its first non-empty line is a header
+/// with a name column and at least one other known column.
+pub fn is_csv_wantslist(content: &str) -> bool {
+    let Some(header_line) = content.lines().find(|l| !l.trim().is_empty()) else {
+        return false;
+    };
+    let headers = csv_headers(header_line);
+    let known = [
+        &SET_HEADERS[..],
+        &CN_HEADERS,
+        &QUANTITY_HEADERS,
+        &LANGUAGE_HEADERS,
+        &CONDITION_HEADERS,
+        &MAX_PRICE_HEADERS,
+    ];
+    find_column(&headers, &NAME_HEADERS).is_some()
+        && known
+            .iter()
+            .any(|names| find_column(&headers, names).is_some())
+}
+
+/// Parses a price such as `12.50`, `12,50 €` or `1.234,56 €`, in the
+/// configured decimal locale.
+fn parse_price(raw: &str) -> Option<f64> {
+    let cleaned = raw.trim().trim_end_matches('€').trim();
+    parse_decimal(cleaned, DecimalLocale::from_env()).filter(|p| p.is_finite() && *p >= 0.0)
+}
+
+/// Parses a CSV wantslist (see the module docs).
+///
+/// A missing or empty quantity means one copy. Rows without a name, or with a
+/// quantity, condition or price that cannot be read, are reported as
+/// unparseable. Rows for the same card with the same constraints are merged.
+pub fn parse_wantslist_csv(content: &str) -> Result<WantslistParse, String> {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    let header_line = content.lines().next().unwrap_or_default();
+    let delimiter = csv_delimiter(header_line);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Failed to read wantslist header: {e}"))?
+        .iter()
+        .map(normalize_header)
+        .collect();
+    let name_col = find_column(&headers, &NAME_HEADERS)
+        .ok_or("Wantslist CSV has no name column (expected Name)")?;
+    let set_col = find_column(&headers, &SET_HEADERS);
+    let cn_col = find_column(&headers, &CN_HEADERS);
+    let quantity_col = find_column(&headers, &QUANTITY_HEADERS);
+    let language_col = find_column(&headers, &LANGUAGE_HEADERS);
+    let condition_col = find_column(&headers, &CONDITION_HEADERS);
+    let max_price_col = find_column(&headers, &MAX_PRICE_HEADERS);
+
+    let mut result = WantslistParse::default();
+    for (line, record) in (2..).zip(reader.records()) {
+        let record = record.map_err(|e| format!("Invalid wantslist line {line}: {e}"))?;
+        let field = |col: Option<usize>| {
+            col.and_then(|c| record.get(c))
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let raw_line = || {
+            record
+                .iter()
+                .collect::<Vec<_>>()
+                .join(&(delimiter as char).to_string())
+        };
+        if record.iter().all(|v| v.is_empty()) {
+            continue;
+        }
+
+        let Some(name) =
+            field(Some(name_col)).map(|n| n.split_whitespace().collect::<Vec<_>>().join(" "))
+        else {
+            result.unparseable.push(raw_line());
+            continue;
+        };
+        let quantity = match field(quantity_col) {
+            None => Some(1),
+            Some(raw) => raw.parse::<i32>().ok().filter(|q| *q > 0),
+        };
+        let min_condition = field(condition_col);
+        let max_price = field(max_price_col).map(parse_price);
+        let valid = min_condition.is_none_or(|c| condition_rank(c).is_some())
+            && max_price.is_none_or(|p| p.is_some());
+        let Some(quantity) = quantity.filter(|_| valid) else {
+            result.unparseable.push(raw_line());
+            continue;
+        };
+
+        let entry = WantsEntry {
+            quantity,
+            name,
+            constraints: WantsConstraints {
+                set_code: field(set_col).map(str::to_string),
+                collector_number: field(cn_col).map(str::to_string),
+                language: field(language_col).map(str::to_string),
+                min_condition: min_condition.map(str::to_string),
+                max_price: max_price.flatten(),
+            },
+        };
+        match result.entries.iter_mut().find(|e| {
+            e.name.to_lowercase() == entry.name.to_lowercase() && e.constraints == entry.constraints
+        }) {
+            Some(existing) => existing.quantity = existing.quantity.saturating_add(entry.quantity),
+            None => result.entries.push(entry),
+        }
+    }
+    Ok(result)
+}
+
 #[path = "wantslist_tests.rs"]
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,239 @@
+//! Whole-wantslist fulfillment optimizer.
+//!
+//! [`find_matching_cards`](crate::card_matching::find_matching_cards) picks
+//! listings one wantslist entry at a time, so two entries that compete for the
+//! same listings (a duplicated line, or one card wanted under its English and
+//! German name) each see the full stock, and the cheapest choice for one entry
+//! can leave a later entry short. This module allocates the whole wantslist at
+//! once instead.
+//!
+//! The allocation is solved exactly as a min-cost flow: the source feeds every
+//! want up to its quantity, each want can draw copies from every listing it
+//! matches, and each listing passes at most its stock to the sink. A copy costs
+//! its per-copy price ([`OptimizationGoal::MinimizeCost`]) or its negated margin
+//! ([`OptimizationGoal::MaximizeMargin`]). The solver first fills as many copies
+//! as the stock allows, then picks the cheapest such combination. Listings in
+//! the preferred language win ties.
+
+use crate::card_matching::{matches_want, MatchedCard};
+use crate::models::{Card, Language, WantsEntry};
+use crate::playset::PLAYSET_SIZE;
+
+/// What the optimizer minimizes across the whole wantslist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptimizationGoal {
+    /// Lowest total price for the buyer.
+    #[default]
+    MinimizeCost,
+    /// Highest total margin (price minus acquisition cost) for the seller.
+    MaximizeMargin,
+}
+
+impl OptimizationGoal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OptimizationGoal::MinimizeCost => "Minimize total cost",
+            OptimizationGoal::MaximizeMargin => "Maximize margin",
+        }
+    }
+}
+
+/// Copies a listing can supply; a playset listing supplies four per unit.
+fn available_copies(card: &Card) -> i64 {
+    let quantity = card.quantity.parse::<i64>().unwrap_or(0).max(0);
+    if card.is_playset_card() {
+        quantity * PLAYSET_SIZE
+    } else {
+        quantity
+    }
+}
+
+/// Price of a single copy in EUR (a playset's price is split over its copies).
+pub fn copy_price(card: &Card) -> f64 {
+    if card.is_playset_card() {
+        card.price_f64() / PLAYSET_SIZE as f64
+    } else {
+        card.price_f64()
+    }
+}
+
+fn to_cents(eur: f64) -> i64 {
+    (eur * 100.0).round() as i64
+}
+
+/// Allocates inventory listings to every wantslist entry at once.
+///
+/// Returns one list of matched listings per entry, in wantslist order, each
+/// sorted cheapest copy first. `cost_basis` gives the acquisition cost of one
+/// copy of a listing; it is only consulted for [`OptimizationGoal::MaximizeMargin`].
+/// A listing never supplies more copies in total than it has in stock, even
+/// when several entries match it.
+pub fn optimize_wantslist<'a>(
+    wantslist: &[WantsEntry],
+    inventory: &'a [Card],
+    preferred_language: Option<Language>,
+    preferred_language_only: bool,
+    goal: OptimizationGoal,
+    cost_basis: impl Fn(&Card) -> f64,
+) -> Vec<Vec<MatchedCard<'a>>> {
+    // Node layout: source, sink, one node per want, one node per listing.
+    let source = 0;
+    let sink = 1;
+    let want_node = |w: usize| 2 + w;
+    let listing_node = |l: usize| 2 + wantslist.len() + l;
+
+    let mut network = FlowNetwork::new(2 + wantslist.len() + inventory.len());
+
+    for (l, card) in inventory.iter().enumerate() {
+        let copies = available_copies(card);
+        if copies > 0 {
+            network.add_edge(listing_node(l), sink, copies, 0);
+        }
+    }
+
+    // (want index, listing index, edge id) for every want → listing edge
+    let mut assignments = Vec::new();
+    for (w, want) in wantslist.iter().enumerate() {
+        if want.quantity <= 0 {
+            continue;
+        }
+        network.add_edge(source, want_node(w), want.quantity as i64, 0);
+
+        for (l, card) in inventory.iter().enumerate() {
+            if available_copies(card) == 0
+                || !matches_want(card, want, preferred_language, preferred_language_only)
+            {
+                continue;
+            }
+            let copy_cost = match goal {
+                OptimizationGoal::MinimizeCost => to_cents(copy_price(card)),
+                OptimizationGoal::MaximizeMargin => -to_cents(copy_price(card) - cost_basis(card)),
+            };
+            let in_preferred_language = preferred_language
+                .zip(Language::parse(&card.language))
+                .is_some_and(|(preferred, lang)| preferred == lang);
+            // Doubling keeps a one-cent difference larger than the language tie-break.
+            let cost = copy_cost * 2 + i64::from(!in_preferred_language);
+            let edge = network.add_edge(want_node(w), listing_node(l), i64::MAX, cost);
+            assignments.push((w, l, edge));
+        }
+    }
+
+    network.min_cost_max_flow(source, sink);
+
+    let mut result: Vec<Vec<MatchedCard<'a>>> = vec![Vec::new(); wantslist.len()];
+    for (w, l, edge) in assignments {
+        let copies = network.flow(edge);
+        if copies > 0 {
+            let card = &inventory[l];
+            result[w].push(MatchedCard {
+                card,
+                quantity: copies as i32,
+                set_name: format!("{} ({})", card.set, card.set_code),
+            });
+        }
+    }
+    for matches in &mut result {
+        matches.sort_by(|a, b| {
+            copy_price(a.card)
+                .total_cmp(&copy_price(b.card))
+                .then_with(|| a.card.name.cmp(&b.card.name))
+                .then_with(|| a.card.cardmarket_id.cmp(&b.card.cardmarket_id))
+        });
+    }
+    result
+}
+
+struct FlowEdge {
+    from: usize,
+    to: usize,
+    capacity: i64,
+    cost: i64,
+}
+
+/// Residual graph for min-cost max-flow. Edges are stored in pairs: edge `i`
+/// and its reverse `i ^ 1`, whose capacity is the flow pushed through `i`.
+struct FlowNetwork {
+    nodes: usize,
+    edges: Vec<FlowEdge>,
+}
+
+impl FlowNetwork {
+    fn new(nodes: usize) -> Self {
+        Self {
+            nodes,
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds an edge and returns its id for [`FlowNetwork::flow`].
+    fn add_edge(&mut self, from: usize, to: usize, capacity: i64, cost: i64) -> usize {
+        let id = self.edges.len();
+        self.edges.push(FlowEdge {
+            from,
+            to,
+            capacity,
+            cost,
+        });
+        self.edges.push(FlowEdge {
+            from: to,
+            to: from,
+            capacity: 0,
+            cost: -cost,
+        });
+        id
+    }
+
+    fn flow(&self, edge: usize) -> i64 {
+        self.edges[edge ^ 1].capacity
+    }
+
+    /// Successive shortest paths: repeatedly augments along the cheapest
+    /// residual source → sink path (Bellman-Ford, as costs may be negative)
+    /// until the sink is unreachable.
+    fn min_cost_max_flow(&mut self, source: usize, sink: usize) {
+        loop {
+            let mut dist = vec![i64::MAX; self.nodes];
+            let mut via = vec![usize::MAX; self.nodes];
+            dist[source] = 0;
+            for _ in 0..self.nodes {
+                let mut updated = false;
+                for (id, edge) in self.edges.iter().enumerate() {
+                    if edge.capacity > 0 && dist[edge.from] != i64::MAX {
+                        let candidate = dist[edge.from] + edge.cost;
+                        if candidate < dist[edge.to] {
+                            dist[edge.to] = candidate;
+                            via[edge.to] = id;
+                            updated = true;
+                        }
+                    }
+                }
+                if !updated {
+                    break;
+                }
+            }
+            if dist[sink] == i64::MAX {
+                return;
+            }
+
+            let mut push = i64::MAX;
+            let mut node = sink;
+            while node != source {
+                let edge = &self.edges[via[node]];
+                push = push.min(edge.capacity);
+                node = edge.from;
+            }
+            let mut node = sink;
+            while node != source {
+                let id = via[node];
+                self.edges[id].capacity -= push;
+                self.edges[id ^ 1].capacity += push;
+                node = self.edges[id].from;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "wantslist_optimizer_tests.rs"]
+mod tests;
@@ -0,0 +1,229 @@
+//! Tests for wantslist_optimizer.
+
+use super::*;
+
+fn listing(id: &str, name: &str, quantity: i32, price: &str) -> Card {
+    Card {
+        cardmarket_id: id.to_string(),
+        name: name.to_string(),
+        quantity: quantity.to_string(),
+        price: price.to_string(),
+        ..Card::test_default()
+    }
+}
+
+fn wants(name: &str, quantity: i32) -> WantsEntry {
+    WantsEntry::new(quantity, name)
+}
+
+fn no_cost(_: &Card) -> f64 {
+    0.0
+}
+
+/// (cardmarket_id, copies) per matched listing, for compact assertions.
+fn picks(matches: &[MatchedCard]) -> Vec<(String, i32)> {
+    matches
+        .iter()
+        .map(|mc| (mc.card.cardmarket_id.clone(), mc.quantity))
+        .collect()
+}
+
+#[test]
+fn picks_cheapest_listings_first() {
+    let inventory = vec![
+        listing("1", "Lightning Bolt", 4, "2.00"),
+        listing("2", "Lightning Bolt", 4, "0.50"),
+    ];
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 5)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert_eq!(
+        picks(&result[0]),
+        vec![("2".to_string(), 4), ("1".to_string(), 1)]
+    );
+}
+
+#[test]
+fn entries_never_share_a_listings_stock() {
+    let mut german = listing("1", "Lightning Bolt", 1, "0.50");
+    german.name_de = "Blitzschlag".to_string();
+    let mut pricier = listing("2", "Lightning Bolt", 1, "3.00");
+    pricier.name_de = "Blitzschlag".to_string();
+    let inventory = vec![german, pricier];
+
+    // Both lines match both listings; per-entry matching would hand listing 1
+    // to each of them.
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 1), wants("Blitzschlag", 1)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    let total: i32 = result.iter().flatten().map(|mc| mc.quantity).sum();
+    assert_eq!(total, 2);
+    let mut used: Vec<_> = result
+        .iter()
+        .flatten()
+        .map(|mc| mc.card.cardmarket_id.clone())
+        .collect();
+    used.sort();
+    assert_eq!(used, vec!["1".to_string(), "2".to_string()]);
+}
+
+#[test]
+fn fills_as_many_copies_as_possible_before_minimizing_cost() {
+    // The cheap Counterspell is the only copy the second line can use, so the
+    // first line must take the pricier Bolt listing to keep both lines filled.
+    let mut shared = listing("1", "Lightning Bolt", 1, "0.10");
+    shared.name_fr = "Counterspell".to_string();
+    let inventory = vec![shared, listing("2", "Lightning Bolt", 1, "5.00")];
+
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 1), wants("Counterspell", 1)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert_eq!(picks(&result[0]), vec![("2".to_string(), 1)]);
+    assert_eq!(picks(&result[1]), vec![("1".to_string(), 1)]);
+}
+
+#[test]
+fn reports_shortfall_when_stock_runs_out() {
+    let inventory = vec![listing("1", "Lightning Bolt", 2, "1.00")];
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 4), wants("Opt", 2)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert_eq!(picks(&result[0]), vec![("1".to_string(), 2)]);
+    assert!(result[1].is_empty());
+}
+
+#[test]
+fn playset_listing_supplies_four_copies_at_quarter_price() {
+    let mut playset = listing("1", "Lightning Bolt", 1, "2.00");
+    playset.is_playset = Some("1".to_string());
+    let inventory = vec![playset, listing("2", "Lightning Bolt", 4, "0.60")];
+
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 4)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    // 0.50 per copy from the playset beats 0.60 singles.
+    assert_eq!(picks(&result[0]), vec![("1".to_string(), 4)]);
+    assert!((copy_price(&inventory[0]) - 0.50).abs() < 1e-9);
+}
+
+#[test]
+fn margin_goal_prefers_higher_margin_over_lower_price() {
+    let inventory = vec![
+        listing("cheap", "Lightning Bolt", 1, "2.00"),
+        listing("dear", "Lightning Bolt", 1, "3.00"),
+    ];
+    let cost_basis = |card: &Card| {
+        if card.cardmarket_id == "cheap" {
+            1.90
+        } else {
+            1.00
+        }
+    };
+
+    let by_cost = optimize_wantslist(
+        &[wants("Lightning Bolt", 1)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        cost_basis,
+    );
+    let by_margin = optimize_wantslist(
+        &[wants("Lightning Bolt", 1)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MaximizeMargin,
+        cost_basis,
+    );
+
+    assert_eq!(picks(&by_cost[0]), vec![("cheap".to_string(), 1)]);
+    assert_eq!(picks(&by_margin[0]), vec![("dear".to_string(), 1)]);
+}
+
+#[test]
+fn preferred_language_breaks_price_ties() {
+    let mut german = listing("de", "Lightning Bolt", 1, "1.00");
+    german.language = "German".to_string();
+    let english = listing("en", "Lightning Bolt", 1, "1.00");
+    let inventory = vec![english, german];
+
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 1)],
+        &inventory,
+        Some(Language::German),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert_eq!(picks(&result[0]), vec![("de".to_string(), 1)]);
+}
+
+#[test]
+fn preferred_language_only_filters_listings() {
+    let mut german = listing("de", "Lightning Bolt", 1, "5.00");
+    german.language = "German".to_string();
+    german.name_de = "Blitzschlag".to_string();
+    let inventory = vec![listing("en", "Lightning Bolt", 1, "0.10"), german];
+
+    let result = optimize_wantslist(
+        &[wants("Blitzschlag", 1)],
+        &inventory,
+        Some(Language::German),
+        true,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert_eq!(picks(&result[0]), vec![("de".to_string(), 1)]);
+}
+
+#[test]
+fn ignores_out_of_stock_listings_and_empty_wants() {
+    let inventory = vec![
+        listing("1", "Lightning Bolt", 0, "0.10"),
+        listing("2", "Lightning Bolt", 2, "1.00"),
+    ];
+    let result = optimize_wantslist(
+        &[wants("Lightning Bolt", 0), wants("Lightning Bolt", 1)],
+        &inventory,
+        Some(Language::English),
+        false,
+        OptimizationGoal::MinimizeCost,
+        no_cost,
+    );
+
+    assert!(result[0].is_empty());
+    assert_eq!(picks(&result[1]), vec![("2".to_string(), 1)]);
+}
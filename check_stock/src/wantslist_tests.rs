@@ -1,10 +1,7 @@
 use super::*;
 
 fn entry(quantity: i32, name: &str) -> WantsEntry {
-    WantsEntry {
-        quantity,
-        name: name.to_string(),
-    }
+    WantsEntry::new(quantity, name)
 }
 
 // ==================== parse_line: quantity formats ====================
@@ -249,3 +246,223 @@ fn plain_legacy_format_still_works() {
 fn empty_document_yields_nothing() {
     assert_eq!(parse_wantslist(""), WantslistParse::default());
 }
+
+// ==================== parse_wantslist_csv ====================
+
+#[test]
+fn csv_wantslist_is_detected_by_its_header() {
+    assert!(is_csv_wantslist(
+        "Name,Set,CN,Quantity\nLightning Bolt,2XM,123,4\n"
+    ));
+    assert!(is_csv_wantslist(
+        "\n card name ; max_price\nSol Ring;3,50\n"
+    ));
+    assert!(!is_csv_wantslist("4 Lightning Bolt\n1 Sol Ring\n"));
+    assert!(!is_csv_wantslist("Name Burn deck\n4 Lightning Bolt\n"));
+    assert!(!is_csv_wantslist(""));
+}
+
+#[test]
+fn csv_columns_become_constraints() {
+    let csv = "Name;Set Code;Collector Number;Qty;Language;Min Condition;Max Price\n\
+               Lightning Bolt;2XM;123;4;German;EX;2,50 €\n\
+               Sol Ring;;;;;;\n";
+
+    let parsed = parse_wantslist_csv(csv).unwrap();
+
+    assert!(parsed.unparseable.is_empty(), "{:?}", parsed.unparseable);
+    assert_eq!(
+        parsed.entries,
+        vec![
+            WantsEntry {
+                quantity: 4,
+                name: "Lightning Bolt".to_string(),
+                constraints: WantsConstraints {
+                    set_code: Some("2XM".to_string()),
+                    collector_number: Some("123".to_string()),
+                    language: Some("German".to_string()),
+                    min_condition: Some("EX".to_string()),
+                    max_price: Some(2.5),
+                },
+            },
+            entry(1, "Sol Ring"),
+        ]
+    );
+}
+
+#[test]
+fn csv_rows_merge_only_with_equal_constraints() {
+    let csv = "name,set,quantity\n\
+               Lightning Bolt,2XM,2\n\
+               lightning bolt,2xm,1\n\
+               Lightning Bolt,2XM,1\n\
+               Lightning Bolt,M10,1\n\
+               Lightning Bolt,,1\n";
+
+    let parsed = parse_wantslist_csv(csv).unwrap();
+
+    let summary: Vec<_> = parsed
+        .entries
+        .iter()
+        .map(|e| (e.quantity, e.constraints.set_code.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (3, Some("2XM")),
+            (1, Some("2xm")),
+            (1, Some("M10")),
+            (1, None)
+        ]
+    );
+}
+
+#[test]
+fn csv_rows_with_bad_values_are_reported() {
+    let csv = "Name,Quantity,Condition,Max Price\n\
+               ,1,,\n\
+               Sol Ring,many,,\n\
+               Mana Crypt,1,Mint-ish,\n\
+               Mox Opal,1,,cheap\n\
+               Counterspell,2,NM,1.00\n";
+
+    let parsed = parse_wantslist_csv(csv).unwrap();
+
+    assert_eq!(parsed.entries.len(), 1);
+    assert_eq!(parsed.entries[0].name, "Counterspell");
+    assert_eq!(parsed.unparseable.len(), 4);
+    assert!(parse_wantslist_csv("Set,Quantity\n2XM,1\n").is_err());
+}
+
+#[test]
+fn csv_max_price_accepts_thousands_separators() {
+    let csv = "Name;Max Price\n\
+               Black Lotus;1.234,56 €\n\
+               Mox Sapphire;1,234.56\n";
+
+    let parsed = parse_wantslist_csv(csv).unwrap();
+
+    assert!(parsed.unparseable.is_empty(), "{:?}", parsed.unparseable);
+    let prices: Vec<_> = parsed
+        .entries
+        .iter()
+        .map(|e| e.constraints.max_price)
+        .collect();
+    assert_eq!(prices, vec![Some(1234.56), Some(1234.56)]);
+}
+
+// ==================== Property Tests ====================
+
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    /// Card names including non-ASCII letters, apostrophes, commas and a
+    /// genuine parenthetical.
+    const NAMES: &[&str] = &[
+        "Lightning Bolt",
+        "Æther Vial",
+        "Jötun Grunt",
+        "Lim-Dûl's Vault",
+        "Ifh-Bíff Efreet",
+        "Séance",
+        "Borrowing 100,000 Arrows",
+        "B.F.M. (Big Furry Monster)",
+        "Fire // Ice",
+        "Sol Ring",
+    ];
+
+    /// Whitespace a pasted list may contain between tokens.
+    fn gap() -> impl Strategy<Value = String> {
+        prop::collection::vec(prop_oneof![Just(' '), Just('\t'), Just('\u{a0}')], 1..4)
+            .prop_map(|chars| chars.into_iter().collect())
+    }
+
+    fn padding() -> impl Strategy<Value = String> {
+        prop_oneof![Just(String::new()), gap()]
+    }
+
+    /// Mixes upper and lower case per character.
+    fn recase(name: &str, mask: u64) -> String {
+        name.chars()
+            .enumerate()
+            .flat_map(|(i, c)| {
+                let upper = mask >> (i % 64) & 1 == 1;
+                let mapped: Vec<char> = if upper {
+                    c.to_uppercase().collect()
+                } else {
+                    c.to_lowercase().collect()
+                };
+                mapped
+            })
+            .collect()
+    }
+
+    /// One rendered card line plus the (quantity, canonical name) it encodes.
+    fn card_line() -> impl Strategy<Value = (String, i32, &'static str)> {
+        (
+            0..NAMES.len(),
+            0..i32::MAX,
+            any::<u64>(),
+            padding(),
+            padding(),
+            gap(),
+            prop::bool::ANY,
+            prop::bool::ANY,
+            prop::collection::vec(gap(), 4),
+        )
+            .prop_map(
+                |(i, quantity, mask, lead, trail, sep, x_suffix, set_code, inner)| {
+                    let name = NAMES[i];
+                    let mut words = name.split(' ');
+                    let mut spaced = words.next().unwrap().to_string();
+                    for (word, gap) in words.zip(inner.iter().cycle()) {
+                        spaced.push_str(gap);
+                        spaced.push_str(word);
+                    }
+                    let line = format!(
+                        "{lead}{quantity}{}{sep}{}{}{trail}",
+                        if x_suffix { "x" } else { "" },
+                        recase(&spaced, mask),
+                        if set_code { " (2XM) 123" } else { "" },
+                    );
+                    (line, quantity, name)
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics_on_arbitrary_input(doc in any::<String>()) {
+            let _ = parse_wantslist(&doc);
+        }
+
+        #[test]
+        fn quantities_are_summed_per_name(
+            lines in prop::collection::vec(card_line(), 0..20),
+            crlf in prop::bool::ANY,
+        ) {
+            let doc = lines
+                .iter()
+                .map(|(line, _, _)| line.as_str())
+                .collect::<Vec<_>>()
+                .join(if crlf { "\r\n" } else { "\n" });
+            let parsed = parse_wantslist(&doc);
+            prop_assert!(parsed.unparseable.is_empty(), "{:?}", parsed.unparseable);
+
+            let mut expected: HashMap<String, i32> = HashMap::new();
+            for (_, quantity, name) in &lines {
+                let total = expected.entry(name.to_lowercase()).or_default();
+                *total = total.saturating_add(*quantity);
+            }
+            prop_assert_eq!(parsed.entries.len(), expected.len());
+            for entry in &parsed.entries {
+                // Whitespace is normalized, only the casing may differ
+                prop_assert!(NAMES.iter().any(|n| n.to_lowercase() == entry.name.to_lowercase()));
+                prop_assert!(entry.quantity >= 0);
+                prop_assert_eq!(Some(&entry.quantity), expected.get(&entry.name.to_lowercase()));
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use d2d_automations::io::{read_csv, read_wantslist};
+use d2d_automations::io::{read_csv, read_csv_in, read_csv_report, read_wantslist, CsvLoadMode};
 use d2d_automations::models::Card;
+use mtg_common::DecimalLocale;
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -72,6 +73,30 @@ fn test_read_csv_valid_file() {
     assert_eq!(cards[0].listed_at, "2024-01-01");
 }
 
+#[test]
+fn test_read_csv_normalizes_localized_prices() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(
+        temp_file,
+        r#"cardmarketId,quantity,name,set,setCode,cn,condition,language,isFoil,isPlayset,isSigned,price,comment,location,nameDE,nameES,nameFR,nameIT,rarity,listedAt
+1,1,Black Lotus,Alpha,LEA,1,NM,EN,false,,false,"15.000,50",,A1,,,,,rare,2024-01-01
+2,1,Lightning Bolt,Alpha,LEA,2,NM,EN,false,,false,"0,25",,A1,,,,,common,2024-01-01
+3,1,Sol Ring,Alpha,LEA,3,NM,EN,false,,false,2.00,,A1,,,,,uncommon,2024-01-01"#
+    )
+    .unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let cards = read_csv_in(path, DecimalLocale::Auto).unwrap();
+    assert_eq!(cards[0].price, "15000.5");
+    assert_eq!(cards[1].price, "0.25");
+    assert_eq!(cards[2].price, "2.00", "plain prices are kept verbatim");
+
+    // Strict English leaves the German prices unparsed
+    let cards = read_csv_in(path, DecimalLocale::English).unwrap();
+    assert_eq!(cards[1].price, "0,25");
+    assert_eq!(cards[2].price, "2.00");
+}
+
 #[test]
 fn test_read_csv_filters_empty_price_and_quantity() {
     let mut temp_file = NamedTempFile::new().unwrap();
@@ -104,6 +129,56 @@ fn test_read_csv_invalid_csv_format() {
     assert!(result.is_err());
 }
 
+fn create_partly_malformed_csv_content() -> String {
+    r#"cardmarketId,quantity,name,set,setCode,cn,condition,language,isFoil,isPlayset,isSigned,price,comment,location,nameDE,nameES,nameFR,nameIT,rarity,listedAt
+12345,4,Lightning Bolt,Limited Edition Alpha,LEA,123,NM,EN,false,,false,25.00,,A1,,,,,common,2024-01-01
+67890,four,Black Lotus,Limited Edition Alpha,LEA,456,NM,EN,false,,false,15000.00,,A1,,,,,rare,2024-01-01
+11111,2,Ancestral Recall
+22222,1,Sol Ring,Limited Edition Alpha,LEA,789,NM,EN,false,,false,2.00,,A2,,,,,uncommon,2024-01-01"#
+        .to_string()
+}
+
+#[test]
+fn test_read_csv_strict_mode_fails_with_line_number() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(temp_file, "{}", create_partly_malformed_csv_content()).unwrap();
+
+    let err = read_csv_report(
+        temp_file.path().to_str().unwrap(),
+        DecimalLocale::Auto,
+        CsvLoadMode::Strict,
+    )
+    .unwrap_err();
+
+    assert!(
+        err.to_string().starts_with("line 3: quantity 'four'"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_read_csv_lenient_mode_reports_malformed_lines() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    write!(temp_file, "{}", create_partly_malformed_csv_content()).unwrap();
+    let path = temp_file.path().to_str().unwrap();
+
+    let (cards, report) = read_csv_report(path, DecimalLocale::Auto, CsvLoadMode::Lenient).unwrap();
+
+    assert_eq!(
+        cards.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        ["Lightning Bolt", "Sol Ring"]
+    );
+    assert_eq!(report.path, path);
+    assert_eq!(report.loaded, 2);
+    assert_eq!(
+        report.errors.iter().map(|e| e.line).collect::<Vec<_>>(),
+        [3, 4]
+    );
+    assert!(report.errors[1].message.contains("end of row"));
+    let csv = report.to_csv();
+    assert!(csv.starts_with("line,error\n3,\"quantity 'four' is not a whole number\"\n"));
+}
+
 #[test]
 fn test_read_csv_empty_file() {
     let temp_file = NamedTempFile::new().unwrap();
@@ -219,6 +294,20 @@ fn test_read_csv_with_whitespace() {
     assert_eq!(cards[0].price, "25.00");
 }
 
+#[test]
+fn test_read_csv_gzipped_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut encoder =
+        flate2::write::GzEncoder::new(temp_file.reopen().unwrap(), flate2::Compression::default());
+    write!(encoder, "{}", create_sample_csv_content()).unwrap();
+    encoder.finish().unwrap();
+
+    let cards = read_csv(temp_file.path().to_str().unwrap()).unwrap();
+
+    assert_eq!(cards.len(), 3);
+    assert_eq!(cards[0].name, "Lightning Bolt");
+}
+
 // Tests for read_wantslist function
 
 #[test]
@@ -247,6 +336,20 @@ fn test_read_wantslist_valid_file() {
     assert_eq!(wants[4].name, "Sol Ring");
 }
 
+#[test]
+fn test_read_wantslist_gzipped_file() {
+    let temp_file = NamedTempFile::new().unwrap();
+    let mut encoder =
+        flate2::write::GzEncoder::new(temp_file.reopen().unwrap(), flate2::Compression::default());
+    write!(encoder, "{}", create_sample_wantslist_content()).unwrap();
+    encoder.finish().unwrap();
+
+    let wants = read_wantslist(temp_file.path().to_str().unwrap()).unwrap();
+
+    assert_eq!(wants.len(), 5);
+    assert_eq!(wants[4].name, "Sol Ring");
+}
+
 #[test]
 fn test_read_wantslist_nonexistent_file() {
     let result = read_wantslist("/this/file/does/not/exist.txt");
@@ -469,3 +572,65 @@ mod edge_cases {
         assert_eq!(cards[0].set, "Sét Spéciał");
     }
 }
+
+// Tests for CSVs re-saved by Excel on Windows
+
+mod excel_encodings {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> String {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_read_csv_in_every_excel_encoding() {
+        for name in [
+            "inventory_utf8_bom.csv",
+            "inventory_utf16le.csv",
+            "inventory_utf16be.csv",
+            "inventory_windows1252.csv",
+        ] {
+            let cards = read_csv(&fixture(name)).unwrap();
+
+            assert_eq!(cards.len(), 2, "{name}");
+            // A BOM left in place would end up in the first header
+            assert_eq!(cards[0].cardmarket_id, "12345", "{name}");
+            assert_eq!(cards[0].price, "25.5", "{name}");
+            assert_eq!(cards[0].comment, "Ecke geknickt – siehe Foto", "{name}");
+            assert_eq!(cards[0].name_fr, "Éclair", "{name}");
+            assert_eq!(cards[1].name, "Æther Vial", "{name}");
+            // CRLF must not leave a carriage return on the last column
+            assert_eq!(cards[1].listed_at, "2024-01-02", "{name}");
+        }
+    }
+
+    #[test]
+    fn test_read_csv_accepts_quoted_windows_path() {
+        let quoted = format!("\"{}\"", fixture("inventory_windows1252.csv"));
+
+        let cards = read_csv(&quoted).unwrap();
+
+        assert_eq!(cards.len(), 2);
+    }
+
+    #[test]
+    fn test_read_wantslist_in_utf16() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "4 Æther Vial\r\n1 Jötun Grunt\r\n".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        temp_file.write_all(&bytes).unwrap();
+
+        let wants = read_wantslist(temp_file.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(wants.len(), 2);
+        assert_eq!(wants[0].name, "Æther Vial");
+        assert_eq!(wants[1].name, "Jötun Grunt");
+    }
+}
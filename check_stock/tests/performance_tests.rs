@@ -1,5 +1,6 @@
-use d2d_automations::card_matching::find_matching_cards;
-use d2d_automations::models::{Card, Language};
+use d2d_automations::card_matching::{find_matches_for_wantslist, find_matching_cards};
+use d2d_automations::models::{Card, Language, WantsEntry};
+use d2d_automations::stock_analysis::StockAnalysis;
 use std::time::Instant;
 
 fn create_test_card(
@@ -450,6 +451,98 @@ fn test_search_performance_concurrent_safety() {
     }
 }
 
+/// Runs `f` on a single-threaded rayon pool, i.e. as before matching and
+/// analysis were parallelized, and returns the result with the time taken.
+fn time_single_threaded<T: Send>(f: impl FnOnce() -> T + Send) -> (T, f64) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    pool.install(|| time_ms(f))
+}
+
+fn time_ms<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed().as_secs_f64() * 1000.0)
+}
+
+#[test]
+fn test_parallel_wantslist_matching_on_100k_inventory() {
+    println!("\n=== Benchmark: Wantslist Matching, 100k Inventory ===");
+
+    let inventory = generate_test_inventory(100_000);
+    let wantslist: Vec<WantsEntry> = (0..16)
+        .map(|i| {
+            WantsEntry::new(
+                (i % 8) + 1,
+                ["Lightning Bolt", "Sol Ring", "Brainstorm", "Not In Stock"][i as usize % 4],
+            )
+        })
+        .collect();
+
+    let (before, before_ms) = time_single_threaded(|| {
+        find_matches_for_wantslist(&wantslist, &inventory, Some(Language::English), false)
+    });
+    let (after, after_ms) = time_ms(|| {
+        find_matches_for_wantslist(&wantslist, &inventory, Some(Language::English), false)
+    });
+
+    println!(
+        "{} entries: 1 thread {:.2}ms, {} threads {:.2}ms ({:.1}x)",
+        wantslist.len(),
+        before_ms,
+        rayon::current_num_threads(),
+        after_ms,
+        before_ms / after_ms
+    );
+
+    // Parallel matching must not change what is matched, or in which order
+    let ids = |per_entry: &[Vec<d2d_automations::MatchedCard>]| -> Vec<Vec<(String, i32)>> {
+        per_entry
+            .iter()
+            .map(|matches| {
+                matches
+                    .iter()
+                    .map(|m| (m.card.cardmarket_id.clone(), m.quantity))
+                    .collect()
+            })
+            .collect()
+    };
+    assert_eq!(ids(&before), ids(&after));
+    assert!(after[3].is_empty());
+}
+
+#[test]
+fn test_parallel_stock_analysis_on_100k_inventory() {
+    println!("\n=== Benchmark: Bin Analysis, 100k Inventory ===");
+
+    let inventory: Vec<Card> = generate_test_inventory(100_000)
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut card)| {
+            card.location = Some(format!("A-{}-{}-{}", i % 4, (i / 4) % 10, (i / 40) % 50));
+            card
+        })
+        .collect();
+    let analysis = StockAnalysis::new(inventory);
+
+    let (before, before_ms) = time_single_threaded(|| analysis.analyze_with_free_slots(-10_000));
+    let (after, after_ms) = time_ms(|| analysis.analyze_with_free_slots(-10_000));
+
+    println!(
+        "{} bins: 1 thread {:.2}ms, {} threads {:.2}ms ({:.1}x)",
+        after.available_bins.len(),
+        before_ms,
+        rayon::current_num_threads(),
+        after_ms,
+        before_ms / after_ms
+    );
+
+    assert_eq!(before.available_bins, after.available_bins);
+    assert_eq!(after.available_bins.len(), 2000);
+}
+
 #[cfg(test)]
 mod benchmark_helpers {
     use super::*;
@@ -0,0 +1,428 @@
+//! Export and import of alert and watchlist definitions
+//!
+//! A tenant's watchlist, its "watch new sets" switch and its price alerts
+//! can be written as one JSON document ([`AlertConfig`], `inventory_sync
+//! export-alerts` or `GET /api/alerts/export`) and read back into the same
+//! or another tenant, on this server or another one (`import-alerts`,
+//! `POST /api/alerts/import`), so the configuration can be versioned and
+//! moved between machines. Products are referred to by Cardmarket product
+//! ID, which is the same everywhere; the names are for the reader.
+//!
+//! Importing adds to what the tenant has: products it already watches and
+//! alerts with the same product, condition and threshold are left alone
+//! (keeping their own hysteresis and cooldown), and entries for products
+//! missing from the catalog are skipped and reported. Fired alert history
+//! is not part of the export.
+//!
+//! `add-alerts` registers alerts in bulk from a CSV of card names and
+//! thresholds ([`parse_alert_csv`], [`add_alerts_from_csv`]).
+
+use crate::alerts::{add_alert, get_alerts, AlertCondition, AlertRearm};
+use crate::database::{
+    add_to_watchlist, find_products_by_name, get_product_by_id, get_watch_new_sets, get_watchlist,
+    set_watch_new_sets, DbResult, ProductSearchResult, Tenant,
+};
+use crate::error::{InventoryError, Result};
+use mtg_common::{parse_decimal, DecimalLocale};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Version of the export format, raised on incompatible changes
+pub const ALERT_CONFIG_VERSION: u32 = 1;
+
+/// Name matches listed when a card name is ambiguous
+const AMBIGUOUS_SHOWN: usize = 5;
+
+/// A tenant's watchlist and price alerts as exported
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AlertConfig {
+    pub version: u32,
+    /// Tenant the file was exported from
+    #[serde(default)]
+    pub tenant: String,
+    #[serde(default)]
+    pub watch_new_sets: bool,
+    #[serde(default)]
+    pub watchlist: Vec<WatchedProduct>,
+    #[serde(default)]
+    pub alerts: Vec<AlertDefinition>,
+}
+
+/// A followed product
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WatchedProduct {
+    pub id_product: u64,
+    #[serde(default)]
+    pub name: String,
+}
+
+/// An alert without its state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AlertDefinition {
+    pub id_product: u64,
+    #[serde(default)]
+    pub name: String,
+    pub condition: AlertCondition,
+    /// Trend price threshold in EUR
+    pub threshold: f64,
+    #[serde(flatten)]
+    pub rearm: AlertRearm,
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct ImportSummary {
+    /// Products added to the watchlist
+    pub watched: usize,
+    pub alerts_added: usize,
+    /// Watchlist entries and alerts the tenant had already
+    pub unchanged: usize,
+    /// Product IDs not in the catalog; their entries were skipped
+    pub unknown_products: Vec<u64>,
+}
+
+impl ImportSummary {
+    /// One line for the log, e.g. `3 products watched, 12 alerts added, 1
+    /// unchanged, 0 unknown products`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} products watched, {} alerts added, {} unchanged, {} unknown products",
+            self.watched,
+            self.alerts_added,
+            self.unchanged,
+            self.unknown_products.len()
+        )
+    }
+}
+
+/// The tenant's watchlist and alerts, by product name
+pub fn export_alert_config(conn: &Connection, tenant: &Tenant) -> DbResult<AlertConfig> {
+    let watchlist = get_watchlist(conn, tenant.id)?
+        .into_iter()
+        .map(|entry| WatchedProduct {
+            id_product: entry.id_product,
+            name: entry.name,
+        })
+        .collect();
+    let alerts = get_alerts(conn, tenant.id)?
+        .into_iter()
+        .map(|alert| AlertDefinition {
+            id_product: alert.id_product,
+            name: alert.name,
+            condition: alert.condition,
+            threshold: alert.threshold,
+            rearm: alert.rearm,
+        })
+        .collect();
+    Ok(AlertConfig {
+        version: ALERT_CONFIG_VERSION,
+        tenant: tenant.name.clone(),
+        watch_new_sets: get_watch_new_sets(conn, tenant.id)?,
+        watchlist,
+        alerts,
+    })
+}
+
+fn check_threshold(threshold: f64) -> std::result::Result<(), String> {
+    if threshold.is_finite() && threshold > 0.0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "threshold {threshold} is not a positive price in EUR"
+        ))
+    }
+}
+
+/// The threshold and rearm settings of an alert, or why they are unusable
+fn check_alert(threshold: f64, rearm: &AlertRearm) -> std::result::Result<(), String> {
+    check_threshold(threshold)?;
+    rearm.check()
+}
+
+/// Adds to a tenant's alert list unless an identical alert exists.
+/// `existing` holds the (product, condition, threshold) of its alerts and
+/// grows with every alert added. Returns false for a duplicate.
+fn add_new_alert(
+    conn: &Connection,
+    tenant_id: i64,
+    existing: &mut Vec<(u64, AlertCondition, f64)>,
+    id_product: u64,
+    condition: AlertCondition,
+    threshold: f64,
+    rearm: AlertRearm,
+) -> DbResult<bool> {
+    let key = (id_product, condition, threshold);
+    if existing.contains(&key) {
+        return Ok(false);
+    }
+    add_alert(conn, tenant_id, id_product, condition, threshold, rearm)?;
+    existing.push(key);
+    Ok(true)
+}
+
+fn alert_keys(conn: &Connection, tenant_id: i64) -> DbResult<Vec<(u64, AlertCondition, f64)>> {
+    Ok(get_alerts(conn, tenant_id)?
+        .into_iter()
+        .map(|alert| (alert.id_product, alert.condition, alert.threshold))
+        .collect())
+}
+
+/// Adds an exported configuration to a tenant's, in one transaction.
+/// `watch_new_sets` is switched on if the file has it on, never off. A file
+/// of a newer format version or with an invalid threshold, hysteresis or
+/// cooldown is rejected before anything is changed.
+pub fn import_alert_config(
+    conn: &mut Connection,
+    tenant_id: i64,
+    config: &AlertConfig,
+) -> Result<ImportSummary> {
+    if config.version > ALERT_CONFIG_VERSION {
+        return Err(InventoryError::InvalidAlerts(format!(
+            "format version {} is newer than this build's {}",
+            config.version, ALERT_CONFIG_VERSION
+        )));
+    }
+    for alert in &config.alerts {
+        check_alert(alert.threshold, &alert.rearm).map_err(|reason| {
+            InventoryError::InvalidAlerts(format!("product {}: {reason}", alert.id_product))
+        })?;
+    }
+
+    let tx = conn.transaction()?;
+    let mut summary = ImportSummary::default();
+    let mut known = |tx: &Connection, id_product: u64| -> DbResult<bool> {
+        let exists = get_product_by_id(tx, id_product)?.is_some();
+        if !exists && !summary.unknown_products.contains(&id_product) {
+            summary.unknown_products.push(id_product);
+        }
+        Ok(exists)
+    };
+    let mut watched = Vec::new();
+    for product in &config.watchlist {
+        if known(&tx, product.id_product)? {
+            watched.push(add_to_watchlist(&tx, tenant_id, product.id_product)?);
+        }
+    }
+    let mut existing = alert_keys(&tx, tenant_id)?;
+    let mut added = Vec::new();
+    for alert in &config.alerts {
+        if known(&tx, alert.id_product)? {
+            added.push(add_new_alert(
+                &tx,
+                tenant_id,
+                &mut existing,
+                alert.id_product,
+                alert.condition,
+                alert.threshold,
+                alert.rearm,
+            )?);
+        }
+    }
+    if config.watch_new_sets {
+        set_watch_new_sets(&tx, tenant_id, true)?;
+    }
+    tx.commit()?;
+
+    summary.watched = watched.iter().filter(|new| **new).count();
+    summary.alerts_added = added.iter().filter(|new| **new).count();
+    summary.unchanged = watched.len() + added.len() - summary.watched - summary.alerts_added;
+    Ok(summary)
+}
+
+/// One alert of a bulk CSV
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRow {
+    /// Line in the file, for messages
+    pub line: u64,
+    pub name: String,
+    /// Cardmarket expansion code, name or ID narrowing the name down to one
+    /// printing
+    pub expansion: Option<String>,
+    pub condition: AlertCondition,
+    pub threshold: f64,
+    pub rearm: AlertRearm,
+}
+
+fn parse_condition(value: &str) -> Option<AlertCondition> {
+    match value.to_lowercase().as_str() {
+        "" | "above" => Some(AlertCondition::Above),
+        "below" => Some(AlertCondition::Below),
+        _ => None,
+    }
+}
+
+/// Reads a bulk alert CSV. The header row names the columns, in any order
+/// and any case: `name` and `threshold` (EUR) are required, `condition`
+/// (`above`, the default, or `below`), `expansion`, `hysteresis_pct` and
+/// `cooldown_days` (both 0 when empty) are optional. Any malformed row
+/// rejects the whole file, with its line number.
+pub fn parse_alert_csv(input: impl std::io::Read) -> Result<Vec<AlertRow>> {
+    let invalid =
+        |line: u64, reason: String| InventoryError::InvalidAlerts(format!("line {line}: {reason}"));
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(input);
+    let headers = reader
+        .headers()
+        .map_err(|e| invalid(1, e.to_string()))?
+        .clone();
+    let column = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    let (Some(name_col), Some(threshold_col)) = (column("name"), column("threshold")) else {
+        return Err(invalid(
+            1,
+            "the header needs a name and a threshold column".to_string(),
+        ));
+    };
+    let condition_col = column("condition");
+    let expansion_col = column("expansion");
+    let hysteresis_col = column("hysteresis_pct");
+    let cooldown_col = column("cooldown_days");
+
+    let locale = DecimalLocale::from_env();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| {
+            let line = e.position().map_or(0, |p| p.line());
+            invalid(line, e.to_string())
+        })?;
+        let line = record.position().map_or(0, |p| p.line());
+        let field = |col: Option<usize>| col.and_then(|c| record.get(c)).unwrap_or("");
+        let name = field(Some(name_col));
+        if name.is_empty() {
+            return Err(invalid(line, "no card name".to_string()));
+        }
+        let threshold_text = field(Some(threshold_col));
+        let threshold = parse_decimal(threshold_text, locale).ok_or_else(|| {
+            invalid(
+                line,
+                format!("threshold '{threshold_text}' is not a number"),
+            )
+        })?;
+        let hysteresis_text = field(hysteresis_col);
+        let hysteresis_pct: f64 = match hysteresis_text {
+            "" => 0.0,
+            text => parse_decimal(text, locale)
+                .ok_or_else(|| invalid(line, format!("hysteresis '{text}' is not a number")))?,
+        };
+        let cooldown_days: u32 = match field(cooldown_col) {
+            "" => 0,
+            text => text
+                .parse()
+                .map_err(|_| invalid(line, format!("cooldown '{text}' is not a number of days")))?,
+        };
+        let rearm = AlertRearm {
+            hysteresis_pct,
+            cooldown_days,
+        };
+        check_alert(threshold, &rearm).map_err(|reason| invalid(line, reason))?;
+        let condition_text = field(condition_col);
+        let condition = parse_condition(condition_text).ok_or_else(|| {
+            invalid(
+                line,
+                format!("condition '{condition_text}' is neither above nor below"),
+            )
+        })?;
+        let expansion = field(expansion_col);
+        rows.push(AlertRow {
+            line,
+            name: name.to_string(),
+            expansion: (!expansion.is_empty()).then(|| expansion.to_string()),
+            condition,
+            threshold,
+            rearm,
+        });
+    }
+    Ok(rows)
+}
+
+/// What a bulk CSV import did
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkAlertReport {
+    pub added: usize,
+    /// Rows the tenant had an identical alert for
+    pub unchanged: usize,
+    /// Rows whose card name matched no product or several, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// The one product `row` names, or why there isn't one
+fn resolve_row(
+    conn: &Connection,
+    row: &AlertRow,
+) -> DbResult<std::result::Result<ProductSearchResult, String>> {
+    let mut matches = find_products_by_name(
+        conn,
+        &row.name,
+        true,
+        row.expansion.as_deref(),
+        AMBIGUOUS_SHOWN + 1,
+    )?;
+    let card = match &row.expansion {
+        Some(expansion) => format!("'{}' in {}", row.name, expansion),
+        None => format!("'{}'", row.name),
+    };
+    Ok(match matches.len() {
+        0 => Err(format!("line {}: no product named {}", row.line, card)),
+        1 => Ok(matches.remove(0)),
+        n => {
+            let shown: Vec<String> = matches
+                .iter()
+                .take(AMBIGUOUS_SHOWN)
+                .map(|p| match &p.expansion_name {
+                    Some(expansion) => format!("{} ({})", p.id_product, expansion),
+                    None => p.id_product.to_string(),
+                })
+                .collect();
+            let more = if n > AMBIGUOUS_SHOWN { ", …" } else { "" };
+            Err(format!(
+                "line {}: {} matches several products ({}{more}); add an expansion column",
+                row.line,
+                card,
+                shown.join(", ")
+            ))
+        }
+    })
+}
+
+/// Registers the alerts of a parsed bulk CSV for a tenant, in one
+/// transaction. Rows whose name doesn't resolve to exactly one product are
+/// skipped and reported; the rest are added unless the tenant has the same
+/// alert already.
+pub fn add_alerts_from_csv(
+    conn: &mut Connection,
+    tenant_id: i64,
+    rows: &[AlertRow],
+) -> DbResult<BulkAlertReport> {
+    let tx = conn.transaction()?;
+    let mut existing = alert_keys(&tx, tenant_id)?;
+    let mut report = BulkAlertReport::default();
+    for row in rows {
+        let product = match resolve_row(&tx, row)? {
+            Ok(product) => product,
+            Err(reason) => {
+                report.skipped.push(reason);
+                continue;
+            }
+        };
+        if add_new_alert(
+            &tx,
+            tenant_id,
+            &mut existing,
+            product.id_product,
+            row.condition,
+            row.threshold,
+            row.rearm,
+        )? {
+            report.added += 1;
+        } else {
+            report.unchanged += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "alert_config_tests.rs"]
+mod tests;
@@ -0,0 +1,289 @@
+//! Tests for alert_config.
+
+use super::*;
+use crate::cardmarket::{make_test_product, ProductCatalog};
+use crate::database::{
+    create_tenant, get_tenants, init_schema, upsert_expansion_name, upsert_products,
+};
+
+/// Black Lotus in Alpha and Beta, Jace and Mox Pearl, tenants alice and bob
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+    let mut beta_lotus = make_test_product(2, "Black Lotus");
+    beta_lotus.id_expansion = 2;
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        beta_lotus,
+        make_test_product(3, "Jace, the Mind Sculptor"),
+        make_test_product(4, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    upsert_expansion_name(&conn, 1, "Alpha").unwrap();
+    upsert_expansion_name(&conn, 2, "Beta").unwrap();
+    create_tenant(&conn, "alice", "alice-hash").unwrap();
+    create_tenant(&conn, "bob", "bob-hash").unwrap();
+    conn
+}
+
+fn tenant(conn: &Connection, name: &str) -> Tenant {
+    get_tenants(conn)
+        .unwrap()
+        .into_iter()
+        .find(|t| t.name == name)
+        .unwrap()
+}
+
+fn definition(id_product: u64, condition: AlertCondition, threshold: f64) -> AlertDefinition {
+    AlertDefinition {
+        id_product,
+        name: String::new(),
+        condition,
+        threshold,
+        rearm: AlertRearm::default(),
+    }
+}
+
+#[test]
+fn export_can_be_imported_into_another_tenant() {
+    let mut conn = test_db();
+    let (alice, bob) = (tenant(&conn, "alice"), tenant(&conn, "bob"));
+    add_to_watchlist(&conn, alice.id, 4).unwrap();
+    set_watch_new_sets(&conn, alice.id, true).unwrap();
+    add_alert(
+        &conn,
+        alice.id,
+        1,
+        AlertCondition::Above,
+        40.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    let rearm = AlertRearm {
+        hysteresis_pct: 10.0,
+        cooldown_days: 3,
+    };
+    add_alert(&conn, alice.id, 4, AlertCondition::Below, 2.5, rearm).unwrap();
+
+    let exported = export_alert_config(&conn, &alice).unwrap();
+    assert_eq!(exported.version, ALERT_CONFIG_VERSION);
+    assert_eq!(exported.tenant, "alice");
+    assert_eq!(
+        exported.watchlist,
+        vec![WatchedProduct {
+            id_product: 4,
+            name: "Mox Pearl".to_string()
+        }]
+    );
+    assert_eq!(exported.alerts[0].name, "Black Lotus");
+    assert_eq!(exported.alerts[1].rearm, rearm);
+
+    let json = serde_json::to_string(&exported).unwrap();
+    let config: AlertConfig = serde_json::from_str(&json).unwrap();
+    let summary = import_alert_config(&mut conn, bob.id, &config).unwrap();
+    assert_eq!(
+        summary,
+        ImportSummary {
+            watched: 1,
+            alerts_added: 2,
+            ..ImportSummary::default()
+        }
+    );
+
+    let mut copied = export_alert_config(&conn, &bob).unwrap();
+    assert_eq!(copied.tenant, "bob");
+    copied.tenant = "alice".to_string();
+    assert_eq!(copied, exported);
+}
+
+#[test]
+fn import_merges_and_skips_unknown_products() {
+    let mut conn = test_db();
+    let alice = tenant(&conn, "alice");
+    add_alert(
+        &conn,
+        alice.id,
+        1,
+        AlertCondition::Above,
+        40.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    let config: AlertConfig = serde_json::from_str(
+        r#"{"version": 1, "watchlist": [{"id_product": 99}],
+            "alerts": [
+                {"id_product": 1, "condition": "above", "threshold": 40},
+                {"id_product": 1, "condition": "below", "threshold": 10},
+                {"id_product": 99, "condition": "below", "threshold": 1}
+            ]}"#,
+    )
+    .unwrap();
+
+    let summary = import_alert_config(&mut conn, alice.id, &config).unwrap();
+    assert_eq!(summary.alerts_added, 1);
+    assert_eq!(summary.unchanged, 1);
+    assert_eq!(summary.unknown_products, vec![99]);
+    assert_eq!(
+        summary.summary(),
+        "0 products watched, 1 alerts added, 1 unchanged, 1 unknown products"
+    );
+    assert!(!get_watch_new_sets(&conn, alice.id).unwrap());
+
+    // Importing again changes nothing
+    let again = import_alert_config(&mut conn, alice.id, &config).unwrap();
+    assert_eq!(again.alerts_added, 0);
+    assert_eq!(get_alerts(&conn, alice.id).unwrap().len(), 2);
+}
+
+#[test]
+fn invalid_imports_change_nothing() {
+    let mut conn = test_db();
+    let alice = tenant(&conn, "alice");
+    let mut config = AlertConfig {
+        version: ALERT_CONFIG_VERSION,
+        tenant: String::new(),
+        watch_new_sets: true,
+        watchlist: vec![],
+        alerts: vec![
+            definition(1, AlertCondition::Above, 40.0),
+            definition(4, AlertCondition::Below, 0.0),
+        ],
+    };
+    let err = import_alert_config(&mut conn, alice.id, &config).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid alerts: product 4: threshold 0 is not a positive price in EUR"
+    );
+
+    config.alerts.pop();
+    config.version = ALERT_CONFIG_VERSION + 1;
+    assert!(import_alert_config(&mut conn, alice.id, &config).is_err());
+    assert!(get_alerts(&conn, alice.id).unwrap().is_empty());
+    assert!(!get_watch_new_sets(&conn, alice.id).unwrap());
+}
+
+#[test]
+fn alert_csv_is_parsed_by_header() {
+    let csv = "Threshold,Name,expansion,condition,hysteresis_pct,cooldown_days\n\
+               40,Black Lotus,Alpha,\n\
+               \"2,5\",\"Jace, the Mind Sculptor\",,BELOW,,\n\
+               12, Mox Pearl ,,,5,7\n";
+    let rows = parse_alert_csv(csv.as_bytes()).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            AlertRow {
+                line: 2,
+                name: "Black Lotus".to_string(),
+                expansion: Some("Alpha".to_string()),
+                condition: AlertCondition::Above,
+                threshold: 40.0,
+                rearm: AlertRearm::default(),
+            },
+            AlertRow {
+                line: 3,
+                name: "Jace, the Mind Sculptor".to_string(),
+                expansion: None,
+                condition: AlertCondition::Below,
+                threshold: 2.5,
+                rearm: AlertRearm::default(),
+            },
+            AlertRow {
+                line: 4,
+                name: "Mox Pearl".to_string(),
+                expansion: None,
+                condition: AlertCondition::Above,
+                threshold: 12.0,
+                rearm: AlertRearm {
+                    hysteresis_pct: 5.0,
+                    cooldown_days: 7,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn alert_csv_threshold_may_group_thousands() {
+    let csv = "name,threshold\nBlack Lotus,\"1.234,56\"\nMox Pearl,\"1,234.56\"\n";
+    let thresholds: Vec<f64> = parse_alert_csv(csv.as_bytes())
+        .unwrap()
+        .iter()
+        .map(|row| row.threshold)
+        .collect();
+    assert_eq!(thresholds, vec![1234.56, 1234.56]);
+}
+
+#[test]
+fn alert_csv_hysteresis_uses_the_decimal_parser() {
+    let csv = "name,threshold,hysteresis_pct\nMox Pearl,12,\"7,5\"\n";
+    let rows = parse_alert_csv(csv.as_bytes()).unwrap();
+    assert_eq!(rows[0].rearm.hysteresis_pct, 7.5);
+}
+
+#[test]
+fn malformed_alert_csv_is_rejected_with_its_line() {
+    let error = |csv: &str| parse_alert_csv(csv.as_bytes()).unwrap_err().to_string();
+    assert_eq!(
+        error("card,price\nBlack Lotus,40\n"),
+        "Invalid alerts: line 1: the header needs a name and a threshold column"
+    );
+    assert_eq!(
+        error("name,threshold\nBlack Lotus,40\nMox Pearl,cheap\n"),
+        "Invalid alerts: line 3: threshold 'cheap' is not a number"
+    );
+    assert_eq!(
+        error("name,threshold\nMox Pearl,-1\n"),
+        "Invalid alerts: line 2: threshold -1 is not a positive price in EUR"
+    );
+    assert_eq!(
+        error("name,threshold,condition\nMox Pearl,1,sideways\n"),
+        "Invalid alerts: line 2: condition 'sideways' is neither above nor below"
+    );
+    assert_eq!(
+        error("name,threshold,hysteresis_pct\nMox Pearl,1,120\n"),
+        "Invalid alerts: line 2: hysteresis 120% is not a percentage from 0 up to 100"
+    );
+    assert_eq!(
+        error("name,threshold,cooldown_days\nMox Pearl,1,a week\n"),
+        "Invalid alerts: line 2: cooldown 'a week' is not a number of days"
+    );
+}
+
+#[test]
+fn bulk_alerts_resolve_names_and_skip_ambiguous_ones() {
+    let mut conn = test_db();
+    let alice = tenant(&conn, "alice");
+    add_alert(
+        &conn,
+        alice.id,
+        4,
+        AlertCondition::Above,
+        12.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    let csv = "name,threshold,expansion\n\
+               Black Lotus,40,\n\
+               black lotus,40,Beta\n\
+               Mox Pearl,12,\n\
+               Mox Pearl,12,\n\
+               Jace,5,\n";
+    let rows = parse_alert_csv(csv.as_bytes()).unwrap();
+
+    let report = add_alerts_from_csv(&mut conn, alice.id, &rows).unwrap();
+    assert_eq!(report.added, 1);
+    assert_eq!(report.unchanged, 2);
+    assert_eq!(
+        report.skipped,
+        vec![
+            "line 2: 'Black Lotus' matches several products (1 (Alpha), 2 (Beta)); \
+             add an expansion column"
+                .to_string(),
+            "line 6: no product named 'Jace'".to_string(),
+        ]
+    );
+    let alerts = get_alerts(&conn, alice.id).unwrap();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].id_product, 2);
+}
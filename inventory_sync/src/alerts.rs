@@ -0,0 +1,395 @@
+//! Price alerts
+//!
+//! A tenant registers a product with a threshold on its Cardmarket trend
+//! price — "rises above 40 €" or "drops below 2 €" — in the `alerts` table.
+//! After each daily price import the sync evaluates every alert against the
+//! new prices ([`evaluate_alerts`]); the ones that fired are logged and
+//! recorded in `triggered_alerts`, one row per alert and price date, and are
+//! served to their tenant under `/api/alerts/triggered`.
+//!
+//! An alert fires when the price crosses its threshold: the trend of the
+//! imported day meets the condition and the product's previous trend did
+//! not (or there is none). A price that stays above the threshold does not
+//! fire again every day; it has to fall back first.
+//!
+//! Two settings per alert ([`AlertRearm`]) keep a trend that hovers around
+//! the threshold from firing over and over: with a hysteresis the trend has
+//! to fall back that many percent past the threshold after the alert fired
+//! before a new crossing counts, and within the cooldown after firing the
+//! alert stays quiet whatever the trend does.
+
+use crate::database::DbResult;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+/// Which side of the threshold an alert watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertCondition {
+    /// The trend rises above the threshold
+    Above,
+    /// The trend drops below the threshold
+    Below,
+}
+
+impl AlertCondition {
+    /// Value stored in `alerts.condition`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertCondition::Above => "above",
+            AlertCondition::Below => "below",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "below" => AlertCondition::Below,
+            _ => AlertCondition::Above,
+        }
+    }
+
+    /// Whether `trend` is on the alerting side of `threshold`
+    pub fn is_met(self, trend: f64, threshold: f64) -> bool {
+        match self {
+            AlertCondition::Above => trend > threshold,
+            AlertCondition::Below => trend < threshold,
+        }
+    }
+
+    /// Whether moving from `previous` to `trend` crosses `threshold`; without
+    /// a previous price, meeting the condition is enough
+    pub fn crossed(self, previous: Option<f64>, trend: f64, threshold: f64) -> bool {
+        self.is_met(trend, threshold) && !previous.is_some_and(|p| self.is_met(p, threshold))
+    }
+
+    /// Whether `trend` is back `hysteresis_pct` percent past `threshold` on
+    /// the quiet side, which rearms a fired alert
+    pub fn has_reset(self, trend: f64, threshold: f64, hysteresis_pct: f64) -> bool {
+        let margin = threshold * hysteresis_pct / 100.0;
+        match self {
+            AlertCondition::Above => trend <= threshold - margin,
+            AlertCondition::Below => trend >= threshold + margin,
+        }
+    }
+}
+
+/// When a fired alert may fire again
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AlertRearm {
+    /// Percent of the threshold the trend has to fall back past it before
+    /// the alert can fire again; 0 rearms it once the condition no longer
+    /// holds
+    #[serde(default)]
+    pub hysteresis_pct: f64,
+    /// Days after firing during which the alert does not fire again
+    #[serde(default)]
+    pub cooldown_days: u32,
+}
+
+impl AlertRearm {
+    /// Why the settings are unusable, if they are
+    pub fn check(&self) -> Result<(), String> {
+        if self.hysteresis_pct.is_finite() && (0.0..100.0).contains(&self.hysteresis_pct) {
+            Ok(())
+        } else {
+            Err(format!(
+                "hysteresis {}% is not a percentage from 0 up to 100",
+                self.hysteresis_pct
+            ))
+        }
+    }
+
+    /// Whether an alert last fired on `last_triggered` is still cooling
+    /// down on `price_date` (both `YYYY-MM-DD`)
+    pub fn cooling_down(&self, last_triggered: &str, price_date: &str) -> bool {
+        let parse = |date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d");
+        match (parse(last_triggered), parse(price_date)) {
+            (Ok(last), Ok(date)) => (date - last).num_days() < i64::from(self.cooldown_days),
+            _ => false,
+        }
+    }
+}
+
+/// A registered alert with the product's latest trend
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct PriceAlert {
+    pub id: i64,
+    pub id_product: u64,
+    pub name: String,
+    pub condition: AlertCondition,
+    /// Trend price threshold in EUR
+    pub threshold: f64,
+    #[serde(flatten)]
+    pub rearm: AlertRearm,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub created_at: String,
+    pub price_date: Option<String>,
+    pub trend: Option<f64>,
+    /// Price date the alert last fired on
+    pub last_triggered: Option<String>,
+}
+
+/// An alert that fired on a price date
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct TriggeredAlert {
+    pub alert_id: i64,
+    #[serde(skip)]
+    pub tenant_id: i64,
+    pub id_product: u64,
+    pub name: String,
+    pub condition: AlertCondition,
+    pub threshold: f64,
+    /// `YYYY-MM-DD`
+    pub price_date: String,
+    pub trend: f64,
+    /// Trend of the product's previous price date
+    pub previous_trend: Option<f64>,
+    /// Change from the previous trend in percent
+    pub change_pct: Option<f64>,
+}
+
+impl TriggeredAlert {
+    /// Log line, e.g. `Black Lotus trend 12.00 € rose above 10.00 € (was 9.50 €)`
+    pub fn describe(&self) -> String {
+        let verb = match self.condition {
+            AlertCondition::Above => "rose above",
+            AlertCondition::Below => "dropped below",
+        };
+        let mut text = format!(
+            "{} trend {:.2} € {verb} {:.2} €",
+            self.name, self.trend, self.threshold
+        );
+        if let Some(previous) = self.previous_trend {
+            text.push_str(&format!(" (was {previous:.2} €)"));
+        }
+        text
+    }
+}
+
+fn change_pct(previous: Option<f64>, trend: f64) -> Option<f64> {
+    previous
+        .filter(|p| *p > 0.0)
+        .map(|p| (trend - p) / p * 100.0)
+}
+
+fn triggered_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TriggeredAlert> {
+    let condition: String = row.get(4)?;
+    let trend: f64 = row.get(7)?;
+    let previous_trend: Option<f64> = row.get(8)?;
+    Ok(TriggeredAlert {
+        alert_id: row.get(0)?,
+        tenant_id: row.get(1)?,
+        id_product: row.get(2)?,
+        name: row.get(3)?,
+        condition: AlertCondition::from_db(&condition),
+        threshold: row.get(5)?,
+        price_date: row.get(6)?,
+        trend,
+        previous_trend,
+        change_pct: change_pct(previous_trend, trend),
+    })
+}
+
+/// Register an alert for a tenant. The product must exist.
+pub fn add_alert(
+    conn: &Connection,
+    tenant_id: i64,
+    id_product: u64,
+    condition: AlertCondition,
+    threshold: f64,
+    rearm: AlertRearm,
+) -> DbResult<PriceAlert> {
+    let id: i64 = conn.query_row(
+        "INSERT INTO alerts
+             (tenant_id, id_product, condition, threshold, hysteresis_pct, cooldown_days)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6) RETURNING id",
+        params![
+            tenant_id,
+            id_product,
+            condition.as_str(),
+            threshold,
+            rearm.hysteresis_pct,
+            rearm.cooldown_days
+        ],
+        |row| row.get(0),
+    )?;
+    get_alerts(conn, tenant_id)?
+        .into_iter()
+        .find(|alert| alert.id == id)
+        .ok_or(rusqlite::Error::QueryReturnedNoRows)
+}
+
+/// Delete one of a tenant's alerts with its history. Returns false if the
+/// tenant has no alert with that ID.
+pub fn remove_alert(conn: &mut Connection, tenant_id: i64, alert_id: i64) -> DbResult<bool> {
+    let tx = conn.transaction()?;
+    let deleted = tx.execute(
+        "DELETE FROM alerts WHERE id = ?1 AND tenant_id = ?2",
+        params![alert_id, tenant_id],
+    )?;
+    if deleted > 0 {
+        tx.execute(
+            "DELETE FROM triggered_alerts WHERE alert_id = ?1",
+            params![alert_id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(deleted > 0)
+}
+
+/// A tenant's alerts, by product name (blank while the product is not in
+/// the catalog)
+pub fn get_alerts(conn: &Connection, tenant_id: i64) -> DbResult<Vec<PriceAlert>> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.id_product, COALESCE(p.name, ''), a.condition, a.threshold,
+             a.created_at, ph.price_date, ph.trend,
+             (SELECT MAX(t.price_date) FROM triggered_alerts t WHERE t.alert_id = a.id),
+             a.hysteresis_pct, a.cooldown_days
+         FROM alerts a
+         LEFT JOIN products p ON p.id_product = a.id_product
+         LEFT JOIN price_history ph ON ph.id_product = a.id_product
+             AND ph.price_date = (
+                 SELECT MAX(price_date) FROM price_history WHERE id_product = a.id_product
+             )
+         WHERE a.tenant_id = ?1
+         ORDER BY p.name, a.id",
+    )?;
+    let rows = stmt.query_map(params![tenant_id], |row| {
+        let condition: String = row.get(3)?;
+        Ok(PriceAlert {
+            id: row.get(0)?,
+            id_product: row.get(1)?,
+            name: row.get(2)?,
+            condition: AlertCondition::from_db(&condition),
+            threshold: row.get(4)?,
+            rearm: AlertRearm {
+                hysteresis_pct: row.get(9)?,
+                cooldown_days: row.get(10)?,
+            },
+            created_at: row.get(5)?,
+            price_date: row.get(6)?,
+            trend: row.get(7)?,
+            last_triggered: row.get(8)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Whether an alert that last fired on `last_triggered` has been rearmed by
+/// a trend back past its hysteresis level before `price_date`
+fn rearmed_since(
+    conn: &Connection,
+    alert: &TriggeredAlert,
+    hysteresis_pct: f64,
+    last_triggered: &str,
+) -> DbResult<bool> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT trend FROM price_history
+         WHERE id_product = ?1 AND price_date > ?2 AND price_date < ?3
+             AND trend IS NOT NULL",
+    )?;
+    let trends = stmt.query_map(
+        params![alert.id_product, last_triggered, alert.price_date],
+        |row| row.get::<_, f64>(0),
+    )?;
+    for trend in trends {
+        if alert
+            .condition
+            .has_reset(trend?, alert.threshold, hysteresis_pct)
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Check every alert against the trends of `price_date` and record the ones
+/// that fired. An alert that fired before only fires again once its
+/// cooldown is over and the trend has been back past its hysteresis level
+/// since. Returns the newly fired alerts; evaluating a date again records
+/// nothing twice.
+pub fn evaluate_alerts(conn: &mut Connection, price_date: &str) -> DbResult<Vec<TriggeredAlert>> {
+    let tx = conn.transaction()?;
+    let candidates = {
+        let mut stmt = tx.prepare(
+            "SELECT a.id, a.tenant_id, a.id_product, p.name, a.condition, a.threshold,
+                 ph.price_date, ph.trend,
+                 (SELECT prev.trend FROM price_history prev
+                  WHERE prev.id_product = a.id_product AND prev.price_date < ?1
+                  ORDER BY prev.price_date DESC LIMIT 1),
+                 a.hysteresis_pct, a.cooldown_days,
+                 (SELECT MAX(t.price_date) FROM triggered_alerts t
+                  WHERE t.alert_id = a.id AND t.price_date < ?1)
+             FROM alerts a
+             JOIN products p ON p.id_product = a.id_product
+             JOIN price_history ph ON ph.id_product = a.id_product AND ph.price_date = ?1
+             WHERE ph.trend IS NOT NULL
+             ORDER BY a.id",
+        )?;
+        let rows = stmt.query_map(params![price_date], |row| {
+            let rearm = AlertRearm {
+                hysteresis_pct: row.get(9)?,
+                cooldown_days: row.get(10)?,
+            };
+            let last_triggered: Option<String> = row.get(11)?;
+            Ok((triggered_from_row(row)?, rearm, last_triggered))
+        })?;
+        rows.collect::<DbResult<Vec<_>>>()?
+    };
+
+    let mut fired = Vec::new();
+    for (alert, rearm, last_triggered) in candidates {
+        if !alert
+            .condition
+            .crossed(alert.previous_trend, alert.trend, alert.threshold)
+        {
+            continue;
+        }
+        if let Some(last) = &last_triggered {
+            if rearm.cooling_down(last, &alert.price_date)
+                || rearm.hysteresis_pct > 0.0
+                    && !rearmed_since(&tx, &alert, rearm.hysteresis_pct, last)?
+            {
+                continue;
+            }
+        }
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO triggered_alerts (alert_id, price_date, trend, previous_trend)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                alert.alert_id,
+                alert.price_date,
+                alert.trend,
+                alert.previous_trend
+            ],
+        )?;
+        if inserted > 0 {
+            fired.push(alert);
+        }
+    }
+    tx.commit()?;
+    Ok(fired)
+}
+
+/// A tenant's fired alerts from `since` (YYYY-MM-DD) on, newest first
+pub fn get_triggered_alerts(
+    conn: &Connection,
+    tenant_id: i64,
+    since: &str,
+) -> DbResult<Vec<TriggeredAlert>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.alert_id, a.tenant_id, a.id_product, p.name, a.condition, a.threshold,
+             t.price_date, t.trend, t.previous_trend
+         FROM triggered_alerts t
+         JOIN alerts a ON a.id = t.alert_id
+         JOIN products p ON p.id_product = a.id_product
+         WHERE a.tenant_id = ?1 AND t.price_date >= ?2
+         ORDER BY t.price_date DESC, p.name, t.alert_id",
+    )?;
+    let rows = stmt.query_map(params![tenant_id, since], triggered_from_row)?;
+    rows.collect()
+}
+
+#[cfg(test)]
+#[path = "alerts_tests.rs"]
+mod tests;
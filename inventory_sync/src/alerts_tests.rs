@@ -0,0 +1,211 @@
+//! Tests for alerts.
+
+use super::*;
+use crate::cardmarket::{make_test_price_entry, make_test_product, PriceGuide, ProductCatalog};
+use crate::database::{
+    create_tenant, delete_tenant, init_schema, insert_price_history, upsert_products,
+};
+
+/// Black Lotus and Mox Pearl with one trend per day
+fn test_db(days: &[(&str, f64, f64)]) -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (date, lotus, mox) in days {
+        let guide = PriceGuide::from_entries(
+            vec![
+                make_test_price_entry(1, Some(*lotus)),
+                make_test_price_entry(2, Some(*mox)),
+            ],
+            &format!("{date}T10:00:00+0100"),
+        );
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    create_tenant(&conn, "alice", "hash").unwrap();
+    conn
+}
+
+#[test]
+fn conditions_fire_on_crossing_only() {
+    use AlertCondition::{Above, Below};
+    assert!(Above.crossed(Some(9.0), 11.0, 10.0));
+    assert!(Above.crossed(None, 11.0, 10.0));
+    assert!(!Above.crossed(Some(10.5), 11.0, 10.0), "already above");
+    assert!(!Above.crossed(Some(9.0), 10.0, 10.0), "not above yet");
+    assert!(Below.crossed(Some(3.0), 1.5, 2.0));
+    assert!(!Below.crossed(Some(1.0), 1.5, 2.0));
+    assert!(Above.has_reset(9.0, 10.0, 10.0));
+    assert!(!Above.has_reset(9.5, 10.0, 10.0));
+    assert!(Below.has_reset(2.2, 2.0, 10.0));
+    assert!(Below.has_reset(2.0, 2.0, 0.0));
+    let cooldown = AlertRearm {
+        hysteresis_pct: 0.0,
+        cooldown_days: 3,
+    };
+    assert!(cooldown.cooling_down("2026-02-27", "2026-03-01"));
+    assert!(!cooldown.cooling_down("2026-02-26", "2026-03-01"));
+    assert!(AlertRearm::default().check().is_ok());
+    assert!(AlertRearm {
+        hysteresis_pct: 100.0,
+        cooldown_days: 0
+    }
+    .check()
+    .is_err());
+}
+
+#[test]
+fn evaluation_records_fired_alerts_once() {
+    let mut conn = test_db(&[("2026-02-01", 8.0, 3.0), ("2026-02-02", 12.0, 2.5)]);
+    let spike = add_alert(
+        &conn,
+        1,
+        1,
+        AlertCondition::Above,
+        10.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    add_alert(
+        &conn,
+        1,
+        2,
+        AlertCondition::Below,
+        2.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+
+    let fired = evaluate_alerts(&mut conn, "2026-02-02").unwrap();
+
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].alert_id, spike.id);
+    assert_eq!(fired[0].previous_trend, Some(8.0));
+    assert_eq!(fired[0].change_pct, Some(50.0));
+    assert_eq!(
+        fired[0].describe(),
+        "Black Lotus trend 12.00 € rose above 10.00 € (was 8.00 €)"
+    );
+    assert!(evaluate_alerts(&mut conn, "2026-02-02").unwrap().is_empty());
+    assert_eq!(
+        get_triggered_alerts(&conn, 1, "2026-02-01").unwrap().len(),
+        1
+    );
+    assert_eq!(
+        get_alerts(&conn, 1).unwrap()[0].last_triggered.as_deref(),
+        Some("2026-02-02")
+    );
+}
+
+#[test]
+fn price_staying_above_does_not_fire_again() {
+    let mut conn = test_db(&[
+        ("2026-02-01", 8.0, 3.0),
+        ("2026-02-02", 12.0, 3.0),
+        ("2026-02-03", 13.0, 3.0),
+    ]);
+    add_alert(
+        &conn,
+        1,
+        1,
+        AlertCondition::Above,
+        10.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+
+    assert_eq!(evaluate_alerts(&mut conn, "2026-02-02").unwrap().len(), 1);
+    assert!(evaluate_alerts(&mut conn, "2026-02-03").unwrap().is_empty());
+}
+
+#[test]
+fn hysteresis_and_cooldown_damp_an_oscillating_trend() {
+    let days = [
+        ("2026-02-01", 9.5, 3.0),
+        ("2026-02-02", 10.5, 3.0),
+        ("2026-02-03", 9.8, 3.0),
+        ("2026-02-04", 10.4, 3.0),
+        ("2026-02-05", 8.9, 3.0),
+        ("2026-02-06", 10.2, 3.0),
+    ];
+    let mut conn = test_db(&days);
+    let add = |hysteresis_pct: f64, cooldown_days: u32| {
+        let rearm = AlertRearm {
+            hysteresis_pct,
+            cooldown_days,
+        };
+        add_alert(&conn, 1, 1, AlertCondition::Above, 10.0, rearm)
+            .unwrap()
+            .id
+    };
+    let plain = add(0.0, 0);
+    // Rearms only once the trend is back at 9.00 € or below
+    let damped = add(10.0, 0);
+    let cooling = add(0.0, 5);
+
+    let mut fired: Vec<(i64, &str)> = Vec::new();
+    for (date, _, _) in days {
+        for alert in evaluate_alerts(&mut conn, date).unwrap() {
+            fired.push((alert.alert_id, date));
+        }
+    }
+    let dates = |id: i64| -> Vec<&str> {
+        fired
+            .iter()
+            .filter(|(alert, _)| *alert == id)
+            .map(|(_, date)| *date)
+            .collect()
+    };
+
+    assert_eq!(dates(plain), ["2026-02-02", "2026-02-04", "2026-02-06"]);
+    assert_eq!(dates(damped), ["2026-02-02", "2026-02-06"]);
+    assert_eq!(dates(cooling), ["2026-02-02"]);
+    let alerts = get_alerts(&conn, 1).unwrap();
+    assert_eq!(alerts[1].rearm.hysteresis_pct, 10.0);
+    assert_eq!(alerts[2].rearm.cooldown_days, 5);
+}
+
+#[test]
+fn removing_alerts_and_tenants_drops_their_history() {
+    let mut conn = test_db(&[("2026-02-01", 12.0, 3.0)]);
+    let alert = add_alert(
+        &conn,
+        1,
+        1,
+        AlertCondition::Above,
+        10.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    evaluate_alerts(&mut conn, "2026-02-01").unwrap();
+
+    assert!(
+        !remove_alert(&mut conn, 2, alert.id).unwrap(),
+        "another tenant's"
+    );
+    assert!(remove_alert(&mut conn, 1, alert.id).unwrap());
+    let count = |conn: &Connection, table: &str| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    };
+    assert_eq!(count(&conn, "triggered_alerts"), 0);
+
+    add_alert(
+        &conn,
+        1,
+        1,
+        AlertCondition::Below,
+        20.0,
+        AlertRearm::default(),
+    )
+    .unwrap();
+    evaluate_alerts(&mut conn, "2026-02-01").unwrap();
+    delete_tenant(&mut conn, 1).unwrap();
+    assert_eq!(count(&conn, "alerts"), 0);
+    assert_eq!(count(&conn, "triggered_alerts"), 0);
+}
@@ -0,0 +1,643 @@
+//! Off-site backups of the price history database
+//!
+//! With a backup target configured, the first sync check of a day that finds
+//! today's prices in the database uploads a snapshot of it: a consistent copy
+//! made with `VACUUM INTO` while the daemon keeps running, gzipped, named
+//! `inventory_sync-YYYY-MM-DD.db.gz`. Afterwards all but the newest
+//! [`BackupOptions::keep`] snapshots are deleted from the target.
+//!
+//! The database lock is only held for the `VACUUM INTO`; compressing and
+//! uploading work on the copy, and the upload streams it from disk.
+//!
+//! Two kinds of targets are supported:
+//!
+//! - **WebDAV** (`https://…`, e.g. a Nextcloud folder): `PUT` with basic
+//!   auth; the folder is created if it does not exist yet.
+//! - **S3-compatible storage** (`s3://bucket/prefix`, e.g. AWS, Backblaze
+//!   B2, MinIO): path-style requests to the configured endpoint, signed with
+//!   AWS Signature Version 4.
+//!
+//! Like the sync itself, a failed backup is logged and retried on the next
+//! check, since no snapshot of the day exists on the target yet.
+
+use crate::database::{has_price_data_for_today, today_date};
+use crate::error::{InventoryError, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use mtg_common::config::InventorySyncConfig;
+use reqwest::{Client, Method, RequestBuilder, Response};
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// File name prefix of the snapshots; the rest is `YYYY-MM-DD.db.gz`
+const NAME_PREFIX: &str = "inventory_sync-";
+const NAME_SUFFIX: &str = ".db.gz";
+/// Request timeout, long enough to upload a large database on a slow line
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(3600);
+/// SHA-256 of an empty request body, signed for S3 requests without one
+const EMPTY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+/// Region used to sign S3 requests when none is configured
+pub const DEFAULT_S3_REGION: &str = "us-east-1";
+
+/// Where snapshots are uploaded to
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackupTarget {
+    /// A WebDAV folder; `url` ends with `/`
+    WebDav {
+        url: String,
+        user: Option<String>,
+        password: Option<String>,
+    },
+    /// A bucket of S3-compatible storage, addressed path-style at `endpoint`
+    S3 {
+        endpoint: String,
+        region: String,
+        bucket: String,
+        /// Key prefix, empty or ending with `/`
+        prefix: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+/// Backup settings of the daemon
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupOptions {
+    pub target: BackupTarget,
+    /// Snapshots kept on the target; 0 keeps all of them
+    pub keep: usize,
+    /// Local file the snapshot is written to before the upload
+    pub snapshot_path: PathBuf,
+}
+
+impl BackupOptions {
+    /// The backup settings of `config`, `None` without a `backup_url`.
+    /// `database` is the path of the database being backed up.
+    pub fn from_config(config: &InventorySyncConfig, database: &Path) -> Result<Option<Self>> {
+        let Some(url) = config
+            .backup_url
+            .as_deref()
+            .filter(|u| !u.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+        let target = match url.trim().strip_prefix("s3://") {
+            Some(location) => {
+                let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+                let prefix = prefix.trim_matches('/');
+                let required = |value: &Option<String>, name: &str| {
+                    value
+                        .clone()
+                        .filter(|v| !v.is_empty())
+                        .ok_or_else(|| InventoryError::Config(format!("S3 backups need {name}")))
+                };
+                if bucket.is_empty() {
+                    return Err(InventoryError::Config(format!(
+                        "backup URL {url} has no bucket"
+                    )));
+                }
+                BackupTarget::S3 {
+                    endpoint: required(&config.backup_s3_endpoint, "backup_s3_endpoint")?
+                        .trim_end_matches('/')
+                        .to_string(),
+                    region: config
+                        .backup_s3_region
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_S3_REGION.to_string()),
+                    bucket: bucket.to_string(),
+                    prefix: if prefix.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{prefix}/")
+                    },
+                    access_key: required(&config.backup_user, "backup_user (access key)")?,
+                    secret_key: required(&config.backup_password, "backup_password (secret key)")?,
+                }
+            }
+            None if url.starts_with("http://") || url.starts_with("https://") => {
+                BackupTarget::WebDav {
+                    url: format!("{}/", url.trim().trim_end_matches('/')),
+                    user: config.backup_user.clone(),
+                    password: config.backup_password.clone(),
+                }
+            }
+            None => {
+                return Err(InventoryError::Config(format!(
+                    "backup URL must start with s3://, http:// or https://: {url}"
+                )))
+            }
+        };
+        Ok(Some(Self {
+            target,
+            keep: config.backup_keep as usize,
+            snapshot_path: database.with_extension("backup.db"),
+        }))
+    }
+}
+
+/// Snapshot name for a price date
+pub fn backup_name(date: &str) -> String {
+    format!("{NAME_PREFIX}{date}{NAME_SUFFIX}")
+}
+
+/// The date of a snapshot name, `None` for other files
+fn backup_date(name: &str) -> Option<&str> {
+    let date = name.strip_prefix(NAME_PREFIX)?.strip_suffix(NAME_SUFFIX)?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .is_ok()
+        .then_some(date)
+}
+
+/// Snapshots beyond the newest `keep`, which retention deletes; none when
+/// `keep` is 0. Other files on the target are never touched.
+pub fn expired_backups(names: &[String], keep: usize) -> Vec<String> {
+    if keep == 0 {
+        return Vec::new();
+    }
+    let mut backups: Vec<&String> = names.iter().filter(|n| backup_date(n).is_some()).collect();
+    // YYYY-MM-DD sorts chronologically
+    backups.sort_by(|a, b| b.cmp(a));
+    backups.into_iter().skip(keep).cloned().collect()
+}
+
+/// Size and SHA-256 of a gzipped snapshot, needed to upload it as a stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Writes a gzipped, consistent copy of the database to `path`.
+pub fn write_snapshot(conn: &Connection, path: &Path) -> Result<Snapshot> {
+    copy_database(conn, path)?;
+    compress_snapshot(path)
+}
+
+/// The uncompressed copy [`copy_database`] writes for `path`
+fn plain_copy(path: &Path) -> PathBuf {
+    path.with_extension("tmp")
+}
+
+/// Writes a consistent, uncompressed copy of the database next to `path`;
+/// the only step that needs the database.
+pub fn copy_database(conn: &Connection, path: &Path) -> Result<()> {
+    let plain = plain_copy(path);
+    let _ = std::fs::remove_file(&plain);
+    conn.execute("VACUUM INTO ?1", [plain.to_string_lossy()])?;
+    Ok(())
+}
+
+/// Gzips the copy [`copy_database`] wrote into `path` and removes the copy.
+pub fn compress_snapshot(path: &Path) -> Result<Snapshot> {
+    let plain = plain_copy(path);
+    let compressed = (|| {
+        let mut input = std::fs::File::open(&plain)?;
+        let mut encoder = flate2::write::GzEncoder::new(
+            HashingWriter::new(std::fs::File::create(path)?),
+            flate2::Compression::default(),
+        );
+        std::io::copy(&mut input, &mut encoder)?;
+        let mut output = encoder.finish()?;
+        output.flush()?;
+        Ok::<_, std::io::Error>(output.finish())
+    })();
+    let _ = std::fs::remove_file(&plain);
+    Ok(compressed?)
+}
+
+/// Counts and hashes what passes through to the inner writer
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    fn finish(self) -> Snapshot {
+        Snapshot {
+            size: self.size,
+            sha256: hex(&self.hasher.finalize()),
+        }
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Uploads today's snapshot unless the target already has one, then applies
+/// the retention. Skipped until today's prices are in the database.
+pub async fn run_backup(db: &Arc<Mutex<Connection>>, options: &BackupOptions) {
+    match has_price_data_for_today(&db.lock().unwrap()) {
+        Ok(true) => {}
+        Ok(false) => {
+            log::debug!("No prices for today yet, postponing the backup");
+            return;
+        }
+        Err(e) => {
+            log::error!(
+                "Failed to check for today's prices before the backup: {}",
+                e
+            );
+            return;
+        }
+    }
+    if let Err(e) = backup(db, options, &today_date()).await {
+        log::error!("Backup failed: {}", e);
+    }
+}
+
+async fn backup(db: &Arc<Mutex<Connection>>, options: &BackupOptions, date: &str) -> Result<()> {
+    let client = mtg_common::http::client_builder(UPLOAD_TIMEOUT)?.build()?;
+    let target = &options.target;
+    let existing = target.list(&client).await?;
+    let name = backup_name(date);
+    if !existing.contains(&name) {
+        let path = options.snapshot_path.clone();
+        copy_database(&db.lock().unwrap(), &path)?;
+        // Gzipping a large copy takes a while; keep it off the runtime
+        let snapshot = tokio::task::spawn_blocking(move || compress_snapshot(&path))
+            .await
+            .map_err(std::io::Error::other)?;
+        let uploaded = match snapshot {
+            Ok(snapshot) => target
+                .upload(&client, &name, &options.snapshot_path, &snapshot)
+                .await
+                .map(|()| snapshot),
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_file(&options.snapshot_path);
+        let snapshot = uploaded?;
+        log::info!(
+            "Uploaded backup {} ({:.1} MB) to {}",
+            name,
+            snapshot.size as f64 / 1_000_000.0,
+            target
+        );
+    }
+
+    let mut names = existing;
+    if !names.contains(&name) {
+        names.push(name);
+    }
+    for expired in expired_backups(&names, options.keep) {
+        match target.delete(&client, &expired).await {
+            Ok(()) => log::info!("Deleted old backup {}", expired),
+            Err(e) => log::warn!("Failed to delete old backup {}: {}", expired, e),
+        }
+    }
+    Ok(())
+}
+
+impl std::fmt::Display for BackupTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackupTarget::WebDav { url, .. } => write!(f, "{url}"),
+            BackupTarget::S3 { bucket, prefix, .. } => write!(f, "s3://{bucket}/{prefix}"),
+        }
+    }
+}
+
+impl BackupTarget {
+    /// Names of the snapshots on the target
+    pub async fn list(&self, client: &Client) -> Result<Vec<String>> {
+        let names = match self {
+            BackupTarget::WebDav { url, .. } => {
+                let propfind = Method::from_bytes(b"PROPFIND").expect("valid method");
+                let response = self
+                    .webdav(client, propfind, url)
+                    .header("Depth", "1")
+                    .header("Content-Type", "application/xml")
+                    .body(r#"<?xml version="1.0"?><propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>"#)
+                    .send()
+                    .await?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    let mkcol = Method::from_bytes(b"MKCOL").expect("valid method");
+                    check(self.webdav(client, mkcol, url).send().await?)?;
+                    log::info!("Created backup folder {}", url);
+                    return Ok(Vec::new());
+                }
+                let xml = check(response)?.text().await?;
+                xml_values(&xml, "href")
+                    .into_iter()
+                    .filter_map(|href| {
+                        let last = href.trim_end_matches('/').rsplit('/').next()?;
+                        urlencoding::decode(last).ok().map(|name| name.into_owned())
+                    })
+                    .collect::<Vec<_>>()
+            }
+            BackupTarget::S3 { prefix, .. } => {
+                // Listings return at most 1000 keys per page
+                let mut names = Vec::new();
+                let mut continuation = None;
+                loop {
+                    let mut query = vec![
+                        ("list-type", "2".to_string()),
+                        ("prefix", format!("{prefix}{NAME_PREFIX}")),
+                    ];
+                    if let Some(token) = continuation.take() {
+                        query.push(("continuation-token", token));
+                    }
+                    let response = self
+                        .s3(client, Method::GET, None, &query, EMPTY_SHA256)
+                        .send()
+                        .await?;
+                    let xml = check(response)?.text().await?;
+                    let (keys, next) = s3_list_page(&xml);
+                    names.extend(
+                        keys.into_iter().filter_map(|key| {
+                            key.strip_prefix(prefix.as_str()).map(str::to_string)
+                        }),
+                    );
+                    match next {
+                        Some(token) => continuation = Some(token),
+                        None => break,
+                    }
+                }
+                names
+            }
+        };
+        Ok(names
+            .into_iter()
+            .filter(|name| backup_date(name).is_some())
+            .collect())
+    }
+
+    /// Streams the snapshot at `path` to the target as `name`
+    pub async fn upload(
+        &self,
+        client: &Client,
+        name: &str,
+        path: &Path,
+        snapshot: &Snapshot,
+    ) -> Result<()> {
+        let request = match self {
+            BackupTarget::WebDav { url, .. } => {
+                self.webdav(client, Method::PUT, &format!("{url}{name}"))
+            }
+            BackupTarget::S3 { .. } => {
+                self.s3(client, Method::PUT, Some(name), &[], &snapshot.sha256)
+            }
+        };
+        let file = tokio::fs::File::open(path).await?;
+        check(
+            request
+                .header("Content-Type", "application/gzip")
+                // S3 refuses chunked uploads
+                .header("Content-Length", snapshot.size)
+                .body(reqwest::Body::from(file))
+                .send()
+                .await?,
+        )?;
+        Ok(())
+    }
+
+    /// Deletes `name` from the target
+    pub async fn delete(&self, client: &Client, name: &str) -> Result<()> {
+        let request = match self {
+            BackupTarget::WebDav { url, .. } => {
+                self.webdav(client, Method::DELETE, &format!("{url}{name}"))
+            }
+            BackupTarget::S3 { .. } => {
+                self.s3(client, Method::DELETE, Some(name), &[], EMPTY_SHA256)
+            }
+        };
+        check(request.send().await?)?;
+        Ok(())
+    }
+
+    fn webdav(&self, client: &Client, method: Method, url: &str) -> RequestBuilder {
+        let request = client
+            .request(method, url)
+            .header("User-Agent", mtg_common::USER_AGENT);
+        match self {
+            BackupTarget::WebDav {
+                user: Some(user),
+                password,
+                ..
+            } => request.basic_auth(user, password.as_deref()),
+            _ => request,
+        }
+    }
+
+    /// A signed request for the bucket (`name: None`) or one of its objects
+    fn s3(
+        &self,
+        client: &Client,
+        method: Method,
+        name: Option<&str>,
+        query: &[(&str, String)],
+        payload_sha256: &str,
+    ) -> RequestBuilder {
+        let BackupTarget::S3 {
+            endpoint,
+            region,
+            bucket,
+            prefix,
+            access_key,
+            secret_key,
+        } = self
+        else {
+            unreachable!("S3 request for a WebDAV target");
+        };
+        let path = match name {
+            Some(name) => format!("/{bucket}/{prefix}{name}"),
+            None => format!("/{bucket}"),
+        };
+        let signer = S3Signer {
+            access_key,
+            secret_key,
+            region,
+        };
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint.as_str(), |(_, rest)| rest);
+        let headers = signer.headers(
+            method.as_str(),
+            host,
+            &path,
+            query,
+            payload_sha256,
+            Utc::now(),
+        );
+        let mut url = format!("{endpoint}{}", uri_encode(&path, false));
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query(query));
+        }
+        headers.into_iter().fold(
+            client
+                .request(method, url)
+                .header("User-Agent", mtg_common::USER_AGENT),
+            |request, (name, value)| request.header(name, value),
+        )
+    }
+}
+
+/// Turns an error status into [`InventoryError::HttpStatus`]
+fn check(response: Response) -> Result<Response> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(InventoryError::HttpStatus(response.status()))
+    }
+}
+
+/// Keys of one page of an S3 listing and the token of the next page, if
+/// the listing is truncated
+fn s3_list_page(xml: &str) -> (Vec<String>, Option<String>) {
+    let truncated = xml_values(xml, "IsTruncated")
+        .first()
+        .is_some_and(|v| v == "true");
+    let next = xml_values(xml, "NextContinuationToken")
+        .into_iter()
+        .next()
+        .filter(|token| truncated && !token.is_empty());
+    (xml_values(xml, "Key"), next)
+}
+
+/// Text of every `<tag>` element, with any namespace prefix (`<d:href>`)
+fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find('>') else {
+            break;
+        };
+        // Name of an opening tag; closing tags and declarations start with
+        // `/`, `?` or `!` and never match
+        let name = rest[..close].split_whitespace().next().unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+        rest = &rest[close + 1..];
+        if local == tag && !name.starts_with(['/', '?', '!']) {
+            let end = rest.find('<').unwrap_or(rest.len());
+            values.push(
+                rest[..end]
+                    .trim()
+                    .replace("&amp;", "&")
+                    .replace("&lt;", "<")
+                    .replace("&gt;", ">"),
+            );
+        }
+    }
+    values
+}
+
+/// Percent-encodes everything but the unreserved characters (and `/` unless
+/// `encode_slash`), as SigV4 requires
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Query string with encoded, sorted parameters
+fn canonical_query(query: &[(&str, String)]) -> String {
+    let mut pairs: Vec<String> = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+        .collect();
+    pairs.sort();
+    pairs.join("&")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// AWS Signature Version 4 for S3 requests
+struct S3Signer<'a> {
+    access_key: &'a str,
+    secret_key: &'a str,
+    region: &'a str,
+}
+
+impl S3Signer<'_> {
+    /// Key derived from the secret for one day, region and service
+    fn signing_key(&self, date: &str, service: &str) -> Vec<u8> {
+        let key = hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), date);
+        let key = hmac_sha256(&key, self.region);
+        let key = hmac_sha256(&key, service);
+        hmac_sha256(&key, "aws4_request")
+    }
+
+    /// `x-amz-date`, `x-amz-content-sha256` and `Authorization` headers of a
+    /// request to `path` on `host`
+    fn headers(
+        &self,
+        method: &str,
+        host: &str,
+        path: &str,
+        query: &[(&str, String)],
+        payload_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Vec<(&'static str, String)> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{method}\n{}\n{}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}",
+            uri_encode(path, false),
+            canonical_query(query),
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signature = hex(&hmac_sha256(
+            &self.signing_key(&date, "s3"),
+            &string_to_sign,
+        ));
+        vec![
+            ("x-amz-date", amz_date),
+            ("x-amz-content-sha256", payload_hash.to_string()),
+            (
+                "Authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                    self.access_key
+                ),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+#[path = "backup_tests.rs"]
+mod tests;
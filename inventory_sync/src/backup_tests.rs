@@ -0,0 +1,230 @@
+//! Tests for backup.
+
+use super::*;
+use chrono::TimeZone;
+use std::io::Read;
+
+fn config(url: &str) -> InventorySyncConfig {
+    InventorySyncConfig {
+        backup_url: Some(url.to_string()),
+        backup_user: Some("user".to_string()),
+        backup_password: Some("password".to_string()),
+        backup_keep: 7,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn no_backup_url_disables_backups() {
+    let config = InventorySyncConfig::default();
+    assert_eq!(
+        BackupOptions::from_config(&config, Path::new("prices.db")).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn parses_webdav_targets() {
+    let options = BackupOptions::from_config(
+        &config("https://cloud.example/dav/backups"),
+        Path::new("/data/prices.db"),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        options.target,
+        BackupTarget::WebDav {
+            url: "https://cloud.example/dav/backups/".to_string(),
+            user: Some("user".to_string()),
+            password: Some("password".to_string()),
+        }
+    );
+    assert_eq!(options.keep, 7);
+    assert_eq!(options.snapshot_path, Path::new("/data/prices.backup.db"));
+}
+
+#[test]
+fn parses_s3_targets() {
+    let mut config = config("s3://backups/inventory/prices/");
+    config.backup_s3_endpoint = Some("https://s3.example.com/".to_string());
+    let options = BackupOptions::from_config(&config, Path::new("prices.db"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        options.target,
+        BackupTarget::S3 {
+            endpoint: "https://s3.example.com".to_string(),
+            region: DEFAULT_S3_REGION.to_string(),
+            bucket: "backups".to_string(),
+            prefix: "inventory/prices/".to_string(),
+            access_key: "user".to_string(),
+            secret_key: "password".to_string(),
+        }
+    );
+    assert_eq!(options.target.to_string(), "s3://backups/inventory/prices/");
+}
+
+#[test]
+fn rejects_incomplete_targets() {
+    // S3 without an endpoint
+    assert!(BackupOptions::from_config(&config("s3://backups"), Path::new("p.db")).is_err());
+    // S3 without a bucket
+    let mut no_bucket = config("s3://");
+    no_bucket.backup_s3_endpoint = Some("https://s3.example.com".to_string());
+    assert!(BackupOptions::from_config(&no_bucket, Path::new("p.db")).is_err());
+    // Unknown scheme
+    assert!(BackupOptions::from_config(&config("ftp://host/backups"), Path::new("p.db")).is_err());
+}
+
+#[test]
+fn retention_deletes_only_the_oldest_snapshots() {
+    let names: Vec<String> = [
+        "inventory_sync-2026-03-02.db.gz",
+        "notes.txt",
+        "inventory_sync-2026-03-04.db.gz",
+        "inventory_sync-2026-03-01.db.gz",
+        "inventory_sync-latest.db.gz",
+        "inventory_sync-2026-03-03.db.gz",
+    ]
+    .iter()
+    .map(|n| n.to_string())
+    .collect();
+
+    assert_eq!(
+        expired_backups(&names, 2),
+        vec![
+            "inventory_sync-2026-03-02.db.gz".to_string(),
+            "inventory_sync-2026-03-01.db.gz".to_string(),
+        ]
+    );
+    assert!(expired_backups(&names, 4).is_empty());
+    assert!(expired_backups(&names, 0).is_empty());
+    assert_eq!(backup_name("2026-03-04"), "inventory_sync-2026-03-04.db.gz");
+}
+
+#[test]
+fn reads_namespaced_xml_values() {
+    let xml = r#"<?xml version="1.0"?>
+        <d:multistatus xmlns:d="DAV:">
+          <d:response><d:href>/dav/backups/</d:href></d:response>
+          <d:response><d:href>/dav/backups/inventory_sync-2026-03-01.db.gz</d:href></d:response>
+        </d:multistatus>"#;
+    assert_eq!(
+        xml_values(xml, "href"),
+        vec![
+            "/dav/backups/",
+            "/dav/backups/inventory_sync-2026-03-01.db.gz"
+        ]
+    );
+    assert_eq!(
+        xml_values(
+            "<ListBucketResult><Key>a&amp;b</Key></ListBucketResult>",
+            "Key"
+        ),
+        vec!["a&b"]
+    );
+}
+
+#[test]
+fn follows_truncated_s3_listings() {
+    let first = "<ListBucketResult><IsTruncated>true</IsTruncated>\
+                 <Contents><Key>b/inventory_sync-2026-03-01.db.gz</Key></Contents>\
+                 <NextContinuationToken>1ueGc=</NextContinuationToken></ListBucketResult>";
+    assert_eq!(
+        s3_list_page(first),
+        (
+            vec!["b/inventory_sync-2026-03-01.db.gz".to_string()],
+            Some("1ueGc=".to_string())
+        )
+    );
+    let last = "<ListBucketResult><IsTruncated>false</IsTruncated>\
+                <Contents><Key>b/inventory_sync-2026-03-02.db.gz</Key></Contents>\
+                </ListBucketResult>";
+    assert_eq!(s3_list_page(last).1, None);
+}
+
+#[test]
+fn derives_the_documented_sigv4_signing_key() {
+    // Example from the AWS Signature Version 4 documentation
+    let signer = S3Signer {
+        access_key: "AKIDEXAMPLE",
+        secret_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+        region: "us-east-1",
+    };
+    assert_eq!(
+        hex(&signer.signing_key("20120215", "iam")),
+        "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+    );
+}
+
+#[test]
+fn signs_s3_requests() {
+    let signer = S3Signer {
+        access_key: "AKIDEXAMPLE",
+        secret_key: "secret",
+        region: "eu-central-1",
+    };
+    let now = Utc.with_ymd_and_hms(2026, 3, 4, 5, 6, 7).unwrap();
+    let headers = signer.headers(
+        "GET",
+        "s3.example.com",
+        "/backups",
+        &[
+            ("list-type", "2".to_string()),
+            ("prefix", "a b/".to_string()),
+        ],
+        EMPTY_SHA256,
+        now,
+    );
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+            .unwrap()
+    };
+    assert_eq!(header("x-amz-date"), "20260304T050607Z");
+    // SHA-256 of the empty body
+    assert_eq!(
+        header("x-amz-content-sha256"),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert!(header("Authorization").starts_with(
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260304/eu-central-1/s3/aws4_request, \
+         SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+    ));
+    assert_eq!(
+        canonical_query(&[
+            ("prefix", "a b/".to_string()),
+            ("list-type", "2".to_string())
+        ]),
+        "list-type=2&prefix=a%20b%2F"
+    );
+}
+
+#[test]
+fn snapshot_is_a_gzipped_copy_of_the_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch("CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('kept');")
+        .unwrap();
+    let path = dir.path().join("prices.backup.db");
+
+    let snapshot = write_snapshot(&conn, &path).unwrap();
+    let gzipped = std::fs::read(&path).unwrap();
+    assert_eq!(snapshot.size, gzipped.len() as u64);
+    assert_eq!(snapshot.sha256, hex(&Sha256::digest(&gzipped)));
+
+    let mut plain = Vec::new();
+    flate2::read::GzDecoder::new(std::fs::File::open(&path).unwrap())
+        .read_to_end(&mut plain)
+        .unwrap();
+    let copy = dir.path().join("copy.db");
+    std::fs::write(&copy, plain).unwrap();
+    let value: String = Connection::open(&copy)
+        .unwrap()
+        .query_row("SELECT v FROM t", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(value, "kept");
+    assert!(!path.with_extension("tmp").exists());
+}
@@ -0,0 +1,64 @@
+//! Cardmarket expansion metadata fetching and parsing
+
+use crate::error::{InventoryError, Result};
+use serde::Deserialize;
+use std::time::Duration;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Cardmarket expansion list URL (MTG = category 1), published next to the product catalog
+pub const EXPANSIONS_URL: &str =
+    "https://downloads.s3.cardmarket.com/productCatalog/expansions/expansions_1.json";
+
+/// Cardmarket expansion entry
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpansionEntry {
+    pub id_expansion: u64,
+    pub name: String,
+    /// Short set code (e.g. "LEA"); not every expansion has one
+    #[serde(default, alias = "abbreviation")]
+    pub code: Option<String>,
+    /// Release date as published by Cardmarket (`YYYY-MM-DD…`)
+    #[serde(default)]
+    pub release_date: Option<String>,
+}
+
+/// Full expansion list file structure from Cardmarket
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct ExpansionFile {
+    pub version: u32,
+    pub created_at: String,
+    pub expansions: Vec<ExpansionEntry>,
+}
+
+/// Fetch the expansion list from Cardmarket's CDN
+pub async fn fetch_expansions() -> Result<Vec<ExpansionEntry>> {
+    fetch_expansions_from(EXPANSIONS_URL).await
+}
+
+/// Fetch the expansion list from a custom URL (useful for testing with mock servers)
+pub async fn fetch_expansions_from(url: &str) -> Result<Vec<ExpansionEntry>> {
+    let client = mtg_common::http::client_builder(HTTP_TIMEOUT)?.build()?;
+
+    log::info!("Fetching expansion list from Cardmarket...");
+    let response = client
+        .get(url)
+        .header("User-Agent", mtg_common::USER_AGENT)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(InventoryError::HttpStatus(response.status()));
+    }
+
+    let file: ExpansionFile = response.json().await?;
+    log::info!("Fetched {} expansions", file.expansions.len());
+    Ok(file.expansions)
+}
+
+#[cfg(test)]
+#[path = "expansions_tests.rs"]
+mod tests;
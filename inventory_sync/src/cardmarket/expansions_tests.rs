@@ -0,0 +1,48 @@
+//! Tests for expansions.
+
+use super::*;
+
+#[test]
+fn expansion_entry_deserializes() {
+    let json = r#"{
+        "idExpansion": 1,
+        "name": "Alpha",
+        "code": "LEA",
+        "releaseDate": "1993-08-05"
+    }"#;
+
+    let entry: ExpansionEntry = serde_json::from_str(json).unwrap();
+    assert_eq!(entry.id_expansion, 1);
+    assert_eq!(entry.name, "Alpha");
+    assert_eq!(entry.code.as_deref(), Some("LEA"));
+    assert_eq!(entry.release_date.as_deref(), Some("1993-08-05"));
+}
+
+#[test]
+fn expansion_entry_accepts_abbreviation_and_missing_fields() {
+    let json = r#"{"idExpansion": 2, "name": "Beta", "abbreviation": "LEB"}"#;
+
+    let entry: ExpansionEntry = serde_json::from_str(json).unwrap();
+    assert_eq!(entry.code.as_deref(), Some("LEB"));
+    assert!(entry.release_date.is_none());
+
+    let json = r#"{"idExpansion": 3, "name": "Promos"}"#;
+    let entry: ExpansionEntry = serde_json::from_str(json).unwrap();
+    assert!(entry.code.is_none());
+}
+
+#[test]
+fn expansion_file_deserializes() {
+    let json = r#"{
+        "version": 1,
+        "createdAt": "2026-02-01T02:42:53+0100",
+        "expansions": [
+            {"idExpansion": 1, "name": "Alpha", "code": "LEA", "releaseDate": "1993-08-05"},
+            {"idExpansion": 2, "name": "Beta", "code": "LEB", "releaseDate": "1993-10-04"}
+        ]
+    }"#;
+
+    let file: ExpansionFile = serde_json::from_str(json).unwrap();
+    assert_eq!(file.expansions.len(), 2);
+    assert_eq!(file.expansions[1].name, "Beta");
+}
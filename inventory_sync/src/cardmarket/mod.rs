@@ -1,10 +1,12 @@
-//! Cardmarket API client for fetching price guides and product catalogs
+//! Cardmarket API client for fetching price guides, product catalogs and expansions
 
+mod expansions;
 mod price_guide;
 mod product_catalog;
 
+pub use expansions::{fetch_expansions, fetch_expansions_from, ExpansionEntry, EXPANSIONS_URL};
 pub use price_guide::{PriceGuide, PriceGuideEntry};
-pub use product_catalog::{ProductCatalog, ProductEntry};
+pub use product_catalog::{ProductCatalog, ProductEntry, NON_SINGLES_URL, SINGLES_URL};
 
 #[cfg(test)]
 pub use price_guide::make_test_price_entry;
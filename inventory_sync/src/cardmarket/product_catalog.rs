@@ -1,16 +1,15 @@
 //! Cardmarket product catalog fetching and parsing
 
-use crate::error::{InventoryError, Result};
+use crate::download::{self, DownloadLimits};
+use crate::error::Result;
+use mtg_common::Category;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::time::Duration;
-
-const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Cardmarket product catalog URLs (MTG = category 1)
-const SINGLES_URL: &str =
+pub const SINGLES_URL: &str =
     "https://downloads.s3.cardmarket.com/productCatalog/productList/products_singles_1.json";
-const NON_SINGLES_URL: &str =
+pub const NON_SINGLES_URL: &str =
     "https://downloads.s3.cardmarket.com/productCatalog/productList/products_nonsingles_1.json";
 
 /// Cardmarket product entry
@@ -26,6 +25,12 @@ pub struct ProductEntry {
     pub date_added: String,
 }
 
+impl ProductEntry {
+    pub fn category(&self) -> Category {
+        Category::from_id(self.id_category)
+    }
+}
+
 /// Full product catalog file structure from Cardmarket
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -46,17 +51,29 @@ pub struct ProductCatalog {
 impl ProductCatalog {
     /// Fetch both singles and non-singles product catalogs from Cardmarket's CDN
     pub async fn fetch() -> Result<Self> {
-        let client = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        Self::fetch_from(SINGLES_URL, NON_SINGLES_URL).await
+    }
 
+    /// Fetch both catalogs from custom URLs (useful for testing with mock servers)
+    pub async fn fetch_from(singles_url: &str, non_singles_url: &str) -> Result<Self> {
+        Self::fetch_with(singles_url, non_singles_url, &DownloadLimits::default()).await
+    }
+
+    /// Fetch both catalogs from custom URLs within the sync's download limits
+    pub async fn fetch_with(
+        singles_url: &str,
+        non_singles_url: &str,
+        limits: &DownloadLimits,
+    ) -> Result<Self> {
         // Fetch singles
         log::info!("Fetching singles product catalog from Cardmarket...");
-        let singles = Self::fetch_catalog(&client, SINGLES_URL).await?;
+        let singles = Self::fetch_catalog(singles_url, limits).await?;
         let singles_count = singles.len();
         log::info!("Fetched {} singles products", singles_count);
 
         // Fetch non-singles
         log::info!("Fetching non-singles product catalog from Cardmarket...");
-        let non_singles = Self::fetch_catalog(&client, NON_SINGLES_URL).await?;
+        let non_singles = Self::fetch_catalog(non_singles_url, limits).await?;
         let non_singles_count = non_singles.len();
         log::info!("Fetched {} non-singles products", non_singles_count);
 
@@ -80,20 +97,11 @@ impl ProductCatalog {
 
     /// Fetch a single catalog file
     async fn fetch_catalog(
-        client: &reqwest::Client,
         url: &str,
+        limits: &DownloadLimits,
     ) -> Result<HashMap<u64, ProductEntry>> {
-        let response = client
-            .get(url)
-            .header("User-Agent", mtg_common::USER_AGENT)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(InventoryError::HttpStatus(response.status()));
-        }
-
-        let file: ProductCatalogFile = response.json().await?;
+        let file: ProductCatalogFile =
+            serde_json::from_str(&download::fetch_text(url, limits).await?)?;
 
         let entries: HashMap<u64, ProductEntry> = file
             .products
@@ -134,15 +142,16 @@ impl ProductCatalog {
         self.entries.values()
     }
 
-    /// Create a ProductCatalog from entries (for testing)
-    #[cfg(test)]
+    /// Create a ProductCatalog from entries, e.g. the products stored in the
+    /// database
     pub fn from_entries(entries: Vec<ProductEntry>) -> Self {
-        let count = entries.len();
+        let singles_count = entries.iter().filter(|p| p.category().is_single()).count();
+        let non_singles_count = entries.len() - singles_count;
         let entries = entries.into_iter().map(|p| (p.id_product, p)).collect();
         Self {
             entries,
-            singles_count: count,
-            non_singles_count: 0,
+            singles_count,
+            non_singles_count,
         }
     }
 }
@@ -3,8 +3,13 @@
 //! Uses parameterized queries exclusively for security (no SQL string concatenation).
 //! All writes are transactional for safe shutdown.
 
-use crate::cardmarket::{PriceGuide, ProductCatalog};
-use rusqlite::{params, Connection, Transaction};
+use crate::cardmarket::{ExpansionEntry, PriceGuide, ProductCatalog, ProductEntry};
+use crate::error::{InventoryError, Result};
+use crate::tcgplayer::{spread, TcgplayerPrice};
+use mtg_common::cardmarket::{priced, Category};
+use mtg_common::scryfall::ScryfallSet;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
 
 /// Result type for database operations
 pub type DbResult<T> = rusqlite::Result<T>;
@@ -13,7 +18,17 @@ pub type DbResult<T> = rusqlite::Result<T>;
 ///
 /// Creates tables if they don't exist:
 /// - `products`: Product catalog with names and metadata
+/// - `product_renames`: Name changes seen by the catalog sync
 /// - `price_history`: Daily price snapshots (historical data)
+/// - `expansions`: Cardmarket expansion metadata (name, code, release date)
+/// - `expansion_names`: Expansion names learned from Scryfall lookups
+/// - `tcgplayer_prices`: Daily TCGplayer USD prices (optional price source)
+/// - `exchange_rates`: Daily ECB reference rates per currency
+/// - `audit_log`: Requests to the web server's admin endpoints
+/// - `tenants`: Users of a shared web server, each with their own token
+/// - `watchlist`: Products each tenant follows
+/// - `upcoming_sets`: Announced sets not yet released (Scryfall)
+/// - `sync_state`: Steps of the daily sync already completed per day
 pub fn init_schema(conn: &Connection) -> DbResult<()> {
     conn.execute_batch(
         "
@@ -33,6 +48,18 @@ pub fn init_schema(conn: &Connection) -> DbResult<()> {
         CREATE INDEX IF NOT EXISTS idx_products_expansion ON products(id_expansion);
         CREATE INDEX IF NOT EXISTS idx_products_metacard ON products(id_metacard);
 
+        -- Product name changes (typo fixes, errata) detected by the catalog
+        -- upsert, so searches and references by an old name still resolve
+        CREATE TABLE IF NOT EXISTS product_renames (
+            id_product INTEGER NOT NULL,
+            old_name TEXT NOT NULL,
+            new_name TEXT NOT NULL,
+            renamed_at TEXT NOT NULL DEFAULT (date('now')),
+            FOREIGN KEY (id_product) REFERENCES products(id_product)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_product_renames_product ON product_renames(id_product);
+
         -- Historical price data table
         -- Composite primary key: (id_product, price_date) ensures one entry per product per day
         CREATE TABLE IF NOT EXISTS price_history (
@@ -60,26 +87,196 @@ pub fn init_schema(conn: &Connection) -> DbResult<()> {
         CREATE INDEX IF NOT EXISTS idx_price_history_date ON price_history(price_date);
         CREATE INDEX IF NOT EXISTS idx_price_history_product ON price_history(id_product);
 
+        -- Expansion metadata from Cardmarket's expansion list
+        CREATE TABLE IF NOT EXISTS expansions (
+            id_expansion INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            code TEXT,
+            release_date TEXT,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
         -- Expansion name cache populated from Scryfall lookups
         CREATE TABLE IF NOT EXISTS expansion_names (
             id_expansion INTEGER PRIMARY KEY,
             name TEXT NOT NULL
         );
 
+        -- TCGplayer market prices (USD), collected via Scryfall for products
+        -- above the --tcgplayer-min-trend threshold
+        CREATE TABLE IF NOT EXISTS tcgplayer_prices (
+            id_product INTEGER NOT NULL,
+            price_date TEXT NOT NULL,
+            usd REAL,
+            usd_foil REAL,
+            inserted_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (id_product, price_date),
+            FOREIGN KEY (id_product) REFERENCES products(id_product)
+        );
+
+        -- One row per price guide import, documenting the data's scope
+        -- (`categories` is a comma-separated list, NULL = all categories)
+        CREATE TABLE IF NOT EXISTS sync_runs (
+            price_date TEXT PRIMARY KEY,
+            categories TEXT,
+            inserted INTEGER NOT NULL,
+            filtered INTEGER NOT NULL,
+            no_product INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            finished_at TEXT NOT NULL DEFAULT (datetime('now')),
+            -- 1 if price_date was set by hand instead of taken from created_at
+            price_date_overridden INTEGER NOT NULL DEFAULT 0
+        );
+
+        -- Position of a price guide import that is committed in chunks; the
+        -- row is removed once the import completes and sync_runs is written
+        CREATE TABLE IF NOT EXISTS price_import_progress (
+            price_date TEXT PRIMARY KEY,
+            created_at TEXT NOT NULL,
+            categories TEXT,
+            last_id_product INTEGER,
+            inserted INTEGER NOT NULL,
+            filtered INTEGER NOT NULL,
+            no_product INTEGER NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        -- ECB reference rates: units of `currency` per 1 EUR
+        CREATE TABLE IF NOT EXISTS exchange_rates (
+            rate_date TEXT NOT NULL,
+            currency TEXT NOT NULL,
+            per_eur REAL NOT NULL,
+            PRIMARY KEY (rate_date, currency)
+        );
+
+        -- Requests to /api/admin/* (when the web server runs with --audit-log)
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY,
+            requested_at TEXT NOT NULL DEFAULT (datetime('now')),
+            method TEXT NOT NULL,
+            path TEXT NOT NULL,
+            status INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            client_ip TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_audit_log_requested_at ON audit_log(requested_at);
+
+        -- Tenants share the catalog and prices; only the token's SHA-256 is stored
+        CREATE TABLE IF NOT EXISTS tenants (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            token_hash TEXT NOT NULL UNIQUE,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        -- Products each tenant follows
+        CREATE TABLE IF NOT EXISTS watchlist (
+            tenant_id INTEGER NOT NULL,
+            id_product INTEGER NOT NULL,
+            added_at TEXT NOT NULL DEFAULT (date('now')),
+            PRIMARY KEY (tenant_id, id_product)
+        );
+
+        -- Price alerts (see alerts.rs): a tenant's threshold on a product's
+        -- trend, and the price dates each alert fired on
+        CREATE TABLE IF NOT EXISTS alerts (
+            id INTEGER PRIMARY KEY,
+            tenant_id INTEGER NOT NULL,
+            id_product INTEGER NOT NULL,
+            condition TEXT NOT NULL,
+            threshold REAL NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            hysteresis_pct REAL NOT NULL DEFAULT 0,
+            cooldown_days INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_alerts_tenant ON alerts(tenant_id);
+        CREATE INDEX IF NOT EXISTS idx_alerts_product ON alerts(id_product);
+
+        CREATE TABLE IF NOT EXISTS triggered_alerts (
+            alert_id INTEGER NOT NULL,
+            price_date TEXT NOT NULL,
+            trend REAL NOT NULL,
+            previous_trend REAL,
+            triggered_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (alert_id, price_date)
+        );
+
+        -- Release calendar: announced paper sets from Scryfall, replaced on
+        -- every catalog sync
+        CREATE TABLE IF NOT EXISTS upcoming_sets (
+            code TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            released_at TEXT NOT NULL,
+            set_type TEXT
+        );
+
+        -- Completed steps of the daily Cardmarket sync (see SyncStep), so a
+        -- restarted daemon does not redo them on the same day
+        CREATE TABLE IF NOT EXISTS sync_state (
+            sync_date TEXT NOT NULL,
+            step TEXT NOT NULL,
+            completed_at TEXT NOT NULL DEFAULT (datetime('now')),
+            PRIMARY KEY (sync_date, step)
+        );
+
         -- The buy-signal scanner was removed (its daily scan cost too much CPU
         -- on the server); drop its leftover tables from older deployments.
         DROP TABLE IF EXISTS buy_signals;
         DROP TABLE IF EXISTS buy_signals_meta;
         ",
     )?;
+    add_column_if_missing(
+        conn,
+        "sync_runs",
+        "price_date_overridden",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    // Tenants opted into automatically watching new products of upcoming sets
+    add_column_if_missing(
+        conn,
+        "tenants",
+        "watch_new_sets",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    // Alerts created before hysteresis and cooldown existed rearm at once
+    add_column_if_missing(conn, "alerts", "hysteresis_pct", "REAL NOT NULL DEFAULT 0")?;
+    add_column_if_missing(
+        conn,
+        "alerts",
+        "cooldown_days",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
 
     log::info!("Database schema initialized");
     Ok(())
 }
 
+/// Adds a column to a table created by an older version of the schema
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> DbResult<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+        ))?;
+    }
+    Ok(())
+}
+
 /// Upsert products from the catalog into the database
 ///
-/// Uses INSERT OR REPLACE to update existing products with new data.
+/// Uses INSERT OR REPLACE to update existing products with new data; a
+/// changed name is recorded in `product_renames` first.
 /// All operations are wrapped in a transaction for atomicity.
 pub fn upsert_products(conn: &mut Connection, catalog: &ProductCatalog) -> DbResult<usize> {
     let tx = conn.transaction()?;
@@ -89,14 +286,22 @@ pub fn upsert_products(conn: &mut Connection, catalog: &ProductCatalog) -> DbRes
 }
 
 fn upsert_products_tx(tx: &Transaction<'_>, catalog: &ProductCatalog) -> DbResult<usize> {
+    let mut rename_stmt = tx.prepare_cached(
+        "INSERT INTO product_renames (id_product, old_name, new_name, renamed_at)
+         SELECT id_product, name, ?2, ?3 FROM products
+         WHERE id_product = ?1 AND name <> ?2",
+    )?;
     let mut stmt = tx.prepare_cached(
         "INSERT OR REPLACE INTO products 
          (id_product, name, id_category, category_name, id_expansion, id_metacard, date_added, updated_at)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'))",
     )?;
 
+    let today = today_date();
     let mut count = 0;
+    let mut renamed = 0;
     for product in catalog.iter() {
+        renamed += rename_stmt.execute(params![product.id_product, &product.name, &today])?;
         stmt.execute(params![
             product.id_product,
             &product.name,
@@ -109,7 +314,56 @@ fn upsert_products_tx(tx: &Transaction<'_>, catalog: &ProductCatalog) -> DbResul
         count += 1;
     }
 
-    log::info!("Upserted {} products into database", count);
+    log::info!(
+        "Upserted {} products into database ({} renamed)",
+        count,
+        renamed
+    );
+    Ok(count)
+}
+
+/// IDs of catalog products not yet in the database, ascending.
+/// Call before [`upsert_products`] to learn which products are new.
+pub fn get_unknown_product_ids(conn: &Connection, catalog: &ProductCatalog) -> DbResult<Vec<u64>> {
+    let mut stmt = conn.prepare("SELECT id_product FROM products")?;
+    let known = stmt
+        .query_map([], |row| row.get::<_, u64>(0))?
+        .collect::<DbResult<std::collections::HashSet<u64>>>()?;
+    let mut unknown: Vec<u64> = catalog
+        .iter()
+        .map(|product| product.id_product)
+        .filter(|id| !known.contains(id))
+        .collect();
+    unknown.sort_unstable();
+    Ok(unknown)
+}
+
+/// Upsert expansion metadata from Cardmarket's expansion list
+///
+/// Uses INSERT OR REPLACE so renamed or re-dated expansions pick up the latest
+/// values. All operations are wrapped in a transaction for atomicity.
+pub fn upsert_expansions(conn: &mut Connection, expansions: &[ExpansionEntry]) -> DbResult<usize> {
+    let tx = conn.transaction()?;
+    let count = {
+        let mut stmt = tx.prepare_cached(
+            "INSERT OR REPLACE INTO expansions
+             (id_expansion, name, code, release_date, updated_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+        )?;
+        for expansion in expansions {
+            stmt.execute(params![
+                expansion.id_expansion,
+                &expansion.name,
+                &expansion.code,
+                // Keep just the date part so all rows sort the same way
+                expansion.release_date.as_deref().map(extract_date),
+            ])?;
+        }
+        expansions.len()
+    };
+    tx.commit()?;
+
+    log::info!("Upserted {} expansions into database", count);
     Ok(count)
 }
 
@@ -134,6 +388,10 @@ pub fn get_id_expansion_for_product(conn: &Connection, id_product: u64) -> DbRes
     }
 }
 
+/// Price rows committed per transaction during an import. A crash loses at
+/// most one chunk; the next run resumes after the last committed one.
+pub const PRICE_INSERT_CHUNK: usize = 10_000;
+
 /// Insert price history for a specific date
 ///
 /// Only inserts prices for products that don't already have data for this date.
@@ -145,10 +403,104 @@ pub fn insert_price_history(
     guide: &PriceGuide,
     catalog: &ProductCatalog,
 ) -> DbResult<InsertResult> {
-    let tx = conn.transaction()?;
-    let result = insert_price_history_tx(&tx, guide, catalog)?;
-    tx.commit()?;
-    Ok(result)
+    insert_price_history_for_categories(conn, guide, catalog, &[])
+}
+
+/// Like [`insert_price_history`], but only stores entries whose Cardmarket
+/// category is in `categories` (empty = all). The filter is recorded in
+/// `sync_runs` alongside the counts.
+///
+/// Rows are committed in chunks of [`PRICE_INSERT_CHUNK`] in product ID order,
+/// with the position kept in `price_import_progress`. An interrupted import of
+/// the same price date resumes after its last committed chunk (with the
+/// category filter it started with); the date only counts as synced once the
+/// `sync_runs` row is written and the progress row removed. An unfinished
+/// import of another date is discarded when a new date's import starts.
+pub fn insert_price_history_for_categories(
+    conn: &mut Connection,
+    guide: &PriceGuide,
+    catalog: &ProductCatalog,
+    categories: &[Category],
+) -> DbResult<InsertResult> {
+    insert_price_history_chunked(conn, guide, catalog, categories, PRICE_INSERT_CHUNK)
+}
+
+/// Like [`insert_price_history_for_categories`], but stores the prices under
+/// `price_date` instead of the date in the guide's `createdAt` header, for
+/// manually inserted files whose header is wrong. The override is recorded in
+/// `sync_runs`.
+///
+/// Unlike a regular import this fails instead of skipping when the date
+/// already has price data, and refuses dates in the future and price guides
+/// that were already imported under another date. An interrupted import of
+/// the same file for the same date resumes.
+pub fn insert_price_history_for_date(
+    conn: &mut Connection,
+    guide: &PriceGuide,
+    catalog: &ProductCatalog,
+    categories: &[Category],
+    price_date: &str,
+) -> Result<InsertResult> {
+    validate_price_date_override(conn, guide, price_date)?;
+    let overridden = price_date != extract_date(guide.created_at());
+    if overridden {
+        log::info!(
+            "Importing price guide created {} as {}",
+            guide.created_at(),
+            price_date
+        );
+    }
+    Ok(insert_price_history_as(
+        conn,
+        guide,
+        catalog,
+        categories,
+        price_date.to_string(),
+        overridden,
+        PRICE_INSERT_CHUNK,
+    )?)
+}
+
+fn validate_price_date_override(
+    conn: &Connection,
+    guide: &PriceGuide,
+    price_date: &str,
+) -> Result<()> {
+    let invalid = |reason: String| Err(InventoryError::InvalidPriceDate(reason));
+
+    if chrono::NaiveDate::parse_from_str(price_date, "%Y-%m-%d").is_err() {
+        return invalid(format!("{price_date} is not a YYYY-MM-DD date"));
+    }
+    if price_date > today_date().as_str() {
+        return invalid(format!("{price_date} is in the future"));
+    }
+    match get_import_progress(conn, price_date)? {
+        Some(progress) if progress.created_at != guide.created_at() => {
+            return invalid(format!(
+                "an import of the price guide created {} for {price_date} is unfinished",
+                progress.created_at
+            ));
+        }
+        Some(_) => return Ok(()),
+        None => {}
+    }
+    if has_complete_price_data(conn, price_date)? {
+        return invalid(format!("{price_date} already has price data"));
+    }
+    let imported_as: Option<String> = conn
+        .query_row(
+            "SELECT price_date FROM sync_runs WHERE created_at = ?1 AND price_date != ?2",
+            params![guide.created_at(), price_date],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if let Some(other) = imported_as {
+        return invalid(format!(
+            "the price guide created {} was already imported for {other}",
+            guide.created_at()
+        ));
+    }
+    Ok(())
 }
 
 /// Result of a price history insert operation
@@ -160,92 +512,529 @@ pub struct InsertResult {
     pub skipped: usize,
     /// Number of entries with no matching product in catalog
     pub no_product: usize,
+    /// Number of entries outside the selected categories
+    pub filtered: usize,
+    /// Duplicate product rows in the price file that were dropped (the last
+    /// entry per product is kept)
+    pub deduplicated: usize,
     /// The price date used
     pub price_date: String,
 }
 
-fn insert_price_history_tx(
-    tx: &Transaction<'_>,
+/// Position and running counts of a price guide import that has not
+/// finished yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProgress {
+    pub price_date: String,
+    /// `createdAt` of the price guide being imported
+    pub created_at: String,
+    /// Category filter the import started with; empty = all
+    pub categories: Vec<Category>,
+    /// Highest product ID committed so far (`None` before the first chunk)
+    pub last_id_product: Option<u64>,
+    pub inserted: usize,
+    pub filtered: usize,
+    pub no_product: usize,
+}
+
+fn insert_price_history_chunked(
+    conn: &mut Connection,
     guide: &PriceGuide,
     catalog: &ProductCatalog,
+    categories: &[Category],
+    chunk_size: usize,
 ) -> DbResult<InsertResult> {
     // Extract date from created_at (format: "2026-02-01T02:42:53+0100")
     let price_date = extract_date(guide.created_at());
+    insert_price_history_as(
+        conn, guide, catalog, categories, price_date, false, chunk_size,
+    )
+}
 
-    // Check if we already have data for this date
-    let existing_count: i64 = tx.query_row(
-        "SELECT COUNT(*) FROM price_history WHERE price_date = ?1",
-        params![&price_date],
-        |row| row.get(0),
-    )?;
+fn insert_price_history_as(
+    conn: &mut Connection,
+    guide: &PriceGuide,
+    catalog: &ProductCatalog,
+    categories: &[Category],
+    price_date: String,
+    price_date_overridden: bool,
+    chunk_size: usize,
+) -> DbResult<InsertResult> {
+    let mut progress = match get_import_progress(conn, &price_date)? {
+        Some(progress) => {
+            log::info!(
+                "Resuming interrupted price import for {} after product {:?} ({} entries already committed)",
+                price_date,
+                progress.last_id_product,
+                progress.inserted
+            );
+            progress
+        }
+        None => {
+            discard_stale_imports(conn, &price_date)?;
 
-    if existing_count > 0 {
-        log::info!(
-            "Price data for {} already exists ({} entries), skipping insert",
-            price_date,
-            existing_count
-        );
-        return Ok(InsertResult {
-            inserted: 0,
-            skipped: guide.len(),
-            no_product: 0,
-            price_date,
-        });
-    }
+            // Check if we already have data for this date
+            let existing_count: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM price_history WHERE price_date = ?1",
+                params![&price_date],
+                |row| row.get(0),
+            )?;
 
-    // Prepare the insert statement (parameterized for security)
-    let mut stmt = tx.prepare_cached(
-        "INSERT INTO price_history 
-         (id_product, price_date, id_category, avg, low, trend, avg1, avg7, avg30,
-          avg_foil, low_foil, trend_foil, avg1_foil, avg7_foil, avg30_foil, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-    )?;
+            if existing_count > 0 {
+                log::info!(
+                    "Price data for {} already exists ({} entries), skipping insert",
+                    price_date,
+                    existing_count
+                );
+                return Ok(InsertResult {
+                    inserted: 0,
+                    skipped: guide.len(),
+                    no_product: 0,
+                    filtered: 0,
+                    deduplicated: guide.duplicates(),
+                    price_date,
+                });
+            }
 
-    let mut inserted = 0;
-    let mut no_product = 0;
+            let progress = ImportProgress {
+                price_date: price_date.clone(),
+                created_at: guide.created_at().to_string(),
+                categories: categories.to_vec(),
+                last_id_product: None,
+                inserted: 0,
+                filtered: 0,
+                no_product: 0,
+            };
+            save_import_progress(conn, &progress)?;
+            progress
+        }
+    };
 
-    for entry in guide.iter() {
-        // Only insert if product exists in catalog (ensures data integrity)
-        if catalog.get(entry.id_product).is_some() {
-            stmt.execute(params![
-                entry.id_product,
-                &price_date,
-                entry.id_category,
-                entry.avg,
-                entry.low,
-                entry.trend,
-                entry.avg1,
-                entry.avg7,
-                entry.avg30,
-                entry.avg_foil,
-                entry.low_foil,
-                entry.trend_foil,
-                entry.avg1_foil,
-                entry.avg7_foil,
-                entry.avg30_foil,
-                guide.created_at(),
-            ])?;
-            inserted += 1;
-        } else {
-            no_product += 1;
+    // Stable order so a restart knows which entries are already committed
+    let mut entries: Vec<_> = guide
+        .iter()
+        .filter(|e| {
+            progress
+                .last_id_product
+                .is_none_or(|last| e.id_product > last)
+        })
+        .collect();
+    entries.sort_by_key(|e| e.id_product);
+
+    for chunk in entries.chunks(chunk_size.max(1)) {
+        let tx = conn.transaction()?;
+        {
+            // Prepare the insert statement (parameterized for security)
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR IGNORE INTO price_history
+                 (id_product, price_date, id_category, avg, low, trend, avg1, avg7, avg30,
+                  avg_foil, low_foil, trend_foil, avg1_foil, avg7_foil, avg30_foil, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            )?;
+
+            for entry in chunk {
+                if !progress.categories.is_empty()
+                    && !progress.categories.contains(&entry.category())
+                {
+                    progress.filtered += 1;
+                    continue;
+                }
+                // Only insert if product exists in catalog (ensures data integrity)
+                if catalog.get(entry.id_product).is_none() {
+                    progress.no_product += 1;
+                    continue;
+                }
+                // Zeros mean "no price for this finish" (e.g. the non-foil side
+                // of a foil-only product); store them as NULL like Cardmarket's
+                // own nulls so queries never read them as 0 €.
+                progress.inserted += stmt.execute(params![
+                    entry.id_product,
+                    &price_date,
+                    entry.id_category,
+                    priced(entry.avg),
+                    priced(entry.low),
+                    priced(entry.trend),
+                    priced(entry.avg1),
+                    priced(entry.avg7),
+                    priced(entry.avg30),
+                    priced(entry.avg_foil),
+                    priced(entry.low_foil),
+                    priced(entry.trend_foil),
+                    priced(entry.avg1_foil),
+                    priced(entry.avg7_foil),
+                    priced(entry.avg30_foil),
+                    guide.created_at(),
+                ])?;
+            }
         }
+        progress.last_id_product = chunk.last().map(|e| e.id_product);
+        save_import_progress(&tx, &progress)?;
+        tx.commit()?;
     }
 
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_runs
+         (price_date, categories, inserted, filtered, no_product, created_at,
+          price_date_overridden)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            &price_date,
+            format_categories(&progress.categories),
+            progress.inserted,
+            progress.filtered,
+            progress.no_product,
+            &progress.created_at,
+            price_date_overridden
+        ],
+    )?;
+    tx.execute(
+        "DELETE FROM price_import_progress WHERE price_date = ?1",
+        params![&price_date],
+    )?;
+    tx.commit()?;
+
     log::info!(
-        "Inserted {} price entries for {} ({} products not in catalog)",
-        inserted,
+        "Inserted {} price entries for {} ({} products not in catalog, {} outside selected categories, {} duplicates dropped)",
+        progress.inserted,
         price_date,
-        no_product
+        progress.no_product,
+        progress.filtered,
+        guide.duplicates()
     );
 
     Ok(InsertResult {
-        inserted,
+        inserted: progress.inserted,
         skipped: 0,
-        no_product,
+        no_product: progress.no_product,
+        filtered: progress.filtered,
+        deduplicated: guide.duplicates(),
         price_date,
     })
 }
 
+/// Drops interrupted imports of other dates, with the price rows they had
+/// committed: Cardmarket only serves the current price guide, so they can
+/// never resume, and their progress row would keep them pending forever.
+fn discard_stale_imports(conn: &mut Connection, price_date: &str) -> DbResult<()> {
+    let tx = conn.transaction()?;
+    let stale: Vec<String> = tx
+        .prepare("SELECT price_date FROM price_import_progress WHERE price_date != ?1")?
+        .query_map(params![price_date], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    for date in &stale {
+        let removed = tx.execute(
+            "DELETE FROM price_history WHERE price_date = ?1",
+            params![date],
+        )?;
+        tx.execute(
+            "DELETE FROM price_import_progress WHERE price_date = ?1",
+            params![date],
+        )?;
+        log::warn!(
+            "Discarded the interrupted price import of {date} ({removed} entries) before importing {price_date}"
+        );
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn save_import_progress(conn: &Connection, progress: &ImportProgress) -> DbResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO price_import_progress
+         (price_date, created_at, categories, last_id_product, inserted, filtered, no_product)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            &progress.price_date,
+            &progress.created_at,
+            format_categories(&progress.categories),
+            progress.last_id_product,
+            progress.inserted,
+            progress.filtered,
+            progress.no_product
+        ],
+    )?;
+    Ok(())
+}
+
+const IMPORT_PROGRESS_COLUMNS: &str =
+    "price_date, created_at, categories, last_id_product, inserted, filtered, no_product";
+
+fn import_progress_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ImportProgress> {
+    Ok(ImportProgress {
+        price_date: row.get(0)?,
+        created_at: row.get(1)?,
+        categories: parse_categories(row.get(2)?),
+        last_id_product: row.get(3)?,
+        inserted: row.get(4)?,
+        filtered: row.get(5)?,
+        no_product: row.get(6)?,
+    })
+}
+
+fn get_import_progress(conn: &Connection, price_date: &str) -> DbResult<Option<ImportProgress>> {
+    conn.query_row(
+        &format!(
+            "SELECT {IMPORT_PROGRESS_COLUMNS} FROM price_import_progress WHERE price_date = ?1"
+        ),
+        params![price_date],
+        import_progress_from_row,
+    )
+    .optional()
+}
+
+/// The most recent price guide import that was interrupted, if any
+pub fn get_pending_import(conn: &Connection) -> DbResult<Option<ImportProgress>> {
+    conn.query_row(
+        &format!(
+            "SELECT {IMPORT_PROGRESS_COLUMNS} FROM price_import_progress
+             ORDER BY price_date DESC LIMIT 1"
+        ),
+        [],
+        import_progress_from_row,
+    )
+    .optional()
+}
+
+/// Category filter as stored in `sync_runs` / `price_import_progress`:
+/// comma-separated IDs, NULL for all categories.
+fn format_categories(categories: &[Category]) -> Option<String> {
+    (!categories.is_empty()).then(|| {
+        categories
+            .iter()
+            .map(Category::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    })
+}
+
+fn parse_categories(list: Option<String>) -> Vec<Category> {
+    list.iter()
+        .flat_map(|list| list.split(','))
+        .filter_map(|id| id.parse().ok())
+        .collect()
+}
+
+/// Scope and counts of one price guide import
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncRun {
+    pub price_date: String,
+    /// Cardmarket categories that were stored; empty = all
+    pub categories: Vec<Category>,
+    pub inserted: usize,
+    pub filtered: usize,
+    pub no_product: usize,
+    /// `createdAt` of the imported price guide
+    pub created_at: String,
+    /// Whether `price_date` was set by hand instead of taken from `created_at`
+    pub price_date_overridden: bool,
+}
+
+/// The import record for a price date, if that date was synced
+pub fn get_sync_run(conn: &Connection, price_date: &str) -> DbResult<Option<SyncRun>> {
+    conn.query_row(
+        "SELECT price_date, categories, inserted, filtered, no_product, created_at,
+                price_date_overridden
+         FROM sync_runs WHERE price_date = ?1",
+        params![price_date],
+        |row| {
+            Ok(SyncRun {
+                price_date: row.get(0)?,
+                categories: parse_categories(row.get(1)?),
+                inserted: row.get(2)?,
+                filtered: row.get(3)?,
+                no_product: row.get(4)?,
+                created_at: row.get(5)?,
+                price_date_overridden: row.get(6)?,
+            })
+        },
+    )
+    .optional()
+}
+
+// ── Web Audit Log ───────────────────────────────────────────────────────────
+
+/// One audited web request
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub requested_at: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    pub client_ip: String,
+}
+
+/// Record a web request in the audit log, timestamped now
+pub fn insert_audit_entry(
+    conn: &Connection,
+    method: &str,
+    path: &str,
+    status: u16,
+    duration_ms: u64,
+    client_ip: &str,
+) -> DbResult<()> {
+    conn.execute(
+        "INSERT INTO audit_log (method, path, status, duration_ms, client_ip)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![method, path, status, duration_ms, client_ip],
+    )?;
+    Ok(())
+}
+
+/// The most recent audit log entries, newest first, optionally only those at
+/// or after `since` (`YYYY-MM-DD` or `YYYY-MM-DD HH:MM:SS`, UTC)
+pub fn get_audit_entries(
+    conn: &Connection,
+    since: Option<&str>,
+    limit: usize,
+) -> DbResult<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, requested_at, method, path, status, duration_ms, client_ip
+         FROM audit_log
+         WHERE ?1 IS NULL OR requested_at >= ?1
+         ORDER BY id DESC
+         LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![since, limit], |row| {
+        Ok(AuditEntry {
+            id: row.get(0)?,
+            requested_at: row.get(1)?,
+            method: row.get(2)?,
+            path: row.get(3)?,
+            status: row.get(4)?,
+            duration_ms: row.get(5)?,
+            client_ip: row.get(6)?,
+        })
+    })?;
+    rows.collect()
+}
+
+// ── Tenants & Watchlists ────────────────────────────────────────────────────
+
+/// A user of a shared web server. Per-tenant data is keyed by `id`.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct Tenant {
+    pub id: i64,
+    pub name: String,
+    /// UTC, `YYYY-MM-DD HH:MM:SS`
+    pub created_at: String,
+}
+
+fn tenant_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Tenant> {
+    Ok(Tenant {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        created_at: row.get(2)?,
+    })
+}
+
+/// Register a tenant authenticated by the token with the given hash.
+/// Fails on a duplicate name or token.
+pub fn create_tenant(conn: &Connection, name: &str, token_hash: &str) -> DbResult<Tenant> {
+    conn.query_row(
+        "INSERT INTO tenants (name, token_hash) VALUES (?1, ?2)
+         RETURNING id, name, created_at",
+        params![name, token_hash],
+        tenant_from_row,
+    )
+}
+
+/// All tenants, oldest first
+pub fn get_tenants(conn: &Connection) -> DbResult<Vec<Tenant>> {
+    let mut stmt = conn.prepare("SELECT id, name, created_at FROM tenants ORDER BY id")?;
+    let rows = stmt.query_map([], tenant_from_row)?;
+    rows.collect()
+}
+
+/// The tenant owning the token with the given hash
+pub fn get_tenant_by_token_hash(conn: &Connection, token_hash: &str) -> DbResult<Option<Tenant>> {
+    conn.query_row(
+        "SELECT id, name, created_at FROM tenants WHERE token_hash = ?1",
+        params![token_hash],
+        tenant_from_row,
+    )
+    .optional()
+}
+
+/// Delete a tenant with its watchlist and price alerts. Returns false for
+/// unknown tenants.
+pub fn delete_tenant(conn: &mut Connection, tenant_id: i64) -> DbResult<bool> {
+    let tx = conn.transaction()?;
+    tx.execute(
+        "DELETE FROM watchlist WHERE tenant_id = ?1",
+        params![tenant_id],
+    )?;
+    tx.execute(
+        "DELETE FROM triggered_alerts
+         WHERE alert_id IN (SELECT id FROM alerts WHERE tenant_id = ?1)",
+        params![tenant_id],
+    )?;
+    tx.execute(
+        "DELETE FROM alerts WHERE tenant_id = ?1",
+        params![tenant_id],
+    )?;
+    let deleted = tx.execute("DELETE FROM tenants WHERE id = ?1", params![tenant_id])?;
+    tx.commit()?;
+    Ok(deleted > 0)
+}
+
+/// One followed product with its most recent trend price
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct WatchlistEntry {
+    pub id_product: u64,
+    pub name: String,
+    /// `YYYY-MM-DD`
+    pub added_at: String,
+    pub price_date: Option<String>,
+    pub trend: Option<f64>,
+}
+
+/// Follow a product. Returns false if the tenant already follows it.
+pub fn add_to_watchlist(conn: &Connection, tenant_id: i64, id_product: u64) -> DbResult<bool> {
+    let inserted = conn.execute(
+        "INSERT OR IGNORE INTO watchlist (tenant_id, id_product, added_at) VALUES (?1, ?2, ?3)",
+        params![tenant_id, id_product, today_date()],
+    )?;
+    Ok(inserted > 0)
+}
+
+/// Unfollow a product. Returns false if the tenant didn't follow it.
+pub fn remove_from_watchlist(conn: &Connection, tenant_id: i64, id_product: u64) -> DbResult<bool> {
+    let deleted = conn.execute(
+        "DELETE FROM watchlist WHERE tenant_id = ?1 AND id_product = ?2",
+        params![tenant_id, id_product],
+    )?;
+    Ok(deleted > 0)
+}
+
+/// A tenant's watchlist, by product name
+pub fn get_watchlist(conn: &Connection, tenant_id: i64) -> DbResult<Vec<WatchlistEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT w.id_product, p.name, w.added_at, ph.price_date, ph.trend
+         FROM watchlist w
+         JOIN products p ON p.id_product = w.id_product
+         LEFT JOIN price_history ph ON ph.id_product = w.id_product
+             AND ph.price_date = (
+                 SELECT MAX(price_date) FROM price_history WHERE id_product = w.id_product
+             )
+         WHERE w.tenant_id = ?1
+         ORDER BY p.name, w.id_product",
+    )?;
+    let rows = stmt.query_map(params![tenant_id], |row| {
+        Ok(WatchlistEntry {
+            id_product: row.get(0)?,
+            name: row.get(1)?,
+            added_at: row.get(2)?,
+            price_date: row.get(3)?,
+            trend: row.get(4)?,
+        })
+    })?;
+    rows.collect()
+}
+
 /// Extract date (YYYY-MM-DD) from a timestamp string
 ///
 /// Expected format: "2026-02-01T02:42:53+0100"
@@ -272,26 +1061,102 @@ pub fn get_latest_price_date(conn: &Connection) -> DbResult<Option<String>> {
 ///
 /// Uses Europe/Berlin timezone because Cardmarket timestamps are in Berlin time.
 /// The server may run in a different timezone, so we must be explicit.
+/// A day whose import was interrupted doesn't count until it is resumed.
 pub fn has_price_data_for_today(conn: &Connection) -> DbResult<bool> {
-    let today = today_date();
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM price_history WHERE price_date = ?1",
-        params![&today],
+    has_complete_price_data(conn, &today_date())
+}
+
+/// Dates from `from` to `to` (inclusive, YYYY-MM-DD) without complete price
+/// data, oldest first. Days with an unfinished import count as missing.
+pub fn get_missing_price_dates(conn: &Connection, from: &str, to: &str) -> Result<Vec<String>> {
+    let parse = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+            InventoryError::InvalidPriceDate(format!("{date} is not a YYYY-MM-DD date"))
+        })
+    };
+    let (from, to) = (parse(from)?, parse(to)?);
+    let mut missing = Vec::new();
+    for day in from.iter_days().take_while(|day| *day <= to) {
+        let date = day.format("%Y-%m-%d").to_string();
+        if !has_complete_price_data(conn, &date)? {
+            missing.push(date);
+        }
+    }
+    Ok(missing)
+}
+
+/// Whether a date has price rows and no unfinished import
+fn has_complete_price_data(conn: &Connection, price_date: &str) -> DbResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM price_history WHERE price_date = ?1)
+            AND NOT EXISTS(SELECT 1 FROM price_import_progress WHERE price_date = ?1)",
+        params![price_date],
+        |row| row.get(0),
+    )
+}
+
+/// A step of the daily Cardmarket sync whose completion is recorded in
+/// `sync_state`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStep {
+    /// Product catalog, expansion list and release calendar stored
+    Catalog,
+    /// The day's price guide imported
+    Prices,
+}
+
+impl SyncStep {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncStep::Catalog => "catalog",
+            SyncStep::Prices => "prices",
+        }
+    }
+}
+
+/// Whether `step` was completed on the sync date `date` (YYYY-MM-DD)
+pub fn is_sync_step_done(conn: &Connection, date: &str, step: SyncStep) -> DbResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sync_state WHERE sync_date = ?1 AND step = ?2)",
+        params![date, step.as_str()],
         |row| row.get(0),
+    )
+}
+
+/// Records that `step` was completed on the sync date `date` (YYYY-MM-DD)
+pub fn mark_sync_step_done(conn: &Connection, date: &str, step: SyncStep) -> DbResult<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO sync_state (sync_date, step) VALUES (?1, ?2)",
+        params![date, step.as_str()],
     )?;
-    Ok(count > 0)
+    Ok(())
 }
 
-/// Get today's date as YYYY-MM-DD string in Berlin timezone
-///
-/// Uses Europe/Berlin because Cardmarket data is timestamped in Berlin time.
-pub fn today_date() -> String {
-    use chrono::Utc;
-    use chrono_tz::Europe::Berlin;
-    Utc::now()
-        .with_timezone(&Berlin)
-        .format("%Y-%m-%d")
-        .to_string()
+/// Today's date as YYYY-MM-DD, as a Cardmarket (Europe/Berlin) day — see
+/// [`mtg_common::dates`].
+pub use mtg_common::dates::today_date;
+
+/// The product catalog as stored by the last catalog sync, for price imports
+/// that run without downloading the catalog again
+pub fn load_product_catalog(conn: &Connection) -> DbResult<ProductCatalog> {
+    let mut stmt = conn.prepare(
+        "SELECT id_product, name, id_category, category_name, id_expansion, id_metacard, date_added
+         FROM products",
+    )?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(ProductEntry {
+                id_product: row.get(0)?,
+                name: row.get(1)?,
+                id_category: row.get(2)?,
+                category_name: row.get(3)?,
+                id_expansion: row.get(4)?,
+                id_metacard: row.get(5)?,
+                date_added: row.get(6)?,
+            })
+        })?
+        .collect::<DbResult<Vec<_>>>()?;
+    Ok(ProductCatalog::from_entries(entries))
 }
 
 /// Get total count of products in database
@@ -304,29 +1169,443 @@ pub fn get_price_history_count(conn: &Connection) -> DbResult<i64> {
     conn.query_row("SELECT COUNT(*) FROM price_history", [], |row| row.get(0))
 }
 
+/// Row counts and covered price dates, as printed by `inventory_sync stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseStats {
+    pub products: i64,
+    pub price_history: i64,
+    /// Days with price data
+    pub price_days: i64,
+    pub first_price_date: Option<String>,
+    pub last_price_date: Option<String>,
+    pub tcgplayer_prices: i64,
+}
+
+/// Product and price history counts for a quick look at the database
+pub fn get_database_stats(conn: &Connection) -> DbResult<DatabaseStats> {
+    let (price_history, price_days, first_price_date, last_price_date) = conn.query_row(
+        "SELECT COUNT(*), COUNT(DISTINCT price_date), MIN(price_date), MAX(price_date)
+         FROM price_history",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    )?;
+    Ok(DatabaseStats {
+        products: get_product_count(conn)?,
+        price_history,
+        price_days,
+        first_price_date,
+        last_price_date,
+        tcgplayer_prices: conn.query_row("SELECT COUNT(*) FROM tcgplayer_prices", [], |row| {
+            row.get(0)
+        })?,
+    })
+}
+
+// ── Release Calendar ────────────────────────────────────────────────────────
+
+/// An announced set with its Cardmarket expansion, once Cardmarket lists it
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct UpcomingSet {
+    /// Scryfall set code, lowercase
+    pub code: String,
+    pub name: String,
+    /// `YYYY-MM-DD`
+    pub released_at: String,
+    pub days_until: i64,
+    pub set_type: Option<String>,
+    /// Matched by set code or name
+    pub id_expansion: Option<u64>,
+    /// Products Cardmarket already lists for the expansion (preorders)
+    pub products: i64,
+}
+
+/// Replace the release calendar with the given sets. Sets without a release
+/// date are skipped; returns the number stored.
+pub fn replace_upcoming_sets(conn: &mut Connection, sets: &[ScryfallSet]) -> DbResult<usize> {
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM upcoming_sets", [])?;
+    let mut count = 0;
+    {
+        let mut stmt = tx.prepare_cached(
+            "INSERT OR REPLACE INTO upcoming_sets (code, name, released_at, set_type)
+             VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for set in sets {
+            let Some(released_at) = &set.released_at else {
+                continue;
+            };
+            stmt.execute(params![
+                set.code.to_lowercase(),
+                &set.name,
+                extract_date(released_at),
+                &set.set_type,
+            ])?;
+            count += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(count)
+}
+
+/// Joins an upcoming set (`u`) to its Cardmarket expansion (`e`)
+const UPCOMING_SET_EXPANSION: &str = "(LOWER(e.code) = u.code OR e.name = u.name COLLATE NOCASE)";
+
+/// Sets releasing from `today` up to `days` days later (YYYY-MM-DD), soonest
+/// first
+pub fn get_upcoming_sets(conn: &Connection, today: &str, days: u32) -> DbResult<Vec<UpcomingSet>> {
+    let sql = format!(
+        "SELECT u.code, u.name, u.released_at,
+                CAST(julianday(u.released_at) - julianday(?1) AS INTEGER),
+                u.set_type,
+                (SELECT e.id_expansion FROM expansions e
+                 WHERE {UPCOMING_SET_EXPANSION} ORDER BY e.id_expansion LIMIT 1) AS id_expansion
+         FROM upcoming_sets u
+         WHERE u.released_at >= ?1 AND u.released_at <= date(?1, '+' || ?2 || ' days')
+         ORDER BY u.released_at, u.name"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let mut count_stmt = conn.prepare("SELECT COUNT(*) FROM products WHERE id_expansion = ?1")?;
+    let rows = stmt.query_map(params![today, days], |row| {
+        Ok(UpcomingSet {
+            code: row.get(0)?,
+            name: row.get(1)?,
+            released_at: row.get(2)?,
+            days_until: row.get(3)?,
+            set_type: row.get(4)?,
+            id_expansion: row.get(5)?,
+            products: 0,
+        })
+    })?;
+    let mut sets = rows.collect::<DbResult<Vec<_>>>()?;
+    for set in &mut sets {
+        if let Some(id_expansion) = set.id_expansion {
+            set.products = count_stmt.query_row(params![id_expansion], |row| row.get(0))?;
+        }
+    }
+    Ok(sets)
+}
+
+/// Opt a tenant in or out of watching new products of upcoming sets.
+/// Returns false for unknown tenants.
+pub fn set_watch_new_sets(conn: &Connection, tenant_id: i64, enabled: bool) -> DbResult<bool> {
+    let updated = conn.execute(
+        "UPDATE tenants SET watch_new_sets = ?2 WHERE id = ?1",
+        params![tenant_id, enabled],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Whether the tenant watches new products of upcoming sets
+pub fn get_watch_new_sets(conn: &Connection, tenant_id: i64) -> DbResult<bool> {
+    conn.query_row(
+        "SELECT watch_new_sets FROM tenants WHERE id = ?1",
+        params![tenant_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(|enabled| enabled.unwrap_or(false))
+}
+
+/// Add the given products to the watchlist of every opted-in tenant if they
+/// belong to an upcoming set. Returns the number of watchlist rows added.
+pub fn watch_new_set_products(conn: &mut Connection, ids: &[u64]) -> DbResult<usize> {
+    let tx = conn.transaction()?;
+    let mut added = 0;
+    {
+        let mut stmt = tx.prepare_cached(&format!(
+            "INSERT OR IGNORE INTO watchlist (tenant_id, id_product, added_at)
+             SELECT t.id, p.id_product, ?2
+             FROM products p
+             JOIN expansions e ON e.id_expansion = p.id_expansion
+             JOIN tenants t ON t.watch_new_sets = 1
+             WHERE p.id_product = ?1
+               AND EXISTS (SELECT 1 FROM upcoming_sets u WHERE {UPCOMING_SET_EXPANSION})"
+        ))?;
+        let today = today_date();
+        for id in ids {
+            added += stmt.execute(params![id, &today])?;
+        }
+    }
+    tx.commit()?;
+    Ok(added)
+}
+
+// ── TCGplayer Price Source ──────────────────────────────────────────────────
+
+/// Whether TCGplayer prices were already collected for the given date
+pub fn has_tcgplayer_prices_for_date(conn: &Connection, date: &str) -> DbResult<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM tcgplayer_prices WHERE price_date = ?1)",
+        params![date],
+        |row| row.get(0),
+    )
+}
+
+/// Products worth comparing across marketplaces: latest Cardmarket trend of
+/// at least `min_trend` EUR, most expensive first, at most `limit`.
+pub fn get_tcgplayer_candidates(
+    conn: &Connection,
+    min_trend: f64,
+    limit: usize,
+) -> DbResult<Vec<u64>> {
+    let mut stmt = conn.prepare(
+        "SELECT id_product FROM price_history
+         WHERE price_date = (SELECT MAX(price_date) FROM price_history)
+           AND trend >= ?1
+         ORDER BY trend DESC
+         LIMIT ?2",
+    )?;
+    let ids = stmt.query_map(params![min_trend, limit], |row| row.get(0))?;
+    ids.collect()
+}
+
+/// Store TCGplayer prices for a date, replacing any earlier run that day.
+/// Products missing from the catalog are skipped.
+pub fn insert_tcgplayer_prices(
+    conn: &mut Connection,
+    price_date: &str,
+    prices: &[TcgplayerPrice],
+) -> DbResult<usize> {
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    {
+        let mut stmt = tx.prepare_cached(
+            "INSERT OR REPLACE INTO tcgplayer_prices (id_product, price_date, usd, usd_foil)
+             SELECT ?1, ?2, ?3, ?4 WHERE EXISTS (SELECT 1 FROM products WHERE id_product = ?1)",
+        )?;
+        for price in prices {
+            inserted += stmt.execute(params![
+                price.id_product,
+                price_date,
+                price.usd,
+                price.usd_foil
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(inserted)
+}
+
+/// Store a reference rate (`per_eur` units of `currency` per 1 EUR)
+pub fn upsert_exchange_rate(
+    conn: &Connection,
+    rate_date: &str,
+    currency: &str,
+    per_eur: f64,
+) -> DbResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO exchange_rates (rate_date, currency, per_eur)
+         VALUES (?1, ?2, ?3)",
+        params![rate_date, currency, per_eur],
+    )?;
+    Ok(())
+}
+
+/// Latest known USD per EUR rate
+fn latest_usd_per_eur(conn: &Connection) -> DbResult<Option<f64>> {
+    conn.query_row(
+        "SELECT per_eur FROM exchange_rates WHERE currency = 'USD'
+         ORDER BY rate_date DESC LIMIT 1",
+        [],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Row layout shared by the comparison queries: id, name, Cardmarket date
+/// and trend, TCGplayer date and USD price.
+type ComparisonRow = (u64, String, String, f64, String, f64);
+
+fn comparison_from_row(row: ComparisonRow, usd_per_eur: f64) -> PriceComparison {
+    let (id_product, name, cardmarket_date, cardmarket_eur, tcgplayer_date, tcgplayer_usd) = row;
+    let (tcgplayer_eur, spread_pct) = spread(cardmarket_eur, tcgplayer_usd, usd_per_eur);
+    PriceComparison {
+        id_product,
+        name,
+        cardmarket_date,
+        cardmarket_eur,
+        tcgplayer_date,
+        tcgplayer_usd,
+        usd_per_eur,
+        tcgplayer_eur,
+        spread_pct,
+    }
+}
+
+/// Cardmarket vs. TCGplayer for every product with both prices, using the
+/// latest price of each source. Only products whose Cardmarket trend is at
+/// least `min_eur` are included; the widest spreads (either direction) come
+/// first. Empty until an exchange rate has been stored.
+pub fn get_price_comparisons(
+    conn: &Connection,
+    min_eur: f64,
+    limit: usize,
+) -> DbResult<Vec<PriceComparison>> {
+    let Some(usd_per_eur) = latest_usd_per_eur(conn)? else {
+        return Ok(Vec::new());
+    };
+    let mut stmt = conn.prepare(
+        "SELECT p.id_product, p.name, cm.price_date, cm.trend, tp.price_date, tp.usd
+         FROM price_history cm
+         JOIN tcgplayer_prices tp ON tp.id_product = cm.id_product
+         JOIN products p ON p.id_product = cm.id_product
+         WHERE cm.price_date = (SELECT MAX(price_date) FROM price_history)
+           AND cm.trend >= ?1 AND cm.trend > 0
+           AND tp.usd IS NOT NULL
+           AND tp.price_date = (SELECT MAX(price_date) FROM tcgplayer_prices
+                                WHERE id_product = cm.id_product)",
+    )?;
+    let rows = stmt.query_map(params![min_eur], |row| {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+        ))
+    })?;
+    let mut comparisons = rows
+        .map(|row| row.map(|r| comparison_from_row(r, usd_per_eur)))
+        .collect::<DbResult<Vec<_>>>()?;
+    comparisons.sort_by(|a, b| b.spread_pct.abs().total_cmp(&a.spread_pct.abs()));
+    comparisons.truncate(limit);
+    Ok(comparisons)
+}
+
+/// Cardmarket vs. TCGplayer for one product, from the latest price of each
+/// source. `None` if either price or the exchange rate is missing.
+pub fn get_price_comparison(
+    conn: &Connection,
+    id_product: u64,
+) -> DbResult<Option<PriceComparison>> {
+    let Some(usd_per_eur) = latest_usd_per_eur(conn)? else {
+        return Ok(None);
+    };
+    conn.query_row(
+        "SELECT p.id_product, p.name, cm.price_date, cm.trend, tp.price_date, tp.usd
+         FROM products p
+         JOIN price_history cm ON cm.id_product = p.id_product
+         JOIN tcgplayer_prices tp ON tp.id_product = p.id_product
+         WHERE p.id_product = ?1
+           AND cm.price_date = (SELECT MAX(price_date) FROM price_history
+                                WHERE id_product = ?1 AND trend > 0)
+           AND tp.price_date = (SELECT MAX(price_date) FROM tcgplayer_prices
+                                WHERE id_product = ?1 AND usd IS NOT NULL)",
+        params![id_product],
+        |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+            ))
+        },
+    )
+    .optional()
+    .map(|row| row.map(|r| comparison_from_row(r, usd_per_eur)))
+}
+
+// ── Price Movers ────────────────────────────────────────────────────────────
+
+/// A product whose trend price changed between the two latest price dates
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceMover {
+    pub id_product: u64,
+    pub name: String,
+    pub expansion_name: Option<String>,
+    /// Latest price date
+    pub price_date: String,
+    /// Price date the latest one is compared with
+    pub previous_date: String,
+    pub previous_trend: f64,
+    pub trend: f64,
+    /// Change from `previous_trend` to `trend` in percent
+    pub change_pct: f64,
+}
+
+/// The products whose trend moved the most (either direction) from the
+/// second latest to the latest price date. Only products priced at least
+/// `min_eur` on one of the two days count, so bulk cards doubling from 0.02
+/// to 0.04 don't crowd out real movers. Empty with fewer than two dates.
+pub fn get_price_movers(
+    conn: &Connection,
+    min_eur: f64,
+    limit: usize,
+) -> DbResult<Vec<PriceMover>> {
+    let mut stmt = conn.prepare(
+        "WITH dates AS (
+             SELECT DISTINCT price_date FROM price_history ORDER BY price_date DESC LIMIT 2
+         )
+         SELECT p.id_product, p.name, COALESCE(e.name, en.name),
+                cur.price_date, prev.price_date, prev.trend, cur.trend
+         FROM price_history cur
+         JOIN price_history prev ON prev.id_product = cur.id_product
+         JOIN products p ON p.id_product = cur.id_product
+         LEFT JOIN expansions e ON e.id_expansion = p.id_expansion
+         LEFT JOIN expansion_names en ON en.id_expansion = p.id_expansion
+         WHERE cur.price_date = (SELECT MAX(price_date) FROM dates)
+           AND prev.price_date = (SELECT MIN(price_date) FROM dates)
+           AND cur.price_date > prev.price_date
+           AND prev.trend > 0 AND cur.trend IS NOT NULL
+           AND MAX(prev.trend, cur.trend) >= ?1
+         ORDER BY ABS(cur.trend - prev.trend) / prev.trend DESC, p.id_product
+         LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![min_eur, limit as i64], |row| {
+        let previous_trend: f64 = row.get(5)?;
+        let trend: f64 = row.get(6)?;
+        Ok(PriceMover {
+            id_product: row.get(0)?,
+            name: row.get(1)?,
+            expansion_name: row.get(2)?,
+            price_date: row.get(3)?,
+            previous_date: row.get(4)?,
+            previous_trend,
+            trend,
+            change_pct: (trend - previous_trend) / previous_trend * 100.0,
+        })
+    })?;
+    rows.collect()
+}
+
 // ── Web API Query Functions ────────────────────────────────────────────────
 
 // Wire types shared with client apps live in mtg_common; re-exported here so
 // the rest of the crate keeps using `crate::database::…` paths.
 pub use mtg_common::inventory_sync::{
-    LatestPrice, PriceHistoryPoint, PriceSnapshot, ProductSearchResult,
+    ExpansionInfo, LatestPrice, PriceComparison, PriceHistoryPoint, PriceOnDate, PriceSnapshot,
+    ProductNames, ProductRename, ProductSearchResult, WantslistCard, WantslistCardPrice,
+    WantslistQuote, WantslistQuoteLine,
 };
 
 /// Search products by name (case-insensitive substring match)
 ///
 /// Returns up to `limit` results, prioritizing exact name matches first,
-/// then partial matches, all ordered alphabetically.
+/// then partial matches, all ordered alphabetically. Products renamed in the
+/// catalog also match their former names. Expansion names come from
+/// the Cardmarket expansion list, falling back to names learned via Scryfall.
+/// With a `category`, only products of that category match.
 pub fn search_products_by_name(
     conn: &Connection,
     query: &str,
+    category: Option<Category>,
     limit: usize,
 ) -> DbResult<Vec<ProductSearchResult>> {
     let pattern = format!("%{}%", query);
     let mut stmt = conn.prepare(
-        "SELECT p.id_product, p.name, p.category_name, p.id_expansion, e.name
+        "SELECT p.id_product, p.name, p.category_name, p.id_expansion,
+                COALESCE(x.name, e.name), p.id_category
          FROM products p
+         LEFT JOIN expansions x ON p.id_expansion = x.id_expansion
          LEFT JOIN expansion_names e ON p.id_expansion = e.id_expansion
-         WHERE p.name LIKE ?1 COLLATE NOCASE
+         WHERE (p.name LIKE ?1 COLLATE NOCASE
+                OR EXISTS (SELECT 1 FROM product_renames r
+                           WHERE r.id_product = p.id_product
+                             AND r.old_name LIKE ?1 COLLATE NOCASE))
+           AND (?4 IS NULL OR p.id_category = ?4)
          ORDER BY
              CASE WHEN p.name = ?2 COLLATE NOCASE THEN 0
                   WHEN p.name LIKE ?2 COLLATE NOCASE THEN 1
@@ -337,15 +1616,89 @@ pub fn search_products_by_name(
     )?;
 
     let results: DbResult<Vec<ProductSearchResult>> = stmt
-        .query_map(params![pattern, query, limit], |row| {
-            Ok(ProductSearchResult {
-                id_product: row.get(0)?,
-                name: row.get(1)?,
-                category_name: row.get(2)?,
-                id_expansion: row.get(3)?,
-                expansion_name: row.get(4)?,
-            })
-        })?
+        .query_map(
+            params![pattern, query, limit, category.map(Category::id)],
+            |row| {
+                Ok(ProductSearchResult {
+                    id_product: row.get(0)?,
+                    name: row.get(1)?,
+                    category_name: row.get(2)?,
+                    is_single: Category::from_id(row.get(5)?).is_single(),
+                    id_expansion: row.get(3)?,
+                    expansion_name: row.get(4)?,
+                })
+            },
+        )?
+        .collect();
+    results
+}
+
+/// Escapes `%`, `_` and `\` for a `LIKE … ESCAPE '\'` pattern
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Products a card name refers to, for lookups by name instead of ID
+///
+/// With `exact`, the name matches case-insensitively as a whole: the current
+/// name, a former name, or the front face of a double-faced card. Otherwise
+/// any product whose name or former name contains it matches. An
+/// `expansion` (Cardmarket expansion code, name or ID, case-insensitive)
+/// narrows the result down to one printing. Exact name matches come first.
+pub fn find_products_by_name(
+    conn: &Connection,
+    name: &str,
+    exact: bool,
+    expansion: Option<&str>,
+    limit: usize,
+) -> DbResult<Vec<ProductSearchResult>> {
+    let name = name.trim();
+    let name_filter = if exact {
+        "p.name = ?1 COLLATE NOCASE
+         OR p.name LIKE ?2 ESCAPE '\\'
+         OR EXISTS (SELECT 1 FROM product_renames r
+                    WHERE r.id_product = p.id_product AND r.old_name = ?1 COLLATE NOCASE)"
+    } else {
+        "p.name LIKE ?2 ESCAPE '\\'
+         OR EXISTS (SELECT 1 FROM product_renames r
+                    WHERE r.id_product = p.id_product AND r.old_name LIKE ?2 ESCAPE '\\')"
+    };
+    let pattern = if exact {
+        format!("{} // %", escape_like(name))
+    } else {
+        format!("%{}%", escape_like(name))
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT p.id_product, p.name, p.category_name, p.id_expansion,
+                COALESCE(x.name, e.name), p.id_category
+         FROM products p
+         LEFT JOIN expansions x ON p.id_expansion = x.id_expansion
+         LEFT JOIN expansion_names e ON p.id_expansion = e.id_expansion
+         WHERE ({name_filter})
+           AND (?3 IS NULL
+                OR x.code = ?3 COLLATE NOCASE
+                OR COALESCE(x.name, e.name) = ?3 COLLATE NOCASE
+                OR CAST(p.id_expansion AS TEXT) = ?3)
+         ORDER BY p.name <> ?1 COLLATE NOCASE, p.name, p.id_product
+         LIMIT ?4"
+    ))?;
+    let results: DbResult<Vec<ProductSearchResult>> = stmt
+        .query_map(
+            params![name, pattern, expansion.map(str::trim), limit],
+            |row| {
+                Ok(ProductSearchResult {
+                    id_product: row.get(0)?,
+                    name: row.get(1)?,
+                    category_name: row.get(2)?,
+                    is_single: Category::from_id(row.get(5)?).is_single(),
+                    id_expansion: row.get(3)?,
+                    expansion_name: row.get(4)?,
+                })
+            },
+        )?
         .collect();
     results
 }
@@ -495,14 +1848,139 @@ pub fn get_price_snapshots_bulk(
     Ok(results)
 }
 
+/// Price a wantslist as of each of `dates` (`YYYY-MM-DD`), e.g. to see what
+/// a deck would have cost six months ago.
+///
+/// Names match products exactly (case-insensitive); a double-faced card also
+/// matches by its front face (`Delver of Secrets` finds
+/// `Delver of Secrets // Insectile Aberration`). Per date, each row is priced
+/// at the cheapest matching printing's trend from its most recent price row
+/// on or before that date.
+pub fn price_wantslist(
+    conn: &Connection,
+    cards: &[WantslistCard],
+    dates: &[String],
+) -> DbResult<WantslistQuote> {
+    let mut products = conn.prepare(
+        "SELECT id_product FROM products
+         WHERE name = ?1 COLLATE NOCASE OR name LIKE ?2 ESCAPE '\\'",
+    )?;
+    let mut trend_on = conn.prepare(
+        "SELECT price_date, trend FROM price_history
+         WHERE id_product = ?1 AND price_date <= ?2 AND trend > 0
+         ORDER BY price_date DESC
+         LIMIT 1",
+    )?;
+    let mut lines = Vec::with_capacity(cards.len());
+    for card in cards {
+        let name = card.name.trim();
+        let front_face = format!("{} // %", escape_like(name));
+        let ids = products
+            .query_map(params![name, front_face], |row| row.get::<_, u64>(0))?
+            .collect::<DbResult<Vec<_>>>()?;
+        let mut prices = Vec::with_capacity(dates.len());
+        for date in dates {
+            let mut cheapest: Option<WantslistCardPrice> = None;
+            for &id_product in &ids {
+                let row = trend_on
+                    .query_row(params![id_product, date], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+                    })
+                    .optional()?;
+                if let Some((price_date, trend)) = row {
+                    if cheapest.as_ref().is_none_or(|c| trend < c.trend) {
+                        cheapest = Some(WantslistCardPrice {
+                            id_product,
+                            price_date,
+                            trend,
+                        });
+                    }
+                }
+            }
+            prices.push(cheapest);
+        }
+        lines.push(WantslistQuoteLine {
+            card: card.clone(),
+            prices,
+        });
+    }
+    Ok(WantslistQuote::new(dates, lines))
+}
+
+/// Get the price row nearest to `date` (`YYYY-MM-DD`) for one product.
+///
+/// Rows on either side of the date qualify; when two are equally far away the
+/// earlier one wins. Returns `None` if the product has no price history.
+pub fn get_price_on_date(
+    conn: &Connection,
+    id_product: u64,
+    date: &str,
+) -> DbResult<Option<PriceOnDate>> {
+    let mut stmt = conn.prepare(
+        "SELECT price_date, avg, low, trend, avg1, avg7, avg30,
+                avg_foil, low_foil, trend_foil, avg1_foil, avg7_foil, avg30_foil
+         FROM price_history
+         WHERE id_product = ?1
+         ORDER BY ABS(julianday(price_date) - julianday(?2)), price_date ASC
+         LIMIT 1",
+    )?;
+    stmt.query_row(params![id_product, date], |row| {
+        let price = PriceHistoryPoint {
+            price_date: row.get(0)?,
+            avg: row.get(1)?,
+            low: row.get(2)?,
+            trend: row.get(3)?,
+            avg1: row.get(4)?,
+            avg7: row.get(5)?,
+            avg30: row.get(6)?,
+            avg_foil: row.get(7)?,
+            low_foil: row.get(8)?,
+            trend_foil: row.get(9)?,
+            avg1_foil: row.get(10)?,
+            avg7_foil: row.get(11)?,
+            avg30_foil: row.get(12)?,
+        };
+        Ok(PriceOnDate {
+            id_product,
+            requested_date: date.to_string(),
+            exact: price.price_date == date,
+            price,
+        })
+    })
+    .optional()
+}
+
+/// List all known expansions, newest release first (undated ones last)
+pub fn get_expansions(conn: &Connection) -> DbResult<Vec<ExpansionInfo>> {
+    let mut stmt = conn.prepare(
+        "SELECT id_expansion, name, code, release_date
+         FROM expansions
+         ORDER BY release_date IS NULL, release_date DESC, name",
+    )?;
+
+    let results: DbResult<Vec<ExpansionInfo>> = stmt
+        .query_map([], |row| {
+            Ok(ExpansionInfo {
+                id_expansion: row.get(0)?,
+                name: row.get(1)?,
+                code: row.get(2)?,
+                release_date: row.get(3)?,
+            })
+        })?
+        .collect();
+    results
+}
+
 /// Get product details by ID
 pub fn get_product_by_id(
     conn: &Connection,
     id_product: u64,
 ) -> DbResult<Option<ProductSearchResult>> {
     let mut stmt = conn.prepare(
-        "SELECT p.id_product, p.name, p.category_name, p.id_expansion, e.name
+        "SELECT p.id_product, p.name, p.category_name, p.id_expansion,
+                COALESCE(x.name, e.name), p.id_category
          FROM products p
+         LEFT JOIN expansions x ON p.id_expansion = x.id_expansion
          LEFT JOIN expansion_names e ON p.id_expansion = e.id_expansion
          WHERE p.id_product = ?1",
     )?;
@@ -513,6 +1991,7 @@ pub fn get_product_by_id(
             id_product: row.get(0)?,
             name: row.get(1)?,
             category_name: row.get(2)?,
+            is_single: Category::from_id(row.get(5)?).is_single(),
             id_expansion: row.get(3)?,
             expansion_name: row.get(4)?,
         })),
@@ -520,6 +1999,42 @@ pub fn get_product_by_id(
     }
 }
 
+/// Get a product's current name and its recorded renames (oldest first),
+/// or `None` if the product is unknown.
+pub fn get_product_names(conn: &Connection, id_product: u64) -> DbResult<Option<ProductNames>> {
+    let Some(name) = conn
+        .query_row(
+            "SELECT name FROM products WHERE id_product = ?1",
+            params![id_product],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?
+    else {
+        return Ok(None);
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT old_name, new_name, renamed_at FROM product_renames
+         WHERE id_product = ?1
+         ORDER BY renamed_at, rowid",
+    )?;
+    let renames = stmt
+        .query_map(params![id_product], |row| {
+            Ok(ProductRename {
+                old_name: row.get(0)?,
+                new_name: row.get(1)?,
+                renamed_at: row.get(2)?,
+            })
+        })?
+        .collect::<DbResult<Vec<_>>>()?;
+
+    Ok(Some(ProductNames {
+        id_product,
+        name,
+        renames,
+    }))
+}
+
 #[cfg(test)]
 #[path = "database_tests.rs"]
 mod tests;
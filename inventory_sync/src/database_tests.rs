@@ -1,7 +1,9 @@
 //! Tests for database.
 
 use super::*;
-use crate::cardmarket::{make_test_price_entry, make_test_product, PriceGuide, ProductCatalog};
+use crate::cardmarket::{
+    make_test_price_entry, make_test_product, ExpansionEntry, PriceGuide, ProductCatalog,
+};
 
 /// Create an in-memory database for testing
 fn test_db() -> Connection {
@@ -101,6 +103,47 @@ fn upsert_products_updates_existing() {
     assert_eq!(name, "Black Lotus (Updated)");
 }
 
+#[test]
+fn upsert_products_records_renames() {
+    let mut conn = test_db();
+    let original = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Jace, the Mind Sculpter"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &original).unwrap();
+    assert!(get_product_names(&conn, 1)
+        .unwrap()
+        .unwrap()
+        .renames
+        .is_empty());
+
+    // Typo fix for product 1; re-syncing the same name records nothing
+    let fixed = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Jace, the Mind Sculptor"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &fixed).unwrap();
+    upsert_products(&mut conn, &fixed).unwrap();
+
+    let names = get_product_names(&conn, 1).unwrap().unwrap();
+    assert_eq!(names.name, "Jace, the Mind Sculptor");
+    assert_eq!(names.renames.len(), 1);
+    assert_eq!(names.renames[0].old_name, "Jace, the Mind Sculpter");
+    assert_eq!(names.renames[0].new_name, "Jace, the Mind Sculptor");
+    assert_eq!(names.renames[0].renamed_at, today_date());
+    assert!(get_product_names(&conn, 2)
+        .unwrap()
+        .unwrap()
+        .renames
+        .is_empty());
+    assert!(get_product_names(&conn, 3).unwrap().is_none());
+
+    // The former name still finds the product
+    let results = search_products_by_name(&conn, "Sculpter", None, 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "Jace, the Mind Sculptor");
+}
+
 #[test]
 fn insert_price_history_inserts_prices() {
     let mut conn = test_db();
@@ -142,6 +185,30 @@ fn insert_price_history_inserts_prices() {
     assert!((trend - 2000.0).abs() < 0.01);
 }
 
+#[test]
+fn insert_price_history_stores_missing_finish_as_null() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Foil-only Promo")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    // Foil-only product: the guide writes 0 for the non-foil columns
+    let mut entry = make_test_price_entry(1, Some(0.0));
+    entry.avg = Some(0.0);
+    entry.trend_foil = Some(12.5);
+    let guide = PriceGuide::from_entries(vec![entry], "2026-02-01T10:00:00+0100");
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+
+    let (trend, avg, trend_foil): (Option<f64>, Option<f64>, Option<f64>) = conn
+        .query_row(
+            "SELECT trend, avg, trend_foil FROM price_history WHERE id_product = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(trend, None);
+    assert_eq!(avg, None);
+    assert_eq!(trend_foil, Some(12.5));
+}
+
 #[test]
 fn insert_price_history_skips_duplicate_date() {
     let mut conn = test_db();
@@ -228,6 +295,386 @@ fn insert_price_history_skips_products_not_in_catalog() {
     assert_eq!(result.no_product, 1);
 }
 
+#[test]
+fn insert_price_history_filters_categories() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Booster Box"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let mut sealed = make_test_price_entry(2, Some(100.0));
+    sealed.id_category = 7;
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(2000.0)), sealed],
+        "2026-02-01T10:00:00+0100",
+    );
+
+    let result =
+        insert_price_history_for_categories(&mut conn, &guide, &catalog, &[Category::Single])
+            .unwrap();
+    assert_eq!(result.inserted, 1);
+    assert_eq!(result.filtered, 1);
+    assert_eq!(get_price_history_count(&conn).unwrap(), 1);
+
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert_eq!(run.categories, vec![Category::Single]);
+    assert_eq!(run.inserted, 1);
+    assert_eq!(run.filtered, 1);
+    assert_eq!(run.created_at, "2026-02-01T10:00:00+0100");
+}
+
+#[test]
+fn insert_price_history_records_unfiltered_run() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(2000.0))],
+        "2026-02-01T10:00:00+0100",
+    );
+
+    assert_eq!(get_sync_run(&conn, "2026-02-01").unwrap(), None);
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert!(run.categories.is_empty());
+    assert_eq!(run.inserted, 1);
+    assert_eq!(run.filtered, 0);
+}
+
+#[test]
+fn insert_price_history_commits_in_chunks() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(
+        (1..=5)
+            .map(|id| make_test_product(id, &format!("Card {id}")))
+            .collect(),
+    );
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        (1..=5)
+            .map(|id| make_test_price_entry(id, Some(1.0)))
+            .collect(),
+        "2026-02-01T10:00:00+0100",
+    );
+
+    let result = insert_price_history_chunked(&mut conn, &guide, &catalog, &[], 2).unwrap();
+    assert_eq!(result.inserted, 5);
+    assert_eq!(get_price_history_count(&conn).unwrap(), 5);
+    assert_eq!(get_pending_import(&conn).unwrap(), None);
+    assert_eq!(
+        get_sync_run(&conn, "2026-02-01").unwrap().unwrap().inserted,
+        5
+    );
+}
+
+#[test]
+fn new_price_date_discards_stale_interrupted_import() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+
+    // A crash left the import of 2026-02-01 half done
+    let stale_created_at = "2026-02-01T10:00:00+0100";
+    insert_price_history(
+        &mut conn,
+        &PriceGuide::from_entries(vec![make_test_price_entry(1, Some(1.0))], stale_created_at),
+        &catalog,
+    )
+    .unwrap();
+    conn.execute("DELETE FROM sync_runs", []).unwrap();
+    save_import_progress(
+        &conn,
+        &ImportProgress {
+            price_date: "2026-02-01".to_string(),
+            created_at: stale_created_at.to_string(),
+            categories: Vec::new(),
+            last_id_product: Some(1),
+            inserted: 1,
+            filtered: 0,
+            no_product: 0,
+        },
+    )
+    .unwrap();
+
+    let guide = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(1, Some(2.0)),
+            make_test_price_entry(2, Some(3.0)),
+        ],
+        "2026-02-02T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+
+    assert_eq!(get_pending_import(&conn).unwrap(), None);
+    assert!(has_complete_price_data(&conn, "2026-02-02").unwrap());
+    assert!(!has_complete_price_data(&conn, "2026-02-01").unwrap());
+    assert_eq!(get_price_history_count(&conn).unwrap(), 2);
+}
+
+#[test]
+fn insert_price_history_resumes_interrupted_import() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+        make_test_product(3, "Booster Box"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let created_at = "2026-02-01T10:00:00+0100";
+
+    // State after a crash that committed the first chunk (product 1) of an
+    // import restricted to singles
+    insert_price_history(
+        &mut conn,
+        &PriceGuide::from_entries(vec![make_test_price_entry(1, Some(2000.0))], created_at),
+        &catalog,
+    )
+    .unwrap();
+    conn.execute("DELETE FROM sync_runs", []).unwrap();
+    let interrupted = ImportProgress {
+        price_date: "2026-02-01".to_string(),
+        created_at: created_at.to_string(),
+        categories: vec![Category::Single],
+        last_id_product: Some(1),
+        inserted: 1,
+        filtered: 0,
+        no_product: 0,
+    };
+    save_import_progress(&conn, &interrupted).unwrap();
+    assert_eq!(get_pending_import(&conn).unwrap(), Some(interrupted));
+    assert!(!has_complete_price_data(&conn, "2026-02-01").unwrap());
+
+    let mut sealed = make_test_price_entry(3, Some(100.0));
+    sealed.id_category = 7;
+    let guide = PriceGuide::from_entries(
+        vec![
+            // Already committed; must not be counted twice
+            make_test_price_entry(1, Some(1.0)),
+            make_test_price_entry(2, Some(500.0)),
+            sealed,
+        ],
+        created_at,
+    );
+    let result = insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    assert_eq!(result.inserted, 2);
+    assert_eq!(result.filtered, 1);
+    assert_eq!(get_price_history_count(&conn).unwrap(), 2);
+    assert_eq!(get_pending_import(&conn).unwrap(), None);
+    assert!(has_complete_price_data(&conn, "2026-02-01").unwrap());
+
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert_eq!(run.categories, vec![Category::Single]);
+    assert_eq!(run.inserted, 2);
+    // The first chunk's row was kept, not overwritten
+    let trend: f64 = conn
+        .query_row(
+            "SELECT trend FROM price_history WHERE id_product = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(trend, 2000.0);
+}
+
+/// Database with one product and the guide `created_at` for it
+fn override_fixture(created_at: &str) -> (Connection, ProductCatalog, PriceGuide) {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(vec![make_test_price_entry(1, Some(2000.0))], created_at);
+    (conn, catalog, guide)
+}
+
+#[test]
+fn insert_price_history_for_date_overrides_header_date() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-03T10:00:00+0100");
+
+    let result =
+        insert_price_history_for_date(&mut conn, &guide, &catalog, &[], "2026-02-01").unwrap();
+    assert_eq!(result.inserted, 1);
+    assert_eq!(result.price_date, "2026-02-01");
+    assert!(has_complete_price_data(&conn, "2026-02-01").unwrap());
+    assert!(!has_complete_price_data(&conn, "2026-02-03").unwrap());
+
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert!(run.price_date_overridden);
+    assert_eq!(run.created_at, "2026-02-03T10:00:00+0100");
+}
+
+#[test]
+fn insert_price_history_for_header_date_is_not_an_override() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-01T10:00:00+0100");
+
+    insert_price_history_for_date(&mut conn, &guide, &catalog, &[], "2026-02-01").unwrap();
+
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert!(!run.price_date_overridden);
+}
+
+#[test]
+fn insert_price_history_for_date_rejects_invalid_dates() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-03T10:00:00+0100");
+
+    for date in ["2026-02-30", "01.02.2026", "2999-01-01"] {
+        let result = insert_price_history_for_date(&mut conn, &guide, &catalog, &[], date);
+        assert!(
+            matches!(result, Err(InventoryError::InvalidPriceDate(_))),
+            "{date} should be rejected"
+        );
+    }
+    assert_eq!(get_price_history_count(&conn).unwrap(), 0);
+}
+
+#[test]
+fn insert_price_history_for_date_rejects_date_with_data() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-03T10:00:00+0100");
+    let existing = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(1.0))],
+        "2026-02-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &existing, &catalog).unwrap();
+
+    let result = insert_price_history_for_date(&mut conn, &guide, &catalog, &[], "2026-02-01");
+    assert!(matches!(result, Err(InventoryError::InvalidPriceDate(_))));
+}
+
+#[test]
+fn insert_price_history_for_date_rejects_guide_imported_for_other_date() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-03T10:00:00+0100");
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+
+    let result = insert_price_history_for_date(&mut conn, &guide, &catalog, &[], "2026-02-01");
+    assert!(matches!(result, Err(InventoryError::InvalidPriceDate(_))));
+    assert!(!has_complete_price_data(&conn, "2026-02-01").unwrap());
+}
+
+#[test]
+fn insert_price_history_for_date_resumes_same_guide_only() {
+    let (mut conn, catalog, guide) = override_fixture("2026-02-03T10:00:00+0100");
+    let interrupted = ImportProgress {
+        price_date: "2026-02-01".to_string(),
+        created_at: "2026-02-03T10:00:00+0100".to_string(),
+        categories: Vec::new(),
+        last_id_product: None,
+        inserted: 0,
+        filtered: 0,
+        no_product: 0,
+    };
+    save_import_progress(&conn, &interrupted).unwrap();
+
+    let other = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(1.0))],
+        "2026-02-04T10:00:00+0100",
+    );
+    let result = insert_price_history_for_date(&mut conn, &other, &catalog, &[], "2026-02-01");
+    assert!(matches!(result, Err(InventoryError::InvalidPriceDate(_))));
+
+    let result =
+        insert_price_history_for_date(&mut conn, &guide, &catalog, &[], "2026-02-01").unwrap();
+    assert_eq!(result.inserted, 1);
+    assert_eq!(get_pending_import(&conn).unwrap(), None);
+}
+
+#[test]
+fn get_missing_price_dates_lists_gaps_and_unfinished_days() {
+    let (mut conn, catalog, _) = override_fixture("2026-02-01T10:00:00+0100");
+    for date in ["2026-02-01", "2026-02-03"] {
+        let guide = PriceGuide::from_entries(
+            vec![make_test_price_entry(1, Some(1.0))],
+            &format!("{date}T10:00:00+0100"),
+        );
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    save_import_progress(
+        &conn,
+        &ImportProgress {
+            price_date: "2026-02-03".to_string(),
+            created_at: "2026-02-03T10:00:00+0100".to_string(),
+            categories: Vec::new(),
+            last_id_product: Some(1),
+            inserted: 1,
+            filtered: 0,
+            no_product: 0,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_missing_price_dates(&conn, "2026-01-31", "2026-02-04").unwrap(),
+        vec!["2026-01-31", "2026-02-02", "2026-02-03", "2026-02-04"]
+    );
+    assert!(get_missing_price_dates(&conn, "2026-02-01", "2026-02-01")
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        get_missing_price_dates(&conn, "yesterday", "2026-02-04"),
+        Err(InventoryError::InvalidPriceDate(_))
+    ));
+}
+
+#[test]
+fn init_schema_adds_override_column_to_old_sync_runs() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE sync_runs (
+            price_date TEXT PRIMARY KEY,
+            categories TEXT,
+            inserted INTEGER NOT NULL,
+            filtered INTEGER NOT NULL,
+            no_product INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            finished_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+        INSERT INTO sync_runs (price_date, inserted, filtered, no_product, created_at)
+        VALUES ('2026-02-01', 1, 0, 0, '2026-02-01T10:00:00+0100');",
+    )
+    .unwrap();
+
+    init_schema(&conn).unwrap();
+    init_schema(&conn).unwrap();
+
+    let run = get_sync_run(&conn, "2026-02-01").unwrap().unwrap();
+    assert!(!run.price_date_overridden);
+}
+
+#[test]
+fn insert_price_history_dedups_duplicate_products() {
+    let mut conn = test_db();
+
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+
+    let guide = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(1, Some(2000.0)),
+            make_test_price_entry(2, Some(500.0)),
+            make_test_price_entry(1, Some(2100.0)), // Duplicate, last one wins
+        ],
+        "2026-02-01T10:00:00+0100",
+    );
+
+    let result = insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    assert_eq!(result.inserted, 2);
+    assert_eq!(result.deduplicated, 1);
+
+    let (rows, trend): (i64, f64) = conn
+        .query_row(
+            "SELECT COUNT(*), MAX(trend) FROM price_history WHERE id_product = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(rows, 1);
+    assert_eq!(trend, 2100.0);
+}
+
 #[test]
 fn get_latest_price_date_returns_none_when_empty() {
     let conn = test_db();
@@ -275,6 +722,46 @@ fn get_product_count_returns_correct_count() {
     assert_eq!(get_product_count(&conn).unwrap(), 3);
 }
 
+#[test]
+fn database_stats_count_products_and_price_days() {
+    let mut conn = test_db();
+    let empty = get_database_stats(&conn).unwrap();
+    assert_eq!(
+        (empty.products, empty.price_history, empty.price_days),
+        (0, 0, 0)
+    );
+    assert_eq!(empty.last_price_date, None);
+
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for created in ["2026-02-01T10:00:00+0100", "2026-02-03T10:00:00+0100"] {
+        let guide = PriceGuide::from_entries(
+            vec![
+                make_test_price_entry(1, Some(2000.0)),
+                make_test_price_entry(2, Some(500.0)),
+            ],
+            created,
+        );
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+
+    let stats = get_database_stats(&conn).unwrap();
+    assert_eq!(
+        stats,
+        DatabaseStats {
+            products: 2,
+            price_history: 4,
+            price_days: 2,
+            first_price_date: Some("2026-02-01".to_string()),
+            last_price_date: Some("2026-02-03".to_string()),
+            tcgplayer_prices: 0,
+        }
+    );
+}
+
 #[test]
 fn price_history_handles_null_prices() {
     let mut conn = test_db();
@@ -353,7 +840,7 @@ fn search_products_by_name_includes_expansion_name_when_known() {
     upsert_products(&mut conn, &catalog).unwrap();
 
     // No expansion name stored yet — should be None
-    let results = search_products_by_name(&conn, "Black Lotus", 10).unwrap();
+    let results = search_products_by_name(&conn, "Black Lotus", None, 10).unwrap();
     assert_eq!(results.len(), 1);
     assert!(results[0].expansion_name.is_none());
 
@@ -361,8 +848,183 @@ fn search_products_by_name_includes_expansion_name_when_known() {
     upsert_expansion_name(&conn, 1, "Alpha").unwrap();
 
     // Now the expansion name should be joined in
-    let results = search_products_by_name(&conn, "Black Lotus", 10).unwrap();
+    let results = search_products_by_name(&conn, "Black Lotus", None, 10).unwrap();
+    assert_eq!(results[0].expansion_name.as_deref(), Some("Alpha"));
+}
+
+#[test]
+fn search_products_by_name_filters_by_category() {
+    let mut conn = test_db();
+    let mut booster = make_test_product(2, "Alpha Booster");
+    booster.id_category = 7;
+    booster.category_name = "Magic Booster".to_string();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Alpha Lotus"), booster]);
+    upsert_products(&mut conn, &catalog).unwrap();
+
+    let all = search_products_by_name(&conn, "Alpha", None, 10).unwrap();
+    assert_eq!(all.len(), 2);
+    assert!(all.iter().any(|p| p.id_product == 1 && p.is_single));
+    assert!(all.iter().any(|p| p.id_product == 2 && !p.is_single));
+
+    let singles = search_products_by_name(&conn, "Alpha", Some(Category::Single), 10).unwrap();
+    assert_eq!(singles.len(), 1);
+    assert_eq!(singles[0].id_product, 1);
+
+    let boosters = search_products_by_name(&conn, "Alpha", Some(Category::Other(7)), 10).unwrap();
+    assert_eq!(boosters.len(), 1);
+    assert_eq!(boosters[0].id_product, 2);
+}
+
+#[test]
+fn find_products_by_name_matches_whole_names_and_expansions() {
+    let mut conn = test_db();
+    let mut reprint = make_test_product(2, "Delver of Secrets // Insectile Aberration");
+    reprint.id_expansion = 2;
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Delver of Secrets"),
+        reprint,
+        make_test_product(3, "100% Lotus"),
+        make_test_product(4, "100 Lotus"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let mut expansion = make_expansion(2, "Innistrad", None);
+    expansion.code = Some("ISD".to_string());
+    upsert_expansions(&mut conn, &[expansion]).unwrap();
+
+    let ids = |name: &str, exact: bool, expansion: Option<&str>| -> Vec<u64> {
+        find_products_by_name(&conn, name, exact, expansion, 10)
+            .unwrap()
+            .iter()
+            .map(|p| p.id_product)
+            .collect()
+    };
+    // The front face finds the double-faced card, the exact name comes first
+    assert_eq!(ids("delver of secrets", true, None), vec![1, 2]);
+    assert_eq!(ids("Delver of Secrets", true, Some("isd")), vec![2]);
+    assert_eq!(ids("Delver of Secrets", true, Some("Innistrad")), vec![2]);
+    assert_eq!(ids("Delver of Secrets", true, Some("2")), vec![2]);
+    assert!(ids("Delver", true, None).is_empty());
+    assert_eq!(ids("Delver", false, None), vec![1, 2]);
+    // LIKE wildcards in names are literal
+    assert_eq!(ids("100%", false, None), vec![3]);
+}
+
+#[test]
+fn sync_steps_are_tracked_per_day() {
+    let conn = test_db();
+    assert!(!is_sync_step_done(&conn, "2026-03-01", SyncStep::Catalog).unwrap());
+
+    mark_sync_step_done(&conn, "2026-03-01", SyncStep::Catalog).unwrap();
+    mark_sync_step_done(&conn, "2026-03-01", SyncStep::Catalog).unwrap();
+
+    assert!(is_sync_step_done(&conn, "2026-03-01", SyncStep::Catalog).unwrap());
+    assert!(!is_sync_step_done(&conn, "2026-03-01", SyncStep::Prices).unwrap());
+    assert!(!is_sync_step_done(&conn, "2026-03-02", SyncStep::Catalog).unwrap());
+}
+
+#[test]
+fn load_product_catalog_returns_stored_products() {
+    let mut conn = test_db();
+    let mut booster = make_test_product(2, "Alpha Booster");
+    booster.id_category = 7;
+    booster.category_name = "Magic Booster".to_string();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Black Lotus"), booster]);
+    upsert_products(&mut conn, &catalog).unwrap();
+
+    let stored = load_product_catalog(&conn).unwrap();
+
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored.singles_count(), 1);
+    assert_eq!(stored.non_singles_count(), 1);
+    let booster = stored.get(2).unwrap();
+    assert_eq!(booster.name, "Alpha Booster");
+    assert_eq!(booster.category_name, "Magic Booster");
+    assert_eq!(booster.date_added, "2007-01-01 00:00:00");
+}
+
+fn make_expansion(id: u64, name: &str, release_date: Option<&str>) -> ExpansionEntry {
+    ExpansionEntry {
+        id_expansion: id,
+        name: name.to_string(),
+        code: None,
+        release_date: release_date.map(str::to_string),
+    }
+}
+
+#[test]
+fn upsert_expansions_inserts_and_replaces() {
+    let mut conn = test_db();
+
+    let count = upsert_expansions(
+        &mut conn,
+        &[make_expansion(1, "Alpha", Some("1993-08-05T00:00:00+0200"))],
+    )
+    .unwrap();
+    assert_eq!(count, 1);
+
+    // Later list renames the expansion — latest values win
+    upsert_expansions(
+        &mut conn,
+        &[make_expansion(1, "Limited Edition Alpha", None)],
+    )
+    .unwrap();
+
+    let expansions = get_expansions(&conn).unwrap();
+    assert_eq!(expansions.len(), 1);
+    assert_eq!(expansions[0].name, "Limited Edition Alpha");
+    assert!(expansions[0].release_date.is_none());
+}
+
+#[test]
+fn upsert_expansions_stores_date_part_only() {
+    let mut conn = test_db();
+
+    upsert_expansions(
+        &mut conn,
+        &[make_expansion(1, "Alpha", Some("1993-08-05T00:00:00+0200"))],
+    )
+    .unwrap();
+
+    let expansions = get_expansions(&conn).unwrap();
+    assert_eq!(expansions[0].release_date.as_deref(), Some("1993-08-05"));
+}
+
+#[test]
+fn get_expansions_orders_newest_first_undated_last() {
+    let mut conn = test_db();
+
+    upsert_expansions(
+        &mut conn,
+        &[
+            make_expansion(1, "Alpha", Some("1993-08-05")),
+            make_expansion(2, "Promos", None),
+            make_expansion(3, "Foundations", Some("2024-11-15")),
+        ],
+    )
+    .unwrap();
+
+    let names: Vec<String> = get_expansions(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert_eq!(names, vec!["Foundations", "Alpha", "Promos"]);
+}
+
+#[test]
+fn search_prefers_cardmarket_expansion_name_over_scryfall() {
+    let mut conn = test_db();
+
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    upsert_expansion_name(&conn, 1, "Limited Edition Alpha").unwrap();
+    upsert_expansions(&mut conn, &[make_expansion(1, "Alpha", None)]).unwrap();
+
+    let results = search_products_by_name(&conn, "Black Lotus", None, 10).unwrap();
     assert_eq!(results[0].expansion_name.as_deref(), Some("Alpha"));
+
+    let product = get_product_by_id(&conn, 1).unwrap().unwrap();
+    assert_eq!(product.expansion_name.as_deref(), Some("Alpha"));
 }
 
 #[test]
@@ -473,3 +1135,421 @@ fn get_price_snapshots_bulk_omits_missing_pairs() {
     let snapshots = get_price_snapshots_bulk(&conn, &[999], &["2026-02-01".to_string()]).unwrap();
     assert!(snapshots.is_empty());
 }
+
+#[test]
+fn get_price_on_date_picks_nearest_row_either_side() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(1, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (trend, date) in [(2000.0, "2026-01-10"), (2100.0, "2026-01-20")] {
+        let guide = PriceGuide::from_entries(
+            vec![make_test_price_entry(1, Some(trend))],
+            &format!("{date}T10:00:00+0100"),
+        );
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+
+    let exact = get_price_on_date(&conn, 1, "2026-01-20").unwrap().unwrap();
+    assert!(exact.exact);
+    assert_eq!(exact.price.trend, Some(2100.0));
+
+    // Closer to the later row → a row after the requested date is fine
+    let later = get_price_on_date(&conn, 1, "2026-01-18").unwrap().unwrap();
+    assert!(!later.exact);
+    assert_eq!(later.requested_date, "2026-01-18");
+    assert_eq!(later.price.price_date, "2026-01-20");
+
+    // Equally far from both → the earlier row wins
+    let tie = get_price_on_date(&conn, 1, "2026-01-15").unwrap().unwrap();
+    assert_eq!(tie.price.price_date, "2026-01-10");
+
+    // Outside the recorded range → clamps to the nearest end
+    let before = get_price_on_date(&conn, 1, "2025-06-01").unwrap().unwrap();
+    assert_eq!(before.price.price_date, "2026-01-10");
+}
+
+#[test]
+fn get_price_on_date_none_without_history() {
+    let conn = test_db();
+    assert!(get_price_on_date(&conn, 999, "2026-01-01")
+        .unwrap()
+        .is_none());
+}
+
+/// Three products priced on Cardmarket, two of them also on TCGplayer.
+fn tcgplayer_test_db() -> Connection {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Cheap Card"),
+        make_test_product(2, "Pricey Card"),
+        make_test_product(3, "Cardmarket Only"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(1, Some(2.0)),
+            make_test_price_entry(2, Some(20.0)),
+            make_test_price_entry(3, Some(30.0)),
+        ],
+        "2026-02-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let prices = [
+        TcgplayerPrice {
+            id_product: 1,
+            usd: Some(4.0),
+            usd_foil: None,
+        },
+        TcgplayerPrice {
+            id_product: 2,
+            usd: Some(22.0),
+            usd_foil: Some(40.0),
+        },
+        // Unknown product is skipped
+        TcgplayerPrice {
+            id_product: 99,
+            usd: Some(1.0),
+            usd_foil: None,
+        },
+    ];
+    assert_eq!(
+        insert_tcgplayer_prices(&mut conn, "2026-02-01", &prices).unwrap(),
+        2
+    );
+    conn
+}
+
+#[test]
+fn tcgplayer_candidates_sorted_by_trend() {
+    let conn = tcgplayer_test_db();
+    assert_eq!(
+        get_tcgplayer_candidates(&conn, 5.0, 10).unwrap(),
+        vec![3, 2]
+    );
+    assert_eq!(get_tcgplayer_candidates(&conn, 0.0, 1).unwrap(), vec![3]);
+    assert!(has_tcgplayer_prices_for_date(&conn, "2026-02-01").unwrap());
+    assert!(!has_tcgplayer_prices_for_date(&conn, "2026-02-02").unwrap());
+}
+
+#[test]
+fn price_comparisons_need_exchange_rate() {
+    let conn = tcgplayer_test_db();
+    assert!(get_price_comparisons(&conn, 0.0, 10).unwrap().is_empty());
+    assert_eq!(get_price_comparison(&conn, 1).unwrap(), None);
+}
+
+#[test]
+fn price_comparisons_sorted_by_spread() {
+    let conn = tcgplayer_test_db();
+    upsert_exchange_rate(&conn, "2026-02-01", "USD", 1.0).unwrap();
+    upsert_exchange_rate(&conn, "2026-01-31", "USD", 2.0).unwrap();
+
+    let rows = get_price_comparisons(&conn, 0.0, 10).unwrap();
+    // Cheap card: 4 USD vs 2 EUR (+100%), pricey card: 22 USD vs 20 EUR (+10%)
+    assert_eq!(
+        rows.iter().map(|r| r.id_product).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+    assert_eq!(rows[0].usd_per_eur, 1.0);
+    assert!((rows[0].spread_pct - 100.0).abs() < 1e-9);
+    assert_eq!(rows[1].name, "Pricey Card");
+
+    let rows = get_price_comparisons(&conn, 5.0, 10).unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id_product, 2);
+    assert_eq!(get_price_comparisons(&conn, 0.0, 1).unwrap().len(), 1);
+
+    let single = get_price_comparison(&conn, 2).unwrap().unwrap();
+    assert_eq!(single, rows[0]);
+    assert_eq!(get_price_comparison(&conn, 3).unwrap(), None);
+}
+
+#[test]
+fn price_movers_compare_the_two_latest_dates() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Riser"),
+        make_test_product(2, "Faller"),
+        make_test_product(3, "Bulk"),
+        make_test_product(4, "Steady"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (created_at, prices) in [
+        ("2026-02-27T10:00:00+0100", [50.0, 50.0, 0.02, 3.0]),
+        ("2026-02-28T10:00:00+0100", [10.0, 20.0, 0.02, 3.0]),
+        ("2026-03-01T10:00:00+0100", [11.0, 15.0, 0.05, 3.0]),
+    ] {
+        let entries = (1..=4)
+            .map(|id| make_test_price_entry(id, Some(prices[id as usize - 1])))
+            .collect();
+        let guide = PriceGuide::from_entries(entries, created_at);
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+
+    let movers = get_price_movers(&conn, 1.0, 10).unwrap();
+
+    assert_eq!(
+        movers.iter().map(|m| m.id_product).collect::<Vec<_>>(),
+        vec![2, 1, 4]
+    );
+    assert_eq!(movers[0].previous_date, "2026-02-28");
+    assert_eq!(movers[0].price_date, "2026-03-01");
+    assert!((movers[0].change_pct + 25.0).abs() < 1e-9);
+    assert!((movers[1].change_pct - 10.0).abs() < 1e-9);
+    assert_eq!(get_price_movers(&conn, 0.0, 1).unwrap()[0].id_product, 3);
+}
+
+#[test]
+fn price_movers_need_two_dates() {
+    let conn = tcgplayer_test_db();
+    assert!(get_price_movers(&conn, 0.0, 10).unwrap().is_empty());
+}
+
+#[test]
+fn price_wantslist_uses_cheapest_printing_as_of_each_date() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Lightning Bolt"),
+        make_test_product(2, "Lightning Bolt"),
+        make_test_product(3, "Delver of Secrets // Insectile Aberration"),
+        make_test_product(4, "Lightning_Bolt"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (created_at, prices) in [
+        ("2026-01-10T10:00:00+0100", [3.0, 5.0, 1.0, 0.1]),
+        ("2026-06-10T10:00:00+0100", [6.0, 4.0, 2.0, 0.1]),
+    ] {
+        let entries = (1..=4)
+            .map(|id| make_test_price_entry(id, Some(prices[id as usize - 1])))
+            .collect();
+        let guide = PriceGuide::from_entries(entries, created_at);
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    let card = |quantity, name: &str| WantslistCard {
+        quantity,
+        name: name.to_string(),
+    };
+    let dates = vec![
+        "2025-12-31".to_string(),
+        "2026-02-01".to_string(),
+        "2026-07-01".to_string(),
+    ];
+
+    let quote = price_wantslist(
+        &conn,
+        &[
+            card(4, "lightning bolt"),
+            card(1, "Delver of Secrets"),
+            card(1, "Nonexistent"),
+        ],
+        &dates,
+    )
+    .unwrap();
+
+    let bolt = &quote.lines[0].prices;
+    assert_eq!(bolt[0], None);
+    let feb = bolt[1].as_ref().unwrap();
+    assert_eq!((feb.id_product, feb.trend), (1, 3.0));
+    assert_eq!(feb.price_date, "2026-01-10");
+    assert_eq!(bolt[2].as_ref().unwrap().id_product, 2);
+    assert_eq!(quote.lines[1].prices[2].as_ref().unwrap().trend, 2.0);
+    assert!(quote.lines[2].prices.iter().all(Option::is_none));
+    assert_eq!(quote.totals[0].priced_cards, 0);
+    assert_eq!(quote.totals[1].total, 13.0);
+    assert_eq!(quote.totals[2].total, 18.0);
+    assert_eq!(quote.totals[2].priced_cards, 2);
+}
+
+#[test]
+fn audit_entries_newest_first_with_since_and_limit() {
+    let conn = test_db();
+    insert_audit_entry(&conn, "GET", "/api/admin/audit", 401, 1, "10.0.0.2").unwrap();
+    insert_audit_entry(&conn, "GET", "/api/admin/audit", 200, 3, "10.0.0.3").unwrap();
+    conn.execute(
+        "UPDATE audit_log SET requested_at = '2026-01-01 08:00:00' WHERE id = 1",
+        [],
+    )
+    .unwrap();
+
+    let entries = get_audit_entries(&conn, None, 10).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].status, 200);
+    assert_eq!(entries[0].client_ip, "10.0.0.3");
+    assert_eq!(entries[1].requested_at, "2026-01-01 08:00:00");
+
+    assert_eq!(get_audit_entries(&conn, None, 1).unwrap().len(), 1);
+    let recent = get_audit_entries(&conn, Some("2026-01-02"), 10).unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].id, 2);
+}
+
+#[test]
+fn tenants_are_looked_up_by_token_hash() {
+    let mut conn = test_db();
+    let alice = create_tenant(&conn, "alice", "hash-a").unwrap();
+    let bob = create_tenant(&conn, "bob", "hash-b").unwrap();
+    assert!(create_tenant(&conn, "alice", "hash-c").is_err());
+    assert!(create_tenant(&conn, "carol", "hash-a").is_err());
+
+    assert_eq!(
+        get_tenant_by_token_hash(&conn, "hash-b").unwrap(),
+        Some(bob.clone())
+    );
+    assert_eq!(get_tenant_by_token_hash(&conn, "nope").unwrap(), None);
+    assert_eq!(get_tenants(&conn).unwrap(), vec![alice.clone(), bob]);
+
+    assert!(delete_tenant(&mut conn, alice.id).unwrap());
+    assert!(!delete_tenant(&mut conn, alice.id).unwrap());
+    assert_eq!(get_tenants(&conn).unwrap().len(), 1);
+}
+
+#[test]
+fn watchlists_are_scoped_per_tenant() {
+    let mut conn = test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Zodiac Dragon"),
+        make_test_product(2, "Ancestral Recall"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(4.0))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let alice = create_tenant(&conn, "alice", "hash-a").unwrap();
+    let bob = create_tenant(&conn, "bob", "hash-b").unwrap();
+
+    assert!(add_to_watchlist(&conn, alice.id, 1).unwrap());
+    assert!(!add_to_watchlist(&conn, alice.id, 1).unwrap());
+    assert!(add_to_watchlist(&conn, alice.id, 2).unwrap());
+    assert!(add_to_watchlist(&conn, bob.id, 2).unwrap());
+
+    let list = get_watchlist(&conn, alice.id).unwrap();
+    assert_eq!(
+        list.iter().map(|e| e.id_product).collect::<Vec<_>>(),
+        vec![2, 1],
+        "sorted by name"
+    );
+    assert_eq!(list[0].trend, None);
+    assert_eq!(list[1].trend, Some(4.0));
+    assert_eq!(list[1].price_date.as_deref(), Some("2026-03-01"));
+
+    assert!(remove_from_watchlist(&conn, bob.id, 2).unwrap());
+    assert!(!remove_from_watchlist(&conn, bob.id, 1).unwrap());
+    assert!(get_watchlist(&conn, bob.id).unwrap().is_empty());
+    assert_eq!(get_watchlist(&conn, alice.id).unwrap().len(), 2);
+
+    delete_tenant(&mut conn, alice.id).unwrap();
+    let rows: i64 = conn
+        .query_row("SELECT COUNT(*) FROM watchlist", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(rows, 0);
+}
+
+fn make_set(code: &str, name: &str, released_at: Option<&str>) -> ScryfallSet {
+    ScryfallSet {
+        code: code.to_string(),
+        name: name.to_string(),
+        icon_svg_uri: String::new(),
+        released_at: released_at.map(str::to_string),
+        set_type: Some("expansion".to_string()),
+        digital: false,
+    }
+}
+
+#[test]
+fn upcoming_sets_are_matched_to_expansions() {
+    let mut conn = test_db();
+    upsert_expansions(
+        &mut conn,
+        &[
+            ExpansionEntry {
+                code: Some("FUT".to_string()),
+                ..make_expansion(1, "Cardmarket Future", None)
+            },
+            make_expansion(2, "Later Set", None),
+        ],
+    )
+    .unwrap();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Preorder Booster"),
+        make_test_product(2, "Preorder Bundle"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+
+    let stored = replace_upcoming_sets(
+        &mut conn,
+        &[
+            make_set("LAT", "later set", Some("2026-05-01")),
+            make_set("FUT", "Future Set", Some("2026-03-10")),
+            make_set("far", "Far Away", Some("2027-01-01")),
+            make_set("tba", "Undated", None),
+        ],
+    )
+    .unwrap();
+    assert_eq!(stored, 3);
+
+    let sets = get_upcoming_sets(&conn, "2026-03-01", 90).unwrap();
+    assert_eq!(
+        sets.iter().map(|s| s.code.as_str()).collect::<Vec<_>>(),
+        vec!["fut", "lat"]
+    );
+    assert_eq!(sets[0].days_until, 9);
+    assert_eq!(sets[0].id_expansion, Some(1), "matched by code");
+    assert_eq!(sets[0].products, 2);
+    assert_eq!(sets[1].id_expansion, Some(2), "matched by name");
+    assert_eq!(sets[1].products, 0);
+
+    // A refresh replaces the whole calendar
+    replace_upcoming_sets(&mut conn, &[]).unwrap();
+    assert!(get_upcoming_sets(&conn, "2026-03-01", 366)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn new_products_of_upcoming_sets_are_watched_on_opt_in() {
+    let mut conn = test_db();
+    upsert_expansions(
+        &mut conn,
+        &[
+            make_expansion(1, "Future Set", None),
+            make_expansion(2, "Alpha", None),
+        ],
+    )
+    .unwrap();
+    let old = ProductCatalog::from_entries(vec![make_test_product(1, "Old Booster")]);
+    upsert_products(&mut conn, &old).unwrap();
+    let mut alpha = make_test_product(3, "Black Lotus");
+    alpha.id_expansion = 2;
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Old Booster"),
+        make_test_product(2, "New Booster"),
+        alpha,
+    ]);
+
+    let new_ids = get_unknown_product_ids(&conn, &catalog).unwrap();
+    assert_eq!(new_ids, vec![2, 3]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    replace_upcoming_sets(
+        &mut conn,
+        &[make_set("fut", "Future Set", Some("2026-03-10"))],
+    )
+    .unwrap();
+    let alice = create_tenant(&conn, "alice", "hash-a").unwrap();
+    let bob = create_tenant(&conn, "bob", "hash-b").unwrap();
+    assert!(set_watch_new_sets(&conn, alice.id, true).unwrap());
+    assert!(get_watch_new_sets(&conn, alice.id).unwrap());
+    assert!(!get_watch_new_sets(&conn, bob.id).unwrap());
+    assert!(!set_watch_new_sets(&conn, 999, true).unwrap());
+
+    assert_eq!(watch_new_set_products(&mut conn, &new_ids).unwrap(), 1);
+    assert_eq!(watch_new_set_products(&mut conn, &new_ids).unwrap(), 0);
+
+    let watched = get_watchlist(&conn, alice.id).unwrap();
+    assert_eq!(
+        watched.iter().map(|e| e.id_product).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert!(get_watchlist(&conn, bob.id).unwrap().is_empty());
+}
@@ -0,0 +1,147 @@
+//! Bandwidth limits and time windows for the large Cardmarket downloads
+//!
+//! The product catalogs and price guides are tens of megabytes each; fetched
+//! at full speed they can saturate a shop's DSL line. [`DownloadLimits`]
+//! caps the transfer rate of those downloads and restricts syncs to an
+//! allowed time of day (Europe/Berlin, like the price dates), e.g.
+//! `02:00-06:00`. The daemon checks every `interval_hours`, so a sync due
+//! outside the window simply runs at the first check inside it.
+
+use crate::error::{InventoryError, Result};
+use chrono::{NaiveTime, Timelike};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Request timeout of an unthrottled download
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+/// Request timeout of a throttled download, which may legitimately take long
+const THROTTLED_TIMEOUT: Duration = Duration::from_secs(3 * 3600);
+
+/// Time of day syncs may download in, e.g. `02:00-06:00`; a window ending
+/// before it starts (`22:00-06:00`) spans midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl DownloadWindow {
+    /// Whether `time` is inside the window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Whether the current Europe/Berlin time is inside the window
+    pub fn is_open_now(&self) -> bool {
+        self.contains(mtg_common::dates::market_time(chrono::Utc::now()))
+    }
+}
+
+impl FromStr for DownloadWindow {
+    type Err = InventoryError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            InventoryError::Config(format!(
+                "invalid download window '{s}', expected HH:MM-HH:MM"
+            ))
+        };
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let time = |t: &str| NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| invalid());
+        let window = Self {
+            start: time(start)?,
+            end: time(end)?,
+        };
+        if window.start == window.end {
+            return Err(InventoryError::Config(format!(
+                "download window '{s}' is empty"
+            )));
+        }
+        Ok(window)
+    }
+}
+
+impl fmt::Display for DownloadWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start.hour(),
+            self.start.minute(),
+            self.end.hour(),
+            self.end.minute()
+        )
+    }
+}
+
+/// Limits applied to the large downloads of a sync; the default has none
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DownloadLimits {
+    /// Maximum transfer rate in kbit/s (e.g. 8000 for 8 Mbit/s)
+    pub max_kbit_per_sec: Option<u64>,
+    /// Only sync inside this time window
+    pub window: Option<DownloadWindow>,
+}
+
+impl DownloadLimits {
+    fn bytes_per_sec(&self) -> Option<u64> {
+        self.max_kbit_per_sec
+            .filter(|&kbit| kbit > 0)
+            .map(|kbit| (kbit * 1000 / 8).max(1))
+    }
+
+    /// Whether a sync may download now
+    pub fn may_download_now(&self) -> bool {
+        self.window.is_none_or(|w| w.is_open_now())
+    }
+}
+
+/// How long to wait so that `bytes` received since `started` stay within
+/// `bytes_per_sec`
+fn throttle_delay(bytes: u64, bytes_per_sec: u64, elapsed: Duration) -> Option<Duration> {
+    let due = Duration::from_secs_f64(bytes as f64 / bytes_per_sec as f64);
+    due.checked_sub(elapsed).filter(|d| !d.is_zero())
+}
+
+/// Downloads `url` as text, at most at the configured rate.
+pub async fn fetch_text(url: &str, limits: &DownloadLimits) -> Result<String> {
+    let bytes_per_sec = limits.bytes_per_sec();
+    let timeout = if bytes_per_sec.is_some() {
+        THROTTLED_TIMEOUT
+    } else {
+        HTTP_TIMEOUT
+    };
+    let mut response = mtg_common::http::client_builder(timeout)?
+        .build()?
+        .get(url)
+        .header("User-Agent", mtg_common::USER_AGENT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(InventoryError::HttpStatus(response.status()));
+    }
+
+    let Some(bytes_per_sec) = bytes_per_sec else {
+        return Ok(response.text().await?);
+    };
+    log::info!("Downloading {} at up to {} kB/s", url, bytes_per_sec / 1000);
+    let started = Instant::now();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if let Some(delay) = throttle_delay(body.len() as u64, bytes_per_sec, started.elapsed()) {
+            tokio::time::sleep(delay).await;
+        }
+    }
+    String::from_utf8(body)
+        .map_err(|e| InventoryError::Config(format!("{url} is not valid UTF-8: {e}")))
+}
+
+#[cfg(test)]
+#[path = "download_tests.rs"]
+mod tests;
@@ -0,0 +1,56 @@
+//! Tests for download.
+
+use super::*;
+
+fn at(h: u32, m: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+#[test]
+fn parses_and_checks_windows() {
+    let night: DownloadWindow = "02:00-06:00".parse().unwrap();
+    assert_eq!(night.start, at(2, 0));
+    assert!(night.contains(at(2, 0)));
+    assert!(night.contains(at(5, 59)));
+    assert!(!night.contains(at(6, 0)));
+    assert!(!night.contains(at(14, 0)));
+    assert_eq!(night.to_string(), "02:00-06:00");
+
+    // Ending before it starts spans midnight
+    let overnight: DownloadWindow = " 22:30 - 6:00 ".parse().unwrap();
+    assert!(overnight.contains(at(23, 0)));
+    assert!(overnight.contains(at(1, 0)));
+    assert!(!overnight.contains(at(12, 0)));
+    assert_eq!(overnight.to_string(), "22:30-06:00");
+}
+
+#[test]
+fn rejects_malformed_windows() {
+    for input in ["02:00", "2-6", "02:00-25:00", "03:00-03:00", ""] {
+        assert!(
+            input.parse::<DownloadWindow>().is_err(),
+            "{input:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn throttles_to_the_configured_rate() {
+    let limits = DownloadLimits {
+        max_kbit_per_sec: Some(8000),
+        window: None,
+    };
+    assert_eq!(limits.bytes_per_sec(), Some(1_000_000));
+    assert_eq!(DownloadLimits::default().bytes_per_sec(), None);
+    assert!(DownloadLimits::default().may_download_now());
+
+    // 2 MB at 1 MB/s are due after two seconds
+    assert_eq!(
+        throttle_delay(2_000_000, 1_000_000, Duration::from_millis(500)),
+        Some(Duration::from_millis(1500))
+    );
+    assert_eq!(
+        throttle_delay(2_000_000, 1_000_000, Duration::from_secs(3)),
+        None
+    );
+}
@@ -19,6 +19,16 @@ pub enum InventoryError {
     NoImageAvailable(String),
     /// Failed to fetch image from URL
     ImageFetchFailed(String),
+    /// Exchange rate feed did not contain the requested rate
+    ExchangeRateUnavailable(String),
+    /// A manual price date override was rejected
+    InvalidPriceDate(String),
+    /// The config file could not be read or parsed
+    Config(String),
+    /// An alert or watchlist import was rejected
+    InvalidAlerts(String),
+    /// Reading or writing a local file failed
+    Io(std::io::Error),
 }
 
 impl fmt::Display for InventoryError {
@@ -37,6 +47,15 @@ impl fmt::Display for InventoryError {
             InventoryError::ImageFetchFailed(url) => {
                 write!(f, "Failed to fetch image from: {}", url)
             }
+            InventoryError::ExchangeRateUnavailable(url) => {
+                write!(f, "No USD exchange rate in feed: {}", url)
+            }
+            InventoryError::InvalidPriceDate(reason) => {
+                write!(f, "Invalid price date: {}", reason)
+            }
+            InventoryError::Config(reason) => write!(f, "Config error: {}", reason),
+            InventoryError::InvalidAlerts(reason) => write!(f, "Invalid alerts: {}", reason),
+            InventoryError::Io(e) => write!(f, "I/O error: {}", e),
         }
     }
 }
@@ -51,6 +70,11 @@ impl std::error::Error for InventoryError {
             InventoryError::ScryfallNotFound(_) => None,
             InventoryError::NoImageAvailable(_) => None,
             InventoryError::ImageFetchFailed(_) => None,
+            InventoryError::ExchangeRateUnavailable(_) => None,
+            InventoryError::InvalidPriceDate(_) => None,
+            InventoryError::Config(_) => None,
+            InventoryError::InvalidAlerts(_) => None,
+            InventoryError::Io(e) => Some(e),
         }
     }
 }
@@ -61,6 +85,12 @@ impl From<reqwest::Error> for InventoryError {
     }
 }
 
+impl From<std::io::Error> for InventoryError {
+    fn from(err: std::io::Error) -> Self {
+        InventoryError::Io(err)
+    }
+}
+
 impl From<serde_json::Error> for InventoryError {
     fn from(err: serde_json::Error) -> Self {
         InventoryError::Parse(err)
@@ -85,6 +115,7 @@ impl From<mtg_common::MtgError> for InventoryError {
             mtg_common::MtgError::Io(_) => {
                 InventoryError::ImageFetchFailed("I/O error".to_string())
             }
+            mtg_common::MtgError::Config(reason) => InventoryError::Config(reason),
         }
     }
 }
@@ -0,0 +1,68 @@
+//! CSV export of one day's prices
+//!
+//! `inventory_sync export` writes the prices of one price date (default: the
+//! latest import) with the columns of the `latest_prices` reporting view —
+//! product name, category and expansion next to every price field — so a
+//! day's prices can go into a spreadsheet without opening the database.
+
+use crate::database::get_latest_price_date;
+use crate::error::Result;
+use crate::views::{PRODUCT_COLUMNS, PRODUCT_JOINS};
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use std::io::Write;
+
+/// Writes the prices of `date` (default: the latest price date) as CSV with
+/// a header row, ordered by product ID. Returns the price date and the number
+/// of rows written; `None` as date when the database has no prices yet.
+pub fn export_prices_csv(
+    conn: &Connection,
+    date: Option<&str>,
+    out: &mut impl Write,
+) -> Result<(Option<String>, usize)> {
+    let date = match date {
+        Some(date) => Some(date.to_string()),
+        None => get_latest_price_date(conn)?,
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {PRODUCT_COLUMNS}
+         FROM price_history ph {PRODUCT_JOINS}
+         WHERE ph.price_date = ?1
+         ORDER BY p.id_product"
+    ))?;
+    let header: Vec<String> = stmt.column_names().iter().map(|c| csv_field(c)).collect();
+    writeln!(out, "{}", header.join(","))?;
+
+    let columns = stmt.column_count();
+    let mut rows = stmt.query([&date])?;
+    let mut written = 0;
+    while let Some(row) = rows.next()? {
+        let fields = (0..columns)
+            .map(|i| {
+                Ok(match row.get_ref(i)? {
+                    ValueRef::Null => String::new(),
+                    ValueRef::Integer(n) => n.to_string(),
+                    ValueRef::Real(x) => x.to_string(),
+                    ValueRef::Text(text) => csv_field(&String::from_utf8_lossy(text)),
+                    ValueRef::Blob(_) => String::new(),
+                })
+            })
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        writeln!(out, "{}", fields.join(","))?;
+        written += 1;
+    }
+    Ok((date, written))
+}
+
+/// Quotes a field that contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+#[path = "export_tests.rs"]
+mod tests;
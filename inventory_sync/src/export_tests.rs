@@ -0,0 +1,82 @@
+//! Tests for export.
+
+use super::*;
+use crate::cardmarket::{make_test_price_entry, make_test_product, PriceGuide, ProductCatalog};
+use crate::database::{init_schema, insert_price_history, upsert_products};
+
+/// Two products priced on two days
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Borborygmos, Enraged"),
+        make_test_product(2, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (created, trend) in [
+        ("2026-02-01T10:00:00+0100", 4.0),
+        ("2026-02-02T10:00:00+0100", 5.0),
+    ] {
+        let guide = PriceGuide::from_entries(
+            vec![
+                make_test_price_entry(1, Some(trend)),
+                make_test_price_entry(2, None),
+            ],
+            created,
+        );
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    conn
+}
+
+fn export(conn: &Connection, date: Option<&str>) -> (Option<String>, usize, Vec<String>) {
+    let mut out = Vec::new();
+    let (date, rows) = export_prices_csv(conn, date, &mut out).unwrap();
+    let lines = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    (date, rows, lines)
+}
+
+#[test]
+fn exports_the_latest_day_by_default() {
+    let (date, rows, lines) = export(&test_db(), None);
+
+    assert_eq!(date.as_deref(), Some("2026-02-02"));
+    assert_eq!(rows, 2);
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("id_product,name,id_category,category_name,"));
+    assert!(lines[0].ends_with(",avg30_foil"));
+    // Names with commas are quoted, missing prices are empty
+    assert!(lines[1].starts_with("1,\"Borborygmos, Enraged\",1,Magic Single,1,,,2026-02-02,5,4,5,"));
+    assert!(lines[2].starts_with("2,Mox Pearl,1,Magic Single,1,,,2026-02-02,,,,"));
+}
+
+#[test]
+fn exports_a_chosen_day() {
+    let (date, rows, lines) = export(&test_db(), Some("2026-02-01"));
+
+    assert_eq!(date.as_deref(), Some("2026-02-01"));
+    assert_eq!(rows, 2);
+    assert!(lines[1].contains(",2026-02-01,4,"));
+}
+
+#[test]
+fn day_without_prices_exports_only_the_header() {
+    let (_, rows, lines) = export(&test_db(), Some("2026-03-01"));
+
+    assert_eq!(rows, 0);
+    assert_eq!(lines.len(), 1);
+}
+
+#[test]
+fn empty_database_has_no_price_date() {
+    let conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+
+    let (date, rows, _) = export(&conn, None);
+
+    assert_eq!((date, rows), (None, 0));
+}
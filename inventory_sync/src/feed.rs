@@ -0,0 +1,94 @@
+//! Atom feed of the daily price movers
+//!
+//! `GET /api/movers.atom` lists the products whose trend price moved the
+//! most between the two latest price guide imports, one entry per product.
+//! Entry titles carry the percentage change so it shows in a feed reader's
+//! list, and each entry links to the product's price chart in the web UI
+//! (`/?product=<id>`). Entry IDs include the price date, so the same product
+//! moving again on a later day shows up as a new entry.
+
+use crate::database::PriceMover;
+
+/// Escapes text for use in XML element content and attribute values.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Link to the product's price chart in the web UI.
+pub fn chart_url(base_url: &str, id_product: u64) -> String {
+    format!("{}/?product={id_product}", base_url.trim_end_matches('/'))
+}
+
+/// Entry title, e.g. `+12.5% Lightning Bolt (Alpha)`.
+pub fn entry_title(mover: &PriceMover) -> String {
+    match &mover.expansion_name {
+        Some(expansion) => format!("{:+.1}% {} ({expansion})", mover.change_pct, mover.name),
+        None => format!("{:+.1}% {}", mover.change_pct, mover.name),
+    }
+}
+
+/// The feed document. `base_url` is the server's external address
+/// (`http://host:port`); `updated` is used when there are no movers.
+pub fn movers_feed(movers: &[PriceMover], base_url: &str, updated: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let updated = movers
+        .first()
+        .map_or_else(|| updated.to_string(), |m| atom_date(&m.price_date));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("  <title>Cardmarket price movers</title>\n");
+    xml.push_str(&format!(
+        "  <id>{0}/api/movers.atom</id>\n  <link rel=\"self\" href=\"{0}/api/movers.atom\"/>\n  <link href=\"{0}/\"/>\n",
+        escape_xml(base_url)
+    ));
+    xml.push_str(&format!("  <updated>{updated}</updated>\n"));
+    xml.push_str("  <author><name>inventory_sync</name></author>\n");
+    for mover in movers {
+        let link = escape_xml(&chart_url(base_url, mover.id_product));
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry_title(mover))
+        ));
+        xml.push_str(&format!("    <link href=\"{link}\"/>\n"));
+        xml.push_str(&format!(
+            "    <id>{link}&amp;date={}</id>\n",
+            escape_xml(&mover.price_date)
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            atom_date(&mover.price_date)
+        ));
+        xml.push_str(&format!(
+            "    <summary>Trend {:.2} € on {} → {:.2} € on {}</summary>\n",
+            mover.previous_trend,
+            escape_xml(&mover.previous_date),
+            mover.trend,
+            escape_xml(&mover.price_date)
+        ));
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// `YYYY-MM-DD` as an RFC 3339 timestamp at midnight UTC.
+fn atom_date(date: &str) -> String {
+    format!("{}T00:00:00Z", escape_xml(date))
+}
+
+#[cfg(test)]
+#[path = "feed_tests.rs"]
+mod tests;
@@ -0,0 +1,48 @@
+//! Tests for the price movers feed.
+
+use super::*;
+
+fn mover(id_product: u64, name: &str, previous_trend: f64, trend: f64) -> PriceMover {
+    PriceMover {
+        id_product,
+        name: name.to_string(),
+        expansion_name: Some("Alpha".to_string()),
+        price_date: "2026-03-02".to_string(),
+        previous_date: "2026-03-01".to_string(),
+        previous_trend,
+        trend,
+        change_pct: (trend - previous_trend) / previous_trend * 100.0,
+    }
+}
+
+#[test]
+fn titles_lead_with_the_signed_change() {
+    assert_eq!(
+        entry_title(&mover(1, "Lightning Bolt", 2.0, 2.25)),
+        "+12.5% Lightning Bolt (Alpha)"
+    );
+    let mut drop = mover(2, "Shock", 1.0, 0.5);
+    drop.expansion_name = None;
+    assert_eq!(entry_title(&drop), "-50.0% Shock");
+}
+
+#[test]
+fn feed_links_entries_to_the_chart_and_escapes_names() {
+    let movers = [mover(7, "Research & Development", 4.0, 5.0)];
+
+    let xml = movers_feed(&movers, "http://cards.local:3000/", "2026-01-01T00:00:00Z");
+
+    assert!(xml.contains("<link href=\"http://cards.local:3000/?product=7\"/>"));
+    assert!(xml.contains("<title>+25.0% Research &amp; Development (Alpha)</title>"));
+    assert!(xml.contains("<id>http://cards.local:3000/?product=7&amp;date=2026-03-02</id>"));
+    assert!(xml.contains("<updated>2026-03-02T00:00:00Z</updated>"));
+    assert_eq!(xml.matches("<entry>").count(), 1);
+}
+
+#[test]
+fn empty_feed_uses_the_given_timestamp() {
+    let xml = movers_feed(&[], "http://cards.local", "2026-01-01T00:00:00Z");
+
+    assert!(xml.contains("<updated>2026-01-01T00:00:00Z</updated>"));
+    assert!(!xml.contains("<entry>"));
+}
@@ -79,6 +79,18 @@ impl ImageCache {
     }
 }
 
+/// MIME type of image bytes, sniffed from the file signature. Scryfall serves
+/// JPEGs for the regular sizes and PNGs for the `png` size.
+pub fn image_content_type(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        "image/jpeg"
+    }
+}
+
 /// Fetch a card image by Cardmarket product ID, checking cache first.
 /// Also caches card metadata alongside the image.
 pub async fn fetch_image_cached(
@@ -83,3 +83,11 @@ fn test_different_products_cached_separately() {
         Some("Beta")
     );
 }
+
+#[test]
+fn test_image_content_type() {
+    assert_eq!(image_content_type(&[0xFF, 0xD8, 0xFF, 0xE0]), "image/jpeg");
+    assert_eq!(image_content_type(b"\x89PNG\r\n\x1a\n\0\0"), "image/png");
+    assert_eq!(image_content_type(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+    assert_eq!(image_content_type(b""), "image/jpeg");
+}
@@ -3,18 +3,40 @@
 //! This application syncs MTG card inventory from CSV exports to a SQLite database
 //! and collects pricing data on a regular schedule.
 
+pub mod alert_config;
+pub mod alerts;
+pub mod backup;
 pub mod cardmarket;
 pub mod database;
+pub mod download;
 pub mod error;
+pub mod export;
+pub mod feed;
 pub mod image_cache;
 pub mod indicators;
+pub mod notify;
+pub mod revenue;
 pub mod scryfall;
+pub mod slow_queries;
+pub mod stock;
+pub mod sync;
+pub mod tcgplayer;
+pub mod valuation;
+pub mod views;
 pub mod web;
 
-pub use cardmarket::{PriceGuide, PriceGuideEntry, ProductCatalog, ProductEntry};
+pub use cardmarket::{
+    fetch_expansions, ExpansionEntry, PriceGuide, PriceGuideEntry, ProductCatalog, ProductEntry,
+};
 pub use database::{
-    get_id_expansion_for_product, get_price_history, get_product_by_id, has_price_data_for_today,
-    init_schema, insert_price_history, search_products_by_name, upsert_expansion_name,
-    upsert_products, InsertResult, PriceHistoryPoint, ProductSearchResult,
+    get_database_stats, get_expansions, get_id_expansion_for_product, get_missing_price_dates,
+    get_pending_import, get_price_history, get_product_by_id, get_product_names, get_sync_run,
+    get_tcgplayer_candidates, has_price_data_for_today, has_tcgplayer_prices_for_date, init_schema,
+    insert_price_history, insert_price_history_for_categories, insert_price_history_for_date,
+    insert_tcgplayer_prices, price_wantslist, search_products_by_name, upsert_exchange_rate,
+    upsert_expansion_name, upsert_expansions, upsert_products, DatabaseStats, ExpansionInfo,
+    InsertResult, PriceComparison, PriceHistoryPoint, ProductNames, ProductRename,
+    ProductSearchResult, SyncRun, Tenant, WatchlistEntry,
 };
 pub use error::{InventoryError, Result};
+pub use views::create_views;
@@ -1,49 +1,264 @@
 //! Inventory Sync - MTG Stock & Pricing Database
 //!
 //! Syncs card inventory from CSV exports to SQLite and collects pricing data.
-//! Runs continuously with daily sync scheduling.
+//! Runs continuously with daily sync scheduling (`serve`, the default), or
+//! one command at a time: `sync [--force]`, `stats`, `export` and the
+//! maintenance commands.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use inventory_sync::alert_config::{
+    add_alerts_from_csv, export_alert_config, import_alert_config, parse_alert_csv, AlertConfig,
+};
+use inventory_sync::backup::BackupOptions;
+use inventory_sync::database::get_tenants;
+use inventory_sync::download::DownloadLimits;
+use inventory_sync::export::export_prices_csv;
+use inventory_sync::slow_queries;
+use inventory_sync::sync::{run_sync, CardmarketSources, SyncOptions};
+use inventory_sync::web::WebOptions;
 use inventory_sync::{
-    has_price_data_for_today, init_schema, insert_price_history, upsert_products, PriceGuide,
-    ProductCatalog,
+    create_views, get_database_stats, has_price_data_for_today, init_schema,
+    insert_price_history_for_categories, insert_price_history_for_date, upsert_products,
+    InventoryError, PriceGuide, ProductCatalog, Tenant,
 };
+use mtg_common::config::{self, D2dConfig, InventorySyncConfig};
+use mtg_common::Category;
 use rusqlite::Connection;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::interval;
 
 /// MTG inventory sync server - collects pricing data and syncs to SQLite
+///
+/// Every option can also be set in the `[inventory_sync]` table of the shared
+/// config file or via env vars; flags given here win over both.
 #[derive(Parser, Debug)]
 #[command(name = "inventory_sync")]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Path to the SQLite database file
-    #[arg(short, long, default_value_t = default_db_path())]
-    database: String,
+    /// Config file (default: $D2D_CONFIG, else config.toml in the
+    /// d2d_automations data dir)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Print the effective configuration (tokens redacted) and exit
+    #[arg(long, default_value_t = false)]
+    print_config: bool,
+
+    /// Path to the SQLite database file (default:
+    /// ~/.local/share/inventory_sync/inventory.db)
+    #[arg(short, long)]
+    database: Option<PathBuf>,
 
-    /// Run once and exit (default: run continuously with daily schedule)
+    /// Run once and exit, like the `sync` command (default: run
+    /// continuously with daily schedule)
     #[arg(long, default_value_t = false)]
     once: bool,
 
-    /// Check interval in hours when running continuously
-    #[arg(long, default_value_t = 1)]
-    interval_hours: u64,
+    /// Check interval in hours when running continuously (default: 1)
+    #[arg(long)]
+    interval_hours: Option<u64>,
 
     /// Enable web UI on specified port (default: disabled)
     #[arg(long)]
     web_port: Option<u16>,
+
+    /// Also collect TCGplayer prices (via Scryfall) for products whose
+    /// Cardmarket trend is at least this many EUR (default: disabled)
+    #[arg(long)]
+    tcgplayer_min_trend: Option<f64>,
+
+    /// Only store prices for these Cardmarket categories: IDs or `singles`
+    /// (comma-separated; default: all)
+    #[arg(long, value_delimiter = ',')]
+    categories: Vec<Category>,
+
+    /// Record requests to the web server's admin endpoints in the database
+    #[arg(long, default_value_t = false)]
+    audit_log: bool,
+
+    /// Bearer token for the web server's admin endpoints (also read from the
+    /// INVENTORY_SYNC_ADMIN_TOKEN env var; admin endpoints are disabled
+    /// without a token)
+    #[arg(long)]
+    admin_token: Option<String>,
+
+    /// check_stock inventory database to browse in the web UI, opened
+    /// read-only (e.g. ~/.local/share/d2d_automations/inventory.db)
+    #[arg(long)]
+    stock_db: Option<PathBuf>,
+
+    /// The accounting app's order board, for rolling revenue figures in the
+    /// web UI (e.g. ~/.local/share/sevdesk_invoicing/order_board.json)
+    #[arg(long)]
+    order_board: Option<PathBuf>,
+
+    /// URL of dated price guide archives with `{date}` for the YYYY-MM-DD
+    /// price date; days missing from the price history are downloaded from
+    /// it and imported (default: no backfilling)
+    #[arg(long)]
+    price_guide_archive_url: Option<String>,
+
+    /// How many days back to look for gaps to backfill from the archive
+    /// (default: 30)
+    #[arg(long)]
+    backfill_days: Option<u32>,
+
+    /// Cap the catalog and price guide downloads at this many kbit/s
+    /// (default: unlimited)
+    #[arg(long)]
+    download_limit_kbit: Option<u64>,
+
+    /// Only run Cardmarket syncs inside this Europe/Berlin time window,
+    /// e.g. 02:00-06:00 (default: any time)
+    #[arg(long)]
+    download_window: Option<String>,
+
+    /// Upload a daily database snapshot to this WebDAV folder (https://…)
+    /// or S3 bucket (s3://bucket/prefix); credentials and the S3 endpoint
+    /// come from the config file or env (default: no backups)
+    #[arg(long)]
+    backup_url: Option<String>,
+
+    /// Number of daily snapshots kept on the backup target, 0 for all
+    /// (default: 14)
+    #[arg(long)]
+    backup_keep: Option<u32>,
+
+    /// Post a summary of each sync run that stored prices or failed to this
+    /// webhook (Discord, Slack, …; also read from the INVENTORY_SYNC_WEBHOOK
+    /// env var; default: none)
+    #[arg(long)]
+    webhook_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// What to run; without a command the sync runs as a daemon (`serve`), or
+/// once with `--once`
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run one sync (Cardmarket, then TCGplayer and the backup if
+    /// configured), then exit
+    Sync {
+        /// Download and import today's catalog and prices even if they are
+        /// already in the database, and even outside the download window
+        #[arg(long, default_value_t = false)]
+        force: bool,
+    },
+    /// Run continuously: check for new prices every `--interval-hours` and
+    /// serve the web UI if a port is set
+    Serve,
+    /// Print product and price history counts, then exit
+    Stats,
+    /// Write one day's prices with product names as CSV, then exit
+    Export {
+        /// Price date (YYYY-MM-DD; default: the latest price date)
+        #[arg(long)]
+        date: Option<String>,
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Create or update the reporting views (latest_prices,
+    /// product_price_latest, daily_index) for Excel/ODBC, Metabase and
+    /// similar tools, then exit
+    CreateViews,
+    /// Import a price guide file by hand (e.g. to backfill a missed day),
+    /// then exit. The product catalog is fetched from Cardmarket first.
+    ImportPriceGuide {
+        /// Price guide JSON as downloaded from Cardmarket (may be gzipped or
+        /// zipped)
+        file: PathBuf,
+        /// Store the prices under this date (YYYY-MM-DD) instead of the one
+        /// in the file's createdAt header; the date must not have price data
+        /// yet
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Write a tenant's watchlist and price alerts as JSON, then exit
+    ExportAlerts {
+        /// Tenant name
+        tenant: String,
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Add the watchlist and price alerts of an `export-alerts` file to a
+    /// tenant's, then exit
+    ImportAlerts {
+        /// Tenant name
+        tenant: String,
+        /// JSON file written by `export-alerts`
+        file: PathBuf,
+    },
+    /// Register price alerts for a tenant from a CSV of card names and
+    /// thresholds, then exit
+    AddAlerts {
+        /// Tenant name
+        tenant: String,
+        /// CSV with a header row: name, threshold (EUR) and optionally
+        /// condition (above/below, default above) and expansion (code, name
+        /// or ID, for names with several printings)
+        file: PathBuf,
+    },
+    /// Print the web API's OpenAPI document (JSON), then exit
+    Openapi,
 }
 
-/// Returns the default database path: ~/.local/share/inventory_sync/inventory.db
-fn default_db_path() -> String {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("inventory_sync")
-        .join("inventory.db")
-        .to_string_lossy()
-        .to_string()
+impl Args {
+    /// Lays the flags that were given over the config file and env settings.
+    fn apply_to(self, config: &mut InventorySyncConfig) {
+        if let Some(database) = self.database {
+            config.database = database;
+        }
+        if let Some(hours) = self.interval_hours {
+            config.interval_hours = hours;
+        }
+        if let Some(port) = self.web_port {
+            config.web_port = Some(port);
+        }
+        if let Some(trend) = self.tcgplayer_min_trend {
+            config.tcgplayer_min_trend = Some(trend);
+        }
+        if !self.categories.is_empty() {
+            config.categories = self.categories;
+        }
+        config.audit_log |= self.audit_log;
+        if let Some(token) = self.admin_token {
+            config.admin_token = Some(token);
+        }
+        if let Some(stock_db) = self.stock_db {
+            config.stock_db = Some(stock_db);
+        }
+        if let Some(order_board) = self.order_board {
+            config.order_board = Some(order_board);
+        }
+        if let Some(url) = self.price_guide_archive_url {
+            config.price_guide_archive_url = Some(url);
+        }
+        if let Some(days) = self.backfill_days {
+            config.backfill_days = days;
+        }
+        if let Some(kbit) = self.download_limit_kbit {
+            config.download_limit_kbit = Some(kbit);
+        }
+        if let Some(window) = self.download_window {
+            config.download_window = Some(window);
+        }
+        if let Some(url) = self.backup_url {
+            config.backup_url = Some(url);
+        }
+        if let Some(keep) = self.backup_keep {
+            config.backup_keep = keep;
+        }
+        if let Some(url) = self.webhook_url {
+            config.webhook_url = Some(url);
+        }
+    }
 }
 
 #[tokio::main]
@@ -51,8 +266,57 @@ async fn main() {
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
-    let args = Args::parse();
-    let db_path = PathBuf::from(&args.database);
+    let mut args = Args::parse();
+    let loaded = match config::init(args.config.as_deref()) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            log::error!("Failed to load config: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let command = args.command.take();
+    let print_config = args.print_config;
+    let once = args.once || matches!(command, Some(Command::Sync { .. }));
+    let force = matches!(command, Some(Command::Sync { force: true }));
+    let mut settings = loaded.inventory_sync.clone();
+    args.apply_to(&mut settings);
+
+    if print_config {
+        let effective = D2dConfig {
+            http: mtg_common::http::config().clone(),
+            inventory_sync: settings,
+            ..loaded.clone()
+        };
+        print!("{}", effective.to_redacted_toml());
+        return;
+    }
+    if matches!(command, Some(Command::Openapi)) {
+        match inventory_sync::web::openapi().to_pretty_json() {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                log::error!("Failed to serialize the OpenAPI document: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let db_path = settings.database.clone();
+
+    if matches!(
+        command,
+        Some(
+            Command::Stats
+                | Command::Export { .. }
+                | Command::ExportAlerts { .. }
+                | Command::ImportAlerts { .. }
+                | Command::AddAlerts { .. }
+        )
+    ) && !db_path.exists()
+    {
+        log::error!("No database at {}", db_path.display());
+        std::process::exit(1);
+    }
 
     log::info!("Starting inventory_sync...");
     log::info!("Database path: {}", db_path.display());
@@ -69,9 +333,11 @@ async fn main() {
     }
 
     // Open database connection
+    mtg_common::sql_profile::set_slow_query_threshold(loaded.sql.slow_query_threshold());
     let conn = match Connection::open(&db_path) {
         Ok(conn) => {
             log::info!("Opened database: {}", db_path.display());
+            slow_queries::instrument(&conn, &db_path);
             conn
         }
         Err(e) => {
@@ -86,146 +352,321 @@ async fn main() {
         std::process::exit(1);
     }
 
+    match command {
+        Some(Command::CreateViews) => {
+            let mut conn = conn;
+            if let Err(e) = create_views(&mut conn) {
+                log::error!("Failed to create views: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::ImportPriceGuide { file, date }) => {
+            let mut conn = conn;
+            if let Err(e) =
+                import_price_guide(&mut conn, &file, date.as_deref(), &settings.categories).await
+            {
+                log::error!("Failed to import {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Stats) => {
+            if let Err(e) = print_stats(&conn, &db_path) {
+                log::error!("Failed to read database stats: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Export { date, output }) => {
+            if let Err(e) = export_prices(&conn, date.as_deref(), output.as_deref()) {
+                log::error!("Failed to export prices: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::ExportAlerts { tenant, output }) => {
+            if let Err(e) = export_alerts(&conn, &tenant, output.as_deref()) {
+                log::error!("Failed to export alerts: {}", e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::ImportAlerts { tenant, file }) => {
+            let mut conn = conn;
+            if let Err(e) = import_alerts(&mut conn, &tenant, &file) {
+                log::error!("Failed to import {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::AddAlerts { tenant, file }) => {
+            let mut conn = conn;
+            if let Err(e) = add_alerts(&mut conn, &tenant, &file) {
+                log::error!("Failed to add alerts from {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Sync { .. } | Command::Serve | Command::Openapi) | None => {}
+    }
+
     // Wrap connection in Arc<Mutex> for thread-safe sharing
     let db = Arc::new(Mutex::new(conn));
 
     // Spawn web server if --web-port specified
-    if let Some(port) = args.web_port {
+    if let Some(port) = settings.web_port {
         let web_db = Arc::clone(&db);
         let web_db_path = db_path.clone();
+        let web_options = WebOptions {
+            audit_log: settings.audit_log,
+            admin_token: settings
+                .admin_token
+                .clone()
+                .filter(|token| !token.is_empty()),
+            stock_db: settings.stock_db.clone(),
+            order_board: settings.order_board.clone(),
+        };
         tokio::spawn(async move {
-            if let Err(e) = inventory_sync::web::serve(web_db, &web_db_path, port).await {
+            if let Err(e) =
+                inventory_sync::web::serve(web_db, &web_db_path, port, web_options).await
+            {
                 log::error!("Web server error: {}", e);
             }
         });
     }
 
-    if args.once {
+    if let Some(url) = &settings.price_guide_archive_url {
+        if !url.contains("{date}") {
+            log::error!("--price-guide-archive-url must contain {{date}}: {}", url);
+            std::process::exit(1);
+        }
+    }
+
+    let window = match settings.download_window.as_deref().map(str::parse) {
+        None => None,
+        Some(Ok(window)) => Some(window),
+        Some(Err(e)) => {
+            log::error!("--download-window: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let backup = match BackupOptions::from_config(&settings, &db_path) {
+        Ok(backup) => backup,
+        Err(e) => {
+            log::error!("--backup-url: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Some(backup) = &backup {
+        log::info!(
+            "Daily backups go to {} (keeping {})",
+            backup.target,
+            if backup.keep == 0 {
+                "all".to_string()
+            } else {
+                backup.keep.to_string()
+            }
+        );
+    }
+
+    let options = SyncOptions {
+        categories: settings.categories,
+        tcgplayer_min_trend: settings.tcgplayer_min_trend,
+        raw_price_guide: db_path.with_extension("price_guide.json"),
+        sources: CardmarketSources {
+            price_guide_archive_url: settings.price_guide_archive_url,
+            ..CardmarketSources::default()
+        },
+        backfill_days: settings.backfill_days,
+        downloads: DownloadLimits {
+            max_kbit_per_sec: settings.download_limit_kbit,
+            window,
+        },
+        backup,
+        force,
+        webhook_url: settings.webhook_url.filter(|url| !url.trim().is_empty()),
+    };
+    if !options.categories.is_empty() {
+        log::info!(
+            "Restricting price history to {}",
+            options
+                .categories
+                .iter()
+                .map(|c| c.display_name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    if once {
         // Run once and exit
-        run_sync(&db).await;
+        run_sync(&db, &options).await;
     } else {
         // Run continuously with interval checks
         log::info!(
             "Running in daemon mode, checking every {} hour(s)",
-            args.interval_hours
+            settings.interval_hours
         );
-        run_daemon(&db, args.interval_hours).await;
+        run_daemon(&db, settings.interval_hours, &options).await;
     }
 }
 
 /// Run the sync daemon - checks periodically and syncs when needed
-async fn run_daemon(db: &Arc<Mutex<Connection>>, interval_hours: u64) {
+async fn run_daemon(db: &Arc<Mutex<Connection>>, interval_hours: u64, options: &SyncOptions) {
     let check_interval = Duration::from_secs(interval_hours * 3600);
     let mut ticker = interval(check_interval);
 
     // Run immediately on startup
-    run_sync(db).await;
+    run_sync(db, options).await;
 
     loop {
         ticker.tick().await;
         log::info!("Scheduled check triggered");
-        run_sync(db).await;
+        run_sync(db, options).await;
     }
 }
 
-/// Run a single sync operation
-async fn run_sync(db: &Arc<Mutex<Connection>>) {
-    // Check if we already have price data for today
-    let should_sync = {
-        let conn = db.lock().unwrap();
-        match has_price_data_for_today(&conn) {
-            Ok(true) => {
-                log::info!("Price data for today already exists in database, skipping download");
-                false
-            }
-            Ok(false) => {
-                log::info!("No price data for today, proceeding with download...");
-                true
-            }
-            Err(e) => {
-                log::error!("Failed to check existing price data: {}", e);
-                false
-            }
-        }
-    };
-
-    if !should_sync {
-        return;
+/// Print the product and price history counts of the database
+fn print_stats(conn: &Connection, db_path: &Path) -> inventory_sync::Result<()> {
+    let stats = get_database_stats(conn)?;
+    println!("Database:         {}", db_path.display());
+    println!("Products:         {}", stats.products);
+    match (&stats.first_price_date, &stats.last_price_date) {
+        (Some(first), Some(last)) => println!(
+            "Price history:    {} rows over {} days ({} to {})",
+            stats.price_history, stats.price_days, first, last
+        ),
+        _ => println!("Price history:    none"),
     }
-
-    // Fetch product catalog from Cardmarket (singles + non-singles)
-    let catalog = match ProductCatalog::fetch().await {
-        Ok(catalog) => {
-            log::info!(
-                "Fetched product catalog: {} products ({} singles, {} non-singles)",
-                catalog.len(),
-                catalog.singles_count(),
-                catalog.non_singles_count()
-            );
-            catalog
+    println!(
+        "Today's prices:   {}",
+        if has_price_data_for_today(conn)? {
+            "imported"
+        } else {
+            "not yet imported"
         }
-        Err(e) => {
-            log::error!("Failed to fetch product catalog: {}", e);
-            return;
+    );
+    println!("TCGplayer prices: {}", stats.tcgplayer_prices);
+    Ok(())
+}
+
+/// Export one day's prices as CSV to `output`, or stdout
+fn export_prices(
+    conn: &Connection,
+    date: Option<&str>,
+    output: Option<&Path>,
+) -> inventory_sync::Result<()> {
+    let (date, rows) = match output {
+        Some(path) => {
+            let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+            let exported = export_prices_csv(conn, date, &mut out)?;
+            out.flush()?;
+            exported
         }
+        None => export_prices_csv(conn, date, &mut std::io::stdout().lock())?,
     };
+    match date {
+        Some(date) if rows == 0 => log::warn!("No prices for {}", date),
+        Some(date) => log::info!("Exported {} prices for {}", rows, date),
+        None => log::warn!("The database has no prices yet"),
+    }
+    Ok(())
+}
 
-    // Upsert products into database
-    {
-        let mut conn = db.lock().unwrap();
-        match upsert_products(&mut conn, &catalog) {
-            Ok(count) => {
-                log::info!("Synced {} products to database", count);
-            }
-            Err(e) => {
-                log::error!("Failed to upsert products: {}", e);
-                return;
-            }
-        }
+/// The tenant called `name`
+fn tenant_named(conn: &Connection, name: &str) -> inventory_sync::Result<Tenant> {
+    get_tenants(conn)?
+        .into_iter()
+        .find(|tenant| tenant.name == name)
+        .ok_or_else(|| InventoryError::InvalidAlerts(format!("no tenant named '{name}'")))
+}
+
+/// Write a tenant's watchlist and alerts as JSON to `output` or stdout
+fn export_alerts(
+    conn: &Connection,
+    tenant: &str,
+    output: Option<&Path>,
+) -> inventory_sync::Result<()> {
+    let config = export_alert_config(conn, &tenant_named(conn, tenant)?)?;
+    let mut json = serde_json::to_string_pretty(&config)?;
+    json.push('\n');
+    match output {
+        Some(path) => std::fs::write(path, json)?,
+        None => std::io::stdout().lock().write_all(json.as_bytes())?,
     }
+    log::info!(
+        "Exported {} watched products and {} alerts of {}",
+        config.watchlist.len(),
+        config.alerts.len(),
+        tenant
+    );
+    Ok(())
+}
 
-    // Fetch price guide from Cardmarket
-    let guide = match PriceGuide::fetch().await {
-        Ok(guide) => {
-            log::info!(
-                "Fetched price guide: {} entries (created: {})",
-                guide.len(),
-                guide.created_at()
-            );
-            guide
-        }
-        Err(e) => {
-            log::error!("Failed to fetch price guide: {}", e);
-            return;
-        }
-    };
+/// Add an `export-alerts` file to a tenant's watchlist and alerts
+fn import_alerts(conn: &mut Connection, tenant: &str, file: &Path) -> inventory_sync::Result<()> {
+    let tenant = tenant_named(conn, tenant)?;
+    let config: AlertConfig = serde_json::from_slice(&std::fs::read(file)?)?;
+    let summary = import_alert_config(conn, tenant.id, &config)?;
+    if !summary.unknown_products.is_empty() {
+        log::warn!(
+            "Skipped products missing from the catalog: {:?}",
+            summary.unknown_products
+        );
+    }
+    log::info!("Imported into {}: {}", tenant.name, summary.summary());
+    Ok(())
+}
 
-    // Insert price history (only if not already present for this date)
-    {
-        let mut conn = db.lock().unwrap();
-        match insert_price_history(&mut conn, &guide, &catalog) {
-            Ok(result) => {
-                if result.inserted > 0 {
-                    log::info!(
-                        "Inserted {} price entries for {} ({} products not in catalog)",
-                        result.inserted,
-                        result.price_date,
-                        result.no_product
-                    );
-                } else {
-                    log::info!(
-                        "Price data for {} already exists, {} entries skipped",
-                        result.price_date,
-                        result.skipped
-                    );
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to insert price history: {}", e);
-                return;
-            }
-        }
+/// Register the alerts of a bulk CSV for a tenant
+fn add_alerts(conn: &mut Connection, tenant: &str, file: &Path) -> inventory_sync::Result<()> {
+    let tenant = tenant_named(conn, tenant)?;
+    let rows = parse_alert_csv(std::fs::File::open(file)?)?;
+    let report = add_alerts_from_csv(conn, tenant.id, &rows)?;
+    for reason in &report.skipped {
+        log::warn!("Skipped {}", reason);
     }
+    log::info!(
+        "{} alerts added for {}, {} already registered, {} rows skipped",
+        report.added,
+        tenant.name,
+        report.unchanged,
+        report.skipped.len()
+    );
+    Ok(())
+}
 
-    log::info!("Sync completed successfully.");
+/// Import a price guide file, optionally under an overridden price date
+async fn import_price_guide(
+    conn: &mut Connection,
+    file: &Path,
+    date: Option<&str>,
+    categories: &[Category],
+) -> inventory_sync::Result<()> {
+    let guide = PriceGuide::load(&file.to_string_lossy())?;
+    let catalog = ProductCatalog::fetch().await?;
+    upsert_products(conn, &catalog)?;
+
+    let result = match date {
+        Some(date) => insert_price_history_for_date(conn, &guide, &catalog, categories, date)?,
+        None => insert_price_history_for_categories(conn, &guide, &catalog, categories)?,
+    };
+    if result.inserted == 0 {
+        log::warn!(
+            "Price data for {} already exists, {} entries skipped",
+            result.price_date,
+            result.skipped
+        );
+    } else {
+        log::info!(
+            "Imported {} price entries for {} ({} products not in catalog, {} outside selected categories)",
+            result.inserted,
+            result.price_date,
+            result.no_product,
+            result.filtered
+        );
+    }
+    Ok(())
 }
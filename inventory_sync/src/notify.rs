@@ -0,0 +1,120 @@
+//! Webhook summary of a sync run
+//!
+//! With a webhook URL configured (`--webhook-url`, `[inventory_sync]
+//! webhook_url` in the shared config or `INVENTORY_SYNC_WEBHOOK`), every sync
+//! run that stored prices or ran into errors posts a short summary: how many
+//! price rows were inserted and skipped, the days backfilled from the
+//! archive, the errors, and the day's biggest movers. Runs that find today's
+//! prices already stored stay quiet.
+//!
+//! The JSON body carries the message as both `text` (Slack, Mattermost) and
+//! `content` (Discord), plus the raw figures for other receivers. A failed
+//! post is logged; it never fails the sync.
+
+use crate::database::{get_price_movers, PriceMover};
+use crate::error::{InventoryError, Result};
+use crate::sync::SyncReport;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Movers listed in the summary
+const TOP_MOVERS: usize = 5;
+/// Movers must be worth this much (EUR) on one of the two days
+const MOVER_MIN_EUR: f64 = 1.0;
+
+/// The summary text, one line per fact, e.g.
+/// `Imported 48213 prices for 2026-03-30 (12 skipped)` followed by the
+/// movers and errors.
+pub fn summary_message(report: &SyncReport, movers: &[PriceMover]) -> String {
+    let mut lines = Vec::new();
+    match &report.price_date {
+        Some(date) if report.inserted > 0 => lines.push(format!(
+            "Imported {} prices for {} ({} skipped)",
+            report.inserted, date, report.skipped
+        )),
+        Some(date) => lines.push(format!(
+            "No new prices for {} ({} skipped)",
+            date, report.skipped
+        )),
+        None => lines.push("No price guide imported".to_string()),
+    }
+    if !report.backfilled.is_empty() {
+        lines.push(format!("Backfilled {}", report.backfilled.join(", ")));
+    }
+    if !movers.is_empty() {
+        lines.push("Top movers:".to_string());
+        for mover in movers {
+            lines.push(format!(
+                "• {} {:+.1}% ({:.2} € → {:.2} €)",
+                mover.name, mover.change_pct, mover.previous_trend, mover.trend
+            ));
+        }
+    }
+    if !report.errors.is_empty() {
+        lines.push(format!("{} errors:", report.errors.len()));
+        for error in &report.errors {
+            lines.push(format!("• {}", error));
+        }
+    }
+    lines.join("\n")
+}
+
+/// The JSON body posted to the webhook
+pub fn webhook_payload(report: &SyncReport, movers: &[PriceMover]) -> serde_json::Value {
+    let message = summary_message(report, movers);
+    serde_json::json!({
+        "text": message,
+        "content": message,
+        "event": "price_sync",
+        "price_date": report.price_date,
+        "inserted": report.inserted,
+        "skipped": report.skipped,
+        "backfilled": report.backfilled,
+        "errors": report.errors,
+        "top_movers": movers,
+    })
+}
+
+/// The movers of a run that imported new prices, empty otherwise
+fn new_movers(conn: &Connection, report: &SyncReport) -> Vec<PriceMover> {
+    if report.inserted == 0 {
+        return Vec::new();
+    }
+    get_price_movers(conn, MOVER_MIN_EUR, TOP_MOVERS).unwrap_or_else(|e| {
+        log::warn!("Failed to load price movers for the webhook: {}", e);
+        Vec::new()
+    })
+}
+
+/// Posts `payload` to `url`
+pub async fn post_webhook(url: &str, payload: &serde_json::Value) -> Result<()> {
+    let response = mtg_common::http::client_builder(WEBHOOK_TIMEOUT)?
+        .build()?
+        .post(url)
+        .header("User-Agent", mtg_common::USER_AGENT)
+        .json(payload)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(InventoryError::HttpStatus(response.status()));
+    }
+    Ok(())
+}
+
+/// Posts the summary of `report` to `url`; a failure is logged
+pub async fn send_sync_summary(db: &Arc<Mutex<Connection>>, url: &str, report: &SyncReport) {
+    let payload = {
+        let conn = db.lock().unwrap();
+        webhook_payload(report, &new_movers(&conn, report))
+    };
+    match post_webhook(url, &payload).await {
+        Ok(()) => log::info!("Posted the sync summary to the webhook"),
+        Err(e) => log::warn!("Failed to post the sync summary to the webhook: {}", e),
+    }
+}
+
+#[cfg(test)]
+#[path = "notify_tests.rs"]
+mod tests;
@@ -0,0 +1,89 @@
+//! Tests for notify.
+
+use super::*;
+
+fn mover(name: &str, previous_trend: f64, trend: f64) -> PriceMover {
+    PriceMover {
+        id_product: 1,
+        name: name.to_string(),
+        expansion_name: None,
+        price_date: "2026-03-30".to_string(),
+        previous_date: "2026-03-29".to_string(),
+        previous_trend,
+        trend,
+        change_pct: (trend - previous_trend) / previous_trend * 100.0,
+    }
+}
+
+#[test]
+fn summary_lists_import_movers_and_errors() {
+    let report = SyncReport {
+        price_date: Some("2026-03-30".to_string()),
+        inserted: 48213,
+        skipped: 12,
+        backfilled: vec!["2026-03-28".to_string()],
+        errors: vec!["Failed to fetch EUR/USD rate: HTTP error: 503".to_string()],
+    };
+    let movers = vec![
+        mover("Black Lotus", 10.0, 12.5),
+        mover("Mox Pearl", 4.0, 3.0),
+    ];
+
+    assert_eq!(
+        summary_message(&report, &movers),
+        "Imported 48213 prices for 2026-03-30 (12 skipped)\n\
+         Backfilled 2026-03-28\n\
+         Top movers:\n\
+         • Black Lotus +25.0% (10.00 € → 12.50 €)\n\
+         • Mox Pearl -25.0% (4.00 € → 3.00 €)\n\
+         1 errors:\n\
+         • Failed to fetch EUR/USD rate: HTTP error: 503"
+    );
+}
+
+#[test]
+fn summary_of_a_failed_run() {
+    let report = SyncReport {
+        errors: vec!["Failed to fetch product catalog: boom".to_string()],
+        ..SyncReport::default()
+    };
+    assert_eq!(
+        summary_message(&report, &[]),
+        "No price guide imported\n1 errors:\n• Failed to fetch product catalog: boom"
+    );
+}
+
+#[test]
+fn payload_carries_message_and_figures() {
+    let report = SyncReport {
+        price_date: Some("2026-03-30".to_string()),
+        inserted: 3,
+        ..SyncReport::default()
+    };
+    let payload = webhook_payload(&report, &[mover("Black Lotus", 10.0, 12.5)]);
+
+    assert_eq!(payload["text"], payload["content"]);
+    assert_eq!(payload["event"], "price_sync");
+    assert_eq!(payload["price_date"], "2026-03-30");
+    assert_eq!(payload["inserted"], 3);
+    assert_eq!(payload["skipped"], 0);
+    assert_eq!(payload["errors"], serde_json::json!([]));
+    assert_eq!(payload["top_movers"][0]["name"], "Black Lotus");
+    assert_eq!(payload["top_movers"][0]["change_pct"], 25.0);
+}
+
+#[test]
+fn idle_runs_have_nothing_to_report() {
+    assert!(SyncReport::default().is_idle());
+    let skipped_only = SyncReport {
+        price_date: Some("2026-03-30".to_string()),
+        skipped: 10,
+        ..SyncReport::default()
+    };
+    assert!(skipped_only.is_idle());
+    let failed = SyncReport {
+        errors: vec!["boom".to_string()],
+        ..SyncReport::default()
+    };
+    assert!(!failed.is_idle());
+}
@@ -0,0 +1,142 @@
+//! Rolling revenue figures from the accounting app's order board
+//!
+//! The accounting app (sevdesk_invoicing) keeps every imported Cardmarket
+//! order on its order board, a JSON file in its data directory
+//! (`sevdesk_invoicing/order_board.json`). When the web server runs with
+//! `--order-board`, that file is read on each request and summed into a few
+//! business figures: revenue, order count and average order value of the
+//! last N days (30 by default), next to the N days before. Nothing here ever
+//! writes to the board; the accounting app stays its only owner.
+
+use crate::error::{InventoryError, Result};
+use chrono::{Duration, NaiveDate};
+use mtg_common::round_cents;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Default length of a revenue period, in days
+pub const DEFAULT_REVENUE_DAYS: u32 = 30;
+
+/// Longest revenue period, in days
+pub const MAX_REVENUE_DAYS: u32 = 366;
+
+/// The part of an order board entry the figures need
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BoardOrder {
+    pub order_id: String,
+    /// Order total in EUR, shipping included
+    pub total_value: f64,
+    /// As exported by Cardmarket, e.g. `2025-01-15 10:30:00`
+    pub date_of_purchase: String,
+}
+
+impl BoardOrder {
+    /// The purchase day, `None` if the date can't be read
+    pub fn date(&self) -> Option<NaiveDate> {
+        let day = self.date_of_purchase.split_whitespace().next()?;
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct OrderBoardFile {
+    #[serde(default)]
+    entries: BTreeMap<String, BoardOrder>,
+}
+
+/// Reads the orders of an order board file; a missing file is an empty board,
+/// as in the accounting app.
+pub fn load_order_board(path: &Path) -> Result<Vec<BoardOrder>> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(InventoryError::Io(e)),
+    };
+    let board: OrderBoardFile = serde_json::from_str(&json).map_err(InventoryError::Parse)?;
+    Ok(board.entries.into_values().collect())
+}
+
+/// Sales of one period
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct PeriodRevenue {
+    /// First day, `YYYY-MM-DD`
+    pub from: String,
+    /// Last day (inclusive), `YYYY-MM-DD`
+    pub to: String,
+    /// Sum of the order totals in EUR
+    pub revenue: f64,
+    pub orders: usize,
+    /// `None` without orders
+    pub average_order_value: Option<f64>,
+}
+
+/// The last `days` days of sales compared with the `days` days before
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct RevenueSummary {
+    pub days: u32,
+    pub current: PeriodRevenue,
+    pub previous: PeriodRevenue,
+    /// Change from the previous period in percent; `None` when the previous
+    /// period has nothing to compare with
+    pub revenue_change_pct: Option<f64>,
+    pub orders_change_pct: Option<f64>,
+    pub average_order_value_change_pct: Option<f64>,
+    /// Orders on the board whose purchase date couldn't be read
+    pub undated_orders: usize,
+}
+
+fn change_pct(previous: f64, current: f64) -> Option<f64> {
+    (previous > 0.0).then(|| round_cents((current - previous) / previous * 100.0))
+}
+
+fn period(orders: &[(NaiveDate, f64)], from: NaiveDate, to: NaiveDate) -> PeriodRevenue {
+    let totals: Vec<f64> = orders
+        .iter()
+        .filter(|(date, _)| (from..=to).contains(date))
+        .map(|(_, total)| *total)
+        .collect();
+    let revenue: f64 = totals.iter().sum();
+    PeriodRevenue {
+        from: from.to_string(),
+        to: to.to_string(),
+        revenue: round_cents(revenue),
+        orders: totals.len(),
+        average_order_value: (!totals.is_empty())
+            .then(|| round_cents(revenue / totals.len() as f64)),
+    }
+}
+
+/// Revenue of the `days` days up to and including `today`, and of the
+/// `days` days before. `days` is clamped to 1..=[`MAX_REVENUE_DAYS`].
+pub fn revenue_summary(orders: &[BoardOrder], today: NaiveDate, days: u32) -> RevenueSummary {
+    let days = days.clamp(1, MAX_REVENUE_DAYS);
+    let dated: Vec<(NaiveDate, f64)> = orders
+        .iter()
+        .filter_map(|order| Some((order.date()?, order.total_value)))
+        .collect();
+    let length = Duration::days(i64::from(days));
+    let current_from = today - length + Duration::days(1);
+    let current = period(&dated, current_from, today);
+    let previous = period(
+        &dated,
+        current_from - length,
+        current_from - Duration::days(1),
+    );
+    RevenueSummary {
+        days,
+        revenue_change_pct: change_pct(previous.revenue, current.revenue),
+        orders_change_pct: change_pct(previous.orders as f64, current.orders as f64),
+        average_order_value_change_pct: previous
+            .average_order_value
+            .zip(current.average_order_value)
+            .and_then(|(previous, current)| change_pct(previous, current)),
+        current,
+        previous,
+        undated_orders: orders.len() - dated.len(),
+    }
+}
+
+#[cfg(test)]
+#[path = "revenue_tests.rs"]
+mod tests;
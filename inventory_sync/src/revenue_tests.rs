@@ -0,0 +1,88 @@
+//! Tests for revenue.
+
+use super::*;
+
+fn order(id: &str, date: &str, total: f64) -> BoardOrder {
+    BoardOrder {
+        order_id: id.to_string(),
+        total_value: total,
+        date_of_purchase: date.to_string(),
+    }
+}
+
+fn day(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+#[test]
+fn summary_compares_the_last_days_with_the_days_before() {
+    let orders = vec![
+        order("1", "2026-03-30 18:00:00", 20.0),
+        order("2", "2026-03-01 09:15:00", 10.0),
+        // Previous period: 2026-01-30 ..= 2026-02-28
+        order("3", "2026-02-28 23:59:59", 15.0),
+        order("4", "2026-01-30 08:00:00", 5.0),
+        order("5", "2026-01-29 12:00:00", 100.0),
+        order("6", "unknown", 50.0),
+    ];
+    let summary = revenue_summary(&orders, day("2026-03-30"), 30);
+
+    assert_eq!(summary.current.from, "2026-03-01");
+    assert_eq!(summary.current.to, "2026-03-30");
+    assert_eq!(summary.current.revenue, 30.0);
+    assert_eq!(summary.current.orders, 2);
+    assert_eq!(summary.current.average_order_value, Some(15.0));
+
+    assert_eq!(summary.previous.from, "2026-01-30");
+    assert_eq!(summary.previous.to, "2026-02-28");
+    assert_eq!(summary.previous.revenue, 20.0);
+    assert_eq!(summary.previous.orders, 2);
+
+    assert_eq!(summary.revenue_change_pct, Some(50.0));
+    assert_eq!(summary.orders_change_pct, Some(0.0));
+    assert_eq!(summary.average_order_value_change_pct, Some(50.0));
+    assert_eq!(summary.undated_orders, 1);
+}
+
+#[test]
+fn empty_previous_period_has_no_change() {
+    let orders = vec![order("1", "2026-03-30", 12.5)];
+    let summary = revenue_summary(&orders, day("2026-03-30"), 7);
+
+    assert_eq!(summary.current.revenue, 12.5);
+    assert_eq!(summary.previous.orders, 0);
+    assert_eq!(summary.previous.average_order_value, None);
+    assert_eq!(summary.revenue_change_pct, None);
+    assert_eq!(summary.average_order_value_change_pct, None);
+}
+
+#[test]
+fn period_length_is_clamped() {
+    assert_eq!(revenue_summary(&[], day("2026-03-30"), 0).days, 1);
+    assert_eq!(
+        revenue_summary(&[], day("2026-03-30"), 10_000).days,
+        MAX_REVENUE_DAYS
+    );
+}
+
+#[test]
+fn order_board_file_is_read_and_missing_file_is_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("order_board.json");
+    assert!(load_order_board(&path).unwrap().is_empty());
+
+    std::fs::write(
+        &path,
+        r#"{"entries": {"1001": {"order_id": "1001", "name": "Jane Doe",
+            "username": "jane", "country": "Germany", "total_value": 11.5,
+            "date_of_purchase": "2025-01-15 10:30:00", "status": "shipped",
+            "updated_at": "2025-01-16"}}}"#,
+    )
+    .unwrap();
+    let orders = load_order_board(&path).unwrap();
+    assert_eq!(orders, vec![order("1001", "2025-01-15 10:30:00", 11.5)]);
+    assert_eq!(orders[0].date(), Some(day("2025-01-15")));
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(load_order_board(&path).is_err());
+}
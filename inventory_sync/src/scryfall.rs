@@ -5,10 +5,10 @@ use crate::error::InventoryError;
 use mtg_common::MtgError;
 use serde::{Deserialize, Serialize};
 
-pub use mtg_common::scryfall::{CardFace, ImageUris, PurchaseUris, ScryfallCard};
+pub use mtg_common::scryfall::{CardFace, ImageUris, PurchaseUris, ScryfallCard, ScryfallSet};
 
 /// Metadata about a card from Scryfall (serializable for caching)
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, utoipa::ToSchema)]
 pub struct CardInfo {
     pub set_name: Option<String>,
     pub type_line: Option<String>,
@@ -57,6 +57,27 @@ pub async fn fetch_card_by_name(name: &str) -> Result<ScryfallCard, InventoryErr
         .map_err(|e| not_found_on_api_error(e, name.to_string()))
 }
 
+/// Fetch the set list from a Scryfall API base URL.
+pub async fn fetch_sets_from(base_url: &str) -> Result<Vec<ScryfallSet>, InventoryError> {
+    Ok(mtg_common::scryfall::fetch_sets_from(base_url).await?)
+}
+
+/// The paper sets releasing on or after `today` (YYYY-MM-DD), soonest first.
+/// Arena/MTGO-only sets and undated sets are dropped.
+pub fn upcoming_paper_sets(sets: Vec<ScryfallSet>, today: &str) -> Vec<ScryfallSet> {
+    let mut upcoming: Vec<ScryfallSet> = sets
+        .into_iter()
+        .filter(|set| !set.digital)
+        .filter(|set| {
+            set.released_at
+                .as_deref()
+                .is_some_and(|date| date.get(..10).unwrap_or(date) >= today)
+        })
+        .collect();
+    upcoming.sort_by(|a, b| a.released_at.cmp(&b.released_at));
+    upcoming
+}
+
 /// Fetch image bytes from a URL
 pub async fn fetch_image(url: &str) -> Result<Vec<u8>, InventoryError> {
     mtg_common::scryfall::fetch_image(url)
@@ -2,7 +2,7 @@
 //!
 //! Note: Some tests require network access and are marked with #[ignore]
 
-use crate::scryfall::{CardInfo, ScryfallCard};
+use crate::scryfall::{upcoming_paper_sets, CardInfo, ScryfallCard, ScryfallSet};
 
 /// Minimal identity fields required by the shared ScryfallCard struct.
 fn base_card_json(extra: &str) -> String {
@@ -143,6 +143,35 @@ fn test_card_info_extraction() {
     assert_eq!(deserialized.rarity, info.rarity);
 }
 
+fn set(code: &str, released_at: Option<&str>, digital: bool) -> ScryfallSet {
+    ScryfallSet {
+        code: code.to_string(),
+        name: code.to_uppercase(),
+        icon_svg_uri: String::new(),
+        released_at: released_at.map(str::to_string),
+        set_type: Some("expansion".to_string()),
+        digital,
+    }
+}
+
+#[test]
+fn test_upcoming_paper_sets() {
+    let sets = vec![
+        set("old", Some("2026-01-10"), false),
+        set("later", Some("2026-06-01"), false),
+        set("today", Some("2026-03-01"), false),
+        set("arena", Some("2026-04-01"), true),
+        set("tba", None, false),
+    ];
+
+    let upcoming = upcoming_paper_sets(sets, "2026-03-01");
+
+    assert_eq!(
+        upcoming.iter().map(|s| s.code.as_str()).collect::<Vec<_>>(),
+        vec!["today", "later"]
+    );
+}
+
 // Integration tests (require network access)
 #[tokio::test]
 #[ignore] // Run with: cargo test -- --ignored
@@ -0,0 +1,109 @@
+//! Slow query logging for the databases the server opens.
+//!
+//! The SQLite side of [`mtg_common::sql_profile`]: a profile hook that hands
+//! statements slower than the threshold to the shared log together with
+//! their `EXPLAIN QUERY PLAN`, and the index list the advisor compares its
+//! suggestions against. The plan is looked up on a separate read-only
+//! connection, since the hook cannot reach the connection that ran the
+//! statement.
+
+use mtg_common::sql_profile::{self, ExistingIndex, IndexAdvice};
+use rusqlite::trace::{TraceEvent, TraceEventCodes};
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+use std::time::Duration;
+
+/// Profiles `conn` (opened from `path`) when slow query logging is on.
+pub fn instrument(conn: &Connection, path: &Path) {
+    if sql_profile::slow_query_threshold().is_none() {
+        return;
+    }
+    sql_profile::watch_database(path);
+    conn.trace_v2(TraceEventCodes::SQLITE_TRACE_PROFILE, Some(on_trace));
+}
+
+fn on_trace(event: TraceEvent<'_>) {
+    if let TraceEvent::Profile(stmt, elapsed) = event {
+        if sql_profile::is_slow(elapsed) {
+            on_slow_statement(&stmt.sql(), elapsed);
+        }
+    }
+}
+
+fn on_slow_statement(sql: &str, elapsed: Duration) {
+    // A statement only prepares against the database that has its tables
+    let plan = sql_profile::watched_databases()
+        .iter()
+        .find_map(|path| explain_query_plan(&open_read_only(path).ok()?, sql).ok())
+        .unwrap_or_default();
+    sql_profile::record_slow_query(sql, elapsed, plan);
+}
+
+/// The detail lines of `EXPLAIN QUERY PLAN` for `sql`. Parameters are left
+/// unbound, which the planner treats as unknown values.
+pub fn explain_query_plan(conn: &Connection, sql: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {sql}"))?;
+    let mut rows = stmt.raw_query();
+    let mut plan = Vec::new();
+    while let Some(row) = rows.next()? {
+        plan.push(row.get(3)?);
+    }
+    Ok(plan)
+}
+
+/// Every index of the database with its columns in order.
+pub fn existing_indexes(conn: &Connection) -> rusqlite::Result<Vec<ExistingIndex>> {
+    let mut stmt = conn.prepare(
+        "SELECT m.tbl_name, m.name, i.name
+         FROM sqlite_master m JOIN pragma_index_info(m.name) i
+         WHERE m.type = 'index'
+         ORDER BY m.name, i.seqno",
+    )?;
+    let mut indexes: Vec<(String, ExistingIndex)> = Vec::new();
+    let rows = stmt.query_map([], |r| {
+        Ok((
+            r.get::<_, String>(0)?,
+            r.get::<_, String>(1)?,
+            r.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (table, index, column) = row?;
+        let column = column.unwrap_or_default();
+        match indexes.last_mut() {
+            Some((name, existing)) if *name == index => existing.columns.push(column),
+            _ => indexes.push((
+                index,
+                ExistingIndex {
+                    table,
+                    columns: vec![column],
+                },
+            )),
+        }
+    }
+    Ok(indexes.into_iter().map(|(_, index)| index).collect())
+}
+
+/// The advisor's suggestions for the slow queries logged so far, checked
+/// against the indexes of every profiled database.
+pub fn index_advice() -> Vec<IndexAdvice> {
+    let mut existing = Vec::new();
+    for path in sql_profile::watched_databases() {
+        match open_read_only(&path).and_then(|conn| existing_indexes(&conn)) {
+            Ok(indexes) => existing.extend(indexes),
+            Err(e) => log::warn!("Could not list the indexes of {}: {}", path.display(), e),
+        }
+    }
+    sql_profile::advise_indexes(&sql_profile::slow_queries(), &existing)
+}
+
+fn open_read_only(path: &Path) -> rusqlite::Result<Connection> {
+    Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+}
+
+#[cfg(test)]
+#[path = "slow_queries_tests.rs"]
+mod tests;
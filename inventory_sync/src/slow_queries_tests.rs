@@ -0,0 +1,55 @@
+//! Tests for slow_queries.
+
+use super::*;
+use crate::database::init_schema;
+use tempfile::TempDir;
+
+#[test]
+fn plans_and_indexes_come_from_the_schema() {
+    let conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+
+    let plan =
+        explain_query_plan(&conn, "SELECT * FROM price_history WHERE id_product = ?1").unwrap();
+    assert_eq!(plan.len(), 1);
+    assert!(plan[0].starts_with("SEARCH price_history USING INDEX"));
+
+    let indexes = existing_indexes(&conn).unwrap();
+    assert!(indexes.contains(&ExistingIndex {
+        table: "products".to_string(),
+        columns: vec!["id_expansion".to_string()],
+    }));
+}
+
+#[test]
+fn slow_statements_are_logged_with_their_plan() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("prices.db");
+    let conn = Connection::open(&path).unwrap();
+    conn.execute_batch("CREATE TABLE slow_probe (id INTEGER PRIMARY KEY, label TEXT);")
+        .unwrap();
+
+    // SQLite times statements in whole milliseconds, so only a zero
+    // threshold catches a query on an empty table
+    sql_profile::set_slow_query_threshold(Some(Duration::ZERO));
+    instrument(&conn, &path);
+    conn.query_row(
+        "SELECT COUNT(*) FROM slow_probe WHERE label = ?1",
+        ["x"],
+        |r| r.get::<_, i64>(0),
+    )
+    .unwrap();
+    sql_profile::set_slow_query_threshold(None);
+
+    let logged = sql_profile::slow_queries();
+    let probe = logged
+        .iter()
+        .find(|q| q.sql.contains("slow_probe WHERE"))
+        .unwrap();
+    assert_eq!(probe.plan, vec!["SCAN slow_probe"]);
+
+    let advice = index_advice();
+    assert!(advice
+        .iter()
+        .any(|a| a.table == "slow_probe" && a.columns == ["label"]));
+}
@@ -0,0 +1,282 @@
+//! Read-only access to the check_stock inventory database
+//!
+//! check_stock keeps the current Cardmarket stock in its own SQLite file
+//! (`~/.local/share/d2d_automations/inventory.db`, table `inventory_cards`,
+//! one row per card variant with its storage location). When the web server
+//! runs with `--stock-db`, that file is opened read-only and the stock can be
+//! browsed and filtered by name, set, rarity, location and price — e.g. from
+//! a phone in the warehouse. Nothing here ever writes to it; check_stock
+//! stays the only owner of the schema.
+//!
+//! check_stock keeps several inventories in that table (`inventory_id`, e.g.
+//! the shop stock and a personal collection). Every reader here covers one
+//! of them, the shop stock unless another is asked for; databases from
+//! before inventories existed hold only the shop stock.
+
+use crate::database::DbResult;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Upper bound on rows returned by one stock query
+pub const MAX_STOCK_LIMIT: usize = 500;
+
+/// Inventory read unless another is asked for: check_stock's shop stock
+pub const DEFAULT_INVENTORY: &str = "shop";
+
+/// Filters for [`search_stock`]; every field is optional and they combine
+/// with AND.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StockFilter {
+    /// Case-insensitive substring of the card name
+    pub q: Option<String>,
+    /// Set code or set name, exact (case-insensitive)
+    pub set: Option<String>,
+    /// Rarity, exact (case-insensitive)
+    pub rarity: Option<String>,
+    /// Location prefix, e.g. `A1` matches `A1`, `A1-03`, …
+    pub location: Option<String>,
+    /// Lowest listed price in EUR (inclusive)
+    pub min_price: Option<f64>,
+    /// Highest listed price in EUR (inclusive)
+    pub max_price: Option<f64>,
+    /// Maximum rows, capped at [`MAX_STOCK_LIMIT`] (default: the cap)
+    pub limit: Option<usize>,
+    /// check_stock inventory, e.g. `collection` (default: `shop`)
+    pub inventory: Option<String>,
+}
+
+/// One in-stock card variant
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct StockCard {
+    pub cardmarket_id: String,
+    pub name: String,
+    pub set_name: String,
+    pub set_code: String,
+    pub cn: String,
+    pub rarity: String,
+    pub condition: String,
+    pub language: String,
+    pub is_foil: bool,
+    pub quantity: i64,
+    /// Listed unit price in EUR
+    pub price: f64,
+    pub location: Option<String>,
+}
+
+/// A set with at least one card in stock
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct StockSet {
+    pub code: String,
+    pub name: String,
+    pub copies: i64,
+}
+
+/// The values the stock filters can take, for the filter dropdowns
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct StockFacets {
+    /// The inventory the facets are of
+    pub inventory: String,
+    /// Every inventory with cards in stock
+    pub inventories: Vec<String>,
+    /// Sets by name
+    pub sets: Vec<StockSet>,
+    pub rarities: Vec<String>,
+    pub locations: Vec<String>,
+}
+
+/// Opens the check_stock database read-only and checks that it has an
+/// inventory table, so a wrong path is reported when the server starts
+/// rather than on the first request.
+pub fn open_stock_db(path: &Path) -> DbResult<Connection> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.query_row("SELECT COUNT(*) FROM inventory_cards", [], |row| {
+        row.get::<_, i64>(0)
+    })?;
+    crate::slow_queries::instrument(&conn, path);
+    Ok(conn)
+}
+
+/// Turns an empty or whitespace-only filter value into `None`.
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// The inventory ID asked for, as check_stock stores it (lower-case, spaces
+/// as dashes), or [`DEFAULT_INVENTORY`].
+pub fn selected_inventory(inventory: Option<&str>) -> String {
+    inventory
+        .map(|id| id.trim().to_lowercase().replace(' ', "-"))
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| DEFAULT_INVENTORY.to_string())
+}
+
+/// SQL for the inventory a row of `schema`'s `inventory_cards` belongs to:
+/// its `inventory_id` column (prefixed with `qualifier`, e.g. `s.`), or the
+/// shop for databases from before check_stock kept several inventories.
+pub(crate) fn inventory_column(
+    conn: &Connection,
+    schema: &str,
+    qualifier: &str,
+) -> DbResult<String> {
+    let has_column: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('inventory_cards', ?1)
+         WHERE name = 'inventory_id'",
+        [schema],
+        |row| row.get(0),
+    )?;
+    Ok(if has_column {
+        format!("{qualifier}inventory_id")
+    } else {
+        format!("'{DEFAULT_INVENTORY}'")
+    })
+}
+
+/// In-stock variants matching `filter`, by name, set and collector number.
+pub fn search_stock(conn: &Connection, filter: &StockFilter) -> DbResult<Vec<StockCard>> {
+    let mut clauses = vec![
+        "quantity > 0".to_string(),
+        format!("{} = ?1", inventory_column(conn, "main", "")?),
+    ];
+    let mut params = vec![Value::Text(selected_inventory(filter.inventory.as_deref()))];
+
+    if let Some(q) = non_empty(&filter.q) {
+        params.push(Value::Text(format!("%{}%", q.to_lowercase())));
+        clauses.push(format!("LOWER(name) LIKE ?{}", params.len()));
+    }
+    if let Some(set) = non_empty(&filter.set) {
+        params.push(Value::Text(set.to_lowercase()));
+        let n = params.len();
+        clauses.push(format!(
+            "(LOWER(set_code) = ?{n} OR LOWER(set_name) = ?{n})"
+        ));
+    }
+    if let Some(rarity) = non_empty(&filter.rarity) {
+        params.push(Value::Text(rarity.to_lowercase()));
+        clauses.push(format!("LOWER(rarity) = ?{}", params.len()));
+    }
+    if let Some(location) = non_empty(&filter.location) {
+        params.push(Value::Text(format!("{}%", location.to_lowercase())));
+        clauses.push(format!("LOWER(location) LIKE ?{}", params.len()));
+    }
+    if let Some(min_price) = filter.min_price {
+        params.push(Value::Real(min_price));
+        clauses.push(format!("CAST(price AS REAL) >= ?{}", params.len()));
+    }
+    if let Some(max_price) = filter.max_price {
+        params.push(Value::Real(max_price));
+        clauses.push(format!("CAST(price AS REAL) <= ?{}", params.len()));
+    }
+    let limit = filter.limit.unwrap_or(MAX_STOCK_LIMIT).min(MAX_STOCK_LIMIT);
+    params.push(Value::Integer(limit as i64));
+
+    let sql = format!(
+        "SELECT {STOCK_CARD_COLUMNS}
+         FROM inventory_cards
+         WHERE {}
+         ORDER BY name COLLATE NOCASE, set_name, cn, condition, language
+         LIMIT ?{}",
+        clauses.join(" AND "),
+        params.len()
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(params), stock_card)?;
+    rows.collect()
+}
+
+/// Every in-stock variant of `inventory` (default: the shop), for totals
+/// over the whole stock (no row cap).
+pub fn all_stock(conn: &Connection, inventory: Option<&str>) -> DbResult<Vec<StockCard>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {STOCK_CARD_COLUMNS} FROM inventory_cards
+         WHERE quantity > 0 AND {} = ?1
+         ORDER BY set_code, cardmarket_id",
+        inventory_column(conn, "main", "")?
+    ))?;
+    let rows = stmt.query_map([selected_inventory(inventory)], stock_card)?;
+    rows.collect()
+}
+
+/// Columns read by [`stock_card`], in order
+pub(crate) const STOCK_CARD_COLUMNS: &str =
+    "cardmarket_id, name, set_name, set_code, cn, rarity, condition,
+    language, (is_foil = '1' OR LOWER(is_foil) = 'true'), quantity, CAST(price AS REAL),
+    NULLIF(location, '')";
+
+/// Number of [`STOCK_CARD_COLUMNS`]
+pub(crate) const STOCK_CARD_COLUMN_COUNT: usize = 12;
+
+pub(crate) fn stock_card(row: &rusqlite::Row<'_>) -> rusqlite::Result<StockCard> {
+    Ok(StockCard {
+        cardmarket_id: row.get(0)?,
+        name: row.get(1)?,
+        set_name: row.get(2)?,
+        set_code: row.get(3)?,
+        cn: row.get(4)?,
+        rarity: row.get(5)?,
+        condition: row.get(6)?,
+        language: row.get(7)?,
+        is_foil: row.get(8)?,
+        quantity: row.get(9)?,
+        price: row.get(10)?,
+        location: row.get(11)?,
+    })
+}
+
+/// Sets, rarities and locations that occur in the current stock of
+/// `inventory` (default: the shop).
+pub fn stock_facets(conn: &Connection, inventory: Option<&str>) -> DbResult<StockFacets> {
+    let inventory = selected_inventory(inventory);
+    let column = inventory_column(conn, "main", "")?;
+    let sets = conn
+        .prepare(&format!(
+            "SELECT set_code, set_name, SUM(quantity) FROM inventory_cards
+             WHERE quantity > 0 AND {column} = ?1
+             GROUP BY set_code, set_name
+             ORDER BY set_name COLLATE NOCASE"
+        ))?
+        .query_map([&inventory], |row| {
+            Ok(StockSet {
+                code: row.get(0)?,
+                name: row.get(1)?,
+                copies: row.get(2)?,
+            })
+        })?
+        .collect::<DbResult<Vec<_>>>()?;
+    let distinct = |facet: &str| -> DbResult<Vec<String>> {
+        conn.prepare(&format!(
+            "SELECT DISTINCT {facet} FROM inventory_cards
+             WHERE quantity > 0 AND {column} = ?1 AND {facet} IS NOT NULL AND {facet} != ''
+             ORDER BY {facet} COLLATE NOCASE"
+        ))?
+        .query_map([&inventory], |row| row.get(0))?
+        .collect()
+    };
+    let inventories = conn
+        .prepare(&format!(
+            "SELECT DISTINCT {column} FROM inventory_cards WHERE quantity > 0 ORDER BY 1"
+        ))?
+        .query_map([], |row| row.get(0))?
+        .collect::<DbResult<Vec<_>>>()?;
+    let rarities = distinct("rarity")?;
+    let locations = distinct("location")?;
+    Ok(StockFacets {
+        inventory,
+        inventories,
+        sets,
+        rarities,
+        locations,
+    })
+}
+
+#[cfg(test)]
+pub use tests::{add_test_collection, create_test_stock_db};
+
+#[cfg(test)]
+#[path = "stock_tests.rs"]
+mod tests;
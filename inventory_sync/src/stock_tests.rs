@@ -0,0 +1,221 @@
+//! Tests for stock.
+
+use super::*;
+use tempfile::TempDir;
+
+/// Writes a check_stock-shaped database with a few cards to `dir` and
+/// returns its path
+pub fn create_test_stock_db(dir: &Path) -> std::path::PathBuf {
+    let path = dir.join("stock.db");
+    let conn = Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE inventory_cards (
+            cardmarket_id TEXT NOT NULL, quantity INTEGER NOT NULL, name TEXT NOT NULL,
+            set_name TEXT NOT NULL, set_code TEXT NOT NULL, cn TEXT NOT NULL,
+            condition TEXT NOT NULL, language TEXT NOT NULL, is_foil TEXT NOT NULL,
+            is_playset TEXT, is_signed TEXT NOT NULL, price TEXT NOT NULL,
+            comment TEXT NOT NULL, location TEXT, rarity TEXT NOT NULL
+        );
+        INSERT INTO inventory_cards VALUES
+            ('1', 2, 'Lightning Bolt', 'Alpha', 'LEA', '161', 'NM', 'English', 'false',
+             NULL, 'false', '120.00', '', 'A1-03', 'Common'),
+            ('1', 1, 'Lightning Bolt', 'Alpha', 'LEA', '161', 'EX', 'German', 'true',
+             NULL, 'false', '95.50', '', 'A1-04', 'Common'),
+            ('2', 1, 'Black Lotus', 'Alpha', 'LEA', '232', 'GD', 'English', 'false',
+             NULL, 'false', '9000', '', 'SAFE', 'Rare'),
+            ('3', 4, 'Counterspell', 'Ice Age', 'ICE', '64', 'NM', 'English', '1',
+             NULL, 'false', '1.50', '', 'B2-01', 'Common'),
+            ('4', 0, 'Sold Out Card', 'Ice Age', 'ICE', '99', 'NM', 'English', 'false',
+             NULL, 'false', '5.00', '', 'B2-02', 'Uncommon');",
+    )
+    .unwrap();
+    path
+}
+
+/// Moves the test stock database to the current schema, where every card
+/// belongs to an inventory, and adds a personal collection next to the shop
+/// stock: a second Black Lotus and a Force of Will.
+pub fn add_test_collection(path: &Path) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(
+        "ALTER TABLE inventory_cards ADD COLUMN inventory_id TEXT NOT NULL DEFAULT 'shop';
+        INSERT INTO inventory_cards VALUES
+            ('2', 1, 'Black Lotus', 'Alpha', 'LEA', '232', 'NM', 'English', 'false',
+             NULL, 'false', '15000', '', 'BINDER', 'Rare', 'collection'),
+            ('5', 1, 'Force of Will', 'Alliances', 'ALL', '28', 'EX', 'English', 'false',
+             NULL, 'false', '80.00', '', 'BINDER', 'Uncommon', 'collection');",
+    )
+    .unwrap();
+}
+
+fn open_test_db() -> (Connection, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let conn = open_stock_db(&create_test_stock_db(dir.path())).unwrap();
+    (conn, dir)
+}
+
+fn names(cards: &[StockCard]) -> Vec<&str> {
+    cards.iter().map(|c| c.name.as_str()).collect()
+}
+
+#[test]
+fn unfiltered_search_lists_in_stock_cards_by_name() {
+    let (conn, _dir) = open_test_db();
+
+    let cards = search_stock(&conn, &StockFilter::default()).unwrap();
+
+    assert_eq!(
+        names(&cards),
+        vec![
+            "Black Lotus",
+            "Counterspell",
+            "Lightning Bolt",
+            "Lightning Bolt"
+        ]
+    );
+    let foil_bolt = cards.iter().find(|c| c.condition == "EX").unwrap();
+    assert!(foil_bolt.is_foil);
+    assert_eq!(foil_bolt.price, 95.5);
+    assert_eq!(foil_bolt.location.as_deref(), Some("A1-04"));
+    assert!(
+        cards
+            .iter()
+            .find(|c| c.name == "Counterspell")
+            .unwrap()
+            .is_foil
+    );
+}
+
+#[test]
+fn filters_combine() {
+    let (conn, _dir) = open_test_db();
+    let search = |filter: StockFilter| names(&search_stock(&conn, &filter).unwrap()).len();
+
+    let by_set = StockFilter {
+        set: Some("lea".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search(by_set.clone()), 3);
+    let by_set_name = StockFilter {
+        set: Some("Ice Age".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search(by_set_name), 1);
+    let rare_alpha = StockFilter {
+        rarity: Some("rare".to_string()),
+        ..by_set.clone()
+    };
+    assert_eq!(search(rare_alpha), 1);
+    let shelf_a1 = StockFilter {
+        location: Some("a1".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search(shelf_a1), 2);
+    let mid_price = StockFilter {
+        min_price: Some(1.5),
+        max_price: Some(100.0),
+        ..Default::default()
+    };
+    assert_eq!(search(mid_price), 2);
+    let by_name = StockFilter {
+        q: Some("bolt".to_string()),
+        location: Some("A1-03".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(search(by_name), 1);
+}
+
+#[test]
+fn blank_filters_are_ignored_and_limit_is_capped() {
+    let (conn, _dir) = open_test_db();
+    let filter = StockFilter {
+        q: Some("  ".to_string()),
+        set: Some(String::new()),
+        limit: Some(1),
+        ..Default::default()
+    };
+
+    assert_eq!(search_stock(&conn, &filter).unwrap().len(), 1);
+}
+
+#[test]
+fn facets_list_in_stock_values() {
+    let (conn, _dir) = open_test_db();
+
+    let facets = stock_facets(&conn, None).unwrap();
+
+    assert_eq!(
+        facets.sets,
+        vec![
+            StockSet {
+                code: "LEA".to_string(),
+                name: "Alpha".to_string(),
+                copies: 4,
+            },
+            StockSet {
+                code: "ICE".to_string(),
+                name: "Ice Age".to_string(),
+                copies: 4,
+            },
+        ]
+    );
+    // The sold-out uncommon does not show up
+    assert_eq!(facets.rarities, vec!["Common", "Rare"]);
+    assert_eq!(facets.inventories, vec!["shop"]);
+    assert_eq!(facets.locations, vec!["A1-03", "A1-04", "B2-01", "SAFE"]);
+}
+
+#[test]
+fn readers_cover_one_inventory_at_a_time() {
+    let dir = TempDir::new().unwrap();
+    let path = create_test_stock_db(dir.path());
+    add_test_collection(&path);
+    let conn = open_stock_db(&path).unwrap();
+    let search = |inventory: Option<&str>| {
+        let filter = StockFilter {
+            inventory: inventory.map(str::to_string),
+            ..Default::default()
+        };
+        search_stock(&conn, &filter).unwrap()
+    };
+
+    // The shop stock by default, as in a database without inventories
+    assert_eq!(search(None).len(), 4);
+    assert_eq!(search(Some(" Shop ")).len(), 4);
+    assert_eq!(
+        names(&search(Some("collection"))),
+        vec!["Black Lotus", "Force of Will"]
+    );
+    assert!(search(Some("nowhere")).is_empty());
+    assert_eq!(all_stock(&conn, None).unwrap().len(), 4);
+    assert_eq!(all_stock(&conn, Some("collection")).unwrap().len(), 2);
+
+    let shop = stock_facets(&conn, None).unwrap();
+    assert_eq!(shop.inventory, "shop");
+    assert_eq!(shop.inventories, vec!["collection", "shop"]);
+    assert!(!shop.locations.contains(&"BINDER".to_string()));
+    let collection = stock_facets(&conn, Some("collection")).unwrap();
+    assert_eq!(collection.locations, vec!["BINDER"]);
+    assert_eq!(collection.rarities, vec!["Rare", "Uncommon"]);
+    assert_eq!(collection.sets.len(), 2);
+}
+
+#[test]
+fn open_rejects_database_without_inventory() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("other.db");
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch("CREATE TABLE unrelated (id INTEGER)")
+        .unwrap();
+
+    assert!(open_stock_db(&path).is_err());
+    assert!(open_stock_db(&dir.path().join("missing.db")).is_err());
+}
+
+#[test]
+fn stock_db_is_opened_read_only() {
+    let (conn, _dir) = open_test_db();
+
+    assert!(conn.execute("DELETE FROM inventory_cards", []).is_err());
+}
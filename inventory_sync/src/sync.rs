@@ -0,0 +1,696 @@
+//! Cardmarket and TCGplayer price sync
+//!
+//! One sync run downloads the product catalog, expansion list and price
+//! guide and stores them in the database, then optionally collects TCGplayer
+//! prices. Every step logs its failures and ends the run early instead of
+//! returning an error, since the daemon simply retries on its next check.
+//! The download URLs live in [`CardmarketSources`], so tests can serve canned
+//! files from a local server.
+//!
+//! With a price guide archive configured, each run also looks for days in the
+//! last [`SyncOptions::backfill_days`] without price data (the daemon was down,
+//! a download failed) and imports their dated archive files, oldest first,
+//! before today's guide — so gaps heal themselves on the next run.
+//!
+//! [`SyncOptions::downloads`] throttles the large catalog and price guide
+//! downloads and can restrict Cardmarket syncs to a time window, so they do
+//! not saturate the shop's line during business hours (see [`download`]).
+//!
+//! Completed steps are recorded per day in the `sync_state` table: once the
+//! catalog step (catalog, expansions, release calendar) has run, later runs
+//! on the same day — e.g. after the daemon restarted before the price import
+//! — take the catalog from the database instead of downloading and upserting
+//! it again, and once today's price guide is imported the day is done.
+//!
+//! Each catalog sync also refreshes the release calendar from Scryfall's set
+//! list. Products that are new to the catalog and belong to one of those
+//! upcoming sets go onto the watchlist of every tenant that opted in, so
+//! preorder prices can be followed from the first day they are listed.
+//!
+//! A forced run ([`SyncOptions::force`], `inventory_sync sync --force`)
+//! ignores all of that: it downloads the catalog and today's price guide even
+//! if the day is already done, and even outside the download window. Price
+//! rows that are already stored are still skipped, so forcing never
+//! duplicates history.
+//!
+//! Every day of prices imported by the daily sync is checked against the
+//! tenants' price alerts; the ones that fired are logged and recorded (see
+//! [`crate::alerts`]).
+//!
+//! Each run returns a [`SyncReport`]; with [`SyncOptions::webhook_url`] set,
+//! a run that stored prices or failed posts it to the webhook with the day's
+//! top movers (see [`notify`]).
+//!
+//! With [`SyncOptions::backup`] set, every run ends by uploading the day's
+//! database snapshot to off-site storage once today's prices are in (see
+//! [`backup`]); like the downloads, uploads only run inside the download
+//! window.
+
+use crate::alerts::evaluate_alerts;
+use crate::backup::{self, BackupOptions};
+use crate::cardmarket::{
+    fetch_expansions_from, PriceGuide, ProductCatalog, EXPANSIONS_URL, NON_SINGLES_URL, SINGLES_URL,
+};
+use crate::database::{
+    get_missing_price_dates, get_pending_import, get_tcgplayer_candidates, get_unknown_product_ids,
+    has_price_data_for_today, has_tcgplayer_prices_for_date, insert_price_history_for_categories,
+    insert_price_history_for_date, insert_tcgplayer_prices, is_sync_step_done,
+    load_product_catalog, mark_sync_step_done, replace_upcoming_sets, today_date,
+    upsert_exchange_rate, upsert_expansions, upsert_products, watch_new_set_products, SyncStep,
+};
+use crate::download::{self, DownloadLimits};
+use crate::error::InventoryError;
+use crate::notify;
+use crate::scryfall::{fetch_sets_from, upcoming_paper_sets};
+use crate::tcgplayer;
+use mtg_common::Category;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Where the Cardmarket files (and the Scryfall set list) are downloaded from
+#[derive(Debug, Clone)]
+pub struct CardmarketSources {
+    pub singles_url: String,
+    pub non_singles_url: String,
+    pub expansions_url: String,
+    pub price_guide_url: String,
+    /// URL of the dated price guide archives, with `{date}` standing for the
+    /// YYYY-MM-DD price date; `None` disables backfilling
+    pub price_guide_archive_url: Option<String>,
+    /// Scryfall API base URL for the release calendar; `None` disables it
+    pub scryfall_api_url: Option<String>,
+}
+
+impl Default for CardmarketSources {
+    /// Cardmarket's public CDN
+    fn default() -> Self {
+        Self {
+            singles_url: SINGLES_URL.to_string(),
+            non_singles_url: NON_SINGLES_URL.to_string(),
+            expansions_url: EXPANSIONS_URL.to_string(),
+            price_guide_url: mtg_common::PRICE_GUIDE_URL.to_string(),
+            price_guide_archive_url: None,
+            scryfall_api_url: Some(mtg_common::scryfall::SCRYFALL_API.to_string()),
+        }
+    }
+}
+
+/// Per-run sync settings
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Only store prices for these categories (empty: all)
+    pub categories: Vec<Category>,
+    pub tcgplayer_min_trend: Option<f64>,
+    /// Raw price guide download, kept next to the database until its import
+    /// completes so an interrupted import can resume without re-fetching
+    pub raw_price_guide: PathBuf,
+    pub sources: CardmarketSources,
+    /// How many days before today are checked for gaps to backfill from the
+    /// archive
+    pub backfill_days: u32,
+    /// Rate limit and time window for the catalog and price guide downloads
+    pub downloads: DownloadLimits,
+    /// Off-site backup of the database after the sync; `None` disables it
+    pub backup: Option<BackupOptions>,
+    /// Download and import even if today's prices are already stored and
+    /// outside the download window (manual `sync --force`)
+    pub force: bool,
+    /// Webhook (Discord, Slack, …) sent a summary after each run; `None`
+    /// disables it
+    pub webhook_url: Option<String>,
+}
+
+/// What one sync run did, for the webhook summary (see [`notify`])
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SyncReport {
+    /// Price date of the guide imported in this run
+    pub price_date: Option<String>,
+    /// Price rows stored for `price_date`
+    pub inserted: usize,
+    /// Price rows already stored for `price_date`
+    pub skipped: usize,
+    /// Days filled in from the price guide archive
+    pub backfilled: Vec<String>,
+    /// The failures of the run, as logged
+    pub errors: Vec<String>,
+}
+
+impl SyncReport {
+    /// Whether the run stored nothing and nothing failed, e.g. because
+    /// today's prices were already in
+    pub fn is_idle(&self) -> bool {
+        self.inserted == 0 && self.backfilled.is_empty() && self.errors.is_empty()
+    }
+
+    /// Logs a failure and keeps it for the summary
+    fn error(&mut self, message: String) {
+        log::error!("{}", message);
+        self.errors.push(message);
+    }
+}
+
+/// Run a single sync operation: Cardmarket prices, then the optional
+/// TCGplayer prices (each skipped if already collected today), then the
+/// optional backup (skipped if already uploaded today). With a webhook
+/// configured, a run that imported prices or failed ends with a summary
+/// posted to it.
+pub async fn run_sync(db: &Arc<Mutex<Connection>>, options: &SyncOptions) -> SyncReport {
+    let mut report = sync_cardmarket(db, options).await;
+    if let Some(min_trend) = options.tcgplayer_min_trend {
+        sync_tcgplayer(db, min_trend, &mut report).await;
+    }
+    if let Some(backup) = &options.backup {
+        if options.force || options.downloads.may_download_now() {
+            backup::run_backup(db, backup).await;
+        }
+    }
+    if let Some(url) = &options.webhook_url {
+        if !report.is_idle() {
+            notify::send_sync_summary(db, url, &report).await;
+        }
+    }
+    report
+}
+
+/// Sync the Cardmarket catalog, expansions and price guide
+pub async fn sync_cardmarket(db: &Arc<Mutex<Connection>>, options: &SyncOptions) -> SyncReport {
+    let mut report = SyncReport::default();
+    if !options.force && !options.downloads.may_download_now() {
+        if let Some(window) = options.downloads.window {
+            log::info!(
+                "Outside the download window {}, postponing the Cardmarket sync",
+                window
+            );
+        }
+        return report;
+    }
+
+    // Check if we already have price data for today
+    let today = today_date();
+    let should_sync = options.force || {
+        let conn = db.lock().unwrap();
+        let done = is_sync_step_done(&conn, &today, SyncStep::Prices)
+            .and_then(|done| Ok(done || has_price_data_for_today(&conn)?));
+        match done {
+            Ok(true) => {
+                log::info!("Price data for today already exists in database, skipping download");
+                false
+            }
+            Ok(false) => {
+                log::info!("No price data for today, proceeding with download...");
+                true
+            }
+            Err(e) => {
+                report.error(format!("Failed to check existing price data: {}", e));
+                false
+            }
+        }
+    };
+
+    let backfill_dates = missing_archive_dates(db, options, &mut report);
+    if !should_sync && backfill_dates.is_empty() {
+        return report;
+    }
+
+    if options.force {
+        log::info!("Forced sync, downloading today's catalog and prices again");
+    }
+    let stored = (!options.force)
+        .then(|| load_stored_catalog(db, &today))
+        .flatten();
+    let catalog = match stored {
+        Some(catalog) => catalog,
+        None => match sync_catalog(db, options, &today, &mut report).await {
+            Some(catalog) => catalog,
+            None => return report,
+        },
+    };
+
+    backfill_price_history(db, options, &catalog, &backfill_dates, &mut report).await;
+    if !should_sync {
+        return report;
+    }
+
+    // Fetch price guide from Cardmarket, or reuse the download of an
+    // interrupted import
+    let Some(guide) = load_price_guide(db, options, &mut report).await else {
+        return report;
+    };
+
+    // Insert price history (only if not already present for this date)
+    {
+        let mut conn = db.lock().unwrap();
+        match insert_price_history_for_categories(&mut conn, &guide, &catalog, &options.categories)
+        {
+            Ok(result) => {
+                report.price_date = Some(result.price_date.clone());
+                report.inserted = result.inserted;
+                report.skipped = result.skipped;
+                if result.inserted > 0 {
+                    log::info!(
+                        "Inserted {} price entries for {} ({} products not in catalog, {} outside selected categories, {} duplicates dropped)",
+                        result.inserted,
+                        result.price_date,
+                        result.no_product,
+                        result.filtered,
+                        result.deduplicated
+                    );
+                    check_price_alerts(&mut conn, &result.price_date, &mut report);
+                } else {
+                    log::info!(
+                        "Price data for {} already exists, {} entries skipped",
+                        result.price_date,
+                        result.skipped
+                    );
+                }
+                // Until Cardmarket publishes today's guide, the next check
+                // tries again
+                if result.price_date == today {
+                    mark_step_done(&conn, &today, SyncStep::Prices);
+                }
+            }
+            Err(e) => {
+                report.error(format!("Failed to insert price history: {}", e));
+                return report;
+            }
+        }
+    }
+
+    match std::fs::remove_file(&options.raw_price_guide) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => log::warn!(
+            "Failed to remove raw price guide {}: {}",
+            options.raw_price_guide.display(),
+            e
+        ),
+        _ => {}
+    }
+
+    log::info!("Sync completed successfully.");
+    report
+}
+
+/// Evaluates the price alerts against a newly imported day; a failure is
+/// logged and the sync carries on
+fn check_price_alerts(conn: &mut Connection, price_date: &str, report: &mut SyncReport) {
+    match evaluate_alerts(conn, price_date) {
+        Ok(fired) => {
+            for alert in &fired {
+                log::info!(
+                    "Price alert #{} of tenant {}: {}",
+                    alert.alert_id,
+                    alert.tenant_id,
+                    alert.describe()
+                );
+            }
+            if !fired.is_empty() {
+                log::info!("{} price alerts fired for {}", fired.len(), price_date);
+            }
+        }
+        Err(e) => report.error(format!("Failed to evaluate price alerts: {}", e)),
+    }
+}
+
+/// The catalog stored by today's completed catalog step, `None` if the step
+/// still has to run (or the stored catalog cannot be read)
+fn load_stored_catalog(db: &Arc<Mutex<Connection>>, today: &str) -> Option<ProductCatalog> {
+    let conn = db.lock().unwrap();
+    match is_sync_step_done(&conn, today, SyncStep::Catalog) {
+        Ok(true) => {}
+        Ok(false) => return None,
+        Err(e) => {
+            log::warn!("Failed to check the catalog sync state: {}", e);
+            return None;
+        }
+    }
+    match load_product_catalog(&conn) {
+        Ok(catalog) => {
+            log::info!(
+                "Product catalog already synced today, using the {} stored products",
+                catalog.len()
+            );
+            Some(catalog)
+        }
+        Err(e) => {
+            log::warn!("Failed to load the stored product catalog: {}", e);
+            None
+        }
+    }
+}
+
+/// Records a completed sync step; a failure only means the step runs again
+fn mark_step_done(conn: &Connection, today: &str, step: SyncStep) {
+    if let Err(e) = mark_sync_step_done(conn, today, step) {
+        log::warn!("Failed to record the {} sync step: {}", step.as_str(), e);
+    }
+}
+
+/// The catalog step: downloads the product catalog and stores it with the
+/// expansion list and release calendar. `None` if the catalog could not be
+/// fetched or stored.
+async fn sync_catalog(
+    db: &Arc<Mutex<Connection>>,
+    options: &SyncOptions,
+    today: &str,
+    report: &mut SyncReport,
+) -> Option<ProductCatalog> {
+    // Fetch product catalog from Cardmarket (singles + non-singles)
+    let catalog = match ProductCatalog::fetch_with(
+        &options.sources.singles_url,
+        &options.sources.non_singles_url,
+        &options.downloads,
+    )
+    .await
+    {
+        Ok(catalog) => {
+            log::info!(
+                "Fetched product catalog: {} products ({} singles, {} non-singles)",
+                catalog.len(),
+                catalog.singles_count(),
+                catalog.non_singles_count()
+            );
+            catalog
+        }
+        Err(e) => {
+            report.error(format!("Failed to fetch product catalog: {}", e));
+            return None;
+        }
+    };
+
+    // Upsert products into database, remembering which ones are new
+    let new_products = {
+        let mut conn = db.lock().unwrap();
+        let new_products = get_unknown_product_ids(&conn, &catalog).unwrap_or_else(|e| {
+            log::warn!("Failed to look up new products: {}", e);
+            Vec::new()
+        });
+        match upsert_products(&mut conn, &catalog) {
+            Ok(count) => {
+                log::info!("Synced {} products to database", count);
+            }
+            Err(e) => {
+                report.error(format!("Failed to upsert products: {}", e));
+                return None;
+            }
+        }
+        new_products
+    };
+
+    // Refresh expansion metadata. Only used for display names, so a failure
+    // here is logged and the price sync carries on.
+    match fetch_expansions_from(&options.sources.expansions_url).await {
+        Ok(expansions) => {
+            let mut conn = db.lock().unwrap();
+            match upsert_expansions(&mut conn, &expansions) {
+                Ok(count) => log::info!("Synced {} expansions to database", count),
+                Err(e) => log::warn!("Failed to upsert expansions: {}", e),
+            }
+        }
+        Err(e) => log::warn!("Failed to fetch expansion list: {}", e),
+    }
+
+    sync_release_calendar(db, options, &new_products).await;
+
+    mark_step_done(&db.lock().unwrap(), today, SyncStep::Catalog);
+    Some(catalog)
+}
+
+/// Refresh the upcoming sets from Scryfall and put new products of those sets
+/// on the opted-in watchlists. Failures are logged; the price sync carries on.
+async fn sync_release_calendar(
+    db: &Arc<Mutex<Connection>>,
+    options: &SyncOptions,
+    new_products: &[u64],
+) {
+    if let Some(api_url) = &options.sources.scryfall_api_url {
+        match fetch_sets_from(api_url).await {
+            Ok(sets) => {
+                let upcoming = upcoming_paper_sets(sets, &today_date());
+                let mut conn = db.lock().unwrap();
+                match replace_upcoming_sets(&mut conn, &upcoming) {
+                    Ok(count) => log::info!("Release calendar: {} upcoming sets", count),
+                    Err(e) => log::warn!("Failed to store upcoming sets: {}", e),
+                }
+            }
+            Err(e) => log::warn!("Failed to fetch Scryfall set list: {}", e),
+        }
+    }
+
+    if new_products.is_empty() {
+        return;
+    }
+    let mut conn = db.lock().unwrap();
+    match watch_new_set_products(&mut conn, new_products) {
+        Ok(0) => {}
+        Ok(added) => log::info!(
+            "Added {} watchlist entries for new products of upcoming sets",
+            added
+        ),
+        Err(e) => log::warn!("Failed to watch new products of upcoming sets: {}", e),
+    }
+}
+
+/// Days in the backfill window (before today) without price data, oldest
+/// first; empty without an archive URL.
+fn missing_archive_dates(
+    db: &Arc<Mutex<Connection>>,
+    options: &SyncOptions,
+    report: &mut SyncReport,
+) -> Vec<String> {
+    if options.sources.price_guide_archive_url.is_none() || options.backfill_days == 0 {
+        return Vec::new();
+    }
+    let today = chrono::NaiveDate::parse_from_str(&today_date(), "%Y-%m-%d")
+        .expect("today_date is YYYY-MM-DD");
+    let window = |days: u32| {
+        (today - chrono::Days::new(u64::from(days)))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    let conn = db.lock().unwrap();
+    match get_missing_price_dates(&conn, &window(options.backfill_days), &window(1)) {
+        Ok(dates) => {
+            if !dates.is_empty() {
+                log::info!(
+                    "{} days without price data in the last {} days: {}",
+                    dates.len(),
+                    options.backfill_days,
+                    dates.join(", ")
+                );
+            }
+            dates
+        }
+        Err(e) => {
+            report.error(format!("Failed to check for missing price dates: {}", e));
+            Vec::new()
+        }
+    }
+}
+
+/// Import the archived price guides of `dates` in order. Dates without an
+/// archive are skipped; any other download failure ends the backfill, which
+/// is retried on the next run.
+async fn backfill_price_history(
+    db: &Arc<Mutex<Connection>>,
+    options: &SyncOptions,
+    catalog: &ProductCatalog,
+    dates: &[String],
+    report: &mut SyncReport,
+) {
+    let Some(template) = options.sources.price_guide_archive_url.as_deref() else {
+        return;
+    };
+    for date in dates {
+        let url = template.replace("{date}", date);
+        let raw = match download::fetch_text(&url, &options.downloads).await {
+            Ok(raw) => raw,
+            Err(InventoryError::HttpStatus(status)) if status == reqwest::StatusCode::NOT_FOUND => {
+                log::info!("No price guide archive for {}, skipping", date);
+                continue;
+            }
+            Err(e) => {
+                report.error(format!(
+                    "Failed to fetch price guide archive for {}: {}",
+                    date, e
+                ));
+                return;
+            }
+        };
+        let guide = match PriceGuide::from_json(&raw) {
+            Ok(guide) => guide,
+            Err(e) => {
+                log::warn!(
+                    "Skipping unreadable price guide archive for {}: {}",
+                    date,
+                    e
+                );
+                continue;
+            }
+        };
+        // An archive holding another day's guide would store prices under
+        // the wrong date
+        if guide.created_at().get(..10) != Some(date.as_str()) {
+            log::warn!(
+                "Skipping price guide archive for {}: it was created {}",
+                date,
+                guide.created_at()
+            );
+            continue;
+        }
+
+        let mut conn = db.lock().unwrap();
+        match insert_price_history_for_date(&mut conn, &guide, catalog, &options.categories, date) {
+            Ok(result) => {
+                log::info!(
+                    "Backfilled {} price entries for {} ({} products not in catalog, {} outside selected categories)",
+                    result.inserted,
+                    date,
+                    result.no_product,
+                    result.filtered
+                );
+                report.backfilled.push(date.clone());
+            }
+            Err(e) => log::warn!("Failed to backfill prices for {}: {}", date, e),
+        }
+    }
+}
+
+/// Load the price guide for this sync. If an import was interrupted and its
+/// raw download is still on disk, that copy is reused; otherwise a fresh
+/// download is stored at `options.raw_price_guide` before it is parsed.
+async fn load_price_guide(
+    db: &Arc<Mutex<Connection>>,
+    options: &SyncOptions,
+    report: &mut SyncReport,
+) -> Option<PriceGuide> {
+    let raw_path = options.raw_price_guide.as_path();
+    let pending = {
+        let conn = db.lock().unwrap();
+        match get_pending_import(&conn) {
+            Ok(pending) => pending,
+            Err(e) => {
+                report.error(format!("Failed to check for an interrupted import: {}", e));
+                return None;
+            }
+        }
+    };
+    if let Some(pending) = pending {
+        match std::fs::read_to_string(raw_path).map(|raw| PriceGuide::from_json(&raw)) {
+            Ok(Ok(guide)) if guide.created_at() == pending.created_at => {
+                log::info!(
+                    "Resuming import of {} from stored download {}",
+                    pending.price_date,
+                    raw_path.display()
+                );
+                return Some(guide);
+            }
+            Ok(Ok(_)) => log::warn!(
+                "Stored download does not match the interrupted import of {}, re-fetching",
+                pending.price_date
+            ),
+            Ok(Err(e)) => log::warn!("Stored download is unreadable, re-fetching: {}", e),
+            Err(e) => log::warn!(
+                "Stored download {} missing, re-fetching: {}",
+                raw_path.display(),
+                e
+            ),
+        }
+    }
+
+    let raw = match download::fetch_text(&options.sources.price_guide_url, &options.downloads).await
+    {
+        Ok(raw) => raw,
+        Err(e) => {
+            report.error(format!("Failed to fetch price guide: {}", e));
+            return None;
+        }
+    };
+    if let Err(e) = std::fs::write(raw_path, &raw) {
+        // Only costs a re-download after a crash, so keep going
+        log::warn!(
+            "Failed to store raw price guide at {}: {}",
+            raw_path.display(),
+            e
+        );
+    }
+    match PriceGuide::from_json(&raw) {
+        Ok(guide) => {
+            let finishes = guide.finish_counts();
+            log::info!(
+                "Fetched price guide: {} entries (created: {}; priced non-foil only: {}, foil only: {}, both: {}, unpriced: {})",
+                guide.len(),
+                guide.created_at(),
+                finishes.non_foil_only,
+                finishes.foil_only,
+                finishes.both,
+                finishes.unpriced
+            );
+            Some(guide)
+        }
+        Err(e) => {
+            report.error(format!("Failed to parse price guide: {}", e));
+            None
+        }
+    }
+}
+
+/// Collect TCGplayer prices and the EUR/USD rate for the products whose
+/// latest Cardmarket trend is at least `min_trend` EUR
+async fn sync_tcgplayer(db: &Arc<Mutex<Connection>>, min_trend: f64, report: &mut SyncReport) {
+    let today = today_date();
+    let ids = {
+        let conn = db.lock().unwrap();
+        match has_tcgplayer_prices_for_date(&conn, &today) {
+            Ok(true) => {
+                log::info!("TCGplayer prices for today already collected, skipping");
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                report.error(format!("Failed to check existing TCGplayer prices: {}", e));
+                return;
+            }
+        }
+        match get_tcgplayer_candidates(&conn, min_trend, tcgplayer::MAX_LOOKUPS) {
+            Ok(ids) => ids,
+            Err(e) => {
+                report.error(format!(
+                    "Failed to select products for TCGplayer prices: {}",
+                    e
+                ));
+                return;
+            }
+        }
+    };
+    if ids.is_empty() {
+        log::info!("No products with a trend of at least {min_trend} EUR, skipping TCGplayer");
+        return;
+    }
+
+    let usd_per_eur = match tcgplayer::fetch_usd_per_eur().await {
+        Ok(rate) => rate,
+        Err(e) => {
+            report.error(format!("Failed to fetch EUR/USD rate: {}", e));
+            return;
+        }
+    };
+
+    log::info!("Fetching TCGplayer prices for {} products...", ids.len());
+    let prices = tcgplayer::fetch_tcgplayer_prices(&ids).await;
+
+    let mut conn = db.lock().unwrap();
+    if let Err(e) = upsert_exchange_rate(&conn, &today, "USD", usd_per_eur) {
+        report.error(format!("Failed to store EUR/USD rate: {}", e));
+        return;
+    }
+    match insert_tcgplayer_prices(&mut conn, &today, &prices) {
+        Ok(count) => log::info!(
+            "Stored {} TCGplayer prices ({} products without one), 1 EUR = {} USD",
+            count,
+            ids.len() - prices.len(),
+            usd_per_eur
+        ),
+        Err(e) => report.error(format!("Failed to store TCGplayer prices: {}", e)),
+    }
+}
@@ -0,0 +1,118 @@
+//! Optional TCGplayer price source for Cardmarket vs. TCGplayer comparisons.
+//!
+//! TCGplayer's own API needs partner keys, but Scryfall publishes TCGplayer
+//! market prices (USD) on every card object and resolves Cardmarket product
+//! IDs directly. The daily sync therefore looks up the products worth
+//! cross-listing (latest Cardmarket trend above a threshold) on Scryfall and
+//! stores their USD prices in `tcgplayer_prices`, next to the ECB's EUR→USD
+//! reference rate in `exchange_rates`.
+
+use std::time::Duration;
+
+use crate::error::{InventoryError, Result};
+use crate::scryfall::ScryfallCard;
+
+/// The ECB's daily euro reference rates (XML, published ~16:00 CET).
+pub const ECB_DAILY_RATES_URL: &str =
+    "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+
+/// Pause between Scryfall lookups, per Scryfall's 50–100 ms request guideline.
+pub const SCRYFALL_DELAY: Duration = Duration::from_millis(100);
+
+/// Upper bound on Scryfall lookups per sync, most expensive products first.
+pub const MAX_LOOKUPS: usize = 2000;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// TCGplayer market prices for one Cardmarket product.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcgplayerPrice {
+    pub id_product: u64,
+    pub usd: Option<f64>,
+    pub usd_foil: Option<f64>,
+}
+
+impl TcgplayerPrice {
+    /// Extracts the USD prices from a Scryfall card, or `None` if TCGplayer
+    /// has no price for either finish.
+    pub fn from_card(id_product: u64, card: &ScryfallCard) -> Option<Self> {
+        let parse = |price: &Option<String>| price.as_deref().and_then(|p| p.parse().ok());
+        let price = Self {
+            id_product,
+            usd: parse(&card.prices.usd),
+            usd_foil: parse(&card.prices.usd_foil),
+        };
+        (price.usd.is_some() || price.usd_foil.is_some()).then_some(price)
+    }
+}
+
+/// Converts a TCGplayer USD price to EUR and returns it with the spread
+/// against the Cardmarket EUR price, in percent. A positive spread means
+/// TCGplayer pays more.
+pub fn spread(cardmarket_eur: f64, tcgplayer_usd: f64, usd_per_eur: f64) -> (f64, f64) {
+    let tcgplayer_eur = tcgplayer_usd / usd_per_eur;
+    let spread_pct = (tcgplayer_eur - cardmarket_eur) / cardmarket_eur * 100.0;
+    (tcgplayer_eur, spread_pct)
+}
+
+/// Extracts the USD rate from the ECB daily rates XML
+/// (`<Cube currency='USD' rate='1.0832'/>`).
+pub fn parse_ecb_usd_rate(xml: &str) -> Option<f64> {
+    let tag_start = xml.find("currency='USD'")?;
+    let rest = &xml[tag_start..];
+    let rest = &rest[rest.find("rate='")? + "rate='".len()..];
+    rest[..rest.find('\'')?]
+        .parse()
+        .ok()
+        .filter(|r: &f64| *r > 0.0)
+}
+
+/// Fetch today's USD per EUR reference rate from the ECB.
+pub async fn fetch_usd_per_eur() -> Result<f64> {
+    fetch_usd_per_eur_from(ECB_DAILY_RATES_URL).await
+}
+
+/// Fetches the USD rate from the given URL (for testing with mock servers).
+pub async fn fetch_usd_per_eur_from(url: &str) -> Result<f64> {
+    let response = mtg_common::http::client_builder(HTTP_TIMEOUT)?
+        .build()?
+        .get(url)
+        .header("User-Agent", mtg_common::USER_AGENT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(InventoryError::HttpStatus(response.status()));
+    }
+    let xml = response.text().await?;
+    parse_ecb_usd_rate(&xml).ok_or_else(|| InventoryError::ExchangeRateUnavailable(url.to_string()))
+}
+
+/// Looks up TCGplayer prices for the given Cardmarket products on Scryfall,
+/// one request per product. Products Scryfall doesn't know, or without a
+/// TCGplayer price, are skipped.
+pub async fn fetch_tcgplayer_prices(ids: &[u64]) -> Vec<TcgplayerPrice> {
+    fetch_tcgplayer_prices_from(mtg_common::scryfall::SCRYFALL_API, ids, SCRYFALL_DELAY).await
+}
+
+/// Fetches TCGplayer prices from the given Scryfall base URL (for testing).
+pub async fn fetch_tcgplayer_prices_from(
+    base_url: &str,
+    ids: &[u64],
+    delay: Duration,
+) -> Vec<TcgplayerPrice> {
+    let mut prices = Vec::new();
+    for (i, &id) in ids.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(delay).await;
+        }
+        match mtg_common::scryfall::fetch_card_by_cardmarket_id_from(base_url, id).await {
+            Ok(card) => prices.extend(TcgplayerPrice::from_card(id, &card)),
+            Err(e) => log::debug!("No Scryfall card for cardmarket_id {}: {}", id, e),
+        }
+    }
+    prices
+}
+
+#[cfg(test)]
+#[path = "tcgplayer_tests.rs"]
+mod tests;
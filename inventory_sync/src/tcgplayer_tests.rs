@@ -0,0 +1,75 @@
+//! Tests for tcgplayer.
+
+use super::*;
+
+/// Trimmed-down ECB daily feed.
+const ECB_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01">
+  <Cube>
+    <Cube time='2026-02-02'>
+      <Cube currency='USD' rate='1.0832'/>
+      <Cube currency='JPY' rate='161.52'/>
+    </Cube>
+  </Cube>
+</gesmes:Envelope>"#;
+
+fn card_with_prices(prices: &str) -> ScryfallCard {
+    serde_json::from_str(&format!(
+        r#"{{
+        "id": "test-uuid",
+        "name": "Test Card",
+        "set": "tst",
+        "set_name": "Test Set",
+        "collector_number": "1",
+        "rarity": "rare",
+        "prices": {prices}
+    }}"#
+    ))
+    .unwrap()
+}
+
+#[test]
+fn parses_usd_rate_from_ecb_feed() {
+    assert_eq!(parse_ecb_usd_rate(ECB_XML), Some(1.0832));
+}
+
+#[test]
+fn ecb_feed_without_usd_has_no_rate() {
+    assert_eq!(parse_ecb_usd_rate(&ECB_XML.replace("USD", "GBP")), None);
+    assert_eq!(parse_ecb_usd_rate("<Cube currency='USD' rate='0'/>"), None);
+    assert_eq!(
+        parse_ecb_usd_rate("<Cube currency='USD' rate='n/a'/>"),
+        None
+    );
+    assert_eq!(parse_ecb_usd_rate(""), None);
+}
+
+#[test]
+fn extracts_usd_prices_from_card() {
+    let card = card_with_prices(r#"{"eur": "9.50", "usd": "12.34", "usd_foil": null}"#);
+    assert_eq!(
+        TcgplayerPrice::from_card(42, &card),
+        Some(TcgplayerPrice {
+            id_product: 42,
+            usd: Some(12.34),
+            usd_foil: None,
+        })
+    );
+}
+
+#[test]
+fn card_without_usd_prices_is_skipped() {
+    let card = card_with_prices(r#"{"eur": "9.50", "usd": null, "usd_foil": null}"#);
+    assert_eq!(TcgplayerPrice::from_card(42, &card), None);
+}
+
+#[test]
+fn spread_converts_usd_to_eur() {
+    // 12 USD at 1.2 USD/EUR = 10 EUR, against 8 EUR on Cardmarket
+    let (eur, pct) = spread(8.0, 12.0, 1.2);
+    assert!((eur - 10.0).abs() < 1e-9);
+    assert!((pct - 25.0).abs() < 1e-9);
+
+    let (_, pct) = spread(10.0, 9.0, 1.0);
+    assert!((pct + 10.0).abs() < 1e-9);
+}
@@ -0,0 +1,456 @@
+//! Value of the mounted check_stock inventory at market prices
+//!
+//! Combines the in-stock cards of the `--stock-db` database with the latest
+//! `price_history` row of each product, so a dashboard can show what the
+//! stock is worth at Cardmarket's trend or 7-day average (foil prices for
+//! foil cards) next to the listed asking prices. Cards without a market
+//! price are counted separately rather than valued at zero. One check_stock
+//! inventory is valued at a time, the shop stock by default.
+//!
+//! Valuing the whole stock takes one indexed lookup per product, so results
+//! are cached per inventory and price basis in a [`ValuationCache`] until
+//! either database changes or [`CACHE_TTL`] passes.
+//!
+//! [`get_inventory_valuation`] answers the same question card by card: it
+//! attaches the stock database (read-only) to the price database, joins
+//! `inventory_cards.cardmarket_id` to each product's latest `price_history`
+//! row in one query, and values every in-stock variant at the trend, average
+//! and low price at once.
+
+use crate::database::{get_latest_price_date, get_latest_prices_bulk, DbResult};
+use crate::stock::{
+    all_stock, inventory_column, selected_inventory, stock_card, StockCard, STOCK_CARD_COLUMNS,
+    STOCK_CARD_COLUMN_COUNT,
+};
+use mtg_common::inventory_sync::{LatestPrice, PriceField, PriceFields};
+use mtg_common::round_cents;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long a cached valuation is served while neither database changed
+pub const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Price the stock is valued at
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ValuationPrice {
+    /// Cardmarket trend price
+    #[default]
+    Trend,
+    /// Cardmarket 7-day average sell price
+    Avg7,
+    /// The listed (asking) price from the inventory
+    Listed,
+}
+
+impl ValuationPrice {
+    /// The market price column, `None` for listed prices
+    fn field(self) -> Option<PriceField> {
+        match self {
+            ValuationPrice::Trend => Some(PriceField::Trend),
+            ValuationPrice::Avg7 => Some(PriceField::Avg7),
+            ValuationPrice::Listed => None,
+        }
+    }
+}
+
+/// Value of the in-stock cards of one set
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct SetValue {
+    pub set_code: String,
+    pub set_name: String,
+    pub copies: i64,
+    /// Value of the priced copies in EUR
+    pub value: f64,
+    /// Value of the same copies at their listed prices
+    pub listed_value: f64,
+    /// Copies without a market price, not included in `value`
+    pub unpriced_copies: i64,
+}
+
+/// Value of the whole stock at one price basis
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct InventoryValue {
+    /// The check_stock inventory valued
+    pub inventory: String,
+    pub price: ValuationPrice,
+    /// Latest price date in `price_history`; `None` for listed prices or
+    /// before the first sync
+    pub price_date: Option<String>,
+    pub copies: i64,
+    pub total_value: f64,
+    pub listed_value: f64,
+    pub unpriced_copies: i64,
+    /// Sets by value, highest first
+    pub sets: Vec<SetValue>,
+    /// UTC time the value was calculated
+    pub calculated_at: String,
+}
+
+/// Unit price of `card` in `field` of its product's latest price row (foil
+/// prices for foils), or its listed price for `None`. Both valuations price
+/// cards through here.
+fn unit_price(
+    card: &StockCard,
+    latest: Option<&LatestPrice>,
+    field: Option<PriceField>,
+) -> Option<f64> {
+    match field {
+        None => Some(card.price),
+        Some(field) => latest.and_then(|row| row.price_for(field, card.is_foil)),
+    }
+}
+
+/// Values `cards` at `price`. `prices` holds the latest price row per
+/// product ID; it is ignored for listed prices.
+pub fn value_stock(
+    cards: &[StockCard],
+    prices: &HashMap<u64, LatestPrice>,
+    price: ValuationPrice,
+) -> InventoryValue {
+    let mut sets: BTreeMap<(&str, &str), (SetValue, LevelValue)> = BTreeMap::new();
+    for card in cards {
+        let latest = card
+            .cardmarket_id
+            .parse::<u64>()
+            .ok()
+            .and_then(|id| prices.get(&id));
+        let (set, level) = sets
+            .entry((card.set_code.as_str(), card.set_name.as_str()))
+            .or_insert_with(|| {
+                let set = SetValue {
+                    set_code: card.set_code.clone(),
+                    set_name: card.set_name.clone(),
+                    copies: 0,
+                    value: 0.0,
+                    listed_value: 0.0,
+                    unpriced_copies: 0,
+                };
+                (set, LevelValue::default())
+            });
+        set.copies += card.quantity;
+        set.listed_value += card.price * card.quantity as f64;
+        level.add(unit_price(card, latest, price.field()), card.quantity);
+    }
+
+    let mut sets: Vec<SetValue> = sets
+        .into_values()
+        .map(|(set, level)| {
+            let level = level.rounded();
+            SetValue {
+                value: level.value,
+                unpriced_copies: level.unpriced_copies,
+                listed_value: round_cents(set.listed_value),
+                ..set
+            }
+        })
+        .collect();
+    sets.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.set_code.cmp(&b.set_code))
+    });
+    InventoryValue {
+        inventory: String::new(),
+        price,
+        price_date: None,
+        copies: sets.iter().map(|s| s.copies).sum(),
+        total_value: round_cents(sets.iter().map(|s| s.value).sum()),
+        listed_value: round_cents(sets.iter().map(|s| s.listed_value).sum()),
+        unpriced_copies: sets.iter().map(|s| s.unpriced_copies).sum(),
+        sets,
+        calculated_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    }
+}
+
+/// Reads the stock of `inventory` (default: the shop) from `stock` and the
+/// latest prices from `prices`, and values it at `price`.
+pub fn calculate_inventory_value(
+    stock: &Connection,
+    prices: &Connection,
+    inventory: Option<&str>,
+    price: ValuationPrice,
+) -> DbResult<InventoryValue> {
+    let cards = all_stock(stock, inventory)?;
+    let latest = match price {
+        ValuationPrice::Listed => HashMap::new(),
+        _ => {
+            let mut ids: Vec<u64> = cards
+                .iter()
+                .filter_map(|c| c.cardmarket_id.parse().ok())
+                .collect();
+            ids.sort_unstable();
+            ids.dedup();
+            get_latest_prices_bulk(prices, &ids)?
+                .into_iter()
+                .map(|row| (row.id_product, row))
+                .collect()
+        }
+    };
+    let mut value = value_stock(&cards, &latest, price);
+    value.inventory = selected_inventory(inventory);
+    if price != ValuationPrice::Listed {
+        value.price_date = get_latest_price_date(prices)?;
+    }
+    Ok(value)
+}
+
+/// Value of the stock at one price level
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, utoipa::ToSchema)]
+pub struct LevelValue {
+    /// Value of the priced copies in EUR
+    pub value: f64,
+    /// Copies without a price at this level, not included in `value`
+    pub unpriced_copies: i64,
+}
+
+impl LevelValue {
+    /// Counts `quantity` copies at `unit` each, or as unpriced
+    fn add(&mut self, unit: Option<f64>, quantity: i64) {
+        match unit {
+            Some(unit) => self.value += unit * quantity as f64,
+            None => self.unpriced_copies += quantity,
+        }
+    }
+
+    fn rounded(self) -> Self {
+        LevelValue {
+            value: round_cents(self.value),
+            ..self
+        }
+    }
+}
+
+/// One in-stock card variant with its latest market prices
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct CardValuation {
+    pub cardmarket_id: String,
+    pub name: String,
+    pub set_code: String,
+    pub cn: String,
+    pub condition: String,
+    pub language: String,
+    pub is_foil: bool,
+    pub quantity: i64,
+    /// Listed unit price in EUR
+    pub listed_price: f64,
+    /// Date of the product's latest price row; `None` without one
+    pub price_date: Option<String>,
+    /// Unit prices in EUR (foil prices for foils); `None` when Cardmarket
+    /// has none
+    pub trend: Option<f64>,
+    pub avg: Option<f64>,
+    pub low: Option<f64>,
+}
+
+impl CardValuation {
+    /// `card` with the unit prices of its product's latest price row
+    fn new(card: StockCard, latest: Option<LatestPrice>) -> Self {
+        let unit = |field| unit_price(&card, latest.as_ref(), Some(field));
+        let (trend, avg, low) = (
+            unit(PriceField::Trend),
+            unit(PriceField::Avg),
+            unit(PriceField::Low),
+        );
+        CardValuation {
+            cardmarket_id: card.cardmarket_id,
+            name: card.name,
+            set_code: card.set_code,
+            cn: card.cn,
+            condition: card.condition,
+            language: card.language,
+            is_foil: card.is_foil,
+            quantity: card.quantity,
+            listed_price: card.price,
+            price_date: latest.map(|row| row.price_date),
+            trend,
+            avg,
+            low,
+        }
+    }
+}
+
+/// Value of the whole stock at the trend, average and low price, card by card
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct InventoryValuation {
+    /// The check_stock inventory valued
+    pub inventory: String,
+    /// Latest price date in `price_history`; `None` before the first sync
+    pub price_date: Option<String>,
+    pub copies: i64,
+    pub trend: LevelValue,
+    pub avg: LevelValue,
+    pub low: LevelValue,
+    pub listed_value: f64,
+    /// In-stock variants by set code, name and collector number
+    pub cards: Vec<CardValuation>,
+}
+
+/// Sums the per-card values of `cards` of `inventory`.
+pub fn summarize_valuation(
+    inventory: String,
+    cards: Vec<CardValuation>,
+    price_date: Option<String>,
+) -> InventoryValuation {
+    let (mut trend, mut avg, mut low) = (
+        LevelValue::default(),
+        LevelValue::default(),
+        LevelValue::default(),
+    );
+    let mut listed_value = 0.0;
+    for card in &cards {
+        trend.add(card.trend, card.quantity);
+        avg.add(card.avg, card.quantity);
+        low.add(card.low, card.quantity);
+        listed_value += card.listed_price * card.quantity as f64;
+    }
+    InventoryValuation {
+        inventory,
+        price_date,
+        copies: cards.iter().map(|c| c.quantity).sum(),
+        trend: trend.rounded(),
+        avg: avg.rounded(),
+        low: low.rounded(),
+        listed_value: round_cents(listed_value),
+        cards,
+    }
+}
+
+/// Schema name the stock database is attached under
+const STOCK_SCHEMA: &str = "stock";
+
+/// Read-only `file:` URI of `path`, so attaching never creates or writes the
+/// stock database
+fn read_only_uri(path: &Path) -> String {
+    let path = path
+        .to_string_lossy()
+        .replace('%', "%25")
+        .replace('?', "%3f")
+        .replace('#', "%23");
+    format!("file:{path}?mode=ro")
+}
+
+/// The in-stock variants of `inventory` in the attached stock database with
+/// the latest price row of their product. The stock columns are read as by
+/// [`all_stock`], so both valuations see the same cards.
+fn attached_stock_valuations(prices: &Connection, inventory: &str) -> DbResult<Vec<CardValuation>> {
+    let inventory_column = inventory_column(prices, STOCK_SCHEMA, "")?;
+    let mut stmt = prices.prepare(&format!(
+        "SELECT s.*, ph.id_product, ph.price_date,
+             ph.avg, ph.low, ph.trend, ph.avg1, ph.avg7, ph.avg30,
+             ph.avg_foil, ph.low_foil, ph.trend_foil, ph.avg1_foil, ph.avg7_foil, ph.avg30_foil
+         FROM (
+             SELECT {STOCK_CARD_COLUMNS} FROM {STOCK_SCHEMA}.inventory_cards
+             WHERE quantity > 0 AND {inventory_column} = ?1
+         ) s
+         LEFT JOIN main.price_history ph
+             ON ph.id_product = CAST(s.cardmarket_id AS INTEGER)
+             AND ph.price_date = (
+                 SELECT MAX(latest.price_date) FROM main.price_history latest
+                 WHERE latest.id_product = ph.id_product
+             )
+         ORDER BY s.set_code, s.name COLLATE NOCASE, s.cn, s.condition, s.language"
+    ))?;
+    let rows = stmt.query_map([inventory], |row| {
+        let card = stock_card(row)?;
+        let at = STOCK_CARD_COLUMN_COUNT;
+        let latest = match row.get::<_, Option<u64>>(at)? {
+            Some(id_product) => Some(LatestPrice {
+                id_product,
+                price_date: row.get(at + 1)?,
+                avg: row.get(at + 2)?,
+                low: row.get(at + 3)?,
+                trend: row.get(at + 4)?,
+                avg1: row.get(at + 5)?,
+                avg7: row.get(at + 6)?,
+                avg30: row.get(at + 7)?,
+                avg_foil: row.get(at + 8)?,
+                low_foil: row.get(at + 9)?,
+                trend_foil: row.get(at + 10)?,
+                avg1_foil: row.get(at + 11)?,
+                avg7_foil: row.get(at + 12)?,
+                avg30_foil: row.get(at + 13)?,
+            }),
+            None => None,
+        };
+        Ok(CardValuation::new(card, latest))
+    })?;
+    rows.collect()
+}
+
+/// Values the stock of `inventory` (default: the shop) in the check_stock
+/// database at `stock_db` at the latest prices in `prices`, card by card.
+/// The stock file is attached read-only for the one query and detached
+/// again.
+pub fn get_inventory_valuation(
+    prices: &Connection,
+    stock_db: &Path,
+    inventory: Option<&str>,
+) -> DbResult<InventoryValuation> {
+    let inventory = selected_inventory(inventory);
+    prices.execute(
+        &format!("ATTACH DATABASE ?1 AS {STOCK_SCHEMA}"),
+        params![read_only_uri(stock_db)],
+    )?;
+    let cards = attached_stock_valuations(prices, &inventory);
+    prices.execute(&format!("DETACH DATABASE {STOCK_SCHEMA}"), [])?;
+    Ok(summarize_valuation(
+        inventory,
+        cards?,
+        get_latest_price_date(prices)?,
+    ))
+}
+
+/// What a cached value was calculated from; a change in either database
+/// invalidates it
+#[derive(Debug, Clone, PartialEq)]
+struct CacheStamp {
+    /// `PRAGMA data_version` of the stock connection, which changes when
+    /// check_stock writes the file
+    stock_version: i64,
+    latest_price_date: Option<String>,
+}
+
+/// Cached valuations, one per inventory and price basis
+#[derive(Debug, Default)]
+pub struct ValuationCache {
+    entries: HashMap<(String, ValuationPrice), (CacheStamp, Instant, InventoryValue)>,
+}
+
+impl ValuationCache {
+    /// The value of `inventory` at `price`, from the cache when neither
+    /// database changed within [`CACHE_TTL`], else freshly calculated.
+    /// `refresh` forces a recalculation.
+    pub fn get(
+        &mut self,
+        stock: &Connection,
+        prices: &Connection,
+        inventory: Option<&str>,
+        price: ValuationPrice,
+        refresh: bool,
+    ) -> DbResult<InventoryValue> {
+        let key = (selected_inventory(inventory), price);
+        let stamp = CacheStamp {
+            stock_version: stock.query_row("PRAGMA data_version", [], |row| row.get(0))?,
+            latest_price_date: get_latest_price_date(prices)?,
+        };
+        if let Some((cached, at, value)) = self.entries.get(&key) {
+            if !refresh && *cached == stamp && at.elapsed() < CACHE_TTL {
+                return Ok(value.clone());
+            }
+        }
+        let value = calculate_inventory_value(stock, prices, inventory, price)?;
+        self.entries
+            .insert(key, (stamp, Instant::now(), value.clone()));
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+#[path = "valuation_tests.rs"]
+mod tests;
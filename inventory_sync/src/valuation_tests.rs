@@ -0,0 +1,283 @@
+//! Tests for valuation.
+
+use super::*;
+use crate::cardmarket::{make_test_price_entry, make_test_product, PriceGuide, ProductCatalog};
+use crate::database::{insert_price_history, upsert_products};
+use crate::init_schema;
+use crate::stock::{add_test_collection, create_test_stock_db, open_stock_db};
+use tempfile::TempDir;
+
+fn latest(id_product: u64, trend: Option<f64>, trend_foil: Option<f64>) -> LatestPrice {
+    LatestPrice {
+        id_product,
+        price_date: "2026-03-01".to_string(),
+        avg: None,
+        low: None,
+        trend,
+        avg1: None,
+        avg7: trend.map(|t| t + 1.0),
+        avg30: None,
+        avg_foil: None,
+        low_foil: None,
+        trend_foil,
+        avg1_foil: None,
+        avg7_foil: None,
+        avg30_foil: None,
+    }
+}
+
+fn test_stock() -> (Connection, TempDir) {
+    let dir = TempDir::new().unwrap();
+    let conn = open_stock_db(&create_test_stock_db(dir.path())).unwrap();
+    (conn, dir)
+}
+
+#[test]
+fn values_stock_per_set_at_foil_aware_market_prices() {
+    let (stock, _dir) = test_stock();
+    let cards = all_stock(&stock, None).unwrap();
+    let prices: HashMap<u64, LatestPrice> = [
+        latest(1, Some(100.0), Some(80.0)),
+        latest(3, Some(2.0), Some(0.5)),
+    ]
+    .into_iter()
+    .map(|p| (p.id_product, p))
+    .collect();
+
+    let value = value_stock(&cards, &prices, ValuationPrice::Trend);
+
+    // Bolt 2 × 100 + foil Bolt 80; the Lotus has no price
+    assert_eq!(value.sets[0].set_code, "LEA");
+    assert_eq!(value.sets[0].copies, 4);
+    assert_eq!(value.sets[0].value, 280.0);
+    assert_eq!(value.sets[0].listed_value, 9335.5);
+    assert_eq!(value.sets[0].unpriced_copies, 1);
+    // 4 foil Counterspells at 0.50
+    assert_eq!(value.sets[1].set_code, "ICE");
+    assert_eq!(value.sets[1].value, 2.0);
+    assert_eq!(value.copies, 8);
+    assert_eq!(value.total_value, 282.0);
+    assert_eq!(value.listed_value, 9341.5);
+    assert_eq!(value.unpriced_copies, 1);
+
+    // No foil 7-day average, so only the non-foil Bolts are priced
+    let avg7 = value_stock(&cards, &prices, ValuationPrice::Avg7);
+    assert_eq!(avg7.total_value, 202.0);
+    assert_eq!(avg7.unpriced_copies, 6);
+}
+
+#[test]
+fn listed_prices_need_no_market_data() {
+    let (stock, _dir) = test_stock();
+    let cards = all_stock(&stock, None).unwrap();
+
+    let value = value_stock(&cards, &HashMap::new(), ValuationPrice::Listed);
+
+    assert_eq!(value.total_value, 9341.5);
+    assert_eq!(value.total_value, value.listed_value);
+    assert_eq!(value.unpriced_copies, 0);
+}
+
+#[test]
+fn inventories_are_valued_separately() {
+    let dir = TempDir::new().unwrap();
+    let stock_path = create_test_stock_db(dir.path());
+    add_test_collection(&stock_path);
+    let stock = open_stock_db(&stock_path).unwrap();
+    let prices = Connection::open_in_memory().unwrap();
+    init_schema(&prices).unwrap();
+
+    let shop = calculate_inventory_value(&stock, &prices, None, ValuationPrice::Listed).unwrap();
+    assert_eq!(shop.inventory, "shop");
+    assert_eq!(shop.copies, 8);
+    assert_eq!(shop.total_value, 9341.5);
+    let collection =
+        calculate_inventory_value(&stock, &prices, Some("collection"), ValuationPrice::Listed)
+            .unwrap();
+    assert_eq!(collection.inventory, "collection");
+    assert_eq!(collection.copies, 2);
+    assert_eq!(collection.total_value, 15080.0);
+}
+
+#[test]
+fn cache_is_invalidated_by_stock_changes() {
+    let (stock, dir) = test_stock();
+    let mut prices = Connection::open(dir.path().join("prices.db")).unwrap();
+    init_schema(&prices).unwrap();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(2, "Black Lotus")]);
+    upsert_products(&mut prices, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(2, Some(5000.0))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut prices, &guide, &catalog).unwrap();
+
+    let mut cache = ValuationCache::default();
+    let value = cache
+        .get(&stock, &prices, None, ValuationPrice::Trend, false)
+        .unwrap();
+    assert_eq!(value.total_value, 5000.0);
+    assert_eq!(value.price_date.as_deref(), Some("2026-03-01"));
+
+    // Served from the cache while nothing changed
+    cache
+        .entries
+        .get_mut(&("shop".to_string(), ValuationPrice::Trend))
+        .unwrap()
+        .2
+        .total_value = 1.0;
+    let cached = cache
+        .get(&stock, &prices, None, ValuationPrice::Trend, false)
+        .unwrap();
+    assert_eq!(cached.total_value, 1.0);
+    let refreshed = cache
+        .get(&stock, &prices, None, ValuationPrice::Trend, true)
+        .unwrap();
+    assert_eq!(refreshed.total_value, 5000.0);
+
+    // check_stock writing the database invalidates it
+    Connection::open(dir.path().join("stock.db"))
+        .unwrap()
+        .execute(
+            "UPDATE inventory_cards SET quantity = 2 WHERE cardmarket_id = '2'",
+            [],
+        )
+        .unwrap();
+    let value = cache
+        .get(&stock, &prices, None, ValuationPrice::Trend, false)
+        .unwrap();
+    assert_eq!(value.total_value, 10000.0);
+}
+
+fn prices_db(dir: &TempDir) -> Connection {
+    let mut prices = Connection::open(dir.path().join("prices.db")).unwrap();
+    init_schema(&prices).unwrap();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Lightning Bolt"),
+        make_test_product(3, "Counterspell"),
+    ]);
+    upsert_products(&mut prices, &catalog).unwrap();
+    let older = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(50.0))],
+        "2026-02-28T10:00:00+0100",
+    );
+    insert_price_history(&mut prices, &older, &catalog).unwrap();
+    let mut foil = make_test_price_entry(3, Some(2.0));
+    foil.trend_foil = Some(0.5);
+    foil.avg_foil = Some(0.6);
+    let latest = PriceGuide::from_entries(
+        vec![make_test_price_entry(1, Some(100.0)), foil],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut prices, &latest, &catalog).unwrap();
+    prices
+}
+
+#[test]
+fn valuation_joins_the_stock_to_the_latest_prices_card_by_card() {
+    let dir = TempDir::new().unwrap();
+    let stock_path = create_test_stock_db(dir.path());
+    let prices = prices_db(&dir);
+
+    let valuation = get_inventory_valuation(&prices, &stock_path, None).unwrap();
+
+    assert_eq!(valuation.price_date.as_deref(), Some("2026-03-01"));
+    assert_eq!(valuation.copies, 8);
+    let names: Vec<(&str, bool)> = valuation
+        .cards
+        .iter()
+        .map(|c| (c.name.as_str(), c.is_foil))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("Counterspell", true),
+            ("Black Lotus", false),
+            ("Lightning Bolt", true),
+            ("Lightning Bolt", false),
+        ]
+    );
+    // The foil Bolt has no foil price; the Lotus has no price row at all
+    let bolt = &valuation.cards[3];
+    assert_eq!(
+        (bolt.trend, bolt.avg, bolt.low),
+        (Some(100.0), Some(100.0), Some(80.0))
+    );
+    assert_eq!(bolt.price_date.as_deref(), Some("2026-03-01"));
+    assert_eq!(valuation.cards[2].trend, None);
+    assert_eq!(valuation.cards[1].price_date, None);
+    let counterspell = &valuation.cards[0];
+    assert_eq!(
+        (counterspell.trend, counterspell.avg, counterspell.low),
+        (Some(0.5), Some(0.6), None)
+    );
+
+    assert_eq!(
+        valuation.trend,
+        LevelValue {
+            value: 202.0,
+            unpriced_copies: 2
+        }
+    );
+    assert_eq!(valuation.avg.value, 202.4);
+    assert_eq!(
+        valuation.low,
+        LevelValue {
+            value: 160.0,
+            unpriced_copies: 6
+        }
+    );
+    assert_eq!(valuation.listed_value, 9341.5);
+
+    // The stock database is detached again and was not written
+    let attached: i64 = prices
+        .query_row("SELECT COUNT(*) FROM pragma_database_list", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(attached, 1);
+}
+
+#[test]
+fn valuation_of_a_missing_stock_database_fails_without_creating_it() {
+    let dir = TempDir::new().unwrap();
+    let prices = prices_db(&dir);
+    let missing = dir.path().join("missing?.db");
+
+    assert!(get_inventory_valuation(&prices, &missing, None).is_err());
+    assert!(!missing.exists());
+    // A failed attach leaves nothing attached, so the next call works
+    let stock_path = create_test_stock_db(dir.path());
+    assert_eq!(
+        get_inventory_valuation(&prices, &stock_path, None)
+            .unwrap()
+            .copies,
+        8
+    );
+}
+
+#[test]
+fn card_by_card_valuation_covers_one_inventory_and_agrees_with_the_total() {
+    let dir = TempDir::new().unwrap();
+    let stock_path = create_test_stock_db(dir.path());
+    add_test_collection(&stock_path);
+    let prices = prices_db(&dir);
+    let stock = open_stock_db(&stock_path).unwrap();
+
+    let shop = get_inventory_valuation(&prices, &stock_path, None).unwrap();
+    assert_eq!(shop.inventory, "shop");
+    assert_eq!(shop.copies, 8);
+    let collection = get_inventory_valuation(&prices, &stock_path, Some("collection")).unwrap();
+    let names: Vec<&str> = collection.cards.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Force of Will", "Black Lotus"]);
+    assert_eq!(collection.listed_value, 15080.0);
+
+    for (inventory, valuation) in [(None, &shop), (Some("collection"), &collection)] {
+        let trend =
+            calculate_inventory_value(&stock, &prices, inventory, ValuationPrice::Trend).unwrap();
+        assert_eq!(trend.total_value, valuation.trend.value);
+        assert_eq!(trend.unpriced_copies, valuation.trend.unpriced_copies);
+        assert_eq!(trend.listed_value, valuation.listed_value);
+        assert_eq!(trend.copies, valuation.copies);
+    }
+}
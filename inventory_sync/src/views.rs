@@ -0,0 +1,106 @@
+//! Reporting views for external tools
+//!
+//! Excel (via ODBC), Metabase and similar tools can read the SQLite file
+//! directly, but the raw tables need joins and "latest row per product"
+//! subqueries to answer anything useful. The `create-views` subcommand sets
+//! up these views instead. Their columns are part of the interface: new
+//! columns may be appended, existing ones are not renamed or reordered.
+//!
+//! - `latest_prices`: every product priced in the most recent price guide
+//!   import, with name, category and expansion
+//! - `product_price_latest`: each product's most recent price row, however
+//!   old, with the same columns plus `days_old`
+//! - `daily_index`: per day and category, how many products were priced and
+//!   the sum, average and median of their trend prices
+
+use crate::database::DbResult;
+use rusqlite::Connection;
+
+/// View names, in creation order
+pub const VIEW_NAMES: [&str; 3] = ["latest_prices", "product_price_latest", "daily_index"];
+
+/// Product, category and expansion columns shared by the price views. The
+/// expansion name falls back to names learned from Scryfall lookups.
+pub(crate) const PRODUCT_COLUMNS: &str = "
+    p.id_product,
+    p.name,
+    p.id_category,
+    p.category_name,
+    p.id_expansion,
+    COALESCE(e.name, en.name) AS expansion_name,
+    e.code AS expansion_code,
+    ph.price_date,
+    ph.avg,
+    ph.low,
+    ph.trend,
+    ph.avg1,
+    ph.avg7,
+    ph.avg30,
+    ph.avg_foil,
+    ph.low_foil,
+    ph.trend_foil,
+    ph.avg1_foil,
+    ph.avg7_foil,
+    ph.avg30_foil";
+
+pub(crate) const PRODUCT_JOINS: &str = "
+    JOIN products p ON p.id_product = ph.id_product
+    LEFT JOIN expansions e ON e.id_expansion = p.id_expansion
+    LEFT JOIN expansion_names en ON en.id_expansion = p.id_expansion";
+
+/// Create (or recreate) the reporting views.
+///
+/// Views are dropped first so a newer binary replaces older definitions;
+/// they hold no data, so this is safe to run at any time. Runs in one
+/// transaction, so tools never see a half-updated set.
+pub fn create_views(conn: &mut Connection) -> DbResult<()> {
+    let tx = conn.transaction()?;
+    for name in VIEW_NAMES {
+        tx.execute_batch(&format!("DROP VIEW IF EXISTS {name}"))?;
+    }
+    tx.execute_batch(&format!(
+        "
+        CREATE VIEW latest_prices AS
+        SELECT {PRODUCT_COLUMNS}
+        FROM price_history ph {PRODUCT_JOINS}
+        WHERE ph.price_date = (SELECT MAX(price_date) FROM price_history);
+
+        CREATE VIEW product_price_latest AS
+        SELECT {PRODUCT_COLUMNS},
+            CAST(julianday(date('now')) - julianday(ph.price_date) AS INTEGER) AS days_old
+        FROM price_history ph {PRODUCT_JOINS}
+        WHERE ph.price_date = (
+            SELECT MAX(latest.price_date) FROM price_history latest
+            WHERE latest.id_product = ph.id_product
+        );
+
+        CREATE VIEW daily_index AS
+        WITH ranked AS (
+            SELECT price_date, id_category, trend,
+                ROW_NUMBER() OVER (
+                    PARTITION BY price_date, id_category ORDER BY trend
+                ) AS pos,
+                COUNT(*) OVER (PARTITION BY price_date, id_category) AS n
+            FROM price_history
+            WHERE trend IS NOT NULL
+        )
+        SELECT
+            price_date,
+            id_category,
+            MAX(n) AS priced_products,
+            SUM(trend) AS total_trend,
+            AVG(trend) AS avg_trend,
+            AVG(CASE WHEN pos IN ((n + 1) / 2, (n + 2) / 2) THEN trend END) AS median_trend
+        FROM ranked
+        GROUP BY price_date, id_category;
+        "
+    ))?;
+    tx.commit()?;
+
+    log::info!("Created views: {}", VIEW_NAMES.join(", "));
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "views_tests.rs"]
+mod tests;
@@ -0,0 +1,189 @@
+//! Tests for views.
+
+use super::*;
+use crate::cardmarket::{
+    make_test_price_entry, make_test_product, ExpansionEntry, PriceGuide, ProductCatalog,
+};
+use crate::database::{init_schema, insert_price_history, upsert_expansions, upsert_products};
+
+/// Database with three products over two days; product 3 is missing from
+/// the second price guide
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    init_schema(&conn).unwrap();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(1, "Black Lotus"),
+        make_test_product(2, "Mox Pearl"),
+        make_test_product(3, "Time Walk"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    upsert_expansions(
+        &mut conn,
+        &[ExpansionEntry {
+            id_expansion: 1,
+            name: "Alpha".to_string(),
+            code: Some("LEA".to_string()),
+            release_date: None,
+        }],
+    )
+    .unwrap();
+
+    let day1 = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(1, Some(10.0)),
+            make_test_price_entry(2, Some(4.0)),
+            make_test_price_entry(3, Some(1.0)),
+        ],
+        "2026-02-01T10:00:00+0100",
+    );
+    let day2 = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(1, Some(12.0)),
+            make_test_price_entry(2, None),
+        ],
+        "2026-02-02T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &day1, &catalog).unwrap();
+    insert_price_history(&mut conn, &day2, &catalog).unwrap();
+    create_views(&mut conn).unwrap();
+    conn
+}
+
+fn column_names(conn: &Connection, view: &str) -> Vec<String> {
+    let stmt = conn.prepare(&format!("SELECT * FROM {view}")).unwrap();
+    stmt.column_names().into_iter().map(String::from).collect()
+}
+
+#[test]
+fn latest_prices_covers_most_recent_import() {
+    let conn = test_db();
+
+    let rows: Vec<(u64, String, String, Option<f64>)> = conn
+        .prepare(
+            "SELECT id_product, expansion_name, price_date, trend
+             FROM latest_prices ORDER BY id_product",
+        )
+        .unwrap()
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            (1, "Alpha".to_string(), "2026-02-02".to_string(), Some(12.0)),
+            (2, "Alpha".to_string(), "2026-02-02".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn product_price_latest_keeps_products_missing_from_last_import() {
+    let conn = test_db();
+
+    let rows: Vec<(u64, String)> = conn
+        .prepare("SELECT id_product, price_date FROM product_price_latest ORDER BY id_product")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            (1, "2026-02-02".to_string()),
+            (2, "2026-02-02".to_string()),
+            (3, "2026-02-01".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn daily_index_aggregates_priced_products() {
+    let conn = test_db();
+
+    let rows: Vec<(String, i64, f64, f64)> = conn
+        .prepare(
+            "SELECT price_date, priced_products, total_trend, median_trend
+             FROM daily_index ORDER BY price_date",
+        )
+        .unwrap()
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            ("2026-02-01".to_string(), 3, 15.0, 4.0),
+            ("2026-02-02".to_string(), 1, 12.0, 12.0),
+        ]
+    );
+}
+
+#[test]
+fn view_columns_are_stable() {
+    let conn = test_db();
+
+    let price_columns = [
+        "id_product",
+        "name",
+        "id_category",
+        "category_name",
+        "id_expansion",
+        "expansion_name",
+        "expansion_code",
+        "price_date",
+        "avg",
+        "low",
+        "trend",
+        "avg1",
+        "avg7",
+        "avg30",
+        "avg_foil",
+        "low_foil",
+        "trend_foil",
+        "avg1_foil",
+        "avg7_foil",
+        "avg30_foil",
+    ];
+    assert_eq!(column_names(&conn, "latest_prices"), price_columns);
+    assert_eq!(
+        column_names(&conn, "product_price_latest"),
+        [&price_columns[..], &["days_old"]].concat()
+    );
+    assert_eq!(
+        column_names(&conn, "daily_index"),
+        [
+            "price_date",
+            "id_category",
+            "priced_products",
+            "total_trend",
+            "avg_trend",
+            "median_trend"
+        ]
+    );
+}
+
+#[test]
+fn create_views_can_run_again() {
+    let mut conn = test_db();
+
+    create_views(&mut conn).unwrap();
+
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'view'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(count, VIEW_NAMES.len() as i64);
+}
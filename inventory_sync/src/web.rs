@@ -1,43 +1,246 @@
 //! Web server for MTG price tracker UI
 //!
 //! Provides REST API endpoints for card search and price history visualization.
+//!
+//! Every request is logged (method, path, status, duration, client IP) under
+//! the `access` log target. Endpoints under `/api/admin/` need the bearer
+//! token given with `--admin-token` and, with `--audit-log`, are also
+//! recorded in the `audit_log` table.
+//!
+//! One server can be shared by several tenants: the catalog and price data
+//! are common, while each tenant's watchlist lives under `/api/watchlist`,
+//! scoped by the tenant's own bearer token. Tenants and their tokens are
+//! managed through `/api/admin/tenants`.
+//!
+//! Tenants also keep price alerts under `/api/alerts`: thresholds on a
+//! product's trend, checked after every daily import, with the alerts that
+//! fired under `/api/alerts/triggered` (see [`crate::alerts`]). Their
+//! watchlist and alerts can be exported as JSON and imported into another
+//! tenant or server (`/api/alerts/export`, `/api/alerts/import`, see
+//! [`crate::alert_config`]).
+//!
+//! Daily price movers are published as an Atom feed under
+//! `/api/movers.atom` (see [`crate::feed`]).
+//!
+//! `/api/upcoming-sets` lists the announced sets from the release calendar;
+//! tenants opt into watching their new products with
+//! `PUT /api/watchlist/new-sets`.
+//!
+//! With `--stock-db`, the check_stock inventory database is mounted read-only
+//! under `/api/stock` and the UI gets a stock browser.
+//! `/api/inventory/value` values that stock at market prices, and
+//! `/api/inventory/valuation` card by card at the trend, average and low
+//! price (see [`crate::valuation`]).
+//!
+//! With `--order-board`, `/api/revenue` sums the accounting app's imported
+//! orders into rolling revenue figures for the UI (see [`crate::revenue`]).
+//!
+//! `/api/openapi.json` documents every endpoint with its parameters and
+//! response types (see [`openapi`]).
 
 use axum::{
     body::Body,
-    extract::{Path, Query, State},
+    extract::{ConnectInfo, Path, Query, Request, State},
     http::{header, StatusCode},
-    response::{Html, Json, Response},
-    routing::{get, post},
-    Router,
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Json, Response},
+    routing::{delete, get, post, put},
+    Extension, Router,
 };
 use rusqlite::Connection;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{IntoParams, Modify, OpenApi, ToSchema};
 
+use crate::alert_config::{export_alert_config, import_alert_config, AlertConfig, ImportSummary};
+use crate::alerts::{
+    add_alert, get_alerts, get_triggered_alerts, remove_alert, AlertCondition, AlertRearm,
+    PriceAlert, TriggeredAlert,
+};
+use crate::database::{
+    add_to_watchlist, create_tenant, delete_tenant, get_tenant_by_token_hash, get_tenants,
+    get_watch_new_sets, get_watchlist, remove_from_watchlist, set_watch_new_sets, Tenant,
+    WatchlistEntry,
+};
 use crate::database::{
-    get_id_expansion_for_product, get_latest_prices_bulk, get_price_history,
-    get_price_snapshots_bulk, get_product_by_id, search_products_by_name, upsert_expansion_name,
+    find_products_by_name, get_expansions, get_id_expansion_for_product, get_latest_prices_bulk,
+    get_price_comparison, get_price_comparisons, get_price_history, get_price_movers,
+    get_price_on_date, get_price_snapshots_bulk, get_product_by_id, get_product_names,
+    price_wantslist, search_products_by_name, upsert_expansion_name,
+};
+use crate::database::{get_audit_entries, insert_audit_entry};
+use crate::database::{get_latest_price_date, get_upcoming_sets, today_date, UpcomingSet};
+use crate::database::{
+    AuditEntry, ExpansionInfo, LatestPrice, PriceComparison, PriceOnDate, PriceSnapshot,
+    ProductNames, ProductSearchResult,
+};
+use crate::error::InventoryError;
+use crate::feed::movers_feed;
+use crate::image_cache::{
+    fetch_card_info_cached, fetch_image_cached, image_content_type, ImageCache,
 };
-use crate::database::{LatestPrice, PriceSnapshot, ProductSearchResult};
-use crate::image_cache::{fetch_card_info_cached, fetch_image_cached, ImageCache};
 use crate::indicators::{calculate_all_indicators, calculate_cardmarket_signals};
+use crate::revenue::{load_order_board, revenue_summary, RevenueSummary, DEFAULT_REVENUE_DAYS};
 use crate::scryfall::CardInfo;
+use crate::slow_queries::index_advice;
+use crate::stock::{
+    open_stock_db, search_stock, stock_facets, StockCard, StockFacets, StockFilter,
+};
+use crate::valuation::{
+    get_inventory_valuation, InventoryValuation, InventoryValue, ValuationCache, ValuationPrice,
+};
 use mtg_common::inventory_sync::{
-    ApiResponse, BulkPriceRequest, PriceData, PriceSnapshotRequest, MAX_BULK_IDS,
-    MAX_SNAPSHOT_DATES,
+    ApiResponse, BulkPriceRequest, PriceData, PriceSnapshotRequest, WantslistPriceRequest,
+    WantslistQuote, MAX_BULK_IDS, MAX_SNAPSHOT_DATES, MAX_WANTSLIST_CARDS,
 };
+use mtg_common::sql_profile::{self, IndexAdvice, SlowQuery};
+use mtg_common::Category;
+
+/// Web server settings taken from the command line
+#[derive(Debug, Clone, Default)]
+pub struct WebOptions {
+    /// Persist requests to `/api/admin/*` in the `audit_log` table
+    pub audit_log: bool,
+    /// Bearer token for `/api/admin/*`; admin endpoints are disabled without one
+    pub admin_token: Option<String>,
+    /// check_stock inventory database to serve under `/api/stock`
+    pub stock_db: Option<PathBuf>,
+    /// The accounting app's order board to serve under `/api/revenue`
+    pub order_board: Option<PathBuf>,
+}
+
+/// State of the `/api/revenue` route: the order board file
+#[derive(Clone)]
+struct RevenueState {
+    order_board: Arc<PathBuf>,
+}
+
+/// Revenue query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RevenueParams {
+    /// Length of the period in days (max 366)
+    #[serde(default = "default_revenue_days")]
+    days: u32,
+}
+
+fn default_revenue_days() -> u32 {
+    DEFAULT_REVENUE_DAYS
+}
+
+/// State of the `/api/stock` routes: the mounted stock database, the price
+/// database and cached valuations
+#[derive(Clone)]
+struct StockState {
+    stock: Arc<Mutex<Connection>>,
+    /// Path of the stock database, attached for card-by-card valuations
+    stock_path: Arc<PathBuf>,
+    prices: Arc<Mutex<Connection>>,
+    valuations: Arc<Mutex<ValuationCache>>,
+}
+
+/// Inventory valuation query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ValuationParams {
+    /// `trend`, `avg7` or `listed`
+    #[serde(default)]
+    price: ValuationPrice,
+    /// Recalculate even if a cached value is still valid
+    #[serde(default)]
+    refresh: bool,
+    /// check_stock inventory, e.g. `collection` (default: `shop`)
+    inventory: Option<String>,
+}
+
+/// Query parameter selecting a check_stock inventory
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct InventoryParams {
+    /// check_stock inventory, e.g. `collection` (default: `shop`)
+    inventory: Option<String>,
+}
 
 /// Shared application state (thread-safe database connection + image cache)
 #[derive(Clone)]
 struct AppState {
     db: Arc<Mutex<Connection>>,
     image_cache: Arc<ImageCache>,
+    options: Arc<WebOptions>,
+}
+
+/// Path prefix of the admin endpoints
+const ADMIN_PREFIX: &str = "/api/admin/";
+
+/// Upper bound on rows returned by `/api/admin/audit`
+const MAX_AUDIT_LIMIT: usize = 1000;
+
+/// Audit log query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AuditParams {
+    /// Only entries at or after this UTC date or datetime
+    since: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+/// Response of `GET /api/admin/slow-queries`
+#[derive(Serialize, ToSchema)]
+struct SlowQueryReport {
+    /// Logging threshold; absent when slow query logging is off
+    threshold_ms: Option<u64>,
+    /// Logged statements, oldest first
+    queries: Vec<SlowQuery>,
+    /// Indexes that would turn the logged full table scans into searches
+    suggestions: Vec<IndexAdvice>,
+}
+
+/// Body of `POST /api/admin/tenants`
+#[derive(Deserialize, ToSchema)]
+struct NewTenant {
+    name: String,
+}
+
+/// Body of `POST /api/alerts`
+#[derive(Deserialize, ToSchema)]
+struct NewAlert {
+    id_product: u64,
+    condition: AlertCondition,
+    /// Trend price threshold in EUR
+    threshold: f64,
+    #[serde(flatten)]
+    rearm: AlertRearm,
+}
+
+/// Fired alerts query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct TriggeredAlertParams {
+    /// Only alerts fired on this price date (YYYY-MM-DD) or later (default:
+    /// the latest price date, i.e. overnight)
+    since: Option<String>,
+}
+
+/// A newly created tenant with its token, which is only ever shown once
+#[derive(Serialize, ToSchema)]
+struct CreatedTenant {
+    tenant: Tenant,
+    token: String,
 }
 
 /// Search query parameters
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct SearchParams {
     q: String,
+    /// Category ID or `singles`; all categories when absent
+    category: Option<String>,
     #[serde(default = "default_limit")]
     limit: usize,
 }
@@ -51,7 +254,8 @@ fn default_limit() -> usize {
 /// Supports two optional filters (if both provided, `since` takes precedence):
 /// - `days=N` — last N days of history
 /// - `since=YYYY-MM-DD` — history on or after this date
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PriceParams {
     days: Option<u32>,
     since: Option<String>,
@@ -64,8 +268,7 @@ impl PriceParams {
             return Some(date.clone());
         }
         if let Some(days) = self.days {
-            let cutoff = chrono::Utc::now()
-                .date_naive()
+            let cutoff = mtg_common::dates::market_today()
                 .checked_sub_days(chrono::Days::new(u64::from(days)))?;
             return Some(cutoff.format("%Y-%m-%d").to_string());
         }
@@ -73,7 +276,94 @@ impl PriceParams {
     }
 }
 
+/// Price history by name query parameters
+///
+/// `days` and `since` work as for [`PriceParams`].
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct NameHistoryParams {
+    name: String,
+    /// Whole-name match (default); `false` matches names containing `name`
+    #[serde(default = "default_exact")]
+    exact: bool,
+    /// Expansion code, name or ID the product must belong to
+    expansion: Option<String>,
+    days: Option<u32>,
+    since: Option<String>,
+}
+
+fn default_exact() -> bool {
+    true
+}
+
+/// Upper bound on the candidates listed when a name is ambiguous
+const MAX_NAME_CANDIDATES: usize = 20;
+
+/// Price-on-date query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PriceOnDateParams {
+    date: String,
+}
+
+/// Upper bound on rows returned by `/api/compare`
+const MAX_COMPARE_LIMIT: usize = 500;
+
+/// Price comparison query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CompareParams {
+    /// Only cards whose Cardmarket trend is at least this many EUR
+    #[serde(default)]
+    min_eur: f64,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+/// Upper bound on entries in `/api/movers.atom`
+const MAX_MOVERS_LIMIT: usize = 200;
+
+/// Default and maximum look-ahead of `/api/upcoming-sets`, in days
+const UPCOMING_SETS_DAYS: u32 = 60;
+const MAX_UPCOMING_SETS_DAYS: u32 = 366;
+
+/// Price movers feed query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct MoversParams {
+    /// Only cards priced at least this many EUR on one of the two days
+    #[serde(default = "default_movers_min_eur")]
+    min_eur: f64,
+    #[serde(default = "default_movers_limit")]
+    limit: usize,
+}
+
+fn default_movers_min_eur() -> f64 {
+    1.0
+}
+
+fn default_movers_limit() -> usize {
+    25
+}
+
+/// Release calendar query parameters
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct UpcomingSetsParams {
+    /// How many days ahead to look
+    #[serde(default = "default_upcoming_days")]
+    days: u32,
+}
+
+fn default_upcoming_days() -> u32 {
+    UPCOMING_SETS_DAYS
+}
+
 /// GET /api/health - Simple connectivity check
+#[utoipa::path(
+    get, path = "/api/health", tag = "meta",
+    responses((status = 200, body = ApiResponse<String>))
+)]
 async fn health_handler() -> Json<ApiResponse<&'static str>> {
     Json(ApiResponse {
         success: true,
@@ -83,18 +373,38 @@ async fn health_handler() -> Json<ApiResponse<&'static str>> {
 }
 
 /// GET / - Serve the web UI (single HTML page)
+#[utoipa::path(
+    get, path = "/", tag = "meta",
+    responses((status = 200, description = "The web UI", content_type = "text/html", body = String))
+)]
 async fn index_handler() -> Html<&'static str> {
     Html(include_str!("../static/index.html"))
 }
 
 /// GET /api/search?q={query}&limit={limit}
+#[utoipa::path(
+    get, path = "/api/search", tag = "catalog", params(SearchParams),
+    responses(
+        (status = 200, body = ApiResponse<Vec<ProductSearchResult>>),
+        (status = 400, description = "Unknown category")
+    )
+)]
 async fn search_handler(
     State(state): State<AppState>,
     Query(params): Query<SearchParams>,
 ) -> Result<Json<ApiResponse<Vec<ProductSearchResult>>>, StatusCode> {
+    let category = params
+        .category
+        .as_deref()
+        .map(str::parse::<Category>)
+        .transpose()
+        .map_err(|e| {
+            log::warn!("Search rejected: {}", e);
+            StatusCode::BAD_REQUEST
+        })?;
     let conn = state.db.lock().unwrap();
 
-    match search_products_by_name(&conn, &params.q, params.limit) {
+    match search_products_by_name(&conn, &params.q, category, params.limit) {
         Ok(results) => Ok(Json(ApiResponse {
             success: true,
             data: Some(results),
@@ -107,17 +417,122 @@ async fn search_handler(
     }
 }
 
+/// GET /api/expansions - All known expansions, newest first
+#[utoipa::path(
+    get, path = "/api/expansions", tag = "catalog",
+    responses((status = 200, body = ApiResponse<Vec<ExpansionInfo>>))
+)]
+async fn expansions_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<ExpansionInfo>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+
+    match get_expansions(&conn) {
+        Ok(expansions) => Ok(Json(ApiResponse::ok(expansions))),
+        Err(e) => {
+            log::error!("Expansion list error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// GET /api/prices/{id_product}?days=90
 /// GET /api/prices/{id_product}?since=2025-01-01
+#[utoipa::path(
+    get, path = "/api/prices/{id}", tag = "prices", params(("id" = u64, Path, description = "Cardmarket product ID"), PriceParams),
+    responses((status = 200, body = ApiResponse<PriceData>), (status = 404, description = "Unknown product"))
+)]
 async fn prices_handler(
     State(state): State<AppState>,
     Path(id_product): Path<u64>,
     Query(params): Query<PriceParams>,
 ) -> Result<Json<ApiResponse<PriceData>>, StatusCode> {
     let conn = state.db.lock().unwrap();
+    price_data(&conn, id_product, &params).map(|data| {
+        Json(ApiResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        })
+    })
+}
+
+/// GET /api/price-history?name=Lightning%20Bolt&expansion=M10&days=90
+///
+/// The same as `/api/prices/{id_product}` for clients that only know the
+/// card's name. The name must resolve to exactly one product: 404 when
+/// nothing matches, an error listing the candidates when several do (e.g.
+/// reprints, unless `expansion` picks one).
+#[utoipa::path(
+    get, path = "/api/price-history", tag = "prices", params(NameHistoryParams),
+    responses(
+        (status = 200, body = ApiResponse<PriceData>),
+        (status = 404, description = "No product has the name")
+    )
+)]
+async fn price_history_by_name_handler(
+    State(state): State<AppState>,
+    Query(params): Query<NameHistoryParams>,
+) -> Result<Json<ApiResponse<PriceData>>, StatusCode> {
+    if params.name.trim().is_empty() {
+        return Ok(Json(ApiResponse::err("Missing card name")));
+    }
+    let conn = state.db.lock().unwrap();
+    let candidates = find_products_by_name(
+        &conn,
+        &params.name,
+        params.exact,
+        params.expansion.as_deref().filter(|e| !e.trim().is_empty()),
+        MAX_NAME_CANDIDATES + 1,
+    )
+    .map_err(|e| {
+        log::error!("Product name lookup error: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let product = match candidates.as_slice() {
+        [] => return Err(StatusCode::NOT_FOUND),
+        [product] => product,
+        _ => {
+            let mut listed: Vec<String> = candidates
+                .iter()
+                .take(MAX_NAME_CANDIDATES)
+                .map(|p| {
+                    format!(
+                        "{} ({}, id {})",
+                        p.name,
+                        p.expansion_name
+                            .clone()
+                            .unwrap_or_else(|| format!("expansion {}", p.id_expansion)),
+                        p.id_product
+                    )
+                })
+                .collect();
+            if candidates.len() > MAX_NAME_CANDIDATES {
+                listed.push("…".to_string());
+            }
+            return Ok(Json(ApiResponse::err(format!(
+                "'{}' matches several products, narrow it down with expansion= or use an id: {}",
+                params.name.trim(),
+                listed.join("; ")
+            ))));
+        }
+    };
+    let history_params = PriceParams {
+        days: params.days,
+        since: params.since,
+    };
+    price_data(&conn, product.id_product, &history_params).map(|data| Json(ApiResponse::ok(data)))
+}
 
+/// Product details, price history and indicators of one product; 404 for
+/// unknown products
+fn price_data(
+    conn: &Connection,
+    id_product: u64,
+    params: &PriceParams,
+) -> Result<PriceData, StatusCode> {
     // Get product details
-    let product = match get_product_by_id(&conn, id_product) {
+    let product = match get_product_by_id(conn, id_product) {
         Ok(Some(p)) => p,
         Ok(None) => return Err(StatusCode::NOT_FOUND),
         Err(e) => {
@@ -129,7 +544,7 @@ async fn prices_handler(
     let since_date = params.since_date();
 
     // Get price history
-    let history = match get_price_history(&conn, id_product, since_date.as_deref()) {
+    let history = match get_price_history(conn, id_product, since_date.as_deref()) {
         Ok(h) => h,
         Err(e) => {
             log::error!("Database error: {}", e);
@@ -149,20 +564,757 @@ async fn prices_handler(
     let trend: Vec<Option<f64>> = history.iter().map(|p| p.trend).collect();
     let cardmarket_signals = calculate_cardmarket_signals(&avg1, &avg7, &avg30, &low, &trend);
 
-    Ok(Json(ApiResponse {
-        success: true,
-        data: Some(PriceData {
-            product,
-            history,
-            indicators,
-            cardmarket_signals,
-        }),
-        error: None,
+    Ok(PriceData {
+        product,
+        history,
+        indicators,
+        cardmarket_signals,
+    })
+}
+
+/// GET /api/products/{id_product}/price?date=2025-06-01
+///
+/// Returns the single price row nearest to the date, with `exact` set when it
+/// is from that very day. 404 if the product has no price history at all.
+#[utoipa::path(
+    get, path = "/api/products/{id}/price", tag = "prices",
+    params(("id" = u64, Path, description = "Cardmarket product ID"), PriceOnDateParams),
+    responses(
+        (status = 200, body = ApiResponse<PriceOnDate>),
+        (status = 404, description = "No price history")
+    )
+)]
+async fn price_on_date_handler(
+    State(state): State<AppState>,
+    Path(id_product): Path<u64>,
+    Query(params): Query<PriceOnDateParams>,
+) -> Result<Json<ApiResponse<PriceOnDate>>, StatusCode> {
+    if chrono::NaiveDate::parse_from_str(&params.date, "%Y-%m-%d").is_err() {
+        return Ok(Json(ApiResponse::err(format!(
+            "Invalid date '{}' (expected YYYY-MM-DD)",
+            params.date
+        ))));
+    }
+    let conn = state.db.lock().unwrap();
+    match get_price_on_date(&conn, id_product, &params.date) {
+        Ok(Some(price)) => Ok(Json(ApiResponse::ok(price))),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Price-on-date lookup error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/products/{id_product}/names
+///
+/// The product's current name plus every rename seen by the catalog sync,
+/// so references by a former name can be resolved. 404 for unknown products.
+#[utoipa::path(
+    get, path = "/api/products/{id}/names", tag = "catalog", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    responses((status = 200, body = ApiResponse<ProductNames>), (status = 404, description = "Unknown product"))
+)]
+async fn product_names_handler(
+    State(state): State<AppState>,
+    Path(id_product): Path<u64>,
+) -> Result<Json<ApiResponse<ProductNames>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_product_names(&conn, id_product) {
+        Ok(Some(names)) => Ok(Json(ApiResponse::ok(names))),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Product names lookup error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/admin/audit?since=2026-03-01&limit=100
+///
+/// Recorded admin requests, newest first. Empty unless the server runs with
+/// `--audit-log`.
+#[utoipa::path(
+    get, path = "/api/admin/audit", tag = "admin", params(AuditParams),
+    security(("admin_token" = [])),
+    responses((status = 200, body = ApiResponse<Vec<AuditEntry>>), (status = 401, description = "Missing or wrong admin token"),
+        (status = 403, description = "Admin endpoints disabled"))
+)]
+async fn audit_handler(
+    State(state): State<AppState>,
+    Query(params): Query<AuditParams>,
+) -> Result<Json<ApiResponse<Vec<AuditEntry>>>, StatusCode> {
+    if let Some(since) = &params.since {
+        let valid = chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_ok()
+            || chrono::NaiveDateTime::parse_from_str(since, "%Y-%m-%d %H:%M:%S").is_ok();
+        if !valid {
+            return Ok(Json(ApiResponse::err(format!(
+                "Invalid since '{since}' (expected YYYY-MM-DD or YYYY-MM-DD HH:MM:SS)"
+            ))));
+        }
+    }
+    let limit = params.limit.min(MAX_AUDIT_LIMIT);
+    let conn = state.db.lock().unwrap();
+    match get_audit_entries(&conn, params.since.as_deref(), limit) {
+        Ok(entries) => Ok(Json(ApiResponse::ok(entries))),
+        Err(e) => {
+            log::error!("Audit log query error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/admin/slow-queries
+///
+/// Statements slower than `[sql] slow_query_ms` since the server started,
+/// with their query plans, and the indexes suggested for their full table
+/// scans. Empty while slow query logging is off.
+#[utoipa::path(
+    get, path = "/api/admin/slow-queries", tag = "admin", security(("admin_token" = [])),
+    responses((status = 200, body = ApiResponse<SlowQueryReport>), (status = 401, description = "Missing or wrong admin token"),
+        (status = 403, description = "Admin endpoints disabled"))
+)]
+async fn slow_queries_handler() -> Json<ApiResponse<SlowQueryReport>> {
+    Json(ApiResponse::ok(SlowQueryReport {
+        threshold_ms: sql_profile::slow_query_threshold().map(|t| t.as_millis() as u64),
+        queries: sql_profile::slow_queries(),
+        suggestions: index_advice(),
     }))
 }
 
+/// GET /api/admin/tenants - All tenants, oldest first
+#[utoipa::path(
+    get, path = "/api/admin/tenants", tag = "admin", security(("admin_token" = [])),
+    responses((status = 200, body = ApiResponse<Vec<Tenant>>), (status = 401, description = "Missing or wrong admin token"),
+        (status = 403, description = "Admin endpoints disabled"))
+)]
+async fn tenants_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<Tenant>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_tenants(&conn) {
+        Ok(tenants) => Ok(Json(ApiResponse::ok(tenants))),
+        Err(e) => {
+            log::error!("Tenant list error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /api/admin/tenants `{"name": "..."}`
+///
+/// Creates a tenant with a fresh random token. The response is the only place
+/// the token appears; the database keeps just its hash.
+#[utoipa::path(
+    post, path = "/api/admin/tenants", tag = "admin", request_body = NewTenant,
+    security(("admin_token" = [])),
+    responses((status = 200, body = ApiResponse<CreatedTenant>), (status = 401, description = "Missing or wrong admin token"),
+        (status = 403, description = "Admin endpoints disabled"))
+)]
+async fn create_tenant_handler(
+    State(state): State<AppState>,
+    Json(body): Json<NewTenant>,
+) -> Result<Json<ApiResponse<CreatedTenant>>, StatusCode> {
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Ok(Json(ApiResponse::err("Tenant name must not be empty")));
+    }
+    let token = generate_token().map_err(|e| {
+        log::error!("Failed to generate tenant token: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let conn = state.db.lock().unwrap();
+    match create_tenant(&conn, name, &hash_token(&token)) {
+        Ok(tenant) => {
+            log::info!("Created tenant '{}' (id {})", tenant.name, tenant.id);
+            Ok(Json(ApiResponse::ok(CreatedTenant { tenant, token })))
+        }
+        Err(rusqlite::Error::SqliteFailure(e, _))
+            if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+        {
+            Ok(Json(ApiResponse::err(format!(
+                "Tenant '{name}' already exists"
+            ))))
+        }
+        Err(e) => {
+            log::error!("Tenant creation error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// DELETE /api/admin/tenants/{id} - Removes the tenant with its watchlist and
+/// alerts
+#[utoipa::path(
+    delete, path = "/api/admin/tenants/{id}", tag = "admin",
+    params(("id" = i64, Path, description = "Tenant ID")), security(("admin_token" = [])),
+    responses(
+        (status = 200, body = ApiResponse<String>),
+        (status = 404, description = "Unknown tenant"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 403, description = "Admin endpoints disabled")
+    )
+)]
+async fn delete_tenant_handler(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<i64>,
+) -> Result<Json<ApiResponse<&'static str>>, StatusCode> {
+    let mut conn = state.db.lock().unwrap();
+    match delete_tenant(&mut conn, tenant_id) {
+        Ok(true) => Ok(Json(ApiResponse::ok("deleted"))),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Tenant deletion error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/watchlist - The calling tenant's watchlist with latest trends
+#[utoipa::path(
+    get, path = "/api/watchlist", tag = "watchlist", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<Vec<WatchlistEntry>>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn watchlist_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+) -> Result<Json<ApiResponse<Vec<WatchlistEntry>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_watchlist(&conn, tenant.id) {
+        Ok(entries) => Ok(Json(ApiResponse::ok(entries))),
+        Err(e) => {
+            log::error!("Watchlist error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// PUT /api/watchlist/{id_product} - Follow a product (404 if unknown)
+#[utoipa::path(
+    put, path = "/api/watchlist/{id}", tag = "watchlist", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    security(("tenant_token" = [])),
+    responses(
+        (status = 200, description = "Whether the product was added", body = ApiResponse<bool>),
+        (status = 404, description = "Unknown product"),
+        (status = 401, description = "Missing or unknown tenant token")
+    )
+)]
+async fn watch_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(id_product): Path<u64>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_product_by_id(&conn, id_product) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+    match add_to_watchlist(&conn, tenant.id, id_product) {
+        Ok(added) => Ok(Json(ApiResponse::ok(added))),
+        Err(e) => {
+            log::error!("Watchlist update error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// DELETE /api/watchlist/{id_product} - Unfollow a product (404 if not followed)
+#[utoipa::path(
+    delete, path = "/api/watchlist/{id}", tag = "watchlist", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    security(("tenant_token" = [])),
+    responses(
+        (status = 200, body = ApiResponse<bool>),
+        (status = 404, description = "Product not followed"),
+        (status = 401, description = "Missing or unknown tenant token")
+    )
+)]
+async fn unwatch_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(id_product): Path<u64>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match remove_from_watchlist(&conn, tenant.id, id_product) {
+        Ok(true) => Ok(Json(ApiResponse::ok(true))),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Watchlist update error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/watchlist/new-sets - Whether new products of upcoming sets are
+/// watched automatically
+#[utoipa::path(
+    get, path = "/api/watchlist/new-sets", tag = "watchlist", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<bool>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn watch_new_sets_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_watch_new_sets(&conn, tenant.id) {
+        Ok(enabled) => Ok(Json(ApiResponse::ok(enabled))),
+        Err(e) => {
+            log::error!("Watchlist settings error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// PUT /api/watchlist/new-sets - Watch new products of upcoming sets
+#[utoipa::path(
+    put, path = "/api/watchlist/new-sets", tag = "watchlist", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<bool>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn enable_watch_new_sets_handler(
+    state: State<AppState>,
+    tenant: Extension<Tenant>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    update_watch_new_sets(state, tenant, true)
+}
+
+/// DELETE /api/watchlist/new-sets - Stop watching new products of upcoming
+/// sets (products already added stay on the watchlist)
+#[utoipa::path(
+    delete, path = "/api/watchlist/new-sets", tag = "watchlist", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<bool>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn disable_watch_new_sets_handler(
+    state: State<AppState>,
+    tenant: Extension<Tenant>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    update_watch_new_sets(state, tenant, false)
+}
+
+fn update_watch_new_sets(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    enabled: bool,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match set_watch_new_sets(&conn, tenant.id, enabled) {
+        Ok(_) => Ok(Json(ApiResponse::ok(enabled))),
+        Err(e) => {
+            log::error!("Watchlist settings error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/alerts - The calling tenant's price alerts with latest trends
+#[utoipa::path(
+    get, path = "/api/alerts", tag = "alerts", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<Vec<PriceAlert>>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn alerts_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+) -> Result<Json<ApiResponse<Vec<PriceAlert>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_alerts(&conn, tenant.id) {
+        Ok(alerts) => Ok(Json(ApiResponse::ok(alerts))),
+        Err(e) => {
+            log::error!("Alert list error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /api/alerts `{"id_product": 1, "condition": "above", "threshold": 40}`
+///
+/// Registers an alert on the product's trend price (404 for unknown products).
+#[utoipa::path(
+    post, path = "/api/alerts", tag = "alerts", request_body = NewAlert,
+    security(("tenant_token" = [])),
+    responses(
+        (status = 200, body = ApiResponse<PriceAlert>),
+        (status = 404, description = "Unknown product"),
+        (status = 401, description = "Missing or unknown tenant token")
+    )
+)]
+async fn create_alert_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Json(body): Json<NewAlert>,
+) -> Result<Json<ApiResponse<PriceAlert>>, StatusCode> {
+    if !body.threshold.is_finite() || body.threshold <= 0.0 {
+        return Ok(Json(ApiResponse::err(
+            "Threshold must be a positive price in EUR",
+        )));
+    }
+    if let Err(reason) = body.rearm.check() {
+        return Ok(Json(ApiResponse::err(reason)));
+    }
+    let conn = state.db.lock().unwrap();
+    match get_product_by_id(&conn, body.id_product) {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+    match add_alert(
+        &conn,
+        tenant.id,
+        body.id_product,
+        body.condition,
+        body.threshold,
+        body.rearm,
+    ) {
+        Ok(alert) => Ok(Json(ApiResponse::ok(alert))),
+        Err(e) => {
+            log::error!("Alert creation error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// DELETE /api/alerts/{id} - Removes one of the tenant's alerts (404 if it
+/// has none with that ID)
+#[utoipa::path(
+    delete, path = "/api/alerts/{id}", tag = "alerts", params(("id" = i64, Path, description = "Alert ID")),
+    security(("tenant_token" = [])),
+    responses(
+        (status = 200, body = ApiResponse<bool>),
+        (status = 404, description = "Unknown alert"),
+        (status = 401, description = "Missing or unknown tenant token")
+    )
+)]
+async fn delete_alert_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Path(alert_id): Path<i64>,
+) -> Result<Json<ApiResponse<bool>>, StatusCode> {
+    let mut conn = state.db.lock().unwrap();
+    match remove_alert(&mut conn, tenant.id, alert_id) {
+        Ok(true) => Ok(Json(ApiResponse::ok(true))),
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Alert deletion error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/alerts/triggered?since=YYYY-MM-DD - The tenant's alerts that
+/// fired, newest first; by default those of the latest price date
+#[utoipa::path(
+    get, path = "/api/alerts/triggered", tag = "alerts", params(TriggeredAlertParams),
+    security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<Vec<TriggeredAlert>>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn triggered_alerts_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Query(params): Query<TriggeredAlertParams>,
+) -> Result<Json<ApiResponse<Vec<TriggeredAlert>>>, StatusCode> {
+    if let Some(since) = &params.since {
+        if chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_err() {
+            return Ok(Json(ApiResponse::err(format!(
+                "Invalid since date '{since}', expected YYYY-MM-DD"
+            ))));
+        }
+    }
+    let conn = state.db.lock().unwrap();
+    let since = match params.since {
+        Some(since) => since,
+        None => match get_latest_price_date(&conn) {
+            Ok(latest) => latest.unwrap_or_else(today_date),
+            Err(e) => {
+                log::error!("Database error: {}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+    match get_triggered_alerts(&conn, tenant.id, &since) {
+        Ok(fired) => Ok(Json(ApiResponse::ok(fired))),
+        Err(e) => {
+            log::error!("Fired alerts error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/alerts/export - The tenant's watchlist and alerts as an
+/// importable document
+#[utoipa::path(
+    get, path = "/api/alerts/export", tag = "alerts", security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<AlertConfig>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn export_alerts_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+) -> Result<Json<ApiResponse<AlertConfig>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match export_alert_config(&conn, &tenant) {
+        Ok(config) => Ok(Json(ApiResponse::ok(config))),
+        Err(e) => {
+            log::error!("Alert export error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /api/alerts/import - Adds an exported watchlist and alerts to the
+/// tenant's; what it has already is left alone, unknown products are
+/// skipped
+#[utoipa::path(
+    post, path = "/api/alerts/import", tag = "alerts", request_body = AlertConfig,
+    security(("tenant_token" = [])),
+    responses((status = 200, body = ApiResponse<ImportSummary>), (status = 401, description = "Missing or unknown tenant token"))
+)]
+async fn import_alerts_handler(
+    State(state): State<AppState>,
+    Extension(tenant): Extension<Tenant>,
+    Json(config): Json<AlertConfig>,
+) -> Result<Json<ApiResponse<ImportSummary>>, StatusCode> {
+    let mut conn = state.db.lock().unwrap();
+    match import_alert_config(&mut conn, tenant.id, &config) {
+        Ok(summary) => Ok(Json(ApiResponse::ok(summary))),
+        Err(e @ InventoryError::InvalidAlerts(_)) => Ok(Json(ApiResponse::err(e.to_string()))),
+        Err(e) => {
+            log::error!("Alert import error for tenant {}: {}", tenant.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Logs every request and, with `--audit-log`, persists admin requests
+/// (including rejected ones) to the audit log.
+async fn access_log(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let client_ip = client_ip(&request);
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16();
+    let duration_ms = started.elapsed().as_millis() as u64;
+    log::info!(
+        target: "access",
+        "method={} path={} status={} duration_ms={} client_ip={}",
+        method,
+        path,
+        status,
+        duration_ms,
+        client_ip
+    );
+
+    if state.options.audit_log && path.starts_with(ADMIN_PREFIX) {
+        let conn = state.db.lock().unwrap();
+        if let Err(e) = insert_audit_entry(&conn, &method, &path, status, duration_ms, &client_ip) {
+            log::error!("Failed to write audit log entry: {}", e);
+        }
+    }
+    response
+}
+
+/// Rejects admin requests without the configured bearer token: 403 when no
+/// token is configured, 401 for a missing or wrong one.
+async fn require_admin(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(expected) = state.options.admin_token.as_deref() else {
+        return StatusCode::FORBIDDEN.into_response();
+    };
+    match bearer_token(&request) {
+        Some(token) if tokens_match(token, expected) => next.run(request).await,
+        _ => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Resolves the bearer token to its tenant and hands it to the handler as an
+/// `Extension<Tenant>`; 401 for a missing or unknown token.
+async fn require_tenant(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = bearer_token(&request) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    // Looking up the hash keeps the token comparison out of string equality
+    let tenant = {
+        let conn = state.db.lock().unwrap();
+        get_tenant_by_token_hash(&conn, &hash_token(token))
+    };
+    match tenant {
+        Ok(Some(tenant)) => {
+            request.extensions_mut().insert(tenant);
+            next.run(request).await
+        }
+        Ok(None) => StatusCode::UNAUTHORIZED.into_response(),
+        Err(e) => {
+            log::error!("Tenant lookup error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// The `Authorization: Bearer <token>` value of a request
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// A random 256-bit token, hex-encoded
+fn generate_token() -> Result<String, getrandom::Error> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)?;
+    Ok(to_hex(&bytes))
+}
+
+/// Hex SHA-256 of a token, as stored in `tenants.token_hash`
+fn hash_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.as_bytes()))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Compares tokens in constant time, so response timing reveals nothing
+/// about how much of a guess was right.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The peer address of the connection, or `-` when unknown (e.g. in tests).
+fn client_ip(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or_else(|| "-".to_string(), |info| info.0.ip().to_string())
+}
+
+/// GET /api/compare?min_eur=5&limit=100
+///
+/// Cards with both a Cardmarket trend and a TCGplayer price, largest
+/// EUR spread (either direction) first. Empty until the TCGplayer source
+/// has been collected at least once.
+#[utoipa::path(
+    get, path = "/api/compare", tag = "prices", params(CompareParams),
+    responses((status = 200, body = ApiResponse<Vec<PriceComparison>>))
+)]
+async fn compare_handler(
+    State(state): State<AppState>,
+    Query(params): Query<CompareParams>,
+) -> Result<Json<ApiResponse<Vec<PriceComparison>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    let limit = params.limit.min(MAX_COMPARE_LIMIT);
+    match get_price_comparisons(&conn, params.min_eur, limit) {
+        Ok(rows) => Ok(Json(ApiResponse::ok(rows))),
+        Err(e) => {
+            log::error!("Price comparison error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/compare/{id_product}
+///
+/// Latest Cardmarket vs. TCGplayer comparison for one product, 404 if either
+/// price (or the exchange rate) is missing.
+#[utoipa::path(
+    get, path = "/api/compare/{id}", tag = "prices", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    responses(
+        (status = 200, body = ApiResponse<PriceComparison>),
+        (status = 404, description = "No comparison for the product")
+    )
+)]
+async fn compare_product_handler(
+    State(state): State<AppState>,
+    Path(id_product): Path<u64>,
+) -> Result<Json<ApiResponse<PriceComparison>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    match get_price_comparison(&conn, id_product) {
+        Ok(Some(row)) => Ok(Json(ApiResponse::ok(row))),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            log::error!("Price comparison error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/movers.atom?min_eur=1&limit=25
+///
+/// Atom feed of the largest trend changes between the two latest price
+/// dates. Links point back at this server, using the request's `Host` (and
+/// `X-Forwarded-Proto` behind a reverse proxy).
+#[utoipa::path(
+    get, path = "/api/movers.atom", tag = "prices", params(MoversParams),
+    responses((status = 200, description = "Atom feed", content_type = "application/atom+xml", body = String))
+)]
+async fn movers_feed_handler(
+    State(state): State<AppState>,
+    Query(params): Query<MoversParams>,
+    headers: header::HeaderMap,
+) -> Result<Response, StatusCode> {
+    let movers = {
+        let conn = state.db.lock().unwrap();
+        let limit = params.limit.min(MAX_MOVERS_LIMIT);
+        get_price_movers(&conn, params.min_eur, limit).map_err(|e| {
+            log::error!("Price movers error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    };
+    let header_value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let host = header_value("host").unwrap_or("localhost");
+    let scheme = header_value("x-forwarded-proto").unwrap_or("http");
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let xml = movers_feed(&movers, &format!("{scheme}://{host}"), &now);
+    Ok((
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        xml,
+    )
+        .into_response())
+}
+
+/// GET /api/upcoming-sets?days=60
+/// Announced sets releasing within the given days, soonest first
+#[utoipa::path(
+    get, path = "/api/upcoming-sets", tag = "catalog", params(UpcomingSetsParams),
+    responses((status = 200, body = ApiResponse<Vec<UpcomingSet>>))
+)]
+async fn upcoming_sets_handler(
+    State(state): State<AppState>,
+    Query(params): Query<UpcomingSetsParams>,
+) -> Result<Json<ApiResponse<Vec<UpcomingSet>>>, StatusCode> {
+    let conn = state.db.lock().unwrap();
+    let days = params.days.min(MAX_UPCOMING_SETS_DAYS);
+    match get_upcoming_sets(&conn, &today_date(), days) {
+        Ok(sets) => Ok(Json(ApiResponse::ok(sets))),
+        Err(e) => {
+            log::error!("Upcoming sets error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// GET /api/card-image/{id_product}
 /// Fetches and caches card images from Scryfall using Cardmarket product ID
+#[utoipa::path(
+    get, path = "/api/card-image/{id}", tag = "catalog", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    responses(
+        (status = 200, description = "Card image", content_type = "image/jpeg", body = Vec<u8>),
+        (status = 404, description = "No image for the product")
+    )
+)]
 async fn card_image_handler(
     State(state): State<AppState>,
     Path(id_product): Path<u64>,
@@ -184,8 +1336,76 @@ async fn card_image_handler(
     }
 }
 
+/// How long browsers may reuse a product image before revalidating it.
+const PRODUCT_IMAGE_CACHE_CONTROL: &str = "public, max-age=604800";
+
+/// GET /api/products/{id_product}/image
+///
+/// The product's card art for the web UI. The product must be in the
+/// catalog; its Scryfall printing is looked up by Cardmarket ID and the image
+/// is served from the shared image cache, downloading it on a miss. The
+/// response carries an `ETag` of the image bytes, so revalidations with
+/// `If-None-Match` get a 304.
+#[utoipa::path(
+    get, path = "/api/products/{id}/image", tag = "catalog", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    responses(
+        (status = 200, description = "Card image", content_type = "image/jpeg", body = Vec<u8>),
+        (status = 304, description = "Image unchanged since the given ETag"),
+        (status = 404, description = "Unknown product or no Scryfall image for it")
+    )
+)]
+async fn product_image_handler(
+    State(state): State<AppState>,
+    Path(id_product): Path<u64>,
+    headers: header::HeaderMap,
+) -> Result<Response, StatusCode> {
+    let known = {
+        let conn = state.db.lock().unwrap();
+        get_product_by_id(&conn, id_product).map_err(|e| {
+            log::error!("Product lookup error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    };
+    if known.is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let bytes = fetch_image_cached(&state.image_cache, id_product)
+        .await
+        .map_err(|e| {
+            log::warn!("No image for product {}: {}", id_product, e);
+            StatusCode::NOT_FOUND
+        })?;
+
+    let etag = format!("\"{}\"", &to_hex(&Sha256::digest(&bytes))[..16]);
+    let cached_by_client = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|t| t.trim() == etag || t.trim() == "*"));
+    let response = Response::builder()
+        .header(header::ETAG, &etag)
+        .header(header::CACHE_CONTROL, PRODUCT_IMAGE_CACHE_CONTROL);
+    let response = if cached_by_client {
+        response
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+    } else {
+        response
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, image_content_type(&bytes))
+            .body(Body::from(bytes))
+    };
+    Ok(response.unwrap())
+}
+
 /// GET /api/card-info/{id_product}
 /// Returns cached Scryfall metadata (set name, type, mana cost, rarity, oracle text, purchase links)
+#[utoipa::path(
+    get, path = "/api/card-info/{id}", tag = "catalog", params(("id" = u64, Path, description = "Cardmarket product ID")),
+    responses(
+        (status = 200, body = ApiResponse<CardInfo>),
+        (status = 404, description = "No Scryfall card for the product")
+    )
+)]
 async fn card_info_handler(
     State(state): State<AppState>,
     Path(id_product): Path<u64>,
@@ -218,6 +1438,10 @@ async fn card_info_handler(
 
 /// POST /api/latest-prices
 /// Returns the most recent price row for each requested product ID.
+#[utoipa::path(
+    post, path = "/api/latest-prices", tag = "prices", request_body = BulkPriceRequest,
+    responses((status = 200, body = ApiResponse<Vec<LatestPrice>>))
+)]
 async fn latest_prices_handler(
     State(state): State<AppState>,
     Json(body): Json<BulkPriceRequest>,
@@ -246,6 +1470,10 @@ async fn latest_prices_handler(
 /// Returns, for each (product ID, date) pair, the price row in effect on that
 /// date (most recent row on or before it). This is a pure indexed lookup —
 /// no aggregation happens server-side; clients compute deltas themselves.
+#[utoipa::path(
+    post, path = "/api/price-snapshots", tag = "prices", request_body = PriceSnapshotRequest,
+    responses((status = 200, body = ApiResponse<Vec<PriceSnapshot>>))
+)]
 async fn price_snapshots_handler(
     State(state): State<AppState>,
     Json(body): Json<PriceSnapshotRequest>,
@@ -279,19 +1507,415 @@ async fn price_snapshots_handler(
     }
 }
 
-/// Build the web server router
+/// POST /api/wantslist/price
+///
+/// Prices a wantslist as of each requested date: per row the cheapest
+/// printing's trend in effect then, plus the total per date. Answers "what
+/// would this deck have cost six months ago" without fetching every
+/// printing's history client-side.
+#[utoipa::path(
+    post, path = "/api/wantslist/price", tag = "prices", request_body = WantslistPriceRequest,
+    responses((status = 200, body = ApiResponse<WantslistQuote>))
+)]
+async fn wantslist_price_handler(
+    State(state): State<AppState>,
+    Json(body): Json<WantslistPriceRequest>,
+) -> Result<Json<ApiResponse<WantslistQuote>>, StatusCode> {
+    if body.cards.len() > MAX_WANTSLIST_CARDS {
+        return Ok(Json(ApiResponse::err(format!(
+            "Too many cards (max {MAX_WANTSLIST_CARDS})"
+        ))));
+    }
+    if body.dates.is_empty() || body.dates.len() > MAX_SNAPSHOT_DATES {
+        return Ok(Json(ApiResponse::err(format!(
+            "Give 1 to {MAX_SNAPSHOT_DATES} dates"
+        ))));
+    }
+    for date in &body.dates {
+        if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+            return Ok(Json(ApiResponse::err(format!(
+                "Invalid date '{date}' (expected YYYY-MM-DD)"
+            ))));
+        }
+    }
+    let conn = state.db.lock().unwrap();
+    match price_wantslist(&conn, &body.cards, &body.dates) {
+        Ok(quote) => Ok(Json(ApiResponse::ok(quote))),
+        Err(e) => {
+            log::error!("Wantslist pricing error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/stock?q=bolt&set=LEA&rarity=rare&location=A1&min_price=1&max_price=50&inventory=shop
+///
+/// In-stock card variants of one inventory (default: the shop) from the
+/// check_stock database matching every given filter, by name. At most
+/// `MAX_STOCK_LIMIT` rows.
+#[utoipa::path(
+    get, path = "/api/stock", tag = "stock", params(StockFilter),
+    responses(
+        (status = 200, body = ApiResponse<Vec<StockCard>>),
+        (status = 404, description = "No stock database mounted")
+    )
+)]
+async fn stock_handler(
+    State(state): State<StockState>,
+    Query(filter): Query<StockFilter>,
+) -> Result<Json<ApiResponse<Vec<StockCard>>>, StatusCode> {
+    let conn = state.stock.lock().unwrap();
+    match search_stock(&conn, &filter) {
+        Ok(cards) => Ok(Json(ApiResponse::ok(cards))),
+        Err(e) => {
+            log::error!("Stock query error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/stock/facets?inventory=shop - Sets, rarities and locations in
+/// stock, and the inventories there are
+#[utoipa::path(
+    get, path = "/api/stock/facets", tag = "stock", params(InventoryParams),
+    responses(
+        (status = 200, body = ApiResponse<StockFacets>),
+        (status = 404, description = "No stock database mounted")
+    )
+)]
+async fn stock_facets_handler(
+    State(state): State<StockState>,
+    Query(params): Query<InventoryParams>,
+) -> Result<Json<ApiResponse<StockFacets>>, StatusCode> {
+    let conn = state.stock.lock().unwrap();
+    match stock_facets(&conn, params.inventory.as_deref()) {
+        Ok(facets) => Ok(Json(ApiResponse::ok(facets))),
+        Err(e) => {
+            log::error!("Stock facets error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/inventory/value?price=trend|avg7|listed&refresh=true&inventory=shop
+///
+/// Total value of one inventory of the mounted stock (default: the shop) at
+/// the latest market (or listed) prices, with a per-set breakdown. Cached
+/// until the stock or price data changes.
+#[utoipa::path(
+    get, path = "/api/inventory/value", tag = "stock", params(ValuationParams),
+    responses(
+        (status = 200, body = ApiResponse<InventoryValue>),
+        (status = 404, description = "No stock database mounted")
+    )
+)]
+async fn inventory_value_handler(
+    State(state): State<StockState>,
+    Query(params): Query<ValuationParams>,
+) -> Result<Json<ApiResponse<InventoryValue>>, StatusCode> {
+    let stock = state.stock.lock().unwrap();
+    let prices = state.prices.lock().unwrap();
+    let mut valuations = state.valuations.lock().unwrap();
+    match valuations.get(
+        &stock,
+        &prices,
+        params.inventory.as_deref(),
+        params.price,
+        params.refresh,
+    ) {
+        Ok(value) => Ok(Json(ApiResponse::ok(value))),
+        Err(e) => {
+            log::error!("Inventory valuation error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/inventory/valuation?inventory=shop
+///
+/// Value of one inventory of the mounted stock (default: the shop) at the
+/// latest trend, average and low prices (foil prices for foils), with every
+/// in-stock variant and its unit prices.
+#[utoipa::path(
+    get, path = "/api/inventory/valuation", tag = "stock", params(InventoryParams),
+    responses(
+        (status = 200, body = ApiResponse<InventoryValuation>),
+        (status = 404, description = "No stock database mounted")
+    )
+)]
+async fn inventory_valuation_handler(
+    State(state): State<StockState>,
+    Query(params): Query<InventoryParams>,
+) -> Result<Json<ApiResponse<InventoryValuation>>, StatusCode> {
+    let prices = state.prices.lock().unwrap();
+    match get_inventory_valuation(&prices, &state.stock_path, params.inventory.as_deref()) {
+        Ok(valuation) => Ok(Json(ApiResponse::ok(valuation))),
+        Err(e) => {
+            log::error!("Inventory valuation error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /api/revenue?days=30
+///
+/// Revenue, order count and average order value of the last days' orders on
+/// the accounting app's order board, compared with the period before.
+#[utoipa::path(
+    get, path = "/api/revenue", tag = "sales", params(RevenueParams),
+    responses(
+        (status = 200, body = ApiResponse<RevenueSummary>),
+        (status = 404, description = "No order board configured")
+    )
+)]
+async fn revenue_handler(
+    State(state): State<RevenueState>,
+    Query(params): Query<RevenueParams>,
+) -> Result<Json<ApiResponse<RevenueSummary>>, StatusCode> {
+    let today = chrono::NaiveDate::parse_from_str(&today_date(), "%Y-%m-%d")
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    match load_order_board(&state.order_board) {
+        Ok(orders) => Ok(Json(ApiResponse::ok(revenue_summary(
+            &orders,
+            today,
+            params.days,
+        )))),
+        Err(e) => {
+            log::error!(
+                "Failed to read order board {}: {}",
+                state.order_board.display(),
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// OpenAPI document of every endpoint, served as `/api/openapi.json`
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "inventory_sync",
+        description = "Cardmarket catalog, price history and stock API. Every JSON \
+                       response is wrapped in an `ApiResponse` envelope."
+    ),
+    paths(
+        index_handler,
+        health_handler,
+        openapi_handler,
+        search_handler,
+        expansions_handler,
+        prices_handler,
+        price_history_by_name_handler,
+        price_on_date_handler,
+        product_names_handler,
+        latest_prices_handler,
+        price_snapshots_handler,
+        wantslist_price_handler,
+        compare_handler,
+        compare_product_handler,
+        movers_feed_handler,
+        upcoming_sets_handler,
+        product_image_handler,
+        card_image_handler,
+        card_info_handler,
+        audit_handler,
+        slow_queries_handler,
+        tenants_handler,
+        create_tenant_handler,
+        delete_tenant_handler,
+        watchlist_handler,
+        watch_handler,
+        unwatch_handler,
+        watch_new_sets_handler,
+        enable_watch_new_sets_handler,
+        disable_watch_new_sets_handler,
+        alerts_handler,
+        create_alert_handler,
+        delete_alert_handler,
+        triggered_alerts_handler,
+        export_alerts_handler,
+        import_alerts_handler,
+        stock_handler,
+        stock_facets_handler,
+        inventory_value_handler,
+        inventory_valuation_handler,
+        revenue_handler,
+    ),
+    modifiers(&BearerTokens)
+)]
+struct ApiDoc;
+
+/// Declares the admin and tenant bearer tokens the secured paths refer to
+struct BearerTokens;
+
+impl Modify for BearerTokens {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        for (name, description) in [
+            ("admin_token", "The token given with `--admin-token`"),
+            (
+                "tenant_token",
+                "A tenant's token from `POST /api/admin/tenants`",
+            ),
+        ] {
+            components.add_security_scheme(
+                name,
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .description(Some(description))
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
+/// The OpenAPI document of the web API
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    // Taken from Cargo.toml, which declares no license
+    doc.info.license = None;
+    doc
+}
+
+/// GET /api/openapi.json - This API's OpenAPI document
+#[utoipa::path(
+    get, path = "/api/openapi.json", tag = "meta",
+    responses((status = 200, description = "OpenAPI 3.1 document", content_type = "application/json"))
+)]
+async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(openapi())
+}
+
+/// The `/api/stock` and `/api/inventory` routes, or none when no stock
+/// database is configured or it can't be opened.
+fn stock_router(
+    stock_db: Option<&std::path::Path>,
+    prices: Arc<Mutex<Connection>>,
+) -> Router<AppState> {
+    let Some(path) = stock_db else {
+        return Router::new();
+    };
+    match open_stock_db(path) {
+        Ok(conn) => {
+            log::info!("Serving stock from {}", path.display());
+            Router::new()
+                .route("/api/stock", get(stock_handler))
+                .route("/api/stock/facets", get(stock_facets_handler))
+                .route("/api/inventory/value", get(inventory_value_handler))
+                .route("/api/inventory/valuation", get(inventory_valuation_handler))
+                .with_state(StockState {
+                    stock: Arc::new(Mutex::new(conn)),
+                    stock_path: Arc::new(path.to_path_buf()),
+                    prices,
+                    valuations: Arc::new(Mutex::new(ValuationCache::default())),
+                })
+        }
+        Err(e) => {
+            log::error!(
+                "Failed to open stock database {}, stock browser disabled: {}",
+                path.display(),
+                e
+            );
+            Router::new()
+        }
+    }
+}
+
+/// The `/api/revenue` route, or none when no order board is configured. The
+/// board is read on every request, so it may not exist yet.
+fn revenue_router(order_board: Option<&std::path::Path>) -> Router<AppState> {
+    let Some(path) = order_board else {
+        return Router::new();
+    };
+    log::info!("Serving revenue from {}", path.display());
+    Router::new()
+        .route("/api/revenue", get(revenue_handler))
+        .with_state(RevenueState {
+            order_board: Arc::new(path.to_path_buf()),
+        })
+}
+
+/// Build the web server router with default options (admin endpoints
+/// disabled, no audit log)
 pub fn create_router(db: Arc<Mutex<Connection>>, image_cache: Arc<ImageCache>) -> Router {
-    let state = AppState { db, image_cache };
+    create_router_with_options(db, image_cache, WebOptions::default())
+}
+
+/// Build the web server router
+pub fn create_router_with_options(
+    db: Arc<Mutex<Connection>>,
+    image_cache: Arc<ImageCache>,
+    options: WebOptions,
+) -> Router {
+    let stock = stock_router(options.stock_db.as_deref(), db.clone());
+    let revenue = revenue_router(options.order_board.as_deref());
+    let state = AppState {
+        db,
+        image_cache,
+        options: Arc::new(options),
+    };
+
+    let admin = Router::new()
+        .route("/audit", get(audit_handler))
+        .route("/slow-queries", get(slow_queries_handler))
+        .route("/tenants", get(tenants_handler).post(create_tenant_handler))
+        .route("/tenants/{id}", delete(delete_tenant_handler))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin));
+
+    let tenant = Router::new()
+        .route("/", get(watchlist_handler))
+        .route(
+            "/new-sets",
+            get(watch_new_sets_handler)
+                .put(enable_watch_new_sets_handler)
+                .delete(disable_watch_new_sets_handler),
+        )
+        .route("/{id}", put(watch_handler).delete(unwatch_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_tenant,
+        ));
+
+    let alerts = Router::new()
+        .route("/", get(alerts_handler).post(create_alert_handler))
+        .route("/triggered", get(triggered_alerts_handler))
+        .route("/export", get(export_alerts_handler))
+        .route("/import", post(import_alerts_handler))
+        .route("/{id}", delete(delete_alert_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_tenant,
+        ));
 
     Router::new()
         .route("/", get(index_handler))
         .route("/api/health", get(health_handler))
+        .route("/api/openapi.json", get(openapi_handler))
         .route("/api/search", get(search_handler))
+        .route("/api/expansions", get(expansions_handler))
         .route("/api/prices/{id}", get(prices_handler))
+        .route("/api/price-history", get(price_history_by_name_handler))
+        .route("/api/products/{id}/price", get(price_on_date_handler))
+        .route("/api/products/{id}/names", get(product_names_handler))
+        .route("/api/products/{id}/image", get(product_image_handler))
         .route("/api/latest-prices", post(latest_prices_handler))
         .route("/api/price-snapshots", post(price_snapshots_handler))
+        .route("/api/wantslist/price", post(wantslist_price_handler))
+        .route("/api/compare", get(compare_handler))
+        .route("/api/compare/{id}", get(compare_product_handler))
+        .route("/api/movers.atom", get(movers_feed_handler))
+        .route("/api/upcoming-sets", get(upcoming_sets_handler))
         .route("/api/card-image/{id}", get(card_image_handler))
         .route("/api/card-info/{id}", get(card_info_handler))
+        .nest(ADMIN_PREFIX.trim_end_matches('/'), admin)
+        .nest("/api/watchlist", tenant)
+        .nest("/api/alerts", alerts)
+        .merge(stock)
+        .merge(revenue)
+        .layer(middleware::from_fn_with_state(state.clone(), access_log))
         .with_state(state)
 }
 
@@ -304,18 +1928,26 @@ pub async fn serve(
     db: Arc<Mutex<Connection>>,
     db_path: &std::path::Path,
     port: u16,
+    options: WebOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Create image cache in the same directory as the database
     let db_dir = db_path.parent().ok_or("Failed to get database directory")?;
     let image_cache = Arc::new(ImageCache::new(db_dir));
 
-    let app = create_router(db, image_cache);
+    if options.admin_token.is_none() {
+        log::info!("No admin token configured, admin endpoints are disabled");
+    }
+    let app = create_router_with_options(db, image_cache, options);
     let addr = format!("0.0.0.0:{}", port);
 
     log::info!("Web UI listening on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
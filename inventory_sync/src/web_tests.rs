@@ -1,6 +1,8 @@
 //! Tests for web.
 
 use super::*;
+use crate::cardmarket::{make_test_price_entry, make_test_product, PriceGuide, ProductCatalog};
+use crate::database::{insert_price_history, upsert_products};
 use crate::init_schema;
 use rusqlite::Connection;
 use tempfile::TempDir;
@@ -32,6 +34,7 @@ fn test_app_state_clone() {
     let state = AppState {
         db: db.clone(),
         image_cache: image_cache.clone(),
+        options: Arc::new(WebOptions::default()),
     };
 
     // Test that AppState is Clone
@@ -42,6 +45,7 @@ fn test_app_state_clone() {
 fn test_search_params_default_limit() {
     let params = SearchParams {
         q: "test".to_string(),
+        category: None,
         limit: default_limit(),
     };
 
@@ -75,3 +79,1042 @@ fn test_api_response_error_serialization() {
     // data should be omitted when None
     assert!(!json.contains("\"data\""));
 }
+
+async fn get_json(router: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    use tower::ServiceExt;
+    let response = router
+        .oneshot(
+            axum::http::Request::builder()
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_price_on_date_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(7, Some(4.5))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/products/7/price?date=2026-03-04").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["price_date"], "2026-03-01");
+    assert_eq!(json["data"]["exact"], false);
+    assert_eq!(json["data"]["trend"], 4.5);
+
+    let (status, json) = get_json(router.clone(), "/api/products/7/price?date=03/04/2026").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["success"], false);
+
+    let (status, _) = get_json(router, "/api/products/8/price?date=2026-03-04").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_product_names_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let before = ProductCatalog::from_entries(vec![make_test_product(7, "Lotus Blak")]);
+    upsert_products(&mut conn, &before).unwrap();
+    let after = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &after).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/products/7/names").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["name"], "Black Lotus");
+    assert_eq!(json["data"]["renames"][0]["old_name"], "Lotus Blak");
+
+    let (status, _) = get_json(router, "/api/products/8/names").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_product_image_endpoint() {
+    use tower::ServiceExt;
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let image_cache = Arc::new(ImageCache::new(temp_dir.path()));
+    let png = b"\x89PNG\r\n\x1a\nart".to_vec();
+    image_cache.insert_image(7, &png);
+    let router = create_router(Arc::new(Mutex::new(conn)), image_cache);
+    let request = |if_none_match: Option<&str>| {
+        let mut request = axum::http::Request::builder().uri("/api/products/7/image");
+        if let Some(tag) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, tag);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let response = router.clone().oneshot(request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+    assert_eq!(
+        response.headers()[header::CACHE_CONTROL],
+        PRODUCT_IMAGE_CACHE_CONTROL
+    );
+    let etag = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body.to_vec(), png);
+
+    let response = router.clone().oneshot(request(Some(&etag))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    let response = router
+        .clone()
+        .oneshot(request(Some("\"stale\"")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Unknown products are rejected before asking Scryfall
+    let (status, _) = get_json(router, "/api/products/8/image").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_price_history_by_name_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let mut reprint = make_test_product(8, "Lightning Bolt");
+    reprint.id_expansion = 2;
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(7, "Lightning Bolt"),
+        reprint,
+        make_test_product(9, "Black Lotus"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    crate::database::upsert_expansions(
+        &mut conn,
+        &[crate::cardmarket::ExpansionEntry {
+            id_expansion: 2,
+            name: "Magic 2010".to_string(),
+            code: Some("M10".to_string()),
+            release_date: None,
+        }],
+    )
+    .unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![
+            make_test_price_entry(7, Some(1.5)),
+            make_test_price_entry(8, Some(2.5)),
+            make_test_price_entry(9, Some(4.5)),
+        ],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/price-history?name=black%20lotus").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["product"]["id_product"], 9);
+    assert_eq!(json["data"]["history"][0]["trend"], 4.5);
+
+    // Reprints are ambiguous until the expansion picks one
+    let (status, json) = get_json(router.clone(), "/api/price-history?name=Lightning%20Bolt").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["success"], false);
+    assert!(json["error"].as_str().unwrap().contains("Magic 2010, id 8"));
+    let (_, json) = get_json(
+        router.clone(),
+        "/api/price-history?name=Lightning%20Bolt&expansion=m10",
+    )
+    .await;
+    assert_eq!(json["data"]["product"]["id_product"], 8);
+
+    // Partial names only match with exact=false
+    let (status, _) = get_json(router.clone(), "/api/price-history?name=Lotus").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, json) = get_json(router.clone(), "/api/price-history?name=Lotus&exact=false").await;
+    assert_eq!(json["data"]["product"]["id_product"], 9);
+
+    let (_, json) = get_json(router, "/api/price-history?name=%20").await;
+    assert_eq!(json["success"], false);
+}
+
+/// Sends a GET from 192.168.1.20 with an optional bearer token.
+async fn get_as_admin(router: Router, uri: &str, token: Option<&str>) -> StatusCode {
+    use tower::ServiceExt;
+    let mut request = axum::http::Request::builder()
+        .uri(uri)
+        .extension(ConnectInfo(SocketAddr::from(([192, 168, 1, 20], 50000))));
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = router
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    response.status()
+}
+
+#[tokio::test]
+async fn test_admin_endpoints_disabled_without_token() {
+    let (conn, temp_dir) = create_test_db();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+    let status = get_as_admin(router, "/api/admin/audit", Some("anything")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_admin_requests_are_authenticated_and_audited() {
+    let (conn, temp_dir) = create_test_db();
+    let db = Arc::new(Mutex::new(conn));
+    let router = create_router_with_options(
+        db.clone(),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            audit_log: true,
+            admin_token: Some("s3cret".to_string()),
+            ..WebOptions::default()
+        },
+    );
+
+    let status = get_as_admin(router.clone(), "/api/admin/audit", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = get_as_admin(router.clone(), "/api/admin/audit", Some("s3cres")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = get_as_admin(router.clone(), "/api/health", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let status = get_as_admin(router.clone(), "/api/admin/audit", Some("s3cret")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Only admin requests are persisted, rejected ones included
+    let entries = get_audit_entries(&db.lock().unwrap(), None, 10).unwrap();
+    assert_eq!(
+        entries.iter().map(|e| e.status).collect::<Vec<_>>(),
+        vec![200, 401, 401]
+    );
+    assert!(entries.iter().all(|e| e.path == "/api/admin/audit"));
+    assert!(entries.iter().all(|e| e.client_ip == "192.168.1.20"));
+}
+
+#[tokio::test]
+async fn test_slow_query_report_is_admin_only() {
+    let (conn, temp_dir) = create_test_db();
+    let router = create_router_with_options(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            admin_token: Some("admin".to_string()),
+            ..WebOptions::default()
+        },
+    );
+
+    let (status, _) = send(router.clone(), "GET", "/api/admin/slow-queries", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, json) = send(
+        router,
+        "GET",
+        "/api/admin/slow-queries",
+        Some("admin"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    // The log is process-wide, so only the shape is checked here
+    assert!(json["data"]["queries"].is_array());
+    assert!(json["data"]["suggestions"].is_array());
+}
+
+#[test]
+fn test_tokens_match() {
+    assert!(tokens_match("abc", "abc"));
+    assert!(!tokens_match("abd", "abc"));
+    assert!(!tokens_match("ab", "abc"));
+    assert!(!tokens_match("", "abc"));
+}
+
+#[tokio::test]
+async fn test_compare_endpoints() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(7, Some(10.0))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let price = crate::tcgplayer::TcgplayerPrice {
+        id_product: 7,
+        usd: Some(15.0),
+        usd_foil: None,
+    };
+    crate::database::insert_tcgplayer_prices(&mut conn, "2026-03-01", &[price]).unwrap();
+    crate::database::upsert_exchange_rate(&conn, "2026-03-01", "USD", 1.25).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/compare?min_eur=5").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"][0]["id_product"], 7);
+    assert_eq!(json["data"][0]["tcgplayer_eur"], 12.0);
+
+    let (_, json) = get_json(router.clone(), "/api/compare?min_eur=50").await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 0);
+
+    let (status, json) = get_json(router.clone(), "/api/compare/7").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["spread_pct"], 20.0);
+
+    let (status, _) = get_json(router, "/api/compare/8").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_movers_feed_endpoint() {
+    use tower::ServiceExt;
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (created_at, trend) in [
+        ("2026-03-01T10:00:00+0100", 10.0),
+        ("2026-03-02T10:00:00+0100", 12.0),
+    ] {
+        let guide =
+            PriceGuide::from_entries(vec![make_test_price_entry(7, Some(trend))], created_at);
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let response = router
+        .oneshot(
+            axum::http::Request::builder()
+                .uri("/api/movers.atom")
+                .header("host", "cards.local:3000")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "application/atom+xml; charset=utf-8"
+    );
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let xml = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(xml.contains("<title>+20.0% Black Lotus</title>"));
+    assert!(xml.contains("href=\"http://cards.local:3000/?product=7\""));
+}
+
+/// Sends a request with an optional bearer token and JSON body.
+async fn send(
+    router: Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    use tower::ServiceExt;
+    let mut request = axum::http::Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let body = match body {
+        Some(json) => {
+            request = request.header(header::CONTENT_TYPE, "application/json");
+            Body::from(json.to_string())
+        }
+        None => Body::empty(),
+    };
+    let response = router.oneshot(request.body(body).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_wantslist_price_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(7, Some(4.5))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+    let body = |dates: serde_json::Value| {
+        Some(serde_json::json!({
+            "cards": [{"quantity": 2, "name": "Black Lotus"}],
+            "dates": dates,
+        }))
+    };
+
+    let (status, json) = send(
+        router.clone(),
+        "POST",
+        "/api/wantslist/price",
+        None,
+        body(serde_json::json!(["2026-02-01", "2026-03-05"])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        json["data"]["lines"][0]["prices"][0],
+        serde_json::Value::Null
+    );
+    assert_eq!(json["data"]["lines"][0]["prices"][1]["id_product"], 7);
+    assert_eq!(json["data"]["totals"][1]["total"], 9.0);
+
+    let (_, json) = send(
+        router.clone(),
+        "POST",
+        "/api/wantslist/price",
+        None,
+        body(serde_json::json!(["01/03/2026"])),
+    )
+    .await;
+    assert_eq!(json["success"], false);
+
+    let (_, json) = send(
+        router,
+        "POST",
+        "/api/wantslist/price",
+        None,
+        body(serde_json::json!([])),
+    )
+    .await;
+    assert_eq!(json["success"], false);
+}
+
+#[tokio::test]
+async fn test_tenant_watchlists_are_isolated() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![
+        make_test_product(7, "Black Lotus"),
+        make_test_product(8, "Mox Pearl"),
+    ]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let router = create_router_with_options(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            admin_token: Some("admin".to_string()),
+            ..WebOptions::default()
+        },
+    );
+
+    // Tenants are created by the admin; the token is returned once
+    let new_tenant = |name: &str| serde_json::json!({ "name": name });
+    let (status, _) = send(
+        router.clone(),
+        "POST",
+        "/api/admin/tenants",
+        None,
+        Some(new_tenant("alice")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (_, alice) = send(
+        router.clone(),
+        "POST",
+        "/api/admin/tenants",
+        Some("admin"),
+        Some(new_tenant("alice")),
+    )
+    .await;
+    let (_, bob) = send(
+        router.clone(),
+        "POST",
+        "/api/admin/tenants",
+        Some("admin"),
+        Some(new_tenant("bob")),
+    )
+    .await;
+    let (_, duplicate) = send(
+        router.clone(),
+        "POST",
+        "/api/admin/tenants",
+        Some("admin"),
+        Some(new_tenant("bob")),
+    )
+    .await;
+    assert_eq!(duplicate["success"], false);
+    let alice_token = alice["data"]["token"].as_str().unwrap();
+    let bob_token = bob["data"]["token"].as_str().unwrap();
+    assert_eq!(alice_token.len(), 64);
+    assert_ne!(alice_token, bob_token);
+
+    let (status, json) = send(
+        router.clone(),
+        "GET",
+        "/api/admin/tenants",
+        Some("admin"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    assert!(
+        json.to_string().find(alice_token).is_none(),
+        "tokens are never listed"
+    );
+
+    // Watchlists need a tenant token and only show the caller's entries
+    let (status, _) = send(router.clone(), "GET", "/api/watchlist", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(router.clone(), "GET", "/api/watchlist", Some("admin"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, json) = send(
+        router.clone(),
+        "PUT",
+        "/api/watchlist/7",
+        Some(alice_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"], true);
+    let (status, _) = send(
+        router.clone(),
+        "PUT",
+        "/api/watchlist/99",
+        Some(alice_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    send(
+        router.clone(),
+        "PUT",
+        "/api/watchlist/8",
+        Some(bob_token),
+        None,
+    )
+    .await;
+
+    let (_, json) = send(
+        router.clone(),
+        "GET",
+        "/api/watchlist",
+        Some(alice_token),
+        None,
+    )
+    .await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["name"], "Black Lotus");
+    let (status, _) = send(
+        router.clone(),
+        "DELETE",
+        "/api/watchlist/7",
+        Some(bob_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Deleting a tenant revokes its token
+    let bob_id = bob["data"]["tenant"]["id"].as_i64().unwrap();
+    let (status, _) = send(
+        router.clone(),
+        "DELETE",
+        &format!("/api/admin/tenants/{bob_id}"),
+        Some("admin"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(router, "GET", "/api/watchlist", Some(bob_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_upcoming_sets_and_new_set_opt_in() {
+    let (mut conn, temp_dir) = create_test_db();
+    let today = chrono::NaiveDate::parse_from_str(&today_date(), "%Y-%m-%d").unwrap();
+    let in_days = |days: u64| {
+        (today + chrono::Days::new(days))
+            .format("%Y-%m-%d")
+            .to_string()
+    };
+    let set = |code: &str, released_at: String| mtg_common::scryfall::ScryfallSet {
+        code: code.to_string(),
+        name: code.to_uppercase(),
+        icon_svg_uri: String::new(),
+        released_at: Some(released_at),
+        set_type: Some("expansion".to_string()),
+        digital: false,
+    };
+    crate::database::replace_upcoming_sets(
+        &mut conn,
+        &[set("soon", in_days(5)), set("later", in_days(200))],
+    )
+    .unwrap();
+    create_tenant(&conn, "alice", &hash_token("alice-token")).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/upcoming-sets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["code"], "soon");
+    assert_eq!(json["data"][0]["days_until"], 5);
+    let (_, json) = get_json(router.clone(), "/api/upcoming-sets?days=365").await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+
+    let token = Some("alice-token");
+    let (status, _) = send(router.clone(), "PUT", "/api/watchlist/new-sets", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (_, json) = send(
+        router.clone(),
+        "GET",
+        "/api/watchlist/new-sets",
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(json["data"], false);
+    let (status, json) = send(
+        router.clone(),
+        "PUT",
+        "/api/watchlist/new-sets",
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"], true);
+    let (_, json) = send(
+        router.clone(),
+        "GET",
+        "/api/watchlist/new-sets",
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(json["data"], true);
+    let (_, json) = send(
+        router.clone(),
+        "DELETE",
+        "/api/watchlist/new-sets",
+        token,
+        None,
+    )
+    .await;
+    assert_eq!(json["data"], false);
+    let (_, json) = send(router, "GET", "/api/watchlist/new-sets", token, None).await;
+    assert_eq!(json["data"], false);
+}
+
+#[tokio::test]
+async fn test_price_alerts_are_per_tenant() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    for (created, trend) in [
+        ("2026-03-01T10:00:00+0100", 4.0),
+        ("2026-03-02T10:00:00+0100", 12.0),
+    ] {
+        let guide = PriceGuide::from_entries(vec![make_test_price_entry(7, Some(trend))], created);
+        insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    }
+    create_tenant(&conn, "alice", &hash_token("alice-token")).unwrap();
+    create_tenant(&conn, "bob", &hash_token("bob-token")).unwrap();
+    let db = Arc::new(Mutex::new(conn));
+    let router = create_router(db.clone(), Arc::new(ImageCache::new(temp_dir.path())));
+    let alice = Some("alice-token");
+    let alert = |id: u64, threshold: f64| {
+        Some(serde_json::json!({ "id_product": id, "condition": "above", "threshold": threshold }))
+    };
+
+    let (status, _) = send(router.clone(), "POST", "/api/alerts", None, alert(7, 10.0)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(
+        router.clone(),
+        "POST",
+        "/api/alerts",
+        alice,
+        alert(99, 10.0),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, json) = send(router.clone(), "POST", "/api/alerts", alice, alert(7, -1.0)).await;
+    assert_eq!(json["success"], false);
+    let damped = serde_json::json!({
+        "id_product": 7, "condition": "above", "threshold": 10.0, "hysteresis_pct": 150.0
+    });
+    let (_, json) = send(router.clone(), "POST", "/api/alerts", alice, Some(damped)).await;
+    assert_eq!(json["success"], false);
+    let (status, json) = send(router.clone(), "POST", "/api/alerts", alice, alert(7, 10.0)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["name"], "Black Lotus");
+    assert_eq!(json["data"]["trend"], 12.0);
+    assert_eq!(json["data"]["hysteresis_pct"], 0.0);
+    assert_eq!(json["data"]["cooldown_days"], 0);
+    let alert_id = json["data"]["id"].as_i64().unwrap();
+
+    crate::alerts::evaluate_alerts(&mut db.lock().unwrap(), "2026-03-02").unwrap();
+
+    // Without `since`, the latest price date's alerts are listed
+    let (status, json) = send(router.clone(), "GET", "/api/alerts/triggered", alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    assert_eq!(json["data"][0]["previous_trend"], 4.0);
+    assert_eq!(json["data"][0]["change_pct"], 200.0);
+    let (_, json) = send(
+        router.clone(),
+        "GET",
+        "/api/alerts/triggered?since=02.03.2026",
+        alice,
+        None,
+    )
+    .await;
+    assert_eq!(json["success"], false);
+    let (_, json) = send(router.clone(), "GET", "/api/alerts", alice, None).await;
+    assert_eq!(json["data"][0]["last_triggered"], "2026-03-02");
+
+    // Bob sees none of it and can't delete it
+    let bob = Some("bob-token");
+    let (_, json) = send(router.clone(), "GET", "/api/alerts/triggered", bob, None).await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 0);
+    let uri = format!("/api/alerts/{alert_id}");
+    let (status, _) = send(router.clone(), "DELETE", &uri, bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(router.clone(), "DELETE", &uri, alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, json) = send(router, "GET", "/api/alerts", alice, None).await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_alerts_export_imports_into_another_tenant() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(7, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    create_tenant(&conn, "alice", &hash_token("alice-token")).unwrap();
+    create_tenant(&conn, "bob", &hash_token("bob-token")).unwrap();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+    let (alice, bob) = (Some("alice-token"), Some("bob-token"));
+    send(router.clone(), "PUT", "/api/watchlist/7", alice, None).await;
+    let alert = serde_json::json!({ "id_product": 7, "condition": "below", "threshold": 2.5 });
+    send(router.clone(), "POST", "/api/alerts", alice, Some(alert)).await;
+
+    let (status, _) = send(router.clone(), "GET", "/api/alerts/export", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, json) = send(router.clone(), "GET", "/api/alerts/export", alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let config = json["data"].clone();
+    assert_eq!(config["tenant"], "alice");
+    assert_eq!(config["watchlist"][0]["name"], "Black Lotus");
+    assert_eq!(config["alerts"][0]["condition"], "below");
+
+    let (status, json) = send(
+        router.clone(),
+        "POST",
+        "/api/alerts/import",
+        bob,
+        Some(config.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["watched"], 1);
+    assert_eq!(json["data"]["alerts_added"], 1);
+    let (_, json) = send(router.clone(), "GET", "/api/alerts", bob, None).await;
+    assert_eq!(json["data"][0]["threshold"], 2.5);
+
+    let mut newer = config;
+    newer["version"] = serde_json::json!(99);
+    let (_, json) = send(router, "POST", "/api/alerts/import", bob, Some(newer)).await;
+    assert_eq!(json["success"], false);
+}
+
+#[test]
+fn test_hash_token_is_hex_sha256() {
+    assert_eq!(
+        hash_token("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[tokio::test]
+async fn test_stock_endpoints() {
+    let (conn, temp_dir) = create_test_db();
+    let db = Arc::new(Mutex::new(conn));
+    let image_cache = Arc::new(ImageCache::new(temp_dir.path()));
+
+    // Not mounted without a stock database
+    let router = create_router(db.clone(), image_cache.clone());
+    let (status, _) = get_json(router, "/api/stock/facets").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let router = create_router_with_options(
+        db,
+        image_cache,
+        WebOptions {
+            stock_db: Some(crate::stock::create_test_stock_db(temp_dir.path())),
+            ..WebOptions::default()
+        },
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/stock?set=LEA&max_price=100").await;
+    assert_eq!(status, StatusCode::OK);
+    let cards = json["data"].as_array().unwrap();
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0]["name"], "Lightning Bolt");
+    assert_eq!(cards[0]["location"], "A1-04");
+    assert_eq!(cards[0]["is_foil"], true);
+
+    let (status, json) = get_json(router.clone(), "/api/stock/facets").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        json["data"]["rarities"],
+        serde_json::json!(["Common", "Rare"])
+    );
+    assert_eq!(json["data"]["sets"][0]["code"], "LEA");
+
+    let (status, _) = get_json(router, "/api/stock?min_price=cheap").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_inventory_value_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(2, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(2, Some(5000.0))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let router = create_router_with_options(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            stock_db: Some(crate::stock::create_test_stock_db(temp_dir.path())),
+            ..WebOptions::default()
+        },
+    );
+
+    let (status, json) = get_json(router.clone(), "/api/inventory/value").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["price"], "trend");
+    assert_eq!(json["data"]["price_date"], "2026-03-01");
+    assert_eq!(json["data"]["total_value"], 5000.0);
+    assert_eq!(json["data"]["unpriced_copies"], 7);
+    assert_eq!(json["data"]["sets"][0]["set_code"], "LEA");
+
+    let (status, json) = get_json(router.clone(), "/api/inventory/value?price=listed").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["total_value"], 9341.5);
+
+    let (status, _) = get_json(router, "/api/inventory/value?price=lowest").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_inventory_valuation_endpoint() {
+    let (mut conn, temp_dir) = create_test_db();
+    let catalog = ProductCatalog::from_entries(vec![make_test_product(2, "Black Lotus")]);
+    upsert_products(&mut conn, &catalog).unwrap();
+    let guide = PriceGuide::from_entries(
+        vec![make_test_price_entry(2, Some(5000.0))],
+        "2026-03-01T10:00:00+0100",
+    );
+    insert_price_history(&mut conn, &guide, &catalog).unwrap();
+    let router = create_router_with_options(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            stock_db: Some(crate::stock::create_test_stock_db(temp_dir.path())),
+            ..WebOptions::default()
+        },
+    );
+
+    let (status, json) = get_json(router, "/api/inventory/valuation").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["price_date"], "2026-03-01");
+    assert_eq!(json["data"]["trend"]["value"], 5000.0);
+    assert_eq!(json["data"]["trend"]["unpriced_copies"], 7);
+    assert_eq!(json["data"]["low"]["value"], 4000.0);
+    assert_eq!(json["data"]["cards"].as_array().unwrap().len(), 4);
+    assert_eq!(json["data"]["cards"][1]["name"], "Black Lotus");
+    assert_eq!(json["data"]["cards"][1]["avg"], 5000.0);
+}
+
+#[tokio::test]
+async fn test_unreadable_stock_db_disables_stock_endpoints() {
+    let (conn, temp_dir) = create_test_db();
+    let router = create_router_with_options(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+        WebOptions {
+            stock_db: Some(temp_dir.path().join("missing.db")),
+            ..WebOptions::default()
+        },
+    );
+
+    let (status, _) = get_json(router.clone(), "/api/stock").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(router, "/api/health").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_revenue_endpoint_reads_the_order_board() {
+    let (conn, temp_dir) = create_test_db();
+    let db = Arc::new(Mutex::new(conn));
+    let image_cache = Arc::new(ImageCache::new(temp_dir.path()));
+    let (status, _) = get_json(
+        create_router(db.clone(), image_cache.clone()),
+        "/api/revenue",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let board = temp_dir.path().join("order_board.json");
+    let router = create_router_with_options(
+        db,
+        image_cache,
+        WebOptions {
+            order_board: Some(board.clone()),
+            ..WebOptions::default()
+        },
+    );
+
+    // A board the accounting app hasn't written yet has no sales
+    let (status, json) = get_json(router.clone(), "/api/revenue").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["days"], 30);
+    assert_eq!(json["data"]["current"]["orders"], 0);
+
+    let today = today_date();
+    std::fs::write(
+        &board,
+        format!(
+            r#"{{"entries": {{
+                "1": {{"order_id": "1", "total_value": 20.0, "date_of_purchase": "{today} 10:00:00"}},
+                "2": {{"order_id": "2", "total_value": 5.5, "date_of_purchase": "{today} 11:00:00"}},
+                "3": {{"order_id": "3", "total_value": 99.0, "date_of_purchase": "2000-01-01 11:00:00"}}
+            }}}}"#
+        ),
+    )
+    .unwrap();
+    let (status, json) = get_json(router.clone(), "/api/revenue?days=7").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["days"], 7);
+    assert_eq!(json["data"]["current"]["to"], today.as_str());
+    assert_eq!(json["data"]["current"]["revenue"], 25.5);
+    assert_eq!(json["data"]["current"]["orders"], 2);
+    assert_eq!(json["data"]["current"]["average_order_value"], 12.75);
+    assert_eq!(json["data"]["previous"]["orders"], 0);
+    assert!(json["data"]["revenue_change_pct"].is_null());
+
+    std::fs::write(&board, "{").unwrap();
+    let (status, _) = get_json(router, "/api/revenue").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_openapi_document_matches_routes() {
+    let (conn, temp_dir) = create_test_db();
+    let router = create_router(
+        Arc::new(Mutex::new(conn)),
+        Arc::new(ImageCache::new(temp_dir.path())),
+    );
+
+    let (status, spec) = get_json(router.clone(), "/api/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(spec["openapi"], "3.1.0");
+    let paths = spec["paths"].as_object().unwrap();
+    for (path, method) in [
+        ("/api/prices/{id}", "get"),
+        ("/api/price-snapshots", "post"),
+        ("/api/admin/tenants/{id}", "delete"),
+        ("/api/watchlist/new-sets", "put"),
+        ("/api/alerts/triggered", "get"),
+        ("/api/alerts/export", "get"),
+        ("/api/alerts/import", "post"),
+        ("/api/inventory/value", "get"),
+        ("/api/inventory/valuation", "get"),
+        ("/api/revenue", "get"),
+    ] {
+        assert!(paths[path].get(method).is_some(), "{method} {path}");
+    }
+    assert_eq!(
+        paths["/api/watchlist"]["get"]["security"][0]["tenant_token"],
+        serde_json::json!([])
+    );
+    let schemas = &spec["components"]["schemas"];
+    assert!(schemas["ApiResponse_PriceData"].is_object());
+    assert!(schemas["AlertRearm"].is_object());
+    assert!(spec["components"]["securitySchemes"]["admin_token"].is_object());
+
+    // Every documented public GET without path parameters is routed
+    for (path, item) in paths {
+        let public = ![
+            "/api/admin",
+            "/api/watchlist",
+            "/api/alerts",
+            "/api/stock",
+            "/api/inventory",
+            "/api/revenue",
+        ]
+        .iter()
+        .any(|prefix| path.starts_with(prefix));
+        if item.get("get").is_none() || path.contains('{') || !public {
+            continue;
+        }
+        let uri = match path.as_str() {
+            "/api/search" => "/api/search?q=x",
+            "/api/price-history" => "/api/price-history?name=%20",
+            _ => path,
+        };
+        let (status, _) = get_json(router.clone(), uri).await;
+        assert!(
+            status != StatusCode::NOT_FOUND && status != StatusCode::METHOD_NOT_ALLOWED,
+            "{path}: {status}"
+        );
+    }
+}
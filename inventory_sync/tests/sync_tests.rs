@@ -0,0 +1,633 @@
+//! Integration tests for the Cardmarket sync.
+//!
+//! Canned product catalog, expansion and price guide files from
+//! `tests/fixtures/cardmarket` are served from a local HTTP server, and a
+//! full sync runs against a fresh database in a temp directory. Dated
+//! archives for the backfill are generated per request under `/archive`, and
+//! a Scryfall set list with dates relative to today under `/scryfall/sets`.
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use inventory_sync::alerts::{add_alert, get_triggered_alerts, AlertCondition, AlertRearm};
+use inventory_sync::database::{
+    create_tenant, mark_sync_step_done, set_watch_new_sets, today_date, SyncStep,
+};
+use inventory_sync::download::{DownloadLimits, DownloadWindow};
+use inventory_sync::init_schema;
+use inventory_sync::sync::{run_sync, sync_cardmarket, CardmarketSources, SyncOptions, SyncReport};
+use mtg_common::Category;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+use tower_http::services::ServeDir;
+
+/// Price date of `price_guide_1.json`
+const PRICE_DATE: &str = "2026-02-01";
+
+fn fixtures_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cardmarket")
+}
+
+/// Dated price guide archives: response per date plus a request counter
+#[derive(Clone, Default)]
+struct Archive {
+    responses: Arc<Mutex<HashMap<String, (StatusCode, String)>>>,
+    requests: Arc<AtomicUsize>,
+}
+
+impl Archive {
+    /// Serves a one-product guide created on `created` for `date`
+    fn publish(&self, date: &str, created: &str, trend: f64) {
+        let body = serde_json::json!({
+            "version": 1,
+            "createdAt": format!("{created}T02:42:53+0100"),
+            "priceGuides": [{ "idProduct": 1, "idCategory": 1, "trend": trend }]
+        });
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(date.to_string(), (StatusCode::OK, body.to_string()));
+    }
+
+    fn fail(&self, date: &str, status: StatusCode) {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(date.to_string(), (status, String::new()));
+    }
+
+    fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+async fn archive_handler(
+    State(archive): State<Archive>,
+    Path(date): Path<String>,
+) -> (StatusCode, String) {
+    archive.requests.fetch_add(1, Ordering::SeqCst);
+    archive
+        .responses
+        .lock()
+        .unwrap()
+        .get(&date)
+        .cloned()
+        .unwrap_or((StatusCode::NOT_FOUND, String::new()))
+}
+
+/// `days` before today's Cardmarket date, as YYYY-MM-DD
+fn days_ago(days: u64) -> String {
+    let today = chrono::NaiveDate::parse_from_str(&today_date(), "%Y-%m-%d").unwrap();
+    (today - chrono::Days::new(days))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// `days` after today's Cardmarket date, as YYYY-MM-DD
+fn days_ahead(days: u64) -> String {
+    let today = chrono::NaiveDate::parse_from_str(&today_date(), "%Y-%m-%d").unwrap();
+    (today + chrono::Days::new(days))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Scryfall set list: Alpha re-announced for next week, plus a released set
+/// and an Arena-only one
+async fn scryfall_sets_handler() -> axum::Json<serde_json::Value> {
+    let set = |code: &str, name: &str, released_at: String, digital: bool| {
+        serde_json::json!({
+            "object": "set", "code": code, "name": name, "released_at": released_at,
+            "set_type": "expansion", "digital": digital,
+            "icon_svg_uri": format!("https://svgs.scryfall.io/sets/{code}.svg")
+        })
+    };
+    axum::Json(serde_json::json!({
+        "object": "list",
+        "has_more": false,
+        "data": [
+            set("lea", "Limited Edition Alpha", days_ahead(7), false),
+            set("old", "Old Set", days_ago(30), false),
+            set("yxy", "Alchemy: Soon", days_ahead(3), true),
+        ]
+    }))
+}
+
+/// Serves the fixture directory; returns its base URL
+async fn serve_fixtures() -> String {
+    serve_fixtures_with_archive(Archive::default()).await
+}
+
+/// Serves the fixture directory and `archive` under `/archive/{date}`
+async fn serve_fixtures_with_archive(archive: Archive) -> String {
+    let app = axum::Router::new()
+        .route("/archive/{date}", axum::routing::get(archive_handler))
+        .route("/scryfall/sets", axum::routing::get(scryfall_sets_handler))
+        .with_state(archive)
+        .fallback_service(ServeDir::new(fixtures_path()));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+fn sources(base_url: &str) -> CardmarketSources {
+    CardmarketSources {
+        singles_url: format!("{base_url}/products_singles_1.json"),
+        non_singles_url: format!("{base_url}/products_nonsingles_1.json"),
+        expansions_url: format!("{base_url}/expansions_1.json"),
+        price_guide_url: format!("{base_url}/price_guide_1.json"),
+        price_guide_archive_url: None,
+        scryfall_api_url: None,
+    }
+}
+
+struct TestDb {
+    db: Arc<Mutex<Connection>>,
+    options: SyncOptions,
+    _dir: TempDir,
+}
+
+impl TestDb {
+    fn new(sources: CardmarketSources) -> Self {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("inventory.db");
+        let conn = Connection::open(&db_path).unwrap();
+        init_schema(&conn).unwrap();
+        Self {
+            db: Arc::new(Mutex::new(conn)),
+            options: SyncOptions {
+                categories: Vec::new(),
+                tcgplayer_min_trend: None,
+                raw_price_guide: db_path.with_extension("price_guide.json"),
+                sources,
+                backfill_days: 3,
+                downloads: DownloadLimits::default(),
+                backup: None,
+                force: false,
+                webhook_url: None,
+            },
+            _dir: dir,
+        }
+    }
+
+    async fn sync(&self) -> SyncReport {
+        sync_cardmarket(&self.db, &self.options).await
+    }
+
+    /// Price dates in the database, oldest first
+    fn price_dates(&self) -> Vec<String> {
+        self.db
+            .lock()
+            .unwrap()
+            .prepare("SELECT DISTINCT price_date FROM price_history ORDER BY price_date")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    fn count(&self, sql: &str) -> i64 {
+        self.db
+            .lock()
+            .unwrap()
+            .query_row(sql, [], |row| row.get(0))
+            .unwrap()
+    }
+}
+
+#[tokio::test]
+async fn full_sync_stores_catalog_expansions_and_prices() {
+    let base_url = serve_fixtures().await;
+    let test_db = TestDb::new(sources(&base_url));
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    let conn = test_db.db.lock().unwrap();
+    let (name, code): (String, String) = conn
+        .query_row(
+            "SELECT name, code FROM expansions WHERE id_expansion = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((name.as_str(), code.as_str()), ("Alpha", "LEA"));
+
+    // Product 999 is not in the catalog and is dropped
+    let prices: Vec<(u64, String, Option<f64>)> = conn
+        .prepare("SELECT id_product, price_date, trend FROM price_history ORDER BY id_product")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap();
+    assert_eq!(
+        prices,
+        vec![
+            (1, PRICE_DATE.to_string(), Some(10000.0)),
+            (2, PRICE_DATE.to_string(), Some(1100.0)),
+            (3, PRICE_DATE.to_string(), Some(16000.0)),
+        ]
+    );
+    drop(conn);
+
+    assert_eq!(
+        test_db.count("SELECT COUNT(*) FROM price_import_progress"),
+        0
+    );
+    assert!(
+        !test_db.options.raw_price_guide.exists(),
+        "raw download should be removed after a completed import"
+    );
+}
+
+#[tokio::test]
+async fn second_sync_does_not_duplicate_prices() {
+    let base_url = serve_fixtures().await;
+    let test_db = TestDb::new(sources(&base_url));
+
+    test_db.sync().await;
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 3);
+}
+
+#[tokio::test]
+async fn throttled_sync_outside_window_is_postponed() {
+    let base_url = serve_fixtures().await;
+    let mut test_db = TestDb::new(sources(&base_url));
+    let now = mtg_common::dates::market_time(chrono::Utc::now());
+    let closed = DownloadWindow {
+        start: now + chrono::Duration::hours(1),
+        end: now + chrono::Duration::hours(2),
+    };
+    test_db.options.downloads = DownloadLimits {
+        max_kbit_per_sec: Some(100_000),
+        window: Some(closed),
+    };
+
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 0);
+
+    // Inside the window the throttled downloads complete as usual
+    test_db.options.downloads.window = Some(DownloadWindow {
+        start: closed.end,
+        end: closed.start,
+    });
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    assert_eq!(test_db.price_dates(), vec![PRICE_DATE.to_string()]);
+}
+
+#[tokio::test]
+async fn category_filter_limits_stored_prices() {
+    let base_url = serve_fixtures().await;
+    let mut test_db = TestDb::new(sources(&base_url));
+    test_db.options.categories = vec![Category::Single];
+
+    test_db.sync().await;
+
+    // The whole catalog is kept, only prices are filtered
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    assert_eq!(
+        test_db.count("SELECT COUNT(*) FROM price_history WHERE id_product IN (1, 2)"),
+        2
+    );
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 2);
+}
+
+#[tokio::test]
+async fn missing_catalog_stops_the_sync() {
+    let base_url = serve_fixtures().await;
+    let mut sources = sources(&base_url);
+    sources.non_singles_url = format!("{base_url}/missing.json");
+    let test_db = TestDb::new(sources);
+
+    let report = test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 0);
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 0);
+    assert_eq!(report.price_date, None);
+    assert_eq!(report.errors.len(), 1);
+    assert!(report.errors[0].starts_with("Failed to fetch product catalog"));
+}
+
+#[tokio::test]
+async fn expansion_failure_does_not_stop_the_sync() {
+    let base_url = serve_fixtures().await;
+    let mut sources = sources(&base_url);
+    sources.expansions_url = format!("{base_url}/missing.json");
+    let test_db = TestDb::new(sources);
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM expansions"), 0);
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 3);
+}
+
+#[tokio::test]
+async fn new_products_of_upcoming_sets_are_watched_by_opted_in_tenants() {
+    let base_url = serve_fixtures().await;
+    let sources = CardmarketSources {
+        scryfall_api_url: Some(format!("{base_url}/scryfall")),
+        ..sources(&base_url)
+    };
+    let test_db = TestDb::new(sources);
+    let (watcher, other) = {
+        let conn = test_db.db.lock().unwrap();
+        let watcher = create_tenant(&conn, "watcher", "hash-w").unwrap();
+        let other = create_tenant(&conn, "other", "hash-o").unwrap();
+        assert!(set_watch_new_sets(&conn, watcher.id, true).unwrap());
+        (watcher, other)
+    };
+
+    test_db.sync().await;
+
+    let conn = test_db.db.lock().unwrap();
+    let sets = inventory_sync::database::get_upcoming_sets(&conn, &today_date(), 30).unwrap();
+    assert_eq!(sets.len(), 1, "released and Arena-only sets are dropped");
+    assert_eq!(sets[0].code, "lea");
+    assert_eq!(sets[0].days_until, 7);
+    assert_eq!(sets[0].id_expansion, Some(1));
+    assert_eq!(sets[0].products, 3);
+    let watched = |tenant_id: i64| -> i64 {
+        conn.query_row(
+            "SELECT COUNT(*) FROM watchlist WHERE tenant_id = ?1",
+            [tenant_id],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    assert_eq!(watched(watcher.id), 3);
+    assert_eq!(watched(other.id), 0);
+}
+
+#[tokio::test]
+async fn release_calendar_failure_does_not_stop_the_sync() {
+    let base_url = serve_fixtures().await;
+    let sources = CardmarketSources {
+        scryfall_api_url: Some(format!("{base_url}/missing")),
+        ..sources(&base_url)
+    };
+    let test_db = TestDb::new(sources);
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM upcoming_sets"), 0);
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 3);
+}
+
+#[tokio::test]
+async fn malformed_price_guide_stores_no_prices() {
+    let base_url = serve_fixtures().await;
+    let mut sources = sources(&base_url);
+    sources.price_guide_url = format!("{base_url}/malformed.json");
+    let test_db = TestDb::new(sources);
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 0);
+}
+
+#[tokio::test]
+async fn restarted_sync_reuses_todays_catalog() {
+    let base_url = serve_fixtures().await;
+    let mut sources = sources(&base_url);
+    sources.price_guide_url = format!("{base_url}/malformed.json");
+    let mut test_db = TestDb::new(sources);
+
+    // The catalog step completes, the price import fails
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 0);
+    assert_eq!(
+        test_db.count("SELECT COUNT(*) FROM sync_state WHERE step = 'catalog'"),
+        1
+    );
+
+    // The next run the same day imports prices without fetching the catalog
+    test_db.options.sources.singles_url = format!("{base_url}/missing.json");
+    test_db.options.sources.price_guide_url = format!("{base_url}/price_guide_1.json");
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 3);
+    // The fixture guide is not today's, so the prices step stays open
+    assert_eq!(
+        test_db.count("SELECT COUNT(*) FROM sync_state WHERE step = 'prices'"),
+        0
+    );
+}
+
+#[tokio::test]
+async fn forced_sync_runs_even_when_today_is_done() {
+    let base_url = serve_fixtures().await;
+    let mut test_db = TestDb::new(sources(&base_url));
+    mark_sync_step_done(&test_db.db.lock().unwrap(), &today_date(), SyncStep::Prices).unwrap();
+
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 0);
+
+    test_db.options.force = true;
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+    assert_eq!(test_db.price_dates(), vec![PRICE_DATE.to_string()]);
+
+    // Stored prices are skipped, not duplicated
+    test_db.sync().await;
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM price_history"), 3);
+}
+
+#[tokio::test]
+async fn forced_sync_ignores_the_download_window() {
+    let base_url = serve_fixtures().await;
+    let mut test_db = TestDb::new(sources(&base_url));
+    let now = mtg_common::dates::market_time(chrono::Utc::now());
+    test_db.options.downloads.window = Some(DownloadWindow {
+        start: now + chrono::Duration::hours(1),
+        end: now + chrono::Duration::hours(2),
+    });
+    test_db.options.force = true;
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 3);
+}
+
+#[tokio::test]
+async fn daily_import_fires_price_alerts() {
+    let base_url = serve_fixtures().await;
+    let test_db = TestDb::new(sources(&base_url));
+    {
+        let conn = test_db.db.lock().unwrap();
+        let tenant = create_tenant(&conn, "alice", "hash").unwrap();
+        add_alert(
+            &conn,
+            tenant.id,
+            1,
+            AlertCondition::Above,
+            5000.0,
+            AlertRearm::default(),
+        )
+        .unwrap();
+        add_alert(
+            &conn,
+            tenant.id,
+            2,
+            AlertCondition::Above,
+            5000.0,
+            AlertRearm::default(),
+        )
+        .unwrap();
+    }
+
+    test_db.sync().await;
+
+    let fired = get_triggered_alerts(&test_db.db.lock().unwrap(), 1, PRICE_DATE).unwrap();
+    assert_eq!(fired.len(), 1);
+    assert_eq!((fired[0].id_product, fired[0].trend), (1, 10000.0));
+}
+
+/// Serves a webhook receiver; returns its URL and the bodies posted to it
+async fn serve_webhook() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let app = axum::Router::new()
+        .route(
+            "/hook",
+            axum::routing::post(
+                |State(received): State<Arc<Mutex<Vec<serde_json::Value>>>>,
+                 axum::Json(body): axum::Json<serde_json::Value>| async move {
+                    received.lock().unwrap().push(body);
+                    StatusCode::NO_CONTENT
+                },
+            ),
+        )
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{addr}/hook"), received)
+}
+
+#[tokio::test]
+async fn webhook_gets_a_summary_of_runs_that_did_something() {
+    let base_url = serve_fixtures().await;
+    let (webhook_url, received) = serve_webhook().await;
+    let mut test_db = TestDb::new(sources(&base_url));
+    test_db.options.webhook_url = Some(webhook_url);
+
+    let report = run_sync(&test_db.db, &test_db.options).await;
+    assert_eq!(report.price_date.as_deref(), Some(PRICE_DATE));
+    assert_eq!(report.inserted, 3);
+    assert!(report.errors.is_empty());
+
+    let bodies = received.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(bodies[0]["event"], "price_sync");
+    assert_eq!(bodies[0]["inserted"], 3);
+    assert_eq!(bodies[0]["price_date"], PRICE_DATE);
+    assert_eq!(bodies[0]["text"], bodies[0]["content"]);
+
+    // Today's prices are in: the next run does nothing and stays quiet
+    let report = run_sync(&test_db.db, &test_db.options).await;
+    assert!(report.is_idle());
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn unreachable_server_stores_nothing() {
+    // Bind and drop a listener to get a port nothing listens on
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let test_db = TestDb::new(sources(&base_url));
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.count("SELECT COUNT(*) FROM products"), 0);
+}
+
+fn archive_sources(base_url: &str) -> CardmarketSources {
+    CardmarketSources {
+        price_guide_archive_url: Some(format!("{base_url}/archive/{{date}}")),
+        ..sources(base_url)
+    }
+}
+
+#[tokio::test]
+async fn backfill_imports_missing_archive_dates_in_order() {
+    let archive = Archive::default();
+    archive.publish(&days_ago(3), &days_ago(3), 3.0);
+    archive.publish(&days_ago(1), &days_ago(1), 1.0);
+    let base_url = serve_fixtures_with_archive(archive.clone()).await;
+    let test_db = TestDb::new(archive_sources(&base_url));
+
+    test_db.sync().await;
+
+    // Two days ago has no archive and stays missing
+    let mut expected = vec![PRICE_DATE.to_string(), days_ago(3), days_ago(1)];
+    expected.sort();
+    assert_eq!(test_db.price_dates(), expected);
+    assert_eq!(archive.requests(), 3);
+    let overridden = test_db.count("SELECT COUNT(*) FROM sync_runs WHERE price_date_overridden");
+    assert_eq!(overridden, 0);
+}
+
+#[tokio::test]
+async fn backfill_only_downloads_dates_still_missing() {
+    let archive = Archive::default();
+    for days in 1..=3 {
+        archive.publish(&days_ago(days), &days_ago(days), days as f64);
+    }
+    let base_url = serve_fixtures_with_archive(archive.clone()).await;
+    let test_db = TestDb::new(archive_sources(&base_url));
+
+    test_db.sync().await;
+    assert_eq!(archive.requests(), 3);
+    test_db.sync().await;
+
+    assert_eq!(archive.requests(), 3);
+    assert_eq!(test_db.price_dates().len(), 4);
+}
+
+#[tokio::test]
+async fn backfill_skips_archive_of_another_day() {
+    let archive = Archive::default();
+    archive.publish(&days_ago(2), &days_ago(5), 2.0);
+    let base_url = serve_fixtures_with_archive(archive).await;
+    let test_db = TestDb::new(archive_sources(&base_url));
+
+    test_db.sync().await;
+
+    assert_eq!(test_db.price_dates(), vec![PRICE_DATE.to_string()]);
+}
+
+#[tokio::test]
+async fn backfill_stops_on_server_error_but_daily_sync_continues() {
+    let archive = Archive::default();
+    archive.fail(&days_ago(3), StatusCode::INTERNAL_SERVER_ERROR);
+    archive.publish(&days_ago(2), &days_ago(2), 2.0);
+    let base_url = serve_fixtures_with_archive(archive.clone()).await;
+    let test_db = TestDb::new(archive_sources(&base_url));
+
+    test_db.sync().await;
+
+    assert_eq!(archive.requests(), 1);
+    assert_eq!(test_db.price_dates(), vec![PRICE_DATE.to_string()]);
+}
+
+#[tokio::test]
+async fn backfill_is_off_without_archive_url() {
+    let archive = Archive::default();
+    archive.publish(&days_ago(1), &days_ago(1), 1.0);
+    let base_url = serve_fixtures_with_archive(archive.clone()).await;
+    let test_db = TestDb::new(sources(&base_url));
+
+    test_db.sync().await;
+
+    assert_eq!(archive.requests(), 0);
+    assert_eq!(test_db.price_dates(), vec![PRICE_DATE.to_string()]);
+}
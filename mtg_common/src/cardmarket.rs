@@ -1,6 +1,8 @@
 use crate::error::{MtgError, MtgResult};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use std::time::Duration;
 
 const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
@@ -33,6 +35,154 @@ pub struct PriceGuideEntry {
     pub avg30_foil: Option<f64>,
 }
 
+/// A Cardmarket product category.
+///
+/// The product catalog files list every product with an `idCategory` and a
+/// `categoryName`; Magic singles are category 1 ("Magic Single"), everything
+/// else (boosters, displays, sealed products, …) is kept by its ID.
+/// Serializes as the plain ID, so config files and stored filters stay
+/// `categories = [1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "u64", into = "u64")]
+pub enum Category {
+    Single,
+    Other(u64),
+}
+
+impl Category {
+    /// Catalog ID of Magic singles.
+    pub const SINGLE_ID: u64 = 1;
+
+    pub fn from_id(id: u64) -> Self {
+        match id {
+            Self::SINGLE_ID => Self::Single,
+            id => Self::Other(id),
+        }
+    }
+
+    pub fn id(self) -> u64 {
+        match self {
+            Self::Single => Self::SINGLE_ID,
+            Self::Other(id) => id,
+        }
+    }
+
+    pub fn is_single(self) -> bool {
+        self == Self::Single
+    }
+
+    /// Name for logs and the UI; singles use the catalog's category name.
+    pub fn display_name(self) -> String {
+        match self {
+            Self::Single => "Magic Single".to_string(),
+            Self::Other(id) => format!("Category {id}"),
+        }
+    }
+}
+
+impl From<u64> for Category {
+    fn from(id: u64) -> Self {
+        Self::from_id(id)
+    }
+}
+
+impl From<Category> for u64 {
+    fn from(category: Category) -> Self {
+        category.id()
+    }
+}
+
+/// Shows the ID, the form [`FromStr`] and the stored filters use.
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// Parses a category ID or `singles` (as on the command line and in the
+/// web API's `category` filter).
+impl FromStr for Category {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("singles") || s.eq_ignore_ascii_case("single") {
+            return Ok(Self::Single);
+        }
+        s.parse()
+            .map(Self::from_id)
+            .map_err(|_| format!("invalid category '{s}': expected an ID or 'singles'"))
+    }
+}
+
+/// Which finishes a price guide entry has prices for.
+///
+/// Foil-only products (many promos, etched and textured printings) come with
+/// empty non-foil columns, and non-foil-only products with empty foil
+/// columns. An empty column means "no market for that finish", never a price
+/// of 0 € — consumers must not fall back to the other finish or to zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PriceFinishes {
+    NonFoil,
+    Foil,
+    Both,
+}
+
+/// A price column's value if it is an actual price. Cardmarket writes both
+/// `null` and `0` for "no data", so non-positive values count as missing.
+pub fn priced(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| *v > 0.0)
+}
+
+impl PriceGuideEntry {
+    pub fn category(&self) -> Category {
+        Category::from_id(self.id_category)
+    }
+
+    /// Whether any non-foil column carries a price.
+    pub fn has_non_foil_prices(&self) -> bool {
+        [
+            self.avg, self.low, self.trend, self.avg1, self.avg7, self.avg30,
+        ]
+        .into_iter()
+        .any(|v| priced(v).is_some())
+    }
+
+    /// Whether any foil column carries a price.
+    pub fn has_foil_prices(&self) -> bool {
+        [
+            self.avg_foil,
+            self.low_foil,
+            self.trend_foil,
+            self.avg1_foil,
+            self.avg7_foil,
+            self.avg30_foil,
+        ]
+        .into_iter()
+        .any(|v| priced(v).is_some())
+    }
+
+    /// The finishes this entry has prices for, `None` if it has none at all.
+    pub fn finishes(&self) -> Option<PriceFinishes> {
+        match (self.has_non_foil_prices(), self.has_foil_prices()) {
+            (true, true) => Some(PriceFinishes::Both),
+            (true, false) => Some(PriceFinishes::NonFoil),
+            (false, true) => Some(PriceFinishes::Foil),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Number of price guide entries per [`PriceFinishes`] value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FinishCounts {
+    pub non_foil_only: usize,
+    pub foil_only: usize,
+    pub both: usize,
+    /// Entries without any price
+    pub unpriced: usize,
+}
+
 /// Full price guide file structure from Cardmarket's CDN.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,43 +197,59 @@ pub struct PriceGuideFile {
 pub struct PriceGuide {
     entries: HashMap<u64, PriceGuideEntry>,
     created_at: String,
+    duplicates: usize,
 }
 
 impl PriceGuide {
-    fn from_file_struct(file: PriceGuideFile) -> Self {
-        let created_at = file.created_at;
-        let entries = file
-            .price_guides
-            .into_iter()
-            .map(|e| (e.id_product, e))
-            .collect();
+    /// Indexes entries by product ID. A product listed more than once keeps
+    /// its last entry; the dropped rows are counted and logged.
+    fn index(entries: Vec<PriceGuideEntry>, created_at: String) -> Self {
+        let total = entries.len();
+        let entries: HashMap<u64, PriceGuideEntry> =
+            entries.into_iter().map(|e| (e.id_product, e)).collect();
+        let duplicates = total - entries.len();
+        if duplicates > 0 {
+            log::warn!(
+                "Price guide contains {} duplicate product entries, keeping the last of each",
+                duplicates
+            );
+        }
         Self {
             entries,
             created_at,
+            duplicates,
         }
     }
 
+    fn from_file_struct(file: PriceGuideFile) -> Self {
+        Self::index(file.price_guides, file.created_at)
+    }
+
     /// Create a PriceGuide directly from entries (for tests and simulations).
     pub fn from_entries(entries: Vec<PriceGuideEntry>, created_at: &str) -> Self {
-        let entries = entries.into_iter().map(|e| (e.id_product, e)).collect();
-        Self {
-            entries,
-            created_at: created_at.to_string(),
-        }
+        Self::index(entries, created_at.to_string())
     }
 
-    /// Load price guide from a JSON file on disk.
+    /// Load price guide from a JSON file on disk, which may be gzipped or
+    /// zipped. The JSON is parsed while it's decompressed.
     pub fn load(path: &str) -> MtgResult<Self> {
         log::info!("Loading price guide from: {}", path);
 
-        let content = std::fs::read_to_string(path)?;
-        let file: PriceGuideFile = serde_json::from_str(&content)?;
-        let guide = Self::from_file_struct(file);
+        let guide = crate::compressed::with_reader(path, |reader| {
+            let file: PriceGuideFile = serde_json::from_reader(reader)?;
+            Ok::<_, MtgError>(Self::from_file_struct(file))
+        })?;
 
         log::info!("Loaded {} price entries", guide.len());
         Ok(guide)
     }
 
+    /// Parse a price guide from the raw JSON as served by Cardmarket's CDN.
+    pub fn from_json(content: &str) -> MtgResult<Self> {
+        let file: PriceGuideFile = serde_json::from_str(content)?;
+        Ok(Self::from_file_struct(file))
+    }
+
     /// Fetch price guide from Cardmarket's CDN (async).
     pub async fn fetch() -> MtgResult<Self> {
         Self::fetch_from(crate::PRICE_GUIDE_URL).await
@@ -91,10 +257,27 @@ impl PriceGuide {
 
     /// Fetches price guide from the given URL (async, for testing with mock servers).
     pub async fn fetch_from(url: &str) -> MtgResult<Self> {
+        let guide = Self::from_json(&Self::fetch_raw_from(url).await?)?;
+
+        log::info!(
+            "Fetched {} price entries (created: {})",
+            guide.len(),
+            guide.created_at()
+        );
+        Ok(guide)
+    }
+
+    /// Download the raw price guide JSON without parsing it, so callers can
+    /// keep a copy on disk (async).
+    pub async fn fetch_raw() -> MtgResult<String> {
+        Self::fetch_raw_from(crate::PRICE_GUIDE_URL).await
+    }
+
+    /// Downloads the raw price guide JSON from the given URL (async).
+    pub async fn fetch_raw_from(url: &str) -> MtgResult<String> {
         log::info!("Fetching price guide from: {}", url);
 
-        let response = reqwest::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(url)
             .header("User-Agent", crate::USER_AGENT)
@@ -105,15 +288,7 @@ impl PriceGuide {
             return Err(MtgError::HttpStatus(response.status()));
         }
 
-        let file: PriceGuideFile = response.json().await?;
-        let guide = Self::from_file_struct(file);
-
-        log::info!(
-            "Fetched {} price entries (created: {})",
-            guide.len(),
-            guide.created_at()
-        );
-        Ok(guide)
+        Ok(response.text().await?)
     }
 
     /// Fetch price guide from Cardmarket's CDN (blocking).
@@ -127,8 +302,7 @@ impl PriceGuide {
     pub fn fetch_from_blocking(url: &str) -> MtgResult<Self> {
         log::info!("Fetching price guide from: {}", url);
 
-        let response = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(url)
             .header("User-Agent", crate::USER_AGENT)
@@ -164,6 +338,11 @@ impl PriceGuide {
         self.entries.is_empty()
     }
 
+    /// Number of duplicate product rows dropped while loading.
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
     /// Get the creation timestamp from Cardmarket.
     pub fn created_at(&self) -> &str {
         &self.created_at
@@ -173,6 +352,21 @@ impl PriceGuide {
     pub fn iter(&self) -> impl Iterator<Item = &PriceGuideEntry> {
         self.entries.values()
     }
+
+    /// How many entries are priced for non-foil only, foil only, both or
+    /// neither.
+    pub fn finish_counts(&self) -> FinishCounts {
+        let mut counts = FinishCounts::default();
+        for entry in self.entries.values() {
+            match entry.finishes() {
+                Some(PriceFinishes::NonFoil) => counts.non_foil_only += 1,
+                Some(PriceFinishes::Foil) => counts.foil_only += 1,
+                Some(PriceFinishes::Both) => counts.both += 1,
+                None => counts.unpriced += 1,
+            }
+        }
+        counts
+    }
 }
 
 #[cfg(test)]
@@ -55,4 +55,135 @@ fn price_guide_file_deserializes() {
     assert_eq!(file.created_at, "2026-03-01T10:00:00+0100");
     assert_eq!(file.price_guides.len(), 1);
     assert_eq!(file.price_guides[0].id_product, 1);
+
+    let guide = PriceGuide::from_json(json).unwrap();
+    assert_eq!(guide.created_at(), "2026-03-01T10:00:00+0100");
+    assert_eq!(guide.get(1).unwrap().trend, Some(9.5));
+    assert!(PriceGuide::from_json("{\"version\": 1}").is_err());
+}
+
+#[test]
+fn duplicate_products_keep_last_entry_and_are_counted() {
+    let entry = |id: u64, trend: f64| PriceGuideEntry {
+        id_product: id,
+        id_category: 1,
+        avg: None,
+        low: None,
+        trend: Some(trend),
+        avg1: None,
+        avg7: None,
+        avg30: None,
+        avg_foil: None,
+        low_foil: None,
+        trend_foil: None,
+        avg1_foil: None,
+        avg7_foil: None,
+        avg30_foil: None,
+    };
+    let guide = PriceGuide::from_entries(
+        vec![entry(1, 1.0), entry(2, 2.0), entry(1, 1.5), entry(1, 1.75)],
+        "2026-03-01T10:00:00+0100",
+    );
+    assert_eq!(guide.len(), 2);
+    assert_eq!(guide.duplicates(), 2);
+    assert_eq!(guide.get(1).unwrap().trend, Some(1.75));
+
+    let unique = PriceGuide::from_entries(vec![entry(1, 1.0)], "2026-03-01T10:00:00+0100");
+    assert_eq!(unique.duplicates(), 0);
+}
+
+fn finish_entry(id: u64, trend: Option<f64>, trend_foil: Option<f64>) -> PriceGuideEntry {
+    PriceGuideEntry {
+        id_product: id,
+        id_category: 1,
+        avg: None,
+        low: None,
+        trend,
+        avg1: None,
+        avg7: None,
+        avg30: None,
+        avg_foil: None,
+        low_foil: None,
+        trend_foil,
+        avg1_foil: None,
+        avg7_foil: None,
+        avg30_foil: None,
+    }
+}
+
+#[test]
+fn finishes_distinguish_non_foil_foil_and_both() {
+    assert_eq!(
+        finish_entry(1, Some(1.0), None).finishes(),
+        Some(PriceFinishes::NonFoil)
+    );
+    assert_eq!(
+        finish_entry(2, None, Some(3.0)).finishes(),
+        Some(PriceFinishes::Foil)
+    );
+    assert_eq!(
+        finish_entry(3, Some(1.0), Some(3.0)).finishes(),
+        Some(PriceFinishes::Both)
+    );
+    assert_eq!(finish_entry(4, None, None).finishes(), None);
+}
+
+#[test]
+fn zero_prices_count_as_missing_finish() {
+    // Foil-only product as some guide files write it: zeros, not nulls
+    let entry = finish_entry(1, Some(0.0), Some(12.5));
+    assert!(!entry.has_non_foil_prices());
+    assert_eq!(entry.finishes(), Some(PriceFinishes::Foil));
+    assert_eq!(priced(Some(0.0)), None);
+    assert_eq!(priced(Some(-1.0)), None);
+    assert_eq!(priced(Some(0.02)), Some(0.02));
+}
+
+#[test]
+fn finish_counts_summarize_guide() {
+    let guide = PriceGuide::from_entries(
+        vec![
+            finish_entry(1, Some(1.0), None),
+            finish_entry(2, Some(1.0), Some(2.0)),
+            finish_entry(3, None, Some(2.0)),
+            finish_entry(4, Some(0.0), Some(2.0)),
+            finish_entry(5, None, Some(0.0)),
+        ],
+        "2026-03-01T10:00:00+0100",
+    );
+    assert_eq!(
+        guide.finish_counts(),
+        FinishCounts {
+            non_foil_only: 1,
+            foil_only: 2,
+            both: 1,
+            unpriced: 1,
+        }
+    );
+}
+
+#[test]
+fn category_ids_round_trip() {
+    assert_eq!(Category::from_id(1), Category::Single);
+    assert!(Category::from_id(1).is_single());
+    assert_eq!(Category::from_id(7), Category::Other(7));
+    assert!(!Category::Other(7).is_single());
+    assert_eq!(Category::Other(7).id(), 7);
+    assert_eq!(Category::Single.display_name(), "Magic Single");
+    assert_eq!(Category::Other(7).display_name(), "Category 7");
+}
+
+#[test]
+fn category_parses_ids_and_singles() {
+    assert_eq!("singles".parse::<Category>(), Ok(Category::Single));
+    assert_eq!(" 1 ".parse::<Category>(), Ok(Category::Single));
+    assert_eq!("7".parse::<Category>(), Ok(Category::Other(7)));
+    assert!("boosters".parse::<Category>().is_err());
+}
+
+#[test]
+fn category_serializes_as_its_id() {
+    let categories: Vec<Category> = serde_json::from_str("[1, 7]").unwrap();
+    assert_eq!(categories, [Category::Single, Category::Other(7)]);
+    assert_eq!(serde_json::to_string(&categories).unwrap(), "[1,7]");
 }
@@ -0,0 +1,127 @@
+//! Transparent decompression of input files.
+//!
+//! Cardmarket exports and price guides are often passed around gzipped or
+//! zipped. Every file reader in the repo opens its input through
+//! [`with_reader`] or [`read_to_string`], which recognise gzip (`.gz`) and
+//! single-entry zip (`.zip`) files by their magic bytes and decompress them
+//! while reading, so large files are never inflated in memory first. Plain
+//! files are read as before.
+//!
+//! Paths pasted from Windows Explorer's "Copy as path" come wrapped in
+//! double quotes; [`with_reader`] strips them. Text read through
+//! [`read_to_string`] is decoded from whatever encoding Excel saved it in
+//! (see [`crate::text_encoding`]).
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+
+use crate::text_encoding::{self, TextEncoding};
+
+/// Container format of an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zip,
+}
+
+impl Compression {
+    /// Detects the format from the first bytes of a file.
+    pub fn from_magic(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if bytes.starts_with(b"PK\x03\x04") {
+            Compression::Zip
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// Opens `path` and calls `f` with a reader over its decompressed content.
+///
+/// Zip archives must contain exactly one file; directories are ignored.
+/// Anything else is an `InvalidData` error, since it's unclear which entry
+/// was meant.
+pub fn with_reader<T, E, F>(path: impl AsRef<Path>, f: F) -> Result<T, E>
+where
+    E: From<io::Error>,
+    F: FnOnce(&mut dyn Read) -> Result<T, E>,
+{
+    let path = clean_path(path.as_ref());
+    let path = path.as_path();
+    let mut file = BufReader::new(File::open(path)?);
+    let compression = Compression::from_magic(file.fill_buf()?);
+    match compression {
+        Compression::None => f(&mut file),
+        Compression::Gzip => {
+            log::debug!("Decompressing gzip input {}", path.display());
+            f(&mut MultiGzDecoder::new(file))
+        }
+        Compression::Zip => {
+            let mut archive = zip::ZipArchive::new(file.into_inner()).map_err(invalid_data)?;
+            let files: Vec<usize> = (0..archive.len())
+                .filter(|&i| archive.by_index(i).is_ok_and(|entry| entry.is_file()))
+                .collect();
+            let [index] = files[..] else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "{} contains {} files, expected exactly one",
+                        path.display(),
+                        files.len()
+                    ),
+                )
+                .into());
+            };
+            let mut entry = archive.by_index(index).map_err(invalid_data)?;
+            log::debug!(
+                "Decompressing {} from zip input {}",
+                entry.name(),
+                path.display()
+            );
+            f(&mut BufReader::new(&mut entry))
+        }
+    }
+}
+
+/// Reads a possibly compressed text file into a string, converting UTF-16
+/// and Windows-1252 content and dropping a byte order mark.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    let bytes = with_reader(path, |reader| {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok::<_, io::Error>(bytes)
+    })?;
+    let (content, encoding) = text_encoding::decode(bytes);
+    if encoding != TextEncoding::Utf8 {
+        log::info!("Decoded {} from {encoding}", path.display());
+    }
+    Ok(content)
+}
+
+/// Strips surrounding whitespace and the double quotes Windows Explorer's
+/// "Copy as path" adds, e.g. `"C:\Exports\stock.csv"`.
+pub fn clean_path(path: &Path) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    let trimmed = text.trim();
+    let unquoted = trimmed
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(trimmed);
+    PathBuf::from(unquoted)
+}
+
+fn invalid_data(err: zip::result::ZipError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+#[path = "compressed_tests.rs"]
+mod tests;
@@ -0,0 +1,97 @@
+//! Tests for compressed.
+
+use super::*;
+use std::io::Write;
+
+const CSV: &str = "cardmarketId,quantity\n1234,2\n5678,1\n";
+
+fn write_gzip(path: &Path, content: &str) {
+    let mut encoder =
+        flate2::write::GzEncoder::new(File::create(path).unwrap(), flate2::Compression::default());
+    encoder.write_all(content.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+}
+
+fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+    let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+    writer
+        .add_directory("exports/", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    for (name, content) in entries {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+#[test]
+fn detects_format_from_magic_bytes() {
+    assert_eq!(Compression::from_magic(&[0x1f, 0x8b, 8]), Compression::Gzip);
+    assert_eq!(Compression::from_magic(b"PK\x03\x04rest"), Compression::Zip);
+    assert_eq!(Compression::from_magic(b"cardmarketId"), Compression::None);
+    assert_eq!(Compression::from_magic(b""), Compression::None);
+}
+
+#[test]
+fn reads_plain_gzip_and_zip_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let plain = dir.path().join("stock.csv");
+    std::fs::write(&plain, CSV).unwrap();
+    let gz = dir.path().join("stock.csv.gz");
+    write_gzip(&gz, CSV);
+    let zipped = dir.path().join("stock.zip");
+    write_zip(&zipped, &[("exports/stock.csv", CSV)]);
+
+    for path in [&plain, &gz, &zipped] {
+        assert_eq!(read_to_string(path).unwrap(), CSV, "{}", path.display());
+    }
+}
+
+#[test]
+fn detection_ignores_the_file_extension() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("price_guide.json");
+    write_gzip(&path, "{}");
+    assert_eq!(read_to_string(&path).unwrap(), "{}");
+}
+
+#[test]
+fn zip_with_several_files_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("two.zip");
+    write_zip(&path, &[("a.csv", CSV), ("b.csv", CSV)]);
+
+    let err = read_to_string(&path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("contains 2 files"));
+}
+
+#[test]
+fn with_reader_streams_into_the_callback() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stock.csv.gz");
+    write_gzip(&path, CSV);
+
+    let lines = with_reader(&path, |reader| {
+        Ok::<_, io::Error>(BufReader::new(reader).lines().count())
+    })
+    .unwrap();
+    assert_eq!(lines, 3);
+}
+
+#[test]
+fn reads_excel_encodings_and_quoted_windows_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stock.csv");
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in "name\r\nÆther Vial\r\n".encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    std::fs::write(&path, bytes).unwrap();
+    let quoted = format!(" \"{}\" ", path.display());
+
+    assert_eq!(read_to_string(&quoted).unwrap(), "name\r\nÆther Vial\r\n");
+    assert_eq!(clean_path(Path::new(&quoted)), path);
+}
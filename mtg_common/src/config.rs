@@ -0,0 +1,410 @@
+//! Shared configuration file for all tools.
+//!
+//! Settings are layered, later layers winning:
+//!
+//! 1. built-in defaults,
+//! 2. `config.toml` in the data dir (`~/.local/share/d2d_automations/` on
+//!    Linux) or the file named by `D2D_CONFIG` / `--config`,
+//! 3. environment variables,
+//! 4. command-line flags (applied by each binary on top of [`config`]).
+//!
+//! Every binary accepts `--print-config` to show the effective settings with
+//! tokens redacted. The file has one table per tool plus the shared `[http]`
+//! and `[sql]` tables (see [`crate::http`] and [`crate::sql_profile`]):
+//!
+//! ```toml
+//! [http]
+//! timeout_secs = 90
+//!
+//! [sql]
+//! slow_query_ms = 200
+//!
+//! [inventory_sync]
+//! database = "/srv/inventory_sync/inventory.db"
+//! web_port = 3000
+//! interval_hours = 2
+//!
+//! [check_stock]
+//! inventory_sync_url = "http://cardscanner.local:3000"
+//!
+//! [accounting]
+//! sevdesk_api_token = "…"
+//! ```
+//!
+//! Env vars per setting:
+//!
+//! | Env var                                  | Setting                                  |
+//! |------------------------------------------|------------------------------------------|
+//! | `INVENTORY_SYNC_DATABASE`                | `inventory_sync.database`                |
+//! | `INVENTORY_SYNC_WEB_PORT`                | `inventory_sync.web_port`                |
+//! | `INVENTORY_SYNC_INTERVAL_HOURS`          | `inventory_sync.interval_hours`          |
+//! | `INVENTORY_SYNC_ADMIN_TOKEN`             | `inventory_sync.admin_token`             |
+//! | `INVENTORY_SYNC_STOCK_DB`                | `inventory_sync.stock_db`                |
+//! | `INVENTORY_SYNC_ORDER_BOARD`             | `inventory_sync.order_board`             |
+//! | `INVENTORY_SYNC_PRICE_GUIDE_ARCHIVE_URL` | `inventory_sync.price_guide_archive_url` |
+//! | `INVENTORY_SYNC_BACKFILL_DAYS`           | `inventory_sync.backfill_days`           |
+//! | `INVENTORY_SYNC_DOWNLOAD_LIMIT_KBIT`     | `inventory_sync.download_limit_kbit`     |
+//! | `INVENTORY_SYNC_DOWNLOAD_WINDOW`         | `inventory_sync.download_window`         |
+//! | `INVENTORY_SYNC_BACKUP_URL`              | `inventory_sync.backup_url`              |
+//! | `INVENTORY_SYNC_BACKUP_S3_ENDPOINT`      | `inventory_sync.backup_s3_endpoint`      |
+//! | `INVENTORY_SYNC_BACKUP_S3_REGION`        | `inventory_sync.backup_s3_region`        |
+//! | `INVENTORY_SYNC_BACKUP_USER`             | `inventory_sync.backup_user`             |
+//! | `INVENTORY_SYNC_BACKUP_PASSWORD`         | `inventory_sync.backup_password`         |
+//! | `INVENTORY_SYNC_BACKUP_KEEP`             | `inventory_sync.backup_keep`             |
+//! | `INVENTORY_SYNC_WEBHOOK`                 | `inventory_sync.webhook_url`             |
+//! | `CHECK_STOCK_DATABASE`                   | `check_stock.database`                   |
+//! | `CHECK_STOCK_INVENTORY_SYNC_URL`         | `check_stock.inventory_sync_url`         |
+//! | `CHECK_STOCK_SYNC_WEBHOOK`               | `check_stock.sync_webhook_url`           |
+//! | `CHECK_STOCK_LENIENT_CSV`                | `check_stock.lenient_csv`                |
+//! | `CHECK_STOCK_PRICE_HISTORY_DB`           | `check_stock.price_history_db`           |
+//! | `SEVDESK_API`                            | `accounting.sevdesk_api_token`           |
+//! | `SEVDESK_BASE_URL`                       | `accounting.sevdesk_base_url`            |
+//! | `D2D_HTTP_*`                             | `http.*`                                 |
+//! | `D2D_SQL_SLOW_QUERY_MS`                  | `sql.slow_query_ms`                      |
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cardmarket::Category;
+use crate::error::{MtgError, MtgResult};
+use crate::http::HttpConfig;
+use crate::sql_profile::SqlConfig;
+
+/// Env var naming the config file, instead of [`default_path`].
+pub const CONFIG_FILE_ENV: &str = "D2D_CONFIG";
+
+/// Shown instead of tokens by `--print-config`.
+const REDACTED: &str = "<redacted>";
+
+fn data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Default config file: `<data dir>/d2d_automations/config.toml`
+pub fn default_path() -> PathBuf {
+    data_dir().join("d2d_automations").join("config.toml")
+}
+
+/// Settings of every tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct D2dConfig {
+    pub http: HttpConfig,
+    pub sql: SqlConfig,
+    pub inventory_sync: InventorySyncConfig,
+    pub check_stock: CheckStockConfig,
+    pub accounting: AccountingConfig,
+}
+
+/// `[inventory_sync]` — the sync server and its schedule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InventorySyncConfig {
+    /// SQLite database with the price history
+    pub database: PathBuf,
+    /// Hours between sync checks when running continuously
+    pub interval_hours: u64,
+    /// Port of the web UI; disabled when unset
+    pub web_port: Option<u16>,
+    /// Bearer token for the admin endpoints; disabled when unset
+    pub admin_token: Option<String>,
+    pub audit_log: bool,
+    /// check_stock database to browse in the web UI
+    pub stock_db: Option<PathBuf>,
+    /// The accounting app's order board, for the revenue figures in the web UI
+    pub order_board: Option<PathBuf>,
+    pub tcgplayer_min_trend: Option<f64>,
+    /// Cardmarket categories to store prices for (IDs); all when empty
+    pub categories: Vec<Category>,
+    pub price_guide_archive_url: Option<String>,
+    pub backfill_days: u32,
+    /// Bandwidth cap for the catalog and price guide downloads, in kbit/s
+    pub download_limit_kbit: Option<u64>,
+    /// Time of day Cardmarket syncs may run in, e.g. `"02:00-06:00"`
+    pub download_window: Option<String>,
+    /// Off-site backup target: a WebDAV folder (`https://…`) or
+    /// `s3://bucket/prefix`; backups are disabled when unset
+    pub backup_url: Option<String>,
+    /// Endpoint of the S3-compatible storage, e.g. `https://s3.eu-central-1.amazonaws.com`
+    pub backup_s3_endpoint: Option<String>,
+    pub backup_s3_region: Option<String>,
+    /// WebDAV user name or S3 access key
+    pub backup_user: Option<String>,
+    /// WebDAV password or S3 secret key
+    pub backup_password: Option<String>,
+    /// Daily backups kept on the target; 0 keeps all
+    pub backup_keep: u32,
+    /// Webhook sent a summary after every sync run that did something
+    pub webhook_url: Option<String>,
+}
+
+impl Default for InventorySyncConfig {
+    fn default() -> Self {
+        Self {
+            database: data_dir().join("inventory_sync").join("inventory.db"),
+            interval_hours: 1,
+            web_port: None,
+            admin_token: None,
+            audit_log: false,
+            stock_db: None,
+            order_board: None,
+            tcgplayer_min_trend: None,
+            categories: Vec::new(),
+            price_guide_archive_url: None,
+            backfill_days: 30,
+            download_limit_kbit: None,
+            download_window: None,
+            backup_url: None,
+            backup_s3_endpoint: None,
+            backup_s3_region: None,
+            backup_user: None,
+            backup_password: None,
+            backup_keep: 14,
+            webhook_url: None,
+        }
+    }
+}
+
+/// `[check_stock]` — the stock checker desktop app.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckStockConfig {
+    /// Inventory database
+    pub database: PathBuf,
+    /// Default inventory_sync server for market prices
+    pub inventory_sync_url: String,
+    /// Webhook notified when an inventory import is blocked as suspicious
+    pub sync_webhook_url: Option<String>,
+    /// Skip malformed inventory CSV rows (and report them) instead of
+    /// failing the whole load
+    pub lenient_csv: bool,
+    /// inventory_sync database to chart price history from directly; the
+    /// `inventory_sync_url` server is asked when unset
+    pub price_history_db: Option<PathBuf>,
+}
+
+impl Default for CheckStockConfig {
+    fn default() -> Self {
+        Self {
+            database: data_dir().join("d2d_automations").join("inventory.db"),
+            inventory_sync_url: "http://cardscanner.local:3000".to_string(),
+            sync_webhook_url: None,
+            lenient_csv: false,
+            price_history_db: None,
+        }
+    }
+}
+
+/// `[accounting]` — the SevDesk invoicing app.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccountingConfig {
+    pub sevdesk_api_token: Option<String>,
+    /// SevDesk API base URL, e.g. a mock server or sandbox (default: the
+    /// public API)
+    pub sevdesk_base_url: Option<String>,
+}
+
+impl D2dConfig {
+    /// Builds the config from optional TOML file contents and an env lookup.
+    /// Env values win over file values; unparsable numbers and flags are
+    /// ignored.
+    pub fn from_sources(
+        file_contents: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> MtgResult<Self> {
+        let mut config: Self = match file_contents {
+            Some(toml) => toml::from_str(toml).map_err(|e| MtgError::Config(e.to_string()))?,
+            None => Self::default(),
+        };
+        config.http.apply_env(&env);
+        config.sql.apply_env(&env);
+
+        let text = |key: &str| env(key).filter(|v| !v.trim().is_empty());
+        let sync = &mut config.inventory_sync;
+        if let Some(path) = text("INVENTORY_SYNC_DATABASE") {
+            sync.database = PathBuf::from(path);
+        }
+        if let Some(port) = parse_env(&env, "INVENTORY_SYNC_WEB_PORT") {
+            sync.web_port = Some(port);
+        }
+        if let Some(hours) = parse_env(&env, "INVENTORY_SYNC_INTERVAL_HOURS") {
+            sync.interval_hours = hours;
+        }
+        if let Some(token) = text("INVENTORY_SYNC_ADMIN_TOKEN") {
+            sync.admin_token = Some(token);
+        }
+        if let Some(path) = text("INVENTORY_SYNC_STOCK_DB") {
+            sync.stock_db = Some(PathBuf::from(path));
+        }
+        if let Some(path) = text("INVENTORY_SYNC_ORDER_BOARD") {
+            sync.order_board = Some(PathBuf::from(path));
+        }
+        if let Some(url) = text("INVENTORY_SYNC_PRICE_GUIDE_ARCHIVE_URL") {
+            sync.price_guide_archive_url = Some(url);
+        }
+        if let Some(days) = parse_env(&env, "INVENTORY_SYNC_BACKFILL_DAYS") {
+            sync.backfill_days = days;
+        }
+        if let Some(kbit) = parse_env(&env, "INVENTORY_SYNC_DOWNLOAD_LIMIT_KBIT") {
+            sync.download_limit_kbit = Some(kbit);
+        }
+        if let Some(window) = text("INVENTORY_SYNC_DOWNLOAD_WINDOW") {
+            sync.download_window = Some(window);
+        }
+        if let Some(url) = text("INVENTORY_SYNC_BACKUP_URL") {
+            sync.backup_url = Some(url);
+        }
+        if let Some(endpoint) = text("INVENTORY_SYNC_BACKUP_S3_ENDPOINT") {
+            sync.backup_s3_endpoint = Some(endpoint);
+        }
+        if let Some(region) = text("INVENTORY_SYNC_BACKUP_S3_REGION") {
+            sync.backup_s3_region = Some(region);
+        }
+        if let Some(user) = text("INVENTORY_SYNC_BACKUP_USER") {
+            sync.backup_user = Some(user);
+        }
+        if let Some(password) = text("INVENTORY_SYNC_BACKUP_PASSWORD") {
+            sync.backup_password = Some(password);
+        }
+        if let Some(keep) = parse_env(&env, "INVENTORY_SYNC_BACKUP_KEEP") {
+            sync.backup_keep = keep;
+        }
+        if let Some(url) = text("INVENTORY_SYNC_WEBHOOK") {
+            sync.webhook_url = Some(url);
+        }
+
+        if let Some(path) = text("CHECK_STOCK_DATABASE") {
+            config.check_stock.database = PathBuf::from(path);
+        }
+        if let Some(url) = text("CHECK_STOCK_INVENTORY_SYNC_URL") {
+            config.check_stock.inventory_sync_url = url;
+        }
+        if let Some(url) = text("CHECK_STOCK_SYNC_WEBHOOK") {
+            config.check_stock.sync_webhook_url = Some(url);
+        }
+        if let Some(lenient) = parse_env(&env, "CHECK_STOCK_LENIENT_CSV") {
+            config.check_stock.lenient_csv = lenient;
+        }
+        if let Some(path) = text("CHECK_STOCK_PRICE_HISTORY_DB") {
+            config.check_stock.price_history_db = Some(PathBuf::from(path));
+        }
+
+        if let Some(token) = text("SEVDESK_API") {
+            config.accounting.sevdesk_api_token = Some(token);
+        }
+        if let Some(url) = text("SEVDESK_BASE_URL") {
+            config.accounting.sevdesk_base_url = Some(url);
+        }
+        Ok(config)
+    }
+
+    /// Loads the config from `path` (or the file named by [`CONFIG_FILE_ENV`],
+    /// or [`default_path`]) and the process environment. Only the default
+    /// file may be missing; a broken file is an error.
+    pub fn load(path: Option<&Path>) -> MtgResult<Self> {
+        let explicit = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_FILE_ENV).map(PathBuf::from));
+        let file =
+            match &explicit {
+                Some(path) => Some(std::fs::read_to_string(path).map_err(|e| {
+                    MtgError::Config(format!("cannot read {}: {e}", path.display()))
+                })?),
+                None => match std::fs::read_to_string(default_path()) {
+                    Ok(contents) => Some(contents),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => return Err(e.into()),
+                },
+            };
+        Self::from_sources(file.as_deref(), |key| std::env::var(key).ok())
+    }
+
+    /// The config as TOML with tokens replaced by a placeholder, for
+    /// `--print-config`.
+    pub fn to_redacted_toml(&self) -> String {
+        let mut config = self.clone();
+        let redact = |token: &mut Option<String>| {
+            if token.is_some() {
+                *token = Some(REDACTED.to_string());
+            }
+        };
+        redact(&mut config.inventory_sync.admin_token);
+        redact(&mut config.inventory_sync.backup_password);
+        redact(&mut config.accounting.sevdesk_api_token);
+        // Chat webhook URLs carry their secret in the path
+        redact(&mut config.check_stock.sync_webhook_url);
+        redact(&mut config.inventory_sync.webhook_url);
+        // Only plain data, which always serializes
+        toml::to_string_pretty(&config).unwrap_or_default()
+    }
+}
+
+fn parse_env<T: FromStr>(env: &impl Fn(&str) -> Option<String>, key: &str) -> Option<T> {
+    let value = env(key)?;
+    match value.trim().parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            log::warn!("Ignoring {key}={value}: not a valid value");
+            None
+        }
+    }
+}
+
+static CONFIG: OnceLock<D2dConfig> = OnceLock::new();
+
+/// Loads the process-wide config from `path` (see [`D2dConfig::load`]).
+/// Call once at startup, before anything reads [`config`]; later calls
+/// return the config loaded first.
+pub fn init(path: Option<&Path>) -> MtgResult<&'static D2dConfig> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = D2dConfig::load(path)?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The process-wide config. Loaded on first use if [`init`] was not called;
+/// a broken file is then logged and the defaults and env vars are used.
+pub fn config() -> &'static D2dConfig {
+    CONFIG.get_or_init(|| {
+        D2dConfig::load(None).unwrap_or_else(|e| {
+            log::warn!("Invalid config file, using environment only: {e}");
+            D2dConfig::from_sources(None, |key| std::env::var(key).ok()).unwrap_or_default()
+        })
+    })
+}
+
+/// `--config <path>` and `--print-config` for the binaries without a CLI
+/// parser. Other arguments are left alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigArgs {
+    pub path: Option<PathBuf>,
+    pub print: bool,
+}
+
+impl ConfigArgs {
+    /// Parses the arguments after the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--print-config" {
+                parsed.print = true;
+            } else if arg == "--config" {
+                let path = args.next().ok_or("--config needs a file path")?;
+                parsed.path = Some(PathBuf::from(path));
+            } else if let Some(path) = arg.strip_prefix("--config=") {
+                parsed.path = Some(PathBuf::from(path));
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+#[path = "config_tests.rs"]
+mod tests;
@@ -0,0 +1,173 @@
+//! Tests for config.
+
+use super::*;
+use std::collections::HashMap;
+
+fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let map: HashMap<String, String> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |key| map.get(key).cloned()
+}
+
+#[test]
+fn defaults_when_nothing_configured() {
+    let config = D2dConfig::from_sources(None, env_of(&[])).unwrap();
+    assert_eq!(config, D2dConfig::default());
+    assert_eq!(config.inventory_sync.interval_hours, 1);
+    assert_eq!(config.inventory_sync.backfill_days, 30);
+    assert!(config
+        .check_stock
+        .database
+        .ends_with("d2d_automations/inventory.db"));
+}
+
+#[test]
+fn reads_sections_and_keeps_other_defaults() {
+    let toml = r#"
+        [http]
+        timeout_secs = 90
+
+        [inventory_sync]
+        web_port = 3000
+        categories = [1]
+
+        [accounting]
+        sevdesk_api_token = "abc"
+    "#;
+    let config = D2dConfig::from_sources(Some(toml), env_of(&[])).unwrap();
+    assert_eq!(config.http.timeout_secs, Some(90));
+    assert_eq!(config.inventory_sync.web_port, Some(3000));
+    assert_eq!(config.inventory_sync.categories, vec![Category::Single]);
+    assert_eq!(config.inventory_sync.interval_hours, 1);
+    assert_eq!(config.accounting.sevdesk_api_token.as_deref(), Some("abc"));
+    assert_eq!(config.check_stock, CheckStockConfig::default());
+}
+
+#[test]
+fn env_overrides_file() {
+    let toml = r#"
+        [inventory_sync]
+        web_port = 3000
+        interval_hours = 6
+        admin_token = "from-file"
+    "#;
+    let env = env_of(&[
+        ("INVENTORY_SYNC_WEB_PORT", "8080"),
+        ("INVENTORY_SYNC_INTERVAL_HOURS", "later"),
+        ("INVENTORY_SYNC_ADMIN_TOKEN", "from-env"),
+        ("INVENTORY_SYNC_DOWNLOAD_LIMIT_KBIT", "8000"),
+        ("INVENTORY_SYNC_DOWNLOAD_WINDOW", "02:00-06:00"),
+        ("INVENTORY_SYNC_BACKUP_URL", "s3://backups/prices"),
+        ("INVENTORY_SYNC_BACKUP_KEEP", "30"),
+        ("INVENTORY_SYNC_ORDER_BOARD", "/srv/order_board.json"),
+        (
+            "INVENTORY_SYNC_WEBHOOK",
+            "https://discord.com/api/webhooks/1/abc",
+        ),
+        ("CHECK_STOCK_DATABASE", "/tmp/stock.db"),
+        ("CHECK_STOCK_LENIENT_CSV", "true"),
+        (
+            "CHECK_STOCK_PRICE_HISTORY_DB",
+            "/srv/inventory_sync/inventory.db",
+        ),
+        ("SEVDESK_API", " "),
+        ("SEVDESK_BASE_URL", "http://localhost:8089/api/v1"),
+        ("D2D_HTTP_PROXY", "http://proxy.local:3128"),
+        ("D2D_SQL_SLOW_QUERY_MS", "250"),
+    ]);
+    let config = D2dConfig::from_sources(Some(toml), env).unwrap();
+    assert_eq!(config.inventory_sync.web_port, Some(8080));
+    // Unparsable numbers and blank values are ignored
+    assert_eq!(config.inventory_sync.interval_hours, 6);
+    assert_eq!(config.accounting.sevdesk_api_token, None);
+    assert_eq!(
+        config.accounting.sevdesk_base_url.as_deref(),
+        Some("http://localhost:8089/api/v1")
+    );
+    assert_eq!(
+        config.inventory_sync.admin_token.as_deref(),
+        Some("from-env")
+    );
+    assert_eq!(config.inventory_sync.download_limit_kbit, Some(8000));
+    assert_eq!(
+        config.inventory_sync.download_window.as_deref(),
+        Some("02:00-06:00")
+    );
+    assert_eq!(
+        config.inventory_sync.backup_url.as_deref(),
+        Some("s3://backups/prices")
+    );
+    assert_eq!(config.inventory_sync.backup_keep, 30);
+    assert_eq!(
+        config.inventory_sync.order_board,
+        Some(PathBuf::from("/srv/order_board.json"))
+    );
+    assert_eq!(
+        config.inventory_sync.webhook_url.as_deref(),
+        Some("https://discord.com/api/webhooks/1/abc")
+    );
+    assert_eq!(config.check_stock.database, PathBuf::from("/tmp/stock.db"));
+    assert!(config.check_stock.lenient_csv);
+    assert_eq!(
+        config.check_stock.price_history_db,
+        Some(PathBuf::from("/srv/inventory_sync/inventory.db"))
+    );
+    assert_eq!(
+        config.http.proxy.as_deref(),
+        Some("http://proxy.local:3128")
+    );
+    assert_eq!(
+        config.sql.slow_query_threshold(),
+        Some(std::time::Duration::from_millis(250))
+    );
+}
+
+#[test]
+fn invalid_file_is_an_error() {
+    for toml in ["[inventory_sync", "[inventory_sync]\nweb_prot = 3000"] {
+        assert!(matches!(
+            D2dConfig::from_sources(Some(toml), env_of(&[])),
+            Err(MtgError::Config(_))
+        ));
+    }
+}
+
+#[test]
+fn printed_config_redacts_tokens_and_round_trips() {
+    let mut config = D2dConfig::default();
+    config.inventory_sync.admin_token = Some("secret-admin".to_string());
+    config.accounting.sevdesk_api_token = Some("secret-sevdesk".to_string());
+    config.inventory_sync.backup_password = Some("secret-s3-key".to_string());
+    config.inventory_sync.webhook_url = Some("https://hooks.slack.com/secret".to_string());
+    config.inventory_sync.web_port = Some(3000);
+
+    let printed = config.to_redacted_toml();
+
+    assert!(!printed.contains("secret"));
+    let reparsed = D2dConfig::from_sources(Some(&printed), env_of(&[])).unwrap();
+    assert_eq!(reparsed.inventory_sync.web_port, Some(3000));
+    assert_eq!(
+        reparsed.inventory_sync.admin_token.as_deref(),
+        Some(REDACTED)
+    );
+}
+
+#[test]
+fn config_args_are_picked_out_of_other_arguments() {
+    let args = |list: &[&str]| ConfigArgs::parse(list.iter().map(|a| a.to_string()));
+    assert_eq!(args(&[]).unwrap(), ConfigArgs::default());
+    assert_eq!(
+        args(&["--verbose", "--config", "/etc/d2d.toml", "--print-config"]).unwrap(),
+        ConfigArgs {
+            path: Some(PathBuf::from("/etc/d2d.toml")),
+            print: true,
+        }
+    );
+    assert_eq!(
+        args(&["--config=/etc/d2d.toml"]).unwrap().path,
+        Some(PathBuf::from("/etc/d2d.toml"))
+    );
+    assert!(args(&["--config"]).is_err());
+}
@@ -0,0 +1,24 @@
+//! Header matching for CSV imports.
+//!
+//! Exports name their columns inconsistently (`Order ID`, `order_id`,
+//! `orderId`), so parsers compare header names after [`normalize_header`]
+//! against a list of known spellings with [`find_column`].
+
+/// Header name in lowercase without spaces, underscores or dashes.
+pub fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Index of the first normalized header that is one of `names`.
+pub fn find_column(headers: &[String], names: &[&str]) -> Option<usize> {
+    headers.iter().position(|h| names.contains(&h.as_str()))
+}
+
+#[cfg(test)]
+#[path = "csv_header_tests.rs"]
+mod tests;
@@ -0,0 +1,21 @@
+//! Tests for csv_header.
+
+use super::*;
+
+#[test]
+fn spellings_of_a_header_normalize_alike() {
+    for header in ["Order ID", "order_id", " orderId ", "ORDER-ID"] {
+        assert_eq!(normalize_header(header), "orderid");
+    }
+}
+
+#[test]
+fn finds_the_first_known_spelling() {
+    let headers: Vec<String> = ["Name", "Qty", "Set Code"]
+        .into_iter()
+        .map(normalize_header)
+        .collect();
+    assert_eq!(find_column(&headers, &["quantity", "qty"]), Some(1));
+    assert_eq!(find_column(&headers, &["setcode", "set"]), Some(2));
+    assert_eq!(find_column(&headers, &["price"]), None);
+}
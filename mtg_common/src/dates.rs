@@ -0,0 +1,45 @@
+//! Calendar dates with an explicit time zone policy.
+//!
+//! Cardmarket dates its price guides and orders in Europe/Berlin time, so
+//! every calendar day the tools store or compare — price dates, sync days,
+//! sale dates, backup names — is a Berlin day (the "market day"), whatever
+//! time zone the machine runs in. Machines east or west of Berlin would
+//! otherwise start a new day an hour or more early or late, syncing or
+//! comparing against the wrong date around midnight.
+//!
+//! Use [`today_date`] / [`market_today`] for those days instead of
+//! `chrono::Local`; local time is only right for wall-clock timestamps shown
+//! to the user.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+/// Time zone of the market day.
+pub const MARKET_TZ: Tz = chrono_tz::Europe::Berlin;
+
+/// Format of stored calendar dates (`YYYY-MM-DD`).
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The market day at instant `at`.
+pub fn market_date(at: DateTime<Utc>) -> NaiveDate {
+    at.with_timezone(&MARKET_TZ).date_naive()
+}
+
+/// The market's wall-clock time at instant `at`.
+pub fn market_time(at: DateTime<Utc>) -> NaiveTime {
+    at.with_timezone(&MARKET_TZ).time()
+}
+
+/// Today's market day.
+pub fn market_today() -> NaiveDate {
+    market_date(Utc::now())
+}
+
+/// Today's market day as `YYYY-MM-DD`.
+pub fn today_date() -> String {
+    market_today().format(DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+#[path = "dates_tests.rs"]
+mod tests;
@@ -0,0 +1,70 @@
+//! Tests for dates.
+
+use super::*;
+
+fn utc(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, DATE_FORMAT).unwrap()
+}
+
+#[test]
+fn market_day_turns_at_berlin_midnight_in_winter() {
+    // CET is UTC+1: the day turns at 23:00 UTC
+    assert_eq!(market_date(utc("2026-01-15T22:59:59Z")), date("2026-01-15"));
+    assert_eq!(market_date(utc("2026-01-15T23:00:00Z")), date("2026-01-16"));
+}
+
+#[test]
+fn market_day_turns_at_berlin_midnight_in_summer() {
+    // CEST is UTC+2: the day turns at 22:00 UTC
+    assert_eq!(market_date(utc("2026-07-15T21:59:59Z")), date("2026-07-15"));
+    assert_eq!(market_date(utc("2026-07-15T22:00:00Z")), date("2026-07-16"));
+}
+
+#[test]
+fn market_day_ignores_the_callers_offset() {
+    // Early morning in New York is already the next day in Berlin
+    let new_york = DateTime::parse_from_rfc3339("2026-01-15T19:30:00-05:00").unwrap();
+    assert_eq!(
+        market_date(new_york.with_timezone(&Utc)),
+        date("2026-01-16")
+    );
+    // Early morning in Tokyo is still the previous day in Berlin
+    let tokyo = DateTime::parse_from_rfc3339("2026-01-16T07:30:00+09:00").unwrap();
+    assert_eq!(market_date(tokyo.with_timezone(&Utc)), date("2026-01-15"));
+}
+
+#[test]
+fn dst_transitions_keep_the_day_and_move_the_clock() {
+    // Spring forward on 2026-03-29: 02:00 CET becomes 03:00 CEST at 01:00 UTC
+    assert_eq!(
+        market_time(utc("2026-03-29T00:59:59Z")),
+        NaiveTime::from_hms_opt(1, 59, 59).unwrap()
+    );
+    assert_eq!(
+        market_time(utc("2026-03-29T01:00:00Z")),
+        NaiveTime::from_hms_opt(3, 0, 0).unwrap()
+    );
+    assert_eq!(market_date(utc("2026-03-29T21:59:59Z")), date("2026-03-29"));
+    assert_eq!(market_date(utc("2026-03-29T22:00:00Z")), date("2026-03-30"));
+
+    // Fall back on 2026-10-25: 02:30 happens twice, an hour apart in UTC
+    let first = utc("2026-10-25T00:30:00Z");
+    let second = utc("2026-10-25T01:30:00Z");
+    assert_eq!(market_time(first), market_time(second));
+    assert_eq!(market_date(first), date("2026-10-25"));
+    assert_eq!(market_date(utc("2026-10-25T22:59:59Z")), date("2026-10-25"));
+    assert_eq!(market_date(utc("2026-10-25T23:00:00Z")), date("2026-10-26"));
+}
+
+#[test]
+fn today_date_is_formatted_market_today() {
+    let today = today_date();
+    assert_eq!(today.len(), 10);
+    // The day may turn between the two calls
+    let parsed = date(&today);
+    assert!((market_today() - parsed).num_days() <= 1);
+}
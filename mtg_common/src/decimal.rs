@@ -0,0 +1,141 @@
+//! Locale-aware decimal parsing for CSV exports.
+//!
+//! Cardmarket exports prices with a decimal comma or a decimal point
+//! depending on the account language, and spreadsheets re-saved in a German
+//! locale add `.` thousands separators on top. Every parser in the repo goes
+//! through [`parse_decimal`] instead of replacing commas ad hoc.
+//!
+//! The format defaults to [`DecimalLocale::Auto`]; set `CSV_DECIMAL_LOCALE`
+//! to `de` or `en` to parse every CSV strictly in one format.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Env var fixing the number format of loaded CSVs (`auto`, `de` or `en`).
+pub const DECIMAL_LOCALE_ENV: &str = "CSV_DECIMAL_LOCALE";
+
+/// Number format of a CSV column.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecimalLocale {
+    /// Detect per value: the last of `.` / `,` is the decimal separator, a
+    /// separator repeated more than once groups thousands. A single
+    /// separator always counts as decimal, so `"1,234"` is 1.234.
+    #[default]
+    Auto,
+    /// `1.234,56`: `,` decimal, `.` thousands.
+    German,
+    /// `1,234.56`: `.` decimal, `,` thousands.
+    English,
+}
+
+impl DecimalLocale {
+    /// The locale configured via [`DECIMAL_LOCALE_ENV`], or `Auto` if unset
+    /// or invalid.
+    pub fn from_env() -> Self {
+        Self::from_env_value(std::env::var(DECIMAL_LOCALE_ENV).ok().as_deref())
+    }
+
+    fn from_env_value(value: Option<&str>) -> Self {
+        match value.map(str::parse) {
+            Some(Ok(locale)) => locale,
+            Some(Err(e)) => {
+                log::warn!("Ignoring {DECIMAL_LOCALE_ENV}: {e}");
+                DecimalLocale::Auto
+            }
+            None => DecimalLocale::Auto,
+        }
+    }
+
+    /// `(thousands, decimal)` separators; `Auto` decides per value.
+    fn separators(self, value: &str) -> (char, char) {
+        match self {
+            DecimalLocale::German => ('.', ','),
+            DecimalLocale::English => (',', '.'),
+            DecimalLocale::Auto => match (value.rfind('.'), value.rfind(',')) {
+                (Some(dot), Some(comma)) if comma > dot => ('.', ','),
+                (Some(_), Some(_)) => (',', '.'),
+                (None, Some(_)) if value.matches(',').count() == 1 => ('.', ','),
+                (Some(_), None) if value.matches('.').count() > 1 => ('.', ','),
+                _ => (',', '.'),
+            },
+        }
+    }
+}
+
+impl FromStr for DecimalLocale {
+    type Err = String;
+
+    /// Accepts `auto`, `de`/`german` and `en`/`english`, case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(DecimalLocale::Auto),
+            "de" | "german" => Ok(DecimalLocale::German),
+            "en" | "english" => Ok(DecimalLocale::English),
+            other => Err(format!(
+                "unknown decimal locale '{other}' (expected auto, de or en)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DecimalLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecimalLocale::Auto => "auto",
+            DecimalLocale::German => "de",
+            DecimalLocale::English => "en",
+        })
+    }
+}
+
+/// Parses a decimal number in the given locale. Surrounding whitespace and a
+/// leading sign are allowed; thousands separators must group exactly three
+/// digits. Returns `None` for anything else, e.g. `"5,00"` in English.
+pub fn parse_decimal(value: &str, locale: DecimalLocale) -> Option<f64> {
+    let value = value.trim();
+    let (negative, body) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (thousands, decimal) = locale.separators(body);
+
+    let (int_part, frac_part) = match body.split_once(decimal) {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (body, None),
+    };
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+    let mut groups = int_part.split(thousands);
+    let first = groups.next()?;
+    let mut digits = first.to_string();
+    let mut grouped = false;
+    for group in groups {
+        grouped = true;
+        if group.len() != 3 {
+            return None;
+        }
+        digits.push_str(group);
+    }
+    if !is_digits(&digits) || (grouped && !(1..=3).contains(&first.len())) {
+        return None;
+    }
+
+    let frac = frac_part.unwrap_or("");
+    if !is_digits(frac) || (digits.is_empty() && frac.is_empty()) {
+        return None;
+    }
+
+    let number: f64 = format!("{}.{}", digits, frac).parse().ok()?;
+    Some(if negative { -number } else { number })
+}
+
+/// Rounds a EUR amount to whole cents, for totals that are summed from many
+/// prices before they are reported.
+pub fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+#[path = "decimal_tests.rs"]
+mod tests;
@@ -0,0 +1,116 @@
+//! Tests for decimal.
+
+use super::*;
+
+#[test]
+fn auto_accepts_both_decimal_separators() {
+    assert_eq!(parse_decimal("5.00", DecimalLocale::Auto), Some(5.0));
+    assert_eq!(parse_decimal("5,00", DecimalLocale::Auto), Some(5.0));
+    assert_eq!(parse_decimal(" 0,19 ", DecimalLocale::Auto), Some(0.19));
+    assert_eq!(parse_decimal("42", DecimalLocale::Auto), Some(42.0));
+    // A lone separator is always decimal, even before three digits
+    assert_eq!(parse_decimal("1,234", DecimalLocale::Auto), Some(1.234));
+}
+
+#[test]
+fn auto_detects_thousands_separators() {
+    assert_eq!(
+        parse_decimal("1.234,56", DecimalLocale::Auto),
+        Some(1234.56)
+    );
+    assert_eq!(
+        parse_decimal("1,234.56", DecimalLocale::Auto),
+        Some(1234.56)
+    );
+    assert_eq!(
+        parse_decimal("1.234.567", DecimalLocale::Auto),
+        Some(1_234_567.0)
+    );
+    assert_eq!(
+        parse_decimal("1,234,567", DecimalLocale::Auto),
+        Some(1_234_567.0)
+    );
+}
+
+#[test]
+fn explicit_locales_are_strict() {
+    assert_eq!(
+        parse_decimal("1.234,56", DecimalLocale::German),
+        Some(1234.56)
+    );
+    assert_eq!(parse_decimal("1,234", DecimalLocale::German), Some(1.234));
+    assert_eq!(parse_decimal("1.234", DecimalLocale::German), Some(1234.0));
+    assert_eq!(parse_decimal("1.50", DecimalLocale::German), None);
+
+    assert_eq!(
+        parse_decimal("1,234.56", DecimalLocale::English),
+        Some(1234.56)
+    );
+    assert_eq!(parse_decimal("1,234", DecimalLocale::English), Some(1234.0));
+    assert_eq!(parse_decimal("5,00", DecimalLocale::English), None);
+}
+
+#[test]
+fn signs_and_partial_numbers() {
+    assert_eq!(parse_decimal("-1,5", DecimalLocale::Auto), Some(-1.5));
+    assert_eq!(parse_decimal("+2.25", DecimalLocale::Auto), Some(2.25));
+    assert_eq!(parse_decimal(",5", DecimalLocale::German), Some(0.5));
+    assert_eq!(parse_decimal("5.", DecimalLocale::English), Some(5.0));
+}
+
+#[test]
+fn rejects_malformed_values() {
+    for value in [
+        "",
+        " ",
+        "-",
+        ".",
+        "abc",
+        "1,2,3.4.5",
+        "12.34.5",
+        "1.2,3,4",
+        "1234.567,8",
+        "1 234",
+        "5 EUR",
+        "1e5",
+        "--1",
+    ] {
+        assert_eq!(parse_decimal(value, DecimalLocale::Auto), None, "{value:?}");
+    }
+    assert_eq!(parse_decimal("1234.567,8", DecimalLocale::German), None);
+}
+
+#[test]
+fn locale_round_trips_through_strings() {
+    for locale in [
+        DecimalLocale::Auto,
+        DecimalLocale::German,
+        DecimalLocale::English,
+    ] {
+        assert_eq!(locale.to_string().parse::<DecimalLocale>(), Ok(locale));
+    }
+    assert_eq!("German".parse(), Ok(DecimalLocale::German));
+    assert_eq!(" EN ".parse(), Ok(DecimalLocale::English));
+    assert!("fr".parse::<DecimalLocale>().is_err());
+    assert_eq!(DecimalLocale::default(), DecimalLocale::Auto);
+}
+
+#[test]
+fn env_value_falls_back_to_auto() {
+    assert_eq!(DecimalLocale::from_env_value(None), DecimalLocale::Auto);
+    assert_eq!(
+        DecimalLocale::from_env_value(Some("de")),
+        DecimalLocale::German
+    );
+    assert_eq!(
+        DecimalLocale::from_env_value(Some("klingon")),
+        DecimalLocale::Auto
+    );
+}
+
+#[test]
+fn round_cents_rounds_to_two_decimals() {
+    assert_eq!(round_cents(0.1 + 0.2), 0.3);
+    assert_eq!(round_cents(12.345_6), 12.35);
+    assert_eq!(round_cents(-1.005_1), -1.01);
+}
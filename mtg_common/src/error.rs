@@ -23,6 +23,10 @@ pub enum MtgError {
     /// File I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// Unreadable or invalid config file
+    #[error("Config error: {0}")]
+    Config(String),
 }
 
 /// Result alias for common MTG operations.
@@ -0,0 +1,187 @@
+//! Shared HTTP client configuration.
+//!
+//! Every outgoing client (Cardmarket, Scryfall, SevDesk, the inventory sync
+//! server) is built from [`client_builder`] / [`blocking_client_builder`], so
+//! timeouts, a proxy and an extra root certificate can be set once for all
+//! tools — e.g. to work behind a TLS-intercepting proxy.
+//!
+//! Settings come from the `[http]` table of the shared config file (see
+//! [`crate::config`]) — or, for older setups, a JSON file named by
+//! `D2D_HTTP_CONFIG` — with environment variables taking precedence:
+//!
+//! | Env var                    | Key                    | Meaning                          |
+//! |----------------------------|------------------------|----------------------------------|
+//! | `D2D_HTTP_CONNECT_TIMEOUT` | `connect_timeout_secs` | TCP/TLS connect timeout, seconds |
+//! | `D2D_HTTP_TIMEOUT`         | `timeout_secs`         | Whole-request timeout, seconds   |
+//! | `D2D_HTTP_PROXY`           | `proxy`                | Proxy URL for all requests       |
+//! | `D2D_HTTP_CA_CERT`         | `ca_cert`              | PEM file with an extra root CA   |
+//!
+//! Unset values keep each call site's own timeout and reqwest's defaults
+//! (which already honour `HTTPS_PROXY` / `HTTP_PROXY`). A configured proxy
+//! still skips the hosts listed in `NO_PROXY`, e.g. a local sync server.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::MtgResult;
+
+/// Env var naming the legacy JSON config file, used instead of `[http]`.
+pub const CONFIG_FILE_ENV: &str = "D2D_HTTP_CONFIG";
+
+/// HTTP settings applied to every client the tools build.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    pub connect_timeout_secs: Option<u64>,
+    /// Overrides each call site's default request timeout when set.
+    pub timeout_secs: Option<u64>,
+    pub proxy: Option<String>,
+    pub ca_cert: Option<PathBuf>,
+}
+
+impl HttpConfig {
+    /// Builds the config from optional JSON file contents and an env lookup.
+    /// Env values win over file values; unparsable numbers are ignored.
+    pub fn from_sources(
+        file_contents: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> MtgResult<Self> {
+        let mut config: Self = match file_contents {
+            Some(json) => serde_json::from_str(json)?,
+            None => Self::default(),
+        };
+        config.apply_env(&env);
+        Ok(config)
+    }
+
+    /// Overrides settings with the `D2D_HTTP_*` env vars that are set.
+    pub(crate) fn apply_env(&mut self, env: &impl Fn(&str) -> Option<String>) {
+        let secs = |key: &str| {
+            env(key).and_then(|v| match v.trim().parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(_) => {
+                    log::warn!("Ignoring {key}={v}: not a number of seconds");
+                    None
+                }
+            })
+        };
+        let text = |key: &str| env(key).filter(|v| !v.trim().is_empty());
+
+        if let Some(n) = secs("D2D_HTTP_CONNECT_TIMEOUT") {
+            self.connect_timeout_secs = Some(n);
+        }
+        if let Some(n) = secs("D2D_HTTP_TIMEOUT") {
+            self.timeout_secs = Some(n);
+        }
+        if let Some(proxy) = text("D2D_HTTP_PROXY") {
+            self.proxy = Some(proxy);
+        }
+        if let Some(path) = text("D2D_HTTP_CA_CERT") {
+            self.ca_cert = Some(PathBuf::from(path));
+        }
+    }
+
+    /// Loads the config from the process environment and the file named by
+    /// [`CONFIG_FILE_ENV`], or else the shared config file's `[http]` table.
+    /// A broken JSON file is logged and skipped, never fatal.
+    pub fn from_env() -> Self {
+        if std::env::var_os(CONFIG_FILE_ENV).is_none() {
+            return crate::config::config().http.clone();
+        }
+        let file = std::env::var(CONFIG_FILE_ENV).ok().and_then(|path| {
+            std::fs::read_to_string(&path)
+                .map_err(|e| log::warn!("Cannot read HTTP config {path}: {e}"))
+                .ok()
+        });
+        let env = |key: &str| std::env::var(key).ok();
+        Self::from_sources(file.as_deref(), env)
+            .or_else(|e| {
+                log::warn!("Invalid HTTP config file, using environment only: {e}");
+                Self::from_sources(None, env)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The request timeout to use for a call site whose default is `default`.
+    pub fn timeout(&self, default: Duration) -> Duration {
+        self.timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(default)
+    }
+
+    fn root_certificate(&self) -> MtgResult<Option<reqwest::Certificate>> {
+        match &self.ca_cert {
+            Some(path) => Ok(Some(reqwest::Certificate::from_pem(&std::fs::read(path)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Applies the config to an async client builder.
+    pub fn apply(&self, default_timeout: Duration) -> MtgResult<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder().timeout(self.timeout(default_timeout));
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(proxy) = &self.proxy {
+            builder =
+                builder.proxy(reqwest::Proxy::all(proxy)?.no_proxy(reqwest::NoProxy::from_env()));
+        }
+        if let Some(cert) = self.root_certificate()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder)
+    }
+
+    /// Applies the config to a blocking client builder.
+    #[cfg(feature = "blocking")]
+    pub fn apply_blocking(
+        &self,
+        default_timeout: Duration,
+    ) -> MtgResult<reqwest::blocking::ClientBuilder> {
+        let mut builder =
+            reqwest::blocking::Client::builder().timeout(self.timeout(default_timeout));
+        if let Some(secs) = self.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(proxy) = &self.proxy {
+            builder =
+                builder.proxy(reqwest::Proxy::all(proxy)?.no_proxy(reqwest::NoProxy::from_env()));
+        }
+        if let Some(cert) = self.root_certificate()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder)
+    }
+}
+
+/// The process-wide config, loaded from env/file on first use.
+pub fn config() -> &'static HttpConfig {
+    static CONFIG: OnceLock<HttpConfig> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let config = HttpConfig::from_env();
+        if config != HttpConfig::default() {
+            log::info!("Using HTTP config: {config:?}");
+        }
+        config
+    })
+}
+
+/// An async client builder with the global config applied.
+pub fn client_builder(default_timeout: Duration) -> MtgResult<reqwest::ClientBuilder> {
+    config().apply(default_timeout)
+}
+
+/// A blocking client builder with the global config applied.
+#[cfg(feature = "blocking")]
+pub fn blocking_client_builder(
+    default_timeout: Duration,
+) -> MtgResult<reqwest::blocking::ClientBuilder> {
+    config().apply_blocking(default_timeout)
+}
+
+#[cfg(test)]
+#[path = "http_tests.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+//! Tests for http.
+
+use super::*;
+use crate::error::MtgError;
+use std::collections::HashMap;
+
+fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let map: HashMap<String, String> = pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    move |key| map.get(key).cloned()
+}
+
+#[test]
+fn defaults_when_nothing_configured() {
+    let config = HttpConfig::from_sources(None, env_of(&[])).unwrap();
+    assert_eq!(config, HttpConfig::default());
+    assert_eq!(
+        config.timeout(Duration::from_secs(30)),
+        Duration::from_secs(30)
+    );
+}
+
+#[test]
+fn reads_config_file() {
+    let json = r#"{"connect_timeout_secs": 5, "timeout_secs": 90,
+        "proxy": "http://proxy.local:3128", "ca_cert": "/etc/ssl/warehouse.pem"}"#;
+    let config = HttpConfig::from_sources(Some(json), env_of(&[])).unwrap();
+    assert_eq!(config.connect_timeout_secs, Some(5));
+    assert_eq!(
+        config.timeout(Duration::from_secs(30)),
+        Duration::from_secs(90)
+    );
+    assert_eq!(config.proxy.as_deref(), Some("http://proxy.local:3128"));
+    assert_eq!(
+        config.ca_cert,
+        Some(PathBuf::from("/etc/ssl/warehouse.pem"))
+    );
+}
+
+#[test]
+fn partial_config_file_keeps_other_defaults() {
+    let config = HttpConfig::from_sources(Some(r#"{"timeout_secs": 10}"#), env_of(&[])).unwrap();
+    assert_eq!(config.timeout_secs, Some(10));
+    assert_eq!(config.proxy, None);
+    assert_eq!(config.connect_timeout_secs, None);
+}
+
+#[test]
+fn env_overrides_file() {
+    let env = env_of(&[
+        ("D2D_HTTP_TIMEOUT", "120"),
+        ("D2D_HTTP_CONNECT_TIMEOUT", " 3 "),
+        ("D2D_HTTP_PROXY", "socks5://127.0.0.1:1080"),
+        ("D2D_HTTP_CA_CERT", "/tmp/ca.pem"),
+    ]);
+    let json = r#"{"timeout_secs": 10, "proxy": "http://file-proxy:8080"}"#;
+    let config = HttpConfig::from_sources(Some(json), env).unwrap();
+    assert_eq!(config.timeout_secs, Some(120));
+    assert_eq!(config.connect_timeout_secs, Some(3));
+    assert_eq!(config.proxy.as_deref(), Some("socks5://127.0.0.1:1080"));
+    assert_eq!(config.ca_cert, Some(PathBuf::from("/tmp/ca.pem")));
+}
+
+#[test]
+fn invalid_or_empty_env_values_are_ignored() {
+    let env = env_of(&[("D2D_HTTP_TIMEOUT", "soon"), ("D2D_HTTP_PROXY", "  ")]);
+    let json = r#"{"timeout_secs": 10, "proxy": "http://file-proxy:8080"}"#;
+    let config = HttpConfig::from_sources(Some(json), env).unwrap();
+    assert_eq!(config.timeout_secs, Some(10));
+    assert_eq!(config.proxy.as_deref(), Some("http://file-proxy:8080"));
+}
+
+#[test]
+fn malformed_config_file_is_an_error() {
+    assert!(matches!(
+        HttpConfig::from_sources(Some("{not json"), env_of(&[])),
+        Err(MtgError::Parse(_))
+    ));
+}
+
+#[test]
+fn apply_builds_client_with_proxy() {
+    let config = HttpConfig {
+        connect_timeout_secs: Some(5),
+        proxy: Some("http://proxy.local:3128".to_string()),
+        ..HttpConfig::default()
+    };
+    let builder = config.apply(Duration::from_secs(30)).unwrap();
+    assert!(builder.build().is_ok());
+}
+
+#[test]
+fn apply_rejects_unreadable_ca_cert() {
+    let config = HttpConfig {
+        ca_cert: Some(PathBuf::from("/nonexistent/ca.pem")),
+        ..HttpConfig::default()
+    };
+    assert!(matches!(
+        config.apply(Duration::from_secs(30)),
+        Err(MtgError::Io(_))
+    ));
+}
@@ -6,6 +6,9 @@
 //! client. Endpoints are designed so the server only ever runs indexed
 //! `SELECT`s on behalf of clients — any aggregation (price deltas, movers)
 //! happens client-side.
+//!
+//! With the `openapi` feature the wire types derive their OpenAPI schemas,
+//! from which the server publishes its API document.
 
 use crate::error::{MtgError, MtgResult};
 use serde::{Deserialize, Serialize};
@@ -20,11 +23,14 @@ const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
 pub const MAX_BULK_IDS: usize = 10_000;
 /// Maximum snapshot dates per bulk price-snapshot request.
 pub const MAX_SNAPSHOT_DATES: usize = 8;
+/// Maximum wantslist rows per wantslist pricing request.
+pub const MAX_WANTSLIST_CARDS: usize = 500;
 
 // ── Wire types ───────────────────────────────────────────────────────────────
 
 /// Envelope for every JSON response from the inventory_sync API.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ApiResponse<T> {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,16 +77,31 @@ impl<T> ApiResponse<T> {
 
 /// Product metadata (for search results and price-detail responses).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ProductSearchResult {
     pub id_product: u64,
     pub name: String,
     pub category_name: String,
+    /// Whether the product is a Magic single ([`crate::Category::Single`])
+    #[serde(default)]
+    pub is_single: bool,
     pub id_expansion: u64,
     pub expansion_name: Option<String>,
 }
 
+/// Cardmarket expansion metadata: `GET /api/expansions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ExpansionInfo {
+    pub id_expansion: u64,
+    pub name: String,
+    pub code: Option<String>,
+    pub release_date: Option<String>,
+}
+
 /// One day of Cardmarket price-guide data for a product.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PriceHistoryPoint {
     pub price_date: String,
     pub avg: Option<f64>,
@@ -99,6 +120,7 @@ pub struct PriceHistoryPoint {
 
 /// Latest price snapshot for a single product (most recent price_date row).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LatestPrice {
     pub id_product: u64,
     pub price_date: String,
@@ -119,6 +141,7 @@ pub struct LatestPrice {
 /// The price row in effect on a requested date: the most recent row with
 /// `price_date <= requested_date`. Returned by `POST /api/price-snapshots`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PriceSnapshot {
     pub id_product: u64,
     /// The date the client asked about.
@@ -139,8 +162,65 @@ pub struct PriceSnapshot {
     pub avg30_foil: Option<f64>,
 }
 
+/// The price row closest to a requested date:
+/// `GET /api/products/{id}/price?date=YYYY-MM-DD`.
+///
+/// Unlike [`PriceSnapshot`] the row may lie after the requested date when that
+/// is nearer; equally distant rows resolve to the earlier one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PriceOnDate {
+    pub id_product: u64,
+    pub requested_date: String,
+    /// Whether `price.price_date` is the requested date itself.
+    pub exact: bool,
+    #[serde(flatten)]
+    pub price: PriceHistoryPoint,
+}
+
+/// Cardmarket trend vs. TCGplayer market price for one product:
+/// `GET /api/compare` and `GET /api/compare/{id}`.
+///
+/// Both prices are non-foil. The TCGplayer price is converted at the latest
+/// ECB reference rate; `spread_pct` is positive when TCGplayer pays more.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PriceComparison {
+    pub id_product: u64,
+    pub name: String,
+    pub cardmarket_date: String,
+    pub cardmarket_eur: f64,
+    pub tcgplayer_date: String,
+    pub tcgplayer_usd: f64,
+    pub usd_per_eur: f64,
+    pub tcgplayer_eur: f64,
+    pub spread_pct: f64,
+}
+
+/// A product's current name and the renames recorded by the catalog sync:
+/// `GET /api/products/{id}/names`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProductNames {
+    pub id_product: u64,
+    pub name: String,
+    /// Oldest first.
+    pub renames: Vec<ProductRename>,
+}
+
+/// One catalog rename (typo fix, errata) of a Cardmarket product.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ProductRename {
+    pub old_name: String,
+    pub new_name: String,
+    /// Date of the catalog sync that saw the new name (`YYYY-MM-DD`).
+    pub renamed_at: String,
+}
+
 /// Technical indicators computed by the server for a single product's history.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TechnicalIndicators {
     pub ema_7: Vec<Option<f64>>,
     pub ema_30: Vec<Option<f64>>,
@@ -167,6 +247,7 @@ pub struct TechnicalIndicators {
 /// These don't need a long price history — they use avg1/avg7/avg30 already
 /// computed by Cardmarket, making them available from day one.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CardmarketSignals {
     /// avg1 - avg7: positive = recent price spike, negative = recent drop
     pub momentum_1_7: Vec<Option<f64>>,
@@ -178,6 +259,7 @@ pub struct CardmarketSignals {
 
 /// Full price detail for one product: `GET /api/prices/{id}`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PriceData {
     pub product: ProductSearchResult,
     pub history: Vec<PriceHistoryPoint>,
@@ -187,12 +269,14 @@ pub struct PriceData {
 
 /// Request body for `POST /api/latest-prices`.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct BulkPriceRequest {
     pub ids: Vec<u64>,
 }
 
 /// Request body for `POST /api/price-snapshots`.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PriceSnapshotRequest {
     pub ids: Vec<u64>,
     /// ISO dates (`YYYY-MM-DD`); one snapshot row is returned per (id, date)
@@ -200,6 +284,89 @@ pub struct PriceSnapshotRequest {
     pub dates: Vec<String>,
 }
 
+/// One wantslist row to price, matched to products by exact name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistCard {
+    pub quantity: u32,
+    pub name: String,
+}
+
+/// Request body for `POST /api/wantslist/price`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistPriceRequest {
+    pub cards: Vec<WantslistCard>,
+    /// ISO dates (`YYYY-MM-DD`); the wantslist is priced as of each one.
+    pub dates: Vec<String>,
+}
+
+/// The cheapest printing of a card as of one requested date: its trend from
+/// the most recent price row on or before the date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistCardPrice {
+    pub id_product: u64,
+    pub price_date: String,
+    pub trend: f64,
+}
+
+/// One wantslist row with a price per requested date (`None` when no
+/// printing had a trend by then, or the name matched nothing).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistQuoteLine {
+    #[serde(flatten)]
+    pub card: WantslistCard,
+    pub prices: Vec<Option<WantslistCardPrice>>,
+}
+
+/// What the whole wantslist cost on one requested date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistDateTotal {
+    pub date: String,
+    /// Sum of quantity × trend over the priced rows, in EUR
+    pub total: f64,
+    /// Rows that had a price on this date
+    pub priced_cards: usize,
+}
+
+/// Response of `POST /api/wantslist/price`: the wantslist priced on every
+/// requested date, for "what would this deck have cost back then".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct WantslistQuote {
+    pub lines: Vec<WantslistQuoteLine>,
+    /// One per requested date, in request order
+    pub totals: Vec<WantslistDateTotal>,
+}
+
+impl WantslistQuote {
+    /// Builds the quote from lines priced on `dates` (same order as each
+    /// line's `prices`), summing the totals.
+    pub fn new(dates: &[String], lines: Vec<WantslistQuoteLine>) -> Self {
+        let totals = dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| {
+                let priced = lines.iter().filter_map(|line| {
+                    let price = line.prices.get(i)?.as_ref()?;
+                    Some(f64::from(line.card.quantity) * price.trend)
+                });
+                let (total, priced_cards) =
+                    priced.fold((0.0, 0), |(sum, count), cost| (sum + cost, count + 1));
+                WantslistDateTotal {
+                    date: date.clone(),
+                    total,
+                    priced_cards,
+                }
+            })
+            .collect();
+        Self { lines, totals }
+    }
+}
+
 // ── Price field selection ────────────────────────────────────────────────────
 
 /// Which of the standard Cardmarket price-guide columns to read.
@@ -244,6 +411,10 @@ impl PriceField {
 /// (six fields × non-foil/foil).
 pub trait PriceFields {
     /// Reads the requested field, choosing the foil column when `is_foil`.
+    ///
+    /// Finishes are never mixed: a foil-only product has no non-foil price,
+    /// so asking for one gives `None` rather than the foil price or 0.
+    /// Zero values count as missing (see [`crate::cardmarket::priced`]).
     fn price_for(&self, field: PriceField, is_foil: bool) -> Option<f64>;
 }
 
@@ -252,7 +423,7 @@ macro_rules! impl_price_fields {
         impl PriceFields for $t {
             fn price_for(&self, field: PriceField, is_foil: bool) -> Option<f64> {
                 use PriceField::*;
-                if is_foil {
+                crate::cardmarket::priced(if is_foil {
                     match field {
                         Trend => self.trend_foil,
                         Avg => self.avg_foil,
@@ -270,7 +441,7 @@ macro_rules! impl_price_fields {
                         Avg7 => self.avg7,
                         Avg30 => self.avg30,
                     }
-                }
+                })
             }
         }
     )+ };
@@ -318,12 +489,33 @@ impl InventorySyncClient {
         }
     }
 
+    fn price_history_by_name_path(
+        name: &str,
+        expansion: Option<&str>,
+        days: Option<u32>,
+    ) -> String {
+        let mut path = format!("/api/price-history?name={}", urlencoding::encode(name));
+        if let Some(expansion) = expansion {
+            path.push_str(&format!("&expansion={}", urlencoding::encode(expansion)));
+        }
+        if let Some(days) = days {
+            path.push_str(&format!("&days={days}"));
+        }
+        path
+    }
+
+    fn price_on_date_path(id_product: u64, date: &str) -> String {
+        format!(
+            "/api/products/{id_product}/price?date={}",
+            urlencoding::encode(date)
+        )
+    }
+
     // ── Async API ────────────────────────────────────────────────────────────
 
     /// `GET /api/health` — checks the server is reachable and healthy.
     pub async fn health(&self) -> MtgResult<()> {
-        let response = reqwest::Client::builder()
-            .timeout(HEALTH_TIMEOUT)
+        let response = crate::http::client_builder(HEALTH_TIMEOUT)?
             .build()?
             .get(self.url("/api/health"))
             .header("User-Agent", crate::USER_AGENT)
@@ -340,7 +532,7 @@ impl InventorySyncClient {
     /// Requests are chunked to [`MAX_BULK_IDS`] internally; callers may pass
     /// any number of IDs. Products without price history are omitted.
     pub async fn latest_prices(&self, ids: &[u64]) -> MtgResult<Vec<LatestPrice>> {
-        let client = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        let client = crate::http::client_builder(HTTP_TIMEOUT)?.build()?;
         let mut out = Vec::with_capacity(ids.len());
         for chunk in ids.chunks(MAX_BULK_IDS) {
             let response = client
@@ -364,8 +556,7 @@ impl InventorySyncClient {
     ///
     /// `days` limits the history window; `None` returns everything.
     pub async fn price_history(&self, id_product: u64, days: Option<u32>) -> MtgResult<PriceData> {
-        let response = reqwest::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(self.url(&format!(
                 "/api/prices/{id_product}{}",
@@ -381,6 +572,30 @@ impl InventorySyncClient {
         body.into_result()
     }
 
+    /// `GET /api/price-history?name=` — [`Self::price_history`] for a card
+    /// known only by its exact name, optionally narrowed down to one
+    /// `expansion` (code, name or ID). Fails with `HttpStatus(404)` when no
+    /// product has that name and with [`MtgError::Api`] listing the
+    /// candidates when several do.
+    pub async fn price_history_by_name(
+        &self,
+        name: &str,
+        expansion: Option<&str>,
+        days: Option<u32>,
+    ) -> MtgResult<PriceData> {
+        let response = crate::http::client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(self.url(&Self::price_history_by_name_path(name, expansion, days)))
+            .header("User-Agent", crate::USER_AGENT)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<PriceData> = response.json().await?;
+        body.into_result()
+    }
+
     /// `POST /api/price-snapshots` — the price row in effect on each requested
     /// date, per product. Chunked to [`MAX_BULK_IDS`] internally.
     ///
@@ -391,7 +606,7 @@ impl InventorySyncClient {
         ids: &[u64],
         dates: &[String],
     ) -> MtgResult<Vec<PriceSnapshot>> {
-        let client = reqwest::Client::builder().timeout(HTTP_TIMEOUT).build()?;
+        let client = crate::http::client_builder(HTTP_TIMEOUT)?.build()?;
         let mut out = Vec::new();
         for chunk in ids.chunks(MAX_BULK_IDS) {
             let response = client
@@ -412,13 +627,53 @@ impl InventorySyncClient {
         Ok(out)
     }
 
+    /// `GET /api/products/{id}/price?date=` — the price row nearest to `date`
+    /// (`YYYY-MM-DD`). Fails with `HttpStatus(404)` when the product has no
+    /// price history.
+    pub async fn price_on_date(&self, id_product: u64, date: &str) -> MtgResult<PriceOnDate> {
+        let response = crate::http::client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(self.url(&Self::price_on_date_path(id_product, date)))
+            .header("User-Agent", crate::USER_AGENT)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<PriceOnDate> = response.json().await?;
+        body.into_result()
+    }
+
+    /// `POST /api/wantslist/price` — prices the wantslist as of each date
+    /// (at most [`MAX_WANTSLIST_CARDS`] rows and [`MAX_SNAPSHOT_DATES`] dates).
+    pub async fn price_wantslist(
+        &self,
+        cards: &[WantslistCard],
+        dates: &[String],
+    ) -> MtgResult<WantslistQuote> {
+        let response = crate::http::client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .post(self.url("/api/wantslist/price"))
+            .header("User-Agent", crate::USER_AGENT)
+            .json(&WantslistPriceRequest {
+                cards: cards.to_vec(),
+                dates: dates.to_vec(),
+            })
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<WantslistQuote> = response.json().await?;
+        body.into_result()
+    }
+
     // ── Blocking API (GUI background threads) ────────────────────────────────
 
     /// Blocking variant of [`Self::health`].
     #[cfg(feature = "blocking")]
     pub fn health_blocking(&self) -> MtgResult<()> {
-        let response = reqwest::blocking::Client::builder()
-            .timeout(HEALTH_TIMEOUT)
+        let response = crate::http::blocking_client_builder(HEALTH_TIMEOUT)?
             .build()?
             .get(self.url("/api/health"))
             .header("User-Agent", crate::USER_AGENT)
@@ -432,9 +687,7 @@ impl InventorySyncClient {
     /// Blocking variant of [`Self::latest_prices`].
     #[cfg(feature = "blocking")]
     pub fn latest_prices_blocking(&self, ids: &[u64]) -> MtgResult<Vec<LatestPrice>> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
-            .build()?;
+        let client = crate::http::blocking_client_builder(HTTP_TIMEOUT)?.build()?;
         let mut out = Vec::with_capacity(ids.len());
         for chunk in ids.chunks(MAX_BULK_IDS) {
             let response = client
@@ -460,8 +713,7 @@ impl InventorySyncClient {
         id_product: u64,
         days: Option<u32>,
     ) -> MtgResult<PriceData> {
-        let response = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(self.url(&format!(
                 "/api/prices/{id_product}{}",
@@ -476,6 +728,26 @@ impl InventorySyncClient {
         body.into_result()
     }
 
+    /// Blocking variant of [`Self::price_history_by_name`].
+    #[cfg(feature = "blocking")]
+    pub fn price_history_by_name_blocking(
+        &self,
+        name: &str,
+        expansion: Option<&str>,
+        days: Option<u32>,
+    ) -> MtgResult<PriceData> {
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(self.url(&Self::price_history_by_name_path(name, expansion, days)))
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<PriceData> = response.json()?;
+        body.into_result()
+    }
+
     /// Blocking variant of [`Self::price_snapshots`].
     #[cfg(feature = "blocking")]
     pub fn price_snapshots_blocking(
@@ -483,9 +755,7 @@ impl InventorySyncClient {
         ids: &[u64],
         dates: &[String],
     ) -> MtgResult<Vec<PriceSnapshot>> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
-            .build()?;
+        let client = crate::http::blocking_client_builder(HTTP_TIMEOUT)?.build()?;
         let mut out = Vec::new();
         for chunk in ids.chunks(MAX_BULK_IDS) {
             let response = client
@@ -504,6 +774,44 @@ impl InventorySyncClient {
         }
         Ok(out)
     }
+
+    /// Blocking variant of [`Self::price_on_date`].
+    #[cfg(feature = "blocking")]
+    pub fn price_on_date_blocking(&self, id_product: u64, date: &str) -> MtgResult<PriceOnDate> {
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(self.url(&Self::price_on_date_path(id_product, date)))
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<PriceOnDate> = response.json()?;
+        body.into_result()
+    }
+
+    /// Blocking variant of [`Self::price_wantslist`].
+    #[cfg(feature = "blocking")]
+    pub fn price_wantslist_blocking(
+        &self,
+        cards: &[WantslistCard],
+        dates: &[String],
+    ) -> MtgResult<WantslistQuote> {
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .post(self.url("/api/wantslist/price"))
+            .header("User-Agent", crate::USER_AGENT)
+            .json(&WantslistPriceRequest {
+                cards: cards.to_vec(),
+                dates: dates.to_vec(),
+            })
+            .send()?;
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let body: ApiResponse<WantslistQuote> = response.json()?;
+        body.into_result()
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +94,91 @@ fn price_snapshot_request_serializes() {
     assert!(json.contains("2026-06-18"));
 }
 
+#[test]
+fn wantslist_quote_sums_priced_lines_per_date() {
+    let price = |trend| {
+        Some(WantslistCardPrice {
+            id_product: 1,
+            price_date: "2026-01-01".to_string(),
+            trend,
+        })
+    };
+    let line = |quantity, name: &str, prices| WantslistQuoteLine {
+        card: WantslistCard {
+            quantity,
+            name: name.to_string(),
+        },
+        prices,
+    };
+    let dates = vec!["2026-01-01".to_string(), "2026-07-01".to_string()];
+
+    let quote = WantslistQuote::new(
+        &dates,
+        vec![
+            line(4, "Lightning Bolt", vec![price(1.5), price(2.0)]),
+            line(1, "Future Card", vec![None, price(10.0)]),
+        ],
+    );
+
+    assert_eq!(quote.totals[0].total, 6.0);
+    assert_eq!(quote.totals[0].priced_cards, 1);
+    assert_eq!(quote.totals[1].total, 18.0);
+    assert_eq!(quote.totals[1].priced_cards, 2);
+    let json = serde_json::to_value(&quote).unwrap();
+    assert_eq!(json["lines"][0]["name"], "Lightning Bolt");
+    assert_eq!(json["lines"][1]["prices"][0], serde_json::Value::Null);
+}
+
+#[test]
+fn price_history_by_name_path_encodes_parameters() {
+    assert_eq!(
+        InventorySyncClient::price_history_by_name_path("Fire // Ice", None, None),
+        "/api/price-history?name=Fire%20%2F%2F%20Ice"
+    );
+    assert_eq!(
+        InventorySyncClient::price_history_by_name_path("Lightning Bolt", Some("M10"), Some(90)),
+        "/api/price-history?name=Lightning%20Bolt&expansion=M10&days=90"
+    );
+}
+
+#[test]
+fn price_on_date_path_encodes_date() {
+    assert_eq!(
+        InventorySyncClient::price_on_date_path(42, "2026-07-18"),
+        "/api/products/42/price?date=2026-07-18"
+    );
+    assert_eq!(
+        InventorySyncClient::price_on_date_path(42, "2026 07&x"),
+        "/api/products/42/price?date=2026%2007%26x"
+    );
+}
+
+#[test]
+fn price_on_date_flattens_price_row() {
+    let json = r#"{"id_product": 9, "requested_date": "2026-07-18", "exact": false,
+                   "price_date": "2026-07-17", "avg": 1.0, "low": 0.5, "trend": 0.9,
+                   "avg1": null, "avg7": null, "avg30": null, "avg_foil": null,
+                   "low_foil": null, "trend_foil": null, "avg1_foil": null,
+                   "avg7_foil": null, "avg30_foil": null}"#;
+    let price: PriceOnDate = serde_json::from_str(json).unwrap();
+    assert!(!price.exact);
+    assert_eq!(price.price.price_date, "2026-07-17");
+    assert_eq!(price.price.trend, Some(0.9));
+
+    let round_trip = serde_json::to_string(&price).unwrap();
+    assert!(round_trip.contains("\"price_date\":\"2026-07-17\""));
+    assert!(!round_trip.contains("\"price\":"));
+}
+
+#[test]
+fn expansion_info_deserializes_with_missing_optionals() {
+    let json = r#"[{"id_expansion": 1, "name": "Alpha", "code": "LEA", "release_date": "1993-08-05"},
+                   {"id_expansion": 2, "name": "Promos", "code": null, "release_date": null}]"#;
+    let expansions: Vec<ExpansionInfo> = serde_json::from_str(json).unwrap();
+    assert_eq!(expansions[0].code.as_deref(), Some("LEA"));
+    assert!(expansions[1].release_date.is_none());
+}
+
 #[test]
 fn price_data_deserializes() {
     let json = r#"{
@@ -155,3 +240,27 @@ fn price_fields_picks_foil_and_nonfoil_columns() {
     assert_eq!(price.price_for(PriceField::Low, true), None);
     assert_eq!(price.price_for(PriceField::Avg30, false), Some(1.3));
 }
+
+#[test]
+fn price_fields_never_mix_finishes_for_foil_only_products() {
+    let entry = crate::cardmarket::PriceGuideEntry {
+        id_product: 1,
+        id_category: 1,
+        avg: Some(0.0),
+        low: None,
+        trend: Some(0.0),
+        avg1: None,
+        avg7: None,
+        avg30: None,
+        avg_foil: Some(8.0),
+        low_foil: Some(6.0),
+        trend_foil: Some(7.5),
+        avg1_foil: None,
+        avg7_foil: None,
+        avg30_foil: None,
+    };
+    // The non-foil side is missing, not free, and never borrows the foil price
+    assert_eq!(entry.price_for(PriceField::Trend, false), None);
+    assert_eq!(entry.price_for(PriceField::Avg, false), None);
+    assert_eq!(entry.price_for(PriceField::Trend, true), Some(7.5));
+}
@@ -1,18 +1,32 @@
 pub mod cardmarket;
+pub mod compressed;
+pub mod config;
+pub mod csv_header;
+pub mod dates;
+pub mod decimal;
 pub mod error;
 pub mod file_cache;
+pub mod http;
 pub mod inventory_sync;
 pub mod scryfall;
+pub mod sql_profile;
+pub mod text_encoding;
 
-pub use cardmarket::{PriceGuide, PriceGuideEntry, PriceGuideFile};
+pub use cardmarket::{
+    Category, FinishCounts, PriceFinishes, PriceGuide, PriceGuideEntry, PriceGuideFile,
+};
+pub use config::D2dConfig;
+pub use csv_header::{find_column, normalize_header};
+pub use decimal::{parse_decimal, round_cents, DecimalLocale};
 pub use error::{MtgError, MtgResult};
 pub use file_cache::FileCache;
 pub use inventory_sync::InventorySyncClient;
 pub use scryfall::{image_url, CardFace, ImageUris, PurchaseUris, ScryfallCard, ScryfallPrices};
+pub use text_encoding::TextEncoding;
 
 /// Shared User-Agent for all HTTP requests to external APIs.
 pub const USER_AGENT: &str = "D2D-Automations/1.0";
 
-/// Cardmarket price guide URL (MTG = category 1).
+/// Cardmarket price guide URL (MTG singles, [`Category::Single`]).
 pub const PRICE_GUIDE_URL: &str =
     "https://downloads.s3.cardmarket.com/productCatalog/priceGuide/price_guide_1.json";
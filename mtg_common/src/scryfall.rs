@@ -7,6 +7,14 @@ pub const SCRYFALL_API: &str = "https://api.scryfall.com";
 
 const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Bulk data files are several hundred MB.
+#[cfg(feature = "blocking")]
+const BULK_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Bulk data export with one entry per printing (English or the only
+/// language it was printed in).
+pub const DEFAULT_CARDS: &str = "default_cards";
+
 /// Scryfall image URIs (superset of all fields used across projects).
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ImageUris {
@@ -43,6 +51,7 @@ pub struct ScryfallPrices {
 
 /// Purchase links from Scryfall.
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PurchaseUris {
     pub cardmarket: Option<String>,
     pub tcgplayer: Option<String>,
@@ -99,6 +108,10 @@ pub struct ScryfallCard {
     pub oracle_text: Option<String>,
     #[serde(default)]
     pub purchase_uris: Option<PurchaseUris>,
+    /// Name as printed on a non-English card (`name` is always the English
+    /// name); only set when the card was fetched in another language
+    #[serde(default)]
+    pub printed_name: Option<String>,
 }
 
 impl ScryfallCard {
@@ -108,6 +121,94 @@ impl ScryfallCard {
     }
 }
 
+/// Scryfall set object (the fields needed to draw set symbols and to track
+/// release dates).
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScryfallSet {
+    pub code: String,
+    pub name: String,
+    /// Set symbol as a monochrome SVG; promo and special sets often reuse
+    /// their parent set's symbol here.
+    pub icon_svg_uri: String,
+    /// Release date (`YYYY-MM-DD`), also set for announced future sets
+    #[serde(default)]
+    pub released_at: Option<String>,
+    /// e.g. `expansion`, `core`, `masters`, `commander`, `promo`
+    #[serde(default)]
+    pub set_type: Option<String>,
+    /// Only released on MTG Arena / MTGO
+    #[serde(default)]
+    pub digital: bool,
+}
+
+/// Scryfall list response of `/sets` (all sets fit on one page).
+#[derive(Debug, Deserialize)]
+struct SetList {
+    data: Vec<ScryfallSet>,
+}
+
+/// Entry of Scryfall's `/bulk-data` list: a daily JSON export of all cards.
+#[derive(Debug, Deserialize, Clone)]
+pub struct BulkData {
+    /// e.g. `default_cards`, `unique_artwork`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub download_uri: String,
+    pub updated_at: String,
+    /// File size in bytes
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// The printing and image fields of one card in a bulk data file.
+#[derive(Debug, Deserialize, Clone)]
+pub struct BulkCard {
+    pub set: String,
+    pub collector_number: String,
+    #[serde(default)]
+    pub image_uris: Option<ImageUris>,
+    #[serde(default)]
+    pub card_faces: Option<Vec<CardFace>>,
+}
+
+impl BulkCard {
+    /// Get the primary image URL (normal size)
+    pub fn image_url(&self) -> Option<&str> {
+        image_url(self.image_uris.as_ref(), self.card_faces.as_deref())
+    }
+}
+
+struct BulkCardVisitor<F>(F);
+
+impl<'de, F: FnMut(BulkCard)> serde::de::Visitor<'de> for BulkCardVisitor<F> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an array of Scryfall cards")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+        let mut count = 0;
+        while let Some(card) = seq.next_element::<BulkCard>()? {
+            (self.0)(card);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Streams the cards of a bulk data file (one JSON array) to `each`, one at a
+/// time, so the file never has to fit in memory. Returns the number of cards.
+pub fn for_each_bulk_card(
+    reader: impl std::io::Read,
+    each: impl FnMut(BulkCard),
+) -> MtgResult<usize> {
+    let mut de = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+    let count = serde::Deserializer::deserialize_seq(&mut de, BulkCardVisitor(each))?;
+    de.end()?;
+    Ok(count)
+}
+
 /// Scryfall API error response payload.
 #[derive(Debug, Deserialize)]
 pub struct ScryfallError {
@@ -125,6 +226,31 @@ fn card_url(base_url: &str, set_code: &str, collector_number: &str) -> String {
     )
 }
 
+/// URL for fetching a printing in another language, e.g. `lang = "de"`.
+#[cfg(feature = "blocking")]
+fn localized_card_url(
+    base_url: &str,
+    set_code: &str,
+    collector_number: &str,
+    lang: &str,
+) -> String {
+    format!(
+        "{}/{}",
+        card_url(base_url, set_code, collector_number),
+        lang
+    )
+}
+
+/// URL for fetching a set by its code.
+#[cfg(feature = "blocking")]
+fn set_url(base_url: &str, set_code: &str) -> String {
+    format!(
+        "{}/sets/{}",
+        base_url,
+        urlencoding::encode(&set_code.to_lowercase())
+    )
+}
+
 /// Turn a non-success response body into an error: Scryfall's structured
 /// code/details payload when parseable, the bare HTTP status otherwise.
 fn error_from_body(status: reqwest::StatusCode, body: &[u8]) -> MtgError {
@@ -184,8 +310,7 @@ pub async fn fetch_card_by_name_from(base_url: &str, name: &str) -> MtgResult<Sc
 }
 
 async fn fetch_card_at_url(url: &str) -> MtgResult<ScryfallCard> {
-    let response = reqwest::Client::builder()
-        .timeout(HTTP_TIMEOUT)
+    let response = crate::http::client_builder(HTTP_TIMEOUT)?
         .build()?
         .get(url)
         .header("User-Agent", crate::USER_AGENT)
@@ -201,12 +326,37 @@ async fn fetch_card_at_url(url: &str) -> MtgResult<ScryfallCard> {
     }
 }
 
+/// Fetch every set Scryfall knows, including announced future sets.
+pub async fn fetch_sets() -> MtgResult<Vec<ScryfallSet>> {
+    fetch_sets_from(SCRYFALL_API).await
+}
+
+/// Fetches the set list from the given base URL (for testing).
+pub async fn fetch_sets_from(base_url: &str) -> MtgResult<Vec<ScryfallSet>> {
+    let url = format!("{}/sets", base_url);
+    log::debug!("Fetching set list from Scryfall: {}", url);
+
+    let response = crate::http::client_builder(HTTP_TIMEOUT)?
+        .build()?
+        .get(&url)
+        .header("User-Agent", crate::USER_AGENT)
+        .send()
+        .await?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(response.json::<SetList>().await?.data)
+    } else {
+        let body = response.bytes().await?;
+        Err(error_from_body(status, &body))
+    }
+}
+
 /// Fetch card image bytes from a URL.
 pub async fn fetch_image(url: &str) -> MtgResult<Vec<u8>> {
     log::debug!("Fetching image: {}", url);
 
-    let response = reqwest::Client::builder()
-        .timeout(HTTP_TIMEOUT)
+    let response = crate::http::client_builder(HTTP_TIMEOUT)?
         .build()?
         .get(url)
         .header("User-Agent", crate::USER_AGENT)
@@ -239,8 +389,34 @@ pub mod blocking {
         let url = card_url(base_url, set_code, collector_number);
         log::info!("Fetching card from Scryfall: {}", url);
 
-        let response = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(&url)
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json::<ScryfallCard>()?)
+        } else {
+            let body = response.bytes()?;
+            Err(error_from_body(status, &body))
+        }
+    }
+
+    /// Fetches the printing of a card in `lang` (a Scryfall language code
+    /// such as `de`) from the given base URL. Its `printed_name` is the
+    /// localized name; printings that don't exist in the language are a 404.
+    pub fn fetch_localized_card_from(
+        base_url: &str,
+        set_code: &str,
+        collector_number: &str,
+        lang: &str,
+    ) -> MtgResult<ScryfallCard> {
+        let url = localized_card_url(base_url, set_code, collector_number, lang);
+        log::debug!("Fetching localized card from Scryfall: {}", url);
+
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(&url)
             .header("User-Agent", crate::USER_AGENT)
@@ -255,12 +431,72 @@ pub mod blocking {
         }
     }
 
+    /// Fetches a set by code from the given base URL.
+    pub fn fetch_set_from(base_url: &str, set_code: &str) -> MtgResult<ScryfallSet> {
+        let url = set_url(base_url, set_code);
+        log::debug!("Fetching set from Scryfall: {}", url);
+
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(&url)
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json::<ScryfallSet>()?)
+        } else {
+            let body = response.bytes()?;
+            Err(error_from_body(status, &body))
+        }
+    }
+
+    /// Fetches the `/bulk-data` entry of one export (e.g. [`DEFAULT_CARDS`])
+    /// from the given base URL.
+    pub fn fetch_bulk_data_from(base_url: &str, kind: &str) -> MtgResult<BulkData> {
+        let url = format!("{}/bulk-data/{}", base_url, urlencoding::encode(kind));
+        log::debug!("Fetching bulk data info from Scryfall: {}", url);
+
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
+            .build()?
+            .get(&url)
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.json::<BulkData>()?)
+        } else {
+            let body = response.bytes()?;
+            Err(error_from_body(status, &body))
+        }
+    }
+
+    /// Downloads a bulk data file to `dest` without holding it in memory.
+    /// Returns the number of bytes written.
+    pub fn download_bulk_data(url: &str, dest: &std::path::Path) -> MtgResult<u64> {
+        log::info!("Downloading Scryfall bulk data: {}", url);
+
+        let mut response = crate::http::blocking_client_builder(BULK_DOWNLOAD_TIMEOUT)?
+            .build()?
+            .get(url)
+            .header("User-Agent", crate::USER_AGENT)
+            .send()?;
+
+        if !response.status().is_success() {
+            return Err(MtgError::HttpStatus(response.status()));
+        }
+        let mut file = std::io::BufWriter::new(std::fs::File::create(dest)?);
+        let bytes = response.copy_to(&mut file)?;
+        std::io::Write::flush(&mut file)?;
+        Ok(bytes)
+    }
+
     /// Fetch card image bytes from a URL.
     pub fn fetch_image(url: &str) -> MtgResult<Vec<u8>> {
         log::debug!("Fetching image: {}", url);
 
-        let response = reqwest::blocking::Client::builder()
-            .timeout(HTTP_TIMEOUT)
+        let response = crate::http::blocking_client_builder(HTTP_TIMEOUT)?
             .build()?
             .get(url)
             .header("User-Agent", crate::USER_AGENT)
@@ -83,3 +83,101 @@ fn image_uris_deserializes_with_missing_fields() {
     assert!(uris.small.is_none());
     assert!(uris.png.is_none());
 }
+
+#[cfg(feature = "blocking")]
+#[test]
+fn set_url_lowercases_code() {
+    assert_eq!(
+        set_url("https://api.scryfall.com", "MH2"),
+        "https://api.scryfall.com/sets/mh2"
+    );
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn localized_card_url_appends_the_language() {
+    assert_eq!(
+        localized_card_url("https://api.scryfall.com", "MH2", "138", "de"),
+        "https://api.scryfall.com/cards/mh2/138/de"
+    );
+}
+
+#[test]
+fn printed_name_is_read_when_present() {
+    let json = r#"{"id": "x", "name": "Lightning Bolt", "set": "m10", "set_name": "Magic 2010",
+                   "collector_number": "146", "rarity": "common", "lang": "de",
+                   "printed_name": "Blitzschlag"}"#;
+    let card: ScryfallCard = serde_json::from_str(json).unwrap();
+    assert_eq!(card.printed_name.as_deref(), Some("Blitzschlag"));
+}
+
+#[test]
+fn scryfall_set_deserializes_ignoring_extra_fields() {
+    let json = r#"{"object": "set", "code": "pm21", "name": "Core Set 2021 Promos",
+                   "set_type": "promo", "icon_svg_uri": "https://svgs.scryfall.io/sets/m21.svg"}"#;
+    let set: ScryfallSet = serde_json::from_str(json).unwrap();
+    assert_eq!(set.code, "pm21");
+    assert_eq!(set.icon_svg_uri, "https://svgs.scryfall.io/sets/m21.svg");
+    assert_eq!(set.set_type.as_deref(), Some("promo"));
+    assert_eq!(set.released_at, None);
+    assert!(!set.digital);
+}
+
+#[test]
+fn set_list_keeps_release_dates() {
+    let json = r#"{"object": "list", "has_more": false, "data": [
+        {"code": "xyz", "name": "Future Set", "released_at": "2027-02-05",
+         "set_type": "expansion", "digital": false, "icon_svg_uri": "https://svgs.scryfall.io/sets/xyz.svg"},
+        {"code": "yxz", "name": "Alchemy: Future", "released_at": "2027-03-01",
+         "set_type": "alchemy", "digital": true, "icon_svg_uri": "https://svgs.scryfall.io/sets/yxz.svg"}
+    ]}"#;
+    let list: SetList = serde_json::from_str(json).unwrap();
+    assert_eq!(list.data.len(), 2);
+    assert_eq!(list.data[0].released_at.as_deref(), Some("2027-02-05"));
+    assert!(list.data[1].digital);
+}
+
+#[test]
+fn bulk_cards_are_streamed_with_their_image_urls() {
+    let json = r#"[
+        {"object": "card", "set": "lea", "collector_number": "161", "name": "Lightning Bolt",
+         "image_uris": {"normal": "https://cards.scryfall.io/normal/front/bolt.jpg"}},
+        {"set": "isd", "collector_number": "51", "card_faces": [
+            {"name": "Delver of Secrets",
+             "image_uris": {"normal": "https://cards.scryfall.io/normal/front/delver.jpg"}},
+            {"name": "Insectile Aberration"}
+        ]},
+        {"set": "tmp", "collector_number": "1"}
+    ]"#;
+    let mut cards = Vec::new();
+    let count = for_each_bulk_card(json.as_bytes(), |card| cards.push(card)).unwrap();
+
+    assert_eq!(count, 3);
+    assert_eq!(cards[0].set, "lea");
+    assert_eq!(
+        cards[0].image_url(),
+        Some("https://cards.scryfall.io/normal/front/bolt.jpg")
+    );
+    assert_eq!(
+        cards[1].image_url(),
+        Some("https://cards.scryfall.io/normal/front/delver.jpg")
+    );
+    assert_eq!(cards[2].image_url(), None);
+}
+
+#[test]
+fn malformed_bulk_file_is_an_error() {
+    assert!(for_each_bulk_card(&b"{}"[..], |_| {}).is_err());
+    assert!(for_each_bulk_card(&b"[{\"set\": \"lea\"}]"[..], |_| {}).is_err());
+    assert!(for_each_bulk_card(&b"[] trailing"[..], |_| {}).is_err());
+}
+
+#[test]
+fn bulk_data_entry_deserializes() {
+    let json = r#"{"object": "bulk_data", "type": "default_cards",
+        "download_uri": "https://data.scryfall.io/default-cards/default-cards-20260301.json",
+        "updated_at": "2026-03-01T09:00:00.000+00:00", "size": 512000000}"#;
+    let bulk: BulkData = serde_json::from_str(json).unwrap();
+    assert_eq!(bulk.kind, DEFAULT_CARDS);
+    assert_eq!(bulk.size, 512_000_000);
+}
@@ -0,0 +1,597 @@
+//! Slow query logging and a missing-index advisor for the SQLite databases.
+//!
+//! Off by default. With `[sql] slow_query_ms` (or `D2D_SQL_SLOW_QUERY_MS`)
+//! set, every tool installs a SQLite profile hook on its connections; a
+//! statement that runs longer than the threshold is logged at warn level with
+//! its `EXPLAIN QUERY PLAN` and kept in a small in-memory log
+//! ([`slow_queries`]). [`advise_indexes`] reads the logged plans for full
+//! table scans and suggests the index that would turn each into a search.
+//!
+//! This module holds the parts that do not need SQLite, so both database
+//! layers (with their own rusqlite versions) share them: the threshold, the
+//! log, the databases whose plans can be looked up ([`watch_database`]) and
+//! the advisor. The advisor reads the SQL with a small tokenizer rather than
+//! a parser, so its suggestions are a starting point to check, not a verdict.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// `[sql]` — query profiling shared by all tools.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SqlConfig {
+    /// Log statements slower than this many milliseconds; off when unset
+    pub slow_query_ms: Option<u64>,
+}
+
+impl SqlConfig {
+    /// Overrides settings with the `D2D_SQL_*` env vars that are set.
+    pub(crate) fn apply_env(&mut self, env: &impl Fn(&str) -> Option<String>) {
+        if let Some(value) = env("D2D_SQL_SLOW_QUERY_MS") {
+            match value.trim().parse() {
+                Ok(ms) => self.slow_query_ms = Some(ms),
+                Err(_) => log::warn!("Ignoring D2D_SQL_SLOW_QUERY_MS={value}: not a number"),
+            }
+        }
+    }
+
+    /// The configured threshold; `0` turns logging off like leaving it unset.
+    pub fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_ms
+            .filter(|&ms| ms > 0)
+            .map(Duration::from_millis)
+    }
+}
+
+/// Slow-query threshold in nanoseconds; `u64::MAX` = logging off.
+static THRESHOLD_NANOS: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Slow queries kept for the report; the oldest are dropped first.
+const MAX_SLOW_QUERIES: usize = 500;
+
+static SLOW_QUERIES: Mutex<Vec<SlowQuery>> = Mutex::new(Vec::new());
+
+static DATABASES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Turns slow query logging on (with the threshold) or off. Call at startup,
+/// before the databases are opened.
+pub fn set_slow_query_threshold(threshold: Option<Duration>) {
+    let nanos = threshold.map_or(u64::MAX, |t| {
+        t.as_nanos().min(u128::from(u64::MAX - 1)) as u64
+    });
+    THRESHOLD_NANOS.store(nanos, Ordering::Relaxed);
+}
+
+/// The threshold, or `None` when slow query logging is off.
+pub fn slow_query_threshold() -> Option<Duration> {
+    match THRESHOLD_NANOS.load(Ordering::Relaxed) {
+        u64::MAX => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
+
+/// Whether a statement that took `elapsed` is logged.
+pub fn is_slow(elapsed: Duration) -> bool {
+    slow_query_threshold().is_some_and(|threshold| elapsed >= threshold)
+}
+
+/// Registers a database file whose statements are profiled, so plans of
+/// slow statements can be looked up on a separate connection.
+pub fn watch_database(path: &Path) {
+    let mut databases = DATABASES.lock().unwrap_or_else(|e| e.into_inner());
+    if !databases.iter().any(|p| p == path) {
+        databases.push(path.to_path_buf());
+    }
+}
+
+/// The database files registered with [`watch_database`].
+pub fn watched_databases() -> Vec<PathBuf> {
+    DATABASES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// One statement that ran longer than the threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SlowQuery {
+    pub sql: String,
+    pub elapsed_ms: f64,
+    /// `EXPLAIN QUERY PLAN` detail lines; empty when the plan was unavailable
+    pub plan: Vec<String>,
+}
+
+/// Logs a slow statement with its plan and keeps it for the report.
+pub fn record_slow_query(sql: &str, elapsed: Duration, plan: Vec<String>) {
+    let query = SlowQuery {
+        sql: sql.trim().to_string(),
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        plan,
+    };
+    if query.plan.is_empty() {
+        log::warn!(
+            "Slow query ({:.0} ms, plan unavailable): {}",
+            query.elapsed_ms,
+            one_line(&query.sql)
+        );
+    } else {
+        log::warn!(
+            "Slow query ({:.0} ms): {}\n  plan: {}",
+            query.elapsed_ms,
+            one_line(&query.sql),
+            query.plan.join("\n        ")
+        );
+    }
+    let mut log = SLOW_QUERIES.lock().unwrap_or_else(|e| e.into_inner());
+    if log.len() >= MAX_SLOW_QUERIES {
+        log.remove(0);
+    }
+    log.push(query);
+}
+
+/// The slow queries logged so far, oldest first.
+pub fn slow_queries() -> Vec<SlowQuery> {
+    SLOW_QUERIES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Collapses the whitespace of multi-line SQL for log lines and reports.
+fn one_line(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// An index the database already has, first column first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistingIndex {
+    pub table: String,
+    pub columns: Vec<String>,
+}
+
+/// What the advisor suggests for a scanned table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AdviceKind {
+    /// A B-tree index on the filtered columns
+    Index,
+    /// The table is only filtered with `LIKE`, which no B-tree index serves
+    /// when the pattern starts with a wildcard
+    FullTextSearch,
+}
+
+/// A suggestion for the full table scans of one or more slow queries.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct IndexAdvice {
+    pub table: String,
+    pub columns: Vec<String>,
+    pub kind: AdviceKind,
+    /// Slow queries that scanned the table this way
+    pub queries: usize,
+    pub total_ms: f64,
+    pub example_sql: String,
+    /// SQL to apply the suggestion
+    pub statement: String,
+}
+
+impl IndexAdvice {
+    fn statement_for(table: &str, columns: &[String], kind: AdviceKind) -> String {
+        match kind {
+            AdviceKind::Index => format!(
+                "CREATE INDEX IF NOT EXISTS idx_{table}_{} ON {table} ({});",
+                columns.join("_"),
+                columns.join(", ")
+            ),
+            AdviceKind::FullTextSearch => format!(
+                "CREATE VIRTUAL TABLE {table}_fts USING fts5({}, content='{table}');",
+                columns.join(", ")
+            ),
+        }
+    }
+}
+
+/// Suggests indexes for the full table scans in the slow queries' plans,
+/// leaving out tables that already have an index starting with the suggested
+/// column. The costliest suggestions come first.
+pub fn advise_indexes(queries: &[SlowQuery], existing: &[ExistingIndex]) -> Vec<IndexAdvice> {
+    let mut advice: Vec<IndexAdvice> = Vec::new();
+    let mut by_key: HashMap<(String, Vec<String>, AdviceKind), usize> = HashMap::new();
+
+    for query in queries {
+        let tokens = tokenize(&query.sql);
+        let tables = table_aliases(&tokens);
+        let predicates = predicates(&tokens);
+        let mut seen = Vec::new();
+
+        for line in &query.plan {
+            let Some(scanned) = scanned_table(line) else {
+                continue;
+            };
+            let table = resolve_table(&tables, scanned);
+            if seen.contains(&table) {
+                continue;
+            }
+            seen.push(table.clone());
+
+            let Some((columns, kind)) = suggestion_for(&table, &tables, &predicates) else {
+                continue;
+            };
+            let indexed = existing.iter().any(|index| {
+                index.table.eq_ignore_ascii_case(&table)
+                    && index
+                        .columns
+                        .first()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(&columns[0]))
+            });
+            if kind == AdviceKind::Index && indexed {
+                continue;
+            }
+
+            let key = (table.clone(), columns.clone(), kind);
+            match by_key.get(&key) {
+                Some(&i) => {
+                    advice[i].queries += 1;
+                    advice[i].total_ms += query.elapsed_ms;
+                }
+                None => {
+                    by_key.insert(key, advice.len());
+                    advice.push(IndexAdvice {
+                        statement: IndexAdvice::statement_for(&table, &columns, kind),
+                        table,
+                        columns,
+                        kind,
+                        queries: 1,
+                        total_ms: query.elapsed_ms,
+                        example_sql: one_line(&query.sql),
+                    });
+                }
+            }
+        }
+    }
+
+    advice.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+    advice
+}
+
+/// Plain-text report of the slow queries and the advisor's suggestions.
+pub fn format_index_report(queries: &[SlowQuery], advice: &[IndexAdvice]) -> String {
+    let mut out = match slow_query_threshold() {
+        Some(threshold) => format!(
+            "Slow queries (over {} ms): {}\n",
+            threshold.as_millis(),
+            queries.len()
+        ),
+        None => "Slow query logging is off (set [sql] slow_query_ms)\n".to_string(),
+    };
+    if advice.is_empty() {
+        out.push_str("\nNo missing indexes found.\n");
+        return out;
+    }
+    out.push_str("\nSuggestions:\n");
+    for a in advice {
+        if a.kind == AdviceKind::FullTextSearch {
+            out.push_str(&format!(
+                "\n-- LIKE on {}({}) scans the whole table; a full-text index serves it\n",
+                a.table,
+                a.columns.join(", ")
+            ));
+        } else {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{}\n--   {} slow {}, {:.0} ms in total, e.g. {}\n",
+            a.statement,
+            a.queries,
+            if a.queries == 1 { "query" } else { "queries" },
+            a.total_ms,
+            a.example_sql
+        ));
+    }
+    out
+}
+
+// ── SQL reading ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifier or keyword, with its qualifier (`p.name` → `p`, `name`)
+    Name {
+        qualifier: Option<String>,
+        name: String,
+    },
+    /// Comparison operator or `,`, `(`, `)`
+    Symbol(String),
+    /// String, number or bound parameter
+    Value,
+}
+
+impl Token {
+    fn keyword(&self) -> Option<String> {
+        match self {
+            Token::Name {
+                qualifier: None,
+                name,
+            } => Some(name.to_ascii_uppercase()),
+            _ => None,
+        }
+    }
+
+    fn is(&self, keyword: &str) -> bool {
+        self.keyword().is_some_and(|k| k == keyword)
+    }
+}
+
+/// Words that end a table reference in `FROM` / `JOIN` clauses.
+const CLAUSE_WORDS: &[&str] = &[
+    "WHERE",
+    "JOIN",
+    "INNER",
+    "LEFT",
+    "RIGHT",
+    "FULL",
+    "OUTER",
+    "CROSS",
+    "NATURAL",
+    "ON",
+    "USING",
+    "GROUP",
+    "ORDER",
+    "LIMIT",
+    "HAVING",
+    "WINDOW",
+    "UNION",
+    "EXCEPT",
+    "INTERSECT",
+    "INDEXED",
+    "NOT",
+    "SET",
+    "VALUES",
+    "RETURNING",
+    "AS",
+];
+
+/// SQL keywords that can stand next to an operator without being a column.
+const VALUE_WORDS: &[&str] = &[
+    "NULL", "NOT", "AND", "OR", "SELECT", "CASE", "WHEN", "THEN", "ELSE", "END", "TRUE", "FALSE",
+];
+
+fn tokenize(sql: &str) -> Vec<Token> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '\'' {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' && chars.get(i + 1) == Some(&'\'') {
+                    i += 2;
+                } else if chars[i] == '\'' {
+                    break;
+                } else {
+                    i += 1;
+                }
+            }
+            i += 1;
+            tokens.push(Token::Value);
+        } else if c.is_ascii_digit() || matches!(c, '?' | ':' | '@' | '$') {
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.')) {
+                i += 1;
+            }
+            tokens.push(Token::Value);
+        } else if c.is_alphabetic() || matches!(c, '_' | '"' | '`' | '[') {
+            let (first, next) = read_name(&chars, i);
+            i = next;
+            if chars.get(i) == Some(&'.') {
+                let (name, next) = read_name(&chars, i + 1);
+                i = next;
+                tokens.push(Token::Name {
+                    qualifier: Some(first),
+                    name,
+                });
+            } else {
+                tokens.push(Token::Name {
+                    qualifier: None,
+                    name: first,
+                });
+            }
+        } else {
+            let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let symbol = match two.as_str() {
+                "==" | "<=" | ">=" | "<>" | "!=" => two,
+                _ => c.to_string(),
+            };
+            i += symbol.chars().count();
+            tokens.push(Token::Symbol(symbol));
+        }
+    }
+    tokens
+}
+
+/// Reads a bare or quoted identifier starting at `start`.
+fn read_name(chars: &[char], start: usize) -> (String, usize) {
+    let close = match chars.get(start) {
+        Some('"') => Some('"'),
+        Some('`') => Some('`'),
+        Some('[') => Some(']'),
+        _ => None,
+    };
+    let mut i = start;
+    if let Some(close) = close {
+        i += 1;
+        let begin = i;
+        while i < chars.len() && chars[i] != close {
+            i += 1;
+        }
+        let name = chars[begin..i].iter().collect();
+        return (name, i + 1);
+    }
+    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+        i += 1;
+    }
+    (chars[start..i].iter().collect(), i)
+}
+
+/// Tables of the statement with their aliases: `(alias or table, table)`.
+fn table_aliases(tokens: &[Token]) -> Vec<(String, String)> {
+    let mut tables = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let starts_list = tokens[i].is("FROM") || tokens[i].is("JOIN") || tokens[i].is("UPDATE");
+        i += 1;
+        if !starts_list {
+            continue;
+        }
+        // `FROM a x, b y` lists several tables
+        while let Some(Token::Name { name: table, .. }) = tokens.get(i) {
+            let table = table.clone();
+            i += 1;
+            if tokens.get(i).is_some_and(|t| t.is("AS")) {
+                i += 1;
+            }
+            let alias = match tokens.get(i).and_then(Token::keyword) {
+                Some(word) if !CLAUSE_WORDS.contains(&word.as_str()) => {
+                    i += 1;
+                    match &tokens[i - 1] {
+                        Token::Name { name, .. } => name.clone(),
+                        _ => table.clone(),
+                    }
+                }
+                _ => table.clone(),
+            };
+            tables.push((alias, table));
+            if tokens.get(i) == Some(&Token::Symbol(",".to_string())) {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    tables
+}
+
+fn resolve_table(tables: &[(String, String)], name: &str) -> String {
+    tables
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map(|(_, table)| table.clone())
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PredicateKind {
+    Equality,
+    Range,
+    Like,
+}
+
+/// A column compared in the statement: `(qualifier, column, kind)`.
+type Predicate = (Option<String>, String, PredicateKind);
+
+fn predicates(tokens: &[Token]) -> Vec<Predicate> {
+    let column =
+        |token: Option<&Token>, after: Option<&Token>| -> Option<(Option<String>, String)> {
+            let Token::Name { qualifier, name } = token? else {
+                return None;
+            };
+            if qualifier.is_none() && VALUE_WORDS.contains(&name.to_ascii_uppercase().as_str()) {
+                return None;
+            }
+            // `LOWER(name)` is a function call, which a plain index cannot serve
+            if after == Some(&Token::Symbol("(".to_string())) {
+                return None;
+            }
+            Some((qualifier.clone(), name.clone()))
+        };
+
+    let mut found = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let kind = match token {
+            Token::Symbol(s) if s == "=" || s == "==" => PredicateKind::Equality,
+            Token::Symbol(s) if matches!(s.as_str(), "<" | "<=" | ">" | ">=") => {
+                PredicateKind::Range
+            }
+            t if t.is("IN") || t.is("IS") => PredicateKind::Equality,
+            t if t.is("BETWEEN") => PredicateKind::Range,
+            t if t.is("LIKE") || t.is("GLOB") => PredicateKind::Like,
+            _ => continue,
+        };
+        let before = i.checked_sub(1).and_then(|j| tokens.get(j));
+        if let Some((qualifier, name)) = column(before, Some(token)) {
+            found.push((qualifier, name, kind));
+        }
+        // `x = p.col` — the other side may be the column (not for LIKE patterns)
+        if kind != PredicateKind::Like {
+            if let Some((qualifier, name)) = column(tokens.get(i + 1), tokens.get(i + 2)) {
+                found.push((qualifier, name, kind));
+            }
+        }
+    }
+    found
+}
+
+/// Columns to index for a scan of `table`: equality columns first, then one
+/// range column; or the `LIKE` columns when nothing else filters the table.
+fn suggestion_for(
+    table: &str,
+    tables: &[(String, String)],
+    predicates: &[Predicate],
+) -> Option<(Vec<String>, AdviceKind)> {
+    let single_table = tables.iter().all(|(_, t)| t.eq_ignore_ascii_case(table));
+    let on_table = |qualifier: &Option<String>| match qualifier {
+        Some(q) => resolve_table(tables, q).eq_ignore_ascii_case(table),
+        None => single_table,
+    };
+    let columns = |kind: PredicateKind| {
+        let mut columns: Vec<String> = Vec::new();
+        for (qualifier, name, k) in predicates {
+            if *k == kind && on_table(qualifier) && !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        columns
+    };
+
+    let mut index = columns(PredicateKind::Equality);
+    if let Some(range) = columns(PredicateKind::Range)
+        .into_iter()
+        .find(|c| !index.contains(c))
+    {
+        index.push(range);
+    }
+    if !index.is_empty() {
+        return Some((index, AdviceKind::Index));
+    }
+    let like = columns(PredicateKind::Like);
+    (!like.is_empty()).then_some((like, AdviceKind::FullTextSearch))
+}
+
+/// The table (or alias) a plan line scans in full, e.g. `SCAN p`. Scans of
+/// an index, subqueries and constant rows are not full table scans.
+fn scanned_table(plan_line: &str) -> Option<&str> {
+    let rest = plan_line.trim().strip_prefix("SCAN ")?;
+    if rest.contains(" USING ") {
+        return None;
+    }
+    let name = rest.split_whitespace().next()?;
+    if name == "CONSTANT" || name == "SUBQUERY" || name.starts_with('(') {
+        return None;
+    }
+    Some(name)
+}
+
+#[cfg(test)]
+#[path = "sql_profile_tests.rs"]
+mod tests;
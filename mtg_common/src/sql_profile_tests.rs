@@ -0,0 +1,134 @@
+//! Tests for sql_profile.
+
+use super::*;
+
+fn slow(sql: &str, elapsed_ms: f64, plan: &[&str]) -> SlowQuery {
+    SlowQuery {
+        sql: sql.to_string(),
+        elapsed_ms,
+        plan: plan.iter().map(|l| l.to_string()).collect(),
+    }
+}
+
+#[test]
+fn threshold_turns_logging_on_and_off() {
+    assert!(!is_slow(Duration::from_secs(60)));
+    set_slow_query_threshold(Some(Duration::from_millis(100)));
+    assert!(is_slow(Duration::from_millis(100)));
+    assert!(!is_slow(Duration::from_millis(99)));
+    set_slow_query_threshold(None);
+    assert_eq!(slow_query_threshold(), None);
+
+    let zero = SqlConfig {
+        slow_query_ms: Some(0),
+    };
+    assert_eq!(zero.slow_query_threshold(), None);
+}
+
+#[test]
+fn recorded_queries_are_kept_for_the_report() {
+    record_slow_query(
+        "SELECT *\n  FROM recorded_test",
+        Duration::from_millis(1500),
+        vec!["SCAN recorded_test".to_string()],
+    );
+    let logged = slow_queries();
+    let query = logged
+        .iter()
+        .find(|q| q.sql.contains("recorded_test"))
+        .unwrap();
+    assert_eq!(query.elapsed_ms, 1500.0);
+    assert_eq!(query.plan, vec!["SCAN recorded_test"]);
+}
+
+#[test]
+fn scan_with_equality_and_range_filters_suggests_an_index() {
+    let queries = [slow(
+        "SELECT id_product, trend FROM price_history
+         WHERE price_date >= ?2 AND id_category = ?1",
+        800.0,
+        &["SCAN price_history"],
+    )];
+
+    let advice = advise_indexes(&queries, &[]);
+
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].kind, AdviceKind::Index);
+    assert_eq!(advice[0].columns, vec!["id_category", "price_date"]);
+    assert_eq!(
+        advice[0].statement,
+        "CREATE INDEX IF NOT EXISTS idx_price_history_id_category_price_date \
+         ON price_history (id_category, price_date);"
+    );
+}
+
+#[test]
+fn aliases_and_joins_resolve_to_the_scanned_table() {
+    let queries = [slow(
+        "SELECT p.name, ph.trend
+         FROM price_history ph JOIN products AS p ON p.id_product = ph.id_product
+         WHERE ph.price_date = ?1 AND LOWER(p.name) = ?2",
+        300.0,
+        &["SCAN ph", "SEARCH p USING INTEGER PRIMARY KEY (rowid=?)"],
+    )];
+
+    let advice = advise_indexes(&queries, &[]);
+
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].table, "price_history");
+    assert_eq!(advice[0].columns, vec!["id_product", "price_date"]);
+}
+
+#[test]
+fn like_only_filters_suggest_full_text_search() {
+    let queries = [
+        slow(
+            "SELECT p.id_product FROM products p
+             WHERE p.name LIKE ?1 COLLATE NOCASE ORDER BY p.name",
+            2400.0,
+            &["SCAN p", "USE TEMP B-TREE FOR ORDER BY"],
+        ),
+        slow(
+            "SELECT p.id_product FROM products p WHERE p.name LIKE '%bolt%'",
+            600.0,
+            &["SCAN p"],
+        ),
+    ];
+
+    let advice = advise_indexes(&queries, &[]);
+
+    assert_eq!(advice.len(), 1);
+    assert_eq!(advice[0].kind, AdviceKind::FullTextSearch);
+    assert_eq!(advice[0].columns, vec!["name"]);
+    assert_eq!(advice[0].queries, 2);
+    assert_eq!(advice[0].total_ms, 3000.0);
+    assert!(format_index_report(&queries, &advice).contains("fts5(name"));
+}
+
+#[test]
+fn existing_indexes_and_searches_are_not_suggested() {
+    let existing = [ExistingIndex {
+        table: "products".to_string(),
+        columns: vec!["id_expansion".to_string()],
+    }];
+    let queries = [
+        // The planner skipped the index (e.g. low selectivity): nothing to add
+        slow(
+            "SELECT * FROM products WHERE id_expansion = ?1",
+            500.0,
+            &["SCAN products"],
+        ),
+        slow(
+            "SELECT * FROM products WHERE id_metacard = ?1",
+            500.0,
+            &["SEARCH products USING INDEX idx_products_metacard (id_metacard=?)"],
+        ),
+        slow(
+            "SELECT COUNT(*) FROM products",
+            500.0,
+            &["SCAN products USING COVERING INDEX idx_products_category"],
+        ),
+    ];
+
+    assert!(advise_indexes(&queries, &existing).is_empty());
+}
@@ -0,0 +1,86 @@
+//! Character encoding detection for text inputs.
+//!
+//! Cardmarket exports are UTF-8, but once a CSV has been opened and saved in
+//! Excel it may come back as UTF-8 with a byte order mark, UTF-16 ("Unicode
+//! Text", always with a BOM) or Windows-1252 ("CSV" on a German or English
+//! Windows). [`decode`] recognises all of these and converts them to a plain
+//! `String`, so readers never see a BOM or mangled umlauts. CRLF line endings
+//! are left as they are; the CSV parsers accept both.
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+
+/// Encoding a text input was read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// UTF-8 with a leading byte order mark, as Excel writes "CSV UTF-8"
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Anything that is not valid UTF-8 and has no BOM
+    Windows1252,
+}
+
+impl TextEncoding {
+    /// Detects the encoding from a file's content.
+    ///
+    /// A BOM decides; without one, UTF-16 is recognised by the NUL byte of
+    /// an ASCII first character, valid UTF-8 is UTF-8 and everything else is
+    /// taken as Windows-1252, which maps every byte to a character.
+    pub fn detect(bytes: &[u8]) -> Self {
+        match Encoding::for_bom(bytes) {
+            Some((encoding, _)) if encoding == UTF_8 => return TextEncoding::Utf8Bom,
+            Some((encoding, _)) if encoding == UTF_16LE => return TextEncoding::Utf16Le,
+            Some((encoding, _)) if encoding == UTF_16BE => return TextEncoding::Utf16Be,
+            _ => {}
+        }
+        match bytes {
+            [first, 0, ..] if *first != 0 => TextEncoding::Utf16Le,
+            [0, second, ..] if *second != 0 => TextEncoding::Utf16Be,
+            _ if std::str::from_utf8(bytes).is_ok() => TextEncoding::Utf8,
+            _ => TextEncoding::Windows1252,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 with BOM",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Windows1252 => "Windows-1252",
+        }
+    }
+
+    fn encoding(self) -> &'static Encoding {
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => UTF_8,
+            TextEncoding::Utf16Le => UTF_16LE,
+            TextEncoding::Utf16Be => UTF_16BE,
+            TextEncoding::Windows1252 => WINDOWS_1252,
+        }
+    }
+}
+
+impl std::fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Decodes a text file's content, dropping any BOM. Returns the text and the
+/// detected encoding; invalid sequences become U+FFFD.
+pub fn decode(bytes: Vec<u8>) -> (String, TextEncoding) {
+    let encoding = TextEncoding::detect(&bytes);
+    if encoding == TextEncoding::Utf8 {
+        // Already validated by `detect`; avoid copying large files
+        let text = String::from_utf8(bytes).unwrap_or_default();
+        return (text, encoding);
+    }
+    let (text, _, _) = encoding.encoding().decode(&bytes);
+    (text.into_owned(), encoding)
+}
+
+#[cfg(test)]
+#[path = "text_encoding_tests.rs"]
+mod tests;
@@ -0,0 +1,57 @@
+//! Tests for text_encoding.
+
+use super::*;
+
+const TEXT: &str = "name;city\r\nJürgen;Köln €\r\n";
+
+fn utf16(text: &str, little_endian: bool) -> Vec<u8> {
+    let mut bytes = if little_endian {
+        vec![0xFF, 0xFE]
+    } else {
+        vec![0xFE, 0xFF]
+    };
+    for unit in text.encode_utf16() {
+        let pair = if little_endian {
+            unit.to_le_bytes()
+        } else {
+            unit.to_be_bytes()
+        };
+        bytes.extend_from_slice(&pair);
+    }
+    bytes
+}
+
+#[test]
+fn decodes_every_excel_encoding() {
+    let mut utf8_bom = b"\xEF\xBB\xBF".to_vec();
+    utf8_bom.extend_from_slice(TEXT.as_bytes());
+    let (windows_1252, _, _) = WINDOWS_1252.encode(TEXT);
+
+    let cases = [
+        (TEXT.as_bytes().to_vec(), TextEncoding::Utf8),
+        (utf8_bom, TextEncoding::Utf8Bom),
+        (utf16(TEXT, true), TextEncoding::Utf16Le),
+        (utf16(TEXT, false), TextEncoding::Utf16Be),
+        (windows_1252.into_owned(), TextEncoding::Windows1252),
+    ];
+
+    for (bytes, expected) in cases {
+        assert_eq!(decode(bytes), (TEXT.to_string(), expected), "{expected}");
+    }
+}
+
+#[test]
+fn recognises_utf16_without_bom() {
+    let bytes = utf16("id", true);
+
+    assert_eq!(TextEncoding::detect(&bytes[2..]), TextEncoding::Utf16Le);
+    assert_eq!(
+        TextEncoding::detect(&utf16("id", false)[2..]),
+        TextEncoding::Utf16Be
+    );
+}
+
+#[test]
+fn empty_input_is_utf8() {
+    assert_eq!(decode(Vec::new()), (String::new(), TextEncoding::Utf8));
+}